@@ -109,7 +109,8 @@ async function refreshTokens(refreshToken) {
             }
             resolve(response);
           } else {
-            console.error('Token refresh failed:', res.statusCode);
+            // The OAuth error code (e.g. invalid_grant) tells the app a retry won't help
+            console.error('Token refresh failed:', res.statusCode, response.error || '');
             if (process.env.DEBUG) {
               console.error('[DEBUG] Response:', JSON.stringify(response, null, 2));
             }
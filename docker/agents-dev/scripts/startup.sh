@@ -140,18 +140,35 @@ if [ "${AGENTS_BOX_MODE}" = "boss" ] && [ -n "${AGENTS_BOX_PROMPT}" ]; then
     # Create log directory
     mkdir -p /workspace/.agents-box/logs
 
+    # Boss mode prompt text to append
+    BOSS_MODE_PROMPT="Ultrathink and understand our project rules, particularly around testing. You must go test first, and you must work in a way that allows for small known-good increments. You must commit when the code is in a working state, and commit early and often. When committing: - Use conventional commit format (feat:, fix:, refactor:, test:, docs:) - Commit after each logical increment (test passes, feature complete, refactor done) - Generate descriptive commit messages that explain the 'what' and 'why' - Never leave code in a broken state between commits"
+
+    # Append boss mode prompt to user prompt
+    ENHANCED_PROMPT="${AGENTS_BOX_PROMPT} ${BOSS_MODE_PROMPT}"
+
+    # Sessions created for the Gemini agent run its CLI, which has its own authentication
+    if [ "${AGENTS_BOX_AGENT}" = "gemini" ]; then
+        if [ -z "${GEMINI_API_KEY}" ] && [ -z "${GOOGLE_API_KEY}" ]; then
+            error "❌ Gemini sessions need GEMINI_API_KEY or GOOGLE_API_KEY in the environment"
+            exit 1
+        fi
+        GEMINI_ARGS=""
+        if [[ "$CLAUDE_CONTINUE_FLAG" == *"--dangerously-skip-permissions"* ]]; then
+            GEMINI_ARGS="--yolo"
+        fi
+
+        success "Container environment ready!"
+        log "🤖 Executing boss mode prompt with the Gemini CLI..."
+        log "Prompt: ${AGENTS_BOX_PROMPT}"
+        exec gemini --prompt "${ENHANCED_PROMPT}" --output-format stream-json $GEMINI_ARGS
+    fi
+
     success "Container environment ready!"
     if [ "${AUTH_OK}" = "true" ]; then
         success "✅ Authentication detected - Claude will work immediately"
         log "🤖 Executing boss mode prompt..."
         log "Prompt: ${AGENTS_BOX_PROMPT}"
 
-        # Boss mode prompt text to append
-        BOSS_MODE_PROMPT="Ultrathink and understand our project rules, particularly around testing. You must go test first, and you must work in a way that allows for small known-good increments. You must commit when the code is in a working state, and commit early and often. When committing: - Use conventional commit format (feat:, fix:, refactor:, test:, docs:) - Commit after each logical increment (test passes, feature complete, refactor done) - Generate descriptive commit messages that explain the 'what' and 'why' - Never leave code in a broken state between commits"
-
-        # Append boss mode prompt to user prompt
-        ENHANCED_PROMPT="${AGENTS_BOX_PROMPT} ${BOSS_MODE_PROMPT}"
-
         # Execute Claude with the enhanced prompt and stream-json output
         log "Running: claude --print --output-format stream-json --verbose \"${ENHANCED_PROMPT}\""
         exec claude --print --output-format stream-json --verbose "${ENHANCED_PROMPT}" $CLI_ARGS
@@ -0,0 +1,359 @@
+// ABOUTME: Gemini JSON stream parser - parses Gemini CLI's --output-format stream-json output
+// Converts Gemini-specific JSON events into unified AgentEvent types for display
+
+use super::types::{AgentEvent, AgentOutputParser, ParserState, ToolCallInfo};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+/// Parser for Gemini CLI's stream-json output format
+pub struct GeminiJsonParser {
+    state: ParserState,
+    messages_seen: usize,
+}
+
+impl GeminiJsonParser {
+    pub fn new() -> Self {
+        Self {
+            state: ParserState::default(),
+            messages_seen: 0,
+        }
+    }
+
+    fn parse_json_event(&mut self, json_str: &str) -> Result<Vec<AgentEvent>, String> {
+        let value: Value =
+            serde_json::from_str(json_str).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+        let mut events = Vec::new();
+
+        let event_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("unknown");
+
+        match event_type {
+            "init" => {
+                events.push(AgentEvent::SessionInfo {
+                    model: Self::str_field(&value, "model").unwrap_or("unknown").to_string(),
+                    tools: Vec::new(),
+                    session_id: Self::str_field(&value, "session_id").unwrap_or("").to_string(),
+                    mcp_servers: None,
+                });
+            }
+
+            "message" => {
+                events.extend(self.parse_message(&value));
+            }
+
+            "tool_use" => {
+                events.extend(self.finish_message());
+                events.push(self.parse_tool_use(&value));
+            }
+
+            "tool_result" => {
+                events.push(self.parse_tool_result(&value));
+            }
+
+            "error" => {
+                events.push(AgentEvent::Error {
+                    message: Self::str_field(&value, "message").unwrap_or("").to_string(),
+                    code: Self::str_field(&value, "severity").map(String::from),
+                });
+            }
+
+            "result" => {
+                events.extend(self.finish_message());
+                events.extend(Self::parse_result(&value));
+            }
+
+            _ => {
+                debug!("Unknown Gemini event type: {} - {}", event_type, json_str);
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+        value.get(key).and_then(|v| v.as_str())
+    }
+
+    /// Assistant text arrives as `delta` chunks; anything else ends the message being built
+    fn parse_message(&mut self, value: &Value) -> Vec<AgentEvent> {
+        let role = Self::str_field(value, "role").unwrap_or("assistant");
+        let content = Self::str_field(value, "content").unwrap_or("");
+        let is_delta = value.get("delta").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        if role != "assistant" {
+            // The user's prompt echoed back: only marks a message boundary
+            return self.finish_message();
+        }
+
+        if !is_delta {
+            let mut events = self.finish_message();
+            events.push(AgentEvent::Message {
+                content: content.to_string(),
+                id: Some(self.next_message_id()),
+            });
+            return events;
+        }
+
+        if self.state.current_message.is_none() {
+            self.state.current_message = Some(String::new());
+            self.state.current_message_id = Some(self.next_message_id());
+        }
+        if let Some(ref mut message) = self.state.current_message {
+            message.push_str(content);
+        }
+        vec![AgentEvent::StreamingText {
+            delta: content.to_string(),
+            message_id: self.state.current_message_id.clone(),
+        }]
+    }
+
+    fn next_message_id(&mut self) -> String {
+        self.messages_seen += 1;
+        format!("gemini-msg-{}", self.messages_seen)
+    }
+
+    /// The complete text of the streamed message, once something else starts
+    fn finish_message(&mut self) -> Vec<AgentEvent> {
+        match self.state.current_message.take() {
+            Some(content) => vec![AgentEvent::Message {
+                content,
+                id: self.state.current_message_id.take(),
+            }],
+            None => Vec::new(),
+        }
+    }
+
+    fn parse_tool_use(&mut self, value: &Value) -> AgentEvent {
+        let id = Self::str_field(value, "tool_id").unwrap_or("").to_string();
+        let name = Self::str_field(value, "tool_name").unwrap_or("unknown").to_string();
+        let input = value.get("parameters").cloned().unwrap_or(Value::Null);
+        let description = input.get("description").and_then(|v| v.as_str()).map(String::from);
+
+        self.state.active_tool_calls.insert(
+            id.clone(),
+            ToolCallInfo {
+                id: id.clone(),
+                name: name.clone(),
+                started_at: chrono::Utc::now(),
+            },
+        );
+
+        AgentEvent::ToolCall {
+            id,
+            name,
+            input,
+            description,
+        }
+    }
+
+    fn parse_tool_result(&mut self, value: &Value) -> AgentEvent {
+        let tool_use_id = Self::str_field(value, "tool_id").unwrap_or("").to_string();
+        let is_error = Self::str_field(value, "status") == Some("error");
+        let content = if is_error {
+            value
+                .get("error")
+                .and_then(|e| e.get("message"))
+                .and_then(|v| v.as_str())
+                .or_else(|| Self::str_field(value, "output"))
+                .unwrap_or("")
+        } else {
+            Self::str_field(value, "output").unwrap_or("")
+        };
+
+        self.state.active_tool_calls.remove(&tool_use_id);
+
+        AgentEvent::ToolResult {
+            tool_use_id,
+            content: content.to_string(),
+            is_error,
+        }
+    }
+
+    fn parse_result(value: &Value) -> Vec<AgentEvent> {
+        let mut events = Vec::new();
+
+        if Self::str_field(value, "status") == Some("error") {
+            let message = value
+                .get("error")
+                .and_then(|e| e.get("message"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("Gemini run failed");
+            events.push(AgentEvent::Error {
+                message: message.to_string(),
+                code: None,
+            });
+        }
+
+        if let Some(stats) = value.get("stats") {
+            let tokens = |key: &str| stats.get(key).and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+            events.push(AgentEvent::Usage {
+                input_tokens: tokens("input_tokens"),
+                output_tokens: tokens("output_tokens"),
+                cache_tokens: stats.get("cached_tokens").and_then(|v| v.as_u64()).map(|v| v as u32),
+                total_cost: None,
+            });
+        }
+
+        events
+    }
+}
+
+impl AgentOutputParser for GeminiJsonParser {
+    fn parse_line(&mut self, line: &str) -> Result<Vec<AgentEvent>, String> {
+        // Handle incomplete lines by buffering
+        let complete_line = if !self.state.line_buffer.is_empty() {
+            let buffered = format!("{}{}", self.state.line_buffer, line);
+            self.state.line_buffer.clear();
+            buffered
+        } else {
+            line.to_string()
+        };
+
+        if complete_line.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        match self.parse_json_event(&complete_line) {
+            Ok(events) => Ok(events),
+            Err(e) => {
+                if line.trim_end().ends_with('}') {
+                    warn!(
+                        "Failed to parse complete JSON line: {} - Error: {}",
+                        complete_line, e
+                    );
+                    Err(e)
+                } else {
+                    // Incomplete line, buffer it
+                    self.state.line_buffer = complete_line;
+                    Ok(vec![])
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Vec<AgentEvent> {
+        let events = self.finish_message();
+        self.state.line_buffer.clear();
+        events
+    }
+
+    fn agent_type(&self) -> &str {
+        "gemini-json"
+    }
+
+    fn reset(&mut self) {
+        self.state = ParserState::default();
+        self.messages_seen = 0;
+    }
+}
+
+impl Default for GeminiJsonParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A short Gemini CLI run: streamed answer, a tool call, then the final stats
+    const FIXTURE: [&str; 8] = [
+        r#"{"type":"init","timestamp":"2025-10-10T12:00:00.000Z","session_id":"c25acda3-b51f-41f9-9bc5-954c70c17bf4","model":"gemini-2.5-pro"}"#,
+        r#"{"type":"message","timestamp":"2025-10-10T12:00:00.100Z","role":"user","content":"List the files"}"#,
+        r#"{"type":"message","timestamp":"2025-10-10T12:00:01.000Z","role":"assistant","content":"I'll list ","delta":true}"#,
+        r#"{"type":"message","timestamp":"2025-10-10T12:00:01.200Z","role":"assistant","content":"the files.","delta":true}"#,
+        r#"{"type":"tool_use","timestamp":"2025-10-10T12:00:02.000Z","tool_name":"list_directory","tool_id":"list_directory-1760097602000-0","parameters":{"path":"."}}"#,
+        r#"{"type":"tool_result","timestamp":"2025-10-10T12:00:02.100Z","tool_id":"list_directory-1760097602000-0","status":"success","output":"Cargo.toml\nsrc"}"#,
+        r#"{"type":"message","timestamp":"2025-10-10T12:00:03.000Z","role":"assistant","content":"Done.","delta":true}"#,
+        r#"{"type":"result","timestamp":"2025-10-10T12:00:03.500Z","status":"success","stats":{"total_tokens":1530,"input_tokens":1400,"output_tokens":130,"duration_ms":3500,"tool_calls":1}}"#,
+    ];
+
+    fn describe(event: &AgentEvent) -> String {
+        match event {
+            AgentEvent::SessionInfo { model, .. } => format!("session {}", model),
+            AgentEvent::StreamingText { delta, message_id } => {
+                format!("delta {:?} {}", delta, message_id.as_deref().unwrap_or(""))
+            }
+            AgentEvent::Message { content, id } => {
+                format!("message {:?} {}", content, id.as_deref().unwrap_or(""))
+            }
+            AgentEvent::ToolCall { name, input, .. } => format!("tool {} {}", name, input),
+            AgentEvent::ToolResult {
+                content, is_error, ..
+            } => format!("result {:?} error={}", content, is_error),
+            AgentEvent::Usage {
+                input_tokens,
+                output_tokens,
+                ..
+            } => format!("usage {}/{}", input_tokens, output_tokens),
+            AgentEvent::Error { message, .. } => format!("error {}", message),
+            other => format!("{:?}", other),
+        }
+    }
+
+    fn parse_all(parser: &mut GeminiJsonParser, lines: &[&str]) -> Vec<String> {
+        let mut events = Vec::new();
+        for line in lines {
+            events.extend(parser.parse_line(line).unwrap());
+        }
+        events.extend(parser.flush());
+        events.iter().map(describe).collect()
+    }
+
+    #[test]
+    fn test_gemini_stream_event_sequence() {
+        let mut parser = GeminiJsonParser::new();
+        assert_eq!(
+            parse_all(&mut parser, &FIXTURE),
+            vec![
+                "session gemini-2.5-pro",
+                "delta \"I'll list \" gemini-msg-1",
+                "delta \"the files.\" gemini-msg-1",
+                "message \"I'll list the files.\" gemini-msg-1",
+                "tool list_directory {\"path\":\".\"}",
+                "result \"Cargo.toml\\nsrc\" error=false",
+                "delta \"Done.\" gemini-msg-2",
+                "message \"Done.\" gemini-msg-2",
+                "usage 1400/130",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gemini_lines_split_across_reads() {
+        let mut whole = GeminiJsonParser::new();
+        let expected = parse_all(&mut whole, &FIXTURE);
+
+        // Every line arrives in two reads, cut mid-string
+        let mut split = GeminiJsonParser::new();
+        let mut chunks = Vec::new();
+        for line in FIXTURE {
+            let (head, tail) = line.split_at(line.len() / 2);
+            chunks.push(head);
+            chunks.push(tail);
+        }
+        assert_eq!(parse_all(&mut split, &chunks), expected);
+    }
+
+    #[test]
+    fn test_gemini_tool_error_and_failed_run() {
+        let mut parser = GeminiJsonParser::new();
+        let events = parse_all(
+            &mut parser,
+            &[
+                r#"{"type":"tool_result","tool_id":"read_file-1","status":"error","output":"","error":{"type":"FILE_NOT_FOUND","message":"File not found: a.txt"}}"#,
+                r#"{"type":"error","severity":"warning","message":"Loop detected"}"#,
+                r#"{"type":"result","status":"error","error":{"type":"FatalTurnLimitedError","message":"Reached max turns"}}"#,
+            ],
+        );
+        assert_eq!(
+            events,
+            vec![
+                "result \"File not found: a.txt\" error=true",
+                "error Loop detected",
+                "error Reached max turns",
+            ]
+        );
+    }
+}
@@ -101,12 +101,19 @@ pub enum AgentEvent {
 }
 
 /// MCP Server information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct McpServerInfo {
     pub name: String,
     pub status: String,
 }
 
+impl McpServerInfo {
+    /// Whether the server failed rather than connecting or still starting up
+    pub fn is_error(&self) -> bool {
+        !matches!(self.status.as_str(), "connected" | "pending")
+    }
+}
+
 /// State tracking for streaming parsers
 #[derive(Debug, Clone, Default)]
 pub struct ParserState {
@@ -157,21 +164,39 @@ impl ParserFactory {
         } else {
             first_line
         };
-        if content.starts_with('{') && content.contains("\"type\"") {
-            Box::new(crate::agent_parsers::claude_json::ClaudeJsonParser::new())
-        } else {
+        if !content.starts_with('{') || !content.contains("\"type\"") {
             // Default to plain text parser
-            Box::new(crate::agent_parsers::plain_text::PlainTextParser::new())
+            return Box::new(crate::agent_parsers::plain_text::PlainTextParser::new());
+        }
+
+        // Gemini CLI opens its stream with an `init` event; Claude's is `system`/`init`
+        let event_type = serde_json::from_str::<Value>(content)
+            .ok()
+            .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(String::from));
+        if event_type.as_deref() == Some("init") {
+            Box::new(crate::agent_parsers::gemini_json::GeminiJsonParser::new())
+        } else {
+            Box::new(crate::agent_parsers::claude_json::ClaudeJsonParser::new())
         }
     }
 
+    /// Create the parser for a session's recorded agent type
+    pub fn for_agent(agent_type: crate::models::AgentType) -> Box<dyn AgentOutputParser> {
+        Self::create_for_agent(agent_type.as_str())
+    }
+
     /// Create a parser for a specific agent type
     pub fn create_for_agent(agent_type: &str) -> Box<dyn AgentOutputParser> {
         match agent_type.to_lowercase().as_str() {
             "claude" | "claude-json" => {
                 Box::new(crate::agent_parsers::claude_json::ClaudeJsonParser::new())
             }
-            "plain" | "text" => Box::new(crate::agent_parsers::plain_text::PlainTextParser::new()),
+            "gemini" | "gemini-json" => {
+                Box::new(crate::agent_parsers::gemini_json::GeminiJsonParser::new())
+            }
+            "plain" | "text" | "plain-text" => {
+                Box::new(crate::agent_parsers::plain_text::PlainTextParser::new())
+            }
             _ => Box::new(crate::agent_parsers::plain_text::PlainTextParser::new()),
         }
     }
@@ -187,4 +212,24 @@ mod parser_factory_tests {
         let parser = ParserFactory::create_parser(line);
         assert_eq!(parser.agent_type(), "claude-json");
     }
+
+    #[test]
+    fn detects_gemini_init_line() {
+        let line = r#"{"type":"init","session_id":"abc","model":"gemini-2.5-pro"}"#;
+        assert_eq!(ParserFactory::create_parser(line).agent_type(), "gemini-json");
+        assert_eq!(ParserFactory::create_for_agent("Gemini").agent_type(), "gemini-json");
+    }
+
+    #[test]
+    fn dispatches_on_session_agent_type() {
+        use crate::models::AgentType;
+
+        assert_eq!(ParserFactory::for_agent(AgentType::Claude).agent_type(), "claude-json");
+        assert_eq!(ParserFactory::for_agent(AgentType::Gemini).agent_type(), "gemini-json");
+        assert_eq!(ParserFactory::for_agent(AgentType::PlainText).agent_type(), "plain-text");
+        assert_eq!(AgentType::from_label(None), AgentType::Claude);
+        for agent in AgentType::ALL {
+            assert_eq!(AgentType::from_label(Some(agent.as_str())), agent);
+        }
+    }
 }
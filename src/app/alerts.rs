@@ -0,0 +1,112 @@
+// ABOUTME: Terminal bell and visual flash alerts for session lifecycle events
+// Lets unattended sessions call for attention when they finish, fail, or wait for input
+
+use crate::config::UiPreferences;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// How long the screen stays inverted for a visual flash
+const FLASH_DURATION: Duration = Duration::from_millis(200);
+
+/// Minimum gap between alerts, so a burst of events (several sessions finishing
+/// together, repeated agent errors) rings once
+const ALERT_COOLDOWN: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionAlert {
+    Completed,
+    Errored,
+    AwaitingInput,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Alerter {
+    bell: bool,
+    flash: bool,
+    last_alert: Option<Instant>,
+    pending_bell: bool,
+    flash_until: Option<Instant>,
+}
+
+impl Alerter {
+    pub fn new(bell: bool, flash: bool) -> Self {
+        Self {
+            bell,
+            flash,
+            ..Self::default()
+        }
+    }
+
+    /// Alerts as configured; `silent` turns both off regardless of the other toggles
+    pub fn from_preferences(preferences: &UiPreferences) -> Self {
+        if preferences.silent {
+            return Self::default();
+        }
+        Self::new(
+            preferences.bell_on_session_events,
+            preferences.flash_on_session_events,
+        )
+    }
+
+    /// Queue a bell and/or start a flash. Returns false when alerts are off or
+    /// the previous alert was too recent.
+    pub fn trigger(&mut self, alert: SessionAlert, now: Instant) -> bool {
+        if !self.bell && !self.flash {
+            return false;
+        }
+        if self.last_alert.is_some_and(|last| now.duration_since(last) < ALERT_COOLDOWN) {
+            debug!("Suppressing {:?} alert during cooldown", alert);
+            return false;
+        }
+
+        debug!("Alerting for {:?}", alert);
+        self.last_alert = Some(now);
+        self.pending_bell |= self.bell;
+        if self.flash {
+            self.flash_until = Some(now + FLASH_DURATION);
+        }
+        true
+    }
+
+    /// Whether a bell is due; clears it so it rings once
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.pending_bell)
+    }
+
+    pub fn is_flashing(&self, now: Instant) -> bool {
+        self.flash_until.is_some_and(|until| now < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alerts_respect_cooldown_and_silent() {
+        let now = Instant::now();
+        let mut alerter = Alerter::new(true, true);
+
+        assert!(alerter.trigger(SessionAlert::Completed, now));
+        assert!(alerter.take_bell());
+        assert!(!alerter.take_bell());
+        assert!(alerter.is_flashing(now));
+        assert!(!alerter.is_flashing(now + FLASH_DURATION));
+
+        // A second event right away is folded into the first alert
+        assert!(!alerter.trigger(SessionAlert::Errored, now + Duration::from_millis(500)));
+        assert!(!alerter.take_bell());
+        assert!(alerter.trigger(SessionAlert::AwaitingInput, now + ALERT_COOLDOWN));
+
+        let preferences = UiPreferences {
+            bell_on_session_events: true,
+            flash_on_session_events: true,
+            silent: true,
+            ..UiPreferences::default()
+        };
+        let mut silent = Alerter::from_preferences(&preferences);
+        assert!(!silent.trigger(SessionAlert::Completed, now));
+        assert!(!silent.take_bell());
+        assert!(!silent.is_flashing(now));
+    }
+}
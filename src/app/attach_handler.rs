@@ -15,6 +15,30 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::Stdout;
 use tokio::process::Command;
 
+/// tmux target for exactly `session_name`; the `=` prefix stops tmux from picking
+/// another session whose name merely starts with it
+fn exact_target(session_name: &str) -> String {
+    format!("={}", session_name)
+}
+
+/// `tmux attach-session` for the named session
+pub fn tmux_attach_command(session_name: &str) -> Command {
+    let mut command = Command::new("tmux");
+    command.arg("attach-session").arg("-t").arg(exact_target(session_name));
+    command
+}
+
+/// Whether the named tmux session is still running
+pub async fn tmux_session_exists(session_name: &str) -> bool {
+    Command::new("tmux")
+        .arg("has-session")
+        .arg("-t")
+        .arg(exact_target(session_name))
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+}
+
 /// Handler for attaching to tmux sessions with TUI suspend/resume
 pub struct AttachHandler<'a> {
     terminal: &'a mut Terminal<CrosstermBackend<Stdout>>,
@@ -56,6 +80,30 @@ impl<'a> AttachHandler<'a> {
         result
     }
 
+    /// Run an external command with the TUI suspended
+    ///
+    /// Gives the command full control of the terminal (e.g. a pager or diff tool)
+    /// and restores the TUI once it exits, even if it failed.
+    ///
+    /// # Arguments
+    /// * `command` - The command to run with inherited stdin/stdout/stderr
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or an error if the command could not run or exited non-zero
+    pub async fn run_external_command(&mut self, command: &mut Command) -> Result<()> {
+        self.suspend_tui().await?;
+
+        let result = command.status().await.context("Failed to run external command");
+
+        self.resume_tui().await?;
+
+        let status = result?;
+        if !status.success() {
+            anyhow::bail!("External command exited with code: {:?}", status.code());
+        }
+        Ok(())
+    }
+
     /// Suspend the TUI
     ///
     /// Leaves alternate screen and disables raw mode, returning control to the normal terminal
@@ -110,17 +158,8 @@ impl<'a> AttachHandler<'a> {
         tracing::info!("[ATTACH] Executing tmux attach-session for '{}'", session_name);
 
         // First verify the session exists
-        let check = Command::new("tmux")
-            .arg("has-session")
-            .arg("-t")
-            .arg(session_name)
-            .output()
-            .await
-            .context("Failed to check if tmux session exists")?;
-
-        if !check.status.success() {
-            let stderr = String::from_utf8_lossy(&check.stderr);
-            tracing::error!("[ATTACH] tmux session '{}' does not exist: {}", session_name, stderr);
+        if !tmux_session_exists(session_name).await {
+            tracing::error!("[ATTACH] tmux session '{}' does not exist", session_name);
             anyhow::bail!("tmux session '{}' does not exist", session_name);
         }
 
@@ -128,10 +167,7 @@ impl<'a> AttachHandler<'a> {
 
         // Execute tmux attach-session
         // Note: We use tokio::process::Command which will inherit stdin/stdout/stderr
-        let status = Command::new("tmux")
-            .arg("attach-session")
-            .arg("-t")
-            .arg(session_name)
+        let status = tmux_attach_command(session_name)
             .status()
             .await
             .context("Failed to execute tmux attach-session")?;
@@ -163,4 +199,13 @@ mod tests {
         // This test just verifies the struct can be created
         // In practice, the handler would be created with a real terminal instance
     }
+
+    #[test]
+    fn test_tmux_attach_command_targets_the_exact_session() {
+        let command = tmux_attach_command("agents_feature");
+        let command = command.as_std();
+        assert_eq!(command.get_program(), "tmux");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, vec!["attach-session", "-t", "=agents_feature"]);
+    }
 }
@@ -0,0 +1,147 @@
+// ABOUTME: Claude chat conversations saved under ~/.agents-in-a-box/chats, one file per session
+// The chat opened without a session uses global.json; starting over archives the file
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::claude::ClaudeMessage;
+
+/// A conversation as written to disk: completed messages only
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedChat {
+    pub associated_session_id: Option<Uuid>,
+    #[serde(default)]
+    pub total_tokens_used: u32,
+    pub messages: Vec<ClaudeMessage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatHistoryStore {
+    dir: PathBuf,
+}
+
+impl ChatHistoryStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Store under `~/.agents-in-a-box/chats`, or None without a home directory
+    pub fn in_home_dir() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".agents-in-a-box").join("chats")))
+    }
+
+    /// `<session id>.json`, or `global.json` for the chat outside any session
+    pub fn path(&self, session_id: Option<Uuid>) -> PathBuf {
+        let name = session_id.map_or_else(|| "global".to_string(), |id| id.to_string());
+        self.dir.join(format!("{}.json", name))
+    }
+
+    pub fn save(&self, chat: &SavedChat) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(chat)?;
+        fs::write(self.path(chat.associated_session_id), json)
+    }
+
+    /// The saved conversation, or None when there isn't one or it can't be read
+    pub fn load(&self, session_id: Option<Uuid>) -> Option<SavedChat> {
+        let path = self.path(session_id);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed to read chat history {}: {}", path.display(), e);
+                }
+                return None;
+            }
+        };
+        serde_json::from_str(&contents)
+            .map_err(|e| warn!("Ignoring unreadable chat history {}: {}", path.display(), e))
+            .ok()
+    }
+
+    /// Move the conversation aside as `<name>-<timestamp>.json` so the next save starts
+    /// fresh. Returns the archive written, if there was a conversation to archive.
+    pub fn archive(&self, session_id: Option<Uuid>) -> io::Result<Option<PathBuf>> {
+        let path = self.path(session_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("chat");
+        let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+        let archive = self.dir.join(format!("{}-{}.json", stem, timestamp));
+        fs::rename(&path, &archive)?;
+        info!("Archived chat history to {}", archive.display());
+        Ok(Some(archive))
+    }
+}
+
+/// `~/.agents-in-a-box/exports/chat-<session id or global>-<timestamp>.md`
+pub fn default_chat_export_path(session_id: Option<Uuid>, now: DateTime<Local>) -> Option<PathBuf> {
+    let name = session_id.map_or_else(|| "global".to_string(), |id| id.to_string());
+    let file = format!("chat-{}-{}.md", name, now.format("%Y%m%d-%H%M%S"));
+    dirs::home_dir().map(|home| home.join(".agents-in-a-box").join("exports").join(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::ClaudeChatState;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_chat_round_trip_excludes_streaming_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ChatHistoryStore::new(temp_dir.path().join("chats"));
+        let session_id = Some(Uuid::new_v4());
+
+        let mut chat =
+            ClaudeChatState { associated_session_id: session_id, ..ClaudeChatState::new() };
+        chat.start_streaming("Sketch the cache layer".to_string());
+        chat.append_streaming_response("Start with an LRU");
+        chat.finish_streaming();
+        chat.total_tokens_used = 42;
+        chat.start_streaming("And eviction?".to_string());
+        chat.append_streaming_response("Evict the least");
+        chat.save(&store).unwrap();
+
+        let saved = fs::read_to_string(store.path(session_id)).unwrap();
+        assert!(!saved.contains("Evict the least"));
+        assert!(!saved.contains("And eviction?"));
+
+        let loaded = ClaudeChatState::load(&store, session_id);
+        assert_eq!(loaded.associated_session_id, session_id);
+        assert_eq!(loaded.total_tokens_used, 42);
+        assert!(!loaded.is_streaming);
+        assert!(loaded.current_streaming_response.is_none());
+        let contents: Vec<&str> = loaded.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["Sketch the cache layer", "Start with an LRU"]);
+
+        // Another association starts empty
+        assert!(ClaudeChatState::load(&store, None).messages.is_empty());
+    }
+
+    #[test]
+    fn test_archive_starts_a_new_conversation() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ChatHistoryStore::new(temp_dir.path().to_path_buf());
+        assert_eq!(store.archive(None).unwrap(), None);
+
+        let chat = SavedChat {
+            associated_session_id: None,
+            total_tokens_used: 0,
+            messages: vec![ClaudeMessage::user("hello".to_string())],
+        };
+        store.save(&chat).unwrap();
+        let archive = store.archive(None).unwrap().unwrap();
+
+        assert!(archive.file_name().unwrap().to_string_lossy().starts_with("global-"));
+        assert!(!store.path(None).exists());
+        assert!(store.load(None).is_none());
+        assert!(fs::read_to_string(archive).unwrap().contains("hello"));
+    }
+}
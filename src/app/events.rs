@@ -6,15 +6,14 @@ use crate::app::{
     AppState,
     state::{AsyncAction, AuthMethod, View},
 };
+use crate::config::KeyAction;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use tracing::info;
 
-// Layout configuration - sessions pane width as percentage of terminal width
-const SESSIONS_PANE_WIDTH_PERCENTAGE: f32 = 0.4;
-
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     Quit,
+    ForceQuit, // Ctrl+C: quit without asking about running sessions
     NextSession,
     PreviousSession,
     NextWorkspace,
@@ -22,15 +21,37 @@ pub enum AppEvent {
     ToggleHelp,
     RefreshWorkspaces, // Manual refresh of workspace data
     ToggleClaudeChat,  // Toggle Claude chat visibility
+    ClaudeChatInputChar(char),
+    ClaudeChatBackspace,
+    ClaudeChatSend,
+    ClaudeChatResetUsage,  // Zero the chat's token count
+    ClaudeChatRaiseBudget, // Allow another budget's worth of chat tokens
+    ClaudeChatNewConversation, // Archive the saved conversation and start over
+    ClaudeChatCancel,          // Stop the reply being streamed
+    ClaudeChatExport,          // Save the conversation as Markdown
     NewSession,        // Create session in current directory
     SearchWorkspace,   // Search all workspaces
+    NewReviewSession,  // Create a review-only session on a detached revision
+    ReturnToSessionList, // Back out of a root view (e.g. the non-git notice) to the session list
     AttachSession,
     DetachSession,
     KillContainer,
     ReauthenticateCredentials,
     RestartSession,
     DeleteSession,
+    StopKeepWorktree, // Remove the container but keep the worktree for later revival
+    StopSession,      // Stop the container but leave it in place for a later restart
+    ToggleSessionProtection, // Protect/unprotect the selected session from bulk operations
+    CycleSessionSort, // Sort sessions by status, name, last activity, or changes
+    StartSessionFilter, // Open the filter input over all sessions
+    SessionFilterInputChar(char),
+    SessionFilterBackspace,
+    ConfirmSessionFilter, // Stop typing, keep the filter applied
+    ClearSessionFilter,   // Drop the filter and show every session again
+    WidenSessionList,  // Move the pane divider right
+    NarrowSessionList, // Move the pane divider left
     CleanupOrphaned, // Clean up orphaned containers
+    CleanupDeadSessions, // Delete all stopped/errored sessions after confirmation
     SwitchToLogs,
     SwitchToTerminal,
     GoToTop,
@@ -43,8 +64,18 @@ pub enum AppEvent {
     ScrollLogsToTop,
     ScrollLogsToBottom,
     ToggleAutoScroll, // Toggle auto-scroll mode in live logs
+    ToggleLogWrap,    // Wrap long live log lines, or scroll them sideways
+    ScrollLogsLeft,   // Scroll unwrapped live log lines left
+    ScrollLogsRight,  // Scroll unwrapped live log lines right
+    ToggleLogTimestamps, // Show/hide the live logs timestamp column
+    ToggleLogAnsiColors, // Render or strip ANSI colors in live log messages
+    ToggleLogBookmark,   // Bookmark/unbookmark the current live log line
+    NextLogBookmark,     // Jump to the next bookmarked log line
+    PreviousLogBookmark, // Jump to the previous bookmarked log line
+    CopyToolCommand,     // Copy the focused tool call's command
+    StartLogFilter,      // Type a substring/regex the live log lines must match
     // Mouse events
-    MouseClick { x: u16, y: u16 },
+    MouseClick(crate::components::layout::ClickTarget), // Focus the clicked pane, select its row
     MouseDragStart { x: u16, y: u16 },
     MouseDragEnd { x: u16, y: u16 },
     MouseDragging { x: u16, y: u16 },
@@ -55,13 +86,22 @@ pub enum AppEvent {
     NewSessionConfirmRepo,
     NewSessionInputChar(char),
     NewSessionBackspace,
+    NewSessionProceedToBaseBranch,
+    NewSessionBranchAddSuffix, // Rename a branch checked out elsewhere with a free -N suffix
+    NewSessionBranchReuse,     // Restart the session that already owns the branch's worktree
+    NewSessionNextBaseBranch,
+    NewSessionPrevBaseBranch,
+    NewSessionConfirmBaseBranch, // Use the highlighted base branch and continue to mode selection
     NewSessionProceedToModeSelection,
     NewSessionToggleMode,
+    NewSessionCycleAgentType, // Switch the agent whose output the session's logs are parsed as
+    NewSessionCycleModel,     // Switch the Claude model the session runs
     NewSessionProceedFromMode,
     NewSessionInputPromptChar(char),
     NewSessionBackspacePrompt,
     NewSessionInsertNewline,
     NewSessionPasteText(String), // Paste text into boss mode prompt
+    NewSessionResolvePaste(crate::app::state::PasteChoice), // Handle a paste over the size threshold
     // Cursor movement events for boss mode prompt
     NewSessionCursorLeft,
     NewSessionCursorRight,
@@ -74,8 +114,15 @@ pub enum AppEvent {
     NewSessionCursorWordRight,
     NewSessionDeleteWordForward,
     NewSessionDeleteWordBackward,
+    NewSessionUndoPrompt, // Ctrl+Z in the boss prompt
+    NewSessionRedoPrompt, // Ctrl+Y or Ctrl+Shift+Z in the boss prompt
     NewSessionProceedToPermissions,
     NewSessionTogglePermissions,
+    NewSessionOpenListStep(crate::app::state::NewSessionStep), // Env vars, mounts or ports
+    NewSessionListEntryChar(char),
+    NewSessionListEntryBackspace,
+    NewSessionListEntrySubmit,     // Add the typed entry (empty input finishes)
+    NewSessionFinishListStep,      // Back to the permissions step
     NewSessionCreate,
     // File finder events for @ symbol trigger
     FileFinderNavigateUp,
@@ -96,11 +143,66 @@ pub enum AppEvent {
     AuthSetupCancel,          // Cancel auth setup (skip)
     AuthSetupInputChar(char), // Input character for API key
     AuthSetupBackspace,       // Backspace in API key input
+    AuthSetupToggleCheck,     // Toggle the online check of a new API key
     AuthSetupCheckStatus,     // Check authentication status
     AuthSetupRefresh,         // Manual refresh to check auth completion
     AuthSetupShowCommand,     // Show manual CLI command
     // Git view events
     ShowGitView,       // Show git view for selected session
+    OpenExternalDiff,  // Open selected session's diff in the external diff tool
+    // Run comparison events
+    CompareWithLastRun,    // Compare selected boss session with the previous run of its prompt
+    RunCompareScrollUp,    // Scroll run comparison up
+    RunCompareScrollDown,  // Scroll run comparison down
+    RunCompareClose,       // Close the run comparison
+    // Dashboard events
+    ShowDashboard,         // Open the at-a-glance dashboard
+    DashboardNext,         // Select next session row
+    DashboardPrevious,     // Select previous session row
+    DashboardOpenSelected, // Jump to the selected session in the session list
+    DashboardRefresh,      // Recompute the summary now
+    // Logs viewer events (search state lives on the component, handled in main.rs)
+    ShowLogsViewer,             // Open the selected session's logs full screen
+    LogsViewerStartSearch,      // Type a search term
+    LogsViewerNextMatch,        // Jump to the next match, wrapping at the end
+    LogsViewerPreviousMatch,    // Jump to the previous match, wrapping at the start
+    LogsViewerToggleCase,       // Switch between case-insensitive and case-sensitive search
+    LogsViewerScrollUp,         // Scroll the logs viewer up
+    LogsViewerScrollDown,       // Scroll the logs viewer down
+    LogsViewerToggleWrap,       // Wrap long lines, or scroll them sideways
+    LogsViewerScrollLeft,       // Scroll unwrapped lines left
+    LogsViewerScrollRight,      // Scroll unwrapped lines right
+    LogsViewerToggleBookmark,   // Bookmark/unbookmark the top line in the logs viewer
+    LogsViewerNextBookmark,     // Jump to the next bookmarked line, wrapping at the end
+    LogsViewerPreviousBookmark, // Jump to the previous bookmarked line, wrapping at the start
+    // Boss prompt viewer events
+    ShowBossPrompt,                 // Copy selected session's boss prompt and open the viewer
+    CopyContainerId,                // Copy selected session's full container ID to the clipboard
+    CopyWorktreePath,               // Copy selected session's worktree path to the clipboard
+    // Log export dialog events
+    StartLogExport,           // Ask where to save the selected session's logs
+    LogExportInputChar(char), // Type into the export filename
+    LogExportBackspace,       // Delete from the export filename
+    LogExportToggleRaw,       // Keep or strip ANSI escape sequences
+    LogExportConfirm,         // Write the file
+    LogExportCancel,          // Close the dialog without exporting
+    PromptViewerScrollUp,           // Scroll prompt viewer up
+    PromptViewerScrollDown,         // Scroll prompt viewer down
+    PromptViewerCopy,               // Copy the shown prompt text again
+    PromptViewerToggleFileContents, // Include/exclude @-file contents
+    PromptViewerClose,              // Close the prompt viewer
+    ShowNotificationHistory,       // Open the archive of past notifications
+    NotificationHistoryScrollUp,   // Scroll toward newer notifications
+    NotificationHistoryScrollDown, // Scroll toward older notifications
+    NotificationHistoryClear,      // Forget all archived notifications
+    NotificationHistoryClose,      // Close the notification history viewer
+    DismissNotification,           // Remove the topmost sticky notification
+    ShowAuthProfiles,              // Open the auth profile switcher
+    AuthProfileUp,                 // Select the previous auth profile
+    AuthProfileDown,               // Select the next auth profile
+    AuthProfileSelect,             // Make the selected auth profile active
+    AuthProfileClose,              // Close the auth profile switcher
+    Logout,                        // Ask to revoke and remove stored credentials
     GitViewSwitchTab,  // Switch between Files and Diff tabs
     GitViewNextFile,   // Navigate to next file
     GitViewPrevFile,   // Navigate to previous file
@@ -130,6 +232,18 @@ pub enum AppEvent {
     GitViewToggleFolder,          // Toggle folder expand/collapse
     GitViewExpandAll,             // Expand all folders
     GitViewCollapseAll,           // Collapse all folders
+    // Files touched navigation events
+    GitViewNextTouchedFile,       // Select next touched file
+    GitViewPrevTouchedFile,       // Select previous touched file
+    GitViewOpenTouchedFile,       // Open the selected touched file's diff
+    GitViewStash,                 // Stash the worktree's changes
+    GitViewPopStash,              // Pop the newest stash back into the worktree
+    GitViewCopyDiff(bool),        // Copy the worktree diff; true includes staged changes
+    GitViewDiscard(bool),         // Ask to discard the selected file's changes; true for all
+    GitViewToggleFileSelection,   // Add/remove the selected file from the next commit
+    GitViewToggleWordDiff,        // Switch the Diff tab between line and word highlighting
+    GitViewToggleBlame,           // Show/hide the Diff tab's author and commit gutter
+    GitViewToggleConflicts,       // Expand/collapse the list of files conflicting with the base
     // Tmux integration events
     AttachTmuxSession,            // Attach to tmux session
     DetachTmuxSession,            // Detach from tmux session
@@ -146,33 +260,6 @@ impl EventHandler {
     /// Handle mouse events and convert to appropriate app events
     pub fn handle_mouse_event(event: AppEvent, state: &mut AppState) -> Option<AppEvent> {
         match event {
-            AppEvent::MouseClick { x, y: _ } => {
-                // Determine which pane was clicked based on terminal dimensions
-                // The layout splits at 40% for sessions, 60% for logs
-                let term_width = crossterm::terminal::size().unwrap_or((80, 24)).0;
-                let split_point = (term_width as f32 * SESSIONS_PANE_WIDTH_PERCENTAGE) as u16;
-
-                // Check if we're in the main view (not in overlays)
-                if state.current_view == View::SessionList && !state.help_visible {
-                    if x < split_point {
-                        // Clicked in sessions pane
-                        if state.focused_pane != crate::app::state::FocusedPane::Sessions {
-                            Some(AppEvent::SwitchPaneFocus)
-                        } else {
-                            None
-                        }
-                    } else {
-                        // Clicked in logs pane
-                        if state.focused_pane != crate::app::state::FocusedPane::LiveLogs {
-                            Some(AppEvent::SwitchPaneFocus)
-                        } else {
-                            None
-                        }
-                    }
-                } else {
-                    None
-                }
-            }
             AppEvent::MouseDragStart { x: _, y: _ } => {
                 // Start text selection in logs pane
                 if state.focused_pane == crate::app::state::FocusedPane::LiveLogs {
@@ -214,6 +301,12 @@ impl EventHandler {
     pub fn handle_key_event(key_event: KeyEvent, state: &mut AppState) -> Option<AppEvent> {
         use crate::app::state::View;
 
+        // Escape follows one policy across every view, so it is resolved before
+        // any view-specific handling
+        if key_event.code == KeyCode::Esc {
+            return Self::handle_escape_key(state);
+        }
+
         // Handle confirmation dialog first (highest priority)
         if state.confirmation_dialog.is_some() {
             match key_event.code {
@@ -223,26 +316,19 @@ impl EventHandler {
                 KeyCode::Enter => {
                     return Some(AppEvent::ConfirmationConfirm);
                 }
-                KeyCode::Esc => {
-                    return Some(AppEvent::ConfirmationCancel);
-                }
                 _ => return None,
             }
         }
 
         if state.help_visible {
-            match key_event.code {
-                KeyCode::Char('?') | KeyCode::Esc => {
-                    return Some(AppEvent::ToggleHelp);
-                }
-                _ => {
-                    return None;
-                }
+            if state.keymap.is(&key_event, KeyAction::ToggleHelp) {
+                return Some(AppEvent::ToggleHelp);
             }
+            return None;
         }
 
         // Handle global help toggle first (should work from any view)
-        if let KeyCode::Char('?') = key_event.code {
+        if state.keymap.is(&key_event, KeyAction::ToggleHelp) {
             return Some(AppEvent::ToggleHelp);
         }
 
@@ -280,7 +366,6 @@ impl EventHandler {
         if state.is_in_quick_commit_mode() {
             return match key_event.code {
                 KeyCode::Enter => Some(AppEvent::QuickCommitConfirm),
-                KeyCode::Esc => Some(AppEvent::QuickCommitCancel),
                 KeyCode::Backspace => Some(AppEvent::QuickCommitBackspace),
                 KeyCode::Left => Some(AppEvent::QuickCommitCursorLeft),
                 KeyCode::Right => Some(AppEvent::QuickCommitCursorRight),
@@ -289,17 +374,123 @@ impl EventHandler {
             };
         }
 
+        // Handle log export filename input
+        if state.log_export.is_some() {
+            return match key_event.code {
+                KeyCode::Enter => Some(AppEvent::LogExportConfirm),
+                KeyCode::Backspace => Some(AppEvent::LogExportBackspace),
+                KeyCode::Tab => Some(AppEvent::LogExportToggleRaw),
+                KeyCode::Char(ch) => Some(AppEvent::LogExportInputChar(ch)),
+                _ => None,
+            };
+        }
+
+        // Typing into the session filter
+        if state.current_view == View::SessionList && state.session_filter_editing {
+            return match key_event.code {
+                KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(AppEvent::ForceQuit)
+                }
+                KeyCode::Enter => Some(AppEvent::ConfirmSessionFilter),
+                KeyCode::Backspace => Some(AppEvent::SessionFilterBackspace),
+                KeyCode::Down => Some(AppEvent::NextSession),
+                KeyCode::Up => Some(AppEvent::PreviousSession),
+                KeyCode::Char(ch) => Some(AppEvent::SessionFilterInputChar(ch)),
+                _ => None,
+            };
+        }
+
+        // Handle boss prompt viewer popup
+        if state.prompt_viewer.is_some() {
+            return match key_event.code {
+                KeyCode::Char('q') => Some(AppEvent::PromptViewerClose),
+                KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::PromptViewerScrollUp),
+                KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::PromptViewerScrollDown),
+                KeyCode::Char('y') => Some(AppEvent::PromptViewerCopy),
+                KeyCode::Char('f') => Some(AppEvent::PromptViewerToggleFileContents),
+                _ => None,
+            };
+        }
+
+        // Handle notification history popup
+        if state.notification_history_scroll.is_some() {
+            return match key_event.code {
+                KeyCode::Char('q') => Some(AppEvent::NotificationHistoryClose),
+                KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::NotificationHistoryScrollUp),
+                KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::NotificationHistoryScrollDown),
+                KeyCode::Char('c') => Some(AppEvent::NotificationHistoryClear),
+                _ => None,
+            };
+        }
+
+        // Handle auth profile switcher popup
+        if state.auth_profile_picker.is_some() {
+            return match key_event.code {
+                KeyCode::Char('q') => Some(AppEvent::AuthProfileClose),
+                KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::AuthProfileUp),
+                KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::AuthProfileDown),
+                KeyCode::Enter => Some(AppEvent::AuthProfileSelect),
+                _ => None,
+            };
+        }
+
+        // Handle run comparison popup
+        if state.run_compare.is_some() {
+            return match key_event.code {
+                KeyCode::Char('q') => Some(AppEvent::RunCompareClose),
+                KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::RunCompareScrollUp),
+                KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::RunCompareScrollDown),
+                _ => None,
+            };
+        }
+
         // Handle git view
         if state.current_view == View::GitView {
             tracing::debug!("In git view, handling git view keys");
             return Self::handle_git_view_keys(key_event, state);
         }
 
+        // Handle dashboard
+        if state.current_view == View::Dashboard {
+            return match key_event.code {
+                KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::DashboardNext),
+                KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::DashboardPrevious),
+                KeyCode::Enter => Some(AppEvent::DashboardOpenSelected),
+                KeyCode::Char('r') => Some(AppEvent::DashboardRefresh),
+                KeyCode::Char('q') => Some(AppEvent::ReturnToSessionList),
+                _ => None,
+            };
+        }
+
+        // Handle the full-screen logs viewer
+        if state.current_view == View::Logs {
+            return match key_event.code {
+                KeyCode::Char('/') => Some(AppEvent::LogsViewerStartSearch),
+                KeyCode::Char('n') => Some(AppEvent::LogsViewerNextMatch),
+                KeyCode::Char('N') => Some(AppEvent::LogsViewerPreviousMatch),
+                KeyCode::Char('c') => Some(AppEvent::LogsViewerToggleCase),
+                KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::LogsViewerScrollUp),
+                KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::LogsViewerScrollDown),
+                KeyCode::Left | KeyCode::Char('h') => Some(AppEvent::LogsViewerScrollLeft),
+                KeyCode::Right | KeyCode::Char('l') => Some(AppEvent::LogsViewerScrollRight),
+                KeyCode::Char('b') => Some(AppEvent::LogsViewerToggleWrap),
+                KeyCode::Char('m') => Some(AppEvent::LogsViewerToggleBookmark),
+                KeyCode::Char(']') => Some(AppEvent::LogsViewerNextBookmark),
+                KeyCode::Char('[') => Some(AppEvent::LogsViewerPreviousBookmark),
+                KeyCode::Char('q') => Some(AppEvent::ReturnToSessionList),
+                _ => None,
+            };
+        }
+
+        // Remappable session list actions (keys.toml) take precedence over built-in keys
+        if let Some(action) = state.keymap.action_for(&key_event) {
+            return Some(Self::key_action_event(action));
+        }
+
         // Handle key events based on focused pane
         use crate::app::state::FocusedPane;
 
         match key_event.code {
-            KeyCode::Char('q') | KeyCode::Esc => Some(AppEvent::Quit),
             KeyCode::Tab => {
                 tracing::debug!(
                     "Tab key pressed, current focused_pane: {:?}",
@@ -308,23 +499,16 @@ impl EventHandler {
                 Some(AppEvent::SwitchPaneFocus)
             }
             KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
-                Some(AppEvent::Quit)
+                Some(AppEvent::ForceQuit)
             }
-            KeyCode::Char('c') => Some(AppEvent::ToggleClaudeChat),
-            KeyCode::Char('f') => Some(AppEvent::RefreshWorkspaces), // Manual refresh
-            KeyCode::Char('n') => Some(AppEvent::NewSession),
-            KeyCode::Char('s') => Some(AppEvent::SearchWorkspace),
-            KeyCode::Char('a') => {
-                tracing::info!("[ACTION] 'a' key pressed - AttachTmuxSession requested");
-                Some(AppEvent::AttachTmuxSession)
-            }
-            KeyCode::Char('r') => Some(AppEvent::ReauthenticateCredentials),
-            KeyCode::Char('e') => Some(AppEvent::RestartSession),
-            KeyCode::Char('d') => Some(AppEvent::DeleteSession),
-            KeyCode::Char('x') => Some(AppEvent::CleanupOrphaned),
-            KeyCode::Char('g') => Some(AppEvent::ShowGitView), // Show git view
-            KeyCode::Char('p') => Some(AppEvent::QuickCommitStart), // Start quick commit dialog
-            KeyCode::Char('E') => Some(AppEvent::ToggleExpandAll), // Toggle expand/collapse all workspaces
+            KeyCode::Char('f') => match state.focused_pane {
+                FocusedPane::Sessions => Some(AppEvent::RefreshWorkspaces), // Manual refresh
+                FocusedPane::LiveLogs => Some(AppEvent::StartLogFilter),
+            },
+            KeyCode::Char('y') => match state.focused_pane {
+                FocusedPane::Sessions => Some(AppEvent::ShowBossPrompt), // Copy boss prompt
+                FocusedPane::LiveLogs => Some(AppEvent::CopyToolCommand),
+            },
 
             // Tmux preview scroll mode (Shift + Up/Down)
             KeyCode::Up if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
@@ -361,6 +545,15 @@ impl EventHandler {
                     }
                 }
             }
+            // Move the divider between the session list and the logs pane
+            KeyCode::Left if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(AppEvent::NarrowSessionList)
+            }
+            KeyCode::Right if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(AppEvent::WidenSessionList)
+            }
+            KeyCode::Char('<') => Some(AppEvent::NarrowSessionList),
+            KeyCode::Char('>') => Some(AppEvent::WidenSessionList),
             KeyCode::Char('h') | KeyCode::Left => {
                 tracing::debug!("Left key pressed, focused_pane: {:?}", state.focused_pane);
                 match state.focused_pane {
@@ -368,10 +561,7 @@ impl EventHandler {
                         tracing::debug!("Sessions pane focused, triggering PreviousWorkspace");
                         Some(AppEvent::PreviousWorkspace)
                     }
-                    FocusedPane::LiveLogs => {
-                        tracing::debug!("LiveLogs pane focused, no left/right scrolling");
-                        None // No left/right scrolling in logs
-                    }
+                    FocusedPane::LiveLogs => Some(AppEvent::ScrollLogsLeft),
                 }
             }
             KeyCode::Char('l') | KeyCode::Right => {
@@ -381,10 +571,7 @@ impl EventHandler {
                         tracing::debug!("Sessions pane focused, triggering NextWorkspace");
                         Some(AppEvent::NextWorkspace)
                     }
-                    FocusedPane::LiveLogs => {
-                        tracing::debug!("LiveLogs pane focused, no left/right scrolling");
-                        None // No left/right scrolling in logs
-                    }
+                    FocusedPane::LiveLogs => Some(AppEvent::ScrollLogsRight),
                 }
             }
             KeyCode::Home => match state.focused_pane {
@@ -399,16 +586,172 @@ impl EventHandler {
                 FocusedPane::Sessions => None, // Space does nothing in sessions pane
                 FocusedPane::LiveLogs => Some(AppEvent::ToggleAutoScroll),
             },
+            KeyCode::Char('t') => match state.focused_pane {
+                FocusedPane::Sessions => None,
+                FocusedPane::LiveLogs => Some(AppEvent::ToggleLogTimestamps),
+            },
+            KeyCode::Char('b') => match state.focused_pane {
+                FocusedPane::Sessions => None,
+                FocusedPane::LiveLogs => Some(AppEvent::ToggleLogWrap),
+            },
+            KeyCode::Char('A') => match state.focused_pane {
+                FocusedPane::Sessions => None,
+                FocusedPane::LiveLogs => Some(AppEvent::ToggleLogAnsiColors),
+            },
+            KeyCode::Char('m') => match state.focused_pane {
+                FocusedPane::Sessions => None,
+                FocusedPane::LiveLogs => Some(AppEvent::ToggleLogBookmark),
+            },
+            KeyCode::Char(']') => match state.focused_pane {
+                FocusedPane::Sessions => None,
+                FocusedPane::LiveLogs => Some(AppEvent::NextLogBookmark),
+            },
+            KeyCode::Char('[') => match state.focused_pane {
+                FocusedPane::Sessions => None,
+                FocusedPane::LiveLogs => Some(AppEvent::PreviousLogBookmark),
+            },
+            KeyCode::Char('w') => match state.focused_pane {
+                FocusedPane::Sessions => None,
+                FocusedPane::LiveLogs => Some(AppEvent::StartLogExport),
+            },
             _ => None,
         }
     }
 
+    fn key_action_event(action: KeyAction) -> AppEvent {
+        match action {
+            KeyAction::Quit => AppEvent::Quit,
+            KeyAction::ToggleHelp => AppEvent::ToggleHelp,
+            KeyAction::NewSession => AppEvent::NewSession,
+            KeyAction::ReviewSession => AppEvent::NewReviewSession,
+            KeyAction::SearchWorkspace => AppEvent::SearchWorkspace,
+            KeyAction::Attach => {
+                tracing::info!("[ACTION] Attach key pressed - AttachTmuxSession requested");
+                AppEvent::AttachTmuxSession
+            }
+            KeyAction::Reauthenticate => AppEvent::ReauthenticateCredentials,
+            KeyAction::Restart => AppEvent::RestartSession,
+            KeyAction::Delete => AppEvent::DeleteSession,
+            KeyAction::StopKeepWorktree => AppEvent::StopKeepWorktree,
+            KeyAction::StopSession => AppEvent::StopSession,
+            KeyAction::ToggleProtection => AppEvent::ToggleSessionProtection,
+            KeyAction::CycleSort => AppEvent::CycleSessionSort,
+            KeyAction::FilterSessions => AppEvent::StartSessionFilter,
+            KeyAction::CleanupOrphaned => AppEvent::CleanupOrphaned,
+            KeyAction::CleanupDead => AppEvent::CleanupDeadSessions,
+            KeyAction::ClaudeChat => AppEvent::ToggleClaudeChat,
+            KeyAction::GitView => AppEvent::ShowGitView,
+            KeyAction::Dashboard => AppEvent::ShowDashboard,
+            KeyAction::LogsViewer => AppEvent::ShowLogsViewer,
+            KeyAction::ExternalDiff => AppEvent::OpenExternalDiff,
+            KeyAction::CompareLastRun => AppEvent::CompareWithLastRun,
+            KeyAction::CopyContainerId => AppEvent::CopyContainerId,
+            KeyAction::CopyWorktreePath => AppEvent::CopyWorktreePath,
+            KeyAction::QuickCommit => AppEvent::QuickCommitStart,
+            KeyAction::ExpandAll => AppEvent::ToggleExpandAll,
+            KeyAction::NotificationHistory => AppEvent::ShowNotificationHistory,
+            KeyAction::DismissNotification => AppEvent::DismissNotification,
+            KeyAction::AuthProfiles => AppEvent::ShowAuthProfiles,
+            KeyAction::Logout => AppEvent::Logout,
+        }
+    }
+
+    /// Resolve Escape under the global policy: close the topmost overlay if one is
+    /// open, otherwise back out of the current view to the session list. Escape on the
+    /// session list only quits when `EscapePolicy::BackOrQuit` is configured.
+    fn handle_escape_key(state: &AppState) -> Option<AppEvent> {
+        use crate::config::EscapePolicy;
+
+        // Overlays, topmost first
+        if state.confirmation_dialog.is_some() {
+            return Some(AppEvent::ConfirmationCancel);
+        }
+        if state.help_visible {
+            return Some(AppEvent::ToggleHelp);
+        }
+        if state.is_in_quick_commit_mode() {
+            return Some(AppEvent::QuickCommitCancel);
+        }
+        if state.log_export.is_some() {
+            return Some(AppEvent::LogExportCancel);
+        }
+        if state.prompt_viewer.is_some() {
+            return Some(AppEvent::PromptViewerClose);
+        }
+        if state.notification_history_scroll.is_some() {
+            return Some(AppEvent::NotificationHistoryClose);
+        }
+        if state.auth_profile_picker.is_some() {
+            return Some(AppEvent::AuthProfileClose);
+        }
+        if state.run_compare.is_some() {
+            return Some(AppEvent::RunCompareClose);
+        }
+
+        match state.current_view {
+            View::NewSession | View::SearchWorkspace => {
+                // The file finder and large-paste choice sit on top of the prompt
+                // input, so close them first
+                let file_finder_active = state
+                    .new_session_state
+                    .as_ref()
+                    .is_some_and(|session_state| session_state.file_finder.is_active);
+                let paste_pending = state
+                    .new_session_state
+                    .as_ref()
+                    .is_some_and(|session_state| session_state.pending_paste.is_some());
+                let editing_list = state
+                    .new_session_state
+                    .as_ref()
+                    .is_some_and(|session_state| session_state.step.is_list_entry());
+                if file_finder_active {
+                    Some(AppEvent::FileFinderCancel)
+                } else if paste_pending {
+                    Some(AppEvent::NewSessionResolvePaste(
+                        crate::app::state::PasteChoice::Cancel,
+                    ))
+                } else if editing_list {
+                    Some(AppEvent::NewSessionFinishListStep)
+                } else {
+                    Some(AppEvent::NewSessionCancel)
+                }
+            }
+            View::GitView => {
+                let in_commit_mode = state
+                    .git_view_state
+                    .as_ref()
+                    .is_some_and(crate::components::git_view::GitViewState::is_in_commit_mode);
+                if in_commit_mode {
+                    Some(AppEvent::GitViewCommitCancel)
+                } else {
+                    Some(AppEvent::GitViewBack)
+                }
+            }
+            // Escape stops a streaming reply before it closes the chat
+            View::ClaudeChat if state.chat_stream.is_some() => Some(AppEvent::ClaudeChatCancel),
+            View::ClaudeChat => Some(AppEvent::ToggleClaudeChat),
+            View::AttachedTerminal => Some(AppEvent::DetachSession),
+            View::AuthSetup => Some(AppEvent::AuthSetupCancel),
+            View::NonGitNotification
+            | View::Logs
+            | View::Terminal
+            | View::Help
+            | View::Dashboard => Some(AppEvent::ReturnToSessionList),
+            View::SessionList if state.session_filter.is_some() => {
+                Some(AppEvent::ClearSessionFilter)
+            }
+            View::SessionList => match state.escape_policy {
+                EscapePolicy::Back => None,
+                EscapePolicy::BackOrQuit => Some(AppEvent::Quit),
+            },
+        }
+    }
+
     fn handle_search_workspace_keys(
         key_event: KeyEvent,
         _state: &mut AppState,
     ) -> Option<AppEvent> {
         match key_event.code {
-            KeyCode::Esc => Some(AppEvent::NewSessionCancel),
             KeyCode::Down => Some(AppEvent::NewSessionNextRepo),
             KeyCode::Up => Some(AppEvent::NewSessionPrevRepo),
             KeyCode::Enter => Some(AppEvent::NewSessionConfirmRepo),
@@ -424,26 +767,38 @@ impl EventHandler {
         if let Some(ref session_state) = state.new_session_state {
             match session_state.step {
                 NewSessionStep::SelectRepo => match key_event.code {
-                    KeyCode::Esc => Some(AppEvent::NewSessionCancel),
                     KeyCode::Down => Some(AppEvent::NewSessionNextRepo),
                     KeyCode::Up => Some(AppEvent::NewSessionPrevRepo),
                     KeyCode::Enter => Some(AppEvent::NewSessionConfirmRepo),
                     _ => None,
                 },
                 NewSessionStep::InputBranch => {
+                    let conflict_session = state
+                        .new_session_state
+                        .as_ref()
+                        .and_then(|s| s.branch_conflict.as_ref())
+                        .map(|conflict| conflict.session_id.is_some());
                     match key_event.code {
-                        KeyCode::Esc => Some(AppEvent::NewSessionCancel),
+                        KeyCode::Tab if conflict_session.is_some() => {
+                            Some(AppEvent::NewSessionBranchAddSuffix)
+                        }
+                        KeyCode::Char('r')
+                            if conflict_session == Some(true)
+                                && key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            Some(AppEvent::NewSessionBranchReuse)
+                        }
                         KeyCode::Enter => {
                             // Check if we're in current directory mode
                             if let Some(ref session_state) = state.new_session_state {
-                                if session_state.is_current_dir_mode {
+                                if session_state.is_current_dir_mode || session_state.review_mode {
                                     // Skip mode selection and permissions for current directory mode
                                     Some(AppEvent::NewSessionCreate)
                                 } else {
-                                    Some(AppEvent::NewSessionProceedToModeSelection)
+                                    Some(AppEvent::NewSessionProceedToBaseBranch)
                                 }
                             } else {
-                                Some(AppEvent::NewSessionProceedToModeSelection)
+                                Some(AppEvent::NewSessionProceedToBaseBranch)
                             }
                         }
                         KeyCode::Backspace => Some(AppEvent::NewSessionBackspace),
@@ -451,10 +806,19 @@ impl EventHandler {
                         _ => None,
                     }
                 }
+                NewSessionStep::SelectBaseBranch => match key_event.code {
+                    KeyCode::Down => Some(AppEvent::NewSessionNextBaseBranch),
+                    KeyCode::Up => Some(AppEvent::NewSessionPrevBaseBranch),
+                    KeyCode::Enter => Some(AppEvent::NewSessionConfirmBaseBranch),
+                    KeyCode::Backspace => Some(AppEvent::NewSessionBackspace),
+                    KeyCode::Char(ch) => Some(AppEvent::NewSessionInputChar(ch)),
+                    _ => None,
+                },
                 NewSessionStep::SelectMode => match key_event.code {
-                    KeyCode::Esc => Some(AppEvent::NewSessionCancel),
                     KeyCode::Enter => Some(AppEvent::NewSessionProceedFromMode),
                     KeyCode::Down | KeyCode::Up => Some(AppEvent::NewSessionToggleMode),
+                    KeyCode::Char('a') => Some(AppEvent::NewSessionCycleAgentType),
+                    KeyCode::Char('m') => Some(AppEvent::NewSessionCycleModel),
                     _ => None,
                 },
                 NewSessionStep::InputPrompt => {
@@ -476,12 +840,6 @@ impl EventHandler {
                     if file_finder_active {
                         // File finder navigation takes precedence
                         match key_event.code {
-                            KeyCode::Esc => {
-                                tracing::debug!(
-                                    "InputPrompt: Escape pressed while file finder active, cancelling file finder"
-                                );
-                                Some(AppEvent::FileFinderCancel)
-                            }
                             KeyCode::Up => {
                                 tracing::debug!("InputPrompt: Up navigation in file finder");
                                 Some(AppEvent::FileFinderNavigateUp)
@@ -515,13 +873,23 @@ impl EventHandler {
                                 None
                             }
                         }
+                    } else if state
+                        .new_session_state
+                        .as_ref()
+                        .is_some_and(|s| s.pending_paste.is_some())
+                    {
+                        // A large paste is waiting on how to insert it
+                        use crate::app::state::PasteChoice;
+                        match key_event.code {
+                            KeyCode::Char('i') => Some(AppEvent::NewSessionResolvePaste(PasteChoice::Inline)),
+                            KeyCode::Char('f') => {
+                                Some(AppEvent::NewSessionResolvePaste(PasteChoice::SaveToFile))
+                            }
+                            _ => None,
+                        }
                     } else {
                         // Normal prompt input handling
                         match key_event.code {
-                            KeyCode::Esc => {
-                                tracing::debug!("InputPrompt: Escape pressed, cancelling session");
-                                Some(AppEvent::NewSessionCancel)
-                            }
                             KeyCode::Enter => {
                                 tracing::debug!(
                                     "InputPrompt: Enter detected, checking prompt validity"
@@ -584,6 +952,20 @@ impl EventHandler {
                                     }
                                 }
                             }
+                            KeyCode::Char('z' | 'Z')
+                                if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                            {
+                                if key_event.modifiers.contains(KeyModifiers::SHIFT) {
+                                    Some(AppEvent::NewSessionRedoPrompt)
+                                } else {
+                                    Some(AppEvent::NewSessionUndoPrompt)
+                                }
+                            }
+                            KeyCode::Char('y')
+                                if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                            {
+                                Some(AppEvent::NewSessionRedoPrompt)
+                            }
                             // Option key combinations for word movement and deletion (must come first)
                             KeyCode::Left if key_event.modifiers.contains(KeyModifiers::ALT) => {
                                 tracing::debug!("InputPrompt: Option+Left - word left");
@@ -643,12 +1025,6 @@ impl EventHandler {
                         key_event.code
                     );
                     match key_event.code {
-                        KeyCode::Esc => {
-                            tracing::debug!(
-                                "ConfigurePermissions: Escape pressed, cancelling session"
-                            );
-                            Some(AppEvent::NewSessionCancel)
-                        }
                         KeyCode::Enter => {
                             tracing::info!(
                                 "ConfigurePermissions: Enter pressed, creating new session"
@@ -661,6 +1037,15 @@ impl EventHandler {
                             );
                             Some(AppEvent::NewSessionTogglePermissions)
                         }
+                        KeyCode::Char('e') => {
+                            Some(AppEvent::NewSessionOpenListStep(NewSessionStep::ConfigureEnv))
+                        }
+                        KeyCode::Char('v') => {
+                            Some(AppEvent::NewSessionOpenListStep(NewSessionStep::ConfigureVolumes))
+                        }
+                        KeyCode::Char('p') => {
+                            Some(AppEvent::NewSessionOpenListStep(NewSessionStep::ConfigurePorts))
+                        }
                         _ => {
                             tracing::debug!(
                                 "ConfigurePermissions: Unhandled key: {:?}",
@@ -670,12 +1055,17 @@ impl EventHandler {
                         }
                     }
                 }
+                NewSessionStep::ConfigureEnv
+                | NewSessionStep::ConfigureVolumes
+                | NewSessionStep::ConfigurePorts => match key_event.code {
+                    KeyCode::Enter => Some(AppEvent::NewSessionListEntrySubmit),
+                    KeyCode::Backspace => Some(AppEvent::NewSessionListEntryBackspace),
+                    KeyCode::Char(ch) => Some(AppEvent::NewSessionListEntryChar(ch)),
+                    _ => None,
+                },
                 NewSessionStep::Creating => {
-                    // During creation, only allow cancellation
-                    match key_event.code {
-                        KeyCode::Esc => Some(AppEvent::NewSessionCancel),
-                        _ => None,
-                    }
+                    // Only Escape is accepted; creation keeps running in the background
+                    None
                 }
             }
         } else {
@@ -688,7 +1078,7 @@ impl EventHandler {
         _state: &mut AppState,
     ) -> Option<AppEvent> {
         match key_event.code {
-            KeyCode::Char('q') | KeyCode::Esc => Some(AppEvent::Quit),
+            KeyCode::Char('q') => Some(AppEvent::Quit),
             KeyCode::Char('s') => Some(AppEvent::SearchWorkspace),
             _ => None,
         }
@@ -700,31 +1090,26 @@ impl EventHandler {
     ) -> Option<AppEvent> {
         match key_event.code {
             KeyCode::Char('d') => Some(AppEvent::DetachSession),
-            KeyCode::Char('q') | KeyCode::Esc => Some(AppEvent::DetachSession),
+            KeyCode::Char('q') => Some(AppEvent::DetachSession),
             KeyCode::Char('k') => Some(AppEvent::KillContainer),
             _ => None, // All other keys are passed through to the terminal
         }
     }
 
     fn handle_claude_chat_keys(key_event: KeyEvent, _state: &mut AppState) -> Option<AppEvent> {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
         match key_event.code {
-            // Escape closes the Claude chat popup
-            KeyCode::Esc => Some(AppEvent::ToggleClaudeChat),
+            KeyCode::Char('r') if ctrl => Some(AppEvent::ClaudeChatResetUsage),
+            KeyCode::Char('b') if ctrl => Some(AppEvent::ClaudeChatRaiseBudget),
+            KeyCode::Char('n') if ctrl => Some(AppEvent::ClaudeChatNewConversation),
+            KeyCode::Char('c') if ctrl => Some(AppEvent::ClaudeChatCancel),
+            KeyCode::Char('e') if ctrl => Some(AppEvent::ClaudeChatExport),
             // Enter sends the message
-            KeyCode::Enter => {
-                // TODO: Add send message event
-                None
-            }
+            KeyCode::Enter => Some(AppEvent::ClaudeChatSend),
             // Backspace for editing input
-            KeyCode::Backspace => {
-                // TODO: Add backspace handling
-                None
-            }
+            KeyCode::Backspace => Some(AppEvent::ClaudeChatBackspace),
             // All other characters are input to the chat
-            KeyCode::Char(_ch) => {
-                // TODO: Add character input handling
-                None
-            }
+            KeyCode::Char(ch) => Some(AppEvent::ClaudeChatInputChar(ch)),
             _ => None,
         }
     }
@@ -738,14 +1123,13 @@ impl EventHandler {
                 match key_event.code {
                     KeyCode::Enter => Some(AppEvent::AuthSetupSelect),
                     KeyCode::Backspace => Some(AppEvent::AuthSetupBackspace),
-                    KeyCode::Esc => Some(AppEvent::AuthSetupBackspace), // Clear input
+                    KeyCode::Tab => Some(AppEvent::AuthSetupToggleCheck),
                     KeyCode::Char(ch) => Some(AppEvent::AuthSetupInputChar(ch)),
                     _ => None,
                 }
             } else {
                 // Method selection mode or waiting for auth completion
                 match key_event.code {
-                    KeyCode::Esc => Some(AppEvent::AuthSetupCancel),
                     KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::AuthSetupPrevious),
                     KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::AuthSetupNext),
                     KeyCode::Enter => Some(AppEvent::AuthSetupSelect),
@@ -773,7 +1157,6 @@ impl EventHandler {
         if in_commit_mode {
             // Handle commit message input
             match key_event.code {
-                KeyCode::Esc => Some(AppEvent::GitViewCommitCancel),
                 KeyCode::Enter => Some(AppEvent::GitViewCommitConfirm),
                 KeyCode::Backspace => Some(AppEvent::GitViewCommitBackspace),
                 KeyCode::Left => Some(AppEvent::GitViewCommitCursorLeft),
@@ -784,7 +1167,6 @@ impl EventHandler {
         } else {
             // Normal git view navigation
             match key_event.code {
-                KeyCode::Esc => Some(AppEvent::GitViewBack),
                 KeyCode::Tab => Some(AppEvent::GitViewSwitchTab),
                 KeyCode::Char('j') | KeyCode::Down => {
                     if let Some(ref git_state) = state.git_view_state {
@@ -798,6 +1180,12 @@ impl EventHandler {
                             crate::components::git_view::GitTab::Markdown => {
                                 Some(AppEvent::GitViewScrollDown)
                             }
+                            crate::components::git_view::GitTab::Touched => {
+                                Some(AppEvent::GitViewNextTouchedFile)
+                            }
+                            crate::components::git_view::GitTab::Commits => {
+                                Some(AppEvent::GitViewScrollDown)
+                            }
                         }
                     } else {
                         None
@@ -815,18 +1203,28 @@ impl EventHandler {
                             crate::components::git_view::GitTab::Markdown => {
                                 Some(AppEvent::GitViewScrollUp)
                             }
+                            crate::components::git_view::GitTab::Touched => {
+                                Some(AppEvent::GitViewPrevTouchedFile)
+                            }
+                            crate::components::git_view::GitTab::Commits => {
+                                Some(AppEvent::GitViewScrollUp)
+                            }
                         }
                     } else {
                         None
                     }
                 }
                 KeyCode::Enter => {
-                    // Toggle folder on Enter key in Files tab
+                    // Toggle folder in Files tab, open the diff in Touched tab
                     if let Some(ref git_state) = state.git_view_state {
-                        if git_state.active_tab == crate::components::git_view::GitTab::Files {
-                            Some(AppEvent::GitViewToggleFolder)
-                        } else {
-                            None
+                        match git_state.active_tab {
+                            crate::components::git_view::GitTab::Files => {
+                                Some(AppEvent::GitViewToggleFolder)
+                            }
+                            crate::components::git_view::GitTab::Touched => {
+                                Some(AppEvent::GitViewOpenTouchedFile)
+                            }
+                            _ => None,
                         }
                     } else {
                         None
@@ -856,7 +1254,63 @@ impl EventHandler {
                         None
                     }
                 }
+                KeyCode::Char(' ') => {
+                    if state.git_view_state.as_ref().is_some_and(|g| {
+                        g.active_tab == crate::components::git_view::GitTab::Files && !g.review_only
+                    }) {
+                        Some(AppEvent::GitViewToggleFileSelection)
+                    } else {
+                        None
+                    }
+                }
+                KeyCode::Char('w') => {
+                    if state.git_view_state.as_ref().is_some_and(|g| {
+                        g.active_tab == crate::components::git_view::GitTab::Diff
+                    }) {
+                        Some(AppEvent::GitViewToggleWordDiff)
+                    } else {
+                        None
+                    }
+                }
+                KeyCode::Char('b') => {
+                    if state.git_view_state.as_ref().is_some_and(|g| {
+                        g.active_tab == crate::components::git_view::GitTab::Diff
+                    }) {
+                        Some(AppEvent::GitViewToggleBlame)
+                    } else {
+                        None
+                    }
+                }
+                KeyCode::Char('c') => {
+                    if state.git_view_state.as_ref().is_some_and(|g| !g.merge_conflicts.is_empty()) {
+                        Some(AppEvent::GitViewToggleConflicts)
+                    } else {
+                        None
+                    }
+                }
+                KeyCode::Char('s') => Some(AppEvent::GitViewStash),
+                KeyCode::Char('u') => Some(AppEvent::GitViewPopStash),
+                KeyCode::Char('y') => Some(AppEvent::GitViewCopyDiff(true)),
+                KeyCode::Char('Y') => Some(AppEvent::GitViewCopyDiff(false)),
+                KeyCode::Char('d') => {
+                    if state.git_view_state.as_ref().is_some_and(|g| {
+                        g.active_tab == crate::components::git_view::GitTab::Files && !g.review_only
+                    }) {
+                        Some(AppEvent::GitViewDiscard(false))
+                    } else {
+                        None
+                    }
+                }
+                KeyCode::Char('D') => {
+                    if state.git_view_state.as_ref().is_some_and(|g| g.review_only) {
+                        return None;
+                    }
+                    Some(AppEvent::GitViewDiscard(true))
+                }
                 KeyCode::Char('p') => {
+                    if state.git_view_state.as_ref().is_some_and(|g| g.review_only) {
+                        return None;
+                    }
                     tracing::info!("Git view 'p' key pressed - starting commit");
                     Some(AppEvent::GitViewStartCommit)
                 }
@@ -867,9 +1321,23 @@ impl EventHandler {
 
     pub fn process_event(event: AppEvent, state: &mut AppState) {
         match event {
-            AppEvent::Quit => state.quit(),
+            AppEvent::Quit => state.request_quit(),
+            AppEvent::ForceQuit => state.quit(),
             AppEvent::ToggleHelp => state.toggle_help(),
             AppEvent::ToggleClaudeChat => state.toggle_claude_chat(),
+            AppEvent::ClaudeChatInputChar(ch) => state.claude_chat_input_char(ch),
+            AppEvent::ClaudeChatBackspace => state.claude_chat_backspace(),
+            AppEvent::ClaudeChatSend => state.submit_claude_message(),
+            AppEvent::ClaudeChatResetUsage => state.reset_chat_token_usage(),
+            AppEvent::ClaudeChatRaiseBudget => state.raise_chat_token_budget(),
+            AppEvent::ClaudeChatNewConversation => state.new_claude_conversation(),
+            AppEvent::ClaudeChatCancel => state.cancel_streaming(),
+            AppEvent::ClaudeChatExport => state.export_claude_chat(),
+            AppEvent::ReturnToSessionList => {
+                state.current_view = View::SessionList;
+                state.dashboard = None;
+                state.ui_needs_refresh = true;
+            }
             AppEvent::ToggleExpandAll => state.toggle_expand_all_workspaces(),
             AppEvent::RefreshWorkspaces => {
                 // Mark for async processing to reload workspace data
@@ -897,6 +1365,9 @@ impl EventHandler {
                 // Mark for async processing - create normal new session with mode selection
                 state.pending_async_action = Some(AsyncAction::NewSessionNormal);
             }
+            AppEvent::NewReviewSession => {
+                state.pending_async_action = Some(AsyncAction::NewReviewSession);
+            }
             AppEvent::SearchWorkspace => {
                 // Don't overwrite pending DeleteSession actions
                 if let Some(AsyncAction::DeleteSession(_)) = state.pending_async_action {
@@ -925,6 +1396,21 @@ impl EventHandler {
                 tracing::debug!("Event: NewSessionBackspace");
                 state.new_session_backspace();
             }
+            AppEvent::NewSessionProceedToBaseBranch => {
+                tracing::info!("Event: NewSessionProceedToBaseBranch");
+                state.new_session_proceed_to_base_branch();
+            }
+            AppEvent::NewSessionBranchAddSuffix => state.new_session_branch_add_suffix(),
+            AppEvent::NewSessionBranchReuse => {
+                tracing::info!("Event: NewSessionBranchReuse");
+                state.new_session_branch_reuse();
+            }
+            AppEvent::NewSessionNextBaseBranch => state.new_session_next_base_branch(),
+            AppEvent::NewSessionPrevBaseBranch => state.new_session_prev_base_branch(),
+            AppEvent::NewSessionConfirmBaseBranch => {
+                tracing::info!("Event: NewSessionConfirmBaseBranch");
+                state.new_session_confirm_base_branch();
+            }
             AppEvent::NewSessionProceedToModeSelection => {
                 tracing::info!("Event: NewSessionProceedToModeSelection");
                 state.new_session_proceed_to_mode_selection();
@@ -933,6 +1419,8 @@ impl EventHandler {
                 tracing::info!("Event: NewSessionToggleMode");
                 state.new_session_toggle_mode();
             }
+            AppEvent::NewSessionCycleAgentType => state.new_session_cycle_agent_type(),
+            AppEvent::NewSessionCycleModel => state.new_session_cycle_model(),
             AppEvent::NewSessionProceedFromMode => {
                 tracing::info!("Event: NewSessionProceedFromMode");
                 state.new_session_proceed_from_mode();
@@ -941,6 +1429,7 @@ impl EventHandler {
             AppEvent::NewSessionBackspacePrompt => state.new_session_backspace_prompt(),
             AppEvent::NewSessionInsertNewline => state.new_session_insert_newline(),
             AppEvent::NewSessionPasteText(text) => state.new_session_paste_text(text),
+            AppEvent::NewSessionResolvePaste(choice) => state.new_session_resolve_paste(choice),
             AppEvent::NewSessionCursorLeft => state.new_session_move_cursor_left(),
             AppEvent::NewSessionCursorRight => state.new_session_move_cursor_right(),
             AppEvent::NewSessionCursorUp => state.new_session_move_cursor_up(),
@@ -952,11 +1441,18 @@ impl EventHandler {
             AppEvent::NewSessionCursorWordRight => state.new_session_move_cursor_word_right(),
             AppEvent::NewSessionDeleteWordForward => state.new_session_delete_word_forward(),
             AppEvent::NewSessionDeleteWordBackward => state.new_session_delete_word_backward(),
+            AppEvent::NewSessionUndoPrompt => state.new_session_undo_prompt(false),
+            AppEvent::NewSessionRedoPrompt => state.new_session_undo_prompt(true),
             AppEvent::NewSessionProceedToPermissions => {
                 tracing::info!("Processing NewSessionProceedToPermissions event");
                 state.new_session_proceed_to_permissions();
             }
             AppEvent::NewSessionTogglePermissions => state.new_session_toggle_permissions(),
+            AppEvent::NewSessionOpenListStep(step) => state.new_session_open_list_step(step),
+            AppEvent::NewSessionListEntryChar(ch) => state.new_session_list_entry_char(ch),
+            AppEvent::NewSessionListEntryBackspace => state.new_session_list_entry_backspace(),
+            AppEvent::NewSessionListEntrySubmit => state.new_session_list_entry_submit(),
+            AppEvent::NewSessionFinishListStep => state.new_session_finish_list_step(),
             AppEvent::NewSessionCreate => {
                 tracing::info!("Processing NewSessionCreate event - queueing async action");
                 // Mark for async processing
@@ -1072,6 +1568,26 @@ impl EventHandler {
                     state.show_delete_confirmation(session.id);
                 }
             }
+            AppEvent::StopKeepWorktree => {
+                if let Some(session_id) = state.selected_session().map(|s| s.id) {
+                    state.show_stop_keep_worktree_confirmation(session_id);
+                }
+            }
+            AppEvent::StopSession => {
+                if let Some(session_id) = state.selected_session().map(|s| s.id) {
+                    state.pending_async_action = Some(AsyncAction::StopSession(session_id));
+                }
+            }
+            AppEvent::ToggleSessionProtection => state.toggle_session_protection(),
+            AppEvent::CycleSessionSort => state.cycle_session_sort(),
+            AppEvent::StartSessionFilter => state.start_session_filter(),
+            AppEvent::SessionFilterInputChar(ch) => state.session_filter_input_char(ch),
+            AppEvent::SessionFilterBackspace => state.session_filter_backspace(),
+            AppEvent::ConfirmSessionFilter => state.confirm_session_filter(),
+            AppEvent::ClearSessionFilter => state.clear_session_filter(),
+            AppEvent::WidenSessionList => state.resize_session_list(true),
+            AppEvent::NarrowSessionList => state.resize_session_list(false),
+            AppEvent::CleanupDeadSessions => state.show_cleanup_dead_sessions_confirmation(),
             AppEvent::CleanupOrphaned => {
                 // Queue cleanup of orphaned containers
                 state.pending_async_action = Some(AsyncAction::CleanupOrphaned);
@@ -1082,6 +1598,22 @@ impl EventHandler {
             AppEvent::SwitchToTerminal => {
                 // TODO: Implement terminal view
             }
+            AppEvent::MouseClick(target) => {
+                use crate::app::state::FocusedPane;
+                use crate::components::layout::ClickTarget;
+                match target {
+                    ClickTarget::SessionList(row) => {
+                        state.focused_pane = FocusedPane::Sessions;
+                        if let Some(row) = row {
+                            state.select_list_row(row);
+                        }
+                    }
+                    // Jumping to the newest lines is handled in main.rs, which has the component
+                    ClickTarget::Logs | ClickTarget::NewLogLines => {
+                        state.focused_pane = FocusedPane::LiveLogs;
+                    }
+                }
+            }
             AppEvent::SwitchPaneFocus => {
                 use crate::app::state::FocusedPane;
                 let old_pane = state.focused_pane.clone();
@@ -1107,7 +1639,23 @@ impl EventHandler {
             AppEvent::ScrollLogsToBottom => {
                 // Handled in main.rs to access layout component
             }
-            AppEvent::ToggleAutoScroll => {
+            AppEvent::ToggleAutoScroll
+            | AppEvent::ToggleLogWrap
+            | AppEvent::ScrollLogsLeft
+            | AppEvent::ScrollLogsRight => {
+                // Handled in main.rs to access layout component
+            }
+            AppEvent::ToggleLogTimestamps => {
+                state.show_log_timestamps = !state.show_log_timestamps;
+            }
+            AppEvent::ToggleLogAnsiColors => {
+                state.log_ansi_colors = !state.log_ansi_colors;
+            }
+            AppEvent::ToggleLogBookmark
+            | AppEvent::NextLogBookmark
+            | AppEvent::PreviousLogBookmark
+            | AppEvent::CopyToolCommand
+            | AppEvent::StartLogFilter => {
                 // Handled in main.rs to access layout component
             }
             AppEvent::ConfirmationToggle => {
@@ -1124,10 +1672,36 @@ impl EventHandler {
                                 state.pending_async_action =
                                     Some(AsyncAction::DeleteSession(session_id));
                             }
+                            crate::app::state::ConfirmAction::StopKeepWorktree(session_id) => {
+                                state.pending_async_action =
+                                    Some(AsyncAction::StopKeepWorktree(session_id));
+                            }
+                            crate::app::state::ConfirmAction::CleanupDeadSessions(session_ids) => {
+                                state.pending_async_action =
+                                    Some(AsyncAction::CleanupDeadSessions(session_ids));
+                            }
+                            crate::app::state::ConfirmAction::CommitAllChanges => {
+                                state.git_commit_and_push();
+                            }
+                            crate::app::state::ConfirmAction::DiscardChanges(paths) => {
+                                state.git_discard_changes(paths);
+                            }
                             crate::app::state::ConfirmAction::KillOtherTmux(session_name) => {
                                 state.pending_async_action =
                                     Some(AsyncAction::KillOtherTmux(session_name));
                             }
+                            crate::app::state::ConfirmAction::BuildAgentsDevImage(then) => {
+                                state.pending_async_action =
+                                    Some(AsyncAction::BuildAgentsDevImage(then));
+                            }
+                            crate::app::state::ConfirmAction::Logout => {
+                                state.pending_async_action = Some(AsyncAction::Logout);
+                            }
+                            crate::app::state::ConfirmAction::RestartSession(session_id) => {
+                                state.pending_async_action =
+                                    Some(AsyncAction::RestartSession(session_id));
+                            }
+                            crate::app::state::ConfirmAction::QuitApp => state.quit(),
                         }
                     }
                     // If not confirmed, just close the dialog
@@ -1169,7 +1743,11 @@ impl EventHandler {
                                     auth_state.show_cursor = true;
                                 }
                             } else {
-                                // Save the API key
+                                // Save the API key, checking it online unless bypassed
+                                if let Some(ref mut auth_state) = state.auth_setup_state {
+                                    auth_state.validating = !auth_state.skip_validation;
+                                    auth_state.error_message = None;
+                                }
                                 state.pending_async_action = Some(AsyncAction::AuthSetupApiKey);
                             }
                         }
@@ -1190,6 +1768,11 @@ impl EventHandler {
                 state.check_current_directory_status();
                 state.pending_async_action = Some(AsyncAction::RefreshWorkspaces);
             }
+            AppEvent::AuthSetupToggleCheck => {
+                if let Some(ref mut auth_state) = state.auth_setup_state {
+                    auth_state.skip_validation = !auth_state.skip_validation;
+                }
+            }
             AppEvent::AuthSetupInputChar(ch) => {
                 if let Some(ref mut auth_state) = state.auth_setup_state {
                     auth_state.api_key_input.push(ch);
@@ -1259,23 +1842,16 @@ impl EventHandler {
             AppEvent::FileFinderSelectFile => {
                 if let Some(ref mut session_state) = state.new_session_state {
                     if let Some(selected_file) = session_state.file_finder.get_selected_file() {
-                        // Replace @query with the selected file path
-                        let file_path = &selected_file.relative_path;
-                        let at_pos = session_state.file_finder.at_symbol_position;
-                        let query_end_pos = at_pos + 1 + session_state.file_finder.query.len();
-
-                        // Construct new prompt by replacing @query with file path
-                        let current_text = session_state.boss_prompt.to_string();
-                        let mut new_prompt =
-                            String::with_capacity(current_text.len() + file_path.len());
-                        new_prompt.push_str(&current_text[..at_pos]);
-                        new_prompt.push_str(file_path);
-                        if query_end_pos < current_text.len() {
-                            new_prompt.push_str(&current_text[query_end_pos..]);
-                        }
-
-                        session_state.boss_prompt =
-                            crate::app::state::TextEditor::from_string(&new_prompt);
+                        use crate::components::fuzzy_file_finder::insert_reference;
+                        // The query is typed into the finder, so only the @ is in the prompt
+                        let (prompt, cursor) = insert_reference(
+                            &session_state.boss_prompt.to_string(),
+                            session_state.file_finder.at_symbol_position,
+                            &selected_file.relative_path,
+                        );
+                        session_state.boss_prompt.set_text(&prompt);
+                        session_state.boss_prompt.set_cursor_offset(cursor);
+                        // The file list stays cached, so the next @ opens the finder at once
                         session_state.file_finder.deactivate();
                     }
                 }
@@ -1286,6 +1862,34 @@ impl EventHandler {
                 }
             }
             // Git view events
+            AppEvent::ShowDashboard => state.show_dashboard(),
+            AppEvent::DashboardNext => {
+                if let Some(ref mut dashboard) = state.dashboard {
+                    dashboard.next();
+                }
+            }
+            AppEvent::DashboardPrevious => {
+                if let Some(ref mut dashboard) = state.dashboard {
+                    dashboard.previous();
+                }
+            }
+            AppEvent::DashboardOpenSelected => state.open_dashboard_selection(),
+            AppEvent::DashboardRefresh => state.refresh_dashboard(),
+            AppEvent::ShowLogsViewer => state.show_logs_viewer(),
+            AppEvent::LogsViewerStartSearch
+            | AppEvent::LogsViewerNextMatch
+            | AppEvent::LogsViewerPreviousMatch
+            | AppEvent::LogsViewerToggleCase
+            | AppEvent::LogsViewerScrollUp
+            | AppEvent::LogsViewerScrollDown
+            | AppEvent::LogsViewerToggleWrap
+            | AppEvent::LogsViewerScrollLeft
+            | AppEvent::LogsViewerScrollRight
+            | AppEvent::LogsViewerToggleBookmark
+            | AppEvent::LogsViewerNextBookmark
+            | AppEvent::LogsViewerPreviousBookmark => {
+                // Handled in main.rs to access layout component
+            }
             AppEvent::ShowGitView => {
                 tracing::info!("Showing git view");
                 state.show_git_view();
@@ -1295,6 +1899,53 @@ impl EventHandler {
                     state.git_view_state.is_some()
                 );
             }
+            AppEvent::OpenExternalDiff => state.open_external_diff(),
+            AppEvent::CompareWithLastRun => state.compare_with_last_run(),
+            AppEvent::RunCompareScrollUp => {
+                if let Some(ref mut compare) = state.run_compare {
+                    compare.scroll_up();
+                }
+            }
+            AppEvent::RunCompareScrollDown => {
+                if let Some(ref mut compare) = state.run_compare {
+                    compare.scroll_down();
+                }
+            }
+            AppEvent::RunCompareClose => state.run_compare = None,
+            AppEvent::ShowBossPrompt => state.show_boss_prompt(),
+            AppEvent::CopyContainerId => state.copy_selected_container_id(),
+            AppEvent::CopyWorktreePath => state.copy_selected_worktree_path(),
+            AppEvent::PromptViewerScrollUp => {
+                if let Some(ref mut viewer) = state.prompt_viewer {
+                    viewer.scroll_up();
+                }
+            }
+            AppEvent::PromptViewerScrollDown => {
+                if let Some(ref mut viewer) = state.prompt_viewer {
+                    viewer.scroll_down();
+                }
+            }
+            AppEvent::PromptViewerCopy => state.copy_prompt_viewer_text(),
+            AppEvent::PromptViewerToggleFileContents => {
+                if let Some(ref mut viewer) = state.prompt_viewer {
+                    viewer.toggle_file_contents();
+                }
+            }
+            AppEvent::PromptViewerClose => state.prompt_viewer = None,
+            AppEvent::ShowNotificationHistory => state.show_notification_history(),
+            AppEvent::NotificationHistoryScrollUp => state.scroll_notification_history(-1),
+            AppEvent::NotificationHistoryScrollDown => state.scroll_notification_history(1),
+            AppEvent::NotificationHistoryClear => state.clear_notification_history(),
+            AppEvent::NotificationHistoryClose => state.notification_history_scroll = None,
+            AppEvent::DismissNotification => {
+                state.dismiss_sticky_notification();
+            }
+            AppEvent::ShowAuthProfiles => state.show_auth_profile_picker(),
+            AppEvent::AuthProfileUp => state.move_auth_profile_selection(-1),
+            AppEvent::AuthProfileDown => state.move_auth_profile_selection(1),
+            AppEvent::AuthProfileSelect => state.switch_auth_profile(),
+            AppEvent::AuthProfileClose => state.auth_profile_picker = None,
+            AppEvent::Logout => state.show_logout_confirmation(),
             AppEvent::GitViewSwitchTab => {
                 if let Some(ref mut git_state) = state.git_view_state {
                     git_state.switch_tab();
@@ -1315,6 +1966,7 @@ impl EventHandler {
                     match git_state.active_tab {
                         crate::components::git_view::GitTab::Diff => git_state.scroll_diff_up(),
                         crate::components::git_view::GitTab::Markdown => git_state.scroll_markdown_up(),
+                        crate::components::git_view::GitTab::Commits => git_state.scroll_commits_up(),
                         _ => {}
                     }
                 }
@@ -1324,6 +1976,7 @@ impl EventHandler {
                     match git_state.active_tab {
                         crate::components::git_view::GitTab::Diff => git_state.scroll_diff_down(),
                         crate::components::git_view::GitTab::Markdown => git_state.scroll_markdown_down(),
+                        crate::components::git_view::GitTab::Commits => git_state.scroll_commits_down(),
                         _ => {}
                     }
                 }
@@ -1343,6 +1996,66 @@ impl EventHandler {
                     git_state.collapse_all_folders();
                 }
             }
+            AppEvent::GitViewNextTouchedFile => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    git_state.next_touched_file();
+                }
+            }
+            AppEvent::GitViewPrevTouchedFile => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    git_state.previous_touched_file();
+                }
+            }
+            AppEvent::GitViewOpenTouchedFile => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    if let Err(e) = git_state.open_selected_touched_file() {
+                        tracing::error!("Failed to open touched file diff: {}", e);
+                        state.add_error_notification(format!("❌ Failed to load diff: {}", e));
+                    }
+                }
+            }
+            AppEvent::GitViewStash => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    match git_state.stash_changes() {
+                        Ok(message) => state.add_success_notification(format!("📦 {}", message)),
+                        Err(e) => {
+                            state.add_error_notification(format!("❌ Stash failed: {}", e));
+                        }
+                    }
+                }
+            }
+            AppEvent::GitViewPopStash => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    match git_state.pop_stash() {
+                        Ok(message) => state.add_success_notification(format!("📦 {}", message)),
+                        Err(e) => {
+                            state.add_error_notification(format!("❌ Unstash failed: {}", e));
+                        }
+                    }
+                }
+            }
+            AppEvent::GitViewCopyDiff(include_staged) => state.copy_git_diff(include_staged),
+            AppEvent::GitViewDiscard(all) => state.confirm_git_discard(all),
+            AppEvent::GitViewToggleFileSelection => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    git_state.toggle_file_selection();
+                }
+            }
+            AppEvent::GitViewToggleWordDiff => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    git_state.toggle_word_diff();
+                }
+            }
+            AppEvent::GitViewToggleBlame => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    git_state.toggle_blame();
+                }
+            }
+            AppEvent::GitViewToggleConflicts => {
+                if let Some(ref mut git_state) = state.git_view_state {
+                    git_state.toggle_merge_conflicts();
+                }
+            }
             AppEvent::GitViewCommitPush => {
                 state.git_commit_and_push();
             }
@@ -1353,7 +2066,11 @@ impl EventHandler {
             // Commit message input events
             AppEvent::GitViewStartCommit => {
                 tracing::info!("Processing GitViewStartCommit event");
-                if let Some(ref mut git_state) = state.git_view_state {
+                if state.git_view_state.as_ref().is_some_and(|g| g.review_only) {
+                    state.add_warning_notification(
+                        "⚠️ Review sessions are read-only - commit & push is disabled".to_string(),
+                    );
+                } else if let Some(ref mut git_state) = state.git_view_state {
                     tracing::info!("Git state found, starting commit message input");
                     git_state.start_commit_message_input();
                     state.add_info_notification(
@@ -1389,7 +2106,7 @@ impl EventHandler {
                 }
             }
             AppEvent::GitViewCommitConfirm => {
-                state.git_commit_and_push();
+                state.confirm_git_commit();
             }
             AppEvent::GitCommitAndPush => {
                 tracing::info!("Direct git commit and push from main view");
@@ -1417,6 +2134,12 @@ impl EventHandler {
             AppEvent::QuickCommitCancel => {
                 state.cancel_quick_commit();
             }
+            AppEvent::StartLogExport => state.start_log_export(),
+            AppEvent::LogExportInputChar(ch) => state.log_export_input_char(ch),
+            AppEvent::LogExportBackspace => state.log_export_backspace(),
+            AppEvent::LogExportToggleRaw => state.toggle_log_export_raw(),
+            AppEvent::LogExportConfirm => state.confirm_log_export(),
+            AppEvent::LogExportCancel => state.cancel_log_export(),
             AppEvent::GitCommitSuccess(message) => {
                 tracing::info!("Git commit successful: {}", message);
                 // Add success notification
@@ -1427,7 +2150,6 @@ impl EventHandler {
                 tracing::info!("Returned to session list after successful commit");
             }
             // Mouse events are handled directly in the main event loop
-            AppEvent::MouseClick { .. } |
             AppEvent::MouseDragStart { .. } |
             AppEvent::MouseDragEnd { .. } |
             AppEvent::MouseDragging { .. } => {
@@ -1436,3 +2158,215 @@ impl EventHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{ConfirmAction, NewSessionState};
+    use crate::components::git_view::GitViewState;
+    use crate::config::EscapePolicy;
+
+    /// Press Escape and apply the resulting event, as the main loop would
+    fn press_escape(state: &mut AppState) {
+        let key_event = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        if let Some(event) = EventHandler::handle_key_event(key_event, state) {
+            EventHandler::process_event(event, state);
+        }
+    }
+
+    #[test]
+    fn test_session_list_keys_follow_the_keymap() {
+        let press = |state: &mut AppState, ch: char| {
+            EventHandler::handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE), state)
+        };
+        let mut state = AppState::default();
+        assert!(matches!(press(&mut state, 'n'), Some(AppEvent::NewSession)));
+
+        state.keymap = crate::config::Keymap::from_toml("new_session = \"N\"").unwrap();
+        assert!(press(&mut state, 'n').is_none());
+        assert!(matches!(press(&mut state, 'N'), Some(AppEvent::NewSession)));
+        // Pane-specific keys stay built in
+        assert!(matches!(press(&mut state, 'f'), Some(AppEvent::RefreshWorkspaces)));
+    }
+
+    #[test]
+    fn test_escape_on_session_list_never_quits_by_default() {
+        let mut state = AppState::default();
+        press_escape(&mut state);
+        assert_eq!(state.current_view, View::SessionList);
+        assert!(!state.should_quit);
+
+        state.escape_policy = EscapePolicy::BackOrQuit;
+        press_escape(&mut state);
+        assert!(state.should_quit);
+    }
+
+    #[test]
+    fn test_quit_is_deferred_while_sessions_run() {
+        let mut state = AppState::default();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        let mut running = crate::models::Session::new("feature".to_string(), String::new());
+        running.set_status(crate::models::SessionStatus::Running);
+        workspace.add_session(running);
+        workspace.add_session(crate::models::Session::new("old".to_string(), String::new()));
+        state.workspaces.push(workspace);
+
+        EventHandler::process_event(AppEvent::Quit, &mut state);
+        assert!(!state.should_quit);
+        let dialog = state.confirmation_dialog.as_ref().unwrap();
+        assert!(dialog.message.starts_with("1 session is running. Quit anyway?"));
+        assert!(dialog.message.contains("keep running"));
+        assert!(matches!(dialog.confirm_action, ConfirmAction::QuitApp));
+
+        // Declining keeps the app open; confirming quits
+        EventHandler::process_event(AppEvent::ConfirmationConfirm, &mut state);
+        assert!(!state.should_quit);
+        EventHandler::process_event(AppEvent::Quit, &mut state);
+        EventHandler::process_event(AppEvent::ConfirmationToggle, &mut state);
+        EventHandler::process_event(AppEvent::ConfirmationConfirm, &mut state);
+        assert!(state.should_quit);
+
+        // The dialog says when exiting will stop the sessions
+        state.should_quit = false;
+        state.stop_sessions_on_exit = true;
+        EventHandler::process_event(AppEvent::Quit, &mut state);
+        let dialog = state.confirmation_dialog.take().unwrap();
+        assert!(dialog.message.ends_with("stopped first."));
+
+        // Protected sessions are left running, as in other bulk stops
+        let running_id = state.workspaces[0].sessions[0].id;
+        state.protected_sessions.insert(running_id);
+        EventHandler::process_event(AppEvent::Quit, &mut state);
+        let dialog = state.confirmation_dialog.take().unwrap();
+        assert!(dialog.message.ends_with("stopped first, except 1 protected."));
+
+        // Ctrl+C still quits straight away
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let event = EventHandler::handle_key_event(ctrl_c, &mut state).unwrap();
+        EventHandler::process_event(event, &mut state);
+        assert!(state.should_quit);
+    }
+
+    #[test]
+    fn test_quit_proceeds_without_running_sessions() {
+        let mut state = AppState::default();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        workspace.add_session(crate::models::Session::new("old".to_string(), String::new()));
+        state.workspaces.push(workspace);
+
+        EventHandler::process_event(AppEvent::Quit, &mut state);
+        assert!(state.should_quit);
+        assert!(state.confirmation_dialog.is_none());
+    }
+
+    #[test]
+    fn test_escape_closes_overlays_topmost_first() {
+        let mut state = AppState::default();
+        state.help_visible = true;
+        state.show_delete_confirmation(uuid::Uuid::new_v4());
+
+        press_escape(&mut state);
+        assert!(state.confirmation_dialog.is_none());
+        assert!(state.help_visible);
+
+        press_escape(&mut state);
+        assert!(!state.help_visible);
+        assert_eq!(state.current_view, View::SessionList);
+
+        state.quick_commit_message = Some("wip".to_string());
+        press_escape(&mut state);
+        assert!(!state.is_in_quick_commit_mode());
+
+        state.prompt_viewer = Some(crate::components::PromptViewerState::new(
+            "session".to_string(),
+            "prompt".to_string(),
+            std::path::PathBuf::from("/tmp"),
+        ));
+        press_escape(&mut state);
+        assert!(state.prompt_viewer.is_none());
+        assert!(!state.should_quit);
+    }
+
+    #[test]
+    fn test_escape_from_new_session_closes_file_finder_then_wizard() {
+        let mut state = AppState::default();
+        let mut session_state = NewSessionState::default();
+        session_state.file_finder.is_active = true;
+        state.new_session_state = Some(session_state);
+        state.current_view = View::NewSession;
+
+        press_escape(&mut state);
+        assert_eq!(state.current_view, View::NewSession);
+        assert!(!state.new_session_state.as_ref().unwrap().file_finder.is_active);
+
+        press_escape(&mut state);
+        assert_eq!(state.current_view, View::SessionList);
+        assert!(state.new_session_state.is_none());
+
+        state.new_session_state = Some(NewSessionState::default());
+        state.current_view = View::SearchWorkspace;
+        press_escape(&mut state);
+        assert_eq!(state.current_view, View::SessionList);
+    }
+
+    #[test]
+    fn test_escape_from_git_view_cancels_commit_then_returns() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut state = AppState::default();
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+        git_state.start_commit_message_input();
+        state.git_view_state = Some(git_state);
+        state.current_view = View::GitView;
+
+        press_escape(&mut state);
+        assert_eq!(state.current_view, View::GitView);
+        assert!(!state.git_view_state.as_ref().unwrap().is_in_commit_mode());
+
+        press_escape(&mut state);
+        assert_eq!(state.current_view, View::SessionList);
+        assert!(state.git_view_state.is_none());
+    }
+
+    #[test]
+    fn test_escape_from_full_screen_views_returns_to_session_list() {
+        for view in [
+            View::ClaudeChat,
+            View::AttachedTerminal,
+            View::NonGitNotification,
+            View::Logs,
+            View::Terminal,
+        ] {
+            let mut state = AppState::default();
+            state.current_view = view.clone();
+            press_escape(&mut state);
+            assert_eq!(state.current_view, View::SessionList, "Escape from {:?}", view);
+            assert!(!state.should_quit, "Escape from {:?} quit the app", view);
+        }
+    }
+
+    #[test]
+    fn test_escape_from_auth_setup_skips_to_main_screen() {
+        let mut state = AppState::default();
+        state.auth_setup_state = Some(crate::app::state::AuthSetupState {
+            selected_method: AuthMethod::ApiKey,
+            api_key_input: "sk-partial".to_string(),
+            validating: false,
+            skip_validation: false,
+            is_processing: false,
+            error_message: None,
+            show_cursor: true,
+        });
+        state.current_view = View::AuthSetup;
+
+        press_escape(&mut state);
+        assert!(state.auth_setup_state.is_none());
+        // Outside a git repository the main screen is the non-git notice
+        assert!(matches!(
+            state.current_view,
+            View::SessionList | View::NonGitNotification
+        ));
+        assert!(!state.should_quit);
+    }
+}
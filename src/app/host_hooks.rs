@@ -0,0 +1,91 @@
+// ABOUTME: Host-side command run after a session is created (open an IDE, start an indexer, ...)
+// It runs arbitrary shell commands on the host, so it only runs when the user's config sets one
+
+use std::path::Path;
+use std::process::Stdio;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+pub(crate) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Fill `{worktree}`, `{branch}` and `{session_id}` with shell-quoted values
+pub fn host_command_line(
+    template: &str,
+    worktree: &Path,
+    branch: &str,
+    session_id: Uuid,
+) -> String {
+    template
+        .replace("{worktree}", &shell_quote(&worktree.display().to_string()))
+        .replace("{branch}", &shell_quote(branch))
+        .replace("{session_id}", &shell_quote(&session_id.to_string()))
+}
+
+/// Run the command in the background from the worktree; its outcome is only logged
+pub fn spawn_on_create_host_command(
+    template: &str,
+    worktree: &Path,
+    branch: &str,
+    session_id: Uuid,
+) {
+    let command_line = host_command_line(template, worktree, branch, session_id);
+    let worktree = worktree.to_path_buf();
+    info!(
+        "Running on_create_host_command for session {}: {}",
+        session_id, command_line
+    );
+
+    tokio::spawn(async move {
+        let output = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command_line)
+            .current_dir(&worktree)
+            .stdin(Stdio::null())
+            .output()
+            .await;
+
+        match output {
+            Ok(output) if output.status.success() => {
+                info!(
+                    "on_create_host_command for session {} succeeded",
+                    session_id
+                );
+            }
+            Ok(output) => warn!(
+                "on_create_host_command for session {} exited with {}: {}",
+                session_id,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => warn!(
+                "Failed to run on_create_host_command for session {}: {}",
+                session_id, e
+            ),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_command_line_quotes_placeholders() {
+        let session_id = Uuid::nil();
+        let line = host_command_line(
+            "code {worktree} && echo {branch} {session_id}",
+            Path::new("/tmp/my work"),
+            "agents/it's",
+            session_id,
+        );
+        assert_eq!(
+            line,
+            format!(
+                "code '/tmp/my work' && echo 'agents/it'\\''s' '{}'",
+                session_id
+            )
+        );
+    }
+}
@@ -0,0 +1,122 @@
+// ABOUTME: Writing a session's logs to a text file for later review
+// Read-only over the log buffers, so exporting never disturbs live streaming
+
+use crate::components::live_logs_stream::{LogEntry, LogEntryLevel};
+use chrono::{DateTime, Local};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    // CSI sequences (colors, cursor movement) and OSC sequences (titles, hyperlinks)
+    static ref ANSI_REGEX: Regex =
+        Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]|\x1b\][^\x07\x1b]*(\x07|\x1b\\)").unwrap();
+}
+
+/// Filename being typed for an export, and whether escape sequences are kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogExportPrompt {
+    pub session_id: uuid::Uuid,
+    pub path: String,
+    pub raw: bool,
+}
+
+pub fn strip_ansi(text: &str) -> String {
+    ANSI_REGEX.replace_all(text, "").to_string()
+}
+
+/// `~/.agents-in-a-box/exports/<branch>-<timestamp>.log`, with `/` in the branch made safe
+pub fn default_export_path(branch: &str, now: DateTime<Local>) -> Option<PathBuf> {
+    let name = format!("{}-{}.log", branch.replace('/', "-"), now.format("%Y%m%d-%H%M%S"));
+    dirs::home_dir().map(|home| home.join(".agents-in-a-box").join("exports").join(name))
+}
+
+/// Expand a leading `~/` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+pub(crate) fn level_name(level: LogEntryLevel) -> &'static str {
+    match level {
+        LogEntryLevel::Debug => "DEBUG",
+        LogEntryLevel::Info => "INFO",
+        LogEntryLevel::Warn => "WARN",
+        LogEntryLevel::Error => "ERROR",
+    }
+}
+
+/// The session's creation logs followed by its live logs, one line per entry.
+/// Live entries carry their timestamp, level and source.
+pub fn format_export(build_logs: &[String], live_logs: &[LogEntry], raw: bool) -> String {
+    let clean = |text: &str| if raw { text.to_string() } else { strip_ansi(text) };
+    let mut out = String::new();
+
+    for line in build_logs {
+        out.push_str(&clean(line));
+        out.push('\n');
+    }
+    for entry in live_logs {
+        out.push_str(&format!(
+            "{} {:<5} [{}] {}\n",
+            entry.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+            level_name(entry.level),
+            entry.source,
+            clean(&entry.message)
+        ));
+    }
+    out
+}
+
+/// Write the export, creating missing parent directories
+pub fn write_export(path: &Path, content: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_export_strips_ansi_unless_raw() {
+        let build = vec!["\x1b[32mBuilding image\x1b[0m".to_string()];
+        let mut entry = LogEntry::new(
+            LogEntryLevel::Warn,
+            "agents-box".to_string(),
+            "\x1b[1;33mcareful\x1b[0m \x1b]8;;https://x\x07link\x1b]8;;\x07".to_string(),
+        );
+        entry.timestamp = chrono::DateTime::from_timestamp(0, 0).unwrap();
+        let stamp = entry.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S");
+
+        assert_eq!(
+            format_export(&build, &[entry.clone()], false),
+            format!("Building image\n{} WARN  [agents-box] careful link\n", stamp)
+        );
+
+        let raw = format_export(&build, &[entry], true);
+        assert!(raw.starts_with("\x1b[32mBuilding image\x1b[0m\n"));
+        assert!(raw.contains("\x1b[1;33mcareful"));
+    }
+
+    #[test]
+    fn test_default_export_path_is_filename_safe() {
+        let now = chrono::TimeZone::with_ymd_and_hms(&Local, 2025, 3, 4, 5, 6, 7).unwrap();
+        if let Some(path) = default_export_path("agents/fix-login", now) {
+            assert_eq!(path.file_name().unwrap(), "agents-fix-login-20250304-050607.log");
+            assert!(path.ends_with(".agents-in-a-box/exports/agents-fix-login-20250304-050607.log"));
+        }
+    }
+
+    #[test]
+    fn test_write_export_creates_directories() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("exports").join("run.log");
+        write_export(&path, "line\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "line\n");
+    }
+}
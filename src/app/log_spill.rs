@@ -0,0 +1,147 @@
+// ABOUTME: On-disk overflow for live log entries pushed out of the in-memory cap
+// Keeps the full history of long runs available to export without growing memory
+
+use crate::components::live_logs_stream::{LogEntry, LogEntryLevel};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use tracing::warn;
+use uuid::Uuid;
+
+/// What survives of a spilled entry: enough to export it, not to re-render rich events
+#[derive(Debug, Serialize, Deserialize)]
+struct SpilledEntry {
+    timestamp: DateTime<Utc>,
+    level: String,
+    source: String,
+    message: String,
+}
+
+impl From<&LogEntry> for SpilledEntry {
+    fn from(entry: &LogEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp,
+            level: crate::app::log_export::level_name(entry.level).to_string(),
+            source: entry.source.clone(),
+            message: entry.message.clone(),
+        }
+    }
+}
+
+impl From<SpilledEntry> for LogEntry {
+    fn from(spilled: SpilledEntry) -> Self {
+        let level = match spilled.level.as_str() {
+            "DEBUG" => LogEntryLevel::Debug,
+            "WARN" => LogEntryLevel::Warn,
+            "ERROR" => LogEntryLevel::Error,
+            _ => LogEntryLevel::Info,
+        };
+        let mut entry = LogEntry::new(level, spilled.source, spilled.message);
+        entry.timestamp = spilled.timestamp;
+        entry
+    }
+}
+
+/// One JSON-lines file per session, appended oldest first as entries overflow
+#[derive(Debug, Clone)]
+pub struct LogSpillStore {
+    dir: PathBuf,
+}
+
+impl LogSpillStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Store under `~/.agents-in-a-box/log_spill`, or None without a home directory
+    pub fn in_home_dir() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".agents-in-a-box").join("log_spill")))
+    }
+
+    fn path(&self, session_id: Uuid) -> PathBuf {
+        self.dir.join(format!("{}.jsonl", session_id))
+    }
+
+    pub fn append(&self, session_id: Uuid, entries: &[LogEntry]) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut content = String::new();
+        for entry in entries {
+            let line = serde_json::to_string(&SpilledEntry::from(entry)).map_err(io::Error::other)?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(session_id))?
+            .write_all(content.as_bytes())
+    }
+
+    /// Spilled entries for a session, oldest first. Unreadable lines are skipped.
+    pub fn load(&self, session_id: Uuid) -> Vec<LogEntry> {
+        let file = match fs::File::open(self.path(session_id)) {
+            Ok(file) => file,
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed to read spilled logs for {}: {}", session_id, e);
+                }
+                return Vec::new();
+            }
+        };
+        BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str::<SpilledEntry>(&line).ok())
+            .map(LogEntry::from)
+            .collect()
+    }
+
+    pub fn discard(&self, session_id: Uuid) {
+        let path = self.path(session_id);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!("Failed to remove spilled logs {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Drop every session's spill. Live logs start empty on launch, so older spills
+    /// would only show up out of order in exports.
+    pub fn clear(&self) {
+        if let Err(e) = fs::remove_dir_all(&self.dir) {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!("Failed to clear spilled logs in {}: {}", self.dir.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spilled_entries_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = LogSpillStore::new(temp_dir.path().join("log_spill"));
+        let session_id = Uuid::new_v4();
+
+        let first = LogEntry::new(LogEntryLevel::Warn, "agents-box".into(), "first".into());
+        let second = LogEntry::new(LogEntryLevel::Error, "claude".into(), "second".into());
+        store.append(session_id, &[first.clone()]).unwrap();
+        store.append(session_id, &[second]).unwrap();
+
+        let loaded = store.load(session_id);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].message, "first");
+        assert_eq!(loaded[0].level, LogEntryLevel::Warn);
+        assert_eq!(loaded[0].timestamp, first.timestamp);
+        assert_eq!(loaded[1].source, "claude");
+        assert_eq!(loaded[1].level, LogEntryLevel::Error);
+
+        store.discard(session_id);
+        assert!(store.load(session_id).is_empty());
+    }
+}
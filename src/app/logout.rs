@@ -0,0 +1,138 @@
+// ABOUTME: Logout: revoke the OAuth refresh token, then back up and remove the auth profile's credentials
+// Shared by the `logout` CLI command and the TUI action; re-authentication reuses the backup step
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::info;
+
+const OAUTH_REVOKE_URL: &str = "https://console.anthropic.com/v1/oauth/revoke";
+const OAUTH_CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
+const REVOKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Files that hold a profile's login
+const CREDENTIAL_FILES: [&str; 2] = [".credentials.json", ".claude.json"];
+
+/// What a logout did, for reporting
+#[derive(Debug, Default)]
+pub struct LogoutOutcome {
+    /// None when there was no refresh token or revocation was skipped
+    pub revocation: Option<Result<(), String>>,
+    pub backups: Vec<PathBuf>,
+}
+
+impl LogoutOutcome {
+    pub fn summary(&self) -> String {
+        let mut summary = if self.backups.is_empty() {
+            "No stored credentials to remove".to_string()
+        } else {
+            format!("Removed credentials ({} backed up)", self.backups.len())
+        };
+        match &self.revocation {
+            Some(Ok(())) => summary.push_str("; refresh token revoked"),
+            Some(Err(e)) => summary.push_str(&format!("; token revocation failed: {}", e)),
+            None => {}
+        }
+        summary
+    }
+}
+
+/// Copy each credential file in `auth_dir` to `<name>.backup-<unix time>`, then delete it.
+/// Returns the backups written.
+pub fn backup_and_remove_credentials(auth_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let backup_suffix = format!(".backup-{}", chrono::Utc::now().timestamp());
+    let mut backups = Vec::new();
+    for name in CREDENTIAL_FILES {
+        let path = auth_dir.join(name);
+        if !path.exists() {
+            continue;
+        }
+        let backup_path = path.with_extension(format!("json{}", backup_suffix));
+        fs::copy(&path, &backup_path)?;
+        info!("Backed up {} to {:?}", name, backup_path);
+        fs::remove_file(&path)?;
+        info!("Removed {}", name);
+        backups.push(backup_path);
+    }
+    Ok(backups)
+}
+
+/// `claudeAiOauth.refreshToken` from the profile's credentials file
+fn read_refresh_token(auth_dir: &Path) -> Option<String> {
+    let contents = fs::read_to_string(auth_dir.join(".credentials.json")).ok()?;
+    let json = serde_json::from_str::<serde_json::Value>(&contents).ok()?;
+    Some(json.get("claudeAiOauth")?.get("refreshToken")?.as_str()?.to_string())
+}
+
+async fn revoke_refresh_token(refresh_token: &str) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .user_agent("agents-in-a-box/0.1.0")
+        .timeout(REVOKE_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(OAUTH_REVOKE_URL)
+        .json(&serde_json::json!({
+            "token": refresh_token,
+            "token_type_hint": "refresh_token",
+            "client_id": OAUTH_CLIENT_ID,
+        }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("server returned {}", response.status()))
+    }
+}
+
+/// Revoke the refresh token (when `revoke` is set and one exists), then back up and
+/// remove the credential files. A failed revocation is reported but doesn't stop the
+/// local files from being removed.
+pub async fn logout(auth_dir: &Path, revoke: bool) -> io::Result<LogoutOutcome> {
+    let mut outcome = LogoutOutcome::default();
+    if revoke {
+        if let Some(refresh_token) = read_refresh_token(auth_dir) {
+            info!("Revoking OAuth refresh token");
+            outcome.revocation = Some(revoke_refresh_token(&refresh_token).await);
+        }
+    }
+    outcome.backups = backup_and_remove_credentials(auth_dir)?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_logout_backs_up_and_removes_credentials() {
+        let temp_dir = TempDir::new().unwrap();
+        let auth_dir = temp_dir.path();
+        let credentials = r#"{"claudeAiOauth":{"refreshToken":"rt-123","expiresAt":1}}"#;
+        fs::write(auth_dir.join(".credentials.json"), credentials).unwrap();
+        fs::write(auth_dir.join(".claude.json"), "{}").unwrap();
+        assert_eq!(read_refresh_token(auth_dir).as_deref(), Some("rt-123"));
+
+        let outcome = logout(auth_dir, false).await.unwrap();
+        assert!(outcome.revocation.is_none());
+        assert_eq!(outcome.backups.len(), 2);
+        assert!(!auth_dir.join(".credentials.json").exists());
+        assert!(!auth_dir.join(".claude.json").exists());
+
+        let backup = outcome
+            .backups
+            .iter()
+            .find(|path| path.to_string_lossy().contains(".credentials.json.backup-"))
+            .unwrap();
+        assert_eq!(fs::read_to_string(backup).unwrap(), credentials);
+        assert_eq!(outcome.summary(), "Removed credentials (2 backed up)");
+
+        let outcome = logout(auth_dir, true).await.unwrap();
+        assert!(outcome.revocation.is_none());
+        assert!(outcome.backups.is_empty());
+    }
+}
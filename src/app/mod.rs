@@ -1,11 +1,31 @@
 // ABOUTME: Main application structure and state management for the TUI
 
+pub mod alerts;
 pub mod attach_handler;
+pub mod chat_history;
 pub mod events;
+pub mod host_hooks;
+pub mod log_export;
+pub mod log_spill;
+pub mod logout;
+pub mod oauth_refresh;
+pub mod prompt_drafts;
+pub mod protected_sessions;
+pub mod session_env;
+pub mod session_images;
 pub mod session_loader;
+pub mod session_manager;
+pub mod session_persistence;
+pub mod session_ports;
+pub mod session_store;
+pub mod session_usage;
+pub mod session_volumes;
+pub mod shutdown;
 pub mod state;
 
 pub use attach_handler::AttachHandler;
 pub use events::EventHandler;
 pub use session_loader::SessionLoader;
+pub use session_manager::SessionManager;
+pub use session_persistence::SessionPersistence;
 pub use state::{App, AppState};
@@ -0,0 +1,190 @@
+// ABOUTME: Retries the containerised OAuth token refresh with exponential backoff
+// Docker and network failures are retried; a rejected refresh token needs a new sign-in
+
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// Wait before the second attempt; each later attempt waits twice as long as the last
+pub const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Phrases in the refresh script's output meaning the refresh token itself was refused.
+/// Only OAuth errors count: Docker's "unauthorized" when pulling the image is transient.
+const REJECTED_MARKERS: &[&str] = &[
+    "invalid_grant",
+    "invalid_token",
+    "invalid refresh token",
+    "refresh token expired",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefreshError {
+    /// Docker or the network failed; another attempt may succeed
+    Transient(String),
+    /// The refresh token was refused; only signing in again helps
+    Rejected(String),
+}
+
+impl RefreshError {
+    /// Classify a failed run of the refresh script; it may report a refused token on
+    /// either stream. Carries stderr, or stdout when stderr is empty.
+    pub fn from_script_output(stderr: &str, stdout: &str) -> Self {
+        let detail = if stderr.trim().is_empty() {
+            stdout
+        } else {
+            stderr
+        };
+        let output = format!("{}\n{}", stderr, stdout).to_lowercase();
+        if REJECTED_MARKERS.iter().any(|marker| output.contains(marker)) {
+            Self::Rejected(detail.trim().to_string())
+        } else {
+            Self::Transient(detail.trim().to_string())
+        }
+    }
+}
+
+/// Run `refresh` up to `attempts` times (at least once), backing off from `base_delay`
+/// between transient failures. A run only counts once `token_valid` confirms the new token.
+/// The error on giving up says how to sign in again.
+pub async fn refresh_with_retry<F, Fut>(
+    attempts: u32,
+    base_delay: Duration,
+    mut refresh: F,
+    token_valid: impl Fn() -> bool,
+) -> Result<(), String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), RefreshError>>,
+{
+    let attempts = attempts.max(1);
+    let mut delay = base_delay;
+    for attempt in 1..=attempts {
+        let detail = match refresh().await {
+            Ok(()) if token_valid() => return Ok(()),
+            Ok(()) => {
+                return Err("Token refresh succeeded but the new token is invalid. Run \
+                            `agents-box auth` to sign in again"
+                    .to_string());
+            }
+            Err(RefreshError::Rejected(detail)) => {
+                return Err(format!(
+                    "Refresh token was rejected: {}. Run `agents-box auth` to sign in again",
+                    detail
+                ));
+            }
+            Err(RefreshError::Transient(detail)) => detail,
+        };
+
+        if attempt == attempts {
+            return Err(format!(
+                "Token refresh failed after {} attempt(s): {}. Check Docker, or run \
+                 `agents-box auth` to sign in again",
+                attempts, detail
+            ));
+        }
+        warn!(
+            "OAuth refresh attempt {}/{} failed, retrying in {:?}: {}",
+            attempt, attempts, delay, detail
+        );
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+    }
+    unreachable!("the last attempt always returns")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Credentials file whose access token expires in an hour
+    fn write_valid_credentials(path: &std::path::Path) {
+        let expires_at = chrono::Utc::now().timestamp_millis() + 60 * 60 * 1000;
+        let json = format!(
+            r#"{{"claudeAiOauth":{{"refreshToken":"r","expiresAt":{}}}}}"#,
+            expires_at
+        );
+        fs::write(path, json).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_refresh_retries_transient_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let credentials = temp_dir.path().join(".credentials.json");
+        fs::write(&credentials, r#"{"claudeAiOauth":{"expiresAt":0}}"#).unwrap();
+
+        let runs = Cell::new(0);
+        let result = refresh_with_retry(
+            3,
+            Duration::from_millis(1),
+            || {
+                runs.set(runs.get() + 1);
+                let run = runs.get();
+                let credentials = credentials.clone();
+                async move {
+                    if run < 3 {
+                        return Err(RefreshError::from_script_output(
+                            "Cannot connect to docker",
+                            "",
+                        ));
+                    }
+                    write_valid_credentials(&credentials);
+                    Ok(())
+                }
+            },
+            || crate::app::AppState::is_oauth_token_valid(&credentials),
+        )
+        .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(runs.get(), 3);
+        assert!(crate::app::AppState::is_oauth_token_valid(&credentials));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_gives_up_with_actionable_error() {
+        // Out of attempts
+        let runs = Cell::new(0);
+        let error = refresh_with_retry(
+            2,
+            Duration::from_millis(1),
+            || {
+                runs.set(runs.get() + 1);
+                async { Err(RefreshError::Transient("network unreachable".to_string())) }
+            },
+            || true,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(runs.get(), 2);
+        assert!(error.contains("after 2 attempt(s): network unreachable"));
+
+        // A registry refusing the image pull is not a refused refresh token
+        assert!(matches!(
+            RefreshError::from_script_output("unauthorized: authentication required", ""),
+            RefreshError::Transient(_)
+        ));
+        assert!(error.contains("agents-box auth"));
+
+        // A rejected refresh token isn't retried
+        runs.set(0);
+        let error = refresh_with_retry(
+            3,
+            Duration::from_millis(1),
+            || {
+                runs.set(runs.get() + 1);
+                async { Err(RefreshError::from_script_output("", "400: invalid_grant")) }
+            },
+            || true,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(runs.get(), 1);
+        assert_eq!(
+            error,
+            "Refresh token was rejected: 400: invalid_grant. Run `agents-box auth` to sign in again"
+        );
+    }
+}
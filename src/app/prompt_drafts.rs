@@ -0,0 +1,268 @@
+// ABOUTME: Auto-saved boss prompt drafts, keyed by repository and branch
+// Lets a long prompt survive a stray Escape or a crash and be recovered on the next attempt
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, warn};
+
+/// Most drafts kept on disk; older ones are pruned first
+const MAX_DRAFTS: usize = 20;
+
+/// Drafts untouched for longer than this are pruned
+const MAX_DRAFT_AGE: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+#[derive(Debug, Clone)]
+pub struct PromptDraftStore {
+    dir: PathBuf,
+}
+
+impl PromptDraftStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Store under `~/.agents-in-a-box/drafts`, or None without a home directory
+    pub fn in_home_dir() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".agents-in-a-box").join("drafts")))
+    }
+
+    /// Save `text` as the draft at `draft`. An empty prompt removes the draft instead.
+    pub fn save(&self, draft: &Path, text: &str) -> io::Result<()> {
+        if text.trim().is_empty() {
+            self.discard(draft);
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(draft, text)?;
+        self.prune_keeping(Some(draft))
+    }
+
+    pub fn load(&self, draft: &Path) -> Option<String> {
+        fs::read_to_string(draft)
+            .ok()
+            .filter(|text| !text.trim().is_empty())
+    }
+
+    pub fn discard(&self, draft: &Path) {
+        if let Err(e) = fs::remove_file(draft) {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!("Failed to remove prompt draft {}: {}", draft.display(), e);
+            }
+        }
+    }
+
+    /// Draft shared by new sessions of a repository whose branch name is still generated
+    pub fn draft_path_for_repo(&self, repo_path: &Path) -> PathBuf {
+        self.draft_path(repo_path, "")
+    }
+
+    /// Directory holding large pastes saved for `@`-references from prompts
+    pub fn pastes_dir(&self) -> PathBuf {
+        self.dir.join("pastes")
+    }
+
+    /// Pastes saved from prompts for the repository at `repo_path`, the only ones mounted
+    /// into its sessions' containers
+    pub fn pastes_dir_for_repo(&self, repo_path: &Path) -> PathBuf {
+        let repo_path = repo_path.canonicalize().unwrap_or_else(|_| repo_path.to_path_buf());
+        self.pastes_dir().join(Self::file_stem(&repo_path, ""))
+    }
+
+    /// Save pasted text as a new file under `pastes_dir_for_repo` and return its path
+    pub fn save_paste(&self, repo_path: &Path, text: &str) -> io::Result<PathBuf> {
+        let dir = self.pastes_dir_for_repo(repo_path);
+        fs::create_dir_all(&dir)?;
+
+        let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+        let path = dir.join(format!("paste-{}-{:016x}.md", timestamp, fnv1a(text.as_bytes())));
+        fs::write(&path, text)?;
+        Ok(path)
+    }
+
+    /// Drop drafts older than `MAX_DRAFT_AGE`, then the oldest beyond `MAX_DRAFTS`.
+    /// Saved pastes are only dropped by age, since prompts may still reference them.
+    pub fn prune(&self) -> io::Result<()> {
+        self.prune_pastes()?;
+        self.prune_keeping(None)
+    }
+
+    fn prune_pastes(&self) -> io::Result<()> {
+        let dir = self.pastes_dir();
+        if !dir.exists() {
+            return Ok(());
+        }
+
+        let now = SystemTime::now();
+        let mut pastes = Vec::new();
+        for entry in fs::read_dir(&dir)?.filter_map(Result::ok) {
+            if entry.path().is_dir() {
+                pastes.extend(fs::read_dir(entry.path())?.filter_map(Result::ok));
+            } else {
+                pastes.push(entry);
+            }
+        }
+        for entry in pastes {
+            let expired = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| now.duration_since(modified).is_ok_and(|age| age > MAX_DRAFT_AGE));
+            if expired {
+                debug!("Pruning saved paste {}", entry.path().display());
+                fs::remove_file(entry.path())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prune, never removing `keep` (the draft just written, whose mtime may tie with others)
+    fn prune_keeping(&self, keep: Option<&Path>) -> io::Result<()> {
+        if !self.dir.exists() {
+            return Ok(());
+        }
+
+        let mut drafts: Vec<(PathBuf, SystemTime)> = fs::read_dir(&self.dir)?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "md"))
+            .filter_map(|entry| {
+                let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+                Some((entry.path(), modified))
+            })
+            .collect();
+
+        // Newest first, with the kept draft ahead of everything
+        drafts.sort_by_key(|(path, modified)| {
+            (Some(path.as_path()) != keep, std::cmp::Reverse(*modified))
+        });
+
+        let now = SystemTime::now();
+        for (index, (path, modified)) in drafts.iter().enumerate() {
+            let expired = now.duration_since(*modified).is_ok_and(|age| age > MAX_DRAFT_AGE);
+            if expired || index >= MAX_DRAFTS {
+                debug!("Pruning prompt draft {}", path.display());
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draft of a new session on `branch` of the repository at `repo_path`: a readable,
+    /// filesystem-safe name plus a hash of the full target so that repositories sharing a
+    /// directory name don't collide. An empty branch keys the repository's own draft.
+    pub fn draft_path(&self, repo_path: &Path, branch: &str) -> PathBuf {
+        self.dir.join(format!("{}.md", Self::file_stem(repo_path, branch)))
+    }
+
+    fn file_stem(repo_path: &Path, branch: &str) -> String {
+        let repo_name = repo_path.file_name().and_then(|n| n.to_str()).unwrap_or("repo");
+        let name = if branch.is_empty() {
+            repo_name.to_string()
+        } else {
+            format!("{}-{}", repo_name, branch)
+        };
+        let readable: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(80)
+            .collect();
+        let key = format!("{}\0{}", repo_path.display(), branch);
+
+        format!("{}-{:016x}", readable, fnv1a(key.as_bytes()))
+    }
+}
+
+/// Stable across runs and Rust versions, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_load_and_discard_draft() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PromptDraftStore::new(temp_dir.path().join("drafts"));
+        let repo = Path::new("/home/me/projects/app");
+
+        let draft = store.draft_path(repo, "agents/feature");
+
+        store.save(&draft, "Refactor the parser").unwrap();
+        assert_eq!(store.load(&draft).as_deref(), Some("Refactor the parser"));
+        assert_eq!(store.load(&store.draft_path(repo, "agents/other")), None);
+        assert_eq!(
+            store.load(&store.draft_path(Path::new("/elsewhere/app"), "agents/feature")),
+            None
+        );
+
+        // Saving an empty prompt clears the draft
+        store.save(&draft, "  ").unwrap();
+        assert_eq!(store.load(&draft), None);
+
+        store.save(&draft, "Again").unwrap();
+        store.discard(&draft);
+        assert_eq!(store.load(&draft), None);
+    }
+
+    #[test]
+    fn test_repository_draft() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PromptDraftStore::new(temp_dir.path().join("drafts"));
+        let repo = Path::new("/home/me/projects/app");
+
+        let draft = store.draft_path_for_repo(repo);
+
+        store.save(&draft, "Draft for any new session").unwrap();
+        assert!(draft.exists());
+        assert_eq!(store.load(&draft).as_deref(), Some("Draft for any new session"));
+        assert_eq!(store.load(&store.draft_path(repo, "agents/feature")), None);
+    }
+
+    #[test]
+    fn test_save_paste() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PromptDraftStore::new(temp_dir.path().to_path_buf());
+
+        let path = store.save_paste(Path::new("/repo"), "fn main() {}\n").unwrap();
+        assert!(path.starts_with(store.pastes_dir_for_repo(Path::new("/repo"))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fn main() {}\n");
+        // Each repository's pastes are kept apart, so only its own get mounted
+        assert_ne!(
+            store.pastes_dir_for_repo(Path::new("/repo")),
+            store.pastes_dir_for_repo(Path::new("/other"))
+        );
+
+        // Pastes live apart from drafts and survive draft pruning
+        store.prune().unwrap();
+        assert!(path.exists());
+        assert_eq!(store.load(&store.draft_path(Path::new("/repo"), "main")), None);
+    }
+
+    #[test]
+    fn test_drafts_are_bounded() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = PromptDraftStore::new(temp_dir.path().to_path_buf());
+        let repo = Path::new("/repo");
+
+        for i in 0..MAX_DRAFTS + 5 {
+            let draft = store.draft_path(repo, &format!("branch-{}", i));
+            store.save(&draft, "draft").unwrap();
+        }
+
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), MAX_DRAFTS);
+    }
+}
@@ -0,0 +1,29 @@
+// ABOUTME: Persisted set of sessions protected from bulk operations
+// Protected sessions are skipped by cleanup and other mass actions; per-session actions still work
+
+use crate::app::session_store::JsonStore;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Protected session ids, kept in `PROTECTED_SESSIONS_FILE`
+pub type ProtectedSessionStore = JsonStore<HashSet<Uuid>>;
+
+pub const PROTECTED_SESSIONS_FILE: &str = "protected_sessions.json";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_protected_sessions_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store =
+            ProtectedSessionStore::new(temp_dir.path().join("nested").join("protected.json"));
+        assert!(store.load().is_empty());
+
+        let protected: HashSet<Uuid> = [Uuid::new_v4(), Uuid::new_v4()].into_iter().collect();
+        store.save(&protected).unwrap();
+        assert_eq!(store.load(), protected);
+    }
+}
@@ -0,0 +1,92 @@
+// ABOUTME: Per-session environment variables for session containers, persisted for restarts
+// Values may hold project secrets; the shared store keeps the file readable only by the user
+
+use crate::app::session_store::SessionMapStore;
+
+pub type EnvVars = Vec<(String, String)>;
+
+/// Whether `key` is a usable variable name (`[A-Za-z_][A-Za-z0-9_]*`)
+pub fn is_valid_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parse a `KEY=VALUE` entry; the value may be empty or contain further `=`
+pub fn parse_env_assignment(input: &str) -> Result<(String, String), String> {
+    let Some((key, value)) = input.split_once('=') else {
+        return Err("Use KEY=VALUE".to_string());
+    };
+    let key = key.trim();
+    if !is_valid_env_key(key) {
+        return Err(format!(
+            "Invalid name '{}': use letters, digits and _, not starting with a digit",
+            key
+        ));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Add or replace `key` in `vars`, keeping the original position of a replaced entry
+pub fn set_env_var(vars: &mut EnvVars, key: String, value: String) {
+    match vars.iter_mut().find(|(existing, _)| *existing == key) {
+        Some(entry) => entry.1 = value,
+        None => vars.push((key, value)),
+    }
+}
+
+/// Variables by session, kept in `SESSION_ENV_FILE`
+pub type SessionEnvStore = SessionMapStore<EnvVars>;
+
+pub const SESSION_ENV_FILE: &str = "session_env.json";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_parse_env_assignment() {
+        assert_eq!(
+            parse_env_assignment("NODE_ENV=production"),
+            Ok(("NODE_ENV".to_string(), "production".to_string()))
+        );
+        assert_eq!(
+            parse_env_assignment("_TOKEN=a=b"),
+            Ok(("_TOKEN".to_string(), "a=b".to_string()))
+        );
+        assert_eq!(
+            parse_env_assignment("EMPTY="),
+            Ok(("EMPTY".to_string(), String::new()))
+        );
+        assert!(parse_env_assignment("NO_VALUE").is_err());
+        assert!(parse_env_assignment("1ST=x").is_err());
+        assert!(parse_env_assignment("MY-KEY=x").is_err());
+        assert!(parse_env_assignment("=x").is_err());
+    }
+
+    #[test]
+    fn test_session_env_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SessionEnvStore::new(temp_dir.path().join("nested").join("env.json"));
+        assert!(store.load().is_empty());
+
+        let mut vars = EnvVars::new();
+        set_env_var(&mut vars, "A".to_string(), "1".to_string());
+        set_env_var(&mut vars, "B".to_string(), "2".to_string());
+        set_env_var(&mut vars, "A".to_string(), "3".to_string());
+        assert_eq!(
+            vars,
+            vec![
+                ("A".to_string(), "3".to_string()),
+                ("B".to_string(), "2".to_string())
+            ]
+        );
+
+        let env: HashMap<Uuid, EnvVars> = [(Uuid::new_v4(), vars)].into_iter().collect();
+        store.save(&env).unwrap();
+        assert_eq!(store.load(), env);
+    }
+}
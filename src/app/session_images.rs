@@ -0,0 +1,9 @@
+// ABOUTME: Container image chosen for a session with `agents-box create --image`, kept for restarts
+// Sessions without an entry use the config file or template image as usual
+
+use crate::app::session_store::SessionMapStore;
+
+/// Image by session, kept in `SESSION_IMAGES_FILE`
+pub type SessionImagesStore = SessionMapStore<String>;
+
+pub const SESSION_IMAGES_FILE: &str = "session_images.json";
@@ -3,12 +3,14 @@
 
 #![allow(dead_code)]
 
+use crate::app::session_persistence::{SessionPersistence, merge_persisted};
 use crate::config::AppConfig;
-use crate::docker::ContainerManager;
+use crate::docker::{ContainerManager, ContainerStatus, OOM_KILLED_MESSAGE};
 use crate::git::WorktreeManager;
-use crate::models::{Session, SessionMode, SessionStatus, Workspace};
+use crate::models::{AgentType, Session, SessionMode, SessionStatus, Workspace};
 use crate::tmux::TmuxSession;
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tracing::{debug, info, warn};
@@ -37,10 +39,19 @@ impl SessionLoader {
     pub async fn load_active_sessions(&self) -> Result<Vec<Workspace>> {
         info!("Loading active sessions from Docker containers");
 
-        // Get all Claude-managed containers
+        // Get all Claude-managed containers, including stopped ones so sessions paused
+        // with a graceful stop still show up (as Stopped) and can be restarted
         let containers = self.container_manager.list_agents_containers().await?;
         info!("Found {} Claude-managed containers", containers.len());
 
+        // Inspect stopped containers for OOM kills in one concurrent batch
+        let exited_ids: Vec<String> = containers
+            .iter()
+            .filter(|c| matches!(c.state.as_deref(), Some("exited" | "dead")))
+            .filter_map(|c| c.id.clone())
+            .collect();
+        let exited_statuses = self.container_manager.get_statuses(&exited_ids).await;
+
         // Group sessions by their source repository
         let mut workspace_map: HashMap<PathBuf, Workspace> = HashMap::new();
 
@@ -51,6 +62,27 @@ impl SessionLoader {
                 .as_ref()
                 .and_then(|labels| labels.get("agents-session-id"))
                 .and_then(|id| Uuid::parse_str(id).ok());
+            // Containers created before the label existed ran Claude
+            let agent_type = AgentType::from_label(
+                container
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.get("agents-agent-type"))
+                    .map(String::as_str),
+            );
+            let model = container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get("agents-model"))
+                .cloned();
+            let base_branch = container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get("agents-base-branch"))
+                .cloned();
+            let ports = parse_ports_label(
+                container.labels.as_ref().and_then(|labels| labels.get("agents-ports")),
+            );
 
             if let Some(session_id) = session_id {
                 debug!("Processing container for session {}", session_id);
@@ -64,20 +96,37 @@ impl SessionLoader {
                             worktree_info.path.to_string_lossy().to_string(), // Use worktree path, not source repo
                         );
                         session.id = session_id;
-                        session.container_id = container.id;
+                        session.container_id = container.id.clone();
                         session.branch_name = worktree_info.branch_name.clone();
+                        session.review_only = worktree_info.detached;
                         session.mode = SessionMode::Boss;
+                        session.agent_type = agent_type;
+                        session.model = model;
+                        session.base_branch = base_branch;
+                        session.ports = ports;
+                        if let Some(created) =
+                            container.created.and_then(|ts| DateTime::from_timestamp(ts, 0))
+                        {
+                            session.created_at = created;
+                        }
 
                         // Set session status based on container state
                         let state = container.state.as_deref().unwrap_or("unknown");
-                        session.set_status(match state {
-                            "running" => SessionStatus::Running,
-                            "paused" => SessionStatus::Stopped,
-                            "exited" | "dead" => SessionStatus::Stopped,
-                            _ => {
-                                SessionStatus::Error(format!("Unknown container state: {}", state))
-                            }
-                        });
+                        let oom_killed = container
+                            .id
+                            .as_ref()
+                            .and_then(|id| exited_statuses.get(id))
+                            .is_some_and(|status| match status {
+                                ContainerStatus::Error(e) => e == OOM_KILLED_MESSAGE,
+                                _ => false,
+                            });
+                        session.set_status(container_session_status(
+                            state,
+                            oom_killed,
+                            session.created_at,
+                            Utc::now(),
+                            self.config.docker.startup_grace(),
+                        ));
 
                         // Get workspace name from worktree path
                         // Worktree naming: <repo-name>--<branch-hash>--<session-id>
@@ -146,6 +195,8 @@ impl SessionLoader {
                             "Worktree missing - container orphaned".to_string(),
                         ));
                         session.mode = SessionMode::Boss;
+                        session.agent_type = agent_type;
+                        session.model = model;
 
                         // Try to determine the original workspace from container labels or name
                         let workspace_name = container
@@ -221,6 +272,7 @@ impl SessionLoader {
                         );
                         session.id = session_id;
                         session.branch_name = worktree_info.branch_name.clone();
+                        session.review_only = worktree_info.detached;
                         session.mode = SessionMode::Boss;
                         session.set_status(SessionStatus::Stopped); // No container = stopped
 
@@ -249,6 +301,16 @@ impl SessionLoader {
             }
         }
 
+        // Restore names, modes and prompts that Docker and git don't record
+        if let Some(persistence) = SessionPersistence::in_home_dir() {
+            let persisted = persistence.load();
+            if !persisted.is_empty() {
+                for workspace in workspace_map.values_mut() {
+                    merge_persisted(&mut workspace.sessions, &persisted);
+                }
+            }
+        }
+
         // Convert map to sorted vector
         let mut workspaces: Vec<Workspace> = workspace_map.into_values().collect();
         workspaces.sort_by(|a, b| a.name.cmp(&b.name));
@@ -260,21 +322,30 @@ impl SessionLoader {
         Ok(workspaces)
     }
 
-    /// Load sessions from persistence (e.g., ~/.agents-box/sessions.json)
+    /// Sessions saved in ~/.agents-in-a-box/sessions.json, as stopped sessions
     pub async fn load_from_persistence(&self) -> Result<Vec<Session>> {
-        // TODO: Implement loading from ~/.agents-box/sessions.json
-        // For now, return empty vec
-        Ok(vec![])
+        Ok(SessionPersistence::in_home_dir().map(|p| p.load()).unwrap_or_default())
     }
 
     /// Create a new session browser to select repository for new session
     pub async fn get_available_repositories(&self) -> Result<Vec<PathBuf>> {
         // Use workspace scanner to find repositories
         use crate::git::WorkspaceScanner;
-
-        let scanner = WorkspaceScanner::with_additional_paths(
-            self.config.workspace_defaults.workspace_scan_paths.clone(),
-        );
+        use crate::git::workspace_scanner::{SEARCH_PATHS_ENV, search_paths_from_env};
+
+        let defaults = &self.config.workspace_defaults;
+        let scanner = if let Some(paths) = search_paths_from_env() {
+            info!("Scanning {} paths from {}", paths.len(), SEARCH_PATHS_ENV);
+            WorkspaceScanner::with_scan_roots(paths)
+        } else if defaults.scan_roots.is_empty() {
+            WorkspaceScanner::with_additional_paths(defaults.workspace_scan_paths.clone())
+        } else {
+            WorkspaceScanner::with_scan_roots(
+                defaults.scan_roots.iter().chain(&defaults.workspace_scan_paths).cloned().collect(),
+            )
+        }
+        .with_max_depth(defaults.max_scan_depth)
+        .with_require_opt_in(defaults.require_opt_in);
         let scan_result = scanner.scan()?;
 
         let max_repos = self.config.workspace_defaults.max_repositories;
@@ -298,10 +369,94 @@ impl SessionLoader {
     }
 }
 
+/// Published ports from a container's `agents-ports` label (`host:container,...`),
+/// skipping malformed entries
+fn parse_ports_label(label: Option<&String>) -> Vec<(u16, u16)> {
+    label
+        .map(|label| {
+            label
+                .split(',')
+                .filter_map(|pair| {
+                    let (host, container) = pair.split_once(':')?;
+                    Some((host.trim().parse().ok()?, container.trim().parse().ok()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Session status for a Docker container state. A new container can pass through
+/// other states before it is running, so those only count as errors once the
+/// startup grace period has passed. A container Docker killed for running out of
+/// memory is an error rather than a normal stop.
+fn container_session_status(
+    state: &str,
+    oom_killed: bool,
+    created_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    grace: Duration,
+) -> SessionStatus {
+    match state {
+        "running" => SessionStatus::Running,
+        "paused" => SessionStatus::Stopped,
+        "exited" | "dead" if oom_killed => SessionStatus::Error(OOM_KILLED_MESSAGE.to_string()),
+        "exited" | "dead" => SessionStatus::Stopped,
+        _ if now - created_at < grace => {
+            debug!("Container still starting (state: {})", state);
+            SessionStatus::Stopped
+        }
+        _ => SessionStatus::Error(format!("Unknown container state: {}", state)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_ports_label() {
+        assert!(parse_ports_label(None).is_empty());
+        let label = "8080:3000,5432:5432,bad,70000:1".to_string();
+        assert_eq!(parse_ports_label(Some(&label)), vec![(8080, 3000), (5432, 5432)]);
+    }
+
+    #[test]
+    fn test_container_status_startup_grace() {
+        let created = Utc::now();
+        let grace = Duration::seconds(30);
+
+        assert_eq!(
+            container_session_status(
+                "created",
+                false,
+                created,
+                created + Duration::seconds(5),
+                grace
+            ),
+            SessionStatus::Stopped
+        );
+        assert!(matches!(
+            container_session_status("created", false, created, created + grace, grace),
+            SessionStatus::Error(_)
+        ));
+        assert_eq!(
+            container_session_status("running", false, created, created, grace),
+            SessionStatus::Running
+        );
+        assert!(matches!(
+            container_session_status("restarting", false, created, created, Duration::zero()),
+            SessionStatus::Error(_)
+        ));
+        assert_eq!(
+            container_session_status("exited", false, created, created, grace),
+            SessionStatus::Stopped
+        );
+        assert_eq!(
+            container_session_status("exited", true, created, created, grace),
+            SessionStatus::Error("container killed (out of memory)".to_string())
+        );
+    }
+
     #[tokio::test]
     #[ignore] // Requires Docker
     async fn test_session_loader_creation() {
@@ -0,0 +1,233 @@
+// ABOUTME: Startup reconciliation of saved sessions against live containers, worktrees and tmux
+// Classifies every session id it finds so orphans can be reported and cleaned in one place
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::SessionPersistence;
+use crate::docker::ContainerManager;
+use crate::git::WorktreeManager;
+use crate::tmux::TmuxSession;
+
+/// How a session id's saved record, container and worktree line up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionHealth {
+    /// A worktree plus a container or tmux session to run it
+    Healthy,
+    /// A container whose worktree is gone
+    OrphanContainer,
+    /// A worktree with neither a container nor a tmux session
+    OrphanWorktree,
+    /// Saved in sessions.json, but nothing of it is left
+    GhostRecord,
+}
+
+/// Session ids seen in each place sessions live
+#[derive(Debug, Clone, Default)]
+pub struct SessionInventory {
+    pub persisted: HashSet<Uuid>,
+    /// Session id to container id
+    pub containers: HashMap<Uuid, String>,
+    pub worktrees: HashSet<Uuid>,
+    /// Worktrees with a live tmux session (Interactive mode)
+    pub tmux: HashSet<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconciledSession {
+    pub session_id: Uuid,
+    pub health: SessionHealth,
+    pub container_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileReport {
+    pub sessions: Vec<ReconciledSession>,
+}
+
+impl ReconcileReport {
+    pub fn with_health(&self, health: SessionHealth) -> impl Iterator<Item = &ReconciledSession> {
+        self.sessions.iter().filter(move |s| s.health == health)
+    }
+
+    pub fn count(&self, health: SessionHealth) -> usize {
+        self.with_health(health).count()
+    }
+
+    /// One line for the notification area naming the keys that clean each kind of orphan,
+    /// or None when everything is healthy
+    pub fn summary(&self, cleanup_orphaned_key: &str, cleanup_dead_key: &str) -> Option<String> {
+        let mut parts = Vec::new();
+        let worktrees = self.count(SessionHealth::OrphanWorktree);
+        if worktrees > 0 {
+            parts.push(format!(
+                "{} orphaned worktree(s) found — press {} to clean",
+                worktrees, cleanup_dead_key
+            ));
+        }
+        let containers = self.count(SessionHealth::OrphanContainer);
+        if containers > 0 {
+            parts.push(format!(
+                "{} orphaned container(s) found — press {} to clean",
+                containers, cleanup_orphaned_key
+            ));
+        }
+        let ghosts = self.count(SessionHealth::GhostRecord);
+        if ghosts > 0 {
+            parts.push(format!("forgot {} saved session(s) with nothing left", ghosts));
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
+    }
+}
+
+/// Cross-references the places a session can live
+pub struct SessionManager {
+    persistence: Option<SessionPersistence>,
+}
+
+impl SessionManager {
+    pub fn new(persistence: Option<SessionPersistence>) -> Self {
+        Self { persistence }
+    }
+
+    /// Gather the live inventory and classify it. Fails when Docker can't be listed, since
+    /// every container-backed session would otherwise look orphaned.
+    pub async fn reconcile(&self) -> anyhow::Result<ReconcileReport> {
+        let report = Self::classify(&self.inventory().await?);
+        info!(
+            "Reconciled {} sessions: {} orphaned containers, {} orphaned worktrees, {} ghosts",
+            report.sessions.len(),
+            report.count(SessionHealth::OrphanContainer),
+            report.count(SessionHealth::OrphanWorktree),
+            report.count(SessionHealth::GhostRecord)
+        );
+        Ok(report)
+    }
+
+    async fn inventory(&self) -> anyhow::Result<SessionInventory> {
+        let mut inventory = SessionInventory {
+            persisted: self
+                .persistence
+                .as_ref()
+                .map(|p| p.load().into_iter().map(|s| s.id).collect())
+                .unwrap_or_default(),
+            ..SessionInventory::default()
+        };
+
+        for container in ContainerManager::new().await?.list_agents_containers().await? {
+            let session_id = container
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get("agents-session-id"))
+                .and_then(|id| Uuid::parse_str(id).ok());
+            if let (Some(session_id), Some(container_id)) = (session_id, container.id) {
+                inventory.containers.insert(session_id, container_id);
+            }
+        }
+
+        for (session_id, worktree_info) in WorktreeManager::new()?.list_all_worktrees()? {
+            inventory.worktrees.insert(session_id);
+            if !inventory.containers.contains_key(&session_id)
+                && TmuxSession::new(worktree_info.branch_name, "claude".to_string())
+                    .does_session_exist()
+                    .await
+            {
+                inventory.tmux.insert(session_id);
+            }
+        }
+        Ok(inventory)
+    }
+
+    /// Classify every session id in the inventory, ordered by id
+    pub fn classify(inventory: &SessionInventory) -> ReconcileReport {
+        let ids: BTreeSet<Uuid> = inventory
+            .persisted
+            .iter()
+            .chain(inventory.containers.keys())
+            .chain(&inventory.worktrees)
+            .copied()
+            .collect();
+
+        let sessions = ids
+            .into_iter()
+            .map(|session_id| {
+                let container_id = inventory.containers.get(&session_id).cloned();
+                let has_worktree = inventory.worktrees.contains(&session_id);
+                let health = match (container_id.is_some(), has_worktree) {
+                    (true, true) => SessionHealth::Healthy,
+                    (true, false) => SessionHealth::OrphanContainer,
+                    (false, true) if inventory.tmux.contains(&session_id) => {
+                        SessionHealth::Healthy
+                    }
+                    (false, true) => SessionHealth::OrphanWorktree,
+                    (false, false) => SessionHealth::GhostRecord,
+                };
+                ReconciledSession { session_id, health, container_id }
+            })
+            .collect();
+        ReconcileReport { sessions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health_of(report: &ReconcileReport, session_id: Uuid) -> SessionHealth {
+        report.sessions.iter().find(|s| s.session_id == session_id).unwrap().health
+    }
+
+    #[test]
+    fn test_classify_each_kind_of_session() {
+        let boss = Uuid::new_v4();
+        let interactive = Uuid::new_v4();
+        let orphan_container = Uuid::new_v4();
+        let orphan_worktree = Uuid::new_v4();
+        let ghost = Uuid::new_v4();
+
+        let inventory = SessionInventory {
+            persisted: [boss, interactive, orphan_worktree, ghost].into_iter().collect(),
+            containers: [(boss, "c-boss".to_string()), (orphan_container, "c-orphan".to_string())]
+                .into_iter()
+                .collect(),
+            worktrees: [boss, interactive, orphan_worktree].into_iter().collect(),
+            tmux: [interactive].into_iter().collect(),
+        };
+        let report = SessionManager::classify(&inventory);
+
+        assert_eq!(report.sessions.len(), 5);
+        assert_eq!(health_of(&report, boss), SessionHealth::Healthy);
+        assert_eq!(health_of(&report, interactive), SessionHealth::Healthy);
+        assert_eq!(health_of(&report, orphan_container), SessionHealth::OrphanContainer);
+        assert_eq!(health_of(&report, orphan_worktree), SessionHealth::OrphanWorktree);
+        assert_eq!(health_of(&report, ghost), SessionHealth::GhostRecord);
+
+        let orphan = report.with_health(SessionHealth::OrphanContainer).next().unwrap();
+        assert_eq!(orphan.container_id.as_deref(), Some("c-orphan"));
+    }
+
+    #[test]
+    fn test_summary_names_the_cleanup_keys() {
+        let session_id = Uuid::new_v4();
+        let healthy = SessionInventory {
+            containers: [(session_id, "c".to_string())].into_iter().collect(),
+            worktrees: [session_id].into_iter().collect(),
+            ..SessionInventory::default()
+        };
+        assert_eq!(SessionManager::classify(&healthy).summary("x", "X"), None);
+
+        let inventory = SessionInventory {
+            persisted: [Uuid::new_v4()].into_iter().collect(),
+            worktrees: [Uuid::new_v4(), Uuid::new_v4()].into_iter().collect(),
+            ..SessionInventory::default()
+        };
+        assert_eq!(
+            SessionManager::classify(&inventory).summary("x", "X").as_deref(),
+            Some(
+                "2 orphaned worktree(s) found — press X to clean; \
+                 forgot 1 saved session(s) with nothing left"
+            )
+        );
+    }
+}
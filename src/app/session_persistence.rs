@@ -0,0 +1,295 @@
+// ABOUTME: Session metadata saved to ~/.agents-in-a-box/sessions.json across restarts
+// Docker and worktrees stay the source of truth; the file restores names, modes and prompts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::models::{Session, SessionMode, SessionStatus};
+
+/// Written to every file; older files are read through the fields' serde defaults
+const SESSIONS_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionsFile {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    sessions: Vec<PersistedSession>,
+}
+
+/// The part of a session that can't be rediscovered from its container or worktree.
+/// Every field but the id is optional so files from other versions still load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedSession {
+    id: Uuid,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    branch_name: Option<String>,
+    #[serde(default)]
+    workspace_path: Option<String>,
+    #[serde(default)]
+    mode: Option<SessionMode>,
+    #[serde(default)]
+    boss_prompt: Option<String>,
+    #[serde(default)]
+    skip_permissions: Option<bool>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    base_branch: Option<String>,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Session> for PersistedSession {
+    fn from(session: &Session) -> Self {
+        Self {
+            id: session.id,
+            name: Some(session.name.clone()),
+            branch_name: Some(session.branch_name.clone()),
+            workspace_path: Some(session.workspace_path.clone()),
+            mode: Some(session.mode.clone()),
+            boss_prompt: session.boss_prompt.clone(),
+            skip_permissions: Some(session.skip_permissions),
+            model: session.model.clone(),
+            base_branch: session.base_branch.clone(),
+            created_at: Some(session.created_at),
+        }
+    }
+}
+
+impl PersistedSession {
+    /// A stopped session without a container, carrying the saved metadata
+    fn into_session(self) -> Session {
+        let name = self.name.unwrap_or_else(|| self.id.to_string());
+        let mut session = Session::new_with_options(
+            name,
+            self.workspace_path.unwrap_or_default(),
+            self.skip_permissions.unwrap_or_default(),
+            self.mode.unwrap_or_default(),
+            self.boss_prompt,
+        );
+        session.id = self.id;
+        if let Some(branch_name) = self.branch_name {
+            session.branch_name = branch_name;
+        }
+        session.model = self.model;
+        session.base_branch = self.base_branch;
+        if let Some(created_at) = self.created_at {
+            session.created_at = created_at;
+        }
+        session.set_status(SessionStatus::Stopped);
+        session
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionPersistence {
+    path: PathBuf,
+}
+
+impl SessionPersistence {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// File at `~/.agents-in-a-box/sessions.json`, or None without a home directory
+    pub fn in_home_dir() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".agents-in-a-box").join("sessions.json")))
+    }
+
+    pub fn save(&self, sessions: &[Session]) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = SessionsFile {
+            version: SESSIONS_FILE_VERSION,
+            sessions: sessions.iter().map(PersistedSession::from).collect(),
+        };
+        let content = serde_json::to_string_pretty(&file).map_err(io::Error::other)?;
+        fs::write(&self.path, content)
+    }
+
+    /// Saved sessions as stopped, container-less sessions; a missing or unreadable file
+    /// means there are none
+    pub fn load(&self) -> Vec<Session> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed to read {}: {}", self.path.display(), e);
+                }
+                return Vec::new();
+            }
+        };
+        let file: SessionsFile = match serde_json::from_str(&content) {
+            Ok(file) => file,
+            Err(e) => {
+                warn!("Ignoring unreadable {}: {}", self.path.display(), e);
+                return Vec::new();
+            }
+        };
+        if file.version > SESSIONS_FILE_VERSION {
+            warn!(
+                "{} was written by a newer version ({}); reading the fields this one knows",
+                self.path.display(),
+                file.version
+            );
+        }
+        file.sessions.into_iter().map(PersistedSession::into_session).collect()
+    }
+}
+
+/// Copy saved metadata onto sessions discovered from containers and worktrees
+///
+/// Matches on session id, falling back to branch name. A branch-only match is a different
+/// session on the same branch, so it only fills in what discovery left unset. Saved sessions
+/// with no discovered counterpart are left out; there is nothing left to restart them from.
+pub fn merge_persisted(discovered: &mut [Session], persisted: &[Session]) {
+    for session in discovered {
+        let by_id = persisted.iter().find(|saved| saved.id == session.id);
+        let saved = by_id.or_else(|| {
+            persisted.iter().find(|saved| {
+                !saved.branch_name.is_empty() && saved.branch_name == session.branch_name
+            })
+        });
+        let Some(saved) = saved else {
+            continue;
+        };
+        session.name = saved.name.clone();
+        if by_id.is_some() {
+            session.mode = saved.mode.clone();
+            session.skip_permissions = saved.skip_permissions;
+        }
+        if saved.boss_prompt.is_some() && (by_id.is_some() || session.boss_prompt.is_none()) {
+            session.boss_prompt = saved.boss_prompt.clone();
+        }
+        // The container's labels win; older containers weren't labelled
+        if session.model.is_none() {
+            session.model = saved.model.clone();
+        }
+        if session.base_branch.is_none() {
+            session.base_branch = saved.base_branch.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn boss_session(name: &str, prompt: &str) -> Session {
+        let mut session = Session::new_with_options(
+            name.to_string(),
+            format!("/worktrees/{}", name),
+            true,
+            SessionMode::Boss,
+            Some(prompt.to_string()),
+        );
+        session.model = Some("claude-opus-4-1".to_string());
+        session.base_branch = Some("develop".to_string());
+        session
+    }
+
+    #[test]
+    fn test_sessions_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let persistence = SessionPersistence::new(temp_dir.path().join("sessions.json"));
+        assert!(persistence.load().is_empty());
+
+        let mut session = boss_session("Fix login", "Fix the login redirect");
+        session.container_id = Some("abc123".to_string());
+        session.set_status(SessionStatus::Running);
+        persistence.save(std::slice::from_ref(&session)).unwrap();
+
+        let loaded = persistence.load();
+        assert_eq!(loaded.len(), 1);
+        let restored = &loaded[0];
+        assert_eq!(restored.id, session.id);
+        assert_eq!(restored.name, "Fix login");
+        assert_eq!(restored.branch_name, session.branch_name);
+        assert_eq!(restored.workspace_path, "/worktrees/Fix login");
+        assert_eq!(restored.mode, SessionMode::Boss);
+        assert_eq!(restored.boss_prompt.as_deref(), Some("Fix the login redirect"));
+        assert!(restored.skip_permissions);
+        assert_eq!(restored.model.as_deref(), Some("claude-opus-4-1"));
+        assert_eq!(restored.base_branch.as_deref(), Some("develop"));
+        assert_eq!(restored.created_at, session.created_at);
+        // Runtime state is rediscovered, not restored
+        assert!(restored.container_id.is_none());
+        assert_eq!(restored.status, SessionStatus::Stopped);
+    }
+
+    #[test]
+    fn test_load_tolerates_older_and_newer_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sessions.json");
+        let persistence = SessionPersistence::new(path.clone());
+        let id = Uuid::new_v4();
+
+        // Unversioned file with only some fields
+        fs::write(&path, format!(r#"{{"sessions":[{{"id":"{}","name":"docs"}}]}}"#, id))
+            .unwrap();
+        let loaded = persistence.load();
+        assert_eq!(loaded[0].id, id);
+        assert_eq!(loaded[0].name, "docs");
+        assert_eq!(loaded[0].mode, SessionMode::Interactive);
+        assert!(loaded[0].boss_prompt.is_none());
+
+        // A newer version's extra fields are ignored
+        let newer = format!(
+            r#"{{"version":9,"sessions":[{{"id":"{}","boss_prompt":"Docs","pinned":true}}]}}"#,
+            id
+        );
+        fs::write(&path, newer).unwrap();
+        assert_eq!(persistence.load()[0].boss_prompt.as_deref(), Some("Docs"));
+
+        fs::write(&path, "not json").unwrap();
+        assert!(persistence.load().is_empty());
+    }
+
+    #[test]
+    fn test_merge_restores_metadata_for_session_without_container() {
+        let saved = boss_session("Fix login", "Fix the login redirect");
+        let gone = boss_session("Old spike", "Try the new parser");
+
+        // The container was removed; the worktree loader rediscovers it by id as stopped
+        let mut worktree_only = Session::new(saved.branch_name.clone(), "/worktrees/x".to_string());
+        worktree_only.id = saved.id;
+        worktree_only.branch_name = saved.branch_name.clone();
+
+        // A session recreated under a new id is matched on its branch
+        let mut same_branch = Session::new("agents-in-a-box/other".to_string(), String::new());
+        same_branch.branch_name = "agents-in-a-box/other".to_string();
+        let mut recreated = boss_session("Other work", "Refactor the parser");
+        recreated.branch_name = same_branch.branch_name.clone();
+
+        let mut discovered = vec![worktree_only, same_branch];
+        merge_persisted(&mut discovered, &[saved.clone(), gone, recreated]);
+
+        assert_eq!(discovered.len(), 2);
+        assert_eq!(discovered[0].name, "Fix login");
+        assert_eq!(discovered[0].boss_prompt.as_deref(), Some("Fix the login redirect"));
+        assert_eq!(discovered[0].mode, SessionMode::Boss);
+        assert!(discovered[0].skip_permissions);
+        assert_eq!(discovered[0].model.as_deref(), Some("claude-opus-4-1"));
+        assert_eq!(discovered[0].base_branch.as_deref(), Some("develop"));
+        assert!(discovered[0].container_id.is_none());
+        assert_eq!(discovered[0].status, SessionStatus::Stopped);
+        assert_eq!(discovered[0].workspace_path, "/worktrees/x");
+
+        assert_ne!(discovered[1].id, saved.id);
+        assert_eq!(discovered[1].name, "Other work");
+        assert_eq!(discovered[1].boss_prompt.as_deref(), Some("Refactor the parser"));
+        // Discovery decided the mode; a branch-only match doesn't override it
+        assert_eq!(discovered[1].mode, SessionMode::Interactive);
+        assert!(!discovered[1].skip_permissions);
+    }
+}
@@ -0,0 +1,67 @@
+// ABOUTME: Ports published from a session's container, persisted for restarts
+// Entries are HOST:CONTAINER, or a single port published as the same number on both sides
+
+use crate::app::session_store::SessionMapStore;
+
+/// Host port, container port
+pub type Ports = Vec<(u16, u16)>;
+
+/// Parse a `HOST:CONTAINER` or `PORT` entry
+pub fn parse_port_spec(input: &str) -> Result<(u16, u16), String> {
+    let parse = |port: &str| match port.trim().parse::<u16>() {
+        Ok(0) | Err(_) => Err(format!("Invalid port '{}': use a number from 1 to 65535", port)),
+        Ok(port) => Ok(port),
+    };
+    match input.trim().split_once(':') {
+        Some((host, container)) => Ok((parse(host)?, parse(container)?)),
+        None => parse(input).map(|port| (port, port)),
+    }
+}
+
+/// Add a mapping, replacing any existing mapping of the same container port in place
+pub fn set_port(ports: &mut Ports, port: (u16, u16)) {
+    match ports.iter_mut().find(|(_, container)| *container == port.1) {
+        Some(entry) => *entry = port,
+        None => ports.push(port),
+    }
+}
+
+/// Ports by session, kept in `SESSION_PORTS_FILE`
+pub type SessionPortsStore = SessionMapStore<Ports>;
+
+pub const SESSION_PORTS_FILE: &str = "session_ports.json";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_parse_port_spec() {
+        assert_eq!(parse_port_spec("3000"), Ok((3000, 3000)));
+        assert_eq!(parse_port_spec(" 8080:3000 "), Ok((8080, 3000)));
+        assert!(parse_port_spec("0").is_err());
+        assert!(parse_port_spec("70000:80").is_err());
+        assert!(parse_port_spec("8080:").is_err());
+        assert!(parse_port_spec("web").unwrap_err().contains("'web'"));
+    }
+
+    #[test]
+    fn test_session_ports_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SessionPortsStore::new(temp_dir.path().join("ports.json"));
+        assert!(store.load().is_empty());
+
+        let mut ports = Ports::new();
+        set_port(&mut ports, (3000, 3000));
+        set_port(&mut ports, (5432, 5432));
+        set_port(&mut ports, (8080, 3000));
+        assert_eq!(ports, vec![(8080, 3000), (5432, 5432)]);
+
+        let all: HashMap<Uuid, Ports> = [(Uuid::new_v4(), ports)].into_iter().collect();
+        store.save(&all).unwrap();
+        assert_eq!(store.load(), all);
+    }
+}
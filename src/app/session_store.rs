@@ -0,0 +1,112 @@
+// ABOUTME: JSON file under ~/.agents-in-a-box holding per-session data across restarts
+// Shared by the env, mount, port and protection stores; only the user can read the file
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Values keyed by session id, e.g. each session's environment variables
+pub type SessionMapStore<T> = JsonStore<HashMap<Uuid, T>>;
+
+#[derive(Debug, Clone)]
+pub struct JsonStore<T> {
+    path: PathBuf,
+    contents: PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned + Default> JsonStore<T> {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            contents: PhantomData,
+        }
+    }
+
+    /// Store at `~/.agents-in-a-box/<file_name>`, or None without a home directory
+    pub fn in_home_dir(file_name: &str) -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".agents-in-a-box").join(file_name)))
+    }
+
+    /// Stored contents; a missing or unreadable file reads as empty
+    pub fn load(&self) -> T {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable {}: {}", self.path.display(), e);
+                T::default()
+            }),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed to read {}: {}", self.path.display(), e);
+                }
+                T::default()
+            }
+        }
+    }
+
+    /// Write `contents`, readable only by the user since values may hold project secrets
+    pub fn save(&self, contents: &T) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(contents).map_err(io::Error::other)?;
+        fs::write(&self.path, content)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&self.path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> SessionMapStore<T> {
+    /// Store one session's value alongside the others already saved, for callers without
+    /// the whole map loaded, like `agents-box create`
+    pub fn insert(&self, session_id: Uuid, value: T) -> io::Result<()> {
+        let mut contents = self.load();
+        contents.insert(session_id, value);
+        self.save(&contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_store_round_trip_and_unreadable_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("nested").join("store.json");
+        let store: SessionMapStore<Vec<String>> = JsonStore::new(path.clone());
+        assert!(store.load().is_empty());
+
+        let values: HashMap<Uuid, Vec<String>> =
+            [(Uuid::new_v4(), vec!["a".to_string()])].into_iter().collect();
+        store.save(&values).unwrap();
+        assert_eq!(store.load(), values);
+
+        let mut values = values;
+        let added = Uuid::new_v4();
+        store.insert(added, vec!["b".to_string()]).unwrap();
+        values.insert(added, vec!["b".to_string()]);
+        assert_eq!(store.load(), values);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::write(&path, "not json").unwrap();
+        assert!(store.load().is_empty());
+    }
+}
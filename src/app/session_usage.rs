@@ -0,0 +1,10 @@
+// ABOUTME: Token usage totals per session, kept across launches so the dashboard doesn't
+// reset to whatever is left in the log tail
+
+use crate::app::session_store::SessionMapStore;
+use crate::models::TokenUsage;
+
+/// Usage by session, kept in `SESSION_USAGE_FILE`
+pub type SessionUsageStore = SessionMapStore<TokenUsage>;
+
+pub const SESSION_USAGE_FILE: &str = "session_usage.json";
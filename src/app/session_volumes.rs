@@ -0,0 +1,102 @@
+// ABOUTME: Extra host directories mounted into a session's container, persisted for restarts
+// The worktree and auth mounts are added by the lifecycle manager and never listed here
+
+use crate::app::session_store::SessionMapStore;
+use std::path::PathBuf;
+
+/// Host path, container path, read-only
+pub type Volumes = Vec<(PathBuf, String, bool)>;
+
+/// Parse a `HOST:CONTAINER[:ro|:rw]` entry. The host path may start with `~/` and must
+/// exist; the container path must be absolute.
+pub fn parse_volume_spec(input: &str) -> Result<(PathBuf, String, bool), String> {
+    let parts: Vec<&str> = input.trim().split(':').collect();
+    let (host, container, read_only) = match parts.as_slice() {
+        [host, container] | [host, container, "rw"] => (*host, *container, false),
+        [host, container, "ro"] => (*host, *container, true),
+        [_, _, mode] => return Err(format!("Unknown mode '{}': use ro or rw", mode)),
+        _ => return Err("Use HOST:CONTAINER or HOST:CONTAINER:ro".to_string()),
+    };
+
+    let host_path = match host.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map_or_else(|| host.into(), |home| home.join(rest)),
+        None => PathBuf::from(host),
+    };
+    if !host_path.is_absolute() {
+        return Err(format!("Host path '{}' must be absolute", host));
+    }
+    if !host_path.exists() {
+        return Err(format!("Host path {} does not exist", host_path.display()));
+    }
+    if !container.starts_with('/') {
+        return Err(format!("Container path '{}' must be absolute", container));
+    }
+    Ok((host_path, container.to_string(), read_only))
+}
+
+/// Add a mount, replacing any existing mount at the same container path in place
+pub fn set_volume(volumes: &mut Volumes, volume: (PathBuf, String, bool)) {
+    match volumes.iter_mut().find(|(_, container, _)| *container == volume.1) {
+        Some(entry) => *entry = volume,
+        None => volumes.push(volume),
+    }
+}
+
+/// Mounts by session, kept in `SESSION_VOLUMES_FILE`
+pub type SessionVolumesStore = SessionMapStore<Volumes>;
+
+pub const SESSION_VOLUMES_FILE: &str = "session_volumes.json";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_parse_volume_spec() {
+        let temp_dir = TempDir::new().unwrap();
+        let host = temp_dir.path().display().to_string();
+
+        assert_eq!(
+            parse_volume_spec(&format!("{}:/cache", host)),
+            Ok((temp_dir.path().to_path_buf(), "/cache".to_string(), false))
+        );
+        assert_eq!(
+            parse_volume_spec(&format!("{}:/secrets:ro", host)),
+            Ok((temp_dir.path().to_path_buf(), "/secrets".to_string(), true))
+        );
+        assert!(parse_volume_spec(&format!("{}:/x:rx", host)).unwrap_err().contains("rx"));
+        assert!(parse_volume_spec(&format!("{}:relative", host)).is_err());
+        assert!(parse_volume_spec("relative:/x").is_err());
+        assert!(parse_volume_spec(&host).is_err());
+
+        let missing = temp_dir.path().join("missing");
+        let error = parse_volume_spec(&format!("{}:/x", missing.display())).unwrap_err();
+        assert!(error.contains("does not exist"));
+    }
+
+    #[test]
+    fn test_session_volumes_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SessionVolumesStore::new(temp_dir.path().join("nested").join("volumes.json"));
+        assert!(store.load().is_empty());
+
+        let mut volumes = Volumes::new();
+        set_volume(&mut volumes, (PathBuf::from("/a"), "/cache".to_string(), false));
+        set_volume(&mut volumes, (PathBuf::from("/b"), "/creds".to_string(), true));
+        set_volume(&mut volumes, (PathBuf::from("/c"), "/cache".to_string(), true));
+        assert_eq!(
+            volumes,
+            vec![
+                (PathBuf::from("/c"), "/cache".to_string(), true),
+                (PathBuf::from("/b"), "/creds".to_string(), true)
+            ]
+        );
+
+        let all: HashMap<Uuid, Volumes> = [(Uuid::new_v4(), volumes)].into_iter().collect();
+        store.save(&all).unwrap();
+        assert_eq!(store.load(), all);
+    }
+}
@@ -0,0 +1,96 @@
+// ABOUTME: Stops running sessions' containers on exit when stop_sessions_on_exit is set
+// Bounded by a timeout so an unresponsive docker daemon can't keep the app from exiting
+
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// How long exit waits for containers to stop before leaving the rest running
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Stop each `(session name, container id)` with `stop`, all at once, giving up after
+/// `timeout`. Returns the names of sessions whose containers may still be running.
+pub async fn stop_containers<F, Fut, E>(
+    containers: Vec<(String, String)>,
+    timeout: Duration,
+    stop: F,
+) -> Vec<String>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    let names: Vec<String> = containers.iter().map(|(name, _)| name.clone()).collect();
+    let mut pending: FuturesUnordered<_> = containers
+        .into_iter()
+        .map(|(name, container_id)| {
+            let stopping = stop(container_id);
+            async move { (name, stopping.await) }
+        })
+        .collect();
+
+    let mut stopped = Vec::new();
+    let drain = async {
+        while let Some((name, result)) = pending.next().await {
+            match result {
+                Ok(()) => stopped.push(name),
+                Err(e) => warn!("Failed to stop session '{}' on exit: {}", name, e),
+            }
+        }
+    };
+    if tokio::time::timeout(timeout, drain).await.is_err() {
+        warn!("Gave up stopping containers on exit after {:?}", timeout);
+    }
+
+    names.into_iter().filter(|name| !stopped.contains(name)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_stop_containers_reports_failures() {
+        let asked = Mutex::new(Vec::new());
+        let containers = vec![
+            ("api".to_string(), "c1".to_string()),
+            ("docs".to_string(), "c2".to_string()),
+        ];
+
+        let left = stop_containers(containers, SHUTDOWN_TIMEOUT, |id| {
+            asked.lock().unwrap().push(id.clone());
+            async move {
+                match id.as_str() {
+                    "c2" => Err("no such container"),
+                    _ => Ok(()),
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(*asked.lock().unwrap(), vec!["c1", "c2"]);
+        assert_eq!(left, vec!["docs"]);
+    }
+
+    #[tokio::test]
+    async fn test_stop_containers_gives_up_on_a_hung_daemon() {
+        let containers = vec![
+            ("api".to_string(), "c1".to_string()),
+            ("hung".to_string(), "c2".to_string()),
+        ];
+
+        let left = stop_containers(containers, Duration::from_millis(50), |id| async move {
+            if id == "c2" {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+            }
+            Ok::<(), String>(())
+        })
+        .await;
+
+        // The stop that finished counts; the one still waiting is reported as left running
+        assert_eq!(left, vec!["hung"]);
+    }
+}
@@ -2,37 +2,91 @@
 
 #![allow(dead_code)]
 
-use crate::app::SessionLoader;
+use crate::app::{SessionLoader, SessionManager};
+use crate::app::alerts::SessionAlert;
+use crate::app::chat_history::{ChatHistoryStore, SavedChat};
+use crate::app::session_manager::SessionHealth;
 use crate::claude::client::ClaudeChatManager;
-use crate::claude::types::ClaudeStreamingEvent;
-use crate::claude::{ClaudeApiClient, ClaudeMessage};
+use crate::claude::types::{ClaudeRole, ClaudeStreamingEvent};
+use crate::claude::{ClaudeApiClient, ClaudeAuth, ClaudeMessage};
 use crate::components::fuzzy_file_finder::FuzzyFileFinderState;
 use crate::components::live_logs_stream::LogEntry;
+use crate::config::AuthProfiles;
 use crate::docker::LogStreamingCoordinator;
+use crate::docker::session_lifecycle::{SessionLifecycleError, SessionState};
 use crate::models::{Session, Workspace};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 use chrono;
-use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// How often the open dashboard recomputes its summary
+const DASHBOARD_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often session git changes and ahead/behind counts are recomputed
+const GIT_STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Notifications kept for the history viewer after their toasts expire
+const NOTIFICATION_HISTORY_LIMIT: usize = 100;
+
+/// How often the header's auth indicator re-reads the stored credentials
+const AUTH_CREDENTIAL_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often the status line's container CPU/memory totals are resampled
+const CONTAINER_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Bounds and step for the session list's share of the width
+const MIN_SESSION_LIST_PERCENT: u16 = 15;
+const MAX_SESSION_LIST_PERCENT: u16 = 85;
+const SESSION_LIST_PERCENT_STEP: u16 = 5;
+
+/// UI tick interval while there is input, streaming, or pending work
+pub const ACTIVE_TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Start of the notification kept on screen while the agents-dev image builds
+const IMAGE_BUILD_MESSAGE: &str = "🏗️ Building agents-dev image";
+const IMAGE_BUILD_RUNNING: &str = "⏳ The agents-dev image is still being built";
+
+/// Most undo steps kept by the boss prompt editor
+const TEXT_EDITOR_HISTORY_LIMIT: usize = 100;
+
+/// Editor contents and cursor, as saved for undo/redo
+#[derive(Debug, Clone)]
+struct EditorSnapshot {
+    lines: Vec<String>,
+    cursor_line: usize,
+    cursor_col: usize,
+}
+
 /// Text editor with cursor support for boss mode prompts
 #[derive(Debug, Clone)]
 pub struct TextEditor {
     lines: Vec<String>,
     cursor_line: usize,
     cursor_col: usize,
+    undo_stack: Vec<EditorSnapshot>,
+    redo_stack: Vec<EditorSnapshot>,
+    typing_at: Option<(usize, usize)>, // Cursor after the last typed character, to merge typing into one undo step
 }
 
 impl TextEditor {
     pub fn new() -> Self {
+        Self::from_lines(vec![String::new()])
+    }
+
+    fn from_lines(lines: Vec<String>) -> Self {
         Self {
-            lines: vec![String::new()],
+            lines,
             cursor_line: 0,
             cursor_col: 0,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            typing_at: None,
         }
     }
 
@@ -43,13 +97,62 @@ impl TextEditor {
             text.lines().map(|s| s.to_string()).collect()
         };
 
-        Self {
-            lines,
-            cursor_line: 0,
-            cursor_col: 0,
+        Self::from_lines(lines)
+    }
+
+    fn snapshot(&self) -> EditorSnapshot {
+        EditorSnapshot {
+            lines: self.lines.clone(),
+            cursor_line: self.cursor_line,
+            cursor_col: self.cursor_col,
         }
     }
 
+    fn restore(&mut self, snapshot: EditorSnapshot) {
+        self.lines = snapshot.lines;
+        self.cursor_line = snapshot.cursor_line;
+        self.cursor_col = snapshot.cursor_col;
+        self.typing_at = None;
+    }
+
+    /// Save the current state as an undo step before an edit; any redo history is dropped
+    fn push_undo(&mut self) {
+        if self.undo_stack.len() == TEXT_EDITOR_HISTORY_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(self.snapshot());
+        self.redo_stack.clear();
+        self.typing_at = None;
+    }
+
+    /// Replace the whole text as one undoable edit, with the cursor at the start
+    pub fn set_text(&mut self, text: &str) {
+        self.push_undo();
+        self.lines = Self::from_string(text).lines;
+        self.cursor_line = 0;
+        self.cursor_col = 0;
+    }
+
+    /// Revert the last edit; returns false when there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    /// Reapply the last undone edit; returns false when there is nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
     pub fn to_string(&self) -> String {
         self.lines.join("\n")
     }
@@ -58,17 +161,36 @@ impl TextEditor {
         self.lines.len() == 1 && self.lines[0].is_empty()
     }
 
+    /// Characters (not bytes) in `to_string()`, newlines included
+    pub fn char_count(&self) -> usize {
+        self.lines.iter().map(|line| line.chars().count()).sum::<usize>() + self.lines.len() - 1
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
     pub fn insert_char(&mut self, ch: char) {
         if ch == '\n' {
             self.insert_newline();
         } else {
+            // Consecutive typing at the same spot is a single undo step
+            if self.typing_at != Some((self.cursor_line, self.cursor_col)) {
+                self.push_undo();
+            }
             let line = &mut self.lines[self.cursor_line];
             line.insert(self.cursor_col, ch);
             self.cursor_col += 1;
+            self.typing_at = Some((self.cursor_line, self.cursor_col));
         }
     }
 
     pub fn insert_newline(&mut self) {
+        self.push_undo();
+        self.split_line();
+    }
+
+    fn split_line(&mut self) {
         let current_line = self.lines[self.cursor_line].clone();
         let (left, right) = current_line.split_at(self.cursor_col);
 
@@ -80,6 +202,9 @@ impl TextEditor {
     }
 
     pub fn backspace(&mut self) {
+        if self.cursor_col > 0 || self.cursor_line > 0 {
+            self.push_undo();
+        }
         if self.cursor_col > 0 {
             // Delete character before cursor
             self.lines[self.cursor_line].remove(self.cursor_col - 1);
@@ -137,6 +262,7 @@ impl TextEditor {
         if text.is_empty() {
             return;
         }
+        self.push_undo();
 
         let mut lines = text.lines();
 
@@ -148,7 +274,7 @@ impl TextEditor {
 
         // Insert newlines and subsequent lines
         for line in lines {
-            self.insert_newline();
+            self.split_line();
             self.lines[self.cursor_line].insert_str(self.cursor_col, line);
             self.cursor_col += line.len();
         }
@@ -176,6 +302,122 @@ impl TextEditor {
         }
     }
 
+    /// Byte offset of the cursor in `to_string()`
+    pub fn cursor_offset(&self) -> usize {
+        self.lines[..self.cursor_line].iter().map(|line| line.len() + 1).sum::<usize>()
+            + self.cursor_col
+    }
+
+    /// Move the cursor to a byte offset in `to_string()`, clamped to the text
+    pub fn set_cursor_offset(&mut self, offset: usize) {
+        let mut remaining = offset;
+        for (index, line) in self.lines.iter().enumerate() {
+            if remaining <= line.len() || index == self.lines.len() - 1 {
+                self.cursor_line = index;
+                self.cursor_col = remaining.min(line.len());
+                return;
+            }
+            remaining -= line.len() + 1;
+        }
+    }
+
+    // Soft-wrap methods. Lines are split into visual rows of at most `width` characters,
+    // breaking after the last space that fits; the line model itself is untouched.
+
+    /// Byte ranges of the rows `line` wraps to
+    fn wrap_ranges(line: &str, width: usize) -> Vec<Range<usize>> {
+        let width = width.max(1);
+        let mut rows = Vec::new();
+        let mut start = 0;
+        loop {
+            let rest = &line[start..];
+            let Some((limit, _)) = rest.char_indices().nth(width) else {
+                rows.push(start..line.len());
+                return rows;
+            };
+            let end = rest[..limit]
+                .rfind(' ')
+                .filter(|&space| space > 0)
+                .map_or(limit, |space| space + 1);
+            rows.push(start..start + end);
+            start += end;
+        }
+    }
+
+    /// Visual rows as (line index, byte range within that line)
+    pub fn visual_rows(&self, width: usize) -> Vec<(usize, Range<usize>)> {
+        self.lines
+            .iter()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                Self::wrap_ranges(line, width).into_iter().map(move |range| (index, range))
+            })
+            .collect()
+    }
+
+    /// Cursor position as (index into `visual_rows`, character column within the row)
+    pub fn visual_cursor(&self, width: usize) -> (usize, usize) {
+        let rows_before: usize = self.lines[..self.cursor_line]
+            .iter()
+            .map(|line| Self::wrap_ranges(line, width).len())
+            .sum();
+        let (row, column) = self.cursor_row(width);
+        (rows_before + row, column)
+    }
+
+    /// Row of the current line holding the cursor, and the cursor's column in it
+    fn cursor_row(&self, width: usize) -> (usize, usize) {
+        let line = &self.lines[self.cursor_line];
+        let rows = Self::wrap_ranges(line, width);
+        // A cursor at a row's end sits at the start of the next row
+        let row = rows
+            .iter()
+            .position(|range| self.cursor_col < range.end)
+            .unwrap_or(rows.len() - 1);
+        let column = line.get(rows[row].start..self.cursor_col).map_or(0, |s| s.chars().count());
+        (row, column)
+    }
+
+    /// Byte offset for `column` in row `row` of `line`, kept before the row's end unless
+    /// it is the line's last row
+    fn offset_in_row(line: &str, width: usize, row: usize, column: usize) -> usize {
+        let rows = Self::wrap_ranges(line, width);
+        let range = rows[row].clone();
+        let text = &line[range.clone()];
+        let chars = text.chars().count();
+        let max = if row + 1 == rows.len() {
+            chars
+        } else {
+            chars.saturating_sub(1)
+        };
+        range.start + text.char_indices().nth(column.min(max)).map_or(text.len(), |(i, _)| i)
+    }
+
+    pub fn move_cursor_up_wrapped(&mut self, width: usize) {
+        let (row, column) = self.cursor_row(width);
+        if row > 0 {
+            self.cursor_col =
+                Self::offset_in_row(&self.lines[self.cursor_line], width, row - 1, column);
+        } else if self.cursor_line > 0 {
+            self.cursor_line -= 1;
+            let line = &self.lines[self.cursor_line];
+            let last_row = Self::wrap_ranges(line, width).len() - 1;
+            self.cursor_col = Self::offset_in_row(line, width, last_row, column);
+        }
+    }
+
+    pub fn move_cursor_down_wrapped(&mut self, width: usize) {
+        let (row, column) = self.cursor_row(width);
+        let rows = Self::wrap_ranges(&self.lines[self.cursor_line], width).len();
+        if row + 1 < rows {
+            self.cursor_col =
+                Self::offset_in_row(&self.lines[self.cursor_line], width, row + 1, column);
+        } else if self.cursor_line < self.lines.len() - 1 {
+            self.cursor_line += 1;
+            self.cursor_col = Self::offset_in_row(&self.lines[self.cursor_line], width, 0, column);
+        }
+    }
+
     // Word movement methods
     pub fn move_cursor_word_forward(&mut self) {
         let current_line = &self.lines[self.cursor_line];
@@ -257,6 +499,7 @@ impl TextEditor {
         if start_pos >= chars.len() {
             return;
         }
+        self.push_undo();
 
         let mut end_pos = start_pos;
 
@@ -284,6 +527,7 @@ impl TextEditor {
         if self.cursor_col == 0 {
             return;
         }
+        self.push_undo();
 
         let current_line_text = self.lines[self.cursor_line].clone();
         let chars: Vec<char> = current_line_text.chars().collect();
@@ -336,48 +580,79 @@ pub struct Notification {
     pub notification_type: NotificationType,
     pub created_at: Instant,
     pub duration: Duration,
+    pub sticky: bool, // Stays on screen until dismissed, whatever the duration
+    pub count: u32,   // How many identical notifications arrived in a row
 }
 
 impl Notification {
+    /// Level constructors use the default configuration; `AppState` applies the user's
     pub fn success(message: String) -> Self {
-        Self {
-            message,
-            notification_type: NotificationType::Success,
-            created_at: Instant::now(),
-            duration: Duration::from_secs(3),
-        }
+        Self::with_defaults(NotificationType::Success, message)
     }
 
     pub fn error(message: String) -> Self {
-        Self {
-            message,
-            notification_type: NotificationType::Error,
-            created_at: Instant::now(),
-            duration: Duration::from_secs(5),
-        }
+        Self::with_defaults(NotificationType::Error, message)
     }
 
     pub fn info(message: String) -> Self {
-        Self {
-            message,
-            notification_type: NotificationType::Info,
-            created_at: Instant::now(),
-            duration: Duration::from_secs(3),
-        }
+        Self::with_defaults(NotificationType::Info, message)
     }
 
     pub fn warning(message: String) -> Self {
+        Self::with_defaults(NotificationType::Warning, message)
+    }
+
+    fn with_defaults(notification_type: NotificationType, message: String) -> Self {
+        let config = crate::config::NotificationConfig::default();
+        Self::configured(notification_type, message, &config)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        !self.sticky && self.created_at.elapsed() > self.duration
+    }
+
+    /// The message with a repeat count like "(x3)" once it has arrived more than once
+    pub fn display_message(&self) -> String {
+        if self.count > 1 {
+            format!("{} (x{})", self.message, self.count)
+        } else {
+            self.message.clone()
+        }
+    }
+
+    /// Whether `other` repeats this notification exactly
+    fn is_repeated_by(&self, other: &Notification) -> bool {
+        self.notification_type == other.notification_type && self.message == other.message
+    }
+
+    /// A notification of `notification_type` with the configured duration and stickiness
+    pub fn configured(
+        notification_type: NotificationType,
+        message: String,
+        config: &crate::config::NotificationConfig,
+    ) -> Self {
+        let (secs, sticky) = match notification_type {
+            NotificationType::Success => (config.success_secs, false),
+            NotificationType::Info => (config.info_secs, false),
+            NotificationType::Warning => (config.warning_secs, false),
+            NotificationType::Error => (config.error_secs, config.sticky_errors),
+        };
         Self {
             message,
-            notification_type: NotificationType::Warning,
+            notification_type,
             created_at: Instant::now(),
-            duration: Duration::from_secs(4),
+            duration: Duration::from_secs(secs),
+            sticky,
+            count: 1,
         }
     }
+}
 
-    pub fn is_expired(&self) -> bool {
-        self.created_at.elapsed() > self.duration
-    }
+/// A notification as it appears in the history viewer, with the wall-clock time it arrived
+#[derive(Debug, Clone)]
+pub struct ArchivedNotification {
+    pub notification: Notification,
+    pub received_at: chrono::DateTime<chrono::Local>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -399,6 +674,27 @@ pub enum View {
     AuthSetup,  // New view for authentication setup
     ClaudeChat, // Claude chat popup overlay
     GitView,    // Git status and diff view
+    Dashboard,  // At-a-glance summary of all sessions
+}
+
+impl View {
+    /// Name shown in the status line
+    pub fn label(&self) -> &'static str {
+        match self {
+            View::SessionList => "Sessions",
+            View::Logs => "Logs",
+            View::Terminal => "Terminal",
+            View::Help => "Help",
+            View::NewSession => "New session",
+            View::SearchWorkspace => "Search",
+            View::NonGitNotification => "Not a git repo",
+            View::AttachedTerminal => "Attached",
+            View::AuthSetup => "Auth setup",
+            View::ClaudeChat => "Chat",
+            View::GitView => "Git",
+            View::Dashboard => "Dashboard",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -412,7 +708,15 @@ pub struct ConfirmationDialog {
 #[derive(Debug, Clone)]
 pub enum ConfirmAction {
     DeleteSession(Uuid),
+    StopKeepWorktree(Uuid), // Remove the session's container but keep its worktree
+    CleanupDeadSessions(Vec<Uuid>), // Delete every listed stopped/errored session
     KillOtherTmux(String), // Kill a non-agents-in-a-box tmux session by name
+    CommitAllChanges,      // Git view commit with no files selected: commit and push everything
+    DiscardChanges(Option<Vec<String>>), // Git view discard of these paths, or of everything
+    BuildAgentsDevImage(Box<AsyncAction>), // Build the missing image, then retry the action that needed it
+    Logout,                // Revoke the refresh token and remove stored credentials
+    RestartSession(Uuid),  // Restart a session whose tmux session has exited
+    QuitApp,               // Quit while sessions are running
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -426,6 +730,8 @@ pub enum AuthMethod {
 pub struct AuthSetupState {
     pub selected_method: AuthMethod,
     pub api_key_input: String,
+    pub validating: bool,      // API key is being checked against the Anthropic API
+    pub skip_validation: bool, // Offline bypass: save the key without the online check
     pub is_processing: bool,
     pub error_message: Option<String>,
     pub show_cursor: bool,
@@ -439,6 +745,7 @@ pub struct ClaudeChatState {
     pub current_streaming_response: Option<String>,
     pub associated_session_id: Option<Uuid>,
     pub total_tokens_used: u32,
+    pub token_budget: Option<u32>, // Sends are blocked once usage reaches this (None = unlimited)
     pub last_activity: chrono::DateTime<chrono::Utc>,
 }
 
@@ -451,10 +758,100 @@ impl ClaudeChatState {
             current_streaming_response: None,
             associated_session_id: None,
             total_tokens_used: 0,
+            token_budget: None,
             last_activity: chrono::Utc::now(),
         }
     }
 
+    /// The saved conversation for this association, or an empty one
+    pub fn load(store: &ChatHistoryStore, session_id: Option<Uuid>) -> Self {
+        let saved = store.load(session_id);
+        Self {
+            messages: saved.as_ref().map(|chat| chat.messages.clone()).unwrap_or_default(),
+            total_tokens_used: saved.map_or(0, |chat| chat.total_tokens_used),
+            associated_session_id: session_id,
+            ..Self::new()
+        }
+    }
+
+    /// Write the completed messages; a reply still streaming and its prompt are left out
+    pub fn save(&self, store: &ChatHistoryStore) -> std::io::Result<()> {
+        let mut messages = self.messages.clone();
+        if self.is_streaming {
+            messages.pop();
+        }
+        store.save(&SavedChat {
+            associated_session_id: self.associated_session_id,
+            total_tokens_used: self.total_tokens_used,
+            messages,
+        })
+    }
+
+    /// The conversation as Markdown: a header with the session, model, export time and
+    /// token count, then each message under a role heading. Message text is copied as
+    /// is, so code fences survive.
+    pub fn export_markdown(&self, model: &str) -> String {
+        let session = self
+            .associated_session_id
+            .map_or_else(|| "none (global chat)".to_string(), |id| id.to_string());
+        let mut out = String::from("# Claude Chat\n\n");
+        out.push_str(&format!("- Session: {}\n", session));
+        out.push_str(&format!("- Model: {}\n", model));
+        out.push_str(&format!(
+            "- Exported: {}\n",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S %:z")
+        ));
+        out.push_str(&format!("- Total tokens: {}\n", self.total_tokens_used));
+
+        for message in &self.messages {
+            let role = match message.role {
+                ClaudeRole::User => "User",
+                ClaudeRole::Assistant => "Assistant",
+            };
+            out.push_str(&format!("\n## {}\n\n", role));
+            out.push_str(message.content.trim_end());
+            out.push('\n');
+            if message.interrupted {
+                out.push_str("\n_(interrupted)_\n");
+            }
+        }
+        out
+    }
+
+    /// Why a new message can't be sent, when the token budget is used up
+    pub fn budget_check(&self) -> Result<(), String> {
+        match self.token_budget {
+            Some(budget) if self.total_tokens_used >= budget => Err(format!(
+                "Chat token budget reached ({} of {} tokens). \
+                 Press Ctrl+B to raise it or Ctrl+R to reset the count.",
+                self.total_tokens_used, budget
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Count the tokens a streamed response reports: the prompt with `message_start`,
+    /// the generated output with the final `message_delta`
+    pub fn record_stream_usage(&mut self, event: &ClaudeStreamingEvent) {
+        let tokens = match event {
+            ClaudeStreamingEvent::MessageStart { message } => message.usage.input_tokens,
+            ClaudeStreamingEvent::MessageDelta { usage: Some(usage), .. } => usage.output_tokens,
+            _ => return,
+        };
+        self.total_tokens_used = self.total_tokens_used.saturating_add(tokens);
+    }
+
+    pub fn reset_token_usage(&mut self) {
+        self.total_tokens_used = 0;
+    }
+
+    /// Allow another `step` tokens beyond the current budget
+    pub fn raise_token_budget(&mut self, step: u32) {
+        if let Some(budget) = self.token_budget.as_mut() {
+            *budget = (*budget).max(self.total_tokens_used).saturating_add(step);
+        }
+    }
+
     pub fn add_message(&mut self, message: ClaudeMessage) {
         self.messages.push(message);
         self.last_activity = chrono::Utc::now();
@@ -482,6 +879,15 @@ impl ClaudeChatState {
         self.is_streaming = false;
     }
 
+    /// Keep whatever part of the reply arrived, marked as interrupted
+    pub fn interrupt_streaming(&mut self) {
+        if let Some(response) = self.current_streaming_response.take() {
+            let reply = ClaudeMessage { interrupted: true, ..ClaudeMessage::assistant(response) };
+            self.add_message(reply);
+        }
+        self.is_streaming = false;
+    }
+
     pub fn clear_input(&mut self) {
         self.input_buffer.clear();
     }
@@ -499,6 +905,117 @@ impl ClaudeChatState {
     }
 }
 
+/// A chat reply being streamed by a background task
+#[derive(Debug)]
+pub struct ChatStream {
+    pub task: tokio::task::JoinHandle<()>,
+    pub events: mpsc::UnboundedReceiver<Result<ClaudeStreamingEvent, String>>,
+}
+
+/// A session container being created by a background task, so the UI keeps drawing its
+/// build log and progress bar meanwhile
+#[derive(Debug)]
+pub struct ContainerCreation {
+    pub task: tokio::task::JoinHandle<Result<SessionState, String>>,
+    pub log_receiver: mpsc::UnboundedReceiver<String>,
+}
+
+impl ContainerCreation {
+    /// Run `create` in the background, handing it the sender for its build log
+    fn spawn<F>(create: impl FnOnce(mpsc::UnboundedSender<String>) -> F) -> Self
+    where
+        F: std::future::Future<Output = Result<SessionState, SessionLifecycleError>>
+            + Send
+            + 'static,
+    {
+        let (log_sender, log_receiver) = mpsc::unbounded_channel();
+        let creation = create(log_sender);
+        Self {
+            task: tokio::spawn(async move { creation.await.map_err(|e| e.to_string()) }),
+            log_receiver,
+        }
+    }
+}
+
+/// A new session whose container is still being created. `poll_session_creation`
+/// finishes it once the container is up.
+#[derive(Debug)]
+pub struct SessionCreation {
+    pub container: ContainerCreation,
+    pub restart_mode: Option<crate::models::SessionMode>, // Set when restarting a session
+    pub context: SessionCreationContext,
+}
+
+/// What finishing a new session needs from the dialog it was created in
+#[derive(Debug, Clone)]
+pub struct SessionCreationContext {
+    pub session_id: Uuid,
+    pub repo_path: PathBuf,
+    pub branch_name: String, // As entered or generated; keys the prompt draft
+    pub session_branch: String,
+    pub session_mode: crate::models::SessionMode,
+    pub has_boss_prompt: bool,
+    pub branch_name_generated: bool,
+    pub base_branch: Option<String>,
+    pub restart_session_id: Option<Uuid>,
+}
+
+/// The agents-dev image being built in the background; `then` runs once it's built
+#[derive(Debug)]
+pub struct ImageBuild {
+    pub task: tokio::task::JoinHandle<Result<(), String>>,
+    pub progress: mpsc::Receiver<crate::docker::AgentsDevProgress>,
+    pub then: AsyncAction,
+}
+
+/// The credential sessions authenticate with, as shown in the header
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AuthCredential {
+    #[default]
+    None,
+    ApiKey,
+    OAuth(chrono::DateTime<chrono::Utc>), // When the access token expires
+}
+
+impl AuthCredential {
+    /// "token expires in 12m", "API key", ... or None when nothing is configured
+    pub fn label(&self, now: chrono::DateTime<chrono::Utc>) -> Option<String> {
+        match self {
+            AuthCredential::None => None,
+            AuthCredential::ApiKey => Some("API key".to_string()),
+            AuthCredential::OAuth(expires_at) if *expires_at <= now => {
+                Some("token expired".to_string())
+            }
+            AuthCredential::OAuth(expires_at) => {
+                let minutes = (*expires_at - now).num_minutes();
+                Some(if minutes < 60 {
+                    format!("token expires in {}m", minutes)
+                } else {
+                    format!("token expires in {}h {:02}m", minutes / 60, minutes % 60)
+                })
+            }
+        }
+    }
+}
+
+/// The auth profile switcher popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthProfilePickerState {
+    pub profiles: Vec<String>,
+    pub active: String,
+    pub selected: usize,
+}
+
+/// OAuth token state shared between background refreshes and session creation
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AuthStatus {
+    #[default]
+    Unknown, // Not checked yet, or no OAuth credentials in use
+    Valid,
+    Refreshing, // Token expired and a refresh is in progress
+    RefreshFailed(String),
+}
+
 #[derive(Debug)]
 pub struct AppState {
     pub workspaces: Vec<Workspace>,
@@ -539,20 +1056,71 @@ pub struct AppState {
     pub last_log_check: Option<std::time::Instant>,
     // Track the last time we checked for OAuth token refresh
     pub last_token_refresh_check: Option<std::time::Instant>,
+    // Current OAuth token state (drives the header indicator)
+    pub auth_status: AuthStatus,
+    // Credential in use and when it was last read from disk
+    pub auth_credential: AuthCredential,
+    pub last_auth_credential_check: Option<Instant>,
+    // Auth profile whose credentials new sessions mount, and the switcher popup
+    pub auth_profile: String,
+    pub auth_profile_picker: Option<AuthProfilePickerState>,
+    // Token expiry the upcoming-refresh notice was shown for, so it is shown once per token
+    pub oauth_refresh_notice_for: Option<chrono::DateTime<chrono::Utc>>,
+    // In-flight background OAuth refresh, awaited by session creation
+    pub auth_refresh_task: Option<tokio::task::JoinHandle<Result<(), String>>>,
     // Claude chat integration
     pub claude_chat_state: Option<ClaudeChatState>,
     // Live logs from Docker containers
     pub live_logs: HashMap<Uuid, Vec<LogEntry>>,
+    // Token usage totals per session, from the agents' usage events, persisted across launches
+    pub session_usage: HashMap<Uuid, crate::models::TokenUsage>,
+    pub session_usage_store: Option<crate::app::session_usage::SessionUsageStore>,
+    // Latest MCP server status reported by each session's agent
+    pub session_mcp_servers: HashMap<Uuid, Vec<crate::agent_parsers::McpServerInfo>>,
     // Claude API client manager (when initialized)
     pub claude_manager: Option<ClaudeChatManager>,
     // Docker log streaming coordinator
     pub log_streaming_coordinator: Option<LogStreamingCoordinator>,
     // Channel sender for log streaming
     pub log_sender: Option<mpsc::UnboundedSender<(Uuid, LogEntry)>>,
+    // Latest image build step or layer pull parsed from the session being created
+    pub build_progress: Option<crate::docker::BuildProgress>,
+    // Session whose container is being created in the background
+    pub session_creation: Option<SessionCreation>,
+    // agents-dev image build reporting its steps in the notification area
+    pub image_build: Option<ImageBuild>,
     // Git view state
     pub git_view_state: Option<crate::components::GitViewState>,
+    // Dashboard (None unless the dashboard view is open) and its background disk measurement
+    pub dashboard: Option<crate::components::DashboardState>,
+    pub last_dashboard_refresh: Option<Instant>,
+    pub worktree_disk_task: Option<tokio::task::JoinHandle<u64>>,
+    pub worktree_disk_bytes: Option<u64>,
+    // Background recount of each session's git changes and ahead/behind commits
+    pub git_status_task: Option<tokio::task::JoinHandle<Vec<(Uuid, crate::models::GitChanges)>>>,
+    pub last_git_status_refresh: Option<Instant>,
+    // Combined CPU/memory of running session containers for the status line (None = unknown)
+    pub container_usage_task:
+        Option<tokio::task::JoinHandle<Option<crate::docker::ContainerUsage>>>,
+    pub container_usage: Option<crate::docker::ContainerUsage>,
+    pub last_container_usage_refresh: Option<Instant>,
+    // Background check that running sessions' containers are still up (None interval = off)
+    pub health_check_task:
+        Option<tokio::task::JoinHandle<HashMap<String, crate::docker::ContainerStatus>>>,
+    pub health_check_interval: Option<Duration>,
+    pub last_health_check: Option<Instant>,
+    pub auto_restart: bool,
+    pub auto_restart_max_attempts: u32,
+    // Automatic restarts per session since its container was last seen up
+    pub restart_attempts: HashMap<Uuid, u32>,
     // Notification system
     pub notifications: Vec<Notification>,
+    // Last NOTIFICATION_HISTORY_LIMIT notifications, oldest first, kept after they expire
+    pub notification_history: VecDeque<ArchivedNotification>,
+    // Scroll offset of the notification history viewer (Some while it is open)
+    pub notification_history_scroll: Option<usize>,
+    // Per-type durations and whether errors stay until dismissed
+    pub notification_config: crate::config::NotificationConfig,
     // Pending event to be processed in next loop iteration
     pub pending_event: Option<crate::app::events::AppEvent>,
 
@@ -560,6 +1128,9 @@ pub struct AppState {
     pub quick_commit_message: Option<String>, // None = not in quick commit mode, Some = message being entered
     pub quick_commit_cursor: usize,           // Cursor position in quick commit message
 
+    // Log export dialog state (None = closed)
+    pub log_export: Option<crate::app::log_export::LogExportPrompt>,
+
     // Tmux integration
     pub tmux_sessions: HashMap<Uuid, crate::tmux::TmuxSession>,
     pub preview_update_task: Option<tokio::task::JoinHandle<()>>,
@@ -568,6 +1139,106 @@ pub struct AppState {
     pub other_tmux_sessions: Vec<crate::models::OtherTmuxSession>,
     pub other_tmux_expanded: bool,
     pub selected_other_tmux_index: Option<usize>,
+
+    // Soft limit on sessions per workspace before a warning badge is shown (None = disabled)
+    pub session_warning_threshold: Option<usize>,
+    // External diff tool command from config (None = use built-in git view)
+    pub diff_tool_command: Option<String>,
+    // Boss prompt review popup (None = not shown)
+    pub prompt_viewer: Option<crate::components::PromptViewerState>,
+    // Files differing from the previous run of the same boss prompt (None = not shown)
+    pub run_compare: Option<crate::components::RunCompareState>,
+    // What Escape does on the session list once nothing is left to close
+    pub escape_policy: crate::config::EscapePolicy,
+    // Whether running sessions' containers are stopped when the app exits
+    pub stop_sessions_on_exit: bool,
+    // Session list keybindings, defaults overridden by ~/.agents-in-a-box/keys.toml
+    pub keymap: crate::config::Keymap,
+    // Colors for the session list, live logs and notifications (ui_preferences.theme + [theme])
+    pub theme: crate::config::Theme,
+    // Minimum (width, height) below which the UI is replaced by a resize message
+    pub min_terminal_size: (u16, u16),
+    // Share of the width given to the session list (Ctrl+Left/Right to adjust)
+    pub session_list_percent: u16,
+    // Order of sessions within each workspace (cycled with 'o'); saved to the user config
+    // only when persistence is on, so tests never touch it
+    pub session_sort_mode: crate::config::SessionSortMode,
+    pub persist_session_sort: bool,
+    // Case-insensitive substring filter over all sessions ('/'); typing goes into it while editing
+    pub session_filter: Option<String>,
+    pub session_filter_editing: bool,
+    // Live logs timestamp column (toggled with 't', defaults from config)
+    pub show_log_timestamps: bool,
+    // Live logs show ANSI colors from agent output; off strips them ('A', defaults from config)
+    pub log_ansi_colors: bool,
+    pub log_timestamp_format: crate::components::TimestampFormat,
+    // Boss prompt draft auto-save (None = disabled in config)
+    pub prompt_drafts: Option<crate::app::prompt_drafts::PromptDraftStore>,
+    pub prompt_draft_interval: Duration,
+    pub last_prompt_draft_save: Option<Instant>,
+    // Sessions protected from bulk operations, persisted across restarts
+    pub protected_sessions: HashSet<Uuid>,
+    pub protected_store: Option<crate::app::protected_sessions::ProtectedSessionStore>,
+    pub session_persistence: Option<crate::app::SessionPersistence>,
+    // Container environment variables per session, persisted so restarts reuse them
+    pub session_env: HashMap<Uuid, crate::app::session_env::EnvVars>,
+    pub session_env_store: Option<crate::app::session_env::SessionEnvStore>,
+    // Extra host directories mounted per session, persisted so restarts reuse them
+    pub session_volumes: HashMap<Uuid, crate::app::session_volumes::Volumes>,
+    pub session_volumes_store: Option<crate::app::session_volumes::SessionVolumesStore>,
+    // Ports published per session, persisted so restarts reuse them
+    pub session_ports: HashMap<Uuid, crate::app::session_ports::Ports>,
+    pub session_ports_store: Option<crate::app::session_ports::SessionPortsStore>,
+    // Images picked with `agents-box create --image`, so restarts recreate on the same one
+    pub session_images: HashMap<Uuid, String>,
+    pub session_images_store: Option<crate::app::session_images::SessionImagesStore>,
+    // Boss prompt pastes above this many bytes ask how to insert them (0 = always inline)
+    pub large_paste_threshold: usize,
+    // Live log entries kept in memory per session; older ones go to the spill store
+    pub max_log_entries: usize,
+    pub log_spill: Option<crate::app::log_spill::LogSpillStore>,
+    // Soft-wrap the boss prompt editor; up/down then move by rows of the last rendered width
+    pub prompt_soft_wrap: bool,
+    pub prompt_wrap_width: Option<usize>,
+    // Boss prompt sizes (soft, hard) in characters where the editor's counter warns
+    pub prompt_char_limits: (usize, usize),
+    // Branch prefix for Boss sessions named after their prompt (None = keep generated names)
+    pub prompt_branch_prefix: Option<String>,
+    // Generated branch prefix and preselected settings for new sessions
+    pub branch_prefix: String,
+    pub default_session_mode: crate::models::SessionMode,
+    pub default_skip_permissions: bool,
+    // Claude model preselected for new sessions (None = the CLI's default), the models the
+    // new session dialog cycles through, and the model the chat popup sends requests to
+    pub default_model: Option<String>,
+    pub model_choices: Vec<String>,
+    pub chat_model: String,
+    // Tokens a chat conversation may use before sends are blocked, also the step the
+    // budget is raised by (None = unlimited)
+    pub chat_token_budget: Option<u32>,
+    // Where chat conversations are saved after each reply (None = not persisted)
+    pub chat_history: Option<ChatHistoryStore>,
+    // Chat reply currently streaming in the background
+    pub chat_stream: Option<ChatStream>,
+    // Host command run after a session is created, and whether repos may supply their own
+    pub on_create_host_command: Option<String>,
+    pub allow_project_host_commands: bool,
+    // Ref the git view lists commits since (None = the repository's default base branch)
+    pub commit_log_base: Option<String>,
+    // Lifecycle and agent events are posted here when `webhook_url` is configured
+    pub webhook: Option<crate::docker::WebhookNotifier>,
+    // Whether a missing agents-dev image is built automatically, after confirmation, or never
+    pub missing_image_policy: crate::config::MissingImagePolicy,
+    // Runs of the OAuth refresh container before a refresh is reported as failed
+    pub oauth_refresh_attempts: u32,
+    // Bell/flash when a session completes, errors, or waits for input
+    pub alerter: crate::app::alerts::Alerter,
+    // Interactive sessions currently showing a Claude prompt, so each prompt alerts once
+    pub sessions_awaiting_input: HashSet<Uuid>,
+    // Adaptive tick: slow to idle_tick_rate after idle_after without activity
+    pub last_activity: Instant,
+    pub idle_tick_rate: Duration,
+    pub idle_after: Duration,
 }
 
 #[derive(Debug)]
@@ -581,9 +1252,26 @@ pub struct NewSessionState {
     pub is_current_dir_mode: bool, // true if creating session in current dir
     pub skip_permissions: bool,    // true to use --dangerously-skip-permissions flag
     pub mode: crate::models::SessionMode, // Interactive or Boss mode
+    pub agent_type: crate::models::AgentType, // Agent whose output format the logs are parsed as
+    pub model: Option<String>,     // Claude model for the session (None = the CLI's default)
     pub boss_prompt: TextEditor,   // The prompt text editor for boss mode execution
     pub file_finder: FuzzyFileFinderState, // Fuzzy file finder for @ symbol
     pub restart_session_id: Option<Uuid>, // If set, this is a restart operation
+    pub review_mode: bool, // Review session: branch_name holds the revision to check out detached
+    pub branch_name_generated: bool, // branch_name is still the generated default, free to rename from the prompt
+    pub saved_draft: String,             // Prompt text as last auto-saved, to skip unchanged writes
+    pub pending_paste: Option<String>,   // Paste over the size threshold, awaiting inline/file/cancel
+    pub base_branch: Option<String>, // Branch the worktree is created from (None = repository default)
+    pub base_branches: Vec<String>,  // Local branches offered in the SelectBaseBranch step
+    pub base_branch_filter: String,  // Typed filter for the base branch list
+    pub selected_base_branch_index: Option<usize>, // Index into filtered_base_branches()
+    pub env_vars: Vec<(String, String)>, // Extra container environment, kept for restarts
+    pub volumes: crate::app::session_volumes::Volumes, // Extra host mounts, kept for restarts
+    pub ports: crate::app::session_ports::Ports, // Published (host, container) ports
+    pub list_entry: ListEntryInput, // Entry being typed in the env, mounts or ports step
+    pub branch_error: Option<String>,    // Why the typed branch name can't be used
+    pub branch_conflict: Option<BranchConflict>, // Worktree already using the typed branch
+    pub branch_prefix: String, // Configured prefix, shown as the branch input's placeholder
 }
 
 impl Default for NewSessionState {
@@ -598,14 +1286,113 @@ impl Default for NewSessionState {
             is_current_dir_mode: false,
             skip_permissions: false,
             mode: crate::models::SessionMode::Interactive,
+            agent_type: crate::models::AgentType::default(),
+            model: None,
             boss_prompt: TextEditor::new(),
             file_finder: FuzzyFileFinderState::new(),
             restart_session_id: None,
+            review_mode: false,
+            branch_name_generated: false,
+            saved_draft: String::new(),
+            pending_paste: None,
+            base_branch: None,
+            base_branches: vec![],
+            base_branch_filter: String::new(),
+            selected_base_branch_index: None,
+            env_vars: vec![],
+            volumes: vec![],
+            ports: vec![],
+            list_entry: ListEntryInput::default(),
+            branch_error: None,
+            branch_conflict: None,
+            branch_prefix: crate::config::default_branch_prefix(),
         }
     }
 }
 
 impl NewSessionState {
+    /// Swap the typed branch name for its sanitized form, or record why it can't be used
+    /// and keep the typed text for editing. Review sessions take a revision instead.
+    pub fn check_branch_name(&mut self) -> bool {
+        if self.review_mode {
+            return true;
+        }
+        match crate::git::sanitize_branch_name(&self.branch_name) {
+            Ok(name) => {
+                if name != self.branch_name {
+                    self.branch_name = name;
+                    self.branch_name_generated = false;
+                }
+                self.branch_error = None;
+                true
+            }
+            Err(e) => {
+                self.branch_error = Some(e.to_string());
+                false
+            }
+        }
+    }
+
+    /// Parse the typed entry into the current list step's list, or say why it can't be added
+    fn add_list_entry(&mut self) -> Result<(), String> {
+        use crate::app::session_env::{parse_env_assignment, set_env_var};
+        use crate::app::session_ports::{parse_port_spec, set_port};
+        use crate::app::session_volumes::{parse_volume_spec, set_volume};
+        use crate::docker::session_container::{find_port_conflicts, host_ports_in_use};
+
+        let input = self.list_entry.text.as_str();
+        match self.step {
+            NewSessionStep::ConfigureEnv => {
+                let (key, value) = parse_env_assignment(input)?;
+                set_env_var(&mut self.env_vars, key, value);
+            }
+            NewSessionStep::ConfigureVolumes => {
+                set_volume(&mut self.volumes, parse_volume_spec(input)?);
+            }
+            NewSessionStep::ConfigurePorts => {
+                let port = parse_port_spec(input)?;
+                let mut ports = self.ports.clone();
+                set_port(&mut ports, port);
+                if find_port_conflicts(&ports, &host_ports_in_use([port.0])).contains(&port.0) {
+                    return Err(format!("Host port {} is already in use", port.0));
+                }
+                self.ports = ports;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Drop the most recently added entry of the current list step's list
+    fn remove_last_list_entry(&mut self) {
+        match self.step {
+            NewSessionStep::ConfigureEnv => {
+                self.env_vars.pop();
+            }
+            NewSessionStep::ConfigureVolumes => {
+                self.volumes.pop();
+            }
+            NewSessionStep::ConfigurePorts => {
+                self.ports.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// Base branches matching the typed filter, in list order
+    pub fn filtered_base_branches(&self) -> Vec<&String> {
+        let filter_lower = self.base_branch_filter.to_lowercase();
+        self.base_branches
+            .iter()
+            .filter(|branch| branch.to_lowercase().contains(&filter_lower))
+            .collect()
+    }
+
+    fn apply_base_branch_filter(&mut self) {
+        let count = self.filtered_base_branches().len();
+        self.selected_base_branch_index = (count > 0).then_some(0);
+    }
+
     pub fn apply_filter(&mut self) {
         self.filtered_repos.clear();
         let filter_lower = self.filter_text.to_lowercase();
@@ -639,12 +1426,46 @@ impl NewSessionState {
 pub enum NewSessionStep {
     SelectRepo,
     InputBranch,
-    SelectMode,  // Choose between Interactive and Boss mode
-    InputPrompt, // Enter prompt for Boss mode
+    SelectBaseBranch, // Choose the branch the new worktree starts from
+    SelectMode,       // Choose between Interactive and Boss mode
+    InputPrompt,      // Enter prompt for Boss mode
     ConfigurePermissions,
+    ConfigureEnv, // Optional: add container environment variables (Boss mode)
+    ConfigureVolumes, // Optional: mount extra host directories (Boss mode)
+    ConfigurePorts,   // Optional: publish container ports on the host (Boss mode)
     Creating,
 }
 
+impl NewSessionStep {
+    /// Optional container steps that add typed entries to a list, then return to
+    /// `ConfigurePermissions`
+    pub fn is_list_entry(&self) -> bool {
+        matches!(self, Self::ConfigureEnv | Self::ConfigureVolumes | Self::ConfigurePorts)
+    }
+}
+
+/// Line being typed in a list entry step (KEY=VALUE, HOST:CONTAINER[:ro] or a port mapping)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListEntryInput {
+    pub text: String,
+    pub error: Option<String>, // Why the typed entry was rejected
+}
+
+/// The typed branch is already checked out in a worktree of the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchConflict {
+    pub worktree: PathBuf,
+    pub session_id: Option<Uuid>, // Session using that worktree, which can be restarted instead
+}
+
+/// What to do with a boss prompt paste over the size threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteChoice {
+    Inline,     // Insert the text at the cursor as usual
+    SaveToFile, // Save it under the drafts directory and insert an @-reference
+    Cancel,     // Drop the paste
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AsyncAction {
     StartNewSession,        // Old - will be removed
@@ -653,6 +1474,8 @@ pub enum AsyncAction {
     NewSessionNormal,       // New - create normal new session with mode selection
     CreateNewSession,
     DeleteSession(Uuid),       // New - delete session with container cleanup
+    StopKeepWorktree(Uuid),    // Remove the container only; the session stays revivable
+    StopSession(Uuid),         // Stop the container but keep it for a later restart
     RefreshWorkspaces,         // Manual refresh of workspace data
     FetchContainerLogs(Uuid),  // Fetch container logs for a session
     AttachToContainer(Uuid),   // Attach to a container session
@@ -661,10 +1484,18 @@ pub enum AsyncAction {
     AuthSetupOAuth,            // Run OAuth authentication setup
     AuthSetupApiKey,           // Save API key authentication
     ReauthenticateCredentials, // Re-authenticate Claude credentials
+    Logout,                    // Revoke the refresh token and remove stored credentials
     RestartSession(Uuid),      // Restart a stopped session with new container
+    AutoRestartSession(Uuid),  // Start a dead session's existing container again
     CleanupOrphaned,           // Clean up orphaned containers without worktrees
+    CleanupDeadSessions(Vec<Uuid>), // Delete these stopped/errored sessions in one pass
     AttachToOtherTmux(String), // Attach to a non-agents-in-a-box tmux session by name
     KillOtherTmux(String),     // Kill a non-agents-in-a-box tmux session by name
+    OpenExternalDiff(Uuid),    // Open a session's diff in the configured external tool
+    NewReviewSession,          // Create a review-only session on a detached worktree
+    BuildAgentsDevImage(Box<AsyncAction>), // Build the agents-dev image, then run the action
+    InitClaudeChat,            // Connect the chat popup to the Claude API
+    SendClaudeMessage(String), // Stream a chat reply to this message
 }
 
 impl Default for AppState {
@@ -692,18 +1523,50 @@ impl Default for AppState {
             log_last_updated: HashMap::new(),
             last_log_check: None,
             last_token_refresh_check: None,
+            auth_status: AuthStatus::Unknown,
+            auth_credential: AuthCredential::None,
+            last_auth_credential_check: None,
+            auth_profile: crate::config::auth_profile::DEFAULT_PROFILE.to_string(),
+            auth_profile_picker: None,
+            oauth_refresh_notice_for: None,
+            auth_refresh_task: None,
             claude_chat_state: None,
             live_logs: HashMap::new(),
+            session_usage: HashMap::new(),
+            session_usage_store: None,
+            session_mcp_servers: HashMap::new(),
             claude_manager: None,
             log_streaming_coordinator: None,
             log_sender: None,
+            build_progress: None,
+            session_creation: None,
+            image_build: None,
             git_view_state: None,
+            dashboard: None,
+            last_dashboard_refresh: None,
+            worktree_disk_task: None,
+            git_status_task: None,
+            last_git_status_refresh: None,
+            container_usage_task: None,
+            container_usage: None,
+            last_container_usage_refresh: None,
+            health_check_task: None,
+            health_check_interval: None,
+            last_health_check: None,
+            auto_restart: false,
+            auto_restart_max_attempts: 3,
+            restart_attempts: HashMap::new(),
+            worktree_disk_bytes: None,
             notifications: Vec::new(),
+            notification_history: VecDeque::new(),
+            notification_history_scroll: None,
+            notification_config: crate::config::NotificationConfig::default(),
             pending_event: None,
 
             // Initialize quick commit state
             quick_commit_message: None,
             quick_commit_cursor: 0,
+            log_export: None,
 
             // Initialize tmux integration
             tmux_sessions: HashMap::new(),
@@ -713,6 +1576,65 @@ impl Default for AppState {
             other_tmux_sessions: Vec::new(),
             other_tmux_expanded: true, // Default to expanded
             selected_other_tmux_index: None,
+
+            session_warning_threshold: None,
+            diff_tool_command: None,
+            prompt_viewer: None,
+            run_compare: None,
+            escape_policy: crate::config::EscapePolicy::default(),
+            stop_sessions_on_exit: false,
+            keymap: crate::config::Keymap::default(),
+            theme: crate::config::Theme::default(),
+            min_terminal_size: (60, 20),
+            session_list_percent: 40,
+            session_sort_mode: crate::config::SessionSortMode::default(),
+            persist_session_sort: false,
+            session_filter: None,
+            session_filter_editing: false,
+            show_log_timestamps: false,
+            log_ansi_colors: true,
+            log_timestamp_format: crate::components::TimestampFormat::default(),
+            prompt_drafts: None,
+            prompt_draft_interval: Duration::from_secs(5),
+            last_prompt_draft_save: None,
+            protected_sessions: HashSet::new(),
+            protected_store: None,
+            session_persistence: None,
+            session_env: HashMap::new(),
+            session_env_store: None,
+            session_volumes: HashMap::new(),
+            session_volumes_store: None,
+            session_ports: HashMap::new(),
+            session_ports_store: None,
+            session_images: HashMap::new(),
+            session_images_store: None,
+            large_paste_threshold: 10_000,
+            max_log_entries: 1000,
+            log_spill: None,
+            prompt_soft_wrap: true,
+            prompt_wrap_width: None,
+            prompt_char_limits: (8000, 16_000),
+            prompt_branch_prefix: None,
+            branch_prefix: crate::config::default_branch_prefix(),
+            default_session_mode: crate::models::SessionMode::Interactive,
+            default_skip_permissions: false,
+            default_model: None,
+            model_choices: vec![],
+            chat_model: crate::claude::DEFAULT_MODEL.to_string(),
+            chat_token_budget: None,
+            chat_history: None,
+            chat_stream: None,
+            on_create_host_command: None,
+            allow_project_host_commands: false,
+            commit_log_base: None,
+            webhook: None,
+            missing_image_policy: crate::config::MissingImagePolicy::default(),
+            oauth_refresh_attempts: 3,
+            alerter: crate::app::alerts::Alerter::default(),
+            sessions_awaiting_input: HashSet::new(),
+            last_activity: Instant::now(),
+            idle_tick_rate: Duration::from_secs(1),
+            idle_after: Duration::from_secs(10),
         }
     }
 }
@@ -729,13 +1651,16 @@ impl AppState {
                 info!("Initializing Claude API integration");
                 match ClaudeApiClient::with_auth(auth) {
                     Ok(client) => {
+                        let client = client.with_model(self.chat_model.clone());
                         // Test connection
                         match client.test_connection().await {
                             Ok(()) => {
                                 let mut manager = ClaudeChatManager::new(client);
                                 manager.create_session(None);
                                 self.claude_manager = Some(manager);
-                                self.claude_chat_state = Some(ClaudeChatState::new());
+                                self.claude_chat_state =
+                                    Some(self.chat_state_for(self.get_selected_session_id()));
+                                self.reset_chat_context();
                                 info!("Claude integration initialized successfully");
                                 Ok(())
                             }
@@ -759,7 +1684,87 @@ impl AppState {
         }
     }
 
-    /// Send a message to Claude
+    /// The chat for a session (or the global chat), reloaded from its saved history
+    fn chat_state_for(&self, session_id: Option<Uuid>) -> ClaudeChatState {
+        let chat_state = match self.chat_history.as_ref() {
+            Some(store) => ClaudeChatState::load(store, session_id),
+            None => ClaudeChatState { associated_session_id: session_id, ..ClaudeChatState::new() },
+        };
+        ClaudeChatState { token_budget: self.chat_token_budget, ..chat_state }
+    }
+
+    /// Replace the API conversation with one carrying the chat's messages as context
+    fn reset_chat_context(&mut self) {
+        let (Some(manager), Some(chat_state)) =
+            (self.claude_manager.as_mut(), self.claude_chat_state.as_ref())
+        else {
+            return;
+        };
+        if let Some(previous) = manager.get_active_session().map(|session| session.session_id) {
+            manager.remove_session(previous);
+        }
+        manager.create_session(None);
+        if let Some(session) = manager.get_active_session_mut() {
+            session.messages = chat_state.messages.clone();
+        }
+    }
+
+    /// Write the chat as Markdown under `~/.agents-in-a-box/exports`
+    pub fn export_claude_chat(&mut self) {
+        let Some(chat_state) = self.claude_chat_state.as_ref() else {
+            return;
+        };
+        if chat_state.messages.is_empty() {
+            self.add_warning_notification("⚠️ No chat messages to export yet".to_string());
+            return;
+        }
+        let Some(path) = crate::app::chat_history::default_chat_export_path(
+            chat_state.associated_session_id,
+            chrono::Local::now(),
+        ) else {
+            self.add_error_notification("❌ Could not determine home directory".to_string());
+            return;
+        };
+        let content = chat_state.export_markdown(&self.chat_model);
+        match crate::app::log_export::write_export(&path, &content) {
+            Ok(()) => {
+                self.add_success_notification(format!("💾 Exported chat to {}", path.display()));
+            }
+            Err(e) => self.add_error_notification(format!(
+                "❌ Failed to export chat to {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    /// Archive the current conversation and start an empty one for the same association
+    pub fn new_claude_conversation(&mut self) {
+        let Some(chat_state) = self.claude_chat_state.as_ref() else {
+            return;
+        };
+        if chat_state.is_streaming {
+            return;
+        }
+        let session_id = chat_state.associated_session_id;
+        if let Some(store) = self.chat_history.as_ref() {
+            if let Err(e) = store.archive(session_id) {
+                self.add_error_notification(format!("❌ Failed to archive the chat: {}", e));
+                return;
+            }
+        }
+        self.claude_chat_state = Some(ClaudeChatState {
+            associated_session_id: session_id,
+            token_budget: self.chat_token_budget,
+            ..ClaudeChatState::new()
+        });
+        self.reset_chat_context();
+        self.add_info_notification("Started a new conversation".to_string());
+    }
+
+    /// Send a message to Claude. The reply streams in a background task that
+    /// `poll_chat_stream` drains every tick, so the UI keeps drawing and the reply can be
+    /// cancelled.
     pub async fn send_claude_message(
         &mut self,
         message: String,
@@ -767,38 +1772,21 @@ impl AppState {
         if let (Some(chat_state), Some(manager)) =
             (&mut self.claude_chat_state, &mut self.claude_manager)
         {
+            chat_state.budget_check()?;
             chat_state.start_streaming(message.clone());
 
             // Start streaming response
             match manager.stream_message(&message).await {
                 Ok(mut stream) => {
-                    // Handle streaming response
-                    while let Some(event) = stream.next().await {
-                        match event {
-                            Ok(ClaudeStreamingEvent::ContentBlockDelta { delta, .. }) => {
-                                chat_state.append_streaming_response(&delta.text);
-                                self.ui_needs_refresh = true;
-                            }
-                            Ok(ClaudeStreamingEvent::MessageStop) => {
-                                chat_state.finish_streaming();
-                                self.ui_needs_refresh = true;
+                    let (sender, events) = mpsc::unbounded_channel();
+                    let task = tokio::spawn(async move {
+                        while let Some(event) = stream.next().await {
+                            if sender.send(event.map_err(|e| e.to_string())).is_err() {
                                 break;
                             }
-                            Ok(ClaudeStreamingEvent::Error { error }) => {
-                                error!("Claude API error: {}", error.message);
-                                chat_state.finish_streaming();
-                                return Err(format!("Claude error: {}", error.message).into());
-                            }
-                            Ok(_) => {
-                                // Other events - continue
-                            }
-                            Err(e) => {
-                                error!("Streaming error: {}", e);
-                                chat_state.finish_streaming();
-                                return Err(e.into());
-                            }
                         }
-                    }
+                    });
+                    self.chat_stream = Some(ChatStream { task, events });
                     Ok(())
                 }
                 Err(e) => {
@@ -811,20 +1799,187 @@ impl AppState {
         }
     }
 
-    /// Add a log entry to live logs
-    pub fn add_live_log(&mut self, session_id: Uuid, log_entry: LogEntry) {
-        self.live_logs.entry(session_id).or_insert_with(Vec::new).push(log_entry);
-
-        // Limit log entries to prevent memory issues (keep last 1000)
-        if let Some(logs) = self.live_logs.get_mut(&session_id) {
-            if logs.len() > 1000 {
-                logs.drain(0..logs.len() - 1000);
-            }
+    /// Apply the chat reply events that arrived since the last tick
+    pub fn poll_chat_stream(&mut self) {
+        let Some(stream) = self.chat_stream.as_mut() else {
+            return;
+        };
+        let mut events = Vec::new();
+        let disconnected = loop {
+            match stream.events.try_recv() {
+                Ok(event) => events.push(event),
+                Err(mpsc::error::TryRecvError::Empty) => break false,
+                Err(mpsc::error::TryRecvError::Disconnected) => break true,
+            }
+        };
+
+        for event in events {
+            let done = match event {
+                Ok(event) => {
+                    if let Some(chat_state) = self.claude_chat_state.as_mut() {
+                        chat_state.record_stream_usage(&event);
+                    }
+                    match event {
+                        ClaudeStreamingEvent::ContentBlockDelta { delta, .. } => {
+                            if let Some(chat_state) = self.claude_chat_state.as_mut() {
+                                chat_state.append_streaming_response(&delta.text);
+                            }
+                            if let Some(manager) = self.claude_manager.as_mut() {
+                                manager.append_streaming_text(&delta.text);
+                            }
+                            self.ui_needs_refresh = true;
+                            false
+                        }
+                        ClaudeStreamingEvent::MessageStop => true,
+                        ClaudeStreamingEvent::Error { error } => {
+                            error!("Claude API error: {}", error.message);
+                            self.add_error_notification(format!(
+                                "❌ Claude error: {}",
+                                error.message
+                            ));
+                            true
+                        }
+                        _ => false,
+                    }
+                }
+                Err(e) => {
+                    error!("Streaming error: {}", e);
+                    self.add_error_notification(format!("❌ Claude streaming error: {}", e));
+                    true
+                }
+            };
+            if done {
+                self.end_chat_reply(false);
+                return;
+            }
+        }
+
+        // The stream closed without a message_stop
+        if disconnected {
+            self.end_chat_reply(false);
+        }
+    }
+
+    /// Stop the reply being streamed, keeping the text received so far
+    pub fn cancel_streaming(&mut self) {
+        if self.chat_stream.is_none() {
+            return;
+        }
+        info!("Cancelling the Claude chat reply");
+        self.end_chat_reply(true);
+        self.add_info_notification("Stopped Claude's reply".to_string());
+    }
+
+    /// Move the streamed reply into the conversation and save it
+    fn end_chat_reply(&mut self, interrupted: bool) {
+        if let Some(stream) = self.chat_stream.take() {
+            stream.task.abort();
+        }
+        if let Some(manager) = self.claude_manager.as_mut() {
+            manager.finish_streaming();
+        }
+        if let Some(chat_state) = self.claude_chat_state.as_mut() {
+            if interrupted {
+                chat_state.interrupt_streaming();
+            } else {
+                chat_state.finish_streaming();
+            }
+            if let Some(store) = self.chat_history.as_ref() {
+                if let Err(e) = chat_state.save(store) {
+                    warn!("Failed to save chat history: {}", e);
+                }
+            }
+        }
+        self.ui_needs_refresh = true;
+    }
+
+    /// Add a log entry to live logs
+    pub fn add_live_log(&mut self, session_id: Uuid, log_entry: LogEntry) {
+        // Usage entries carry token counts for the dashboard and are never displayed
+        if log_entry.metadata.get("event_type").is_some_and(|t| t == "usage") {
+            let count = |key: &str| {
+                log_entry.metadata.get(key).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0)
+            };
+            let total = self.session_usage.entry(session_id).or_default();
+            // A restarted log stream replays its tail; those reports are already counted
+            if total.counted_through.is_some_and(|through| log_entry.timestamp <= through) {
+                return;
+            }
+            total.add(&crate::models::TokenUsage {
+                input_tokens: count("input_tokens"),
+                output_tokens: count("output_tokens"),
+                cache_tokens: count("cache_tokens"),
+                reported_cost_usd: log_entry
+                    .metadata
+                    .get("total_cost")
+                    .and_then(|v| v.parse::<f64>().ok()),
+                counted_through: Some(log_entry.timestamp),
+            });
+            if let Some(ref store) = self.session_usage_store {
+                if let Err(e) = store.save(&self.session_usage) {
+                    warn!("Failed to save session usage: {}", e);
+                }
+            }
+            return;
+        }
+
+        // MCP status entries replace the session's last report for the live logs header
+        if log_entry.metadata.get("event_type").is_some_and(|t| t == "mcp_servers") {
+            if let Some(servers) =
+                log_entry.metadata.get("servers").and_then(|s| serde_json::from_str(s).ok())
+            {
+                self.session_mcp_servers.insert(session_id, servers);
+                self.ui_needs_refresh = true;
+            }
+            return;
+        }
+
+        match log_entry.metadata.get("event_type").map(String::as_str) {
+            Some("error" | "stream_error") => self.alert_session(session_id, SessionAlert::Errored),
+            Some("stream_end") => self.alert_session(session_id, SessionAlert::Completed),
+            _ => {}
+        }
+
+        if let Some(session) = self.find_session_mut(session_id) {
+            session.update_last_accessed();
+        }
+        self.live_logs.entry(session_id).or_insert_with(Vec::new).push(log_entry);
+
+        // Keep memory bounded; the oldest entries move to disk rather than being dropped.
+        // They go in batches of a tenth of the cap, so the file isn't written on every push.
+        let max_entries = self.max_log_entries.max(1);
+        let spill_batch = (max_entries / 10).max(1);
+        if let Some(logs) = self.live_logs.get_mut(&session_id) {
+            if logs.len() >= max_entries + spill_batch {
+                let overflow: Vec<LogEntry> = logs.drain(0..logs.len() - max_entries).collect();
+                if let Some(ref spill) = self.log_spill {
+                    if let Err(e) = spill.append(session_id, &overflow) {
+                        warn!("Failed to spill old logs for session {}: {}", session_id, e);
+                    }
+                }
+            }
         }
 
         self.ui_needs_refresh = true;
     }
 
+    /// Ring the bell and/or flash for a session event. Skipped while attached to a
+    /// session, where the user is already watching it.
+    fn alert_session(&mut self, session_id: Uuid, alert: SessionAlert) {
+        let attached = self.attached_session_id.is_some()
+            || self.find_session(session_id).is_some_and(|s| s.is_attached);
+        if !attached && self.alerter.trigger(alert, Instant::now()) {
+            self.ui_needs_refresh = true;
+        }
+    }
+
+    /// Post an event to the configured webhook, if any. Never waits on delivery.
+    fn notify_webhook(&self, event: crate::docker::WebhookEvent) {
+        if let Some(ref webhook) = self.webhook {
+            webhook.notify(event);
+        }
+    }
+
     /// Start log streaming for a session when it becomes active
     pub async fn start_log_streaming_for_session(
         &mut self,
@@ -835,25 +1990,36 @@ impl AppState {
             let session_info = self
                 .workspaces
                 .iter()
-                .flat_map(|w| &w.sessions)
-                .find(|s| s.id == session_id)
-                .and_then(|s| {
+                .flat_map(|w| w.sessions.iter().map(move |s| (w, s)))
+                .find(|(_, s)| s.id == session_id)
+                .and_then(|(w, s)| {
                     s.container_id.clone().map(|container_id| {
                         (
                             container_id,
                             format!("{}-{}", s.name, s.branch_name),
+                            w.name.clone(),
                             s.mode.clone(),
+                            s.agent_type,
                         )
                     })
                 });
 
-            if let Some((container_id, container_name, session_mode)) = session_info {
+            if let Some((container_id, container_name, workspace, session_mode, agent_type)) =
+                session_info
+            {
                 info!(
                     "Starting log streaming for session {} (container: {})",
                     session_id, container_id
                 );
                 coordinator
-                    .start_streaming(session_id, container_id, container_name, session_mode)
+                    .start_streaming(
+                        session_id,
+                        container_id,
+                        container_name,
+                        workspace,
+                        session_mode,
+                        agent_type,
+                    )
                     .await?;
             }
         }
@@ -875,6 +2041,9 @@ impl AppState {
     /// Clear live logs for a session
     pub fn clear_live_logs(&mut self, session_id: Uuid) {
         self.live_logs.remove(&session_id);
+        if let Some(ref spill) = self.log_spill {
+            spill.discard(session_id);
+        }
         self.ui_needs_refresh = true;
     }
 
@@ -885,26 +2054,14 @@ impl AppState {
 
     /// Check if this is first time setup (no auth configured)
     pub fn is_first_time_setup() -> bool {
-        let home_dir = match dirs::home_dir() {
-            Some(dir) => dir,
-            None => return false,
+        let Some(profiles) = AuthProfiles::in_home_dir() else {
+            return false;
         };
-
-        let auth_dir = home_dir.join(".agents-in-a-box/auth");
+        let auth_dir = profiles.active_dir();
 
         let has_credentials = auth_dir.join(".credentials.json").exists();
         let has_claude_json = auth_dir.join(".claude.json").exists();
-        let has_api_key = std::env::var("ANTHROPIC_API_KEY").is_ok();
-        let has_env_file = home_dir.join(".agents-in-a-box/.env").exists();
-
-        // Load .env file if it exists to check for API key
-        let has_env_api_key = if has_env_file {
-            std::fs::read_to_string(home_dir.join(".agents-in-a-box/.env"))
-                .map(|contents| contents.contains("ANTHROPIC_API_KEY="))
-                .unwrap_or(false)
-        } else {
-            false
-        };
+        let has_api_key = Self::api_key_configured(&profiles);
 
         // For OAuth authentication, we need BOTH .credentials.json AND .claude.json
         // If we have a refresh token, we can refresh expired access tokens, so it's not "first time setup"
@@ -926,49 +2083,83 @@ impl AppState {
         };
 
         // Show auth screen if we don't have valid OAuth setup AND no API key alternatives
-        !has_valid_oauth && !has_api_key && !has_env_api_key
+        !has_valid_oauth && !has_api_key
     }
 
-    /// Check if OAuth token in credentials file is still valid (not expired)
-    fn is_oauth_token_valid(credentials_path: &std::path::Path) -> bool {
-        use std::fs;
+    /// Whether an API key is set in the environment or in the active profile's `.env`
+    fn api_key_configured(profiles: &AuthProfiles) -> bool {
+        std::env::var("ANTHROPIC_API_KEY").is_ok()
+            || std::fs::read_to_string(profiles.active_env_file())
+                .map(|contents| contents.contains("ANTHROPIC_API_KEY="))
+                .unwrap_or(false)
+    }
 
-        if let Ok(contents) = fs::read_to_string(credentials_path) {
-            // Parse the JSON to extract OAuth token info
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents) {
-                if let Some(oauth) = json.get("claudeAiOauth") {
-                    if let Some(expires_at) = oauth.get("expiresAt").and_then(|v| v.as_u64()) {
-                        // Check if current time is before expiration time
-                        let current_time = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_millis() as u64;
+    /// Tell the user once per token that an automatic refresh is starting
+    pub fn announce_oauth_refresh(&mut self) {
+        let Some(expires_at) = Self::oauth_token_expiry() else {
+            return;
+        };
+        if self.oauth_refresh_notice_for == Some(expires_at) {
+            return;
+        }
+        self.oauth_refresh_notice_for = Some(expires_at);
+        let when = AuthCredential::OAuth(expires_at)
+            .label(chrono::Utc::now())
+            .unwrap_or_default();
+        self.add_info_notification(format!("🔑 OAuth {}, refreshing it now", when));
+    }
 
-                        if current_time < expires_at {
-                            info!(
-                                "OAuth token is valid, expires at: {}",
-                                chrono::DateTime::from_timestamp_millis(expires_at as i64)
-                                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-                                    .unwrap_or_else(|| "unknown".to_string())
-                            );
-                            return true;
-                        } else {
-                            warn!(
-                                "OAuth token has expired at: {}",
-                                chrono::DateTime::from_timestamp_millis(expires_at as i64)
-                                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-                                    .unwrap_or_else(|| "unknown".to_string())
-                            );
-                            return false;
-                        }
-                    }
-                }
-            }
+    /// Re-read which credential sessions use, for the header's auth indicator
+    pub fn refresh_auth_credential(&mut self) {
+        self.last_auth_credential_check = Some(Instant::now());
+        if let Some(profiles) = AuthProfiles::in_home_dir() {
+            self.auth_profile = profiles.active();
         }
+        self.auth_credential = match Self::oauth_token_expiry() {
+            Some(expires_at) => AuthCredential::OAuth(expires_at),
+            None if AuthProfiles::in_home_dir().is_some_and(|p| Self::api_key_configured(&p)) => {
+                AuthCredential::ApiKey
+            }
+            None => AuthCredential::None,
+        };
+    }
 
-        // If we can't parse or find expiration info, assume invalid
-        warn!("Could not validate OAuth token from credentials file");
-        false
+    /// Access token expiry (`claudeAiOauth.expiresAt`) from a credentials file
+    fn read_oauth_expiry(
+        credentials_path: &std::path::Path,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let contents = std::fs::read_to_string(credentials_path).ok()?;
+        let json = serde_json::from_str::<serde_json::Value>(&contents).ok()?;
+        let expires_at = json.get("claudeAiOauth")?.get("expiresAt")?.as_u64()?;
+        chrono::DateTime::from_timestamp_millis(expires_at as i64)
+    }
+
+    /// When the stored OAuth access token expires, or None without OAuth credentials
+    pub fn oauth_token_expiry() -> Option<chrono::DateTime<chrono::Utc>> {
+        Self::read_oauth_expiry(&Self::active_auth_dir()?.join(".credentials.json"))
+    }
+
+    /// Auth directory of the active profile (`~/.agents-in-a-box/auth` by default)
+    fn active_auth_dir() -> Option<PathBuf> {
+        AuthProfiles::in_home_dir().map(|profiles| profiles.active_dir())
+    }
+
+    /// Check if OAuth token in credentials file is still valid (not expired)
+    pub fn is_oauth_token_valid(credentials_path: &std::path::Path) -> bool {
+        let Some(expires_at) = Self::read_oauth_expiry(credentials_path) else {
+            // If we can't parse or find expiration info, assume invalid
+            warn!("Could not validate OAuth token from credentials file");
+            return false;
+        };
+
+        let expiry = expires_at.format("%Y-%m-%d %H:%M:%S UTC");
+        if chrono::Utc::now() < expires_at {
+            info!("OAuth token is valid, expires at: {}", expiry);
+            true
+        } else {
+            warn!("OAuth token has expired at: {}", expiry);
+            false
+        }
     }
 
     /// Check if OAuth token needs refresh (expires within 30 minutes)
@@ -1012,10 +2203,63 @@ impl AppState {
 
     /// Refresh OAuth tokens using the refresh token
     pub async fn refresh_oauth_tokens(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Join an in-flight background refresh rather than starting a second one
+        if self.auth_refresh_task.is_some() {
+            return self.wait_for_auth_refresh().await.map_err(Into::into);
+        }
+
+        self.auth_status = AuthStatus::Refreshing;
+        let result =
+            Self::run_oauth_refresh(self.missing_image_policy, self.oauth_refresh_attempts).await;
+        self.finish_auth_refresh(&result);
+        result.map_err(Into::into)
+    }
+
+    /// Start an OAuth token refresh in the background so the UI keeps rendering
+    /// the "auth refreshing" indicator while it runs
+    pub fn start_background_auth_refresh(&mut self) {
+        if self.auth_refresh_task.is_some() {
+            return;
+        }
+        info!("Starting background OAuth token refresh");
+        self.auth_status = AuthStatus::Refreshing;
+        self.auth_refresh_task = Some(tokio::spawn(Self::run_oauth_refresh(
+            self.missing_image_policy,
+            self.oauth_refresh_attempts,
+        )));
+    }
+
+    /// Wait for an in-flight background OAuth refresh, if any
+    pub async fn wait_for_auth_refresh(&mut self) -> Result<(), String> {
+        let Some(task) = self.auth_refresh_task.take() else {
+            return Ok(());
+        };
+
+        info!("Waiting for in-flight OAuth token refresh to finish");
+        let result = task
+            .await
+            .unwrap_or_else(|e| Err(format!("Token refresh task failed: {}", e)));
+        self.finish_auth_refresh(&result);
+        result
+    }
+
+    fn finish_auth_refresh(&mut self, result: &Result<(), String>) {
+        self.auth_status = match result {
+            Ok(()) => AuthStatus::Valid,
+            Err(e) => AuthStatus::RefreshFailed(e.clone()),
+        };
+        self.ui_needs_refresh = true;
+    }
+
+    async fn run_oauth_refresh(
+        image_policy: crate::config::MissingImagePolicy,
+        attempts: u32,
+    ) -> Result<(), String> {
+        use crate::app::oauth_refresh::{RETRY_BASE_DELAY, RefreshError, refresh_with_retry};
+
         info!("Attempting to refresh OAuth tokens");
 
-        let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-        let auth_dir = home_dir.join(".agents-in-a-box").join("auth");
+        let auth_dir = Self::active_auth_dir().ok_or("Could not determine home directory")?;
         let credentials_path = auth_dir.join(".credentials.json");
 
         // Check if tokens actually need refresh
@@ -1024,26 +2268,13 @@ impl AppState {
             return Ok(());
         }
 
-        // Build the Docker image if needed
-        let image_name = "agents-box:agents-dev";
-        let image_check = tokio::process::Command::new("docker")
-            .args(["image", "inspect", image_name])
-            .output()
-            .await?;
-
-        if !image_check.status.success() {
-            info!("Building agents-dev image for token refresh...");
-            let build_status = tokio::process::Command::new("docker")
-                .args(["build", "-t", image_name, "docker/agents-dev"])
-                .status()
-                .await?;
-
-            if !build_status.success() {
-                return Err("Failed to build image for token refresh".into());
-            }
-        }
+        // Background refreshes can't ask for confirmation, so under the prompt
+        // policy a missing image fails the refresh instead of building
+        crate::docker::ensure_agents_dev_image(image_policy, false, None)
+            .await
+            .map_err(|e| e.to_string())?;
 
-        // Run the oauth-refresh.js script in a container (with retries built-in)
+        // Run the oauth-refresh.js script in a container, retrying Docker and network failures
         info!("Running OAuth token refresh in container");
 
         // Create the volume mount string that will live long enough
@@ -1075,31 +2306,35 @@ impl AppState {
             "claude-user",
             "--entrypoint",
             "node",
-            image_name,
+            crate::docker::agents_dev::AGENTS_DEV_IMAGE,
             "/app/scripts/oauth-refresh.js",
         ]);
 
-        let output = tokio::process::Command::new("docker").args(&args).output().await?;
+        let run_refresh = || async {
+            let output = tokio::process::Command::new("docker")
+                .args(&args)
+                .output()
+                .await
+                .map_err(|e| RefreshError::Transient(e.to_string()))?;
 
-        if output.status.success() {
             let stdout = String::from_utf8_lossy(&output.stdout);
-            info!("OAuth token refresh successful: {}", stdout.trim());
-
-            // Verify the new token is valid
-            if Self::is_oauth_token_valid(&credentials_path) {
-                info!("New OAuth token verified as valid");
+            if output.status.success() {
+                info!("OAuth token refresh successful: {}", stdout.trim());
                 Ok(())
             } else {
-                Err("Token refresh succeeded but new token is invalid".into())
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                warn!("OAuth token refresh failed");
+                warn!("Stderr: {}", stderr.trim());
+                warn!("Stdout: {}", stdout.trim());
+                Err(RefreshError::from_script_output(&stderr, &stdout))
             }
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            warn!("OAuth token refresh failed");
-            warn!("Stderr: {}", stderr.trim());
-            warn!("Stdout: {}", stdout.trim());
-            Err(format!("Token refresh failed: {}", stderr.trim()).into())
-        }
+        };
+
+        // A run only counts once the new token in the credentials file is valid
+        refresh_with_retry(attempts, RETRY_BASE_DELAY, run_refresh, || {
+            Self::is_oauth_token_valid(&credentials_path)
+        })
+        .await
     }
 
     pub fn check_current_directory_status(&mut self) {
@@ -1132,14 +2367,21 @@ impl AppState {
     pub async fn load_real_workspaces(&mut self) {
         info!("Loading active sessions (both Docker and Interactive)");
 
+        // Reloaded sessions start with a fresh last_accessed and no git counts; keep the
+        // activity and counts already seen until the next git status refresh
+        let previous: HashMap<Uuid, (chrono::DateTime<chrono::Utc>, crate::models::GitChanges)> =
+            self.workspaces
+                .iter()
+                .flat_map(|w| w.sessions.iter())
+                .map(|s| (s.id, (s.last_accessed, s.git_changes.clone())))
+                .collect();
+
         // Clear existing workspaces before loading to prevent duplicates
         self.workspaces.clear();
 
         // Check and refresh OAuth tokens if needed (only if Docker is available)
-        let home_dir = dirs::home_dir();
-        if let Some(home) = home_dir {
-            let credentials_path =
-                home.join(".agents-in-a-box").join("auth").join(".credentials.json");
+        if let Some(auth_dir) = Self::active_auth_dir() {
+            let credentials_path = auth_dir.join(".credentials.json");
 
             // Only attempt refresh if we have OAuth credentials AND Docker is available
             if credentials_path.exists() && Self::oauth_token_needs_refresh(&credentials_path) {
@@ -1156,12 +2398,13 @@ impl AppState {
         }
 
         // Load Boss mode sessions (Docker-based) if Docker is available
-        if self.is_docker_available().await {
+        let boss_sessions_loaded = if self.is_docker_available().await {
             info!("Docker available - loading Boss mode sessions");
-            self.load_boss_mode_sessions().await;
+            self.load_boss_mode_sessions().await
         } else {
             info!("Docker not available - skipping Boss mode session loading");
-        }
+            false
+        };
 
         // Load Interactive mode sessions (always attempt, no Docker needed)
         info!("Loading Interactive mode sessions");
@@ -1171,6 +2414,17 @@ impl AppState {
         info!("Loading other tmux sessions");
         self.load_other_tmux_sessions().await;
 
+        self.apply_protected_flags();
+        for session in self.workspaces.iter_mut().flat_map(|w| w.sessions.iter_mut()) {
+            if let Some((seen, git_changes)) = previous.get(&session.id) {
+                session.last_accessed = *seen;
+                session.git_changes = git_changes.clone();
+            }
+        }
+        self.sort_sessions();
+        // Without Docker the Boss sessions are missing, so keep their saved entries
+        self.persist_sessions(!boss_sessions_loaded);
+
         // Set initial selection
         if !self.workspaces.is_empty() {
             self.selected_workspace_index = Some(0);
@@ -1182,13 +2436,16 @@ impl AppState {
             self.selected_workspace_index = None;
             self.selected_session_index = None;
         }
+        if self.session_filter.is_some() {
+            self.select_filtered_session();
+        }
 
         // Queue logs fetch for the currently selected session if any
         self.queue_logs_fetch();
     }
 
-    /// Load Boss mode sessions from Docker containers
-    async fn load_boss_mode_sessions(&mut self) {
+    /// Load Boss mode sessions from Docker containers; false if they couldn't be listed
+    async fn load_boss_mode_sessions(&mut self) -> bool {
         // Try to load active Docker sessions
         match SessionLoader::new().await {
             Ok(loader) => {
@@ -1201,18 +2458,43 @@ impl AppState {
                             workspaces.len(),
                             self.workspaces.len()
                         );
+                        true
                     }
                     Err(e) => {
                         warn!("Failed to load Boss mode sessions: {}", e);
+                        false
                     }
                 }
             }
             Err(e) => {
                 warn!("Failed to create session loader for Boss mode: {}", e);
+                false
             }
         }
     }
 
+    /// Save every loaded session's metadata so the next launch can restore it
+    ///
+    /// With `keep_unloaded`, saved sessions that weren't loaded this time are kept too.
+    pub fn persist_sessions(&self, keep_unloaded: bool) {
+        let Some(ref persistence) = self.session_persistence else {
+            return;
+        };
+        let mut sessions: Vec<Session> =
+            self.workspaces.iter().flat_map(|w| w.sessions.iter().cloned()).collect();
+        if keep_unloaded {
+            let unloaded: Vec<Session> = persistence
+                .load()
+                .into_iter()
+                .filter(|saved| sessions.iter().all(|s| s.id != saved.id))
+                .collect();
+            sessions.extend(unloaded);
+        }
+        if let Err(e) = persistence.save(&sessions) {
+            warn!("Failed to save sessions: {}", e);
+        }
+    }
+
     /// Load Interactive mode sessions from tmux
     async fn load_interactive_mode_sessions(&mut self) {
         use crate::interactive::InteractiveSessionManager;
@@ -1230,10 +2512,19 @@ impl AppState {
         match manager.list_sessions().await {
             Ok(sessions) => {
                 info!("Discovered {} Interactive sessions from tmux", sessions.len());
+                let persisted = self
+                    .session_persistence
+                    .as_ref()
+                    .map(crate::app::SessionPersistence::load)
+                    .unwrap_or_default();
 
                 // Convert to Session models and add to workspaces
                 for interactive_session in sessions {
-                    let session = interactive_session.to_session_model();
+                    let mut session = interactive_session.to_session_model();
+                    crate::app::session_persistence::merge_persisted(
+                        std::slice::from_mut(&mut session),
+                        &persisted,
+                    );
 
                     // Find or create workspace for this session
                     // Use source_repository (the original git repo) not worktree_path parent
@@ -1420,7 +2711,99 @@ impl AppState {
         self.workspaces.get(workspace_idx)
     }
 
+    /// `(workspace_idx, session_idx)` of every session matching the session filter, in list
+    /// order; all sessions when no filter is set
+    pub fn filtered_sessions(&self) -> Vec<(usize, usize)> {
+        let needle = self.session_filter.as_deref().unwrap_or_default().to_lowercase();
+        let mut matches = Vec::new();
+        for (workspace_idx, workspace) in self.workspaces.iter().enumerate() {
+            let workspace_matches = workspace.name.to_lowercase().contains(&needle);
+            for (session_idx, session) in workspace.sessions.iter().enumerate() {
+                if workspace_matches
+                    || session.name.to_lowercase().contains(&needle)
+                    || session.branch_name.to_lowercase().contains(&needle)
+                {
+                    matches.push((workspace_idx, session_idx));
+                }
+            }
+        }
+        matches
+    }
+
+    /// Open the session filter input, keeping any filter already typed
+    pub fn start_session_filter(&mut self) {
+        self.session_filter.get_or_insert_with(String::new);
+        self.session_filter_editing = true;
+        self.select_filtered_session();
+    }
+
+    pub fn session_filter_input_char(&mut self, ch: char) {
+        if let Some(filter) = self.session_filter.as_mut() {
+            filter.push(ch);
+            self.select_filtered_session();
+        }
+    }
+
+    pub fn session_filter_backspace(&mut self) {
+        if let Some(filter) = self.session_filter.as_mut() {
+            filter.pop();
+            self.select_filtered_session();
+        }
+    }
+
+    /// Stop typing into the filter; an empty filter is dropped
+    pub fn confirm_session_filter(&mut self) {
+        self.session_filter_editing = false;
+        if self.session_filter.as_deref() == Some("") {
+            self.session_filter = None;
+        }
+    }
+
+    pub fn clear_session_filter(&mut self) {
+        self.session_filter = None;
+        self.session_filter_editing = false;
+    }
+
+    /// Move the selection onto the first match when the selected session is filtered out
+    fn select_filtered_session(&mut self) {
+        let matches = self.filtered_sessions();
+        let selected = self.selected_workspace_index.zip(self.selected_session_index);
+        if selected.is_some_and(|pair| matches.contains(&pair)) {
+            return;
+        }
+        if let Some(&(workspace_idx, session_idx)) = matches.first() {
+            self.selected_workspace_index = Some(workspace_idx);
+            self.selected_session_index = Some(session_idx);
+            self.selected_other_tmux_index = None;
+            self.queue_logs_fetch();
+        }
+    }
+
+    /// Step through the filtered sessions, stopping at either end
+    fn step_filtered_session(&mut self, forward: bool) {
+        let matches = self.filtered_sessions();
+        if matches.is_empty() {
+            return;
+        }
+        let selected = self.selected_workspace_index.zip(self.selected_session_index);
+        let next = match matches.iter().position(|&pair| Some(pair) == selected) {
+            Some(current) if forward => (current + 1).min(matches.len() - 1),
+            Some(current) => current.saturating_sub(1),
+            None => 0,
+        };
+        let (workspace_idx, session_idx) = matches[next];
+        self.selected_workspace_index = Some(workspace_idx);
+        self.selected_session_index = Some(session_idx);
+        self.selected_other_tmux_index = None;
+        self.queue_logs_fetch();
+    }
+
     pub fn next_session(&mut self) {
+        if self.session_filter.is_some() {
+            self.step_filtered_session(true);
+            return;
+        }
+
         // Check if we're in the "Other tmux" section
         if self.selected_other_tmux_index.is_some() {
             // Navigate within other tmux sessions
@@ -1453,6 +2836,11 @@ impl AppState {
     }
 
     pub fn previous_session(&mut self) {
+        if self.session_filter.is_some() {
+            self.step_filtered_session(false);
+            return;
+        }
+
         // Check if we're in the "Other tmux" section
         if let Some(other_idx) = self.selected_other_tmux_index {
             if other_idx > 0 {
@@ -1524,6 +2912,40 @@ impl AppState {
         }
     }
 
+    /// Select what a clicked session list row stands for. A workspace row selects its first
+    /// session, as `next_workspace` does; the "Other tmux" header expands or collapses.
+    pub fn select_list_row(&mut self, row: crate::components::session_list::SessionListRow) {
+        use crate::components::session_list::SessionListRow;
+        let (workspace_idx, session_idx) = match row {
+            SessionListRow::Workspace(workspace_idx) => {
+                let has_sessions =
+                    self.workspaces.get(workspace_idx).is_some_and(|w| !w.sessions.is_empty());
+                (workspace_idx, has_sessions.then_some(0))
+            }
+            SessionListRow::Session(workspace_idx, session_idx) => {
+                (workspace_idx, Some(session_idx))
+            }
+            SessionListRow::OtherTmux(other_idx) => {
+                self.selected_workspace_index = None;
+                self.selected_session_index = None;
+                self.selected_other_tmux_index = Some(other_idx);
+                return;
+            }
+            SessionListRow::OtherTmuxHeader => {
+                self.toggle_other_tmux_expanded();
+                return;
+            }
+            SessionListRow::Blank => return,
+        };
+        if workspace_idx >= self.workspaces.len() {
+            return;
+        }
+        self.selected_workspace_index = Some(workspace_idx);
+        self.selected_session_index = session_idx;
+        self.selected_other_tmux_index = None;
+        self.queue_logs_fetch();
+    }
+
     pub fn toggle_help(&mut self) {
         self.help_visible = !self.help_visible;
     }
@@ -1557,30 +2979,204 @@ impl AppState {
             // Open Claude chat popup
             self.current_view = View::ClaudeChat;
             self.claude_chat_visible = true;
+            if self.claude_manager.is_none() {
+                self.pending_async_action = Some(AsyncAction::InitClaudeChat);
+                return;
+            }
+            // Each session has its own conversation; switch when the selection has moved
+            let session_id = self.get_selected_session_id();
+            let switch = self
+                .claude_chat_state
+                .as_ref()
+                .is_some_and(|chat| !chat.is_streaming && chat.associated_session_id != session_id);
+            if switch {
+                self.claude_chat_state = Some(self.chat_state_for(session_id));
+                self.reset_chat_context();
+            }
         }
     }
 
-    pub fn quit(&mut self) {
-        self.should_quit = true;
+    pub fn claude_chat_input_char(&mut self, ch: char) {
+        if let Some(chat_state) = self.claude_chat_state.as_mut() {
+            chat_state.add_char_to_input(ch);
+        }
     }
 
-    pub fn show_delete_confirmation(&mut self, session_id: Uuid) {
-        info!("!!! SHOWING DELETE CONFIRMATION DIALOG for session: {}", session_id);
-        self.confirmation_dialog = Some(ConfirmationDialog {
-            title: "Delete Session".to_string(),
-            message: "Are you sure you want to delete this session? This will stop the container and remove the git worktree.".to_string(),
-            confirm_action: ConfirmAction::DeleteSession(session_id),
-            selected_option: false, // Default to "No"
-        });
+    pub fn claude_chat_backspace(&mut self) {
+        if let Some(chat_state) = self.claude_chat_state.as_mut() {
+            chat_state.backspace_input();
+        }
     }
 
-    /// Show confirmation dialog for killing an "other" tmux session
-    pub fn show_kill_other_tmux_confirmation(&mut self, session_name: String) {
-        info!("Showing kill confirmation for other tmux session: {}", session_name);
-        self.confirmation_dialog = Some(ConfirmationDialog {
-            title: "Kill tmux Session".to_string(),
-            message: format!("Are you sure you want to kill tmux session '{}'?", session_name),
-            confirm_action: ConfirmAction::KillOtherTmux(session_name),
+    /// Queue the typed chat message, unless the token budget is used up
+    pub fn submit_claude_message(&mut self) {
+        let Some(chat_state) = self.claude_chat_state.as_ref() else {
+            return;
+        };
+        if chat_state.is_streaming || chat_state.input_buffer.trim().is_empty() {
+            return;
+        }
+        if let Err(warning) = chat_state.budget_check() {
+            self.add_warning_notification(format!("⚠️ {}", warning));
+            return;
+        }
+        let message = chat_state.input_buffer.clone();
+        self.pending_async_action = Some(AsyncAction::SendClaudeMessage(message));
+    }
+
+    pub fn reset_chat_token_usage(&mut self) {
+        if let Some(chat_state) = self.claude_chat_state.as_mut() {
+            chat_state.reset_token_usage();
+            self.add_info_notification("Chat token count reset".to_string());
+        }
+    }
+
+    /// Raise the chat's budget by the configured amount
+    pub fn raise_chat_token_budget(&mut self) {
+        let (Some(chat_state), Some(step)) =
+            (self.claude_chat_state.as_mut(), self.chat_token_budget)
+        else {
+            return;
+        };
+        chat_state.raise_token_budget(step);
+        let budget = chat_state.token_budget.unwrap_or_default();
+        self.add_info_notification(format!("Chat token budget raised to {}", budget));
+    }
+
+    pub fn quit(&mut self) {
+        self.should_quit = true;
+    }
+
+    /// Sessions whose containers are running, across all workspaces
+    pub fn running_sessions(&self) -> Vec<&Session> {
+        self.workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .filter(|s| s.status == crate::models::SessionStatus::Running)
+            .collect()
+    }
+
+    /// Quit, asking first while any session is running; their containers outlive the app
+    /// unless `stop_sessions_on_exit` is set
+    pub fn request_quit(&mut self) {
+        let running_sessions = self.running_sessions();
+        let running = running_sessions.len();
+        let protected =
+            running_sessions.iter().filter(|s| self.protected_sessions.contains(&s.id)).count();
+        if running == 0 {
+            self.quit();
+            return;
+        }
+
+        let count = match running {
+            1 => "1 session is".to_string(),
+            n => format!("{} sessions are", n),
+        };
+        let message = if self.stop_sessions_on_exit && protected > 0 {
+            format!(
+                "{} running. Quit anyway? They will be stopped first, except {} protected.",
+                count, protected
+            )
+        } else if self.stop_sessions_on_exit {
+            format!("{} running. Quit anyway? They will be stopped first.", count)
+        } else {
+            format!("{} running. Quit anyway? They will keep running in the background.", count)
+        };
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Quit".to_string(),
+            message,
+            confirm_action: ConfirmAction::QuitApp,
+            selected_option: false, // Default to "No"
+        });
+    }
+
+    pub fn show_delete_confirmation(&mut self, session_id: Uuid) {
+        info!("!!! SHOWING DELETE CONFIRMATION DIALOG for session: {}", session_id);
+        let mut message = "Are you sure you want to delete this session? This will stop the container and remove the git worktree. Use 'S' or 'P' instead to keep the worktree.".to_string();
+        if self.protected_sessions.contains(&session_id) {
+            message.insert_str(0, "🔒 This session is protected. ");
+        }
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Delete Session".to_string(),
+            message,
+            confirm_action: ConfirmAction::DeleteSession(session_id),
+            selected_option: false, // Default to "No"
+        });
+    }
+
+    /// Sessions in a stopped or error state across all workspaces
+    pub fn dead_session_ids(&self) -> Vec<Uuid> {
+        self.workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .filter(|s| {
+                matches!(
+                    s.status,
+                    crate::models::SessionStatus::Stopped | crate::models::SessionStatus::Error(_)
+                )
+            })
+            .map(|s| s.id)
+            .collect()
+    }
+
+    /// Ask before deleting every stopped or errored session; protected ones are left out
+    pub fn show_cleanup_dead_sessions_confirmation(&mut self) {
+        let (session_ids, protected) = self.partition_protected(self.dead_session_ids());
+        if session_ids.is_empty() {
+            self.add_info_notification("✅ No stopped or errored sessions to remove".to_string());
+            return;
+        }
+
+        let mut message = format!(
+            "Delete {} stopped/errored session(s)? This will remove their containers and git worktrees.",
+            session_ids.len()
+        );
+        if !protected.is_empty() {
+            message.push_str(&format!(
+                " {} protected session(s) will be skipped.",
+                protected.len()
+            ));
+        }
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Delete Dead Sessions".to_string(),
+            message,
+            confirm_action: ConfirmAction::CleanupDeadSessions(session_ids),
+            selected_option: false, // Default to "No"
+        });
+    }
+
+    pub fn show_stop_keep_worktree_confirmation(&mut self, session_id: Uuid) {
+        let Some(session) = self.find_session(session_id) else {
+            return;
+        };
+        if session.container_id.is_none() {
+            let name = session.name.clone();
+            self.add_info_notification(format!(
+                "Session '{}' has no container to stop - use 'd' to delete it",
+                name
+            ));
+            return;
+        }
+
+        let mut message = "Remove this session's container but keep its git worktree and uncommitted work? The session can be revived later with 'e'.".to_string();
+        if self.protected_sessions.contains(&session_id) {
+            message.insert_str(0, "🔒 This session is protected. ");
+        }
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Stop Session (Keep Worktree)".to_string(),
+            message,
+            confirm_action: ConfirmAction::StopKeepWorktree(session_id),
+            selected_option: false, // Default to "No"
+        });
+    }
+
+    /// Show confirmation dialog for killing an "other" tmux session
+    pub fn show_kill_other_tmux_confirmation(&mut self, session_name: String) {
+        info!("Showing kill confirmation for other tmux session: {}", session_name);
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Kill tmux Session".to_string(),
+            message: format!("Are you sure you want to kill tmux session '{}'?", session_name),
+            confirm_action: ConfirmAction::KillOtherTmux(session_name),
             selected_option: false, // Default to "No"
         });
     }
@@ -1826,6 +3422,23 @@ impl AppState {
         }
     }
 
+    /// Random branch name under the configured prefix, offered for new sessions
+    fn generated_branch_name(&self) -> String {
+        let uuid_str = uuid::Uuid::new_v4().to_string();
+        format!("{}{}", self.branch_prefix, &uuid_str[..8])
+    }
+
+    /// Empty new session state with the configured mode and permissions preselected
+    fn new_session_defaults(&self) -> NewSessionState {
+        NewSessionState {
+            mode: self.default_session_mode.clone(),
+            skip_permissions: self.default_skip_permissions,
+            model: self.default_model.clone(),
+            branch_prefix: self.branch_prefix.clone(),
+            ..Default::default()
+        }
+    }
+
     pub async fn new_session_normal(&mut self) {
         use crate::git::WorkspaceScanner;
         use std::env;
@@ -1876,10 +3489,7 @@ impl AppState {
         }
 
         // Generate branch name with UUID
-        let branch_base = format!(
-            "agents-in-a-box/{}",
-            uuid::Uuid::new_v4().to_string().split('-').next().unwrap_or("session")
-        );
+        let branch_base = self.generated_branch_name();
 
         // Create new session state for normal new session (NOT current directory mode)
         self.new_session_state = Some(NewSessionState {
@@ -1888,7 +3498,7 @@ impl AppState {
             selected_repo_index: Some(0),
             branch_name: branch_base.clone(),
             step: NewSessionStep::InputBranch,
-            ..Default::default()
+            ..self.new_session_defaults()
         });
 
         self.current_view = View::NewSession;
@@ -1912,6 +3522,8 @@ impl AppState {
             self.auth_setup_state = Some(AuthSetupState {
                 selected_method: AuthMethod::OAuth,
                 api_key_input: String::new(),
+                validating: false,
+                skip_validation: false,
                 is_processing: false,
                 error_message: Some("Authentication required before creating sessions.\n\nPlease set up Claude authentication to continue.".to_string()),
                 show_cursor: false,
@@ -1958,10 +3570,7 @@ impl AppState {
         }
 
         // Generate branch name with UUID
-        let branch_base = format!(
-            "agents-in-a-box/{}",
-            uuid::Uuid::new_v4().to_string().split('-').next().unwrap_or("session")
-        );
+        let branch_base = self.generated_branch_name();
 
         // Create new session state for current directory
         self.new_session_state = Some(NewSessionState {
@@ -1971,7 +3580,7 @@ impl AppState {
             branch_name: branch_base.clone(),
             step: NewSessionStep::InputBranch,
             is_current_dir_mode: true,
-            ..Default::default()
+            ..self.new_session_defaults()
         });
 
         self.current_view = View::NewSession;
@@ -1982,6 +3591,19 @@ impl AppState {
         );
     }
 
+    /// Start creating a review session: same flow as a current-directory session, but the
+    /// input asks for a revision and the worktree is checked out detached at it
+    pub async fn new_review_session(&mut self) {
+        self.new_session_in_current_dir().await;
+
+        if let Some(ref mut state) = self.new_session_state {
+            state.review_mode = true;
+            if state.step == NewSessionStep::InputBranch {
+                state.branch_name.clear();
+            }
+        }
+    }
+
     pub async fn start_workspace_search(&mut self) {
         info!("Starting workspace search from NonGitNotification view");
 
@@ -2000,10 +3622,7 @@ impl AppState {
                         }
 
                         // Generate branch name with UUID
-                        let branch_base = format!(
-                            "agents-in-a-box/{}",
-                            uuid::Uuid::new_v4().to_string().split('-').next().unwrap_or("session")
-                        );
+                        let branch_base = self.generated_branch_name();
 
                         // Initialize filtered repos with all repos (even if empty)
                         let filtered_repos: Vec<(usize, std::path::PathBuf)> = repos
@@ -2024,7 +3643,7 @@ impl AppState {
                             filtered_repos,
                             selected_repo_index: if has_repos { Some(0) } else { None },
                             branch_name: branch_base,
-                            ..Default::default()
+                            ..self.new_session_defaults()
                         });
 
                         self.current_view = View::SearchWorkspace;
@@ -2034,15 +3653,8 @@ impl AppState {
                         warn!("Failed to load repositories: {}", e);
                         // Still transition to search view with empty state
                         self.new_session_state = Some(NewSessionState {
-                            branch_name: format!(
-                                "agents-in-a-box/{}",
-                                uuid::Uuid::new_v4()
-                                    .to_string()
-                                    .split('-')
-                                    .next()
-                                    .unwrap_or("session")
-                            ),
-                            ..Default::default()
+                            branch_name: self.generated_branch_name(),
+                            ..self.new_session_defaults()
                         });
                         self.current_view = View::SearchWorkspace;
                         info!("Transitioned to SearchWorkspace view with empty state due to error");
@@ -2053,11 +3665,8 @@ impl AppState {
                 warn!("Failed to create session loader: {}", e);
                 // Still transition to search view with empty state
                 self.new_session_state = Some(NewSessionState {
-                    branch_name: format!(
-                        "agents-in-a-box/{}",
-                        uuid::Uuid::new_v4().to_string().split('-').next().unwrap_or("session")
-                    ),
-                    ..Default::default()
+                    branch_name: self.generated_branch_name(),
+                    ..self.new_session_defaults()
                 });
                 self.current_view = View::SearchWorkspace;
                 info!("Transitioned to SearchWorkspace view with empty state due to loader error");
@@ -2080,7 +3689,7 @@ impl AppState {
                         available_repos: repos,
                         filtered_repos,
                         selected_repo_index: if has_repos { Some(0) } else { None },
-                        ..Default::default()
+                        ..self.new_session_defaults()
                     });
                     self.current_view = View::NewSession;
                 }
@@ -2095,6 +3704,8 @@ impl AppState {
     }
 
     pub fn cancel_new_session(&mut self) {
+        // Keep whatever was typed so a stray Escape doesn't lose the prompt
+        self.autosave_prompt_draft();
         self.new_session_state = None;
         self.current_view = View::SessionList;
         // Also clear any pending async actions to prevent race conditions
@@ -2126,6 +3737,7 @@ impl AppState {
     }
 
     pub fn new_session_confirm_repo(&mut self) {
+        let branch_name = self.generated_branch_name();
         if let Some(ref mut state) = self.new_session_state {
             if state.selected_repo_index.is_some() {
                 tracing::info!(
@@ -2151,8 +3763,13 @@ impl AppState {
                 }
 
                 state.step = NewSessionStep::InputBranch;
-                let uuid_str = uuid::Uuid::new_v4().to_string();
-                state.branch_name = format!("agents-session-{}", &uuid_str[..8]);
+                if state.review_mode {
+                    // Review sessions ask for a revision rather than a new branch name
+                    state.branch_name.clear();
+                } else {
+                    state.branch_name = branch_name;
+                    state.branch_name_generated = true;
+                }
 
                 // Change view from SearchWorkspace to NewSession to show branch input
                 self.current_view = View::NewSession;
@@ -2168,6 +3785,12 @@ impl AppState {
         if let Some(ref mut state) = self.new_session_state {
             if state.step == NewSessionStep::InputBranch {
                 state.branch_name.push(ch);
+                state.branch_name_generated = false;
+                state.branch_error = None;
+                state.branch_conflict = None;
+            } else if state.step == NewSessionStep::SelectBaseBranch {
+                state.base_branch_filter.push(ch);
+                state.apply_base_branch_filter();
             }
         }
     }
@@ -2176,16 +3799,196 @@ impl AppState {
         if let Some(ref mut state) = self.new_session_state {
             if state.step == NewSessionStep::InputBranch {
                 state.branch_name.pop();
+                state.branch_name_generated = false;
+                state.branch_error = None;
+                state.branch_conflict = None;
+            } else if state.step == NewSessionStep::SelectBaseBranch {
+                state.base_branch_filter.pop();
+                state.apply_base_branch_filter();
+            }
+        }
+    }
+
+    /// Offer the repository's local branches as the base for the new worktree, starting on
+    /// the checked-out branch. Restarts reuse their worktree, so they skip straight to modes.
+    pub fn new_session_proceed_to_base_branch(&mut self) {
+        if !self.check_new_session_branch() {
+            return;
+        }
+        let Some(ref mut state) = self.new_session_state else {
+            return;
+        };
+
+        let branches = state
+            .selected_repo_index
+            .and_then(|index| state.filtered_repos.get(index))
+            .filter(|_| state.restart_session_id.is_none())
+            .and_then(|(_, repo_path)| git2::Repository::open(repo_path).ok())
+            .map(|repo| crate::git::worktree_manager::local_branches(&repo))
+            .unwrap_or_default();
+        if branches.is_empty() {
+            self.new_session_proceed_to_mode_selection();
+            return;
+        }
+
+        tracing::info!(
+            "Proceeding from InputBranch to SelectBaseBranch ({} branches)",
+            branches.len()
+        );
+        state.base_branches = branches;
+        state.base_branch_filter.clear();
+        state.apply_base_branch_filter();
+        state.step = NewSessionStep::SelectBaseBranch;
+    }
+
+    /// Check the typed branch before leaving the branch step: it must be a valid name and
+    /// not already checked out in a worktree, which `git worktree add` would refuse
+    fn check_new_session_branch(&mut self) -> bool {
+        let Some(state) = self.new_session_state.as_mut() else {
+            return false;
+        };
+        if state.step != NewSessionStep::InputBranch || !state.check_branch_name() {
+            return false;
+        }
+        if state.review_mode || state.restart_session_id.is_some() {
+            return true;
+        }
+
+        let Some(worktree) = state
+            .selected_repo_index
+            .and_then(|index| state.filtered_repos.get(index))
+            .and_then(|(_, repo_path)| {
+                crate::git::WorktreeManager::new()
+                    .ok()?
+                    .branch_worktree(repo_path, &state.branch_name)
+            })
+        else {
+            return true;
+        };
+
+        let session_id = self.session_for_worktree(&worktree);
+        if let Some(state) = self.new_session_state.as_mut() {
+            let message = format!(
+                "Branch {} is already checked out at {}",
+                state.branch_name,
+                worktree.display()
+            );
+            info!("{}", message);
+            state.branch_error = Some(message);
+            state.branch_conflict = Some(BranchConflict {
+                worktree,
+                session_id,
+            });
+        }
+        false
+    }
+
+    /// Session whose worktree is at `worktree`
+    fn session_for_worktree(&self, worktree: &Path) -> Option<Uuid> {
+        let worktree = worktree.canonicalize().unwrap_or_else(|_| worktree.to_path_buf());
+        self.workspaces.iter().flat_map(|w| &w.sessions).find_map(|session| {
+            let path = PathBuf::from(&session.workspace_path);
+            (path.canonicalize().unwrap_or(path) == worktree).then_some(session.id)
+        })
+    }
+
+    /// Resolve a branch conflict by switching to the first free `-N` variant of the name
+    pub fn new_session_branch_add_suffix(&mut self) {
+        let Some(ref mut state) = self.new_session_state else {
+            return;
+        };
+        if state.branch_conflict.take().is_none() {
+            return;
+        }
+        let Some((_, repo_path)) =
+            state.selected_repo_index.and_then(|index| state.filtered_repos.get(index))
+        else {
+            return;
+        };
+        if let Ok(manager) = crate::git::WorktreeManager::new() {
+            state.branch_name = manager.unique_branch_name(repo_path, &state.branch_name);
+            state.branch_name_generated = false;
+            state.branch_error = None;
+        }
+    }
+
+    /// Resolve a branch conflict by restarting the session that owns the worktree, as the
+    /// restart flow does, instead of creating a new worktree
+    pub fn new_session_branch_reuse(&mut self) {
+        let Some(session_id) = self
+            .new_session_state
+            .as_ref()
+            .and_then(|state| state.branch_conflict.as_ref())
+            .and_then(|conflict| conflict.session_id)
+        else {
+            return;
+        };
+        let env_vars = self.session_env.get(&session_id).cloned().unwrap_or_default();
+        let volumes = self.session_volumes.get(&session_id).cloned().unwrap_or_default();
+        let ports = self.session_ports.get(&session_id).cloned().unwrap_or_default();
+        if let Some(ref mut state) = self.new_session_state {
+            info!("Reusing the worktree of session {}", session_id);
+            state.restart_session_id = Some(session_id);
+            state.env_vars = env_vars;
+            state.volumes = volumes;
+            state.ports = ports;
+            state.branch_conflict = None;
+            state.branch_error = None;
+        }
+        self.new_session_proceed_to_base_branch();
+    }
+
+    pub fn new_session_next_base_branch(&mut self) {
+        if let Some(ref mut state) = self.new_session_state {
+            let count = state.filtered_base_branches().len();
+            if count > 0 {
+                let current = state.selected_base_branch_index.unwrap_or(0);
+                state.selected_base_branch_index = Some((current + 1) % count);
+            }
+        }
+    }
+
+    pub fn new_session_prev_base_branch(&mut self) {
+        if let Some(ref mut state) = self.new_session_state {
+            let count = state.filtered_base_branches().len();
+            if count > 0 {
+                let current = state.selected_base_branch_index.unwrap_or(0);
+                state.selected_base_branch_index = Some((current + count - 1) % count);
+            }
+        }
+    }
+
+    /// Use the highlighted branch as the base and move on to mode selection
+    pub fn new_session_confirm_base_branch(&mut self) {
+        if let Some(ref mut state) = self.new_session_state {
+            if state.step != NewSessionStep::SelectBaseBranch {
+                return;
             }
+            let Some(branch) = state
+                .selected_base_branch_index
+                .and_then(|index| state.filtered_base_branches().get(index).copied().cloned())
+            else {
+                return;
+            };
+            tracing::info!("Selected base branch: {}", branch);
+            state.base_branch = Some(branch);
         }
+        self.new_session_proceed_to_mode_selection();
     }
 
     pub fn new_session_proceed_to_mode_selection(&mut self) {
         if let Some(ref mut state) = self.new_session_state {
-            if state.step == NewSessionStep::InputBranch {
+            if state.step == NewSessionStep::InputBranch && !state.check_branch_name() {
+                return;
+            }
+            if matches!(
+                state.step,
+                NewSessionStep::InputBranch | NewSessionStep::SelectBaseBranch
+            ) {
                 tracing::info!(
-                    "Proceeding from InputBranch to SelectMode with branch: {}",
-                    state.branch_name
+                    "Proceeding to SelectMode with branch: {} (base: {:?})",
+                    state.branch_name,
+                    state.base_branch
                 );
                 state.step = NewSessionStep::SelectMode;
             }
@@ -2213,6 +4016,84 @@ impl AppState {
                 }
             }
         }
+
+        if self.new_session_state.as_ref().is_some_and(|s| s.step == NewSessionStep::InputPrompt) {
+            self.restore_prompt_draft();
+        }
+    }
+
+    /// Draft file for the boss prompt being composed. A generated branch name changes every
+    /// time, so those drafts are kept per repository.
+    fn prompt_draft_target(&self) -> Option<std::path::PathBuf> {
+        let store = self.prompt_drafts.as_ref()?;
+        let state = self.new_session_state.as_ref()?;
+        let (_, repo_path) = state.filtered_repos.get(state.selected_repo_index?)?;
+        Some(Self::prompt_draft_path(
+            store,
+            repo_path,
+            &state.branch_name,
+            state.branch_name_generated,
+        ))
+    }
+
+    fn prompt_draft_path(
+        store: &crate::app::prompt_drafts::PromptDraftStore,
+        repo_path: &std::path::Path,
+        branch_name: &str,
+        generated: bool,
+    ) -> std::path::PathBuf {
+        if generated {
+            store.draft_path_for_repo(repo_path)
+        } else {
+            store.draft_path(repo_path, branch_name)
+        }
+    }
+
+    /// Load the auto-saved draft for this repo/branch into the prompt, if there is one
+    /// that differs from it
+    pub fn restore_prompt_draft(&mut self) {
+        let Some(draft_path) = self.prompt_draft_target() else {
+            return;
+        };
+        let (Some(store), Some(state)) = (&self.prompt_drafts, &mut self.new_session_state) else {
+            return;
+        };
+
+        if let Some(draft) = store.load(&draft_path) {
+            if draft != state.boss_prompt.to_string() {
+                info!("Restored prompt draft {}", draft_path.display());
+                state.boss_prompt.set_text(&draft);
+                state.saved_draft = draft;
+                self.add_info_notification("📝 Restored your unsaved prompt draft".to_string());
+            }
+        }
+    }
+
+    /// Save the boss prompt being composed if it changed since the last save
+    pub fn autosave_prompt_draft(&mut self) {
+        self.last_prompt_draft_save = Some(Instant::now());
+        let Some(draft_path) = self.prompt_draft_target() else {
+            return;
+        };
+        let (Some(store), Some(state)) = (&self.prompt_drafts, &mut self.new_session_state) else {
+            return;
+        };
+
+        let composing = matches!(
+            state.step,
+            NewSessionStep::InputPrompt | NewSessionStep::ConfigurePermissions
+        ) && state.mode == crate::models::SessionMode::Boss;
+        if !composing {
+            return;
+        }
+
+        let text = state.boss_prompt.to_string();
+        if text != state.saved_draft {
+            match store.save(&draft_path, &text) {
+                Ok(()) => state.saved_draft = text,
+                Err(e) => warn!("Failed to auto-save prompt draft: {}", e),
+            }
+        }
     }
 
     pub fn new_session_proceed_to_permissions(&mut self) {
@@ -2245,6 +4126,34 @@ impl AppState {
         }
     }
 
+    pub fn new_session_cycle_agent_type(&mut self) {
+        if let Some(ref mut state) = self.new_session_state {
+            if state.step == NewSessionStep::SelectMode {
+                state.agent_type = state.agent_type.next();
+            }
+        }
+    }
+
+    /// Step through the default model and each configured model
+    pub fn new_session_cycle_model(&mut self) {
+        let Some(ref mut state) = self.new_session_state else {
+            return;
+        };
+        if state.step != NewSessionStep::SelectMode {
+            return;
+        }
+        let mut choices: Vec<Option<&String>> = vec![None];
+        choices.extend(self.model_choices.iter().map(Some));
+        if let Some(custom) = self.default_model.as_ref() {
+            if !self.model_choices.contains(custom) {
+                choices.insert(1, Some(custom));
+            }
+        }
+        let current = choices.iter().position(|choice| *choice == state.model.as_ref());
+        let next = current.map_or(0, |index| (index + 1) % choices.len());
+        state.model = choices[next].cloned();
+    }
+
     pub fn new_session_add_char_to_prompt(&mut self, ch: char) {
         if let Some(ref mut state) = self.new_session_state {
             if state.step == NewSessionStep::InputPrompt {
@@ -2259,7 +4168,7 @@ impl AppState {
                     if state.file_finder.is_active {
                         state.file_finder.deactivate();
                     }
-                    state.file_finder.activate(state.boss_prompt.to_string().len(), workspace_root);
+                    state.file_finder.activate(state.boss_prompt.cursor_offset(), workspace_root);
                     state.boss_prompt.insert_char(ch);
                 } else if state.file_finder.is_active {
                     // File finder is active, handle character input for filtering
@@ -2317,7 +4226,12 @@ impl AppState {
     pub fn new_session_move_cursor_up(&mut self) {
         if let Some(ref mut state) = self.new_session_state {
             if state.step == NewSessionStep::InputPrompt && !state.file_finder.is_active {
-                state.boss_prompt.move_cursor_up();
+                match self.prompt_wrap_width {
+                    Some(width) if self.prompt_soft_wrap => {
+                        state.boss_prompt.move_cursor_up_wrapped(width)
+                    }
+                    _ => state.boss_prompt.move_cursor_up(),
+                }
             }
         }
     }
@@ -2325,7 +4239,12 @@ impl AppState {
     pub fn new_session_move_cursor_down(&mut self) {
         if let Some(ref mut state) = self.new_session_state {
             if state.step == NewSessionStep::InputPrompt && !state.file_finder.is_active {
-                state.boss_prompt.move_cursor_down();
+                match self.prompt_wrap_width {
+                    Some(width) if self.prompt_soft_wrap => {
+                        state.boss_prompt.move_cursor_down_wrapped(width)
+                    }
+                    _ => state.boss_prompt.move_cursor_down(),
+                }
             }
         }
     }
@@ -2378,64 +4297,308 @@ impl AppState {
         }
     }
 
-    pub fn new_session_insert_newline(&mut self) {
+    /// Undo (or redo) the last boss prompt edit
+    pub fn new_session_undo_prompt(&mut self, redo: bool) {
         if let Some(ref mut state) = self.new_session_state {
             if state.step == NewSessionStep::InputPrompt && !state.file_finder.is_active {
-                state.boss_prompt.insert_newline();
+                if redo {
+                    state.boss_prompt.redo();
+                } else {
+                    state.boss_prompt.undo();
+                }
             }
         }
     }
 
-    pub fn new_session_paste_text(&mut self, text: String) {
+    pub fn new_session_insert_newline(&mut self) {
         if let Some(ref mut state) = self.new_session_state {
             if state.step == NewSessionStep::InputPrompt && !state.file_finder.is_active {
-                // Insert the pasted text at the current cursor position
-                state.boss_prompt.insert_text(&text);
+                state.boss_prompt.insert_newline();
             }
         }
     }
 
-    pub fn new_session_toggle_permissions(&mut self) {
+    pub fn new_session_paste_text(&mut self, text: String) {
+        let threshold = self.large_paste_threshold;
         if let Some(ref mut state) = self.new_session_state {
-            if state.step == NewSessionStep::ConfigurePermissions {
-                state.skip_permissions = !state.skip_permissions;
+            if state.step == NewSessionStep::InputPrompt && !state.file_finder.is_active {
+                if threshold > 0 && text.len() > threshold {
+                    // Too large to insert without asking; see new_session_resolve_paste
+                    state.pending_paste = Some(text);
+                } else {
+                    // Insert the pasted text at the current cursor position
+                    state.boss_prompt.insert_text(&text);
+                }
             }
         }
     }
 
-    pub async fn new_session_create(&mut self) {
-        // Check session mode FIRST to determine if auth is needed
-        let session_mode = if let Some(ref state) = self.new_session_state {
-            state.mode.clone()
-        } else {
-            tracing::error!("new_session_create called but new_session_state is None");
+    /// Insert a large pending paste inline, save it to a file and insert an
+    /// `@`-reference to it, or drop it
+    pub fn new_session_resolve_paste(&mut self, choice: PasteChoice) {
+        let Some(state) = self.new_session_state.as_mut() else {
             return;
         };
-
-        // ONLY check authentication for Boss mode (Docker-based sessions)
-        // Interactive mode uses host ~/.claude and doesn't need Docker auth
-        if session_mode == crate::models::SessionMode::Boss {
-            // First check if Docker is available (Boss mode requires Docker)
-            if !self.is_docker_available().await {
-                error!("Boss mode requires Docker but Docker is not running");
-                self.add_error_notification(
-                    "Boss mode requires Docker.\n\nPlease start Docker and try again, or use Interactive mode instead.".to_string()
-                );
-                // Stay in current view so user can go back and select Interactive mode
-                return;
+        let Some(text) = state.pending_paste.take() else {
+            return;
+        };
+        let repo_path = state
+            .selected_repo_index
+            .and_then(|index| state.filtered_repos.get(index))
+            .map(|(_, path)| path.clone());
+
+        let insert = match choice {
+            PasteChoice::Inline => text,
+            PasteChoice::SaveToFile => {
+                let saved = self
+                    .prompt_drafts
+                    .clone()
+                    .or_else(crate::app::prompt_drafts::PromptDraftStore::in_home_dir)
+                    .ok_or_else(|| std::io::Error::other("no home directory"))
+                    .and_then(|store| {
+                        let repo_path = repo_path
+                            .ok_or_else(|| std::io::Error::other("no repository selected"))?;
+                        store.save_paste(&repo_path, &text)
+                    });
+                match saved {
+                    Ok(path) => format!("@{} ", path.display()),
+                    Err(e) => {
+                        warn!("Failed to save pasted text to a file: {}", e);
+                        self.add_error_notification(format!(
+                            "❌ Failed to save paste to a file: {}",
+                            e
+                        ));
+                        return;
+                    }
+                }
             }
+            PasteChoice::Cancel => return,
+        };
 
-            // Check if tokens need refresh (Docker is available at this point)
-            if let Some(home) = dirs::home_dir() {
-                let credentials_path = home.join(".agents-in-a-box/auth/.credentials.json");
-                if credentials_path.exists() && Self::oauth_token_needs_refresh(&credentials_path) {
-                    info!("Boss mode selected - OAuth tokens need refresh, attempting refresh");
+        if let Some(ref mut state) = self.new_session_state {
+            state.boss_prompt.insert_text(&insert);
+        }
+    }
+
+    /// Open an optional list entry step (env vars, mounts or ports); container sessions only
+    pub fn new_session_open_list_step(&mut self, step: NewSessionStep) {
+        if let Some(ref mut state) = self.new_session_state {
+            if state.step == NewSessionStep::ConfigurePermissions
+                && state.mode == crate::models::SessionMode::Boss
+                && step.is_list_entry()
+            {
+                state.step = step;
+                state.list_entry = ListEntryInput::default();
+            }
+        }
+    }
+
+    pub fn new_session_list_entry_char(&mut self, ch: char) {
+        if let Some(ref mut state) = self.new_session_state {
+            if state.step.is_list_entry() {
+                state.list_entry.text.push(ch);
+                state.list_entry.error = None;
+            }
+        }
+    }
+
+    /// Delete the last typed character, or the last added entry when nothing is typed
+    pub fn new_session_list_entry_backspace(&mut self) {
+        if let Some(ref mut state) = self.new_session_state {
+            if state.step.is_list_entry() {
+                if state.list_entry.text.pop().is_none() {
+                    state.remove_last_list_entry();
+                }
+                state.list_entry.error = None;
+            }
+        }
+    }
+
+    /// Add the typed entry, or go back to the permissions step when nothing is typed
+    pub fn new_session_list_entry_submit(&mut self) {
+        let Some(ref mut state) = self.new_session_state else {
+            return;
+        };
+        if !state.step.is_list_entry() {
+            return;
+        }
+        if state.list_entry.text.trim().is_empty() {
+            state.list_entry = ListEntryInput::default();
+            state.step = NewSessionStep::ConfigurePermissions;
+            return;
+        }
+        match state.add_list_entry() {
+            Ok(()) => state.list_entry = ListEntryInput::default(),
+            Err(e) => state.list_entry.error = Some(e),
+        }
+    }
+
+    pub fn new_session_finish_list_step(&mut self) {
+        if let Some(ref mut state) = self.new_session_state {
+            if state.step.is_list_entry() {
+                state.list_entry = ListEntryInput::default();
+                state.step = NewSessionStep::ConfigurePermissions;
+            }
+        }
+    }
+
+    /// Remember (or forget, when empty) a session's container environment
+    fn set_session_env(&mut self, session_id: Uuid, env_vars: crate::app::session_env::EnvVars) {
+        let changed = if env_vars.is_empty() {
+            self.session_env.remove(&session_id).is_some()
+        } else {
+            self.session_env.insert(session_id, env_vars.clone()) != Some(env_vars)
+        };
+        if !changed {
+            return;
+        }
+        if let Some(ref store) = self.session_env_store {
+            if let Err(e) = store.save(&self.session_env) {
+                warn!("Failed to save session environment variables: {}", e);
+            }
+        }
+    }
+
+    /// Remember (or forget, when empty) a session's extra mounts
+    fn set_session_volumes(
+        &mut self,
+        session_id: Uuid,
+        volumes: crate::app::session_volumes::Volumes,
+    ) {
+        let changed = if volumes.is_empty() {
+            self.session_volumes.remove(&session_id).is_some()
+        } else {
+            self.session_volumes.insert(session_id, volumes.clone()) != Some(volumes)
+        };
+        if !changed {
+            return;
+        }
+        if let Some(ref store) = self.session_volumes_store {
+            if let Err(e) = store.save(&self.session_volumes) {
+                warn!("Failed to save session mounts: {}", e);
+            }
+        }
+    }
+
+    /// Remember (or forget, when empty) a session's published ports
+    fn set_session_ports(&mut self, session_id: Uuid, ports: crate::app::session_ports::Ports) {
+        let changed = if ports.is_empty() {
+            self.session_ports.remove(&session_id).is_some()
+        } else {
+            self.session_ports.insert(session_id, ports.clone()) != Some(ports)
+        };
+        if !changed {
+            return;
+        }
+        if let Some(ref store) = self.session_ports_store {
+            if let Err(e) = store.save(&self.session_ports) {
+                warn!("Failed to save session ports: {}", e);
+            }
+        }
+    }
+
+    /// Forget the image a deleted session was created on
+    fn forget_session_image(&mut self, session_id: Uuid) {
+        if self.session_images.remove(&session_id).is_none() {
+            return;
+        }
+        if let Some(ref store) = self.session_images_store {
+            if let Err(e) = store.save(&self.session_images) {
+                warn!("Failed to save session images: {}", e);
+            }
+        }
+    }
+
+    /// Drop a deleted session's token usage
+    fn forget_session_usage(&mut self, session_id: Uuid) {
+        if self.session_usage.remove(&session_id).is_none() {
+            return;
+        }
+        if let Some(ref store) = self.session_usage_store {
+            if let Err(e) = store.save(&self.session_usage) {
+                warn!("Failed to save session usage: {}", e);
+            }
+        }
+    }
+
+    pub fn new_session_toggle_permissions(&mut self) {
+        if let Some(ref mut state) = self.new_session_state {
+            if state.step == NewSessionStep::ConfigurePermissions {
+                state.skip_permissions = !state.skip_permissions;
+            }
+        }
+    }
+
+    pub async fn new_session_create(&mut self) {
+        if self.session_creation.is_some() {
+            self.add_warning_notification("⏳ Another session is still being created".to_string());
+            return;
+        }
+
+        // Check session mode FIRST to determine if auth is needed
+        let session_mode = if let Some(ref state) = self.new_session_state {
+            state.mode.clone()
+        } else {
+            tracing::error!("new_session_create called but new_session_state is None");
+            return;
+        };
+
+        // ONLY check authentication for Boss mode (Docker-based sessions)
+        // Interactive mode uses host ~/.claude and doesn't need Docker auth
+        if session_mode == crate::models::SessionMode::Boss {
+            // First check if Docker is available (Boss mode requires Docker)
+            if let Some(reason) = self.docker_unavailable_reason().await {
+                error!("Boss mode requires Docker but Docker is not usable");
+                self.add_error_notification(format!(
+                    "Boss mode requires Docker.\n\n{}\n\nOr use Interactive mode instead.",
+                    reason
+                ));
+                // Stay in current view so user can go back and select Interactive mode
+                return;
+            }
+
+            if self.image_build.is_some() {
+                self.add_warning_notification(IMAGE_BUILD_RUNNING.to_string());
+                return;
+            }
+
+            // Session creation builds a missing image itself, with progress, unless
+            // the policy asks for confirmation or forbids implicit builds
+            if self.missing_image_policy != crate::config::MissingImagePolicy::Build {
+                match self.ensure_agents_dev_image(AsyncAction::CreateNewSession).await {
+                    Ok(true) => {}
+                    Ok(false) => return,
+                    Err(e) => {
+                        self.add_error_notification(format!("❌ {}", e));
+                        return;
+                    }
+                }
+            }
+
+            // A background refresh may be in flight; wait for it instead of failing
+            if self.auth_status == AuthStatus::Refreshing {
+                info!("Boss mode selected while OAuth refresh is in progress - waiting");
+                if let Err(e) = self.wait_for_auth_refresh().await {
+                    error!("OAuth token refresh failed while creating session: {}", e);
+                    self.add_error_notification(format!(
+                        "Failed to refresh OAuth tokens: {}",
+                        e
+                    ));
+                    return;
+                }
+            }
+
+            // Check if tokens need refresh (Docker is available at this point)
+            if let Some(auth_dir) = Self::active_auth_dir() {
+                let credentials_path = auth_dir.join(".credentials.json");
+                if credentials_path.exists() && Self::oauth_token_needs_refresh(&credentials_path) {
+                    info!("Boss mode selected - OAuth tokens need refresh, attempting refresh");
                     match self.refresh_oauth_tokens().await {
                         Ok(()) => info!("OAuth tokens refreshed successfully for Boss mode"),
                         Err(e) => {
                             error!("Failed to refresh OAuth tokens for Boss mode: {}", e);
                             self.add_error_notification(
-                                format!("Failed to refresh OAuth tokens: {}\n\nPlease check Docker and try again.", e)
+                                format!("Failed to refresh OAuth tokens: {}", e)
                             );
                             return;
                         }
@@ -2450,6 +4613,8 @@ impl AppState {
                 self.auth_setup_state = Some(AuthSetupState {
                     selected_method: AuthMethod::OAuth,
                     api_key_input: String::new(),
+                    validating: false,
+                    skip_validation: false,
                     is_processing: false,
                     error_message: Some("Boss mode requires Docker authentication.\n\nPlease set up Claude authentication to continue.".to_string()),
                     show_cursor: false,
@@ -2462,6 +4627,12 @@ impl AppState {
             info!("Interactive mode selected - skipping Docker auth check (will use host ~/.claude)");
         }
 
+        if self.new_session_state.as_ref().is_some_and(|s| s.step == NewSessionStep::InputBranch)
+            && !self.check_new_session_branch()
+        {
+            return;
+        }
+        let default_skip_permissions = self.default_skip_permissions;
         let (
             repo_path,
             branch_name,
@@ -2470,6 +4641,12 @@ impl AppState {
             mode,
             boss_prompt,
             restart_session_id,
+            review_mode,
+            branch_name_generated,
+            base_branch,
+            env_vars,
+            volumes,
+            ports,
         ) = {
             if let Some(ref mut state) = self.new_session_state {
                 tracing::info!("new_session_create called with step: {:?}", state.step);
@@ -2477,11 +4654,12 @@ impl AppState {
                 // Handle both ConfigurePermissions step (normal flow) and InputBranch step (current dir mode)
                 let can_create = match state.step {
                     NewSessionStep::ConfigurePermissions => true,
-                    NewSessionStep::InputBranch if state.is_current_dir_mode => {
+                    NewSessionStep::InputBranch if state.is_current_dir_mode || state.review_mode => {
                         // For current directory mode, skip to permissions step with defaults
                         state.step = NewSessionStep::ConfigurePermissions;
-                        state.skip_permissions = false; // Default to safe permissions
-                        state.mode = crate::models::SessionMode::Interactive; // Default mode
+                        state.skip_permissions = default_skip_permissions;
+                        // Boss mode needs a prompt, which this shortcut never asks for
+                        state.mode = crate::models::SessionMode::Interactive;
                         true
                     }
                     _ => false,
@@ -2513,6 +4691,12 @@ impl AppState {
                                     None
                                 },
                                 state.restart_session_id, // Pass restart session ID
+                                state.review_mode,
+                                state.branch_name_generated,
+                                state.base_branch.clone(),
+                                state.env_vars.clone(),
+                                state.volumes.clone(),
+                                state.ports.clone(),
                             )
                         } else {
                             tracing::error!(
@@ -2541,45 +4725,207 @@ impl AppState {
 
         // Create the session with log streaming
         tracing::info!(
-            "Calling create_session_with_logs for session {} (mode: {:?}, restart: {})",
+            "Creating session {} (mode: {:?}, restart: {})",
             session_id,
             mode,
             restart_session_id.is_some()
         );
 
-        let result = if let Some(restart_id) = restart_session_id {
+        // Name Boss sessions after their prompt unless the user picked a branch name;
+        // `branch_name` still keys the prompt draft
+        let session_branch = match (&self.prompt_branch_prefix, &boss_prompt) {
+            (Some(prefix), Some(prompt)) if branch_name_generated && restart_session_id.is_none() => {
+                git2::Repository::open(&repo_path)
+                    .ok()
+                    .and_then(|repo| {
+                        crate::git::worktree_manager::branch_name_from_prompt(&repo, prefix, prompt)
+                    })
+                    .unwrap_or_else(|| branch_name.clone())
+            }
+            _ => branch_name.clone(),
+        };
+        if session_branch != branch_name {
+            info!("Naming session branch {} from its prompt", session_branch);
+        }
+
+        // Creation and later restarts read the session's variables from here
+        if mode == crate::models::SessionMode::Boss && !review_mode {
+            self.set_session_env(session_id, env_vars);
+            self.set_session_volumes(session_id, volumes);
+            self.set_session_ports(session_id, ports);
+        }
+
+        let context = SessionCreationContext {
+            session_id,
+            repo_path,
+            branch_name,
+            session_branch,
+            session_mode,
+            has_boss_prompt: boss_prompt.is_some(),
+            branch_name_generated,
+            base_branch,
+            restart_session_id,
+        };
+        let repo_path = context.repo_path.as_path();
+        let base_branch = context.base_branch.as_deref();
+        let started = if review_mode {
+            // branch_name holds the revision to review
+            self.create_interactive_session(
+                repo_path,
+                &context.branch_name,
+                session_id,
+                false,
+                true,
+                None,
+            )
+            .await
+            .map(|()| None)
+        } else if let Some(restart_id) = restart_session_id {
             // This is a restart - try to reuse existing worktree
             info!(
                 "Restarting session {} with potentially updated configuration",
                 restart_id
             );
-            self.create_restart_session_with_logs(
-                &repo_path,
-                &branch_name,
+            self.start_restart_session(
+                repo_path,
+                &context.branch_name,
                 session_id,
                 skip_permissions,
-                mode,
+                mode.clone(),
                 boss_prompt,
             )
             .await
+            .map(|container| Some((container, Some(mode))))
         } else {
             // Normal new session creation
-            self.create_session_with_logs(
-                &repo_path,
-                &branch_name,
-                session_id,
-                skip_permissions,
-                mode,
-                boss_prompt,
-            )
+            match mode {
+                crate::models::SessionMode::Interactive => self
+                    .create_interactive_session(
+                        repo_path,
+                        &context.session_branch,
+                        session_id,
+                        skip_permissions,
+                        false,
+                        base_branch,
+                    )
+                    .await
+                    .map(|()| None),
+                crate::models::SessionMode::Boss => self
+                    .start_boss_session(
+                        repo_path,
+                        &context.session_branch,
+                        session_id,
+                        skip_permissions,
+                        boss_prompt,
+                        base_branch,
+                    )
+                    .await
+                    .map(|container| Some((container, None))),
+            }
+        };
+
+        match started {
+            Ok(Some((container, restart_mode))) => {
+                // The dialog shows progress until `poll_session_creation` finishes up
+                self.build_progress = None;
+                self.session_creation = Some(SessionCreation {
+                    container,
+                    restart_mode,
+                    context,
+                });
+            }
+            Ok(None) => self.finish_new_session(context, Ok(())).await,
+            Err(e) => self.finish_new_session(context, Err(e.to_string())).await,
+        }
+    }
+
+    /// Stream the build log of the session being created into its logs and, once its
+    /// container is up, finish creating the session
+    pub async fn poll_session_creation(&mut self) {
+        let Some(creation) = self.session_creation.as_mut() else {
+            return;
+        };
+        let session_id = creation.context.session_id;
+        // Checked before draining so messages sent just before finishing aren't lost
+        let finished = creation.container.task.is_finished();
+        let mut messages = Vec::new();
+        while let Ok(message) = creation.container.log_receiver.try_recv() {
+            messages.push(message);
+        }
+        for message in messages {
+            self.push_build_log(session_id, message);
+        }
+        if !finished {
+            return;
+        }
+
+        let Some(creation) = self.session_creation.take() else {
+            return;
+        };
+        let result = creation
+            .container
+            .task
             .await
+            .unwrap_or_else(|e| Err(format!("Session creation task failed: {}", e)));
+        let result = if let Some(mode) = creation.restart_mode {
+            self.finish_restart_session(session_id, &creation.context.branch_name, mode, result)
+                .await
+        } else {
+            let logs = self.logs.entry(session_id).or_default();
+            match &result {
+                Ok(_) => logs.push("Boss session created successfully!".to_string()),
+                Err(e) => logs.push(format!("Session creation failed: {}", e)),
+            }
+            result.map(|_| ())
         };
+        self.finish_new_session(creation.context, result).await;
+    }
+
+    /// Announce the new session and close the dialog, or clean up after a failure
+    async fn finish_new_session(
+        &mut self,
+        context: SessionCreationContext,
+        result: Result<(), String>,
+    ) {
+        let SessionCreationContext {
+            session_id,
+            repo_path,
+            branch_name,
+            session_branch,
+            session_mode,
+            has_boss_prompt,
+            branch_name_generated,
+            base_branch,
+            restart_session_id,
+        } = context;
 
+        let workspace_name = repo_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
         match result {
             Ok(()) => {
                 info!("Session created successfully");
+                self.notify_webhook(crate::docker::WebhookEvent::new(
+                    crate::docker::WebhookEventKind::Created,
+                    session_id,
+                    &workspace_name,
+                    serde_json::json!({ "branch": session_branch, "mode": session_mode }),
+                ));
+                if has_boss_prompt {
+                    if let Some(ref store) = self.prompt_drafts {
+                        store.discard(&Self::prompt_draft_path(
+                            store,
+                            &repo_path,
+                            &branch_name,
+                            branch_name_generated,
+                        ));
+                    }
+                }
                 // Reload workspaces BEFORE switching view to ensure UI shows new session immediately
                 self.load_real_workspaces().await;
+                self.run_on_create_host_command(&repo_path, session_id);
+                self.warn_about_merge_conflicts(&repo_path, session_id, base_branch.as_deref());
 
                 // Start log streaming for the newly created session
                 if let Err(e) = self.start_log_streaming_for_session(session_id).await {
@@ -2591,16 +4937,36 @@ impl AppState {
 
                 // Force UI refresh to show new session immediately
                 self.ui_needs_refresh = true;
-                self.cancel_new_session();
+                self.close_creating_dialog();
             }
             Err(e) => {
                 error!("Failed to create session: {}", e);
-                self.cancel_new_session();
+                if restart_session_id.is_none() {
+                    self.set_session_env(session_id, Vec::new());
+                    self.set_session_volumes(session_id, Vec::new());
+                    self.set_session_ports(session_id, Vec::new());
+                }
+                self.notify_webhook(crate::docker::WebhookEvent::new(
+                    crate::docker::WebhookEventKind::Errored,
+                    session_id,
+                    &workspace_name,
+                    serde_json::json!({ "message": format!("Failed to create session: {}", e) }),
+                ));
+                self.close_creating_dialog();
             }
         }
     }
 
-    async fn create_restart_session_with_logs(
+    /// Close the new session dialog if it still shows this creation's progress. Escape
+    /// leaves creation running in the background, and the user may have moved on since.
+    fn close_creating_dialog(&mut self) {
+        if self.new_session_state.as_ref().is_some_and(|s| s.step == NewSessionStep::Creating) {
+            self.cancel_new_session();
+        }
+    }
+
+    /// Start recreating a session's container, reusing its worktree when it still exists
+    async fn start_restart_session(
         &mut self,
         repo_path: &std::path::Path,
         branch_name: &str,
@@ -2608,7 +4974,7 @@ impl AppState {
         skip_permissions: bool,
         mode: crate::models::SessionMode,
         boss_prompt: Option<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<ContainerCreation, Box<dyn std::error::Error>> {
         use crate::docker::session_lifecycle::{SessionLifecycleManager, SessionRequest};
         use std::path::PathBuf;
 
@@ -2617,49 +4983,45 @@ impl AppState {
             session_id
         );
 
-        // Create a channel for build logs
-        let (log_sender, mut log_receiver) = mpsc::unbounded_channel::<String>();
-
         // Initialize logs for this session
         self.logs.insert(
             session_id,
             vec!["Restarting session with updated configuration...".to_string()],
         );
 
-        // Create a shared vector for logs
-        let session_logs = Arc::new(Mutex::new(Vec::new()));
-        let logs_clone = session_logs.clone();
-
-        // Spawn a task to collect logs
-        let session_id_clone = session_id;
-        tokio::spawn(async move {
-            while let Some(log_message) = log_receiver.recv().await {
-                if let Ok(mut logs) = logs_clone.lock() {
-                    logs.push(log_message.clone());
-                }
-                info!(
-                    "Restart log for session {}: {}",
-                    session_id_clone, log_message
-                );
-            }
-        });
-
         let workspace_name =
             repo_path.file_name().and_then(|n| n.to_str()).unwrap_or("unknown").to_string();
 
-        // Clone mode so we can use it later for tmux check
-        let mode_clone = mode.clone();
+        // Picked in the new session dialog, which stays open until creation finishes
+        let agent_type = self.new_session_state.as_ref().map(|s| s.agent_type).unwrap_or_default();
+        let model = self.new_session_state.as_ref().and_then(|s| s.model.clone());
+
+        // Still compared with the branch it was created from after the restart
+        let base_branch = self
+            .workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .find(|s| s.id == session_id)
+            .and_then(|s| s.base_branch.clone());
 
         let request = SessionRequest {
             session_id,
             workspace_name,
             workspace_path: repo_path.to_path_buf(),
             branch_name: branch_name.to_string(),
-            base_branch: None,
+            base_branch,
             container_config: None,
             skip_permissions,
             mode,
             boss_prompt,
+            env_vars: self.session_env.get(&session_id).cloned().unwrap_or_default(),
+            volumes: self.session_volumes.get(&session_id).cloned().unwrap_or_default(),
+            ports: self.session_ports.get(&session_id).cloned().unwrap_or_default(),
+            memory_limit_mb: None,
+            cpu_shares: None,
+            agent_type,
+            model,
+            image: self.session_images.get(&session_id).cloned(),
         };
 
         // Add initial log message
@@ -2677,8 +5039,8 @@ impl AppState {
             .find(|s| s.id == session_id)
             .map(|s| PathBuf::from(&s.workspace_path));
 
-        let result = if let Some(worktree_path) = existing_worktree_path {
-            if worktree_path.exists() {
+        let existing_worktree = match existing_worktree_path {
+            Some(worktree_path) if worktree_path.exists() => {
                 info!(
                     "Found existing worktree at {}, reusing it",
                     worktree_path.display()
@@ -2691,45 +5053,52 @@ impl AppState {
                     ));
                 }
 
-                let worktree_info = crate::git::WorktreeInfo {
+                Some(crate::git::WorktreeInfo {
                     id: session_id, // Use session ID as worktree ID
                     path: worktree_path.clone(),
-                    session_path: worktree_path.clone(), // Same as path for existing worktrees
+                    session_path: worktree_path, // Same as path for existing worktrees
                     branch_name: branch_name.to_string(),
                     source_repository: repo_path.to_path_buf(),
                     commit_hash: None, // We don't track this for existing worktrees
-                };
-
-                manager.create_session_with_existing_worktree(request, worktree_info).await
-            } else {
+                    detached: false,
+                })
+            }
+            Some(_) => {
                 info!("Worktree path no longer exists, creating fresh session");
 
                 if let Some(logs) = self.logs.get_mut(&session_id) {
                     logs.push("Worktree not found, creating fresh session...".to_string());
                 }
-
-                manager.create_session_with_logs(request, Some(log_sender.clone())).await
+                None
             }
-        } else {
-            info!("No existing worktree info found, creating fresh session");
+            None => {
+                info!("No existing worktree info found, creating fresh session");
 
-            if let Some(logs) = self.logs.get_mut(&session_id) {
-                logs.push("Creating fresh session...".to_string());
+                if let Some(logs) = self.logs.get_mut(&session_id) {
+                    logs.push("Creating fresh session...".to_string());
+                }
+                None
             }
-
-            manager.create_session_with_logs(request, Some(log_sender.clone())).await
         };
 
-        // Wait a moment for logs to be collected
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
-        // Transfer collected logs to our main logs HashMap
-        if let Ok(collected_logs) = session_logs.lock() {
-            if let Some(logs) = self.logs.get_mut(&session_id) {
-                logs.extend(collected_logs.clone());
+        Ok(ContainerCreation::spawn(move |log_sender| async move {
+            match existing_worktree {
+                Some(worktree_info) => {
+                    manager.create_session_with_existing_worktree(request, worktree_info).await
+                }
+                None => manager.create_session_with_logs(request, Some(log_sender)).await,
             }
-        }
+        }))
+    }
 
+    /// Log how a restart went and give a restarted Interactive session its tmux session
+    async fn finish_restart_session(
+        &mut self,
+        session_id: Uuid,
+        branch_name: &str,
+        mode: crate::models::SessionMode,
+        result: Result<SessionState, String>,
+    ) -> Result<(), String> {
         // Add completion log based on result
         if let Some(logs) = self.logs.get_mut(&session_id) {
             match &result {
@@ -2742,12 +5111,14 @@ impl AppState {
         // If Docker session creation succeeded AND this is Interactive mode, create corresponding tmux session
         // Boss mode sessions should NOT have tmux integration
         if let Ok(ref session_state) = result {
-            if mode_clone == crate::models::SessionMode::Interactive {
+            if mode == crate::models::SessionMode::Interactive {
                 if let Some(ref worktree_info) = session_state.worktree_info {
                     info!("Creating tmux session for restarted Interactive mode session {}", session_id);
 
-                    // Send log message about tmux session creation
-                    let _ = log_sender.send("Creating tmux session for interactive mode...".to_string());
+                    self.push_build_log(
+                        session_id,
+                        "Creating tmux session for interactive mode...".to_string(),
+                    );
 
                     // Create tmux session name from session info
                     let tmux_name = format!("tmux_{}", branch_name.replace('/', "_").replace(' ', "_"));
@@ -2771,11 +5142,17 @@ impl AppState {
                             // Store tmux session in our map
                             self.tmux_sessions.insert(session_id, tmux_session);
 
-                            let _ = log_sender.send("Tmux session created successfully!".to_string());
+                            self.push_build_log(
+                                session_id,
+                                "Tmux session created successfully!".to_string(),
+                            );
                         }
                         Err(e) => {
                             warn!("Failed to start tmux session: {}", e);
-                            let _ = log_sender.send(format!("Warning: Failed to create tmux session: {}", e));
+                            self.push_build_log(
+                                session_id,
+                                format!("Warning: Failed to create tmux session: {}", e),
+                            );
                             // Don't fail the whole session creation if tmux fails
                         }
                     }
@@ -2787,50 +5164,37 @@ impl AppState {
             }
         }
 
-        result.map(|_| ())?;
-        Ok(())
+        result.map(|_| ())
     }
 
-    async fn create_session_with_logs(
-        &mut self,
-        repo_path: &std::path::Path,
-        branch_name: &str,
-        session_id: Uuid,
-        skip_permissions: bool,
-        mode: crate::models::SessionMode,
-        boss_prompt: Option<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        // Branch based on session mode
-        match mode {
-            crate::models::SessionMode::Interactive => {
-                self.create_interactive_session(
-                    repo_path,
-                    branch_name,
-                    session_id,
-                    skip_permissions,
-                )
-                .await
-            }
-            crate::models::SessionMode::Boss => {
-                self.create_boss_session(
-                    repo_path,
-                    branch_name,
-                    session_id,
-                    skip_permissions,
-                    boss_prompt,
-                )
-                .await
+    /// Record a build log message. Build steps also move the progress bar; layer pull
+    /// updates only move the bar, since each layer reports dozens of them.
+    fn push_build_log(&mut self, session_id: Uuid, message: String) {
+        use crate::docker::BuildProgress;
+
+        self.ui_needs_refresh = true;
+        match crate::docker::parse_build_line(&message) {
+            BuildProgress::Line(_) => {}
+            progress @ BuildProgress::Step { .. } => self.build_progress = Some(progress),
+            progress @ BuildProgress::Layer { .. } => {
+                self.build_progress = Some(progress);
+                return;
             }
         }
+        info!("Build log for session {}: {}", session_id, message);
+        self.logs.entry(session_id).or_default().push(message);
     }
 
-    /// Create an Interactive mode session (host-based, no Docker)
+    /// Create an Interactive mode session (host-based, no Docker). With `review_only`,
+    /// `branch_name` is the revision to check out on a detached review worktree.
     async fn create_interactive_session(
         &mut self,
         repo_path: &std::path::Path,
         branch_name: &str,
         session_id: Uuid,
         skip_permissions: bool,
+        review_only: bool,
+        base_branch: Option<&str>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         use crate::interactive::InteractiveSessionManager;
 
@@ -2839,60 +5203,46 @@ impl AppState {
             session_id, branch_name, skip_permissions
         );
 
-        // Create a channel for logs
-        let (log_sender, mut log_receiver) = mpsc::unbounded_channel::<String>();
-
         // Initialize logs for this session
         self.logs.insert(session_id, vec!["Starting Interactive session creation...".to_string()]);
 
-        // Create a shared vector for logs
-        let session_logs = Arc::new(Mutex::new(Vec::new()));
-        let logs_clone = session_logs.clone();
-
-        // Spawn a task to collect logs
-        let session_id_clone = session_id;
-        tokio::spawn(async move {
-            while let Some(log_message) = log_receiver.recv().await {
-                if let Ok(mut logs) = logs_clone.lock() {
-                    logs.push(log_message.clone());
-                }
-                info!("Interactive session log for {}: {}", session_id_clone, log_message);
-            }
-        });
-
         let workspace_name = repo_path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown")
             .to_string();
 
-        // Send log updates
-        let _ = log_sender.send("Creating git worktree...".to_string());
+        self.push_build_log(session_id, "Creating git worktree...".to_string());
 
         // Create Interactive session manager (NO Docker dependency)
         let mut manager = InteractiveSessionManager::new()?;
 
-        // Create the session
-        let result = manager
-            .create_session(
-                session_id,
-                workspace_name.clone(),
-                repo_path.to_path_buf(),
-                branch_name.to_string(),
-                None, // base_branch
-                skip_permissions,
-            )
-            .await;
-
-        // Wait for logs to be collected
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        let model = self.new_session_state.as_ref().and_then(|s| s.model.clone());
 
-        // Transfer collected logs
-        if let Ok(collected_logs) = session_logs.lock() {
-            if let Some(logs) = self.logs.get_mut(&session_id) {
-                logs.extend(collected_logs.clone());
-            }
-        }
+        // Create the session
+        let result = if review_only {
+            manager
+                .create_review_session(
+                    session_id,
+                    workspace_name.clone(),
+                    repo_path.to_path_buf(),
+                    branch_name.to_string(),
+                    model.as_deref(),
+                )
+                .await
+        } else {
+            manager
+                .create_session(
+                    session_id,
+                    workspace_name.clone(),
+                    repo_path.to_path_buf(),
+                    branch_name.to_string(),
+                    base_branch.map(str::to_string),
+                    skip_permissions,
+                    model.as_deref(),
+                )
+                .await
+        };
 
         match result {
             Ok(interactive_session) => {
@@ -2902,7 +5252,9 @@ impl AppState {
                 }
 
                 // Convert to Session model and add to workspaces
-                let session = interactive_session.to_session_model();
+                let mut session = interactive_session.to_session_model();
+                session.model = model;
+                session.base_branch = base_branch.map(str::to_string);
 
                 // Find or create workspace for this repo
                 if let Some(workspace) = self.workspaces.iter_mut().find(|w| {
@@ -2942,15 +5294,16 @@ impl AppState {
         }
     }
 
-    /// Create a Boss mode session (Docker-based)
-    async fn create_boss_session(
+    /// Start creating a Boss mode session (Docker-based)
+    async fn start_boss_session(
         &mut self,
         repo_path: &std::path::Path,
         branch_name: &str,
         session_id: Uuid,
         skip_permissions: bool,
         boss_prompt: Option<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        base_branch: Option<&str>,
+    ) -> Result<ContainerCreation, Box<dyn std::error::Error>> {
         use crate::docker::session_lifecycle::{SessionLifecycleManager, SessionRequest};
 
         info!(
@@ -2958,43 +5311,34 @@ impl AppState {
             session_id, branch_name
         );
 
-        // Create a channel for build logs
-        let (log_sender, mut log_receiver) = mpsc::unbounded_channel::<String>();
-
         // Initialize logs for this session
         self.logs.insert(session_id, vec!["Starting Boss session creation...".to_string()]);
 
-        // Create a shared vector for logs
-        let session_logs = Arc::new(Mutex::new(Vec::new()));
-        let logs_clone = session_logs.clone();
-
-        // Spawn a task to collect logs
-        let session_id_clone = session_id;
-        tokio::spawn(async move {
-            while let Some(log_message) = log_receiver.recv().await {
-                if let Ok(mut logs) = logs_clone.lock() {
-                    logs.push(log_message.clone());
-                }
-                info!(
-                    "Build log for session {}: {}",
-                    session_id_clone, log_message
-                );
-            }
-        });
-
         let workspace_name =
             repo_path.file_name().and_then(|n| n.to_str()).unwrap_or("unknown").to_string();
 
+        // Picked in the new session dialog, which stays open until creation finishes
+        let agent_type = self.new_session_state.as_ref().map(|s| s.agent_type).unwrap_or_default();
+        let model = self.new_session_state.as_ref().and_then(|s| s.model.clone());
+
         let request = SessionRequest {
             session_id,
             workspace_name,
             workspace_path: repo_path.to_path_buf(),
             branch_name: branch_name.to_string(),
-            base_branch: None,
+            base_branch: base_branch.map(str::to_string),
             container_config: None,
             skip_permissions,
             mode: crate::models::SessionMode::Boss,
             boss_prompt,
+            env_vars: self.session_env.get(&session_id).cloned().unwrap_or_default(),
+            volumes: self.session_volumes.get(&session_id).cloned().unwrap_or_default(),
+            ports: self.session_ports.get(&session_id).cloned().unwrap_or_default(),
+            memory_limit_mb: None,
+            cpu_shares: None,
+            agent_type,
+            model,
+            image: None,
         };
 
         // Add initial log message
@@ -3006,28 +5350,9 @@ impl AppState {
         let mut manager = SessionLifecycleManager::new().await?;
 
         // Pass the log sender to the session lifecycle manager
-        let result = manager.create_session_with_logs(request, Some(log_sender)).await;
-
-        // Wait a moment for logs to be collected
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
-        // Transfer collected logs to our main logs HashMap
-        if let Ok(collected_logs) = session_logs.lock() {
-            if let Some(logs) = self.logs.get_mut(&session_id) {
-                logs.extend(collected_logs.clone());
-            }
-        }
-
-        // Add completion log based on result
-        if let Some(logs) = self.logs.get_mut(&session_id) {
-            match &result {
-                Ok(_) => logs.push("Boss session created successfully!".to_string()),
-                Err(e) => logs.push(format!("Session creation failed: {}", e)),
-            }
-        }
-
-        result.map(|_| ())?;
-        Ok(())
+        Ok(ContainerCreation::spawn(move |log_sender| async move {
+            manager.create_session_with_logs(request, Some(log_sender)).await
+        }))
     }
 
     /// Clean up orphaned containers (containers without worktrees) AND orphaned session state
@@ -3037,50 +5362,29 @@ impl AppState {
         info!("Starting cleanup of orphaned containers and state entries");
 
         let container_manager = ContainerManager::new().await?;
-        let containers = container_manager.list_agents_containers().await?;
+        let report = SessionManager::new(self.session_persistence.clone()).reconcile().await?;
 
         let mut cleaned_up = 0;
+        let mut skipped_protected = HashSet::new();
 
         // Step 1: Clean up orphaned containers (containers without worktrees)
-        for container in containers {
-            if let Some(session_id_str) =
-                container.labels.as_ref().and_then(|labels| labels.get("agents-session-id"))
-            {
-                if let Ok(session_id) = uuid::Uuid::parse_str(session_id_str) {
-                    // Check if worktree exists for this session
-                    let worktree_manager = crate::git::WorktreeManager::new()?;
-                    match worktree_manager.get_worktree_info(session_id) {
-                        Ok(_) => {
-                            // Worktree exists, container is not orphaned
-                            continue;
-                        }
-                        Err(_) => {
-                            // Worktree missing, this is an orphaned container
-                            info!(
-                                "Found orphaned container for session {}, removing it",
-                                session_id
-                            );
-
-                            if let Some(container_id) = &container.id {
-                                // Remove the orphaned container (this will stop it first)
-                                if let Err(e) =
-                                    container_manager.remove_container_by_id(container_id).await
-                                {
-                                    warn!(
-                                        "Failed to remove orphaned container {}: {}",
-                                        container_id, e
-                                    );
-                                } else {
-                                    cleaned_up += 1;
-                                    info!(
-                                        "Successfully removed orphaned container {}",
-                                        container_id
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
+        for orphan in report.with_health(SessionHealth::OrphanContainer) {
+            let session_id = orphan.session_id;
+            if self.protected_sessions.contains(&session_id) {
+                skipped_protected.insert(session_id);
+                continue;
+            }
+            let Some(container_id) = &orphan.container_id else {
+                continue;
+            };
+
+            info!("Found orphaned container for session {}, removing it", session_id);
+            // Remove the orphaned container (this will stop it first)
+            if let Err(e) = container_manager.remove_container_by_id(container_id).await {
+                warn!("Failed to remove orphaned container {}: {}", container_id, e);
+            } else {
+                cleaned_up += 1;
+                info!("Successfully removed orphaned container {}", container_id);
             }
         }
 
@@ -3104,6 +5408,9 @@ impl AppState {
             }
         }
 
+        let (orphaned_sessions, protected) = self.partition_protected(orphaned_sessions);
+        skipped_protected.extend(protected);
+
         // Remove orphaned session state entries
         for session_id in &orphaned_sessions {
             info!("Removing orphaned session state: {}", session_id);
@@ -3115,6 +5422,10 @@ impl AppState {
 
             // Clean up any remaining state
             self.live_logs.remove(session_id);
+            self.session_mcp_servers.remove(session_id);
+            if let Some(ref spill) = self.log_spill {
+                spill.discard(*session_id);
+            }
 
             cleaned_up += 1;
         }
@@ -3153,6 +5464,14 @@ impl AppState {
             }
         }
 
+        if !skipped_protected.is_empty() {
+            info!("Skipped {} protected sessions during cleanup", skipped_protected.len());
+            self.add_info_notification(format!(
+                "🔒 Skipped {} protected session(s)",
+                skipped_protected.len()
+            ));
+        }
+
         if cleaned_up > 0 {
             info!("Cleaned up {} orphaned items (containers + state + git refs)", cleaned_up);
             self.add_success_notification(format!(
@@ -3172,6 +5491,20 @@ impl AppState {
     }
 
     async fn delete_session(&mut self, session_id: Uuid) -> anyhow::Result<()> {
+        self.delete_session_resources(session_id).await?;
+
+        // Reload workspaces to ensure UI reflects the actual state
+        self.load_real_workspaces().await;
+        // Force UI refresh to show updated session list immediately
+        self.ui_needs_refresh = true;
+
+        info!("Successfully deleted session: {}", session_id);
+        Ok(())
+    }
+
+    /// Remove a session's container or tmux session, worktree and saved env without
+    /// reloading the workspace list
+    async fn delete_session_resources(&mut self, session_id: Uuid) -> anyhow::Result<()> {
         info!("Deleting session: {}", session_id);
 
         // Determine session mode by finding the session
@@ -3204,13 +5537,40 @@ impl AppState {
             }
         }
 
-        // Reload workspaces to ensure UI reflects the actual state
+        self.set_session_env(session_id, Vec::new());
+        self.set_session_volumes(session_id, Vec::new());
+        self.set_session_ports(session_id, Vec::new());
+        self.forget_session_image(session_id);
+        self.forget_session_usage(session_id);
+        Ok(())
+    }
+
+    /// Delete every given session in one pass, then reload once and report how many
+    /// were removed and how many failed
+    pub async fn cleanup_dead_sessions(&mut self, session_ids: Vec<Uuid>) -> (usize, usize) {
+        info!("Deleting {} stopped/errored sessions", session_ids.len());
+
+        let (mut removed, mut failed) = (0, 0);
+        for session_id in session_ids {
+            match self.delete_session_resources(session_id).await {
+                Ok(()) => removed += 1,
+                Err(e) => {
+                    error!("Failed to delete session {}: {}", session_id, e);
+                    failed += 1;
+                }
+            }
+        }
+
         self.load_real_workspaces().await;
-        // Force UI refresh to show updated session list immediately
         self.ui_needs_refresh = true;
 
-        info!("Successfully deleted session: {}", session_id);
-        Ok(())
+        let summary = format!("🧹 Removed {}, failed {}", removed, failed);
+        if failed > 0 {
+            self.add_error_notification(summary);
+        } else {
+            self.add_success_notification(summary);
+        }
+        (removed, failed)
     }
 
     /// Delete an Interactive mode session
@@ -3245,6 +5605,81 @@ impl AppState {
         Ok(())
     }
 
+    /// Remove a session's container and tmux attach session, leaving the worktree in
+    /// place. The reloaded session shows as stopped and can be revived with restart.
+    async fn stop_session_keep_worktree(&mut self, session_id: Uuid) -> anyhow::Result<()> {
+        use crate::docker::ContainerManager;
+
+        let Some(session) = self.find_session(session_id) else {
+            anyhow::bail!("Session {} not found", session_id);
+        };
+        let name = session.name.clone();
+        let Some(container_id) = session.container_id.clone() else {
+            anyhow::bail!("Session '{}' has no container", name);
+        };
+
+        info!("Stopping session {} and keeping its worktree", session_id);
+        if let Some(mut tmux_session) = self.tmux_sessions.remove(&session_id) {
+            if let Err(e) = tmux_session.cleanup().await {
+                warn!("Failed to cleanup tmux session: {}", e);
+            }
+        }
+        if self.attached_session_id == Some(session_id) {
+            self.attached_session_id = None;
+        }
+
+        ContainerManager::new().await?.remove_container_by_id(&container_id).await?;
+
+        self.load_real_workspaces().await;
+        self.ui_needs_refresh = true;
+        self.add_success_notification(format!(
+            "⏸ Stopped '{}' - worktree kept, press 'e' to revive",
+            name
+        ));
+        Ok(())
+    }
+
+    /// Stop a session's container but leave it in place with its worktree. The session
+    /// shows as stopped and restarts through the normal restart flow.
+    pub async fn stop_session(&mut self, session_id: Uuid) -> anyhow::Result<()> {
+        use crate::docker::ContainerManager;
+
+        let Some(session) = self.find_session(session_id) else {
+            anyhow::bail!("Session {} not found", session_id);
+        };
+        let name = session.name.clone();
+        let Some(container_id) = session.container_id.clone() else {
+            anyhow::bail!("Session '{}' has no container", name);
+        };
+        if session.status == crate::models::SessionStatus::Stopped {
+            self.add_info_notification(format!("Session '{}' is already stopped", name));
+            return Ok(());
+        }
+
+        info!("Stopping container {} for session {}", container_id, session_id);
+        if let Some(mut tmux_session) = self.tmux_sessions.remove(&session_id) {
+            if let Err(e) = tmux_session.cleanup().await {
+                warn!("Failed to cleanup tmux session: {}", e);
+            }
+        }
+        if self.attached_session_id == Some(session_id) {
+            self.attached_session_id = None;
+            self.current_view = View::SessionList;
+        }
+
+        ContainerManager::new().await?.stop_container_by_id(&container_id).await?;
+
+        if let Some(session) = self.find_session_mut(session_id) {
+            session.set_status(crate::models::SessionStatus::Stopped);
+        }
+        self.ui_needs_refresh = true;
+        self.add_success_notification(format!(
+            "⏸ Stopped '{}' - press 'e' to restart it",
+            name
+        ));
+        Ok(())
+    }
+
     /// Delete a Boss mode session
     async fn delete_boss_session(&mut self, session_id: Uuid) -> anyhow::Result<()> {
         use crate::docker::{ContainerManager, SessionLifecycleManager};
@@ -3337,6 +5772,12 @@ impl AppState {
                 AsyncAction::NewSessionNormal => {
                     self.new_session_normal().await;
                 }
+                AsyncAction::NewReviewSession => {
+                    self.new_review_session().await;
+                }
+                AsyncAction::BuildAgentsDevImage(then) => {
+                    self.build_agents_dev_image(*then);
+                }
                 AsyncAction::CreateNewSession => {
                     self.new_session_create().await;
                 }
@@ -3345,6 +5786,18 @@ impl AppState {
                         error!("Failed to delete session {}: {}", session_id, e);
                     }
                 }
+                AsyncAction::StopKeepWorktree(session_id) => {
+                    if let Err(e) = self.stop_session_keep_worktree(session_id).await {
+                        error!("Failed to stop session {}: {}", session_id, e);
+                        self.add_error_notification(format!("❌ Failed to stop session: {}", e));
+                    }
+                }
+                AsyncAction::StopSession(session_id) => {
+                    if let Err(e) = self.stop_session(session_id).await {
+                        error!("Failed to stop session {}: {}", session_id, e);
+                        self.add_error_notification(format!("❌ Failed to stop session: {}", e));
+                    }
+                }
                 AsyncAction::RefreshWorkspaces => {
                     info!("Manual refresh triggered");
                     // Reload workspace data and force UI refresh
@@ -3411,12 +5864,37 @@ impl AppState {
                         error!("Failed to re-authenticate: {}", e);
                     }
                 }
+                AsyncAction::Logout => {
+                    info!("Logging out of the active auth profile");
+                    self.handle_logout().await;
+                }
+                AsyncAction::InitClaudeChat => {
+                    if let Err(e) = self.init_claude_integration().await {
+                        self.add_error_notification(format!("❌ Claude chat unavailable: {}", e));
+                    } else if self.claude_manager.is_none() {
+                        self.add_warning_notification(
+                            "Claude chat needs authentication. Run `agents-box auth` first."
+                                .to_string(),
+                        );
+                    }
+                    self.ui_needs_refresh = true;
+                }
+                AsyncAction::SendClaudeMessage(message) => {
+                    if let Err(e) = self.send_claude_message(message).await {
+                        error!("Claude chat message failed: {}", e);
+                        self.add_error_notification(format!("❌ {}", e));
+                    }
+                    self.ui_needs_refresh = true;
+                }
                 AsyncAction::RestartSession(session_id) => {
                     info!("Starting session restart for session {}", session_id);
                     if let Err(e) = self.handle_restart_session(session_id).await {
                         error!("Failed to restart session: {}", e);
                     }
                 }
+                AsyncAction::AutoRestartSession(session_id) => {
+                    self.auto_restart_session(session_id).await;
+                }
                 AsyncAction::CleanupOrphaned => {
                     info!("Starting cleanup of orphaned containers");
                     if let Err(e) = self.cleanup_orphaned_containers().await {
@@ -3427,6 +5905,9 @@ impl AppState {
                         ));
                     }
                 }
+                AsyncAction::CleanupDeadSessions(session_ids) => {
+                    self.cleanup_dead_sessions(session_ids).await;
+                }
                 AsyncAction::AttachToOtherTmux(_session_name) => {
                     // NOTE: This action must be handled in main.rs where terminal access is available
                     warn!("AttachToOtherTmux action should be handled in main loop, not here");
@@ -3437,11 +5918,146 @@ impl AppState {
                     warn!("KillOtherTmux action should be handled in main loop, not here");
                     self.ui_needs_refresh = true;
                 }
+                AsyncAction::OpenExternalDiff(_session_id) => {
+                    // NOTE: This action must be handled in main.rs where terminal access is available
+                    warn!("OpenExternalDiff action should be handled in main loop, not here");
+                    self.ui_needs_refresh = true;
+                }
             }
         }
         Ok(())
     }
 
+    /// Make sure the agents-dev image exists before `then`, which needs it.
+    ///
+    /// A missing image is built in the background, after asking under the `prompt`
+    /// policy, and the build re-queues `then`; Ok(false) means `then` should stop for now.
+    async fn ensure_agents_dev_image(
+        &mut self,
+        then: AsyncAction,
+    ) -> Result<bool, crate::docker::MissingImageError> {
+        use crate::config::MissingImagePolicy;
+
+        if self.image_build.is_some() {
+            self.add_warning_notification(IMAGE_BUILD_RUNNING.to_string());
+            return Ok(false);
+        }
+        // Checked as under `prompt` so the `build` policy doesn't build inside this action
+        let check_policy = match self.missing_image_policy {
+            MissingImagePolicy::Build => MissingImagePolicy::Prompt,
+            policy => policy,
+        };
+        match crate::docker::ensure_agents_dev_image(check_policy, false, None).await {
+            Ok(()) => Ok(true),
+            Err(crate::docker::MissingImageError::NeedsConfirmation(_))
+                if self.missing_image_policy == MissingImagePolicy::Build =>
+            {
+                self.build_agents_dev_image(then);
+                Ok(false)
+            }
+            Err(crate::docker::MissingImageError::NeedsConfirmation(image)) => {
+                self.confirmation_dialog = Some(ConfirmationDialog {
+                    title: "Build Image".to_string(),
+                    message: format!(
+                        "Image {} is missing.\n\nBuild it now? This can take several minutes.",
+                        image
+                    ),
+                    confirm_action: ConfirmAction::BuildAgentsDevImage(Box::new(then)),
+                    selected_option: false,
+                });
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Start building the agents-dev image in the background; `poll_image_build` shows
+    /// its steps and runs `then` once it's built
+    fn build_agents_dev_image(&mut self, then: AsyncAction) {
+        if self.image_build.is_some() {
+            self.add_warning_notification(IMAGE_BUILD_RUNNING.to_string());
+            return;
+        }
+        info!("Building agents-dev image before {:?}", then);
+        let policy = self.missing_image_policy;
+        let (progress_tx, progress) = mpsc::channel(100);
+        self.image_build = Some(ImageBuild {
+            task: tokio::spawn(async move {
+                crate::docker::ensure_agents_dev_image(policy, true, Some(progress_tx))
+                    .await
+                    .map_err(|e| e.to_string())
+            }),
+            progress,
+            then,
+        });
+        self.show_image_build_progress(format!("{}...", IMAGE_BUILD_MESSAGE));
+    }
+
+    /// Show the steps of the agents-dev image build and, once it's done, report the
+    /// outcome and run the action that needed it
+    pub async fn poll_image_build(&mut self) {
+        use crate::docker::{AgentsDevProgress, BuildProgress};
+
+        let Some(build) = self.image_build.as_mut() else {
+            return;
+        };
+        // Checked before draining so steps sent just before finishing aren't lost
+        let finished = build.task.is_finished();
+        let mut step = None;
+        while let Ok(progress) = build.progress.try_recv() {
+            if let AgentsDevProgress::BuildingImage(line) = progress {
+                if let BuildProgress::Step { current, total, .. } =
+                    crate::docker::parse_build_line(&line)
+                {
+                    step = Some((current, total));
+                } else {
+                    debug!("agents-dev image build: {}", line.trim_end());
+                }
+            }
+        }
+        if let Some((current, total)) = step {
+            self.show_image_build_progress(format!(
+                "{}: step {}/{}",
+                IMAGE_BUILD_MESSAGE, current, total
+            ));
+        }
+        if !finished {
+            return;
+        }
+
+        let Some(build) = self.image_build.take() else {
+            return;
+        };
+        self.notifications.retain(|n| !n.message.starts_with(IMAGE_BUILD_MESSAGE));
+        match build.task.await.unwrap_or_else(|e| Err(e.to_string())) {
+            Ok(()) => {
+                self.add_success_notification("✅ agents-dev image built".to_string());
+                self.pending_async_action = Some(build.then);
+            }
+            Err(e) => {
+                error!("Failed to build agents-dev image: {}", e);
+                self.add_error_notification(format!("❌ Failed to build image: {}", e));
+            }
+        }
+        self.ui_needs_refresh = true;
+    }
+
+    /// Update the sticky image build notification in place rather than stacking one per
+    /// step. It's brought back if dismissed, and removed once the build finishes.
+    fn show_image_build_progress(&mut self, message: String) {
+        self.ui_needs_refresh = true;
+        if let Some(notification) =
+            self.notifications.iter_mut().find(|n| n.message.starts_with(IMAGE_BUILD_MESSAGE))
+        {
+            notification.message = message;
+            return;
+        }
+        self.add_notification(Notification {
+            sticky: true,
+            ..Notification::info(message)
+        });
+    }
+
     /// Run OAuth authentication setup
     async fn run_oauth_setup(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         use crossterm::{
@@ -3450,8 +6066,7 @@ impl AppState {
         };
 
         // Create auth directory
-        let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-        let auth_dir = home_dir.join(".agents-in-a-box/auth");
+        let auth_dir = Self::active_auth_dir().ok_or("Could not determine home directory")?;
 
         info!("Creating auth directory: {}", auth_dir.display());
         std::fs::create_dir_all(&auth_dir)?;
@@ -3463,41 +6078,32 @@ impl AppState {
         }
 
         // First check if Docker is available
-        if !self.is_docker_available().await {
+        if let Some(reason) = self.docker_unavailable_reason().await {
             warn!("Docker is not available or not running");
             if let Some(ref mut auth_state) = self.auth_setup_state {
-                auth_state.error_message = Some(
-                    "❌ Docker is not available\n\n\
-                     Please start Docker and try again."
-                        .to_string(),
-                );
+                auth_state.error_message =
+                    Some(format!("❌ Docker is not available\n\n{}", reason));
                 auth_state.is_processing = false;
             }
             return Err("Docker not available".into());
         }
 
-        // Check if image exists
-        let image_name = "agents-box:agents-dev";
-        let image_check = std::process::Command::new("docker")
-            .args(["image", "inspect", image_name])
-            .output()?;
-
-        if !image_check.status.success() {
-            info!("Building agents-dev image...");
-            let build_status = std::process::Command::new("docker")
-                .args(["build", "-t", image_name, "docker/agents-dev"])
-                .status()?;
-
-            if !build_status.success() {
+        // Make sure the agents-dev image exists, asking first if the policy says so
+        match self.ensure_agents_dev_image(AsyncAction::AuthSetupOAuth).await {
+            Ok(true) => {}
+            Ok(false) => {
                 if let Some(ref mut auth_state) = self.auth_setup_state {
-                    auth_state.error_message = Some(
-                        "❌ Failed to build claude-dev image\n\n\
-                         Please check Docker and try again."
-                            .to_string(),
-                    );
+                    auth_state.error_message = None;
                     auth_state.is_processing = false;
                 }
-                return Err("Failed to build image".into());
+                return Ok(());
+            }
+            Err(e) => {
+                if let Some(ref mut auth_state) = self.auth_setup_state {
+                    auth_state.error_message = Some(format!("❌ {}", e));
+                    auth_state.is_processing = false;
+                }
+                return Err(e.into());
             }
         }
 
@@ -3533,7 +6139,7 @@ impl AppState {
                 "claude-user",
                 "--entrypoint",
                 "bash",
-                image_name,
+                crate::docker::agents_dev::AGENTS_DEV_IMAGE,
                 "-c",
                 "/app/scripts/auth-setup.sh",
             ])
@@ -3600,50 +6206,38 @@ impl AppState {
 
     /// Check if Docker is available and running
     async fn is_docker_available(&self) -> bool {
-        // Try to run a simple docker command to check if Docker is available
-        match std::process::Command::new("docker")
-            .args(["version", "--format", "{{.Server.Version}}"])
-            .output()
-        {
-            Ok(output) => {
-                if output.status.success() {
-                    let version = String::from_utf8_lossy(&output.stdout);
-                    info!("Docker is available, version: {}", version.trim());
-                    true
-                } else {
-                    let error = String::from_utf8_lossy(&output.stderr);
-                    warn!("Docker command failed: {}", error);
-                    false
-                }
+        self.docker_unavailable_reason().await.is_none()
+    }
+
+    /// Why Docker cannot be used, or None when the daemon is reachable
+    async fn docker_unavailable_reason(&self) -> Option<String> {
+        match crate::docker::check_docker_daemon() {
+            Ok(version) => {
+                info!("Docker is available, version: {}", version);
+                None
             }
-            Err(e) => {
-                warn!("Docker not found or not accessible: {}", e);
-                false
+            Err(reason) => {
+                warn!("Docker not available: {}", reason);
+                Some(reason)
             }
         }
     }
 
     /// Save API key authentication
     async fn save_api_key(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let api_key = match &self.auth_setup_state {
-            Some(auth_state) => auth_state.api_key_input.clone(),
+        let (api_key, skip_validation) = match &self.auth_setup_state {
+            Some(auth_state) => (auth_state.api_key_input.clone(), auth_state.skip_validation),
             None => return Err("No API key to save".into()),
         };
 
-        // Validate API key format
-        if !api_key.starts_with("sk-") || api_key.len() < 20 {
-            return Err("Invalid API key format".into());
+        let profiles = AuthProfiles::in_home_dir().ok_or("Could not determine home directory")?;
+        let env_path = profiles.active_env_file();
+        let check_with = (!skip_validation).then(|| ClaudeAuth::from_api_key(api_key.clone()));
+        let result = Self::store_api_key(&api_key, &env_path, check_with).await;
+        if let Some(ref mut auth_state) = self.auth_setup_state {
+            auth_state.validating = false;
         }
-
-        // Create .env file in agents-in-a-box directory
-        let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-        let claude_box_dir = home_dir.join(".agents-in-a-box");
-        std::fs::create_dir_all(&claude_box_dir)?;
-
-        let env_path = claude_box_dir.join(".env");
-        std::fs::write(&env_path, format!("ANTHROPIC_API_KEY={}\n", api_key))?;
-
-        info!("API key saved to {:?}", env_path);
+        result?;
 
         // Success - transition to main view
         self.auth_setup_state = None;
@@ -3654,26 +6248,202 @@ impl AppState {
         Ok(())
     }
 
-    /// Handle re-authentication of Claude credentials
-    async fn handle_reauthenticate(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Check if any sessions are currently running
-        let running_session_count =
-            self.workspaces.iter().map(|w| w.running_sessions().len()).sum::<usize>();
+    /// Sessions running with the current credentials
+    fn running_session_count(&self) -> usize {
+        self.workspaces.iter().map(|w| w.running_sessions().len()).sum()
+    }
+
+    pub fn show_auth_profile_picker(&mut self) {
+        let Some(profiles) = AuthProfiles::in_home_dir() else {
+            self.add_error_notification("Could not determine home directory".to_string());
+            return;
+        };
+        let names = profiles.list();
+        let active = profiles.active();
+        let selected = names.iter().position(|name| *name == active).unwrap_or(0);
+        self.auth_profile_picker = Some(AuthProfilePickerState {
+            profiles: names,
+            active,
+            selected,
+        });
+    }
+
+    pub fn move_auth_profile_selection(&mut self, delta: isize) {
+        if let Some(picker) = self.auth_profile_picker.as_mut() {
+            let last = picker.profiles.len().saturating_sub(1);
+            picker.selected = picker.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Make the selected profile active. Like re-authentication, this needs every
+    /// session stopped, as running containers keep the old profile's credentials.
+    pub fn switch_auth_profile(&mut self) {
+        let Some(picker) = self.auth_profile_picker.as_ref() else {
+            return;
+        };
+        let Some(name) = picker.profiles.get(picker.selected).cloned() else {
+            return;
+        };
+        if name == picker.active {
+            self.auth_profile_picker = None;
+            return;
+        }
 
+        let running_session_count = self.running_session_count();
         if running_session_count > 0 {
-            warn!(
-                "Found {} running sessions - re-authentication will affect them",
+            self.add_error_notification(format!(
+                "❌ Cannot switch auth profile with {} running sessions\n\n\
+                 Running sessions use the current credentials.\n\
+                 Please stop all sessions before switching profiles.",
                 running_session_count
-            );
+            ));
+            return;
+        }
 
-            // For now, we'll show an error and require manual session cleanup
-            // TODO: Add confirmation dialog with option to stop sessions automatically
-            if let Some(ref mut auth_state) = self.auth_setup_state {
-                auth_state.error_message = Some(format!(
-                    "❌ Cannot re-authenticate with {} running sessions\n\n\
-                     Running sessions use the current credentials.\n\
-                     Please stop all sessions before re-authenticating.\n\n\
-                     Use 'd' to delete sessions or wait for them to complete.",
+        let result = AuthProfiles::in_home_dir()
+            .ok_or_else(|| "Could not determine home directory".to_string())
+            .and_then(|profiles| profiles.set_active(&name));
+        self.auth_profile_picker = None;
+        if let Err(e) = result {
+            self.add_error_notification(format!("Failed to switch auth profile: {}", e));
+            return;
+        }
+
+        info!("Switched auth profile to '{}'", name);
+        self.refresh_auth_credential();
+        if Self::is_first_time_setup() {
+            // A new profile has no credentials yet
+            self.auth_setup_state = Some(AuthSetupState {
+                selected_method: AuthMethod::OAuth,
+                api_key_input: String::new(),
+                validating: false,
+                skip_validation: false,
+                is_processing: false,
+                show_cursor: false,
+                error_message: None,
+            });
+            self.current_view = View::AuthSetup;
+        } else {
+            self.add_success_notification(format!("Switched to auth profile '{}'", name));
+        }
+    }
+
+    /// Ask before logging out; refused while sessions run, as for re-authentication
+    pub fn show_logout_confirmation(&mut self) {
+        let running_session_count = self.running_session_count();
+        if running_session_count > 0 {
+            self.add_error_notification(format!(
+                "❌ Cannot log out with {} running sessions\n\n\
+                 Running sessions use the current credentials.\n\
+                 Please stop all sessions before logging out.",
+                running_session_count
+            ));
+            return;
+        }
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Log Out".to_string(),
+            message: format!(
+                "Log out of auth profile '{}'? The refresh token is revoked and the stored \
+                 credentials are removed (a backup is kept in the auth directory).",
+                self.auth_profile
+            ),
+            confirm_action: ConfirmAction::Logout,
+            selected_option: false,
+        });
+    }
+
+    /// Revoke the OAuth refresh token and remove the active profile's credentials
+    async fn handle_logout(&mut self) {
+        let running_session_count = self.running_session_count();
+        if running_session_count > 0 {
+            self.add_error_notification(format!(
+                "❌ Cannot log out with {} running sessions",
+                running_session_count
+            ));
+            return;
+        }
+        let Some(auth_dir) = Self::active_auth_dir() else {
+            self.add_error_notification("Could not determine home directory".to_string());
+            return;
+        };
+
+        match crate::app::logout::logout(&auth_dir, true).await {
+            Ok(outcome) => {
+                info!("Logged out: {}", outcome.summary());
+                if matches!(outcome.revocation, Some(Err(_))) {
+                    self.add_warning_notification(format!("⚠ {}", outcome.summary()));
+                } else {
+                    self.add_success_notification(format!("🔒 {}", outcome.summary()));
+                }
+            }
+            Err(e) => {
+                error!("Logout failed: {}", e);
+                self.add_error_notification(format!("❌ Logout failed: {}", e));
+            }
+        }
+        self.auth_status = AuthStatus::Unknown;
+        self.refresh_auth_credential();
+    }
+
+    /// Write an API key to the active profile's `.env`. With `check_with`, a test request
+    /// is sent first and a key Anthropic rejects is never written; without it (the offline
+    /// bypass) only the key's format is checked.
+    pub(crate) async fn store_api_key(
+        api_key: &str,
+        env_path: &Path,
+        check_with: Option<ClaudeAuth>,
+    ) -> Result<(), String> {
+        if !api_key.starts_with("sk-") || api_key.len() < 20 {
+            return Err("Invalid API key format".to_string());
+        }
+
+        if let Some(auth) = check_with {
+            info!("Validating API key against the Anthropic API");
+            let client = ClaudeApiClient::with_auth(auth).map_err(|e| e.to_string())?;
+            if let Err(e) = client.test_connection().await {
+                let message = e.to_string();
+                return Err(if message.starts_with("Claude API error 401") {
+                    "Anthropic rejected this API key (401 Unauthorized). \
+                     Check it was pasted in full."
+                        .to_string()
+                } else {
+                    format!(
+                        "Could not validate API key: {}\n\
+                         Press Tab to save it without the online check.",
+                        message
+                    )
+                });
+            }
+        }
+
+        if let Some(parent) = env_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(env_path, format!("ANTHROPIC_API_KEY={}\n", api_key))
+            .map_err(|e| e.to_string())?;
+        info!("API key saved to {:?}", env_path);
+        Ok(())
+    }
+
+    /// Handle re-authentication of Claude credentials
+    async fn handle_reauthenticate(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Check if any sessions are currently running
+        let running_session_count = self.running_session_count();
+
+        if running_session_count > 0 {
+            warn!(
+                "Found {} running sessions - re-authentication will affect them",
+                running_session_count
+            );
+
+            // For now, we'll show an error and require manual session cleanup
+            // TODO: Add confirmation dialog with option to stop sessions automatically
+            if let Some(ref mut auth_state) = self.auth_setup_state {
+                auth_state.error_message = Some(format!(
+                    "❌ Cannot re-authenticate with {} running sessions\n\n\
+                     Running sessions use the current credentials.\n\
+                     Please stop all sessions before re-authenticating.\n\n\
+                     Use 'd' to delete sessions or wait for them to complete.",
                     running_session_count
                 ));
                 auth_state.is_processing = false;
@@ -3682,6 +6452,8 @@ impl AppState {
                 self.auth_setup_state = Some(AuthSetupState {
                     selected_method: AuthMethod::OAuth,
                     api_key_input: String::new(),
+                    validating: false,
+                    skip_validation: false,
                     is_processing: false,
                     show_cursor: false,
                     error_message: Some(format!(
@@ -3700,42 +6472,16 @@ impl AppState {
         // No running sessions - safe to proceed with re-authentication
         info!("No running sessions found - proceeding with re-authentication");
 
-        // Create backup of existing credentials
-        let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-        let auth_dir = home_dir.join(".agents-in-a-box/auth");
-
-        let credentials_path = auth_dir.join(".credentials.json");
-        let claude_json_path = auth_dir.join(".claude.json");
-        let backup_suffix = format!(".backup-{}", chrono::Utc::now().timestamp());
-
-        // Create backups if files exist
-        if credentials_path.exists() {
-            let backup_path = credentials_path.with_extension(&format!("json{}", backup_suffix));
-            std::fs::copy(&credentials_path, &backup_path)?;
-            info!("Backed up credentials to {:?}", backup_path);
-        }
-
-        if claude_json_path.exists() {
-            let backup_path = claude_json_path.with_extension(&format!("json{}", backup_suffix));
-            std::fs::copy(&claude_json_path, &backup_path)?;
-            info!("Backed up claude.json to {:?}", backup_path);
-        }
-
-        // Remove existing credentials to trigger re-authentication
-        if credentials_path.exists() {
-            std::fs::remove_file(&credentials_path)?;
-            info!("Removed existing credentials");
-        }
-
-        if claude_json_path.exists() {
-            std::fs::remove_file(&claude_json_path)?;
-            info!("Removed existing claude.json");
-        }
+        // Back up and remove existing credentials to trigger re-authentication
+        let auth_dir = Self::active_auth_dir().ok_or("Could not determine home directory")?;
+        crate::app::logout::backup_and_remove_credentials(&auth_dir)?;
 
         // Initialize auth setup state and switch to auth view
         self.auth_setup_state = Some(AuthSetupState {
             selected_method: AuthMethod::OAuth, // Default to OAuth
             api_key_input: String::new(),
+            validating: false,
+            skip_validation: false,
             is_processing: false,
             show_cursor: false,
             error_message: Some(
@@ -3783,6 +6529,8 @@ impl AppState {
                         is_current_dir_mode: false,
                         skip_permissions: session.skip_permissions,
                         mode: session.mode.clone(),
+                        agent_type: session.agent_type,
+                        model: session.model.clone(),
                         boss_prompt: if let Some(ref prompt) = session.boss_prompt {
                             TextEditor::from_string(prompt)
                         } else {
@@ -3790,6 +6538,25 @@ impl AppState {
                         },
                         file_finder: FuzzyFileFinderState::new(),
                         restart_session_id: Some(session_id), // Mark this as a restart operation
+                        review_mode: false,
+                        branch_name_generated: false,
+                                    saved_draft: String::new(),
+                        pending_paste: None,
+                        base_branch: None,
+                        base_branches: vec![],
+                        base_branch_filter: String::new(),
+                        selected_base_branch_index: None,
+                        env_vars: self.session_env.get(&session_id).cloned().unwrap_or_default(),
+                        volumes: self
+                            .session_volumes
+                            .get(&session_id)
+                            .cloned()
+                            .unwrap_or_default(),
+                        ports: self.session_ports.get(&session_id).cloned().unwrap_or_default(),
+                        list_entry: ListEntryInput::default(),
+                        branch_error: None,
+                        branch_conflict: None,
+                        branch_prefix: self.branch_prefix.clone(),
                     });
 
                     self.add_info_notification(
@@ -3802,54 +6569,950 @@ impl AppState {
                         session_id
                     );
 
-                    // For Idle sessions, we restart Claude within the existing tmux session
-                    if let Err(e) = self.restart_claude_in_tmux(session_id).await {
-                        error!("Failed to restart Claude in tmux for session {}: {}", session_id, e);
-                        self.add_error_notification(format!(
-                            "❌ Failed to restart Claude: {}",
-                            e
-                        ));
-                    } else {
-                        self.add_success_notification(
-                            "✓ Claude restarted successfully".to_string(),
-                        );
-                    }
-                }
-                status => {
-                    warn!(
-                        "Cannot restart session {} - current status: {:?}",
-                        session_id, status
-                    );
-                    self.add_error_notification(format!(
-                        "❌ Cannot restart session - current status: {:?}",
-                        status
-                    ));
-                }
+                    // For Idle sessions, we restart Claude within the existing tmux session
+                    if let Err(e) = self.restart_claude_in_tmux(session_id).await {
+                        error!("Failed to restart Claude in tmux for session {}: {}", session_id, e);
+                        self.add_error_notification(format!(
+                            "❌ Failed to restart Claude: {}",
+                            e
+                        ));
+                    } else {
+                        self.add_success_notification(
+                            "✓ Claude restarted successfully".to_string(),
+                        );
+                    }
+                }
+                status => {
+                    warn!(
+                        "Cannot restart session {} - current status: {:?}",
+                        session_id, status
+                    );
+                    self.add_error_notification(format!(
+                        "❌ Cannot restart session - current status: {:?}",
+                        status
+                    ));
+                }
+            }
+        } else {
+            error!("Session {} not found in workspaces", session_id);
+            self.add_error_notification("❌ Session not found".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// The tmux session to attach to for `session_id`
+    pub fn tmux_session_name_for(&self, session_id: Uuid) -> Result<String, String> {
+        let session = self
+            .workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .find(|s| s.id == session_id)
+            .ok_or_else(|| "Session not found".to_string())?;
+        session
+            .tmux_session_name
+            .clone()
+            .ok_or_else(|| format!("Session '{}' has no tmux session", session.name))
+    }
+
+    /// The session's tmux session has exited: mark the session stopped and offer to
+    /// restart it
+    pub fn offer_restart_for_dead_tmux(&mut self, session_id: Uuid, tmux_session_name: &str) {
+        warn!("tmux session '{}' for session {} is gone", tmux_session_name, session_id);
+        if let Some(session) = self.find_session_mut(session_id) {
+            session.set_status(crate::models::SessionStatus::Stopped);
+        }
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Session Ended".to_string(),
+            message: format!(
+                "The tmux session '{}' is no longer running. Restart the session?",
+                tmux_session_name
+            ),
+            confirm_action: ConfirmAction::RestartSession(session_id),
+            selected_option: false,
+        });
+        self.ui_needs_refresh = true;
+    }
+
+    pub fn show_logs_viewer(&mut self) {
+        if self.selected_session().is_none() {
+            self.add_warning_notification("⚠️ No session selected".to_string());
+            return;
+        }
+        self.current_view = View::Logs;
+    }
+
+    pub fn show_dashboard(&mut self) {
+        self.refresh_dashboard();
+        self.current_view = View::Dashboard;
+    }
+
+    /// Recompute the dashboard summary. Worktree disk use is measured in the background
+    /// and shows the previous measurement until the new one lands.
+    pub fn refresh_dashboard(&mut self) {
+        self.last_dashboard_refresh = Some(Instant::now());
+
+        if self.worktree_disk_task.is_none() {
+            let mut worktree_paths: Vec<std::path::PathBuf> = self
+                .workspaces
+                .iter()
+                .flat_map(|w| w.sessions.iter().map(|s| s.workspace_path.clone().into()))
+                .collect();
+            worktree_paths.sort();
+            worktree_paths.dedup();
+            self.worktree_disk_task = Some(tokio::task::spawn_blocking(move || {
+                worktree_paths
+                    .iter()
+                    .map(|path| crate::components::dashboard::directory_size(path))
+                    .sum()
+            }));
+        }
+
+        let summary = crate::components::dashboard::DashboardSummary::collect(
+            &self.workspaces,
+            &self.live_logs,
+            &self.session_usage,
+            self.worktree_disk_bytes,
+        );
+        if let Some(ref mut dashboard) = self.dashboard {
+            dashboard.update(summary);
+        } else {
+            self.dashboard = Some(crate::components::DashboardState::new(summary));
+        }
+    }
+
+    /// Recount every session's git changes and divergence from its base in the background.
+    /// Sessions whose worktree is gone are left as they are.
+    pub fn refresh_git_status(&mut self) {
+        self.last_git_status_refresh = Some(Instant::now());
+        if self.git_status_task.is_some() {
+            return;
+        }
+
+        let worktrees: Vec<(Uuid, PathBuf, Option<String>)> = self
+            .workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .map(|s| (s.id, PathBuf::from(&s.workspace_path), s.base_branch.clone()))
+            .filter(|(_, path, _)| path.is_dir())
+            .collect();
+        self.git_status_task = Some(tokio::task::spawn_blocking(move || {
+            worktrees
+                .into_iter()
+                .filter_map(|(session_id, path, base_branch)| {
+                    let analyzer = crate::git::diff_analyzer::DiffAnalyzer::new(&path).ok()?;
+                    match analyzer.get_simple_changes(base_branch.as_deref()) {
+                        Ok(changes) => Some((session_id, changes)),
+                        Err(e) => {
+                            debug!("Git status failed for {}: {}", path.display(), e);
+                            None
+                        }
+                    }
+                })
+                .collect()
+        }));
+    }
+
+    /// Pick up finished git status counts
+    pub async fn poll_git_status(&mut self) {
+        if !self.git_status_task.as_ref().is_some_and(tokio::task::JoinHandle::is_finished) {
+            return;
+        }
+        if let Some(task) = self.git_status_task.take() {
+            match task.await {
+                Ok(statuses) => {
+                    for (session_id, changes) in statuses {
+                        if let Some(session) = self.find_session_mut(session_id) {
+                            session.git_changes = changes;
+                        }
+                    }
+                    self.ui_needs_refresh = true;
+                }
+                Err(e) => warn!("Git status refresh failed: {}", e),
+            }
+        }
+    }
+
+    /// Sample CPU and memory of every running session container in the background
+    pub fn refresh_container_usage(&mut self) {
+        self.last_container_usage_refresh = Some(Instant::now());
+        if self.container_usage_task.is_some() {
+            return;
+        }
+
+        let container_ids: Vec<String> = self
+            .workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .filter(|s| s.status == crate::models::SessionStatus::Running)
+            .filter_map(|s| s.container_id.clone())
+            .collect();
+        if container_ids.is_empty() {
+            self.container_usage = None;
+            return;
+        }
+
+        self.container_usage_task = Some(tokio::spawn(async move {
+            let manager = crate::docker::ContainerManager::new().await.ok()?;
+            let samples = futures_util::future::join_all(
+                container_ids.iter().map(|id| manager.container_usage(id)),
+            )
+            .await;
+            let mut total = crate::docker::ContainerUsage::default();
+            for sample in samples {
+                match sample {
+                    Ok(usage) => total.add(usage),
+                    Err(e) => debug!("Container stats failed: {}", e),
+                }
+            }
+            Some(total)
+        }));
+    }
+
+    /// Pick up a finished container usage sample
+    pub async fn poll_container_usage(&mut self) {
+        if !self.container_usage_task.as_ref().is_some_and(tokio::task::JoinHandle::is_finished) {
+            return;
+        }
+        if let Some(task) = self.container_usage_task.take() {
+            match task.await {
+                Ok(usage) => {
+                    self.container_usage = usage;
+                    self.ui_needs_refresh = true;
+                }
+                Err(e) => warn!("Container usage sample failed: {}", e),
+            }
+        }
+    }
+
+    /// Start a background status check of running sessions' containers, plus those being
+    /// restarted automatically
+    pub fn refresh_container_health(&mut self) {
+        self.last_health_check = Some(Instant::now());
+        if self.health_check_task.is_some() {
+            return;
+        }
+
+        let container_ids: Vec<String> = self
+            .workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .filter(|s| {
+                s.status == crate::models::SessionStatus::Running
+                    || self.restart_attempts.contains_key(&s.id)
+            })
+            .filter_map(|s| s.container_id.clone())
+            .collect();
+        if container_ids.is_empty() {
+            return;
+        }
+
+        self.health_check_task = Some(tokio::spawn(async move {
+            match crate::docker::ContainerManager::new().await {
+                Ok(manager) => manager.get_statuses(&container_ids).await,
+                Err(e) => {
+                    debug!("Container health check skipped: {}", e);
+                    HashMap::new()
+                }
+            }
+        }));
+    }
+
+    /// Pick up a finished container health check
+    pub async fn poll_container_health(&mut self) {
+        if !self.health_check_task.as_ref().is_some_and(tokio::task::JoinHandle::is_finished) {
+            return;
+        }
+        if let Some(task) = self.health_check_task.take() {
+            match task.await {
+                Ok(statuses) => self.apply_container_health(&statuses),
+                Err(e) => warn!("Container health check failed: {}", e),
+            }
+        }
+    }
+
+    /// Stop sessions whose containers died and, with auto-restart on, queue a restart.
+    /// A session restarted `auto_restart_max_attempts` times without its container being
+    /// seen up in between is marked errored instead.
+    pub fn apply_container_health(
+        &mut self,
+        statuses: &HashMap<String, crate::docker::ContainerStatus>,
+    ) {
+        use crate::docker::ContainerStatus;
+        use crate::models::SessionStatus;
+
+        let checked: Vec<(Uuid, String, bool, ContainerStatus)> = self
+            .workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .filter_map(|s| {
+                let status = statuses.get(s.container_id.as_ref()?)?;
+                Some((s.id, s.name.clone(), s.status == SessionStatus::Running, status.clone()))
+            })
+            .collect();
+
+        for (session_id, name, was_running, status) in checked {
+            if matches!(
+                status,
+                ContainerStatus::Running | ContainerStatus::Creating | ContainerStatus::Paused
+            ) {
+                if was_running {
+                    self.restart_attempts.remove(&session_id);
+                }
+                continue;
+            }
+            if !was_running && !self.restart_attempts.contains_key(&session_id) {
+                continue;
+            }
+
+            if was_running {
+                warn!("Container for session {} stopped unexpectedly: {:?}", session_id, status);
+                if let Some(session) = self.find_session_mut(session_id) {
+                    session.set_status(SessionStatus::Stopped);
+                }
+                let reason = match status {
+                    ContainerStatus::Error(e) => format!(" ({})", e),
+                    _ => String::new(),
+                };
+                self.add_warning_notification(format!(
+                    "💀 '{}' stopped unexpectedly{}",
+                    name, reason
+                ));
+                self.ui_needs_refresh = true;
+            }
+            if !self.auto_restart {
+                continue;
+            }
+
+            let attempts = self.restart_attempts.get(&session_id).copied().unwrap_or(0);
+            if attempts >= self.auto_restart_max_attempts {
+                self.restart_attempts.remove(&session_id);
+                let message = format!("Gave up restarting after {} attempts", attempts);
+                if let Some(session) = self.find_session_mut(session_id) {
+                    session.set_status(SessionStatus::Error(message.clone()));
+                }
+                self.add_error_notification(format!("❌ '{}': {}", name, message));
+                self.ui_needs_refresh = true;
+                continue;
+            }
+            // Another action is queued; the next check tries again
+            if self.pending_async_action.is_some() {
+                continue;
+            }
+            self.restart_attempts.insert(session_id, attempts + 1);
+            self.pending_async_action = Some(AsyncAction::AutoRestartSession(session_id));
+            self.add_info_notification(format!(
+                "🔄 Restarting '{}' (attempt {}/{})",
+                name,
+                attempts + 1,
+                self.auto_restart_max_attempts
+            ));
+        }
+    }
+
+    /// Start a session's existing container again after the health check found it dead
+    async fn auto_restart_session(&mut self, session_id: Uuid) {
+        let Some(session) = self.find_session(session_id) else {
+            self.restart_attempts.remove(&session_id);
+            return;
+        };
+        let name = session.name.clone();
+        let Some(container_id) = session.container_id.clone() else {
+            self.restart_attempts.remove(&session_id);
+            return;
+        };
+
+        let result = match crate::docker::ContainerManager::new().await {
+            Ok(manager) => manager.start_container_by_id(&container_id).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                if let Some(session) = self.find_session_mut(session_id) {
+                    session.set_status(crate::models::SessionStatus::Running);
+                }
+                self.add_success_notification(format!("✅ Restarted '{}'", name));
+            }
+            // The session stays stopped; the next health check retries or gives up
+            Err(e) => {
+                warn!("Failed to restart container for session {}: {}", session_id, e);
+                self.add_error_notification(format!("❌ Failed to restart '{}': {}", name, e));
+            }
+        }
+        self.ui_needs_refresh = true;
+    }
+
+    /// Pick up a finished worktree disk measurement
+    pub async fn poll_worktree_disk_usage(&mut self) {
+        if !self.worktree_disk_task.as_ref().is_some_and(tokio::task::JoinHandle::is_finished) {
+            return;
+        }
+        if let Some(task) = self.worktree_disk_task.take() {
+            match task.await {
+                Ok(bytes) => {
+                    self.worktree_disk_bytes = Some(bytes);
+                    if let Some(ref mut dashboard) = self.dashboard {
+                        dashboard.summary.worktree_disk_bytes = Some(bytes);
+                    }
+                }
+                Err(e) => warn!("Worktree disk measurement failed: {}", e),
+            }
+        }
+    }
+
+    /// Leave the dashboard with the selected session selected in the session list
+    pub fn open_dashboard_selection(&mut self) {
+        let Some(session_id) = self.dashboard.as_ref().and_then(crate::components::DashboardState::selected_session_id)
+        else {
+            return;
+        };
+
+        let position = self.workspaces.iter().enumerate().find_map(|(w_idx, workspace)| {
+            workspace.sessions.iter().position(|s| s.id == session_id).map(|s_idx| (w_idx, s_idx))
+        });
+        match position {
+            Some((workspace_index, session_index)) => {
+                self.selected_workspace_index = Some(workspace_index);
+                self.selected_session_index = Some(session_index);
+                self.focused_pane = FocusedPane::Sessions;
+                self.current_view = View::SessionList;
+                self.dashboard = None;
+            }
+            None => {
+                self.add_warning_notification("⚠️ Session no longer exists".to_string());
+                self.refresh_dashboard();
+            }
+        }
+    }
+
+    pub fn show_git_view(&mut self) {
+        // Get the selected session's workspace path
+        if let Some(session) = self.get_selected_session() {
+            let worktree_path = std::path::PathBuf::from(&session.workspace_path);
+            let mut git_state = crate::components::GitViewState::new(worktree_path);
+            git_state.review_only = session.review_only;
+            // The branch the session started from, else the configured or default base
+            git_state.commit_log_base =
+                session.base_branch.clone().or_else(|| self.commit_log_base.clone());
+
+            // Refresh git status
+            if let Err(e) = git_state.refresh_git_status() {
+                tracing::error!("Failed to refresh git status: {}", e);
+                return;
+            }
+
+            self.git_view_state = Some(git_state);
+            self.current_view = View::GitView;
+        } else {
+            tracing::warn!("No session selected for git view");
+        }
+    }
+
+    /// Open the selected session's diff in the configured external diff tool,
+    /// falling back to the built-in git view when no tool is configured
+    pub fn open_external_diff(&mut self) {
+        let Some(session_id) = self.get_selected_session_id() else {
+            tracing::warn!("No session selected for external diff");
+            return;
+        };
+
+        if self.diff_tool_command.is_none() {
+            info!("No diff_tool_command configured, using built-in git view");
+            self.show_git_view();
+            return;
+        }
+
+        self.pending_async_action = Some(AsyncAction::OpenExternalDiff(session_id));
+    }
+
+    /// Copy the selected session's boss prompt to the clipboard and open it for review
+    pub fn show_boss_prompt(&mut self) {
+        let Some(session) = self.get_selected_session() else {
+            tracing::warn!("No session selected to copy boss prompt from");
+            return;
+        };
+
+        let Some(prompt) = session.boss_prompt.clone().filter(|p| !p.trim().is_empty()) else {
+            let name = session.name.clone();
+            self.add_info_notification(format!("Session '{}' has no boss prompt", name));
+            return;
+        };
+
+        self.prompt_viewer = Some(crate::components::PromptViewerState::new(
+            session.name.clone(),
+            prompt,
+            std::path::PathBuf::from(&session.workspace_path),
+        ));
+        self.copy_prompt_viewer_text();
+    }
+
+    /// The latest boss session in the same workspace that ran the same prompt before this one
+    pub fn find_previous_run(&self, session_id: Uuid) -> Option<&Session> {
+        let workspace =
+            self.workspaces.iter().find(|w| w.sessions.iter().any(|s| s.id == session_id))?;
+        let session = workspace.sessions.iter().find(|s| s.id == session_id)?;
+        let prompt = session.boss_prompt.as_deref().map(str::trim).filter(|p| !p.is_empty())?;
+
+        workspace
+            .sessions
+            .iter()
+            .filter(|s| s.id != session_id && s.created_at < session.created_at)
+            .filter(|s| s.boss_prompt.as_deref().map(str::trim) == Some(prompt))
+            .max_by_key(|s| s.created_at)
+    }
+
+    /// Compare the selected boss session's branch with the previous run of its prompt,
+    /// listing the files whose committed contents differ
+    pub fn compare_with_last_run(&mut self) {
+        let Some(session) = self.get_selected_session() else {
+            tracing::warn!("No session selected to compare with a previous run");
+            return;
+        };
+
+        let Some(previous) = self.find_previous_run(session.id) else {
+            self.add_info_notification(
+                "No earlier run of this boss prompt to compare with".to_string(),
+            );
+            return;
+        };
+
+        let session_name = session.name.clone();
+        let previous_name = previous.name.clone();
+        let result = crate::git::diff_analyzer::DiffAnalyzer::new(std::path::Path::new(
+            &session.workspace_path,
+        ))
+        .and_then(|analyzer| analyzer.analyze_branch_diff(&previous.branch_name, &session.branch_name));
+
+        match result {
+            Ok(stats) => {
+                self.run_compare = Some(crate::components::RunCompareState::new(
+                    session_name,
+                    previous_name,
+                    stats.files,
+                ));
+            }
+            Err(e) => {
+                warn!("Failed to compare {} with {}: {}", session_name, previous_name, e);
+                self.add_error_notification(format!(
+                    "❌ Failed to compare with previous run '{}': {}",
+                    previous_name, e
+                ));
+            }
+        }
+    }
+
+    /// Copy the selected session's full container ID for use with `docker exec`/`docker logs`
+    pub fn copy_selected_container_id(&mut self) {
+        let Some(session) = self.get_selected_session() else {
+            tracing::warn!("No session selected to copy container ID from");
+            return;
+        };
+
+        let Some(container_id) = session.container_id.clone() else {
+            let name = session.name.clone();
+            self.add_info_notification(format!("Session '{}' has no container", name));
+            return;
+        };
+
+        let short_id = &container_id[..12.min(container_id.len())];
+        self.copy_for_shell("Container ID", &container_id, short_id);
+    }
+
+    pub fn copy_selected_worktree_path(&mut self) {
+        let Some(session) = self.get_selected_session() else {
+            tracing::warn!("No session selected to copy worktree path from");
+            return;
+        };
+
+        let path = session.workspace_path.clone();
+        self.copy_for_shell("Worktree path", &path, &path);
+    }
+
+    /// Copy a value meant for commands in another terminal
+    fn copy_for_shell(&mut self, label: &str, value: &str, shown: &str) {
+        if self.copy_or_show(label, value) {
+            self.add_success_notification(format!("📋 {} {} copied to clipboard", label, shown));
+        }
+    }
+
+    /// Copy `value` to the clipboard, returning whether it was copied. Without a usable
+    /// clipboard (e.g. no display over SSH) the full value is shown instead so it can be selected.
+    fn copy_or_show(&mut self, label: &str, value: &str) -> bool {
+        match Self::copy_to_clipboard(value) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to copy {} to clipboard: {}", label, e);
+                self.add_info_notification(format!("Clipboard unavailable. {}: {}", label, value));
+                false
+            }
+        }
+    }
+
+    /// Copy the prompt viewer's current text (with @-files if toggled on) to the clipboard
+    pub fn copy_prompt_viewer_text(&mut self) {
+        let Some(viewer) = self.prompt_viewer.as_ref() else {
+            return;
+        };
+        let text = viewer.display_text().to_string();
+
+        if self.copy_or_show("Boss prompt", &text) {
+            self.add_success_notification("📋 Boss prompt copied to clipboard".to_string());
+        }
+    }
+
+    /// Host command for sessions of this repository: the project's own when allowed, else the global one
+    fn on_create_host_command_for(&self, repo_path: &std::path::Path) -> Option<String> {
+        let project_command = self
+            .allow_project_host_commands
+            .then(|| crate::config::ProjectConfig::load_from_dir(repo_path).ok().flatten())
+            .flatten()
+            .and_then(|project| project.on_create_host_command);
+        project_command
+            .or_else(|| self.on_create_host_command.clone())
+            .filter(|command| !command.trim().is_empty())
+    }
+
+    /// Start the configured host command for a newly created session, if any
+    fn run_on_create_host_command(&self, repo_path: &std::path::Path, session_id: Uuid) {
+        let Some(command) = self.on_create_host_command_for(repo_path) else {
+            return;
+        };
+        let Some(session) = self
+            .workspaces
+            .iter()
+            .flat_map(|w| &w.sessions)
+            .find(|s| s.id == session_id)
+        else {
+            warn!("Session {} not found, skipping on_create_host_command", session_id);
+            return;
+        };
+        crate::app::host_hooks::spawn_on_create_host_command(
+            &command,
+            std::path::Path::new(&session.workspace_path),
+            &session.branch_name,
+            session_id,
+        );
+    }
+
+    /// Warn when a new session's branch already conflicts with its base, e.g. a reused
+    /// branch that fell behind. Advisory only; the git view lists the files.
+    fn warn_about_merge_conflicts(
+        &mut self,
+        repo_path: &Path,
+        session_id: Uuid,
+        base_branch: Option<&str>,
+    ) {
+        let base = match base_branch {
+            Some(base) => base.to_string(),
+            None => match git2::Repository::open(repo_path) {
+                Ok(repo) => crate::git::worktree_manager::default_base_branch(&repo),
+                Err(_) => return,
+            },
+        };
+        let conflicts = match crate::git::WorktreeManager::new() {
+            Ok(manager) => manager.check_merge_conflicts(session_id, &base),
+            Err(_) => return,
+        };
+        if !conflicts.is_empty() {
+            self.add_warning_notification(format!(
+                "⚠️ Branch conflicts with {} in {} file(s) - see the git view ('g')",
+                base,
+                conflicts.len()
+            ));
+        }
+    }
+
+    /// Record input or other work so the UI ticks at full rate again
+    pub fn mark_activity(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Interval until the next tick: full rate while busy, slower once idle for a while
+    pub fn tick_rate(&self, now: Instant) -> Duration {
+        let busy = self.pending_event.is_some()
+            || self.pending_async_action.is_some()
+            || self.auth_refresh_task.is_some()
+            || self.chat_stream.is_some()
+            || self.worktree_disk_task.is_some()
+            || self.git_status_task.is_some()
+            || self.session_creation.is_some()
+            || self.image_build.is_some()
+            || self.ui_needs_refresh;
+        if busy || now.duration_since(self.last_activity) < self.idle_after {
+            ACTIVE_TICK_RATE
+        } else {
+            self.idle_tick_rate.max(ACTIVE_TICK_RATE)
+        }
+    }
+
+    /// Move the divider between the session list and the logs pane by one step
+    pub fn resize_session_list(&mut self, wider: bool) {
+        self.session_list_percent = if wider {
+            self.session_list_percent + SESSION_LIST_PERCENT_STEP
+        } else {
+            self.session_list_percent.saturating_sub(SESSION_LIST_PERCENT_STEP)
+        }
+        .clamp(MIN_SESSION_LIST_PERCENT, MAX_SESSION_LIST_PERCENT);
+    }
+
+    /// Re-sort every workspace's sessions by the current sort mode, keeping the selected session
+    pub fn sort_sessions(&mut self) {
+        let selected_id = self.selected_session().map(|s| s.id);
+        let mode = self.session_sort_mode;
+        for workspace in &mut self.workspaces {
+            workspace.sessions.sort_by(|a, b| Self::compare_sessions(mode, a, b));
+        }
+
+        if let Some(session_id) = selected_id {
+            self.selected_session_index = self
+                .selected_workspace()
+                .and_then(|w| w.sessions.iter().position(|s| s.id == session_id));
+        }
+    }
+
+    /// Order two sessions for `mode`, falling back to the name so ties stay put
+    fn compare_sessions(
+        mode: crate::config::SessionSortMode,
+        a: &Session,
+        b: &Session,
+    ) -> std::cmp::Ordering {
+        use crate::config::SessionSortMode;
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        match mode {
+            SessionSortMode::Status => {
+                a.status.sort_rank().cmp(&b.status.sort_rank()).then_with(by_name)
+            }
+            SessionSortMode::Name => by_name(),
+            SessionSortMode::LastActivity => {
+                b.last_accessed.cmp(&a.last_accessed).then_with(by_name)
+            }
+            SessionSortMode::Changes => {
+                b.git_changes.total().cmp(&a.git_changes.total()).then_with(by_name)
+            }
+        }
+    }
+
+    /// Switch to the next session sort mode and remember it for the next launch
+    pub fn cycle_session_sort(&mut self) {
+        self.session_sort_mode = self.session_sort_mode.next();
+        self.sort_sessions();
+
+        if self.persist_session_sort {
+            let saved = toml::Value::try_from(self.session_sort_mode)
+                .map_err(anyhow::Error::from)
+                .and_then(|value| {
+                    crate::config::AppConfig::save_ui_preference("session_sort", value)
+                });
+            if let Err(e) = saved {
+                warn!("Failed to save session sort mode: {}", e);
+            }
+        }
+
+        self.add_info_notification(format!(
+            "Sorting sessions by {}",
+            self.session_sort_mode.label()
+        ));
+    }
+
+    /// Mark loaded sessions with their persisted protection
+    pub fn apply_protected_flags(&mut self) {
+        for session in self.workspaces.iter_mut().flat_map(|w| w.sessions.iter_mut()) {
+            session.protected = self.protected_sessions.contains(&session.id);
+        }
+    }
+
+    /// Protect or unprotect the selected session from bulk operations
+    pub fn toggle_session_protection(&mut self) {
+        let Some((session_id, name)) = self.selected_session().map(|s| (s.id, s.name.clone()))
+        else {
+            return;
+        };
+
+        let protected = if self.protected_sessions.remove(&session_id) {
+            false
+        } else {
+            self.protected_sessions.insert(session_id)
+        };
+        self.apply_protected_flags();
+
+        if let Some(ref store) = self.protected_store {
+            if let Err(e) = store.save(&self.protected_sessions) {
+                warn!("Failed to save protected sessions: {}", e);
+                self.add_error_notification(format!("❌ Failed to save protection: {}", e));
+                return;
             }
+        }
+
+        if protected {
+            self.add_info_notification(format!(
+                "🔒 {} is protected; bulk actions will skip it",
+                name
+            ));
         } else {
-            error!("Session {} not found in workspaces", session_id);
-            self.add_error_notification("❌ Session not found".to_string());
+            self.add_info_notification(format!("🔓 {} is no longer protected", name));
         }
+    }
 
-        Ok(())
+    /// Split session ids into those a bulk operation may touch and the protected ones it skips
+    pub fn partition_protected(&self, session_ids: Vec<Uuid>) -> (Vec<Uuid>, Vec<Uuid>) {
+        session_ids.into_iter().partition(|id| !self.protected_sessions.contains(id))
     }
 
-    pub fn show_git_view(&mut self) {
-        // Get the selected session's workspace path
-        if let Some(session) = self.get_selected_session() {
-            let worktree_path = std::path::PathBuf::from(&session.workspace_path);
-            let mut git_state = crate::components::GitViewState::new(worktree_path);
+    /// Copy a tool call's command from the live logs and say what was copied
+    pub fn copy_tool_command(&mut self, command: Option<String>) {
+        let Some(command) = command else {
+            self.add_info_notification("No tool call at or above this log line".to_string());
+            return;
+        };
 
-            // Refresh git status
-            if let Err(e) = git_state.refresh_git_status() {
-                tracing::error!("Failed to refresh git status: {}", e);
+        if self.copy_or_show("Tool command", &command) {
+            let preview: String =
+                command.lines().next().unwrap_or_default().chars().take(60).collect();
+            let ellipsis = if preview.len() < command.len() {
+                "…"
+            } else {
+                ""
+            };
+            self.add_success_notification(format!("📋 Copied: {}{}", preview, ellipsis));
+        }
+    }
+
+    /// Copy the git view's worktree diff. Without a usable clipboard the diff is too long to
+    /// show in a notification, so it's left in a temp file and the path shown instead.
+    pub fn copy_git_diff(&mut self, include_staged: bool) {
+        let Some(git_state) = self.git_view_state.as_ref() else {
+            return;
+        };
+        let diff_file = git_state.diff_file_path();
+        let (text, full_path) = match git_state.diff_to_copy(include_staged) {
+            Ok(diff) => diff,
+            Err(e) => {
+                self.add_error_notification(format!("❌ Copy failed: {}", e));
                 return;
             }
+        };
+        let scope = if include_staged { "diff" } else { "unstaged diff" };
+
+        match (Self::copy_to_clipboard(&text), full_path) {
+            (Ok(()), Some(path)) => self.add_success_notification(format!(
+                "📋 Copied truncated {}; full diff in {}",
+                scope,
+                path.display()
+            )),
+            (Ok(()), None) => self.add_success_notification(format!(
+                "📋 Copied {} ({} lines)",
+                scope,
+                text.lines().count()
+            )),
+            (Err(e), full_path) => {
+                warn!("Failed to copy {} to clipboard: {}", scope, e);
+                let saved = match full_path {
+                    Some(path) => Ok(path),
+                    None => std::fs::write(&diff_file, &text).map(|()| diff_file),
+                };
+                match saved {
+                    Ok(path) => self.add_info_notification(format!(
+                        "Clipboard unavailable. Full {} in {}",
+                        scope,
+                        path.display()
+                    )),
+                    Err(e) => self.add_error_notification(format!("❌ Copy failed: {}", e)),
+                }
+            }
+        }
+    }
 
-            self.git_view_state = Some(git_state);
-            self.current_view = View::GitView;
+    fn copy_to_clipboard(text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        use arboard::Clipboard;
+        let mut clipboard = Clipboard::new()?;
+        clipboard.set_text(text.to_string())?;
+        Ok(())
+    }
+
+    /// Commit from the git view's message input. Committing everything is the fallback
+    /// when no files are selected, so it asks first.
+    pub fn confirm_git_commit(&mut self) {
+        let Some(git_state) = self.git_view_state.as_ref() else {
+            return;
+        };
+        if !git_state.selected_paths.is_empty() {
+            self.git_commit_and_push();
+            return;
+        }
+
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Commit All Changes".to_string(),
+            message: format!(
+                "No files are selected. Commit and push all {} changed files?",
+                git_state.changed_files.len()
+            ),
+            confirm_action: ConfirmAction::CommitAllChanges,
+            selected_option: false, // Default to "No"
+        });
+    }
+
+    /// Ask before discarding the selected file's changes, or every change when `all`.
+    /// Discarding can't be undone, so it always goes through the confirmation dialog.
+    pub fn confirm_git_discard(&mut self, all: bool) {
+        let Some(git_state) = self.git_view_state.as_ref() else {
+            return;
+        };
+        if git_state.review_only {
+            return;
+        }
+        let paths = if all {
+            git_state.changed_files.iter().map(|file| file.path.clone()).collect()
         } else {
-            tracing::warn!("No session selected for git view");
+            git_state.selected_discard_paths()
+        };
+        if paths.is_empty() && !(all && git_state.is_dirty) {
+            self.add_info_notification("No changes to discard".to_string());
+            return;
+        }
+
+        let question = if all {
+            format!("Discard all changes to {} files in this worktree?", paths.len())
+        } else if let [path] = paths.as_slice() {
+            format!("Discard changes to {}?", path)
+        } else {
+            format!("Discard changes to {} files?", paths.len())
+        };
+        let untracked = match git_state.untracked_count(&paths) {
+            0 => String::new(),
+            count => format!(" {} untracked will be deleted.", count),
+        };
+        let message = format!("{}{} This cannot be undone.", question, untracked);
+
+        self.confirmation_dialog = Some(ConfirmationDialog {
+            title: "Discard Changes".to_string(),
+            message,
+            confirm_action: ConfirmAction::DiscardChanges((!all).then_some(paths)),
+            selected_option: false, // Default to "No"
+        });
+    }
+
+    /// Discard confirmed changes: the listed paths, or everything when None
+    pub fn git_discard_changes(&mut self, paths: Option<Vec<String>>) {
+        let Some(git_state) = self.git_view_state.as_mut() else {
+            return;
+        };
+        let result = match paths {
+            None => git_state.discard_all_changes(),
+            Some(paths) => paths
+                .iter()
+                .map(|path| git_state.discard_file(path))
+                .collect::<anyhow::Result<Vec<_>>>()
+                .map(|discarded| match discarded.as_slice() {
+                    [message] => message.clone(),
+                    _ => format!("Discarded changes to {} files", discarded.len()),
+                }),
+        };
+        match result {
+            Ok(message) => self.add_success_notification(format!("🗑 {}", message)),
+            Err(e) => {
+                tracing::error!("Git discard failed: {}", e);
+                self.add_error_notification(format!("❌ Discard failed: {}", e));
+            }
         }
     }
 
@@ -3887,9 +7550,28 @@ impl AppState {
         self.quick_commit_message.is_some()
     }
 
+    /// Whether a dialog or popup is drawn on top of the current view
+    pub fn is_overlay_open(&self) -> bool {
+        self.confirmation_dialog.is_some()
+            || self.help_visible
+            || self.is_in_quick_commit_mode()
+            || self.log_export.is_some()
+            || self.prompt_viewer.is_some()
+            || self.run_compare.is_some()
+            || self.notification_history_scroll.is_some()
+            || self.auth_profile_picker.is_some()
+    }
+
     pub fn start_quick_commit(&mut self) {
         // Only start quick commit if we have a selected session and it's in a git repository
         if let Some(session) = self.get_selected_session() {
+            if session.review_only {
+                self.add_warning_notification(
+                    "⚠️ Review sessions are read-only - commit & push is disabled".to_string(),
+                );
+                return;
+            }
+
             // Check if the workspace path is a git repository
             let workspace_path = std::path::Path::new(&session.workspace_path);
             let git_dir = workspace_path.join(".git");
@@ -3958,6 +7640,92 @@ impl AppState {
         }
     }
 
+    /// Write a session's creation and live logs to `path`, returning the number of lines.
+    /// Escape sequences are stripped unless `raw` is set.
+    pub fn export_logs(
+        &self,
+        session_id: Uuid,
+        path: &std::path::Path,
+        raw: bool,
+    ) -> std::io::Result<usize> {
+        use crate::app::log_export::{format_export, write_export};
+
+        let build_logs = self.logs.get(&session_id).map(Vec::as_slice).unwrap_or_default();
+        let mut live_logs =
+            self.log_spill.as_ref().map(|spill| spill.load(session_id)).unwrap_or_default();
+        live_logs.extend(self.live_logs.get(&session_id).into_iter().flatten().cloned());
+        let content = format_export(build_logs, &live_logs, raw);
+        write_export(path, &content)?;
+        Ok(content.lines().count())
+    }
+
+    /// Open the export dialog for the selected session with the default filename
+    pub fn start_log_export(&mut self) {
+        let Some(session) = self.selected_session() else {
+            self.add_warning_notification("⚠️ No session selected".to_string());
+            return;
+        };
+        let path = crate::app::log_export::default_export_path(
+            &session.branch_name,
+            chrono::Local::now(),
+        );
+        self.log_export = Some(crate::app::log_export::LogExportPrompt {
+            session_id: session.id,
+            path: path.map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+            raw: false,
+        });
+    }
+
+    pub fn log_export_input_char(&mut self, ch: char) {
+        if let Some(prompt) = &mut self.log_export {
+            prompt.path.push(ch);
+        }
+    }
+
+    pub fn log_export_backspace(&mut self) {
+        if let Some(prompt) = &mut self.log_export {
+            prompt.path.pop();
+        }
+    }
+
+    pub fn toggle_log_export_raw(&mut self) {
+        if let Some(prompt) = &mut self.log_export {
+            prompt.raw = !prompt.raw;
+        }
+    }
+
+    pub fn cancel_log_export(&mut self) {
+        self.log_export = None;
+    }
+
+    pub fn confirm_log_export(&mut self) {
+        let Some(prompt) = self.log_export.clone() else {
+            return;
+        };
+        if prompt.path.trim().is_empty() {
+            self.add_warning_notification("⚠️ Enter a file to export to".to_string());
+            return;
+        }
+
+        let path = crate::app::log_export::expand_home(prompt.path.trim());
+        match self.export_logs(prompt.session_id, &path, prompt.raw) {
+            Ok(lines) => {
+                self.log_export = None;
+                self.add_success_notification(format!(
+                    "💾 Exported {} log lines to {}",
+                    lines,
+                    path.display()
+                ));
+            }
+            // Keep the dialog open so the path can be corrected
+            Err(e) => self.add_error_notification(format!(
+                "❌ Failed to export logs to {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
     fn perform_quick_commit(&mut self, commit_message: String) {
         let worktree_path = if let Some(session) = self.get_selected_session() {
             std::path::PathBuf::from(&session.workspace_path)
@@ -3986,28 +7754,79 @@ impl AppState {
     }
 
     /// Add a notification to the notification queue
+    /// Show a notification. A repeat of the newest one still on screen bumps that one's
+    /// count and restarts its timer instead of stacking another toast.
     pub fn add_notification(&mut self, notification: Notification) {
+        if let Some(latest) = self
+            .notifications
+            .iter_mut()
+            .rev()
+            .find(|n| !n.is_expired())
+            .filter(|latest| latest.is_repeated_by(&notification))
+        {
+            latest.count += 1;
+            latest.created_at = notification.created_at;
+            if let Some(last) = self
+                .notification_history
+                .back_mut()
+                .filter(|last| last.notification.is_repeated_by(&notification))
+            {
+                last.notification.count = latest.count;
+                last.received_at = chrono::Local::now();
+            }
+            return;
+        }
+
+        if self.notification_history.len() == NOTIFICATION_HISTORY_LIMIT {
+            self.notification_history.pop_front();
+        }
+        self.notification_history.push_back(ArchivedNotification {
+            notification: notification.clone(),
+            received_at: chrono::Local::now(),
+        });
         self.notifications.push(notification);
     }
 
+    /// Add a notification with the configured duration for its type
+    fn add_configured_notification(
+        &mut self,
+        notification_type: NotificationType,
+        message: String,
+    ) {
+        let notification =
+            Notification::configured(notification_type, message, &self.notification_config);
+        self.add_notification(notification);
+    }
+
     /// Add a success notification
     pub fn add_success_notification(&mut self, message: String) {
-        self.add_notification(Notification::success(message));
+        self.add_configured_notification(NotificationType::Success, message);
     }
 
     /// Add an error notification
     pub fn add_error_notification(&mut self, message: String) {
-        self.add_notification(Notification::error(message));
+        self.add_configured_notification(NotificationType::Error, message);
     }
 
     /// Add an info notification
     pub fn add_info_notification(&mut self, message: String) {
-        self.add_notification(Notification::info(message));
+        self.add_configured_notification(NotificationType::Info, message);
     }
 
     /// Add a warning notification
     pub fn add_warning_notification(&mut self, message: String) {
-        self.add_notification(Notification::warning(message));
+        self.add_configured_notification(NotificationType::Warning, message);
+    }
+
+    /// Remove the topmost sticky notification. Returns false when none is showing.
+    pub fn dismiss_sticky_notification(&mut self) -> bool {
+        match self.notifications.iter().position(|n| n.sticky) {
+            Some(index) => {
+                self.notifications.remove(index);
+                true
+            }
+            None => false,
+        }
     }
 
     /// Remove expired notifications
@@ -4015,6 +7834,26 @@ impl AppState {
         self.notifications.retain(|n| !n.is_expired());
     }
 
+    pub fn show_notification_history(&mut self) {
+        self.notification_history_scroll = Some(0);
+    }
+
+    /// Scroll the history viewer by `delta` entries, staying within the history
+    pub fn scroll_notification_history(&mut self, delta: isize) {
+        if let Some(ref mut scroll) = self.notification_history_scroll {
+            let last = self.notification_history.len().saturating_sub(1);
+            *scroll = scroll.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Forget archived notifications; toasts still on screen stay until they expire
+    pub fn clear_notification_history(&mut self) {
+        self.notification_history.clear();
+        if self.notification_history_scroll.is_some() {
+            self.notification_history_scroll = Some(0);
+        }
+    }
+
     /// Get current notifications (non-expired)
     pub fn get_current_notifications(&self) -> Vec<&Notification> {
         self.notifications.iter().filter(|n| !n.is_expired()).collect()
@@ -4064,7 +7903,8 @@ impl AppState {
                     Ok(content) => {
                         // Check if Claude is running by analyzing the content
                         let claude_running = detector.has_claude_status_bar(&content);
-                        updates.push((*session_id, content, claude_running));
+                        let awaiting_input = detector.is_awaiting_input(&content);
+                        updates.push((*session_id, content, claude_running, awaiting_input));
                     }
                     Err(e) => {
                         warn!("Failed to capture tmux pane content for session {}: {}", session_id, e);
@@ -4074,7 +7914,16 @@ impl AppState {
         }
 
         // Apply updates
-        for (session_id, content, claude_running) in updates {
+        let mut alerts = Vec::new();
+        for (session_id, content, claude_running, awaiting_input) in updates {
+            if awaiting_input {
+                if self.sessions_awaiting_input.insert(session_id) {
+                    alerts.push((session_id, SessionAlert::AwaitingInput));
+                }
+            } else {
+                self.sessions_awaiting_input.remove(&session_id);
+            }
+
             if let Some(session) = self.find_session_mut(session_id) {
                 session.set_preview(content);
 
@@ -4088,6 +7937,10 @@ impl AppState {
 
                 // Only update if status changed to avoid unnecessary refreshes
                 if session.status != new_status {
+                    // Claude exiting on its own is the interactive equivalent of completing
+                    if session.status == SessionStatus::Running {
+                        alerts.push((session_id, SessionAlert::Completed));
+                    }
                     session.set_status(new_status);
                     info!(
                         "Session {} status updated to: {}",
@@ -4100,6 +7953,10 @@ impl AppState {
             }
         }
 
+        for (session_id, alert) in alerts {
+            self.alert_session(session_id, alert);
+        }
+
         Ok(())
     }
 
@@ -4198,6 +8055,13 @@ impl App {
     }
 
     pub async fn init(&mut self) {
+        use crate::app::protected_sessions::{PROTECTED_SESSIONS_FILE, ProtectedSessionStore};
+        use crate::app::session_env::{SESSION_ENV_FILE, SessionEnvStore};
+        use crate::app::session_images::{SESSION_IMAGES_FILE, SessionImagesStore};
+        use crate::app::session_ports::{SESSION_PORTS_FILE, SessionPortsStore};
+        use crate::app::session_usage::{SESSION_USAGE_FILE, SessionUsageStore};
+        use crate::app::session_volumes::{SESSION_VOLUMES_FILE, SessionVolumesStore};
+
         // Initialize log streaming coordinator
         let (mut coordinator, log_sender) = LogStreamingCoordinator::new();
 
@@ -4213,16 +8077,20 @@ impl App {
         } else {
             info!("Docker not available - skipping log streaming manager initialization");
             info!("Log streaming will be available when Docker is started");
+            // A running daemon we are not allowed to use needs a fix, not a restart
+            if let Some(reason) = self.state.docker_unavailable_reason().await {
+                if crate::docker::is_docker_permission_error(&reason) {
+                    self.state.add_warning_notification(format!("⚠️ {}", reason));
+                }
+            }
         }
 
         self.state.log_streaming_coordinator = Some(coordinator);
         self.state.log_sender = Some(log_sender);
 
         // Try to refresh OAuth tokens if they're expired (before checking first-time setup)
-        let home_dir = dirs::home_dir();
-        if let Some(home) = home_dir {
-            let credentials_path =
-                home.join(".agents-in-a-box").join("auth").join(".credentials.json");
+        if let Some(auth_dir) = AppState::active_auth_dir() {
+            let credentials_path = auth_dir.join(".credentials.json");
 
             // Only attempt refresh if we have OAuth credentials that need refreshing
             // AND Docker is available (token refresh requires Docker for Boss mode)
@@ -4252,9 +8120,173 @@ impl App {
             warn!("Failed to initialize Claude integration: {}", e);
         }
 
+        let app_config = crate::config::AppConfig::load().unwrap_or_else(|e| {
+            warn!("Failed to load config, using defaults: {}", e);
+            crate::config::AppConfig::default()
+        });
+        let ui_preferences = &app_config.ui_preferences;
+        self.state.session_warning_threshold = ui_preferences
+            .warn_on_session_count
+            .then_some(ui_preferences.max_sessions_per_workspace);
+        self.state.diff_tool_command = ui_preferences.diff_tool_command.clone();
+        self.state.escape_policy = ui_preferences.escape_policy;
+        self.state.stop_sessions_on_exit = ui_preferences.stop_sessions_on_exit;
+        self.state.notification_config = ui_preferences.notifications;
+        if let Some(path) = crate::config::Keymap::default_path() {
+            let (keymap, errors) = crate::config::Keymap::load(&path);
+            if !errors.is_empty() {
+                warn!("Ignoring {}: {}", path.display(), errors.join("; "));
+                self.state.add_error_notification(format!(
+                    "⌨️ keys.toml ignored, using default keys: {}",
+                    errors.join("; ")
+                ));
+            }
+            self.state.keymap = keymap;
+        }
+        match crate::config::Theme::resolve(&ui_preferences.theme, &app_config.theme) {
+            Ok(theme) => self.state.theme = theme,
+            Err(e) => {
+                warn!("Using the default theme: {}", e);
+                self.state.add_error_notification(format!("🎨 Using the default theme: {}", e));
+            }
+        }
+        self.state.session_list_percent = ui_preferences
+            .session_list_percent
+            .clamp(MIN_SESSION_LIST_PERCENT, MAX_SESSION_LIST_PERCENT);
+        self.state.session_sort_mode = ui_preferences.session_sort;
+        self.state.persist_session_sort = true;
+        self.state.min_terminal_size = (
+            ui_preferences.min_terminal_width,
+            ui_preferences.min_terminal_height,
+        );
+        self.state.show_log_timestamps = ui_preferences.show_log_timestamps;
+        self.state.log_ansi_colors = ui_preferences.log_ansi_colors;
+        self.state.log_timestamp_format =
+            crate::components::TimestampFormat::from_config(&ui_preferences.log_timestamp_format);
+        self.state.missing_image_policy = app_config.docker.missing_image_policy;
+        self.state.oauth_refresh_attempts = app_config.docker.oauth_refresh_attempts;
+        self.state.health_check_interval = (app_config.docker.health_check_interval_secs > 0)
+            .then(|| Duration::from_secs(app_config.docker.health_check_interval_secs));
+        self.state.auto_restart = app_config.docker.auto_restart;
+        self.state.auto_restart_max_attempts = app_config.docker.auto_restart_max_attempts;
+        self.state.prompt_branch_prefix = app_config
+            .workspace_defaults
+            .name_from_prompt
+            .then(|| app_config.workspace_defaults.branch_prefix.clone());
+        self.state.branch_prefix = app_config.workspace_defaults.branch_prefix.clone();
+        self.state.default_session_mode = app_config.workspace_defaults.default_mode.clone();
+        self.state.default_skip_permissions =
+            app_config.workspace_defaults.default_skip_permissions;
+        self.state.default_model = app_config.workspace_defaults.model.clone();
+        self.state.model_choices = app_config.workspace_defaults.models.clone();
+        self.state.chat_model = app_config
+            .workspace_defaults
+            .model
+            .clone()
+            .unwrap_or_else(|| crate::claude::DEFAULT_MODEL.to_string());
+        self.state.chat_token_budget = app_config.workspace_defaults.chat_token_budget;
+        self.state.commit_log_base = app_config.workspace_defaults.commit_log_base.clone();
+        self.state.on_create_host_command =
+            app_config.workspace_defaults.on_create_host_command.clone();
+        self.state.allow_project_host_commands =
+            app_config.workspace_defaults.allow_project_host_commands;
+        if let Some(url) = app_config.webhook_url.as_ref().filter(|url| !url.trim().is_empty()) {
+            match crate::docker::WebhookNotifier::spawn(url.trim().to_string()) {
+                Ok(notifier) => {
+                    if let Some(coordinator) = &mut self.state.log_streaming_coordinator {
+                        coordinator.set_webhook(Some(notifier.clone()));
+                    }
+                    self.state.webhook = Some(notifier);
+                }
+                Err(e) => warn!("Failed to set up webhook: {}", e),
+            }
+        }
+        self.state.protected_store = ProtectedSessionStore::in_home_dir(PROTECTED_SESSIONS_FILE);
+        self.state.session_persistence = crate::app::SessionPersistence::in_home_dir();
+        if let Some(ref store) = self.state.protected_store {
+            self.state.protected_sessions = store.load();
+            self.state.apply_protected_flags();
+        }
+        self.state.session_env_store = SessionEnvStore::in_home_dir(SESSION_ENV_FILE);
+        self.state.chat_history = ChatHistoryStore::in_home_dir();
+        if let Some(ref store) = self.state.session_env_store {
+            self.state.session_env = store.load();
+        }
+        self.state.session_volumes_store = SessionVolumesStore::in_home_dir(SESSION_VOLUMES_FILE);
+        if let Some(ref store) = self.state.session_volumes_store {
+            self.state.session_volumes = store.load();
+        }
+        self.state.session_ports_store = SessionPortsStore::in_home_dir(SESSION_PORTS_FILE);
+        if let Some(ref store) = self.state.session_ports_store {
+            self.state.session_ports = store.load();
+        }
+        self.state.session_images_store = SessionImagesStore::in_home_dir(SESSION_IMAGES_FILE);
+        if let Some(ref store) = self.state.session_images_store {
+            self.state.session_images = store.load();
+        }
+        self.state.session_usage_store = SessionUsageStore::in_home_dir(SESSION_USAGE_FILE);
+        if let Some(ref store) = self.state.session_usage_store {
+            self.state.session_usage = store.load();
+        }
+        if ui_preferences.auto_save_prompt_drafts {
+            self.state.prompt_drafts = crate::app::prompt_drafts::PromptDraftStore::in_home_dir();
+            if let Some(Err(e)) = self.state.prompt_drafts.as_ref().map(crate::app::prompt_drafts::PromptDraftStore::prune) {
+                warn!("Failed to prune old prompt drafts: {}", e);
+            }
+        }
+        self.state.prompt_draft_interval =
+            Duration::from_secs(ui_preferences.prompt_draft_interval_secs.max(1));
+        self.state.alerter = crate::app::alerts::Alerter::from_preferences(ui_preferences);
+        self.state.large_paste_threshold = ui_preferences.large_paste_threshold;
+        self.state.max_log_entries = ui_preferences.max_log_entries;
+        self.state.log_spill = crate::app::log_spill::LogSpillStore::in_home_dir();
+        if let Some(ref spill) = self.state.log_spill {
+            spill.clear();
+        }
+        self.state.prompt_soft_wrap = ui_preferences.prompt_soft_wrap;
+        self.state.prompt_char_limits =
+            (ui_preferences.prompt_soft_char_limit, ui_preferences.prompt_hard_char_limit);
+        self.state.idle_tick_rate = Duration::from_millis(ui_preferences.idle_tick_ms);
+        self.state.idle_after = Duration::from_secs(ui_preferences.idle_after_secs);
+
         self.state.check_current_directory_status();
+
+        // Reconcile before loading: the load rewrites sessions.json, dropping ghost records
+        let mut reconcile_report = if self.state.is_docker_available().await {
+            SessionManager::new(self.state.session_persistence.clone())
+                .reconcile()
+                .await
+                .map_err(|e| warn!("Failed to reconcile sessions: {}", e))
+                .ok()
+        } else {
+            None
+        };
         self.state.load_real_workspaces().await;
 
+        // Optionally clean up orphaned containers and stale worktree references
+        if app_config.workspace_defaults.cleanup_orphaned_on_startup {
+            if self.state.is_docker_available().await {
+                info!("Running orphan cleanup on startup");
+                if let Err(e) = self.state.cleanup_orphaned_containers().await {
+                    warn!("Startup orphan cleanup failed: {}", e);
+                    self.state
+                        .add_error_notification(format!("❌ Startup cleanup failed: {}", e));
+                } else if let Some(ref mut report) = reconcile_report {
+                    report.sessions.retain(|s| s.health != SessionHealth::OrphanContainer);
+                }
+            } else {
+                info!("Docker not available - skipping orphan cleanup on startup");
+            }
+        }
+        if let Some(summary) = reconcile_report.and_then(|report| {
+            report.summary(
+                &self.state.keymap.label(crate::config::KeyAction::CleanupOrphaned),
+                &self.state.keymap.label(crate::config::KeyAction::CleanupDead),
+            )
+        }) {
+            self.state.add_warning_notification(format!("🧹 {}", summary));
+        }
+
         // Start log streaming for any running sessions
         if let Err(e) = self.init_log_streaming_for_sessions().await {
             warn!(
@@ -4268,19 +8300,28 @@ impl App {
     async fn init_log_streaming_for_sessions(&mut self) -> anyhow::Result<()> {
         if let Some(coordinator) = &mut self.state.log_streaming_coordinator {
             // Collect session info for streaming
-            let sessions: Vec<(Uuid, String, String, crate::models::SessionMode)> = self
+            let sessions: Vec<(
+                Uuid,
+                String,
+                String,
+                String,
+                crate::models::SessionMode,
+                crate::models::AgentType,
+            )> = self
                 .state
                 .workspaces
                 .iter()
-                .flat_map(|w| &w.sessions)
-                .filter(|s| s.status == crate::models::SessionStatus::Running)
-                .filter_map(|s| {
+                .flat_map(|w| w.sessions.iter().map(move |s| (w, s)))
+                .filter(|(_, s)| s.status == crate::models::SessionStatus::Running)
+                .filter_map(|(w, s)| {
                     s.container_id.clone().map(|container_id| {
                         (
                             s.id,
                             container_id,
                             format!("{}-{}", s.name, s.branch_name),
+                            w.name.clone(),
                             s.mode.clone(),
+                            s.agent_type,
                         )
                     })
                 })
@@ -4291,13 +8332,17 @@ impl App {
                     "Starting log streaming for {} running sessions",
                     sessions.len()
                 );
-                for (session_id, container_id, container_name, session_mode) in &sessions {
+                for (session_id, container_id, container_name, workspace, session_mode, agent_type) in
+                    &sessions
+                {
                     if let Err(e) = coordinator
                         .start_streaming(
                             *session_id,
                             container_id.clone(),
                             container_name.clone(),
+                            workspace.clone(),
                             session_mode.clone(),
+                            *agent_type,
                         )
                         .await
                     {
@@ -4316,8 +8361,56 @@ impl App {
         // Clean up expired notifications
         self.state.cleanup_expired_notifications();
 
+        self.state.poll_chat_stream();
+
+        // Stream the build log of a session being created, finishing it once it's up
+        self.state.poll_session_creation().await;
+
+        // Show the agents-dev image build's progress, running what needed it once built
+        self.state.poll_image_build().await;
+
+        // Report the outcome of a finished background OAuth refresh
+        if self.state.auth_refresh_task.as_ref().is_some_and(|task| task.is_finished()) {
+            match self.state.wait_for_auth_refresh().await {
+                Ok(()) => {
+                    info!("OAuth tokens refreshed successfully (periodic)");
+                    // Add a notification to inform the user
+                    self.state.add_notification(Notification {
+                        message: "✅ OAuth tokens refreshed automatically".to_string(),
+                        notification_type: NotificationType::Success,
+                        created_at: Instant::now(),
+                        duration: Duration::from_secs(5),
+                        sticky: false,
+                        count: 1,
+                    });
+                }
+                Err(e) => {
+                    warn!("Failed to refresh OAuth tokens (periodic): {}", e);
+                    // Add a warning notification
+                    self.state.add_notification(Notification {
+                        message: format!("⚠️ Token refresh failed: {}", e),
+                        notification_type: NotificationType::Warning,
+                        created_at: Instant::now(),
+                        duration: Duration::from_secs(10),
+                        sticky: false,
+                        count: 1,
+                    });
+                }
+            }
+        }
+
         // Periodic OAuth token refresh check (every 5 minutes)
         let now = Instant::now();
+
+        // Auto-save the boss prompt being composed so a crash doesn't lose it
+        if self.state.new_session_state.is_some()
+            && self
+                .state
+                .last_prompt_draft_save
+                .is_none_or(|last| now.duration_since(last) >= self.state.prompt_draft_interval)
+        {
+            self.state.autosave_prompt_draft();
+        }
         let should_check_token = self
             .state
             .last_token_refresh_check
@@ -4328,10 +8421,8 @@ impl App {
             self.state.last_token_refresh_check = Some(now);
 
             // Check if we need to refresh OAuth tokens
-            let home_dir = dirs::home_dir();
-            if let Some(home) = home_dir {
-                let credentials_path =
-                    home.join(".agents-in-a-box").join("auth").join(".credentials.json");
+            if let Some(auth_dir) = AppState::active_auth_dir() {
+                let credentials_path = auth_dir.join(".credentials.json");
 
                 if credentials_path.exists()
                     && AppState::oauth_token_needs_refresh(&credentials_path)
@@ -4340,29 +8431,9 @@ impl App {
 
                     // Only attempt refresh if Docker is available
                     if self.state.is_docker_available().await {
-                        // Refresh tokens inline (this is quick enough not to block UI)
-                        match self.state.refresh_oauth_tokens().await {
-                            Ok(()) => {
-                                info!("OAuth tokens refreshed successfully (periodic)");
-                                // Add a notification to inform the user
-                                self.state.add_notification(Notification {
-                                    message: "✅ OAuth tokens refreshed automatically".to_string(),
-                                    notification_type: NotificationType::Success,
-                                    created_at: Instant::now(),
-                                    duration: Duration::from_secs(5),
-                                });
-                            }
-                            Err(e) => {
-                                warn!("Failed to refresh OAuth tokens (periodic): {}", e);
-                                // Add a warning notification
-                                self.state.add_notification(Notification {
-                                    message: format!("⚠️ Token refresh failed: {}", e),
-                                    notification_type: NotificationType::Warning,
-                                    created_at: Instant::now(),
-                                    duration: Duration::from_secs(10),
-                                });
-                            }
-                        }
+                        self.state.announce_oauth_refresh();
+                        // Refresh in the background so the header can show progress
+                        self.state.start_background_auth_refresh();
                     } else {
                         info!("Docker not available - skipping periodic OAuth token refresh");
                     }
@@ -4370,6 +8441,54 @@ impl App {
             }
         }
 
+        // Keep the dashboard current while it's open
+        self.state.poll_worktree_disk_usage().await;
+        if self.state.current_view == View::Dashboard
+            && self
+                .state
+                .last_dashboard_refresh
+                .is_none_or(|last| now.duration_since(last) >= DASHBOARD_REFRESH_INTERVAL)
+        {
+            self.state.refresh_dashboard();
+        }
+
+        // Keep session git changes and ahead/behind counts current
+        self.state.poll_git_status().await;
+        if self
+            .state
+            .last_git_status_refresh
+            .is_none_or(|last| now.duration_since(last) >= GIT_STATUS_REFRESH_INTERVAL)
+        {
+            self.state.refresh_git_status();
+        }
+
+        // Keep the header's token countdown current
+        if self
+            .state
+            .last_auth_credential_check
+            .is_none_or(|last| now.duration_since(last) >= AUTH_CREDENTIAL_CHECK_INTERVAL)
+        {
+            self.state.refresh_auth_credential();
+        }
+
+        // Notice session containers that died on their own
+        self.state.poll_container_health().await;
+        if self.state.health_check_interval.is_some_and(|interval| {
+            self.state.last_health_check.is_none_or(|last| now.duration_since(last) >= interval)
+        }) {
+            self.state.refresh_container_health();
+        }
+
+        // Keep the status line's container resource totals current
+        self.state.poll_container_usage().await;
+        if self
+            .state
+            .last_container_usage_refresh
+            .is_none_or(|last| now.duration_since(last) >= CONTAINER_USAGE_REFRESH_INTERVAL)
+        {
+            self.state.refresh_container_usage();
+        }
+
         // Process incoming log entries (non-blocking)
         let mut log_entries = Vec::new();
         if let Some(coordinator) = &mut self.state.log_streaming_coordinator {
@@ -4379,6 +8498,11 @@ impl App {
             }
         }
 
+        // Streaming logs keep the UI ticking at full rate
+        if !log_entries.is_empty() {
+            self.state.mark_activity();
+        }
+
         // Add log entries to the state
         for (session_id, log_entry) in log_entries {
             self.state.add_live_log(session_id, log_entry);
@@ -4448,6 +8572,46 @@ impl App {
         Ok(())
     }
 
+    /// Stop running sessions' containers if `stop_sessions_on_exit` is set, within
+    /// `SHUTDOWN_TIMEOUT`. Protected sessions are skipped like in other bulk stops.
+    /// Returns the names of sessions that may still be running, then of the protected
+    /// sessions left running.
+    pub async fn stop_sessions_on_exit(&mut self) -> (Vec<String>, Vec<String>) {
+        use crate::app::shutdown::{SHUTDOWN_TIMEOUT, stop_containers};
+        use crate::docker::ContainerManager;
+
+        if !self.state.stop_sessions_on_exit {
+            return (Vec::new(), Vec::new());
+        }
+        let (containers, protected): (Vec<_>, Vec<_>) = self
+            .state
+            .running_sessions()
+            .into_iter()
+            .filter_map(|s| Some((s.id, s.name.clone(), s.container_id.clone()?)))
+            .partition(|(id, _, _)| !self.state.protected_sessions.contains(id));
+        let containers: Vec<(String, String)> =
+            containers.into_iter().map(|(_, name, container_id)| (name, container_id)).collect();
+        let protected: Vec<String> = protected.into_iter().map(|(_, name, _)| name).collect();
+        if containers.is_empty() {
+            return (Vec::new(), protected);
+        }
+
+        info!("Stopping {} session container(s) on exit", containers.len());
+        let manager = match ContainerManager::new().await {
+            Ok(manager) => manager,
+            Err(e) => {
+                warn!("Cannot reach docker to stop sessions on exit: {}", e);
+                return (containers.into_iter().map(|(name, _)| name).collect(), protected);
+            }
+        };
+        let left_running = stop_containers(containers, SHUTDOWN_TIMEOUT, |container_id| {
+            let manager = &manager;
+            async move { manager.stop_container_by_id(&container_id).await }
+        })
+        .await;
+        (left_running, protected)
+    }
+
     /// Check if UI needs immediate refresh and clear the flag
     pub fn needs_ui_refresh(&mut self) -> bool {
         if self.state.ui_needs_refresh {
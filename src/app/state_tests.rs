@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::app::state::{AppState, NewSessionState, NewSessionStep};
+    use crate::app::state::{AppState, ListEntryInput, NewSessionState, NewSessionStep};
     use crate::models::SessionMode;
     use std::path::PathBuf;
 
@@ -37,9 +37,26 @@ mod tests {
             is_current_dir_mode: false, // This should be false for 'n' key press
             skip_permissions: false,
             mode: SessionMode::Interactive,
+            agent_type: crate::models::AgentType::Claude,
+            model: None,
             boss_prompt: crate::app::state::TextEditor::new(),
             file_finder: crate::components::fuzzy_file_finder::FuzzyFileFinderState::new(),
             restart_session_id: None, // Not a restart
+            review_mode: false,
+            branch_name_generated: false,
+            saved_draft: String::new(),
+            pending_paste: None,
+            base_branch: None,
+            base_branches: vec![],
+            base_branch_filter: String::new(),
+            selected_base_branch_index: None,
+            env_vars: vec![],
+            volumes: vec![],
+            ports: vec![],
+            list_entry: ListEntryInput::default(),
+            branch_error: None,
+            branch_conflict: None,
+            branch_prefix: "agents-in-a-box/".to_string(),
         });
 
         // Now simulate pressing Enter in InputBranch step
@@ -79,9 +96,26 @@ mod tests {
             is_current_dir_mode: true, // This should be true for current dir mode
             skip_permissions: false,
             mode: SessionMode::Interactive,
+            agent_type: crate::models::AgentType::Claude,
+            model: None,
             boss_prompt: crate::app::state::TextEditor::new(),
             file_finder: crate::components::fuzzy_file_finder::FuzzyFileFinderState::new(),
             restart_session_id: None, // Not a restart
+            review_mode: false,
+            branch_name_generated: false,
+            saved_draft: String::new(),
+            pending_paste: None,
+            base_branch: None,
+            base_branches: vec![],
+            base_branch_filter: String::new(),
+            selected_base_branch_index: None,
+            env_vars: vec![],
+            volumes: vec![],
+            ports: vec![],
+            list_entry: ListEntryInput::default(),
+            branch_error: None,
+            branch_conflict: None,
+            branch_prefix: "agents-in-a-box/".to_string(),
         });
 
         // In current directory mode, pressing Enter should skip mode selection
@@ -114,9 +148,26 @@ mod tests {
             is_current_dir_mode: false,
             skip_permissions: false,
             mode: SessionMode::Interactive, // Start with Interactive
+            agent_type: crate::models::AgentType::Claude,
+            model: None,
             boss_prompt: crate::app::state::TextEditor::new(),
             file_finder: crate::components::fuzzy_file_finder::FuzzyFileFinderState::new(),
             restart_session_id: None, // Not a restart
+            review_mode: false,
+            branch_name_generated: false,
+            saved_draft: String::new(),
+            pending_paste: None,
+            base_branch: None,
+            base_branches: vec![],
+            base_branch_filter: String::new(),
+            selected_base_branch_index: None,
+            env_vars: vec![],
+            volumes: vec![],
+            ports: vec![],
+            list_entry: ListEntryInput::default(),
+            branch_error: None,
+            branch_conflict: None,
+            branch_prefix: "agents-in-a-box/".to_string(),
         });
 
         // Test toggling mode
@@ -159,9 +210,26 @@ mod tests {
             is_current_dir_mode: false,
             skip_permissions: false,
             mode: SessionMode::Interactive,
+            agent_type: crate::models::AgentType::Claude,
+            model: None,
             boss_prompt: crate::app::state::TextEditor::new(),
             file_finder: crate::components::fuzzy_file_finder::FuzzyFileFinderState::new(),
             restart_session_id: None, // Not a restart
+            review_mode: false,
+            branch_name_generated: false,
+            saved_draft: String::new(),
+            pending_paste: None,
+            base_branch: None,
+            base_branches: vec![],
+            base_branch_filter: String::new(),
+            selected_base_branch_index: None,
+            env_vars: vec![],
+            volumes: vec![],
+            ports: vec![],
+            list_entry: ListEntryInput::default(),
+            branch_error: None,
+            branch_conflict: None,
+            branch_prefix: "agents-in-a-box/".to_string(),
         });
 
         state.new_session_proceed_from_mode();
@@ -185,9 +253,26 @@ mod tests {
             is_current_dir_mode: false,
             skip_permissions: false,
             mode: SessionMode::Boss,
+            agent_type: crate::models::AgentType::Claude,
+            model: None,
             boss_prompt: crate::app::state::TextEditor::new(),
             file_finder: crate::components::fuzzy_file_finder::FuzzyFileFinderState::new(),
             restart_session_id: None, // Not a restart
+            review_mode: false,
+            branch_name_generated: false,
+            saved_draft: String::new(),
+            pending_paste: None,
+            base_branch: None,
+            base_branches: vec![],
+            base_branch_filter: String::new(),
+            selected_base_branch_index: None,
+            env_vars: vec![],
+            volumes: vec![],
+            ports: vec![],
+            list_entry: ListEntryInput::default(),
+            branch_error: None,
+            branch_conflict: None,
+            branch_prefix: "agents-in-a-box/".to_string(),
         });
 
         state.new_session_proceed_from_mode();
@@ -287,6 +372,166 @@ mod tests {
         assert_eq!(state.notifications.len(), 0);
     }
 
+    /// The token expiry comes from `expiresAt` and drives the header countdown
+    #[test]
+    fn test_oauth_expiry_countdown() {
+        use crate::app::state::AuthCredential;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let credentials = temp_dir.path().join(".credentials.json");
+        std::fs::write(
+            &credentials,
+            r#"{"claudeAiOauth":{"accessToken":"a","refreshToken":"r","expiresAt":1750000000000}}"#,
+        )
+        .unwrap();
+
+        let expires_at = AppState::read_oauth_expiry(&credentials).unwrap();
+        assert_eq!(expires_at.timestamp_millis(), 1_750_000_000_000);
+        assert!(!AppState::is_oauth_token_valid(&credentials));
+
+        std::fs::write(&credentials, r#"{"apiKey":"sk-test"}"#).unwrap();
+        assert_eq!(AppState::read_oauth_expiry(&credentials), None);
+
+        let credential = AuthCredential::OAuth(expires_at);
+        let label = |minutes: i64| {
+            let now = expires_at - chrono::Duration::minutes(minutes);
+            credential.label(now - chrono::Duration::seconds(1))
+        };
+        assert_eq!(label(12).as_deref(), Some("token expires in 12m"));
+        assert_eq!(label(125).as_deref(), Some("token expires in 2h 05m"));
+        assert_eq!(label(0).as_deref(), Some("token expires in 0m"));
+        assert_eq!(credential.label(expires_at).as_deref(), Some("token expired"));
+        assert_eq!(AuthCredential::ApiKey.label(expires_at).as_deref(), Some("API key"));
+        assert_eq!(AuthCredential::None.label(expires_at), None);
+    }
+
+    /// Sticky notifications outlive their duration until dismissed
+    #[test]
+    fn test_sticky_notifications_never_expire() {
+        use crate::app::state::Notification;
+        let mut error = Notification::error("Container crashed".to_string());
+        assert!(error.sticky);
+        error.duration = std::time::Duration::ZERO;
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(!error.is_expired());
+
+        // Errors follow the configured stickiness like the other levels
+        let config = crate::config::NotificationConfig {
+            sticky_errors: false,
+            ..Default::default()
+        };
+        let configured = Notification::configured(
+            crate::app::state::NotificationType::Error,
+            "Container crashed".to_string(),
+            &config,
+        );
+        assert!(!configured.sticky);
+
+        let mut info = Notification::info("Refreshed".to_string());
+        assert!(!info.sticky);
+        info.duration = std::time::Duration::ZERO;
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(info.is_expired());
+
+        let mut state = AppState::new();
+        state.add_notification(info);
+        state.add_notification(error);
+        state.add_error_notification("Build failed".to_string());
+        assert!(state.dismiss_sticky_notification());
+        assert_eq!(state.notifications.len(), 2);
+        assert_eq!(state.notifications[1].message, "Build failed");
+        assert!(state.dismiss_sticky_notification());
+        assert!(!state.dismiss_sticky_notification());
+        assert_eq!(state.notifications.len(), 1);
+    }
+
+    /// Notification helpers take their durations and error stickiness from the config
+    #[test]
+    fn test_notification_durations_follow_config() {
+        let mut state = AppState::new();
+        state.notification_config = crate::config::NotificationConfig {
+            success_secs: 1,
+            info_secs: 2,
+            warning_secs: 7,
+            error_secs: 30,
+            sticky_errors: false,
+        };
+        state.add_success_notification("ok".to_string());
+        state.add_info_notification("fyi".to_string());
+        state.add_warning_notification("careful".to_string());
+        state.add_error_notification("failed".to_string());
+
+        let durations: Vec<u64> =
+            state.notifications.iter().map(|n| n.duration.as_secs()).collect();
+        assert_eq!(durations, [1, 2, 7, 30]);
+        assert!(state.notifications.iter().all(|n| !n.sticky));
+
+        state.notification_config.sticky_errors = true;
+        state.add_error_notification("failed again".to_string());
+        assert!(state.notifications.last().unwrap().sticky);
+    }
+
+    /// Identical notifications in a row collapse into one toast with a repeat count
+    #[test]
+    fn test_repeated_notifications_are_merged() {
+        let mut state = AppState::new();
+        for _ in 0..3 {
+            state.add_warning_notification("Build failed: exit 1".to_string());
+        }
+
+        assert_eq!(state.notifications.len(), 1);
+        assert_eq!(state.notifications[0].count, 3);
+        assert_eq!(state.notifications[0].display_message(), "Build failed: exit 1 (x3)");
+        assert_eq!(state.notification_history.len(), 1);
+        assert_eq!(state.notification_history[0].notification.count, 3);
+
+        state.add_warning_notification("Build failed: exit 2".to_string());
+        state.add_error_notification("Build failed: exit 2".to_string());
+        assert_eq!(state.notifications.len(), 3);
+        assert_eq!(state.notifications[1].display_message(), "Build failed: exit 2");
+        assert_eq!(state.notification_history.len(), 3);
+    }
+
+    /// Expired toasts disappear from the screen but stay in the history
+    #[test]
+    fn test_expired_notifications_stay_in_history() {
+        let mut state = AppState::new();
+        let mut notification = crate::app::state::Notification::warning("Build failed".to_string());
+        notification.duration = std::time::Duration::from_millis(1);
+        state.add_notification(notification);
+        state.add_info_notification("Image ready".to_string());
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        state.cleanup_expired_notifications();
+
+        assert_eq!(state.notifications.len(), 1);
+        let messages: Vec<&str> =
+            state.notification_history.iter().map(|e| e.notification.message.as_str()).collect();
+        assert_eq!(messages, ["Build failed", "Image ready"]);
+
+        state.clear_notification_history();
+        assert!(state.notification_history.is_empty());
+        assert_eq!(state.notifications.len(), 1);
+    }
+
+    /// The history keeps only the most recent notifications
+    #[test]
+    fn test_notification_history_is_bounded() {
+        let mut state = AppState::new();
+        for i in 0..250 {
+            state.add_info_notification(format!("message {}", i));
+        }
+
+        assert_eq!(state.notification_history.len(), 100);
+        assert_eq!(state.notification_history.front().unwrap().notification.message, "message 150");
+        assert_eq!(state.notification_history.back().unwrap().notification.message, "message 249");
+
+        state.show_notification_history();
+        state.scroll_notification_history(500);
+        assert_eq!(state.notification_history_scroll, Some(99));
+        state.scroll_notification_history(-1);
+        assert_eq!(state.notification_history_scroll, Some(98));
+    }
+
     /// Test git commit and push notifications (without actual git operations)
     #[test]
     fn test_git_commit_and_push_notifications() {
@@ -299,4 +544,1641 @@ mod tests {
         // Should not crash and should not add any notifications since git_view_state is None
         assert_eq!(state.notifications.len(), 0);
     }
+
+    /// Copying a container ID from a session without a container explains why nothing was copied
+    #[test]
+    fn test_copy_container_id_without_container() {
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        workspace.add_session(crate::models::Session::new(
+            "feature".to_string(),
+            "/tmp/repo".to_string(),
+        ));
+        state.workspaces.push(workspace);
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(0);
+
+        state.copy_selected_container_id();
+
+        assert_eq!(state.notifications.len(), 1);
+        assert_eq!(state.notifications[0].message, "Session 'feature' has no container");
+    }
+
+    /// The worktree path reaches the user whether or not a clipboard is available
+    #[test]
+    fn test_copy_worktree_path_reports_path() {
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        workspace.add_session(crate::models::Session::new(
+            "feature".to_string(),
+            "/tmp/worktrees/feature".to_string(),
+        ));
+        state.workspaces.push(workspace);
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(0);
+
+        state.copy_selected_worktree_path();
+
+        assert_eq!(state.notifications.len(), 1);
+        assert!(state.notifications[0].message.contains("/tmp/worktrees/feature"));
+    }
+
+    /// Copying a tool command reaches the user, never as an error, without a clipboard
+    #[test]
+    fn test_copy_tool_command_without_clipboard_is_not_an_error() {
+        let mut state = AppState::new();
+
+        state.copy_tool_command(Some("cargo test --workspace".to_string()));
+
+        assert_eq!(state.notifications.len(), 1);
+        let notification = &state.notifications[0];
+        assert_ne!(notification.notification_type, crate::app::state::NotificationType::Error);
+        assert!(notification.message.contains("cargo test --workspace"));
+    }
+
+    /// The previous run is the latest earlier boss session with the same prompt
+    #[test]
+    fn test_find_previous_run() {
+        use crate::models::{Session, SessionMode};
+
+        let boss = |name: &str, prompt: &str, minutes_ago: i64| {
+            let mut session = Session::new_with_options(
+                name.to_string(),
+                "/tmp/repo".to_string(),
+                false,
+                SessionMode::Boss,
+                Some(prompt.to_string()),
+            );
+            session.created_at = chrono::Utc::now() - chrono::Duration::minutes(minutes_ago);
+            session
+        };
+
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        let oldest = boss("oldest", "Fix the parser", 30);
+        let previous = boss("previous", "Fix the parser ", 20);
+        let other = boss("other", "Write docs", 10);
+        let latest = boss("latest", "Fix the parser", 0);
+        let (oldest_id, previous_id, other_id, latest_id) = (oldest.id, previous.id, other.id, latest.id);
+        for session in [oldest, previous, other, latest] {
+            workspace.add_session(session);
+        }
+        state.workspaces.push(workspace);
+
+        assert_eq!(state.find_previous_run(latest_id).map(|s| s.id), Some(previous_id));
+        assert_eq!(state.find_previous_run(previous_id).map(|s| s.id), Some(oldest_id));
+        assert!(state.find_previous_run(oldest_id).is_none());
+        assert!(state.find_previous_run(other_id).is_none());
+    }
+
+    /// Stopping while keeping the worktree only applies to sessions with a container
+    #[test]
+    fn test_stop_keep_worktree_needs_container() {
+        use crate::app::state::ConfirmAction;
+
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        let bare = crate::models::Session::new("bare".to_string(), "/tmp/repo".to_string());
+        let mut boxed = crate::models::Session::new("boxed".to_string(), "/tmp/repo".to_string());
+        boxed.container_id = Some("abc123".to_string());
+        let (bare_id, boxed_id) = (bare.id, boxed.id);
+        workspace.add_session(bare);
+        workspace.add_session(boxed);
+        state.workspaces.push(workspace);
+
+        state.show_stop_keep_worktree_confirmation(bare_id);
+        assert!(state.confirmation_dialog.is_none());
+        assert!(state.notifications[0].message.contains("no container"));
+
+        state.show_stop_keep_worktree_confirmation(boxed_id);
+        let dialog = state.confirmation_dialog.as_ref().unwrap();
+        assert!(matches!(dialog.confirm_action, ConfirmAction::StopKeepWorktree(id) if id == boxed_id));
+        assert!(!dialog.selected_option);
+    }
+
+    /// Protected sessions are flagged, skipped by bulk operations, and still deletable with confirmation
+    #[test]
+    fn test_protected_sessions_skip_bulk_operations() {
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        let keep = crate::models::Session::new("keep".to_string(), "/tmp/repo".to_string());
+        let other = crate::models::Session::new("other".to_string(), "/tmp/repo".to_string());
+        let (keep_id, other_id) = (keep.id, other.id);
+        workspace.add_session(keep);
+        workspace.add_session(other);
+        state.workspaces.push(workspace);
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(0);
+
+        state.toggle_session_protection();
+        assert!(state.selected_session().unwrap().protected);
+        assert_eq!(
+            state.partition_protected(vec![keep_id, other_id]),
+            (vec![other_id], vec![keep_id])
+        );
+
+        state.show_delete_confirmation(keep_id);
+        assert!(state.confirmation_dialog.take().unwrap().message.contains("protected"));
+
+        state.toggle_session_protection();
+        assert!(!state.selected_session().unwrap().protected);
+        assert!(state.partition_protected(vec![keep_id]).1.is_empty());
+    }
+
+    /// Review sessions are read-only, so the quick commit dialog never opens for them
+    #[test]
+    fn test_quick_commit_blocked_for_review_session() {
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        let mut session = crate::models::Session::new("review".to_string(), "/tmp/repo".to_string());
+        session.review_only = true;
+        workspace.add_session(session);
+        state.workspaces.push(workspace);
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(0);
+
+        state.start_quick_commit();
+
+        assert!(state.quick_commit_message.is_none());
+        assert!(state.notifications[0].message.contains("read-only"));
+    }
+
+    /// The agent type is picked on the mode step and defaults to Claude
+    #[test]
+    fn test_new_session_cycle_agent_type() {
+        use crate::models::AgentType;
+
+        let mut state = AppState::new();
+        state.new_session_state = Some(NewSessionState {
+            step: NewSessionStep::SelectMode,
+            ..Default::default()
+        });
+        let agent_type = |state: &AppState| state.new_session_state.as_ref().unwrap().agent_type;
+        assert_eq!(agent_type(&state), AgentType::Claude);
+
+        state.new_session_cycle_agent_type();
+        assert_eq!(agent_type(&state), AgentType::Gemini);
+        // Plain text has no CLI for the container to launch, so it isn't offered
+        state.new_session_cycle_agent_type();
+        assert_eq!(agent_type(&state), AgentType::Claude);
+
+        // Other steps leave it alone
+        state.new_session_state.as_mut().unwrap().step = NewSessionStep::InputPrompt;
+        state.new_session_cycle_agent_type();
+        assert_eq!(agent_type(&state), AgentType::Claude);
+    }
+
+    /// A saved boss prompt draft is loaded when re-entering the prompt step, and edits to it
+    /// keep being saved
+    #[test]
+    fn test_prompt_draft_recovery() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = crate::app::prompt_drafts::PromptDraftStore::new(temp_dir.path().to_path_buf());
+        let repo = PathBuf::from("/tmp/repo");
+        let draft = store.draft_path(&repo, "agents/feature");
+        store.save(&draft, "Long careful prompt").unwrap();
+
+        let mut state = AppState::new();
+        state.prompt_drafts = Some(store.clone());
+        state.new_session_state = Some(NewSessionState {
+            filtered_repos: vec![(0, repo.clone())],
+            selected_repo_index: Some(0),
+            branch_name: "agents/feature".to_string(),
+            step: NewSessionStep::SelectMode,
+            mode: SessionMode::Boss,
+            ..Default::default()
+        });
+
+        state.new_session_proceed_from_mode();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.boss_prompt.to_string(), "Long careful prompt");
+        assert_eq!(
+            state.notifications.last().unwrap().message,
+            "📝 Restored your unsaved prompt draft"
+        );
+
+        let session_state = state.new_session_state.as_mut().unwrap();
+        session_state.boss_prompt = crate::app::state::TextEditor::from_string("Edited prompt");
+        state.autosave_prompt_draft();
+        assert_eq!(store.load(&draft).as_deref(), Some("Edited prompt"));
+
+        state.new_session_state.as_mut().unwrap().boss_prompt =
+            crate::app::state::TextEditor::from_string("Rewritten prompt");
+        state.cancel_new_session();
+        assert_eq!(store.load(&draft).as_deref(), Some("Rewritten prompt"));
+    }
+
+    /// Prompts for a generated branch name are drafted per repository, so cancelling and
+    /// starting over (which generates a new name) still finds the draft
+    #[test]
+    fn test_prompt_draft_survives_cancel_with_generated_branch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = crate::app::prompt_drafts::PromptDraftStore::new(temp_dir.path().to_path_buf());
+        let repo = PathBuf::from("/tmp/repo");
+
+        let mut state = AppState::new();
+        state.prompt_drafts = Some(store.clone());
+        state.new_session_state = Some(NewSessionState {
+            filtered_repos: vec![(0, repo.clone())],
+            selected_repo_index: Some(0),
+            branch_name: "agents-session-1234abcd".to_string(),
+            branch_name_generated: true,
+            step: NewSessionStep::InputPrompt,
+            mode: SessionMode::Boss,
+            boss_prompt: crate::app::state::TextEditor::from_string("Half-written prompt"),
+            ..Default::default()
+        });
+        state.cancel_new_session();
+        let draft = store.draft_path_for_repo(&repo);
+        assert_eq!(store.load(&draft).as_deref(), Some("Half-written prompt"));
+
+        state.new_session_state = Some(NewSessionState {
+            filtered_repos: vec![(0, repo.clone())],
+            selected_repo_index: Some(0),
+            branch_name: "agents-session-5678ef90".to_string(),
+            branch_name_generated: true,
+            step: NewSessionStep::SelectMode,
+            mode: SessionMode::Boss,
+            ..Default::default()
+        });
+        state.new_session_proceed_from_mode();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.boss_prompt.to_string(), "Half-written prompt");
+    }
+
+    /// Pastes over the threshold wait for a choice; saving to a file inserts an @-reference
+    #[test]
+    fn test_large_paste_choices() {
+        use crate::app::state::PasteChoice;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = crate::app::prompt_drafts::PromptDraftStore::new(temp_dir.path().to_path_buf());
+        let mut state = AppState::new();
+        state.prompt_drafts = Some(store.clone());
+        state.large_paste_threshold = 10;
+        let repo = temp_dir.path().join("repo");
+        state.new_session_state = Some(NewSessionState {
+            step: NewSessionStep::InputPrompt,
+            mode: SessionMode::Boss,
+            filtered_repos: vec![(0, repo.clone())],
+            selected_repo_index: Some(0),
+            ..Default::default()
+        });
+        let prompt = |state: &AppState| state.new_session_state.as_ref().unwrap().boss_prompt.to_string();
+
+        state.new_session_paste_text("short".to_string());
+        assert_eq!(prompt(&state), "short");
+
+        let large = "x".repeat(20);
+        state.new_session_paste_text(large.clone());
+        assert_eq!(prompt(&state), "short");
+        assert!(state.new_session_state.as_ref().unwrap().pending_paste.is_some());
+
+        state.new_session_resolve_paste(PasteChoice::Cancel);
+        assert_eq!(prompt(&state), "short");
+        assert!(state.new_session_state.as_ref().unwrap().pending_paste.is_none());
+
+        state.new_session_paste_text(large.clone());
+        state.new_session_resolve_paste(PasteChoice::SaveToFile);
+        let reference = prompt(&state);
+        let path = reference.strip_prefix("short@").unwrap().trim_end();
+        assert!(path.starts_with(&*store.pastes_dir_for_repo(&repo).to_string_lossy()));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), large);
+
+        state.new_session_paste_text(large.clone());
+        state.new_session_resolve_paste(PasteChoice::Inline);
+        assert!(prompt(&state).ends_with(&large));
+    }
+
+    /// Usage events are totalled per session rather than shown in the live logs
+    #[test]
+    fn test_usage_log_entries_accumulate() {
+        use crate::components::live_logs_stream::{LogEntry, LogEntryLevel};
+
+        let mut state = AppState::new();
+        let session_id = uuid::Uuid::new_v4();
+        for _ in 0..2 {
+            let entry = LogEntry::new(LogEntryLevel::Debug, "c".to_string(), String::new())
+                .with_metadata("event_type", "usage")
+                .with_metadata("input_tokens", "100")
+                .with_metadata("output_tokens", "20");
+            state.add_live_log(session_id, entry);
+        }
+
+        assert!(!state.live_logs.contains_key(&session_id));
+        let usage = state.session_usage[&session_id];
+        assert_eq!((usage.input_tokens, usage.output_tokens), (200, 40));
+    }
+
+    /// A restarted log stream replays its tail; usage already counted isn't added again,
+    /// and the totals are saved and dropped with the session
+    #[test]
+    fn test_usage_replay_is_not_counted_twice() {
+        use crate::app::session_usage::SessionUsageStore;
+        use crate::components::live_logs_stream::{LogEntry, LogEntryLevel};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = SessionUsageStore::new(temp_dir.path().join("session_usage.json"));
+        let mut state = AppState::new();
+        state.session_usage_store = Some(store.clone());
+        let session_id = uuid::Uuid::new_v4();
+        let start = chrono::Utc::now();
+        let usage_at = |seconds: i64| {
+            let mut entry = LogEntry::new(LogEntryLevel::Debug, "c".to_string(), String::new())
+                .with_metadata("event_type", "usage")
+                .with_metadata("input_tokens", "100")
+                .with_metadata("output_tokens", "20");
+            entry.timestamp = start + chrono::Duration::seconds(seconds);
+            entry
+        };
+
+        state.add_live_log(session_id, usage_at(0));
+        state.add_live_log(session_id, usage_at(1));
+        // The stream restarts and replays both lines before a new one
+        for seconds in [0, 1, 2] {
+            state.add_live_log(session_id, usage_at(seconds));
+        }
+
+        assert_eq!(state.session_usage[&session_id].input_tokens, 300);
+        assert_eq!(store.load()[&session_id].input_tokens, 300);
+
+        state.forget_session_usage(session_id);
+        assert!(!state.session_usage.contains_key(&session_id));
+        assert!(store.load().is_empty());
+    }
+
+    /// Past the cap, old live logs leave memory for the spill file and still reach exports
+    #[test]
+    fn test_live_logs_over_cap_spill_to_disk() {
+        use crate::components::live_logs_stream::{LogEntry, LogEntryLevel};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut state = AppState::new();
+        state.max_log_entries = 1000;
+        state.log_spill = Some(crate::app::log_spill::LogSpillStore::new(
+            temp_dir.path().join("log_spill"),
+        ));
+        let session_id = uuid::Uuid::new_v4();
+
+        for i in 0..5000 {
+            let entry = LogEntry::new(LogEntryLevel::Info, "claude".into(), format!("line {}", i));
+            state.add_live_log(session_id, entry);
+        }
+
+        let in_memory = &state.live_logs[&session_id];
+        assert_eq!(in_memory.len(), 1000);
+        assert_eq!(in_memory[0].message, "line 4000");
+
+        let spilled = state.log_spill.as_ref().unwrap().load(session_id);
+        assert_eq!(spilled.len(), 4000);
+        assert_eq!(spilled[0].message, "line 0");
+        assert_eq!(spilled[3999].message, "line 3999");
+
+        // Overflow waits in memory until a full batch (a tenth of the cap) is ready to spill
+        for i in 5000..5099 {
+            let entry = LogEntry::new(LogEntryLevel::Info, "claude".into(), format!("line {}", i));
+            state.add_live_log(session_id, entry);
+        }
+        assert_eq!(state.live_logs[&session_id].len(), 1099);
+        assert_eq!(state.log_spill.as_ref().unwrap().load(session_id).len(), 4000);
+
+        let export_path = temp_dir.path().join("export.log");
+        assert_eq!(state.export_logs(session_id, &export_path, false).unwrap(), 5099);
+        let exported = std::fs::read_to_string(&export_path).unwrap();
+        assert!(exported.lines().next().unwrap().ends_with("[claude] line 0"));
+        assert!(exported.lines().last().unwrap().ends_with("[claude] line 5098"));
+    }
+
+    /// MCP status from an agent's init event reaches the per-session map, not the log lines
+    #[test]
+    fn test_mcp_servers_from_parser_reach_state() {
+        use crate::agent_parsers::{AgentOutputParser, ClaudeJsonParser, McpServerInfo};
+        use crate::docker::log_streaming::DockerLogStreamingManager;
+        use crate::widgets::MessageRouter;
+
+        let mut state = AppState::new();
+        let session_id = uuid::Uuid::new_v4();
+        let mut parser = ClaudeJsonParser::new();
+        let mut router = MessageRouter::new();
+        let init = r#"{"type":"system","subtype":"init","model":"claude-sonnet","session_id":"s1","tools":[],"mcp_servers":[{"name":"github","status":"connected"},{"name":"slack","status":"failed"}]}"#;
+
+        for event in parser.parse_line(init).unwrap() {
+            for entry in DockerLogStreamingManager::agent_event_to_log_entries(
+                event,
+                "container",
+                session_id,
+                &mut router,
+            ) {
+                state.add_live_log(session_id, entry);
+            }
+        }
+
+        let servers = &state.session_mcp_servers[&session_id];
+        assert_eq!(
+            servers,
+            &vec![
+                McpServerInfo {
+                    name: "github".to_string(),
+                    status: "connected".to_string(),
+                },
+                McpServerInfo {
+                    name: "slack".to_string(),
+                    status: "failed".to_string(),
+                },
+            ]
+        );
+        assert!(!servers[0].is_error());
+        assert!(servers[1].is_error());
+        // The init event is still shown in the logs, without the status entry
+        let logs = &state.live_logs[&session_id];
+        assert!(!logs.is_empty());
+        assert!(!logs.iter().any(|log| log.metadata.contains_key("servers")));
+    }
+
+    /// Enter on a dashboard row selects that session back in the session list
+    #[tokio::test]
+    async fn test_dashboard_opens_selected_session() {
+        use crate::app::state::View;
+
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        workspace.add_session(crate::models::Session::new("a".to_string(), "/tmp/a".to_string()));
+        workspace.add_session(crate::models::Session::new("b".to_string(), "/tmp/b".to_string()));
+        let second_id = workspace.sessions[1].id;
+        state.workspaces.push(workspace);
+
+        state.show_dashboard();
+        assert_eq!(state.current_view, View::Dashboard);
+
+        let dashboard = state.dashboard.as_mut().unwrap();
+        while dashboard.selected_session_id() != Some(second_id) {
+            dashboard.next();
+        }
+        state.open_dashboard_selection();
+
+        assert_eq!(state.current_view, View::SessionList);
+        assert_eq!(state.selected_workspace_index, Some(0));
+        assert_eq!(state.selected_session_index, Some(1));
+        assert!(state.dashboard.is_none());
+    }
+
+    /// Session creation waits on an in-flight refresh and picks up its outcome
+    #[tokio::test]
+    async fn test_wait_for_auth_refresh_updates_status() {
+        use crate::app::state::AuthStatus;
+
+        let mut state = AppState::new();
+        assert_eq!(state.auth_status, AuthStatus::Unknown);
+
+        // No refresh in flight: nothing to wait for
+        assert!(state.wait_for_auth_refresh().await.is_ok());
+
+        state.auth_status = AuthStatus::Refreshing;
+        state.auth_refresh_task = Some(tokio::spawn(async { Ok(()) }));
+        assert!(state.wait_for_auth_refresh().await.is_ok());
+        assert_eq!(state.auth_status, AuthStatus::Valid);
+        assert!(state.auth_refresh_task.is_none());
+
+        state.auth_status = AuthStatus::Refreshing;
+        state.auth_refresh_task = Some(tokio::spawn(async { Err("expired".to_string()) }));
+        assert!(state.wait_for_auth_refresh().await.is_err());
+        assert_eq!(state.auth_status, AuthStatus::RefreshFailed("expired".to_string()));
+    }
+
+    #[test]
+    fn test_prompt_soft_wrap_cursor_movement() {
+        use crate::app::state::TextEditor;
+
+        let mut editor = TextEditor::from_string("hello wide world\nend");
+        let rows: Vec<_> = editor
+            .visual_rows(8)
+            .into_iter()
+            .map(|(line, range)| &editor.get_lines()[line][range])
+            .collect();
+        assert_eq!(rows, vec!["hello ", "wide ", "world", "end"]);
+
+        // Down moves a visual row within the wrapped line, then into the next line
+        editor.set_cursor_position(0, 2);
+        editor.move_cursor_down_wrapped(8);
+        assert_eq!(editor.get_cursor_position(), (0, 8));
+        assert_eq!(editor.visual_cursor(8), (1, 2));
+        editor.move_cursor_down_wrapped(8);
+        editor.move_cursor_down_wrapped(8);
+        assert_eq!(editor.get_cursor_position(), (1, 2));
+
+        editor.move_cursor_up_wrapped(8);
+        assert_eq!(editor.visual_cursor(8), (2, 2));
+
+        // Offsets into the full text, as used for @ file references
+        editor.set_cursor_offset(18);
+        assert_eq!(editor.get_cursor_position(), (1, 1));
+        assert_eq!(editor.cursor_offset(), 18);
+    }
+
+    #[test]
+    fn test_adaptive_tick_rate() {
+        use crate::app::state::ACTIVE_TICK_RATE;
+        use std::time::{Duration, Instant};
+
+        let mut state = AppState::default();
+        state.idle_tick_rate = Duration::from_secs(1);
+        state.idle_after = Duration::from_secs(10);
+        let now = Instant::now();
+
+        state.last_activity = now - Duration::from_secs(2);
+        assert_eq!(state.tick_rate(now), ACTIVE_TICK_RATE);
+
+        state.last_activity = now - Duration::from_secs(30);
+        assert_eq!(state.tick_rate(now), Duration::from_secs(1));
+
+        // Pending work keeps the full rate even without input
+        state.pending_event = Some(crate::app::events::AppEvent::Quit);
+        assert_eq!(state.tick_rate(now), ACTIVE_TICK_RATE);
+        state.pending_event = None;
+
+        // An idle rate faster than the normal tick never speeds the loop up
+        state.idle_tick_rate = Duration::from_millis(50);
+        assert_eq!(state.tick_rate(now), ACTIVE_TICK_RATE);
+    }
+
+    #[test]
+    fn test_prompt_undo_redo() {
+        use crate::app::state::TextEditor;
+
+        let mut editor = TextEditor::new();
+        for ch in "fix the".chars() {
+            editor.insert_char(ch);
+        }
+        editor.insert_newline();
+        editor.insert_text("pasted\nblock");
+        assert_eq!(editor.to_string(), "fix the\npasted\nblock");
+
+        // Undo after a paste restores the pre-paste buffer and cursor
+        assert!(editor.undo());
+        assert_eq!(editor.to_string(), "fix the\n");
+        assert_eq!(editor.get_cursor_position(), (1, 0));
+
+        // The newline is its own step, and typing undoes as one step
+        assert!(editor.undo());
+        assert_eq!(editor.to_string(), "fix the");
+        assert!(editor.undo());
+        assert!(editor.is_empty());
+        assert!(!editor.undo());
+
+        assert!(editor.redo());
+        assert_eq!(editor.to_string(), "fix the");
+        assert_eq!(editor.get_cursor_position(), (0, 7));
+
+        // Word deletes are separate steps, and typing after an undo clears redo
+        editor.delete_word_backward();
+        assert_eq!(editor.to_string(), "fix ");
+        assert!(editor.undo());
+        assert_eq!(editor.to_string(), "fix the");
+        editor.insert_char('!');
+        assert!(!editor.redo());
+        assert_eq!(editor.to_string(), "fix the!");
+    }
+
+    #[test]
+    fn test_prompt_char_and_line_count() {
+        use crate::app::state::TextEditor;
+
+        let editor = TextEditor::new();
+        assert_eq!((editor.char_count(), editor.line_count()), (0, 1));
+
+        // Counted by chars, not bytes, and newlines count like to_string() produces them
+        let editor = TextEditor::from_string("héllo wörld\n日本語\n🦀");
+        assert_eq!(editor.char_count(), editor.to_string().chars().count());
+        assert_eq!(editor.char_count(), 17);
+        assert_eq!(editor.line_count(), 3);
+    }
+
+    #[test]
+    fn test_select_base_branch_step() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit_id =
+            repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[]).unwrap();
+        let commit = repo.find_commit(commit_id).unwrap();
+        repo.branch("develop", &commit, false).unwrap();
+        repo.branch("release/1.0", &commit, false).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let mut state = AppState::new();
+        state.new_session_state = Some(NewSessionState {
+            filtered_repos: vec![(0, temp_dir.path().to_path_buf())],
+            selected_repo_index: Some(0),
+            branch_name: "agents/feature".to_string(),
+            step: NewSessionStep::InputBranch,
+            ..Default::default()
+        });
+
+        state.new_session_proceed_to_base_branch();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.step, NewSessionStep::SelectBaseBranch);
+        // The checked-out branch is listed first and selected by default
+        assert_eq!(session_state.filtered_base_branches()[0], &current);
+        assert_eq!(session_state.selected_base_branch_index, Some(0));
+
+        // Typing filters the list like the repository picker
+        for ch in "rel".chars() {
+            state.new_session_update_branch(ch);
+        }
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.filtered_base_branches(), vec!["release/1.0"]);
+        assert_eq!(session_state.branch_name, "agents/feature");
+
+        state.new_session_confirm_base_branch();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.step, NewSessionStep::SelectMode);
+        assert_eq!(session_state.base_branch.as_deref(), Some("release/1.0"));
+    }
+
+    /// Cycling the sort mode re-sorts each workspace and keeps the selected session selected
+    #[test]
+    fn test_cycle_session_sort_keeps_selection() {
+        use crate::config::SessionSortMode;
+        use crate::models::{Session, SessionStatus};
+
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), PathBuf::from("/tmp/repo"));
+        let mut stopped = Session::new("alpha".to_string(), "/tmp/repo".to_string());
+        stopped.set_status(SessionStatus::Stopped);
+        let mut running = Session::new("charlie".to_string(), "/tmp/repo".to_string());
+        running.set_status(SessionStatus::Running);
+        running.git_changes.modified = 3;
+        let mut errored = Session::new("bravo".to_string(), "/tmp/repo".to_string());
+        errored.set_status(SessionStatus::Error("boom".to_string()));
+        errored.last_accessed = chrono::Utc::now() + chrono::Duration::seconds(60);
+        let errored_id = errored.id;
+        workspace.add_session(stopped);
+        workspace.add_session(running);
+        workspace.add_session(errored);
+        state.workspaces.push(workspace);
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(2);
+
+        let names = |state: &AppState| -> Vec<String> {
+            state.workspaces[0].sessions.iter().map(|s| s.name.clone()).collect()
+        };
+
+        state.sort_sessions();
+        assert_eq!(names(&state), ["charlie", "alpha", "bravo"]);
+        assert_eq!(state.selected_session().unwrap().id, errored_id);
+
+        state.cycle_session_sort();
+        assert_eq!(state.session_sort_mode, SessionSortMode::Name);
+        assert_eq!(names(&state), ["alpha", "bravo", "charlie"]);
+        assert_eq!(state.selected_session().unwrap().id, errored_id);
+
+        state.cycle_session_sort();
+        assert_eq!(state.session_sort_mode, SessionSortMode::LastActivity);
+        assert_eq!(names(&state)[0], "bravo");
+
+        state.cycle_session_sort();
+        assert_eq!(state.session_sort_mode, SessionSortMode::Changes);
+        assert_eq!(names(&state)[0], "charlie");
+        assert_eq!(state.selected_session().unwrap().id, errored_id);
+
+        state.cycle_session_sort();
+        assert_eq!(state.session_sort_mode, SessionSortMode::Status);
+    }
+
+    /// The session filter matches across workspaces, drives navigation, and clears on Escape
+    #[test]
+    fn test_session_filter_across_workspaces() {
+        use crate::models::{Session, Workspace};
+
+        let mut state = AppState::new();
+        let repos = [
+            ("api", ["agents/login", "agents/cache"]),
+            ("web", ["agents/login-page", "agents/theme"]),
+        ];
+        for (repo, branches) in repos {
+            let mut workspace = Workspace::new(repo.to_string(), PathBuf::from("/tmp").join(repo));
+            for branch in branches {
+                workspace.add_session(Session::new(branch.to_string(), "/tmp".to_string()));
+            }
+            state.workspaces.push(workspace);
+        }
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(1);
+
+        state.start_session_filter();
+        for ch in "LOGIN".chars() {
+            state.session_filter_input_char(ch);
+        }
+        assert_eq!(state.filtered_sessions(), vec![(0, 0), (1, 0)]);
+        assert_eq!(state.selected_session_index, Some(0)); // cache was filtered out
+
+        let selected =
+            |state: &AppState| (state.selected_workspace_index, state.selected_session_index);
+        state.next_session();
+        assert_eq!(selected(&state), (Some(1), Some(0)));
+        state.next_session();
+        assert_eq!(selected(&state), (Some(1), Some(0)));
+        state.previous_session();
+        assert_eq!(selected(&state), (Some(0), Some(0)));
+
+        // Workspace names match too
+        state.session_filter = Some("web".to_string());
+        assert_eq!(state.filtered_sessions(), vec![(1, 0), (1, 1)]);
+
+        state.confirm_session_filter();
+        assert!(!state.session_filter_editing);
+        let escape = crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Esc,
+            crossterm::event::KeyModifiers::NONE,
+        );
+        let event = crate::app::EventHandler::handle_key_event(escape, &mut state);
+        assert!(matches!(event, Some(crate::app::events::AppEvent::ClearSessionFilter)));
+        crate::app::EventHandler::process_event(event.unwrap(), &mut state);
+        assert!(state.session_filter.is_none());
+        assert_eq!(state.filtered_sessions().len(), 4);
+    }
+
+    /// Discarding from the git view always asks first and leaves the files alone on "No"
+    #[test]
+    fn test_git_discard_requires_confirmation() {
+        use crate::app::events::AppEvent;
+        use crate::app::EventHandler;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("tracked.txt"), "original").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("tracked.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[]).unwrap();
+        std::fs::write(temp_dir.path().join("tracked.txt"), "edited").unwrap();
+        std::fs::write(temp_dir.path().join("new.txt"), "untracked").unwrap();
+
+        let mut state = AppState::new();
+        let mut git_state =
+            crate::components::git_view::GitViewState::new(temp_dir.path().to_path_buf());
+        git_state.refresh_git_status().unwrap();
+        state.git_view_state = Some(git_state);
+
+        EventHandler::process_event(AppEvent::GitViewDiscard(true), &mut state);
+        let dialog = state.confirmation_dialog.as_ref().unwrap();
+        assert!(dialog.message.contains("2 files"));
+        assert!(dialog.message.contains("1 untracked will be deleted"));
+        assert!(!dialog.selected_option, "Defaults to No");
+
+        // Confirming with the default answer closes the dialog without touching anything
+        EventHandler::process_event(AppEvent::ConfirmationConfirm, &mut state);
+        assert!(state.confirmation_dialog.is_none());
+        assert!(temp_dir.path().join("new.txt").exists());
+
+        EventHandler::process_event(AppEvent::GitViewDiscard(true), &mut state);
+        EventHandler::process_event(AppEvent::ConfirmationToggle, &mut state);
+        EventHandler::process_event(AppEvent::ConfirmationConfirm, &mut state);
+        let tracked = std::fs::read_to_string(temp_dir.path().join("tracked.txt")).unwrap();
+        assert_eq!(tracked, "original");
+        assert!(!temp_dir.path().join("new.txt").exists());
+        assert!(!state.git_view_state.as_ref().unwrap().is_dirty);
+    }
+
+    /// A copied diff reaches the user, on the clipboard or in a file when there's none
+    #[test]
+    fn test_copy_git_diff_without_clipboard_is_not_an_error() {
+        use crate::app::state::NotificationType;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("tracked.txt"), "original").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("tracked.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[]).unwrap();
+        std::fs::write(temp_dir.path().join("tracked.txt"), "edited").unwrap();
+
+        let mut state = AppState::new();
+        let git_state =
+            crate::components::git_view::GitViewState::new(temp_dir.path().to_path_buf());
+        let diff_file = git_state.diff_file_path();
+        state.git_view_state = Some(git_state);
+
+        state.copy_git_diff(true);
+
+        assert_eq!(state.notifications.len(), 1);
+        let notification = &state.notifications[0];
+        assert_ne!(notification.notification_type, NotificationType::Error);
+        if notification.notification_type == NotificationType::Info {
+            assert!(notification.message.contains(&diff_file.display().to_string()));
+            assert!(std::fs::read_to_string(&diff_file).unwrap().contains("+edited"));
+            std::fs::remove_file(diff_file).unwrap();
+        }
+    }
+
+    /// The environment step validates entries inline and returns to the permissions step
+    #[test]
+    fn test_configure_env_step() {
+        let mut state = AppState::new();
+        state.new_session_state = Some(NewSessionState {
+            step: NewSessionStep::ConfigurePermissions,
+            mode: SessionMode::Interactive,
+            ..Default::default()
+        });
+        state.new_session_open_list_step(NewSessionStep::ConfigureEnv);
+        assert_eq!(
+            state.new_session_state.as_ref().unwrap().step,
+            NewSessionStep::ConfigurePermissions,
+            "Interactive sessions have no container to configure"
+        );
+
+        state.new_session_state.as_mut().unwrap().mode = SessionMode::Boss;
+        state.new_session_open_list_step(NewSessionStep::ConfigureEnv);
+        for ch in "9BAD=x".chars() {
+            state.new_session_list_entry_char(ch);
+        }
+        state.new_session_list_entry_submit();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert!(session_state.list_entry.error.is_some());
+        assert!(session_state.env_vars.is_empty());
+
+        state.new_session_state.as_mut().unwrap().list_entry.text = "NODE_ENV=test".to_string();
+        state.new_session_list_entry_submit();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.env_vars, vec![("NODE_ENV".to_string(), "test".to_string())]);
+        assert!(session_state.list_entry.text.is_empty());
+        assert!(session_state.list_entry.error.is_none());
+
+        // Enter on an empty input finishes the step
+        state.new_session_list_entry_submit();
+        assert_eq!(
+            state.new_session_state.as_ref().unwrap().step,
+            NewSessionStep::ConfigurePermissions
+        );
+    }
+
+    /// Mounts are rejected inline when the host path is missing and kept per session
+    #[test]
+    fn test_configure_volumes_step() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut state = AppState::new();
+        state.new_session_state = Some(NewSessionState {
+            step: NewSessionStep::ConfigurePermissions,
+            mode: SessionMode::Boss,
+            ..Default::default()
+        });
+        state.new_session_open_list_step(NewSessionStep::ConfigureVolumes);
+        assert_eq!(
+            state.new_session_state.as_ref().unwrap().step,
+            NewSessionStep::ConfigureVolumes
+        );
+
+        let missing = temp_dir.path().join("missing");
+        for ch in format!("{}:/cache", missing.display()).chars() {
+            state.new_session_list_entry_char(ch);
+        }
+        state.new_session_list_entry_submit();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert!(session_state.list_entry.error.as_ref().unwrap().contains("does not exist"));
+        assert!(session_state.volumes.is_empty());
+
+        state.new_session_state.as_mut().unwrap().list_entry.text =
+            format!("{}:/cache:ro", temp_dir.path().display());
+        state.new_session_list_entry_submit();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(
+            session_state.volumes,
+            vec![(temp_dir.path().to_path_buf(), "/cache".to_string(), true)]
+        );
+        assert!(session_state.list_entry.error.is_none());
+
+        // Saved mounts come back for a restart and are forgotten with the session
+        let session_id = uuid::Uuid::new_v4();
+        state.set_session_volumes(session_id, session_state.volumes.clone());
+        assert_eq!(state.session_volumes[&session_id].len(), 1);
+        state.set_session_volumes(session_id, Vec::new());
+        assert!(!state.session_volumes.contains_key(&session_id));
+
+        state.new_session_list_entry_submit();
+        assert_eq!(
+            state.new_session_state.as_ref().unwrap().step,
+            NewSessionStep::ConfigurePermissions
+        );
+    }
+
+    /// A port already taken on the host is rejected inline instead of failing creation
+    #[test]
+    fn test_configure_ports_step_rejects_taken_host_port() {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let taken = listener.local_addr().unwrap().port();
+        let mut state = AppState::new();
+        state.new_session_state = Some(NewSessionState {
+            step: NewSessionStep::ConfigurePermissions,
+            mode: SessionMode::Boss,
+            ..Default::default()
+        });
+        state.new_session_open_list_step(NewSessionStep::ConfigurePorts);
+
+        state.new_session_state.as_mut().unwrap().list_entry.text = format!("{}:3000", taken);
+        state.new_session_list_entry_submit();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert!(session_state.list_entry.error.as_ref().unwrap().contains(&taken.to_string()));
+        assert!(session_state.ports.is_empty());
+
+        drop(listener);
+        state.new_session_list_entry_submit();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.ports, vec![(taken, 3000)]);
+        assert!(session_state.list_entry.error.is_none());
+
+        state.new_session_list_entry_submit();
+        assert_eq!(
+            state.new_session_state.as_ref().unwrap().step,
+            NewSessionStep::ConfigurePermissions
+        );
+    }
+
+    /// New sessions start from the configured mode, permissions and branch prefix
+    #[test]
+    fn test_new_session_uses_configured_defaults() {
+        let mut state = AppState::new();
+        state.branch_prefix = "me/".to_string();
+        state.default_session_mode = SessionMode::Boss;
+        state.default_skip_permissions = true;
+
+        state.new_session_state = Some(NewSessionState {
+            filtered_repos: vec![(0, PathBuf::from("/tmp/repo"))],
+            selected_repo_index: Some(0),
+            ..state.new_session_defaults()
+        });
+        state.new_session_confirm_repo();
+
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.step, NewSessionStep::InputBranch);
+        assert!(session_state.branch_name.starts_with("me/"));
+        assert_eq!(session_state.branch_name.len(), "me/".len() + 8);
+        assert_eq!(session_state.mode, SessionMode::Boss);
+        assert!(session_state.skip_permissions);
+    }
+
+    /// The model key cycles the CLI default, the configured model and the model list
+    #[test]
+    fn test_new_session_model_cycles_through_choices() {
+        let mut state = AppState::new();
+        state.default_model = Some("claude-custom".to_string());
+        state.model_choices = vec!["sonnet".to_string(), "opus".to_string()];
+        state.new_session_state = Some(NewSessionState {
+            step: NewSessionStep::SelectMode,
+            ..state.new_session_defaults()
+        });
+        let model = |state: &AppState| state.new_session_state.as_ref().unwrap().model.clone();
+        assert_eq!(model(&state).as_deref(), Some("claude-custom"));
+
+        let mut seen = vec![];
+        for _ in 0..4 {
+            state.new_session_cycle_model();
+            seen.push(model(&state));
+        }
+        assert_eq!(
+            seen,
+            vec![
+                Some("sonnet".to_string()),
+                Some("opus".to_string()),
+                None,
+                Some("claude-custom".to_string())
+            ]
+        );
+    }
+
+    /// Invalid branch names block the next step and keep the typed text for editing
+    #[test]
+    fn test_branch_name_checked_before_mode_selection() {
+        let mut state = AppState::new();
+        state.new_session_state = Some(NewSessionState {
+            branch_name: "../escape".to_string(),
+            step: NewSessionStep::InputBranch,
+            ..Default::default()
+        });
+
+        state.new_session_proceed_to_base_branch();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.step, NewSessionStep::InputBranch);
+        assert_eq!(session_state.branch_name, "../escape");
+        assert!(session_state.branch_error.is_some());
+
+        state.new_session_state.as_mut().unwrap().branch_name = "feat/foo ba".to_string();
+        state.new_session_update_branch('r');
+        assert!(state.new_session_state.as_ref().unwrap().branch_error.is_none());
+
+        state.new_session_proceed_to_base_branch();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.step, NewSessionStep::SelectMode);
+        assert_eq!(session_state.branch_name, "feat/foo-bar");
+    }
+
+    /// A branch already checked out in a worktree is caught at the branch step and can be
+    /// renamed with a suffix or resolved by restarting the session that owns the worktree
+    #[test]
+    fn test_branch_checked_out_in_worktree_is_flagged() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo_path = temp_dir.path().join("repo");
+        let repo = git2::Repository::init(&repo_path).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[]).unwrap();
+        let worktree_path = temp_dir.path().join("taken");
+        let status = std::process::Command::new("git")
+            .args(["worktree", "add", "-q", "-b", "agents/taken"])
+            .arg(&worktree_path)
+            .current_dir(&repo_path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let new_state = || NewSessionState {
+            filtered_repos: vec![(0, repo_path.clone())],
+            selected_repo_index: Some(0),
+            branch_name: "agents/taken".to_string(),
+            step: NewSessionStep::InputBranch,
+            ..Default::default()
+        };
+
+        let mut state = AppState::new();
+        state.new_session_state = Some(new_state());
+        state.new_session_proceed_to_base_branch();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.step, NewSessionStep::InputBranch);
+        assert!(session_state.branch_error.is_some());
+        let conflict = session_state.branch_conflict.as_ref().unwrap();
+        assert_eq!(conflict.session_id, None);
+
+        state.new_session_branch_add_suffix();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.branch_name, "agents/taken-2");
+        assert!(session_state.branch_conflict.is_none());
+        state.new_session_proceed_to_base_branch();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.step, NewSessionStep::SelectBaseBranch);
+
+        // With a session on that worktree, reusing it switches to the restart flow
+        let mut workspace = crate::models::Workspace::new("repo".to_string(), repo_path.clone());
+        let session = crate::models::Session::new(
+            "taken".to_string(),
+            worktree_path.to_string_lossy().to_string(),
+        );
+        let session_id = session.id;
+        workspace.add_session(session);
+        state.workspaces = vec![workspace];
+        state.new_session_state = Some(new_state());
+        state.new_session_proceed_to_base_branch();
+        let conflict = state.new_session_state.as_ref().unwrap().branch_conflict.as_ref();
+        assert_eq!(conflict.unwrap().session_id, Some(session_id));
+
+        state.new_session_branch_reuse();
+        let session_state = state.new_session_state.as_ref().unwrap();
+        assert_eq!(session_state.restart_session_id, Some(session_id));
+        assert_eq!(session_state.step, NewSessionStep::SelectMode);
+    }
+
+    /// Stopping needs a container, and an already stopped one is left alone
+    #[tokio::test]
+    async fn test_stop_session_without_running_container() {
+        use crate::models::{Session, SessionStatus};
+
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), PathBuf::from("/tmp/repo"));
+        let bare = Session::new("bare".to_string(), "/tmp/repo".to_string());
+        let bare_id = bare.id;
+        let mut stopped = Session::new("stopped".to_string(), "/tmp/repo".to_string());
+        stopped.container_id = Some("abc123".to_string());
+        stopped.set_status(SessionStatus::Stopped);
+        let stopped_id = stopped.id;
+        workspace.add_session(bare);
+        workspace.add_session(stopped);
+        state.workspaces.push(workspace);
+
+        assert!(state.stop_session(bare_id).await.is_err());
+
+        assert!(state.stop_session(stopped_id).await.is_ok());
+        let session = &state.workspaces[0].sessions[1];
+        assert_eq!(session.status, SessionStatus::Stopped);
+        assert_eq!(session.container_id.as_deref(), Some("abc123"));
+        assert!(state.notifications.iter().any(|n| n.message.contains("already stopped")));
+    }
+
+    /// Bulk cleanup confirms only the unprotected stopped and errored sessions
+    #[test]
+    fn test_cleanup_dead_sessions_confirmation() {
+        use crate::app::state::ConfirmAction;
+        use crate::models::{Session, SessionStatus};
+
+        let mut state = AppState::new();
+        state.show_cleanup_dead_sessions_confirmation();
+        assert!(state.confirmation_dialog.is_none());
+
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), PathBuf::from("/tmp/repo"));
+        let mut running = Session::new("running".to_string(), "/tmp/repo".to_string());
+        running.set_status(SessionStatus::Running);
+        let mut stopped = Session::new("stopped".to_string(), "/tmp/repo".to_string());
+        stopped.set_status(SessionStatus::Stopped);
+        let mut errored = Session::new("errored".to_string(), "/tmp/repo".to_string());
+        errored.set_status(SessionStatus::Error("boom".to_string()));
+        let mut locked = Session::new("locked".to_string(), "/tmp/repo".to_string());
+        locked.set_status(SessionStatus::Stopped);
+        let (stopped_id, errored_id, locked_id) = (stopped.id, errored.id, locked.id);
+        for session in [running, stopped, errored, locked] {
+            workspace.add_session(session);
+        }
+        state.workspaces.push(workspace);
+        state.protected_sessions.insert(locked_id);
+
+        assert_eq!(state.dead_session_ids(), vec![stopped_id, errored_id, locked_id]);
+
+        state.show_cleanup_dead_sessions_confirmation();
+        let dialog = state.confirmation_dialog.as_ref().unwrap();
+        assert!(dialog.message.contains("Delete 2 stopped/errored"));
+        assert!(dialog.message.contains("1 protected"));
+        assert!(matches!(
+            &dialog.confirm_action,
+            ConfirmAction::CleanupDeadSessions(ids) if *ids == vec![stopped_id, errored_id]
+        ));
+    }
+
+    /// Like re-authentication, switching profiles is refused while sessions run
+    #[test]
+    fn test_auth_profile_switch_needs_no_running_sessions() {
+        use crate::app::state::AuthProfilePickerState;
+        use crate::models::{Session, SessionStatus};
+
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), PathBuf::from("/tmp/repo"));
+        let mut running = Session::new("running".to_string(), "/tmp/repo".to_string());
+        running.set_status(SessionStatus::Running);
+        workspace.add_session(running);
+        state.workspaces.push(workspace);
+
+        state.auth_profile_picker = Some(AuthProfilePickerState {
+            profiles: vec!["default".to_string(), "work".to_string()],
+            active: "default".to_string(),
+            selected: 0,
+        });
+        state.move_auth_profile_selection(5);
+        assert_eq!(state.auth_profile_picker.as_ref().unwrap().selected, 1);
+
+        state.switch_auth_profile();
+        assert!(state.auth_profile_picker.is_some());
+        assert!(state.notifications[0].message.contains("1 running sessions"));
+        assert_eq!(state.auth_profile, "default");
+    }
+
+    /// Serve one HTTP response on localhost, standing in for the Anthropic API
+    async fn serve_once(status: &'static str, body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            // Read the headers and the body they announce before answering
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_ascii_lowercase();
+                let Some(end) = text.find("\r\n\r\n") else {
+                    if n == 0 {
+                        break;
+                    }
+                    continue;
+                };
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                if n == 0 || request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+                 connection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    /// A key the API rejects with 401 is never written; one it accepts is
+    #[tokio::test]
+    async fn test_api_key_is_validated_before_saving() {
+        use crate::claude::ClaudeAuth;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let env_path = temp_dir.path().join("profile").join(".env");
+        let key = "sk-ant-REDACTED";
+        let auth = |base_url| ClaudeAuth { base_url, ..ClaudeAuth::from_api_key(key.to_string()) };
+
+        let rejecting = serve_once(
+            "401 Unauthorized",
+            r#"{"type":"error","error":{"type":"authentication_error"}}"#,
+        )
+        .await;
+        let err = AppState::store_api_key(key, &env_path, Some(auth(rejecting))).await.unwrap_err();
+        assert!(err.contains("401 Unauthorized"), "{}", err);
+        assert!(!env_path.exists());
+
+        let accepting = serve_once(
+            "200 OK",
+            r#"{"id":"msg_1","content":[{"type":"text","text":"Hi"}],"model":"claude",
+                "role":"assistant","stop_reason":"end_turn",
+                "usage":{"input_tokens":1,"output_tokens":1}}"#,
+        )
+        .await;
+        AppState::store_api_key(key, &env_path, Some(auth(accepting))).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&env_path).unwrap(),
+            format!("ANTHROPIC_API_KEY={}\n", key)
+        );
+
+        // The offline bypass still checks the format
+        assert!(AppState::store_api_key("sk-short", &env_path, None).await.is_err());
+    }
+
+    /// Sends are allowed below the chat token budget and blocked at or above it
+    #[test]
+    fn test_chat_token_budget_enforcement() {
+        use crate::app::state::{AsyncAction, ClaudeChatState};
+        fn chat(state: &mut AppState) -> &mut ClaudeChatState {
+            state.claude_chat_state.as_mut().unwrap()
+        }
+
+        let mut state = AppState::new();
+        state.chat_token_budget = Some(100);
+        state.claude_chat_state =
+            Some(ClaudeChatState { token_budget: Some(100), ..ClaudeChatState::new() });
+        chat(&mut state).input_buffer = "hello".to_string();
+
+        // Below the budget
+        chat(&mut state).total_tokens_used = 99;
+        state.submit_claude_message();
+        assert!(matches!(
+            state.pending_async_action.take(),
+            Some(AsyncAction::SendClaudeMessage(message)) if message == "hello"
+        ));
+
+        // At and above the budget the send is refused with a warning
+        for used in [100, 150] {
+            chat(&mut state).total_tokens_used = used;
+            assert!(chat(&mut state).budget_check().is_err());
+            state.notifications.clear();
+            state.submit_claude_message();
+            assert!(state.pending_async_action.is_none());
+            assert!(state.notifications[0].message.contains("token budget reached"));
+        }
+
+        // Raising allows another budget's worth beyond what has been used
+        state.raise_chat_token_budget();
+        assert_eq!(chat(&mut state).token_budget, Some(250));
+        assert!(chat(&mut state).budget_check().is_ok());
+
+        state.reset_chat_token_usage();
+        assert_eq!(chat(&mut state).total_tokens_used, 0);
+    }
+
+    /// Streamed responses add their prompt and output token counts
+    #[test]
+    fn test_chat_usage_accumulates_from_stream_events() {
+        use crate::app::state::ClaudeChatState;
+        use crate::claude::types::ClaudeStreamingEvent;
+
+        let mut chat = ClaudeChatState::new();
+        for data in [
+            r#"{"type":"message_start","message":{"id":"msg_1","model":"claude",
+                "role":"assistant","usage":{"input_tokens":25,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_delta","index":0,
+                "delta":{"type":"text_delta","text":"Hi"}}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},
+                "usage":{"output_tokens":15}}"#,
+            r#"{"type":"message_stop"}"#,
+        ] {
+            let event: ClaudeStreamingEvent = serde_json::from_str(data).unwrap();
+            chat.record_stream_usage(&event);
+        }
+        assert_eq!(chat.total_tokens_used, 40);
+    }
+
+    /// Cancelling mid-stream keeps the partial reply, marked interrupted, and lets the
+    /// next message be sent
+    #[tokio::test]
+    async fn test_cancel_streaming_keeps_partial_reply() {
+        use crate::app::state::{AsyncAction, ChatStream, ClaudeChatState};
+        use crate::claude::types::ClaudeStreamingEvent;
+
+        let mut state = AppState::new();
+        let mut chat_state = ClaudeChatState::new();
+        chat_state.start_streaming("Explain the build".to_string());
+        state.claude_chat_state = Some(chat_state);
+
+        let (sender, events) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(std::future::pending::<()>());
+        state.chat_stream = Some(ChatStream { task, events });
+        let delta = r#"{"type":"content_block_delta","index":0,
+            "delta":{"type":"text_delta","text":"The build starts by"}}"#;
+        sender.send(Ok(serde_json::from_str::<ClaudeStreamingEvent>(delta).unwrap())).unwrap();
+        state.poll_chat_stream();
+        assert!(state.chat_stream.is_some());
+
+        state.cancel_streaming();
+        assert!(state.chat_stream.is_none());
+        assert!(sender.is_closed());
+        let chat_state = state.claude_chat_state.as_mut().unwrap();
+        assert!(!chat_state.is_streaming);
+        let reply = chat_state.messages.last().unwrap();
+        assert_eq!(reply.content, "The build starts by");
+        assert!(reply.interrupted);
+
+        chat_state.input_buffer = "Go on".to_string();
+        state.submit_claude_message();
+        assert!(matches!(state.pending_async_action, Some(AsyncAction::SendClaudeMessage(_))));
+    }
+
+    /// Markdown export has the header, then each message under its role in order
+    #[test]
+    fn test_chat_export_markdown() {
+        use crate::app::state::ClaudeChatState;
+        use crate::claude::ClaudeMessage;
+
+        let session_id = uuid::Uuid::new_v4();
+        let mut chat_state =
+            ClaudeChatState { associated_session_id: Some(session_id), ..ClaudeChatState::new() };
+        chat_state.total_tokens_used = 321;
+        chat_state.add_message(ClaudeMessage::user("How do I parse args?".to_string()));
+        chat_state.add_message(ClaudeMessage::assistant(
+            "Use clap:\n\n```rust\nlet cli = Cli::parse();\n```\n".to_string(),
+        ));
+        chat_state.add_message(ClaudeMessage::user("Thanks".to_string()));
+
+        let markdown = chat_state.export_markdown("claude-opus-4-1");
+        assert!(markdown.starts_with("# Claude Chat\n"));
+        assert!(markdown.contains(&format!("- Session: {}\n", session_id)));
+        assert!(markdown.contains("- Model: claude-opus-4-1\n"));
+        assert!(markdown.contains("- Total tokens: 321\n"));
+        assert!(markdown.contains("```rust\nlet cli = Cli::parse();\n```\n"));
+
+        let headers: Vec<&str> = markdown.lines().filter(|line| line.starts_with("## ")).collect();
+        assert_eq!(headers, vec!["## User", "## Assistant", "## User"]);
+        let first = markdown.find("How do I parse args?").unwrap();
+        let reply = markdown.find("Use clap:").unwrap();
+        let last = markdown.find("Thanks").unwrap();
+        assert!(first < reply && reply < last);
+    }
+
+    /// Attaching looks up the session's tmux name; a dead tmux session offers a restart
+    #[test]
+    fn test_tmux_attach_name_lookup_and_dead_session() {
+        use crate::app::state::ConfirmAction;
+        use crate::models::SessionStatus;
+
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        let bare = crate::models::Session::new("bare".to_string(), "/tmp/repo".to_string());
+        let mut attached = crate::models::Session::new("tmux".to_string(), "/tmp/repo".to_string());
+        attached.set_tmux_session_name("agents_tmux".to_string());
+        attached.set_status(SessionStatus::Running);
+        let (bare_id, tmux_id) = (bare.id, attached.id);
+        workspace.add_session(bare);
+        workspace.add_session(attached);
+        state.workspaces.push(workspace);
+
+        assert_eq!(state.tmux_session_name_for(tmux_id).as_deref(), Ok("agents_tmux"));
+        assert!(state.tmux_session_name_for(bare_id).unwrap_err().contains("no tmux session"));
+        assert_eq!(
+            state.tmux_session_name_for(uuid::Uuid::new_v4()),
+            Err("Session not found".to_string())
+        );
+
+        state.offer_restart_for_dead_tmux(tmux_id, "agents_tmux");
+        let dialog = state.confirmation_dialog.as_ref().unwrap();
+        assert!(matches!(dialog.confirm_action, ConfirmAction::RestartSession(id) if id == tmux_id));
+        let session = state.workspaces[0].sessions.iter().find(|s| s.id == tmux_id).unwrap();
+        assert_eq!(session.status, SessionStatus::Stopped);
+    }
+
+    fn state_with_container_session(container_id: &str) -> (AppState, uuid::Uuid) {
+        let mut state = AppState::new();
+        let mut workspace =
+            crate::models::Workspace::new("repo".to_string(), std::path::PathBuf::from("/tmp/repo"));
+        let mut session = crate::models::Session::new("boss".to_string(), "/tmp/repo".to_string());
+        session.container_id = Some(container_id.to_string());
+        session.set_status(crate::models::SessionStatus::Running);
+        let session_id = session.id;
+        workspace.add_session(session);
+        state.workspaces.push(workspace);
+        (state, session_id)
+    }
+
+    #[test]
+    fn test_health_check_stops_session_whose_container_died() {
+        use crate::docker::ContainerStatus;
+        use crate::models::SessionStatus;
+
+        let (mut state, session_id) = state_with_container_session("c1");
+        let statuses = |status| std::collections::HashMap::from([("c1".to_string(), status)]);
+
+        state.apply_container_health(&statuses(ContainerStatus::Running));
+        assert_eq!(state.find_session(session_id).unwrap().status, SessionStatus::Running);
+
+        state.apply_container_health(&statuses(ContainerStatus::Stopped));
+        assert_eq!(state.find_session(session_id).unwrap().status, SessionStatus::Stopped);
+        assert!(state.notifications.iter().any(|n| n.message.contains("stopped unexpectedly")));
+        // Auto-restart is off: nothing is queued and the session isn't watched any more
+        assert!(state.pending_async_action.is_none());
+        assert!(state.restart_attempts.is_empty());
+    }
+
+    #[test]
+    fn test_auto_restart_gives_up_after_max_attempts() {
+        use crate::docker::ContainerStatus;
+        use crate::models::SessionStatus;
+
+        let (mut state, session_id) = state_with_container_session("c1");
+        state.auto_restart = true;
+        state.auto_restart_max_attempts = 2;
+        let dead = std::collections::HashMap::from([("c1".to_string(), ContainerStatus::Stopped)]);
+
+        // Each restart that doesn't keep the container up counts as a failed attempt
+        for attempt in 1..=2 {
+            state.apply_container_health(&dead);
+            assert!(matches!(
+                state.pending_async_action.take(),
+                Some(crate::app::state::AsyncAction::AutoRestartSession(id)) if id == session_id
+            ));
+            assert_eq!(state.restart_attempts.get(&session_id), Some(&attempt));
+        }
+
+        state.apply_container_health(&dead);
+        assert!(state.pending_async_action.is_none());
+        assert!(state.restart_attempts.is_empty());
+        assert_eq!(
+            state.find_session(session_id).unwrap().status,
+            SessionStatus::Error("Gave up restarting after 2 attempts".to_string())
+        );
+
+        // A container seen up again resets the count
+        let (mut state, session_id) = state_with_container_session("c1");
+        state.auto_restart = true;
+        state.apply_container_health(&dead);
+        state.pending_async_action = None;
+        state.find_session_mut(session_id).unwrap().set_status(SessionStatus::Running);
+        let up = std::collections::HashMap::from([("c1".to_string(), ContainerStatus::Running)]);
+        state.apply_container_health(&up);
+        assert!(state.restart_attempts.is_empty());
+    }
+
+    /// Session creation runs in the background; each tick shows the build log so far
+    #[tokio::test]
+    async fn test_build_logs_reach_state_while_build_runs() {
+        use crate::app::state::{ContainerCreation, SessionCreation, SessionCreationContext};
+
+        let mut state = AppState::new();
+        let session_id = uuid::Uuid::new_v4();
+        let (tx, log_receiver) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let (finish_tx, finish_rx) = tokio::sync::oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            tx.send("Step 1/12 : FROM node:20".to_string()).unwrap();
+            tx.send("Step 2/12 : RUN apt-get update".to_string()).unwrap();
+            finish_rx.await.unwrap();
+            // Sent as the work completes, and still not lost
+            tx.send("Container failed to start".to_string()).unwrap();
+            Err("Docker daemon went away".to_string())
+        });
+        state.session_creation = Some(SessionCreation {
+            container: ContainerCreation { task, log_receiver },
+            restart_mode: None,
+            context: SessionCreationContext {
+                session_id,
+                repo_path: PathBuf::from("/tmp/repo"),
+                branch_name: "feature".to_string(),
+                session_branch: "feature".to_string(),
+                session_mode: SessionMode::Boss,
+                has_boss_prompt: true,
+                branch_name_generated: false,
+                base_branch: None,
+                restart_session_id: None,
+            },
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        state.poll_session_creation().await;
+        assert_eq!(
+            state.logs[&session_id],
+            vec!["Step 1/12 : FROM node:20", "Step 2/12 : RUN apt-get update"]
+        );
+        assert!(state.ui_needs_refresh);
+        assert!(state.session_creation.is_some());
+
+        finish_tx.send(()).unwrap();
+        for _ in 0..100 {
+            if state.session_creation.is_none() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            state.poll_session_creation().await;
+        }
+        assert!(state.session_creation.is_none());
+        assert_eq!(
+            state.logs[&session_id][2..],
+            [
+                "Container failed to start",
+                "Session creation failed: Docker daemon went away"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_progress_follows_steps_and_layers() {
+        use crate::docker::BuildProgress;
+
+        let mut state = AppState::new();
+        let session_id = uuid::Uuid::new_v4();
+
+        state.push_build_log(session_id, "Step 2/5 : RUN apt-get update".to_string());
+        assert!(matches!(
+            state.build_progress,
+            Some(BuildProgress::Step { current: 2, total: 5, .. })
+        ));
+
+        // Layer updates move the bar without flooding the log
+        state.push_build_log(session_id, "f1e2d3: Downloading [==>   ] 5MB/10MB".to_string());
+        assert!(matches!(state.build_progress, Some(BuildProgress::Layer { percent: 50, .. })));
+
+        state.push_build_log(session_id, " ---> Running in 4f2c9a1b".to_string());
+        assert_eq!(
+            state.logs[&session_id],
+            vec!["Step 2/5 : RUN apt-get update", " ---> Running in 4f2c9a1b"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_image_build_reports_steps_then_runs_action() {
+        use crate::app::state::{AsyncAction, ImageBuild};
+        use crate::docker::AgentsDevProgress;
+
+        let mut state = AppState::new();
+        let (tx, progress) = tokio::sync::mpsc::channel(100);
+        let (finish_tx, finish_rx) = tokio::sync::oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            for line in ["Step 3/15 : RUN npm install", "added 120 packages"] {
+                tx.send(AgentsDevProgress::BuildingImage(line.to_string())).await.unwrap();
+            }
+            finish_rx.await.unwrap();
+            Ok(())
+        });
+        state.image_build = Some(ImageBuild {
+            task,
+            progress,
+            then: AsyncAction::RefreshWorkspaces,
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        state.poll_image_build().await;
+        let messages: Vec<&str> = state.notifications.iter().map(|n| n.message.as_str()).collect();
+        assert_eq!(messages, ["🏗️ Building agents-dev image: step 3/15"]);
+        assert!(state.notifications[0].sticky);
+        assert!(state.image_build.is_some());
+        assert_eq!(state.pending_async_action, None);
+
+        finish_tx.send(()).unwrap();
+        for _ in 0..100 {
+            if state.image_build.is_none() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            state.poll_image_build().await;
+        }
+        assert!(state.image_build.is_none());
+        let messages: Vec<&str> = state.notifications.iter().map(|n| n.message.as_str()).collect();
+        assert_eq!(messages, ["✅ agents-dev image built"]);
+        assert_eq!(state.pending_async_action, Some(AsyncAction::RefreshWorkspaces));
+    }
 }
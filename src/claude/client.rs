@@ -4,7 +4,7 @@
 
 use crate::claude::streaming::ClaudeStreamingResponse;
 use crate::claude::types::{
-    ClaudeAuth, ClaudeChatSession, ClaudeMessage, ClaudeRequest, ClaudeResponse,
+    ClaudeAuth, ClaudeChatSession, ClaudeMessage, ClaudeRequest, ClaudeResponse, DEFAULT_MODEL,
 };
 use anyhow::{Context, Result, anyhow};
 use reqwest::Client;
@@ -19,6 +19,7 @@ pub struct ClaudeApiClient {
     client: Client,
     auth: ClaudeAuth,
     base_url: String,
+    model: String,
 }
 
 impl ClaudeApiClient {
@@ -41,6 +42,7 @@ impl ClaudeApiClient {
             client,
             auth: auth.clone(),
             base_url: auth.base_url,
+            model: DEFAULT_MODEL.to_string(),
         })
     }
 
@@ -60,15 +62,27 @@ impl ClaudeApiClient {
             client,
             base_url: auth.base_url.clone(),
             auth,
+            model: DEFAULT_MODEL.to_string(),
         })
     }
 
-    /// Send a single message and get a complete response
-    pub async fn send_message(
+    /// Send requests to `model` instead of the default
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Request for the configured model with optional context and the new user message
+    fn build_request(
         &self,
         message: &str,
         context: Option<&[ClaudeMessage]>,
-    ) -> Result<String> {
+        stream: bool,
+    ) -> ClaudeRequest {
         let mut messages = Vec::new();
 
         // Add context messages if provided
@@ -79,12 +93,21 @@ impl ClaudeApiClient {
         // Add the user message
         messages.push(ClaudeMessage::user(message.to_string()));
 
-        let request = ClaudeRequest {
+        ClaudeRequest {
+            model: self.model.clone(),
             messages,
-            stream: Some(false),
+            stream: Some(stream),
             ..Default::default()
-        };
+        }
+    }
 
+    /// Send a single message and get a complete response
+    pub async fn send_message(
+        &self,
+        message: &str,
+        context: Option<&[ClaudeMessage]>,
+    ) -> Result<String> {
+        let request = self.build_request(message, context, false);
         let response = self.send_request(&request).await?;
 
         // Extract the text content from the response
@@ -101,22 +124,7 @@ impl ClaudeApiClient {
         message: &str,
         context: Option<&[ClaudeMessage]>,
     ) -> Result<ClaudeStreamingResponse> {
-        let mut messages = Vec::new();
-
-        // Add context messages if provided
-        if let Some(context_msgs) = context {
-            messages.extend(context_msgs.iter().cloned());
-        }
-
-        // Add the user message
-        messages.push(ClaudeMessage::user(message.to_string()));
-
-        let request = ClaudeRequest {
-            messages,
-            stream: Some(true),
-            ..Default::default()
-        };
-
+        let request = self.build_request(message, context, true);
         self.stream_request(&request).await
     }
 
@@ -226,13 +234,13 @@ impl ClaudeApiClient {
         ])
     }
 
-    /// Load authentication from agents-in-a-box config files
+    /// Load authentication from the active auth profile's files
     pub fn load_auth_from_config() -> Result<ClaudeAuth> {
-        let home_dir =
-            dirs::home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
+        let profiles = crate::config::AuthProfiles::in_home_dir()
+            .ok_or_else(|| anyhow!("Could not determine home directory"))?;
 
-        // Try API key from .env file first
-        let env_file = home_dir.join(".agents-in-a-box/.env");
+        // Try API key from the active profile's .env file first
+        let env_file = profiles.active_env_file();
         if env_file.exists() {
             if let Ok(contents) = std::fs::read_to_string(&env_file) {
                 for line in contents.lines() {
@@ -245,7 +253,7 @@ impl ClaudeApiClient {
         }
 
         // Try OAuth credentials
-        let auth_dir = home_dir.join(".agents-in-a-box/auth");
+        let auth_dir = profiles.active_dir();
         let credentials_file = auth_dir.join(".credentials.json");
         let claude_json_file = auth_dir.join(".claude.json");
 
@@ -296,6 +304,11 @@ impl ClaudeChatManager {
         }
     }
 
+    /// Model the chat client sends requests to
+    pub fn model(&self) -> &str {
+        self.client.model()
+    }
+
     pub fn create_session(&mut self, session_id: Option<Uuid>) -> Uuid {
         let id = session_id.unwrap_or_else(Uuid::new_v4);
         let session = ClaudeChatSession::new(id);
@@ -391,3 +404,25 @@ impl ClaudeChatManager {
         self.sessions.remove(&session_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_model_is_sent_in_requests() {
+        let client = ClaudeApiClient::with_auth(ClaudeAuth::from_api_key("sk-test".to_string()))
+            .unwrap();
+        assert_eq!(client.model(), DEFAULT_MODEL);
+
+        let client = client.with_model("claude-opus-4-1");
+        let context = [ClaudeMessage::user("earlier".to_string())];
+        for stream in [false, true] {
+            let request = client.build_request("hello", Some(&context), stream);
+            let payload = serde_json::to_value(&request).unwrap();
+            assert_eq!(payload["model"], "claude-opus-4-1");
+            assert_eq!(payload["stream"], stream);
+            assert_eq!(payload["messages"].as_array().unwrap().len(), 2);
+        }
+    }
+}
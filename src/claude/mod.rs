@@ -6,4 +6,4 @@ pub mod streaming;
 pub mod types;
 
 pub use client::ClaudeApiClient;
-pub use types::ClaudeMessage;
+pub use types::{ClaudeAuth, ClaudeMessage, DEFAULT_MODEL};
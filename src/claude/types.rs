@@ -4,6 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Model used by the chat client when none is configured
+pub const DEFAULT_MODEL: &str = "claude-3-5-sonnet-20241022";
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ClaudeRole {
     #[serde(rename = "user")]
@@ -18,6 +21,9 @@ pub struct ClaudeMessage {
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// Reply stopped by the user before it finished
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub interrupted: bool,
 }
 
 impl ClaudeMessage {
@@ -26,6 +32,7 @@ impl ClaudeMessage {
             role: ClaudeRole::User,
             content,
             timestamp: Some(chrono::Utc::now()),
+            interrupted: false,
         }
     }
 
@@ -34,6 +41,7 @@ impl ClaudeMessage {
             role: ClaudeRole::Assistant,
             content,
             timestamp: Some(chrono::Utc::now()),
+            interrupted: false,
         }
     }
 }
@@ -54,7 +62,7 @@ pub struct ClaudeRequest {
 impl Default for ClaudeRequest {
     fn default() -> Self {
         Self {
-            model: "claude-3-5-sonnet-20241022".to_string(),
+            model: DEFAULT_MODEL.to_string(),
             messages: Vec::new(),
             max_tokens: 4096,
             system: None,
@@ -107,7 +115,11 @@ pub enum ClaudeStreamingEvent {
     #[serde(rename = "content_block_stop")]
     ContentBlockStop { index: u32 },
     #[serde(rename = "message_delta")]
-    MessageDelta { delta: ClaudeStreamingMessageDelta },
+    MessageDelta {
+        delta: ClaudeStreamingMessageDelta,
+        #[serde(default)]
+        usage: Option<ClaudeDeltaUsage>,
+    },
     #[serde(rename = "message_stop")]
     MessageStop,
     #[serde(rename = "error")]
@@ -140,6 +152,12 @@ pub struct ClaudeStreamingMessageDelta {
     pub stop_reason: Option<String>,
 }
 
+/// Output tokens so far, reported with `message_delta`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClaudeDeltaUsage {
+    pub output_tokens: u32,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ClaudeApiError {
     pub message: String,
@@ -0,0 +1,112 @@
+// ABOUTME: Converts ANSI-colored log text into styled ratatui spans
+// SGR parsing is left to ansi-to-tui, as for the tmux preview; this keeps each line on one row
+
+use ansi_to_tui::IntoText;
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+/// Spans kept per line; the rest of a pathological line continues in the last span
+pub const MAX_SPANS_PER_LINE: usize = 64;
+
+/// Lines longer than this (in bytes) are stripped rather than interpreted
+const MAX_INTERPRETED_BYTES: usize = 8192;
+
+/// Styled spans for one line of log text. Plain text comes back as a single span;
+/// newlines are shown as spaces, as the line is rendered on one row.
+pub fn ansi_line_spans(text: &str) -> Vec<Span<'static>> {
+    if !text.contains('\x1b') {
+        return vec![Span::raw(text.to_string())];
+    }
+    if text.len() > MAX_INTERPRETED_BYTES {
+        return vec![Span::raw(crate::app::log_export::strip_ansi(text))];
+    }
+
+    let text = text.replace(['\n', '\r'], " ");
+    let Ok(styled) = text.into_text() else {
+        return vec![Span::raw(crate::app::log_export::strip_ansi(&text))];
+    };
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for span in styled.lines.into_iter().flat_map(|line| line.spans) {
+        let style = without_resets(span.style);
+        let at_cap = spans.len() >= MAX_SPANS_PER_LINE;
+        match spans.last_mut() {
+            Some(last) if last.style == style || at_cap => {
+                last.content.to_mut().push_str(&span.content);
+            }
+            _ => spans.push(Span::styled(span.content, style)),
+        }
+    }
+    spans
+}
+
+/// A reset (`ESC[0m`) comes back as explicit `Color::Reset` values; treat it as no style,
+/// so reset text picks up the log view's own styling like unescaped text does
+fn without_resets(style: Style) -> Style {
+    let mut clean = Style::default().add_modifier(style.add_modifier);
+    if let Some(fg) = style.fg.filter(|color| *color != Color::Reset) {
+        clean = clean.fg(fg);
+    }
+    if let Some(bg) = style.bg.filter(|color| *color != Color::Reset) {
+        clean = clean.bg(bg);
+    }
+    clean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Modifier;
+
+    fn described(text: &str) -> Vec<(String, Option<Color>)> {
+        ansi_line_spans(text)
+            .into_iter()
+            .map(|span| (span.content.to_string(), span.style.fg))
+            .collect()
+    }
+
+    #[test]
+    fn test_sgr_colors_become_span_styles() {
+        assert_eq!(
+            described("\x1b[31m- removed\x1b[0m \x1b[32m+ added\x1b[0m"),
+            vec![
+                ("- removed".to_string(), Some(Color::Red)),
+                (" ".to_string(), None),
+                ("+ added".to_string(), Some(Color::Green)),
+            ]
+        );
+        assert_eq!(
+            described("\x1b[38;5;208morange\x1b[38;2;1;2;3mrgb\x1b[94mbright"),
+            vec![
+                ("orange".to_string(), Some(Color::Indexed(208))),
+                ("rgb".to_string(), Some(Color::Rgb(1, 2, 3))),
+                ("bright".to_string(), Some(Color::LightBlue)),
+            ]
+        );
+
+        let bold = ansi_line_spans("\x1b[1;33mwarning:\x1b[0m done");
+        assert!(bold[0].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(bold[1].content, " done");
+        assert_eq!(bold[1].style, Style::default());
+    }
+
+    #[test]
+    fn test_plain_and_wide_text_pass_through() {
+        assert_eq!(described("no colors here"), vec![("no colors here".to_string(), None)]);
+        assert_eq!(
+            described("\x1b[36m✅ 完了\x1b[0m"),
+            vec![("✅ 完了".to_string(), Some(Color::Cyan))]
+        );
+    }
+
+    #[test]
+    fn test_span_count_is_capped() {
+        let line: String = (0..500).map(|i| format!("\x1b[3{}mx", i % 2 + 1)).collect();
+        let spans = ansi_line_spans(&line);
+        assert_eq!(spans.len(), MAX_SPANS_PER_LINE);
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, "x".repeat(500));
+    }
+}
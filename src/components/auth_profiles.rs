@@ -0,0 +1,87 @@
+// ABOUTME: Popup for switching the auth profile whose credentials new sessions mount
+
+use ratatui::{
+    prelude::*,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::app::state::AuthProfilePickerState;
+use crate::config::Theme;
+
+const GOLD: Color = Color::Rgb(255, 215, 0);
+const CORNFLOWER_BLUE: Color = Color::Rgb(100, 149, 237);
+const SELECTION_GREEN: Color = Color::Rgb(100, 200, 100);
+
+pub struct AuthProfilesComponent;
+
+impl AuthProfilesComponent {
+    pub fn render(frame: &mut Frame, area: Rect, picker: &AuthProfilePickerState, theme: &Theme) {
+        frame.render_widget(Clear, area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let lines: Vec<Line> = picker
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let selected = index == picker.selected;
+                let style = if selected {
+                    Style::default()
+                        .fg(theme.selection)
+                        .bg(theme.selection_bg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let mut spans = vec![
+                    Span::styled(if selected { " ▶ " } else { "   " }, style),
+                    Span::styled(name.clone(), style),
+                ];
+                if *name == picker.active {
+                    spans.push(Span::styled(" (active)", Style::default().fg(theme.muted)));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        // Keep the selection visible in long lists
+        let visible_rows = layout[0].height.saturating_sub(2) as usize;
+        let scroll = picker.selected.saturating_sub(visible_rows.saturating_sub(1));
+        let list = Paragraph::new(lines).scroll((scroll.min(u16::MAX as usize) as u16, 0)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(CORNFLOWER_BLUE))
+                .style(Style::default().bg(theme.background))
+                .title(Line::from(vec![
+                    Span::styled(" 👤 ", Style::default().fg(GOLD)),
+                    Span::styled(
+                        "Auth Profiles ",
+                        Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+                    ),
+                ])),
+        );
+        frame.render_widget(list, layout[0]);
+
+        let key = |label| {
+            Span::styled(label, Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD))
+        };
+        let instructions = Paragraph::new(Line::from(vec![
+            key("j/k"),
+            Span::styled(" select  ", Style::default().fg(theme.muted)),
+            key("Enter"),
+            Span::styled(" switch  ", Style::default().fg(theme.muted)),
+            key("Esc"),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ]))
+        .style(Style::default().bg(theme.background))
+        .alignment(Alignment::Center);
+        frame.render_widget(instructions, layout[1]);
+    }
+}
@@ -59,7 +59,9 @@ impl AuthSetupComponent {
         frame.render_widget(subtitle, chunks[1]);
 
         // Render based on state
-        if auth_state.is_processing {
+        if auth_state.validating {
+            self.render_validating(frame, chunks[2]);
+        } else if auth_state.is_processing {
             self.render_processing(frame, chunks[2]);
         } else if auth_state.selected_method == AuthMethod::ApiKey
             && !auth_state.api_key_input.is_empty()
@@ -96,7 +98,11 @@ impl AuthSetupComponent {
                 AuthMethod::ApiKey if auth_state.api_key_input.is_empty() => {
                     "↑/↓: Navigate • Enter: Select • Esc: Cancel"
                 }
-                AuthMethod::ApiKey => "Enter: Save API Key • Esc: Back",
+                AuthMethod::ApiKey if auth_state.validating => "",
+                AuthMethod::ApiKey if auth_state.skip_validation => {
+                    "Enter: Save API Key • Tab: Check key online (off) • Esc: Back"
+                }
+                AuthMethod::ApiKey => "Enter: Save API Key • Tab: Skip online check • Esc: Back",
                 AuthMethod::Skip if !auth_state.is_processing => {
                     "↑/↓: Navigate • Enter: Select • Esc: Cancel"
                 }
@@ -180,6 +186,15 @@ impl AuthSetupComponent {
         frame.render_widget(input, chunks[1]);
     }
 
+    fn render_validating(&self, frame: &mut Frame, area: Rect) {
+        let validating = Paragraph::new("🔄 Validating API key with Anthropic...\n\nPlease wait...")
+            .style(Style::default().fg(Color::Cyan))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(validating, area);
+    }
+
     fn render_processing(&self, frame: &mut Frame, area: Rect) {
         let processing_msg = "🔄 Starting authentication container...\n\n\
                              Setting up OAuth authentication flow.\n\
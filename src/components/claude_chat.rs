@@ -28,11 +28,28 @@ impl ClaudeChatComponent {
     }
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        // Running token count, against the budget when one is set
+        let chat_state = state.claude_chat_state.as_ref();
+        let usage = chat_state
+            .map(|chat| match chat.token_budget {
+                Some(budget) => format!(" · {}/{} tokens", chat.total_tokens_used, budget),
+                None => format!(" · {} tokens", chat.total_tokens_used),
+            })
+            .unwrap_or_default();
+        let title_color = if chat_state.is_some_and(|chat| chat.budget_check().is_err()) {
+            Color::Red
+        } else {
+            Color::Yellow
+        };
+
         // Clear the popup area with a background
         let popup_block = Block::default()
             .borders(Borders::ALL)
-            .title(" Claude Chat - Press [ESC] to close ")
-            .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .title(format!(
+                " Claude Chat ({}){} - Press [ESC] to close ",
+                state.chat_model, usage
+            ))
+            .title_style(Style::default().fg(title_color).add_modifier(Modifier::BOLD))
             .border_style(Style::default().fg(Color::Cyan))
             .style(Style::default().bg(Color::Black));
 
@@ -166,7 +183,8 @@ impl ClaudeChatComponent {
             message.content.clone()
         };
 
-        let formatted = format!("{}{} {}", timestamp, icon, content);
+        let interrupted = if message.interrupted { " (interrupted)" } else { "" };
+        let formatted = format!("{}{} {}{}", timestamp, icon, content, interrupted);
 
         ListItem::new(formatted).style(Style::default().fg(color))
     }
@@ -181,10 +199,18 @@ impl ClaudeChatComponent {
         let is_streaming =
             state.claude_chat_state.as_ref().map(|s| s.is_streaming).unwrap_or(false);
 
+        let over_budget =
+            state.claude_chat_state.as_ref().is_some_and(|s| s.budget_check().is_err());
+
         let (title, border_color) = if is_streaming {
-            (" Input (Claude is responding...) ", Color::Yellow)
+            (" Input (Claude is responding... Esc to stop) ", Color::Yellow)
+        } else if over_budget {
+            (" Token budget reached (Ctrl+B raise, Ctrl+R reset) ", Color::Red)
         } else {
-            (" Type your message (Enter to send) ", Color::Gray)
+            (
+                " Type your message (Enter to send, Ctrl+N new conversation, Ctrl+E export) ",
+                Color::Gray,
+            )
         };
 
         let input_block = Block::default()
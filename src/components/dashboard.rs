@@ -0,0 +1,567 @@
+// ABOUTME: At-a-glance dashboard summarizing every session: status counts, token usage,
+// worktree disk use, sessions needing attention and the most recently active ones
+
+use chrono::{DateTime, Utc};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::components::live_logs_stream::LogEntry;
+use crate::models::{SessionStatus, TokenUsage, Workspace};
+
+// Premium color palette (TUI Style Guide)
+const CORNFLOWER_BLUE: Color = Color::Rgb(100, 149, 237);
+const GOLD: Color = Color::Rgb(255, 215, 0);
+const SELECTION_GREEN: Color = Color::Rgb(100, 200, 100);
+const WARNING_ORANGE: Color = Color::Rgb(255, 165, 0);
+const ERROR_RED: Color = Color::Rgb(230, 100, 100);
+const DARK_BG: Color = Color::Rgb(25, 25, 35);
+const PANEL_BG: Color = Color::Rgb(30, 30, 40);
+const LIST_HIGHLIGHT_BG: Color = Color::Rgb(40, 40, 60);
+const SOFT_WHITE: Color = Color::Rgb(220, 220, 230);
+const MUTED_GRAY: Color = Color::Rgb(120, 120, 140);
+const SUBDUED_BORDER: Color = Color::Rgb(60, 60, 80);
+
+/// Number of most-recently-active sessions listed
+const RECENT_SESSION_COUNT: usize = 5;
+
+/// One session row on the dashboard; Enter jumps to it in the session list
+#[derive(Debug, Clone)]
+pub struct DashboardItem {
+    pub session_id: Uuid,
+    pub workspace_name: String,
+    pub branch_name: String,
+    pub status: SessionStatus,
+    pub last_accessed: DateTime<Utc>,
+    pub last_log_line: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DashboardSummary {
+    pub running: usize,
+    pub idle: usize,
+    pub stopped: usize,
+    pub errored: usize,
+    pub usage: TokenUsage,
+    pub cost_usd: Option<f64>, // Only sessions whose agent's pricing is known; None if none are
+    pub worktree_disk_bytes: Option<u64>, // None until the background measurement finishes
+    pub attention: Vec<DashboardItem>,    // Errored sessions and idle ones waiting on a restart
+    pub recent: Vec<DashboardItem>,       // Most recently active first
+}
+
+impl DashboardSummary {
+    pub fn collect(
+        workspaces: &[Workspace],
+        live_logs: &HashMap<Uuid, Vec<LogEntry>>,
+        session_usage: &HashMap<Uuid, TokenUsage>,
+        worktree_disk_bytes: Option<u64>,
+    ) -> Self {
+        let mut summary = Self {
+            worktree_disk_bytes,
+            ..Self::default()
+        };
+        let mut all_items = Vec::new();
+
+        for workspace in workspaces {
+            for session in &workspace.sessions {
+                match session.status {
+                    SessionStatus::Running => summary.running += 1,
+                    SessionStatus::Idle => summary.idle += 1,
+                    SessionStatus::Stopped => summary.stopped += 1,
+                    SessionStatus::Error(_) => summary.errored += 1,
+                }
+                if let Some(usage) = session_usage.get(&session.id) {
+                    summary.usage.add(usage);
+                    if let Some(cost) = usage.cost_usd(session.agent_type) {
+                        *summary.cost_usd.get_or_insert(0.0) += cost;
+                    }
+                }
+
+                // Streamed logs first, then the tmux pane for interactive sessions
+                let last_log_line = live_logs
+                    .get(&session.id)
+                    .and_then(|logs| {
+                        logs.iter().rev().map(|entry| entry.message.trim()).find(|m| !m.is_empty())
+                    })
+                    .or_else(|| {
+                        session.preview_content.as_deref().and_then(|preview| {
+                            preview.lines().rev().map(str::trim).find(|l| !l.is_empty())
+                        })
+                    })
+                    .map(str::to_string);
+
+                all_items.push(DashboardItem {
+                    session_id: session.id,
+                    workspace_name: workspace.name.clone(),
+                    branch_name: session.branch_name.clone(),
+                    status: session.status.clone(),
+                    last_accessed: session.last_accessed,
+                    last_log_line,
+                });
+            }
+        }
+
+        all_items.sort_by_key(|item| std::cmp::Reverse(item.last_accessed));
+        summary.attention = all_items
+            .iter()
+            .filter(|item| matches!(item.status, SessionStatus::Error(_) | SessionStatus::Idle))
+            .cloned()
+            .collect();
+        summary.recent = all_items.into_iter().take(RECENT_SESSION_COUNT).collect();
+        summary
+    }
+
+    pub fn total_sessions(&self) -> usize {
+        self.running + self.idle + self.stopped + self.errored
+    }
+
+    /// Navigable rows in display order: attention first, then recent
+    pub fn items(&self) -> impl Iterator<Item = &DashboardItem> {
+        self.attention.iter().chain(self.recent.iter())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DashboardState {
+    pub summary: DashboardSummary,
+    pub selected_index: usize,
+}
+
+impl DashboardState {
+    pub fn new(summary: DashboardSummary) -> Self {
+        Self {
+            summary,
+            selected_index: 0,
+        }
+    }
+
+    /// Replace the summary on refresh, keeping the selection in range
+    pub fn update(&mut self, summary: DashboardSummary) {
+        self.summary = summary;
+        let count = self.summary.items().count();
+        self.selected_index = self.selected_index.min(count.saturating_sub(1));
+    }
+
+    pub fn next(&mut self) {
+        let count = self.summary.items().count();
+        if count > 0 {
+            self.selected_index = (self.selected_index + 1) % count;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let count = self.summary.items().count();
+        if count > 0 {
+            self.selected_index = (self.selected_index + count - 1) % count;
+        }
+    }
+
+    pub fn selected_session_id(&self) -> Option<Uuid> {
+        self.summary.items().nth(self.selected_index).map(|item| item.session_id)
+    }
+}
+
+/// Total size of the files under `path`, without following symlinks
+pub fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => directory_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=999_999 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.2}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+fn format_age(when: DateTime<Utc>) -> String {
+    let seconds = (Utc::now() - when).num_seconds().max(0);
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+pub struct DashboardComponent;
+
+impl DashboardComponent {
+    pub fn render(frame: &mut Frame, area: Rect, dashboard: &DashboardState) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CORNFLOWER_BLUE))
+            .title(Line::from(vec![
+                Span::styled(" 🏠 ", Style::default().fg(GOLD)),
+                Span::styled(
+                    "Dashboard ",
+                    Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+                ),
+            ]))
+            .style(Style::default().bg(DARK_BG));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4), // Summary cards
+                Constraint::Min(0),    // Session lists
+                Constraint::Length(1), // Key hints
+            ])
+            .split(inner);
+
+        Self::render_summary(frame, chunks[0], &dashboard.summary);
+        Self::render_sessions(frame, chunks[1], dashboard);
+
+        let hints = Paragraph::new(Line::from(vec![
+            Span::styled(
+                "j/k",
+                Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" select  ", Style::default().fg(MUTED_GRAY)),
+            Span::styled(
+                "Enter",
+                Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" go to session  ", Style::default().fg(MUTED_GRAY)),
+            Span::styled("r", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+            Span::styled(" refresh  ", Style::default().fg(MUTED_GRAY)),
+            Span::styled(
+                "Esc",
+                Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" back", Style::default().fg(MUTED_GRAY)),
+        ]))
+        .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(hints, chunks[2]);
+    }
+
+    fn render_summary(frame: &mut Frame, area: Rect, summary: &DashboardSummary) {
+        let cards = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(35),
+                Constraint::Percentage(25),
+            ])
+            .split(area);
+
+        let card = |title: &'static str| {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(SUBDUED_BORDER))
+                .title(Span::styled(title, Style::default().fg(CORNFLOWER_BLUE)))
+                .style(Style::default().bg(PANEL_BG))
+        };
+
+        let statuses = Paragraph::new(vec![
+            Line::from(vec![Span::styled(
+                format!("{} sessions", summary.total_sessions()),
+                Style::default().fg(SOFT_WHITE).add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(vec![
+                Span::styled(
+                    format!("● {} running  ", summary.running),
+                    Style::default().fg(SELECTION_GREEN),
+                ),
+                Span::styled(
+                    format!("○ {} idle  ", summary.idle),
+                    Style::default().fg(WARNING_ORANGE),
+                ),
+                Span::styled(
+                    format!("⏸ {} stopped  ", summary.stopped),
+                    Style::default().fg(MUTED_GRAY),
+                ),
+                Span::styled(
+                    format!("✗ {} errored", summary.errored),
+                    Style::default().fg(ERROR_RED),
+                ),
+            ]),
+        ])
+        .block(card(" Status "));
+        frame.render_widget(statuses, cards[0]);
+
+        let mut usage_spans = vec![Span::styled(
+            format!(
+                "{} in / {} out  ",
+                format_tokens(summary.usage.input_tokens),
+                format_tokens(summary.usage.output_tokens)
+            ),
+            Style::default().fg(MUTED_GRAY),
+        )];
+        if let Some(cost) = summary.cost_usd {
+            usage_spans.push(Span::styled(format!("~${:.2}", cost), Style::default().fg(GOLD)));
+        }
+        let usage = Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!("{} tokens", format_tokens(summary.usage.total_tokens())),
+                Style::default().fg(SOFT_WHITE).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(usage_spans),
+        ])
+        .block(card(" Usage "));
+        frame.render_widget(usage, cards[1]);
+
+        let disk_text = summary
+            .worktree_disk_bytes
+            .map_or_else(|| "measuring…".to_string(), format_bytes);
+        let disk = Paragraph::new(vec![
+            Line::from(Span::styled(
+                disk_text,
+                Style::default().fg(SOFT_WHITE).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                "in worktrees",
+                Style::default().fg(MUTED_GRAY),
+            )),
+        ])
+        .block(card(" Disk "));
+        frame.render_widget(disk, cards[2]);
+    }
+
+    fn render_sessions(frame: &mut Frame, area: Rect, dashboard: &DashboardState) {
+        let summary = &dashboard.summary;
+        let mut items = Vec::new();
+        // List rows include section headers, so map the selection onto the row index
+        let mut selected_row = None;
+        let mut item_index = 0;
+
+        let sections = [
+            (
+                "⚠ Needs attention",
+                &summary.attention,
+                "Nothing needs attention",
+            ),
+            ("🕒 Recently active", &summary.recent, "No sessions yet"),
+        ];
+        for (title, section_items, empty_text) in sections {
+            items.push(ListItem::new(Line::from(Span::styled(
+                title,
+                Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+            ))));
+            if section_items.is_empty() {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("  {}", empty_text),
+                    Style::default().fg(MUTED_GRAY),
+                ))));
+            }
+            for item in section_items {
+                if item_index == dashboard.selected_index {
+                    selected_row = Some(items.len());
+                }
+                item_index += 1;
+                items.push(Self::session_row(item));
+            }
+            items.push(ListItem::new(""));
+        }
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(SUBDUED_BORDER))
+                    .style(Style::default().bg(PANEL_BG)),
+            )
+            .highlight_style(Style::default().bg(LIST_HIGHLIGHT_BG))
+            .highlight_symbol("▶ ");
+
+        let mut list_state = ListState::default();
+        list_state.select(selected_row);
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+
+    fn session_row(item: &DashboardItem) -> ListItem<'static> {
+        let status_color = match item.status {
+            SessionStatus::Running => SELECTION_GREEN,
+            SessionStatus::Idle => WARNING_ORANGE,
+            SessionStatus::Stopped => MUTED_GRAY,
+            SessionStatus::Error(_) => ERROR_RED,
+        };
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                format!("  {} ", item.status.indicator()),
+                Style::default().fg(status_color),
+            ),
+            Span::styled(
+                format!("{}/", item.workspace_name),
+                Style::default().fg(MUTED_GRAY),
+            ),
+            Span::styled(
+                item.branch_name.clone(),
+                Style::default().fg(SOFT_WHITE).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {}", format_age(item.last_accessed)),
+                Style::default().fg(MUTED_GRAY),
+            ),
+        ])];
+
+        let detail = match (&item.status, &item.last_log_line) {
+            (SessionStatus::Error(message), _) => Some(message.clone()),
+            (_, Some(line)) => Some(line.clone()),
+            _ => None,
+        };
+        if let Some(detail) = detail {
+            let detail: String = detail.chars().take(120).collect();
+            lines.push(Line::from(Span::styled(
+                format!("      {}", detail),
+                Style::default().fg(MUTED_GRAY).add_modifier(Modifier::ITALIC),
+            )));
+        }
+
+        ListItem::new(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::live_logs_stream::LogEntryLevel;
+    use crate::models::{AgentType, Session};
+    use std::path::PathBuf;
+
+    fn session(branch: &str, status: SessionStatus, minutes_ago: i64) -> Session {
+        let mut session = Session::new(branch.to_string(), "/tmp/repo".to_string());
+        session.branch_name = branch.to_string();
+        session.status = status;
+        session.last_accessed = Utc::now() - chrono::Duration::minutes(minutes_ago);
+        session
+    }
+
+    #[test]
+    fn test_collect_summarizes_sessions() {
+        let mut workspace = Workspace::new("repo".to_string(), PathBuf::from("/tmp/repo"));
+        let running = session("running", SessionStatus::Running, 1);
+        let mut broken = session("broken", SessionStatus::Error("exit 1".to_string()), 30);
+        broken.agent_type = AgentType::Gemini;
+        let idle = session("idle", SessionStatus::Idle, 10);
+        let running_id = running.id;
+        let broken_id = broken.id;
+        workspace.add_session(running);
+        workspace.add_session(broken);
+        workspace.add_session(idle);
+        workspace.add_session(session("old", SessionStatus::Stopped, 600));
+
+        let mut live_logs = HashMap::new();
+        live_logs.insert(
+            running_id,
+            vec![
+                LogEntry::new(
+                    LogEntryLevel::Info,
+                    "c".to_string(),
+                    "Editing main.rs".to_string(),
+                ),
+                LogEntry::new(LogEntryLevel::Debug, "c".to_string(), "  ".to_string()),
+            ],
+        );
+        let mut usage = HashMap::new();
+        usage.insert(
+            running_id,
+            TokenUsage {
+                input_tokens: 1_000,
+                output_tokens: 500,
+                ..TokenUsage::default()
+            },
+        );
+        usage.insert(
+            broken_id,
+            TokenUsage {
+                input_tokens: 1_000,
+                ..TokenUsage::default()
+            },
+        );
+
+        let summary = DashboardSummary::collect(&[workspace], &live_logs, &usage, Some(2048));
+
+        assert_eq!(
+            (
+                summary.running,
+                summary.idle,
+                summary.stopped,
+                summary.errored
+            ),
+            (1, 1, 1, 1)
+        );
+        assert_eq!(summary.usage.total_tokens(), 2_500);
+        // Gemini pricing isn't known, so only the Claude session is costed
+        let cost = summary.cost_usd.unwrap();
+        assert!((cost - 0.0105).abs() < 1e-9, "cost was {}", cost);
+        assert_eq!(summary.worktree_disk_bytes, Some(2048));
+
+        let attention: Vec<&str> =
+            summary.attention.iter().map(|i| i.branch_name.as_str()).collect();
+        assert_eq!(attention, vec!["idle", "broken"]);
+        assert_eq!(summary.recent[0].branch_name, "running");
+        assert_eq!(
+            summary.recent[0].last_log_line.as_deref(),
+            Some("Editing main.rs")
+        );
+        assert_eq!(summary.items().count(), 6);
+    }
+
+    #[test]
+    fn test_selection_wraps_and_stays_in_range() {
+        let mut workspace = Workspace::new("repo".to_string(), PathBuf::from("/tmp/repo"));
+        workspace.add_session(session("a", SessionStatus::Running, 1));
+        workspace.add_session(session("b", SessionStatus::Running, 2));
+        let summary =
+            DashboardSummary::collect(&[workspace], &HashMap::new(), &HashMap::new(), None);
+
+        let mut dashboard = DashboardState::new(summary);
+        dashboard.previous();
+        assert_eq!(dashboard.selected_index, 1);
+        dashboard.next();
+        assert_eq!(dashboard.selected_index, 0);
+
+        dashboard.selected_index = 1;
+        dashboard.update(DashboardSummary::default());
+        assert_eq!(dashboard.selected_index, 0);
+        assert_eq!(dashboard.selected_session_id(), None);
+    }
+
+    #[test]
+    fn test_format_helpers() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+        assert_eq!(format_tokens(1_500), "1.5k");
+        assert_eq!(format_tokens(2_000_000), "2.00M");
+    }
+}
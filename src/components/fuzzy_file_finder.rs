@@ -3,9 +3,22 @@
 #![allow(dead_code)]
 
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
+/// Files considered per workspace; the walk stops here in very large repos
+const MAX_CANDIDATES: usize = 20_000;
+
+/// Matches listed for a query
+const MAX_MATCHES: usize = 50;
+
+/// Lines of the highlighted file shown in the preview pane
+pub const PREVIEW_LINES: usize = 20;
+
+/// Bytes read for the preview, and searched for a NUL to spot binary files
+const PREVIEW_BYTES: u64 = 16 * 1024;
+
 #[derive(Debug, Clone)]
 pub struct FileMatch {
     pub path: PathBuf,
@@ -21,6 +34,9 @@ pub struct FuzzyFileFinderState {
     pub selected_index: usize,
     pub at_symbol_position: usize, // Position of @ in the prompt
     pub workspace_root: Option<PathBuf>,
+    pub preview: Vec<String>, // First lines of the highlighted file
+    candidates: Vec<FileInfo>, // Files under `candidates_root`, kept for the next @
+    candidates_root: Option<PathBuf>,
 }
 
 impl FuzzyFileFinderState {
@@ -32,9 +48,14 @@ impl FuzzyFileFinderState {
             selected_index: 0,
             at_symbol_position: 0,
             workspace_root: None,
+            preview: Vec::new(),
+            candidates: Vec::new(),
+            candidates_root: None,
         }
     }
 
+    /// Start a search for the `@` at `at_position`. The workspace is only walked again
+    /// when it changed, so each further `@` in the prompt opens instantly.
     pub fn activate(&mut self, at_position: usize, workspace_root: Option<PathBuf>) {
         self.is_active = true;
         self.at_symbol_position = at_position;
@@ -43,6 +64,12 @@ impl FuzzyFileFinderState {
         self.matches.clear();
         self.selected_index = 0;
 
+        if self.candidates_root != self.workspace_root {
+            self.candidates =
+                self.workspace_root.as_deref().map(collect_candidates).unwrap_or_default();
+            self.candidates_root = self.workspace_root.clone();
+        }
+
         // Initial scan with empty query shows all files
         self.update_matches();
     }
@@ -52,6 +79,7 @@ impl FuzzyFileFinderState {
         self.query.clear();
         self.matches.clear();
         self.selected_index = 0;
+        self.preview.clear();
     }
 
     pub fn add_char_to_query(&mut self, ch: char) {
@@ -77,6 +105,7 @@ impl FuzzyFileFinderState {
             } else {
                 self.matches.len() - 1
             };
+            self.update_preview();
         }
     }
 
@@ -87,6 +116,7 @@ impl FuzzyFileFinderState {
             } else {
                 0
             };
+            self.update_preview();
         }
     }
 
@@ -99,9 +129,15 @@ impl FuzzyFileFinderState {
     }
 
     fn update_matches(&mut self) {
-        if let Some(ref root) = self.workspace_root {
-            self.matches = find_files_fuzzy(root, &self.query, 50); // Limit to 50 matches
-        }
+        self.matches = rank_candidates(&self.candidates, &self.query, MAX_MATCHES);
+        self.selected_index = 0;
+        self.update_preview();
+    }
+
+    fn update_preview(&mut self) {
+        self.preview = self.matches.get(self.selected_index).map_or_else(Vec::new, |file| {
+            read_preview(&file.path, PREVIEW_LINES)
+        });
     }
 }
 
@@ -111,36 +147,60 @@ impl Default for FuzzyFileFinderState {
     }
 }
 
-pub fn find_files_fuzzy(root: &Path, query: &str, limit: usize) -> Vec<FileMatch> {
-    let mut matches = Vec::new();
+/// Insert the chosen file after the `@` at byte `at_pos`
+///
+/// Returns the new text and the cursor offset after the reference. A space follows the
+/// reference unless whitespace already does, so the next `@` can be typed straight away.
+pub fn insert_reference(text: &str, at_pos: usize, relative_path: &str) -> (String, usize) {
+    let (before, after) = text.split_at(at_pos.min(text.len()));
+    let after = after.strip_prefix('@').unwrap_or(after);
+    let separator = if after.starts_with(char::is_whitespace) { "" } else { " " };
+    let reference = format!("@{}{}", relative_path, separator);
+    let cursor = before.len() + reference.len();
+    (format!("{}{}{}", before, reference, after), cursor)
+}
 
-    if !root.exists() || !root.is_dir() {
-        return matches;
+/// The first `max_lines` lines of a file, or a note when it can't be shown as text
+pub fn read_preview(path: &Path, max_lines: usize) -> Vec<String> {
+    let mut bytes = Vec::new();
+    let read = fs::File::open(path).map(|file| file.take(PREVIEW_BYTES));
+    if let Err(e) = read.and_then(|mut file| file.read_to_end(&mut bytes)) {
+        return vec![format!("(can't read file: {})", e)];
+    }
+    if bytes.contains(&0) {
+        return vec!["(binary file)".to_string()];
     }
+    String::from_utf8_lossy(&bytes).lines().take(max_lines).map(String::from).collect()
+}
 
+pub fn find_files_fuzzy(root: &Path, query: &str, limit: usize) -> Vec<FileMatch> {
     debug!("Searching for files in {:?} with query: '{}'", root, query);
+    rank_candidates(&collect_candidates(root), query, limit)
+}
 
-    // Collect all files first
-    let mut all_files = Vec::new();
-    collect_files_recursive(root, root, &mut all_files, 0, 5); // Max depth 5
-
-    // Filter and score files
-    for file_info in all_files {
-        let score = if query.is_empty() {
-            1 // Show all files when no query
-        } else {
-            calculate_fuzzy_score(&file_info.relative_path, query)
-        };
+/// Files under `root` that the finder offers: no hidden, build or binary files, nothing
+/// the repository's .gitignore excludes, and at most `MAX_CANDIDATES` of them
+fn collect_candidates(root: &Path) -> Vec<FileInfo> {
+    let mut files = Vec::new();
+    if root.is_dir() {
+        let repo = git2::Repository::discover(root).ok();
+        collect_files_recursive(root, root, repo.as_ref(), &mut files, 0, 5); // Max depth 5
+    }
+    files
+}
 
+/// Candidates matching `query`, best first
+fn rank_candidates(candidates: &[FileInfo], query: &str, limit: usize) -> Vec<FileMatch> {
+    let mut matches = Vec::new();
+    for file_info in candidates {
+        let score = calculate_fuzzy_score(&file_info.relative_path, query);
         if score > 0 {
             matches.push(FileMatch {
-                path: file_info.path,
-                relative_path: file_info.relative_path,
+                path: file_info.path.clone(),
+                relative_path: file_info.relative_path.clone(),
                 score,
             });
         }
-
-        // Note: Don't break early - we need to score all files to find the best matches
     }
 
     // Sort by score (higher is better) and then by path length (shorter is better)
@@ -155,7 +215,7 @@ pub fn find_files_fuzzy(root: &Path, query: &str, limit: usize) -> Vec<FileMatch
     matches
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FileInfo {
     path: PathBuf,
     relative_path: String,
@@ -164,6 +224,7 @@ struct FileInfo {
 fn collect_files_recursive(
     current_dir: &Path,
     root: &Path,
+    repo: Option<&git2::Repository>,
     files: &mut Vec<FileInfo>,
     depth: usize,
     max_depth: usize,
@@ -177,7 +238,13 @@ fn collect_files_recursive(
     };
 
     for entry in entries.flatten() {
+        if files.len() >= MAX_CANDIDATES {
+            return;
+        }
         let path = entry.path();
+        if repo.is_some_and(|repo| is_git_ignored(repo, &path)) {
+            continue;
+        }
 
         if path.is_file() {
             if let Ok(relative_path) = path.strip_prefix(root) {
@@ -195,13 +262,20 @@ fn collect_files_recursive(
             // Skip certain directories
             if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
                 if should_include_directory(dir_name) {
-                    collect_files_recursive(&path, root, files, depth + 1, max_depth);
+                    collect_files_recursive(&path, root, repo, files, depth + 1, max_depth);
                 }
             }
         }
     }
 }
 
+fn is_git_ignored(repo: &git2::Repository, path: &Path) -> bool {
+    let Some(relative) = repo.workdir().and_then(|workdir| path.strip_prefix(workdir).ok()) else {
+        return false;
+    };
+    repo.is_path_ignored(relative).unwrap_or(false)
+}
+
 fn should_include_file(relative_path: &str) -> bool {
     // Skip hidden files and certain extensions
     let path = Path::new(relative_path);
@@ -239,6 +313,10 @@ fn should_include_directory(dir_name: &str) -> bool {
     }
 }
 
+/// Subsequence score of `query` in `path`, 0 when its characters don't all appear in
+/// order. Contiguous matches rank first, ahead of scattered ones; among the rest, hits at
+/// the start of a path segment or word, runs of consecutive characters and hits in the
+/// file name score higher. Shorter paths break ties.
 fn calculate_fuzzy_score(path: &str, query: &str) -> usize {
     if query.is_empty() {
         return 1;
@@ -246,46 +324,42 @@ fn calculate_fuzzy_score(path: &str, query: &str) -> usize {
 
     let path_lower = path.to_lowercase();
     let query_lower = query.to_lowercase();
+    let length_bonus = 100 - path.len().min(100);
+    let name_start = path_lower.rfind('/').map_or(0, |slash| slash + 1);
 
-    // Exact match gets highest score
+    // Exact match gets highest score, higher still within the file name
     if path_lower.contains(&query_lower) {
-        return 1000 + (100 - path.len().min(100));
+        let in_name = path_lower[name_start..].contains(&query_lower);
+        return 1000 + if in_name { 100 } else { 0 } + length_bonus;
     }
 
-    // Fuzzy matching
-    let mut score = 0;
     let path_chars: Vec<char> = path_lower.chars().collect();
-    let query_chars: Vec<char> = query_lower.chars().collect();
-
-    let mut path_idx = 0;
-    let mut consecutive_matches = 0;
-
-    for query_char in query_chars {
-        let mut found = false;
-
-        // Look for the character in the remaining path
-        while path_idx < path_chars.len() {
-            if path_chars[path_idx] == query_char {
-                found = true;
-                score += 10 + consecutive_matches * 5; // Bonus for consecutive matches
-                consecutive_matches += 1;
-                path_idx += 1;
-                break;
-            } else {
-                consecutive_matches = 0;
-                path_idx += 1;
-            }
-        }
+    let name_start = path_chars.iter().rposition(|ch| *ch == '/').map_or(0, |slash| slash + 1);
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    let mut run = 0;
 
-        if !found {
+    for query_char in query_lower.chars() {
+        let Some(offset) = path_chars[next..].iter().position(|ch| *ch == query_char) else {
             return 0; // All characters must be found
+        };
+        let index = next + offset;
+
+        run = if previous.is_some_and(|last| last + 1 == index) { run + 1 } else { 0 };
+        score += 10 + run * 5; // Bonus for consecutive matches
+        if index == 0 || matches!(path_chars[index - 1], '/' | '_' | '-' | '.' | ' ') {
+            score += 15;
         }
+        if index >= name_start {
+            score += 5;
+        }
+        previous = Some(index);
+        next = index + 1;
     }
 
-    // Bonus for shorter paths
-    score += (100 - path.len().min(100)) / 10;
-
-    score
+    // Bonus for shorter paths; never outranks a contiguous match
+    (score + length_bonus / 10).min(999)
 }
 
 #[cfg(test)]
@@ -321,6 +395,105 @@ mod tests {
         assert_eq!(score, 1);
     }
 
+    #[test]
+    fn test_fuzzy_score_ranks_segment_starts_and_file_names() {
+        // Subsequences match across path segments
+        assert!(calculate_fuzzy_score("a/b/c.rs", "abc") > 0);
+        let score = calculate_fuzzy_score("a/b/c.rs", "abc");
+        assert!(score > calculate_fuzzy_score("xaxbxc.rs", "abc"));
+        assert_eq!(calculate_fuzzy_score("a/b/c.rs", "cba"), 0);
+
+        let candidates: Vec<FileInfo> = [
+            "docs/state/notes.md",
+            "src/app/state_tests.rs",
+            "src/app/state.rs",
+            "src/components/sample.rs",
+            "src/components/session_list.rs",
+        ]
+        .into_iter()
+        .map(|path| FileInfo { path: PathBuf::from(path), relative_path: path.to_string() })
+        .collect();
+        let ranked = |query: &str| -> Vec<String> {
+            rank_candidates(&candidates, query, 10)
+                .into_iter()
+                .map(|file| file.relative_path)
+                .collect()
+        };
+
+        // Contiguous hits in the file name first, shorter paths breaking ties
+        assert_eq!(
+            ranked("state"),
+            vec!["src/app/state.rs", "src/app/state_tests.rs", "docs/state/notes.md"]
+        );
+        // Word starts beat scattered letters: l starts a word in session_list only
+        assert_eq!(
+            ranked("sl"),
+            vec!["src/components/session_list.rs", "src/components/sample.rs"]
+        );
+        assert_eq!(ranked("").len(), candidates.len());
+    }
+
+    #[test]
+    fn test_insert_reference_keeps_each_at_and_continues_after_it() {
+        // Two references added one after the other, as a prompt is typed
+        let (text, cursor) = insert_reference("Compare @", 8, "src/main.rs");
+        assert_eq!(text, "Compare @src/main.rs ");
+        assert_eq!(cursor, text.len());
+
+        let text = format!("{}with @", text);
+        let at = text.rfind('@').unwrap();
+        let (text, cursor) = insert_reference(&text, at, "src/lib.rs");
+        assert_eq!(text, "Compare @src/main.rs with @src/lib.rs ");
+        assert_eq!(cursor, text.len());
+
+        // Inserted before existing text, which is already separated by a space
+        let (text, cursor) = insert_reference("Read @ first", 5, "README.md");
+        assert_eq!(text, "Read @README.md first");
+        assert_eq!(&text[cursor..], " first");
+
+        // Glued to the following word, a space is added
+        let (text, cursor) = insert_reference("@then explain", 0, "a.rs");
+        assert_eq!(text, "@a.rs then explain");
+        assert_eq!(&text[cursor..], "then explain");
+    }
+
+    #[test]
+    fn test_candidates_respect_gitignore_and_preview_first_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        git2::Repository::init(root).unwrap();
+        fs::write(root.join(".gitignore"), "generated/\n*.secret\n").unwrap();
+        fs::create_dir_all(root.join("generated")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("generated/out.rs"), "").unwrap();
+        fs::write(root.join("keys.secret"), "").unwrap();
+        let source: String = (1..=30).map(|i| format!("line {}\n", i)).collect();
+        fs::write(root.join("src/main.rs"), source).unwrap();
+        fs::write(root.join("logo.png"), [0x89, b'P', b'N', b'G', 0, 1]).unwrap();
+
+        let mut finder = FuzzyFileFinderState::new();
+        finder.activate(0, Some(root.to_path_buf()));
+        let mut paths: Vec<&str> =
+            finder.matches.iter().map(|file| file.relative_path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(paths, vec!["logo.png", "src/main.rs"]);
+
+        "main".chars().for_each(|ch| finder.add_char_to_query(ch));
+        assert_eq!(finder.preview.len(), PREVIEW_LINES);
+        assert_eq!(finder.preview[0], "line 1");
+        finder.query.clear();
+        finder.add_char_to_query('p');
+        finder.add_char_to_query('n');
+        assert_eq!(finder.get_selected_file().unwrap().relative_path, "logo.png");
+        assert_eq!(finder.preview, vec!["(binary file)"]);
+
+        // Another @ reuses the list without walking the workspace again
+        fs::write(root.join("src/new.rs"), "").unwrap();
+        finder.deactivate();
+        finder.activate(3, Some(root.to_path_buf()));
+        assert_eq!(finder.matches.len(), 2);
+    }
+
     #[test]
     fn test_should_include_file() {
         assert!(should_include_file("src/main.rs"));
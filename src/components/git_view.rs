@@ -32,10 +32,15 @@ const SUBDUED_BORDER: Color = Color::Rgb(60, 60, 80);      // Secondary borders
 
 // Status colors
 const PROGRESS_CYAN: Color = Color::Rgb(100, 200, 230);    // Loading/processing
-use std::collections::HashSet;
+use crate::git::diff_analyzer::{DiffAnalyzer, TouchedFile, WordChange, WordDiffLine};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::process::Command;
 use tracing::{debug, error};
 
+/// Larger diffs are truncated on copy, with the full diff written to a temp file
+const CLIPBOARD_DIFF_MAX_BYTES: usize = 512 * 1024;
+
 #[derive(Debug, Clone)]
 pub struct GitViewState {
     pub active_tab: GitTab,
@@ -43,9 +48,14 @@ pub struct GitViewState {
     pub selected_file_index: usize,
     pub diff_content: Vec<String>,
     pub diff_scroll_offset: usize,
+    pub word_diff: bool,                      // Highlight changed words instead of whole lines
+    pub word_diff_content: Option<Vec<WordDiffLine>>, // None = line mode, or the file is too large
+    pub show_blame: bool,                     // Author and commit gutter beside the Diff tab
+    pub blame_cache: HashMap<String, Vec<BlameLine>>, // Per path; empty = no blame (new/binary)
     pub worktree_path: PathBuf,
     pub is_dirty: bool,
     pub can_push: bool,
+    pub review_only: bool,                    // Detached review worktree: commit & push disabled
     pub commit_message_input: Option<String>, // None = not in commit mode, Some = commit message being entered
     pub commit_message_cursor: usize,         // Cursor position in commit message
     // File tree state
@@ -55,6 +65,106 @@ pub struct GitViewState {
     // Markdown viewer state
     pub markdown_content: Vec<MarkdownLine>,  // Rendered markdown lines
     pub markdown_scroll_offset: usize,
+    // Files touched over the session's lifetime (commits since the base plus working tree)
+    pub touched_files: Vec<TouchedFile>,
+    pub selected_touched_index: usize,
+    pub touched_base: Option<git2::Oid>,      // Commit the session branched off from
+    pub touched_diff_path: Option<String>,    // Diff tab shows this touched file since the base
+    pub stashes: Vec<String>,                 // This branch's `git stash list` lines, newest first
+    // Commits on the session branch since its base
+    pub commit_log_base: Option<String>,      // Base the session is compared with; None = default
+    pub commits: Vec<CommitLogEntry>,
+    pub commits_scroll_offset: usize,
+    pub selected_paths: HashSet<String>,      // Paths picked for the next commit; empty = commit all
+    pub merge_conflicts: Vec<PathBuf>,        // Files that would conflict when merging the base
+    pub merge_conflicts_base: String,
+    pub show_merge_conflicts: bool,           // Conflict banner expanded into the file list
+}
+
+/// Who last changed one line of a file, from `git blame --porcelain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    pub commit: String, // Short hash; all zeros for uncommitted lines
+    pub author: String,
+}
+
+/// Blame per final line (index 0 is line 1) from `git blame --porcelain` output
+pub fn parse_blame_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut authors: HashMap<&str, &str> = HashMap::new();
+    let mut lines: Vec<(usize, &str)> = Vec::new();
+    let mut current: Option<&str> = None;
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            current = None; // File content, ends the entry
+            continue;
+        }
+        if let Some(author) = line.strip_prefix("author ") {
+            if let Some(commit) = current {
+                authors.insert(commit, author);
+            }
+            continue;
+        }
+        // Entry header: <40-hex commit> <original line> <final line> [<group size>]
+        let mut fields = line.split(' ');
+        let (Some(commit), Some(final_line)) = (fields.next(), fields.nth(1)) else {
+            continue;
+        };
+        if commit.len() != 40 || !commit.bytes().all(|b| b.is_ascii_hexdigit()) {
+            continue;
+        }
+        if let Ok(final_line) = final_line.parse::<usize>() {
+            current = Some(commit);
+            lines.push((final_line, commit));
+        }
+    }
+
+    let mut blame = vec![
+        BlameLine { commit: String::new(), author: String::new() };
+        lines.iter().map(|(line, _)| *line).max().unwrap_or(0)
+    ];
+    for (final_line, commit) in lines {
+        if let Some(entry) = final_line.checked_sub(1).and_then(|i| blame.get_mut(i)) {
+            *entry = BlameLine {
+                commit: commit[..7].to_string(),
+                author: authors.get(commit).copied().unwrap_or_default().to_string(),
+            };
+        }
+    }
+    blame
+}
+
+/// Working-tree line number of each diff line; None for removed lines and headers
+pub fn diff_new_line_numbers(diff_lines: &[String]) -> Vec<Option<usize>> {
+    let mut next_line: Option<usize> = None;
+    diff_lines
+        .iter()
+        .map(|line| {
+            // Hunk headers read "@@ -a,b +c,d @@", possibly after the "Hunk: " label
+            if let Some(hunk) = line.find("@@ -").map(|i| &line[i + 4..]) {
+                next_line = hunk
+                    .split_once(" +")
+                    .and_then(|(_, new)| new.split([',', ' ']).next())
+                    .and_then(|start| start.parse().ok());
+                return None;
+            }
+            let number = next_line?;
+            match line.chars().next() {
+                Some('+' | ' ') => {
+                    next_line = Some(number + 1);
+                    Some(number)
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// One commit in the Commits tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitLogEntry {
+    pub hash: String,          // Abbreviated hash
+    pub subject: String,
+    pub relative_date: String, // e.g. "3 hours ago"
 }
 
 /// Represents an item in the file tree (either a folder or file)
@@ -99,6 +209,8 @@ pub enum GitTab {
     Files,
     Diff,
     Markdown, // Preview for .md files
+    Touched,  // Every file the session has changed, committed or not
+    Commits,  // Commits on the session branch since its base
 }
 
 #[derive(Debug, Clone)]
@@ -148,6 +260,10 @@ impl GitViewState {
             selected_file_index: 0,
             diff_content: Vec::new(),
             diff_scroll_offset: 0,
+            word_diff: false,
+            word_diff_content: None,
+            show_blame: false,
+            blame_cache: HashMap::new(),
             worktree_path,
             is_dirty: false,
             can_push: false,
@@ -160,6 +276,19 @@ impl GitViewState {
             // Markdown viewer state
             markdown_content: Vec::new(),
             markdown_scroll_offset: 0,
+            review_only: false,
+            touched_files: Vec::new(),
+            selected_touched_index: 0,
+            touched_base: None,
+            touched_diff_path: None,
+            stashes: Vec::new(),
+            commit_log_base: None,
+            commits: Vec::new(),
+            commits_scroll_offset: 0,
+            selected_paths: HashSet::new(),
+            merge_conflicts: Vec::new(),
+            merge_conflicts_base: String::new(),
+            show_merge_conflicts: false,
         };
         // Expand root by default
         state.expanded_folders.insert(String::new());
@@ -216,6 +345,7 @@ impl GitViewState {
 
         self.changed_files = changed_files;
         self.is_dirty = !self.changed_files.is_empty() || has_staged_changes;
+        self.blame_cache.clear(); // Line numbers may have shifted
 
         // Check if we can push (has commits ahead of remote)
         self.can_push = self.check_can_push(&repo)?;
@@ -223,6 +353,42 @@ impl GitViewState {
         // Build the file tree from changed files
         self.build_file_tree();
 
+        // Drop selections whose changes were committed, stashed or reverted meanwhile
+        let changed_files = &self.changed_files;
+        self.selected_paths.retain(|path| Self::is_changed_path(changed_files, path));
+
+        // A repository without a resolvable base simply has no touched list
+        if let Err(e) = self.refresh_touched_files(&repo) {
+            debug!("Could not determine files touched by session: {}", e);
+            self.touched_files.clear();
+            self.touched_base = None;
+        }
+
+        self.stashes = self.list_stashes().unwrap_or_else(|e| {
+            debug!("Could not list stashes: {}", e);
+            Vec::new()
+        });
+        self.commits = self.commit_log().unwrap_or_else(|e| {
+            debug!("Could not list commits since the base: {}", e);
+            Vec::new()
+        });
+        self.commits_scroll_offset =
+            self.commits_scroll_offset.min(self.commits.len().saturating_sub(1));
+
+        // Advisory dry-run merge so conflicts show up before push time
+        self.merge_conflicts_base = self.base_branch(&repo);
+        self.merge_conflicts = crate::git::worktree_manager::merge_conflicts(
+            &self.worktree_path,
+            &self.merge_conflicts_base,
+        )
+        .unwrap_or_else(|e| {
+            debug!("Could not check for conflicts with {}: {}", self.merge_conflicts_base, e);
+            Vec::new()
+        });
+        if self.merge_conflicts.is_empty() {
+            self.show_merge_conflicts = false;
+        }
+
         // Reset selection if needed
         if self.selected_tree_index >= self.file_tree_items.len() && !self.file_tree_items.is_empty() {
             self.selected_tree_index = 0;
@@ -237,13 +403,61 @@ impl GitViewState {
             // Also load markdown if it's an .md file
             self.load_markdown_if_applicable();
         } else {
-            self.diff_content.clear();
+            self.set_diff_content(Vec::new());
             self.markdown_content.clear();
         }
 
         Ok(())
     }
 
+    fn refresh_touched_files(&mut self, repo: &Repository) -> Result<()> {
+        let analyzer = DiffAnalyzer::new(&self.worktree_path)?;
+        let base = analyzer.session_base(&self.base_branch(repo))?;
+        self.touched_files = analyzer.files_touched(base)?;
+        self.touched_base = Some(base);
+        if self.selected_touched_index >= self.touched_files.len() {
+            self.selected_touched_index = 0;
+        }
+        Ok(())
+    }
+
+    pub fn next_touched_file(&mut self) {
+        if !self.touched_files.is_empty() {
+            self.selected_touched_index = (self.selected_touched_index + 1) % self.touched_files.len();
+        }
+    }
+
+    pub fn previous_touched_file(&mut self) {
+        if !self.touched_files.is_empty() {
+            self.selected_touched_index = self
+                .selected_touched_index
+                .checked_sub(1)
+                .unwrap_or(self.touched_files.len() - 1);
+        }
+    }
+
+    /// Show the selected touched file's diff since the session base in the Diff tab
+    pub fn open_selected_touched_file(&mut self) -> Result<()> {
+        let (Some(base), Some(file)) = (
+            self.touched_base,
+            self.touched_files.get(self.selected_touched_index),
+        ) else {
+            return Ok(());
+        };
+
+        let analyzer = DiffAnalyzer::new(&self.worktree_path)?;
+        let mut diff_content = Self::patch_lines(&analyzer.file_diff_since(base, &file.path)?)?;
+        if diff_content.is_empty() {
+            diff_content.push("No net change since the session started (edits were reverted)".to_string());
+        }
+
+        self.touched_diff_path = Some(file.path.clone());
+        self.set_diff_content(diff_content);
+        self.diff_scroll_offset = 0;
+        self.active_tab = GitTab::Diff;
+        Ok(())
+    }
+
     /// Build file tree from flat list of changed files
     fn build_file_tree(&mut self) {
         use std::collections::BTreeMap;
@@ -859,12 +1073,13 @@ impl GitViewState {
 
     pub fn refresh_diff_for_selected_file(&mut self) -> Result<()> {
         if self.changed_files.is_empty() {
-            self.diff_content.clear();
+            self.set_diff_content(Vec::new());
             return Ok(());
         }
 
         let selected_file = &self.changed_files[self.selected_file_index];
         debug!("Refreshing diff for file: {}", selected_file.path);
+        self.touched_diff_path = None;
 
         let repo = Repository::open(&self.worktree_path)?;
         let mut diff_content = Vec::new();
@@ -890,13 +1105,108 @@ impl GitViewState {
                         diff_content.push(format!("Error reading file: {}", e));
                     }
                 }
-                self.diff_content = diff_content;
+                self.set_diff_content(diff_content);
                 return Ok(());
             }
             _ => repo.diff_index_to_workdir(None, Some(&mut opts))?,
         };
 
-        // Format the diff
+        diff_content.extend(Self::patch_lines(&diff)?);
+
+        self.set_diff_content(diff_content);
+        self.diff_scroll_offset = 0; // Reset scroll when changing files
+
+        Ok(())
+    }
+
+    /// Replace the Diff tab's lines, recomputing the word highlighting when it's on
+    fn set_diff_content(&mut self, diff_content: Vec<String>) {
+        self.diff_content = diff_content;
+        self.update_word_diff();
+        self.update_blame();
+    }
+
+    /// Path of the file the Diff tab is showing
+    pub fn diff_path(&self) -> Option<&str> {
+        match self.touched_diff_path {
+            Some(ref path) => Some(path),
+            None => self.changed_files.get(self.selected_file_index).map(|f| f.path.as_str()),
+        }
+    }
+
+    /// Who last changed each line of `path` in the working tree. New and binary files
+    /// have no history to show, so they get no lines rather than an error.
+    pub fn blame(&self, path: &str) -> Result<Vec<BlameLine>> {
+        let is_new = self.touched_diff_path.is_none()
+            && self.changed_files.iter().any(|file| {
+                file.path == path
+                    && matches!(file.status, GitFileStatus::Added | GitFileStatus::Untracked)
+            });
+        let content = std::fs::read(self.worktree_path.join(path)).unwrap_or_default();
+        if is_new || content.iter().take(8000).any(|&b| b == 0) {
+            return Ok(Vec::new());
+        }
+        Ok(parse_blame_porcelain(&self.run_git(&["blame", "--porcelain", "--", path])?))
+    }
+
+    /// Blame the shown file once, so scrolling and reselecting it don't shell out again
+    fn update_blame(&mut self) {
+        if !self.show_blame {
+            return;
+        }
+        let Some(path) = self.diff_path().map(str::to_string) else {
+            return;
+        };
+        if !self.blame_cache.contains_key(&path) {
+            let blame = self.blame(&path).unwrap_or_else(|e| {
+                debug!("No blame for {}: {}", path, e);
+                Vec::new()
+            });
+            self.blame_cache.insert(path, blame);
+        }
+    }
+
+    /// Show or hide the Diff tab's author and commit gutter
+    pub fn toggle_blame(&mut self) {
+        self.show_blame = !self.show_blame;
+        self.update_blame();
+    }
+
+    /// Blame of the shown file, when the gutter is on and the file has any
+    pub fn diff_blame(&self) -> Option<&[BlameLine]> {
+        if !self.show_blame {
+            return None;
+        }
+        self.blame_cache.get(self.diff_path()?).map(Vec::as_slice).filter(|b| !b.is_empty())
+    }
+
+    fn update_word_diff(&mut self) {
+        self.word_diff_content = if self.word_diff {
+            crate::git::diff_analyzer::word_diff_patch(&self.diff_content)
+        } else {
+            None
+        };
+    }
+
+    /// Switch the Diff tab between line and word highlighting
+    pub fn toggle_word_diff(&mut self) {
+        self.word_diff = !self.word_diff;
+        self.update_word_diff();
+        self.diff_scroll_offset =
+            self.diff_scroll_offset.min(self.diff_line_count().saturating_sub(1));
+    }
+
+    /// Number of lines the Diff tab shows in the current highlighting mode
+    pub fn diff_line_count(&self) -> usize {
+        match self.word_diff_content {
+            Some(ref lines) => lines.len(),
+            None => self.diff_content.len(),
+        }
+    }
+
+    /// Format a diff as patch lines for the Diff tab
+    fn patch_lines(diff: &git2::Diff) -> Result<Vec<String>> {
+        let mut diff_content = Vec::new();
         diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
             let content = std::str::from_utf8(line.content()).unwrap_or("<binary>");
             let line_str = match line.origin() {
@@ -914,10 +1224,7 @@ impl GitViewState {
             true
         })?;
 
-        self.diff_content = diff_content;
-        self.diff_scroll_offset = 0; // Reset scroll when changing files
-
-        Ok(())
+        Ok(diff_content)
     }
 
     /// Navigate to the next item in the file tree
@@ -956,6 +1263,55 @@ impl GitViewState {
         }
     }
 
+    /// Whether `path` is a changed file, or lies inside a changed (untracked) directory
+    fn is_changed_path(changed_files: &[ChangedFile], path: &str) -> bool {
+        changed_files.iter().any(|file| {
+            let dir = file.path.trim_end_matches('/');
+            file.path == path || path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    /// Paths a tree item stands for when selecting: the item itself for files and
+    /// untracked directories, every changed file beneath it for other folders
+    fn selectable_paths(&self, item: &FileTreeItem) -> Vec<String> {
+        if !item.is_folder || item.status.is_some() {
+            return vec![item.full_path.clone()];
+        }
+        let prefix = format!("{}/", item.full_path);
+        self.changed_files
+            .iter()
+            .filter(|file| file.path.starts_with(&prefix))
+            .map(|file| file.path.clone())
+            .collect()
+    }
+
+    /// Checkbox for a tree item: `[x]` selected, `[~]` partly selected folder, `[ ]` otherwise
+    pub fn selection_marker(&self, item: &FileTreeItem) -> &'static str {
+        let paths = self.selectable_paths(item);
+        let selected = paths.iter().filter(|path| self.selected_paths.contains(*path)).count();
+        match selected {
+            0 => "[ ]",
+            n if n == paths.len() => "[x]",
+            _ => "[~]",
+        }
+    }
+
+    /// Add the selected tree item to the next commit, or remove it if already selected.
+    /// A folder is selected as a whole unless all of its files already are.
+    pub fn toggle_file_selection(&mut self) {
+        let Some(item) = self.file_tree_items.get(self.selected_tree_index) else {
+            return;
+        };
+        let paths = self.selectable_paths(item);
+        if paths.iter().all(|path| self.selected_paths.contains(path)) {
+            for path in &paths {
+                self.selected_paths.remove(path);
+            }
+        } else {
+            self.selected_paths.extend(paths);
+        }
+    }
+
     /// Toggle folder expansion/collapse
     pub fn toggle_folder(&mut self) {
         if let Some(item) = self.file_tree_items.get(self.selected_tree_index).cloned() {
@@ -1025,7 +1381,7 @@ impl GitViewState {
 
     /// Scroll diff down by N lines
     pub fn scroll_diff_down_by(&mut self, lines: usize) {
-        let max_offset = self.diff_content.len().saturating_sub(1);
+        let max_offset = self.diff_line_count().saturating_sub(1);
         self.diff_scroll_offset = (self.diff_scroll_offset + lines).min(max_offset);
     }
 
@@ -1053,7 +1409,9 @@ impl GitViewState {
     pub fn switch_tab(&mut self) {
         self.active_tab = match self.active_tab {
             GitTab::Files => GitTab::Diff,
-            GitTab::Diff => {
+            GitTab::Diff => GitTab::Touched,
+            GitTab::Touched => GitTab::Commits,
+            GitTab::Commits => {
                 // Only show Markdown tab if current file is a markdown file
                 if self.is_selected_markdown() && !self.markdown_content.is_empty() {
                     GitTab::Markdown
@@ -1109,6 +1467,202 @@ impl GitViewState {
         }
     }
 
+    /// Run git in the worktree, returning stdout or the trimmed stderr as the error
+    fn run_git(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git").args(args).current_dir(&self.worktree_path).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            return Err(anyhow::anyhow!(if stderr.is_empty() { stdout } else { stderr }));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Message tagging the stashes made here. Every worktree of a repository shares one
+    /// stash list, so each session only sees and pops the entries tagged with its branch.
+    fn stash_message(&self) -> Result<String> {
+        let branch = self.run_git(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+        Ok(format!("agents-box:{}", branch.trim()))
+    }
+
+    /// This branch's stash entries as printed by `git stash list`, newest first
+    pub fn list_stashes(&self) -> Result<Vec<String>> {
+        let suffix = format!(": {}", self.stash_message()?);
+        Ok(self
+            .run_git(&["stash", "list"])?
+            .lines()
+            .filter(|entry| entry.ends_with(&suffix))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Branch the session is compared against for commits, touched files and conflicts:
+    /// `commit_log_base` or the default base
+    fn base_branch(&self, repo: &Repository) -> String {
+        match self.commit_log_base {
+            Some(ref base) => base.clone(),
+            None => crate::git::worktree_manager::default_base_branch(repo),
+        }
+    }
+
+    pub fn toggle_merge_conflicts(&mut self) {
+        self.show_merge_conflicts = !self.show_merge_conflicts && !self.merge_conflicts.is_empty();
+    }
+
+    /// The newest 50 commits on HEAD that `commit_log_base` (or the repository's default
+    /// base branch) doesn't have. Empty when the branch has no commits of its own yet.
+    pub fn commit_log(&self) -> Result<Vec<CommitLogEntry>> {
+        let base = self.base_branch(&Repository::open(&self.worktree_path)?);
+        let range = format!("{}..HEAD", base);
+        let output = self.run_git(&["log", "--format=%h%x09%cr%x09%s", "-n", "50", &range])?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                Some(CommitLogEntry {
+                    hash: fields.next()?.to_string(),
+                    relative_date: fields.next()?.to_string(),
+                    subject: fields.next().unwrap_or_default().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    pub fn scroll_commits_up(&mut self) {
+        self.commits_scroll_offset = self.commits_scroll_offset.saturating_sub(1);
+    }
+
+    pub fn scroll_commits_down(&mut self) {
+        let max_offset = self.commits.len().saturating_sub(1);
+        self.commits_scroll_offset = (self.commits_scroll_offset + 1).min(max_offset);
+    }
+
+    /// Stash the working changes, untracked files included, and refresh the view
+    pub fn stash_changes(&mut self) -> Result<String> {
+        let message = self.stash_message()?;
+        let output = self.run_git(&["stash", "push", "--include-untracked", "-m", &message])?;
+        if output.contains("No local changes to save") {
+            return Err(anyhow::anyhow!("No local changes to stash"));
+        }
+        self.refresh_git_status()?;
+        Ok(output.lines().next().unwrap_or("Stashed changes").to_string())
+    }
+
+    /// Re-apply and drop this branch's newest stash. The view is refreshed even when the
+    /// pop fails, since a conflicting pop still changes the working tree.
+    pub fn pop_stash(&mut self) -> Result<String> {
+        let Some(entry) = self.list_stashes()?.into_iter().next() else {
+            return Err(anyhow::anyhow!("No stashed changes to restore"));
+        };
+        let stash_ref = entry.split(':').next().unwrap_or_default();
+        let result = self.run_git(&["stash", "pop", stash_ref]);
+        self.refresh_git_status()?;
+        result?;
+        Ok("Restored stashed changes".to_string())
+    }
+
+    /// Whether `path` is, or lies inside, an untracked entry of the status list
+    fn is_untracked(&self, path: &str) -> bool {
+        self.changed_files.iter().any(|file| {
+            file.status == GitFileStatus::Untracked
+                && Self::is_changed_path(std::slice::from_ref(file), path)
+        })
+    }
+
+    /// Changed paths under the selected tree item, the ones `discard_file` would reset
+    pub fn selected_discard_paths(&self) -> Vec<String> {
+        let Some(item) = self.file_tree_items.get(self.selected_tree_index) else {
+            return Vec::new();
+        };
+        self.selectable_paths(item)
+            .into_iter()
+            .filter(|path| Self::is_changed_path(&self.changed_files, path))
+            .collect()
+    }
+
+    /// How many of `paths` are untracked, and so deleted rather than restored on discard
+    pub fn untracked_count(&self, paths: &[String]) -> usize {
+        paths.iter().filter(|path| self.is_untracked(path)).count()
+    }
+
+    /// Reset one path to HEAD, staged changes included; an untracked path is deleted.
+    /// Only plain relative paths are accepted, so nothing outside the worktree is touched.
+    pub fn discard_file(&mut self, path: &str) -> Result<String> {
+        let inside_worktree = !path.is_empty()
+            && std::path::Path::new(path)
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)));
+        if !inside_worktree {
+            return Err(anyhow::anyhow!("Refusing to discard '{}' outside the worktree", path));
+        }
+        if self.is_untracked(path) {
+            self.run_git(&["clean", "-fd", "--", path])?;
+        } else {
+            self.run_git(&["restore", "--source=HEAD", "--staged", "--worktree", "--", path])?;
+        }
+        self.refresh_git_status()?;
+        Ok(format!("Discarded changes to {}", path))
+    }
+
+    /// Reset every tracked file to HEAD and delete untracked files and directories.
+    /// Ignored files are kept.
+    pub fn discard_all_changes(&mut self) -> Result<String> {
+        if !self.is_dirty {
+            return Err(anyhow::anyhow!("No changes to discard"));
+        }
+        let count = self.changed_files.len();
+        self.run_git(&["restore", "--source=HEAD", "--staged", "--worktree", "--", "."])?;
+        self.run_git(&["clean", "-fd"])?;
+        self.refresh_git_status()?;
+        Ok(format!("Discarded changes to {} files", count))
+    }
+
+    /// The worktree's `git diff` for the clipboard, cut at `max_bytes`. A truncated diff
+    /// ends with a note, and the full diff is written to a temp file whose path is returned.
+    fn clipboard_diff(
+        &self,
+        include_staged: bool,
+        max_bytes: usize,
+    ) -> Result<(String, Option<PathBuf>)> {
+        let patch = DiffAnalyzer::new(&self.worktree_path)?.working_tree_patch(include_staged)?;
+        if patch.is_empty() {
+            return Err(anyhow::anyhow!("No changes to copy"));
+        }
+        if patch.len() <= max_bytes {
+            return Ok((patch, None));
+        }
+
+        let full_path = self.diff_file_path();
+        std::fs::write(&full_path, &patch)?;
+
+        // Cut on a line boundary so the copied part is still a readable patch
+        let mut cut = max_bytes;
+        while !patch.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let cut = patch[..cut].rfind('\n').map_or(cut, |i| i + 1);
+        let text = format!(
+            "{}\n[diff truncated at {} of {} bytes; full diff in {}]\n",
+            &patch[..cut],
+            cut,
+            patch.len(),
+            full_path.display()
+        );
+        Ok((text, Some(full_path)))
+    }
+
+    /// The worktree's diff to copy, staged changes included when `include_staged`. Diffs over
+    /// `CLIPBOARD_DIFF_MAX_BYTES` are truncated, with the full diff's file returned alongside.
+    pub fn diff_to_copy(&self, include_staged: bool) -> Result<(String, Option<PathBuf>)> {
+        self.clipboard_diff(include_staged, CLIPBOARD_DIFF_MAX_BYTES)
+    }
+
+    /// Temp file a diff of this worktree is written to when it can't all go on the clipboard
+    pub fn diff_file_path(&self) -> PathBuf {
+        let name = self.worktree_path.file_name().unwrap_or_default().to_string_lossy();
+        std::env::temp_dir().join(format!("agents-in-a-box-{}.diff", name))
+    }
+
     pub fn commit_and_push(&mut self) -> Result<String> {
         // Get the commit message, or return error if not in commit mode
         let commit_message = match &self.commit_message_input {
@@ -1121,14 +1675,24 @@ impl GitViewState {
             }
         };
 
-        // Use the shared git operations function
-        let result =
-            crate::git::operations::commit_and_push_changes(&self.worktree_path, &commit_message);
+        // Use the shared git operations functions
+        let result = if self.selected_paths.is_empty() {
+            crate::git::operations::commit_and_push_changes(&self.worktree_path, &commit_message)
+        } else {
+            let mut paths: Vec<PathBuf> = self.selected_paths.iter().map(PathBuf::from).collect();
+            paths.sort();
+            crate::git::operations::commit_selected_and_push(
+                &self.worktree_path,
+                &commit_message,
+                &paths,
+            )
+        };
 
-        // Clear commit message input after successful commit
+        // Clear commit message input and selection after successful commit
         if result.is_ok() {
             self.commit_message_input = None;
             self.commit_message_cursor = 0;
+            self.selected_paths.clear();
         }
 
         result
@@ -1162,24 +1726,45 @@ impl GitViewComponent {
 
         // Render raised tab style - dynamically include Markdown tab if applicable
         let tab_titles: Vec<&str> = if git_state.is_selected_markdown() && !git_state.markdown_content.is_empty() {
-            vec!["Files", "Diff", "Markdown"]
+            vec!["Files", "Diff", "Touched", "Commits", "Markdown"]
         } else {
-            vec!["Files", "Diff"]
+            vec!["Files", "Diff", "Touched", "Commits"]
         };
 
         let selected_tab = match git_state.active_tab {
             GitTab::Files => 0,
             GitTab::Diff => 1,
-            GitTab::Markdown => if tab_titles.len() > 2 { 2 } else { 0 },
+            GitTab::Touched => 2,
+            GitTab::Commits => 3,
+            GitTab::Markdown => if tab_titles.len() > 4 { 4 } else { 0 },
         };
 
         Self::render_raised_tabs(frame, chunks[0], &tab_titles, selected_tab);
 
+        // Conflict banner sits above the tab content
+        let content_area = if git_state.merge_conflicts.is_empty() {
+            chunks[1]
+        } else {
+            let banner_height = if git_state.show_merge_conflicts {
+                git_state.merge_conflicts.len().min(8) as u16 + 3
+            } else {
+                3
+            };
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(banner_height), Constraint::Min(0)])
+                .split(chunks[1]);
+            Self::render_conflict_banner(frame, parts[0], git_state);
+            parts[1]
+        };
+
         // Render content based on active tab
         match git_state.active_tab {
-            GitTab::Files => Self::render_files_tab(frame, chunks[1], git_state),
-            GitTab::Diff => Self::render_diff_tab(frame, chunks[1], git_state),
-            GitTab::Markdown => Self::render_markdown_tab(frame, chunks[1], git_state),
+            GitTab::Files => Self::render_files_tab(frame, content_area, git_state),
+            GitTab::Diff => Self::render_diff_tab(frame, content_area, git_state),
+            GitTab::Markdown => Self::render_markdown_tab(frame, content_area, git_state),
+            GitTab::Touched => Self::render_touched_tab(frame, content_area, git_state),
+            GitTab::Commits => Self::render_commits_tab(frame, content_area, git_state),
         }
 
         // Render commit message input if in commit mode
@@ -1193,6 +1778,50 @@ impl GitViewComponent {
         }
     }
 
+    fn render_conflict_banner(frame: &mut Frame, area: Rect, git_state: &GitViewState) {
+        let count = git_state.merge_conflicts.len();
+        let summary = format!(
+            "Merging {} would conflict in {} file{}",
+            git_state.merge_conflicts_base,
+            count,
+            if count == 1 { "" } else { "s" }
+        );
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled("⚠️ ", Style::default().fg(WARNING_ORANGE)),
+            Span::styled(summary, Style::default().fg(WARNING_ORANGE).add_modifier(Modifier::BOLD)),
+        ])];
+        if git_state.show_merge_conflicts {
+            let shown = area.height.saturating_sub(3) as usize;
+            lines.extend(git_state.merge_conflicts.iter().take(shown).map(|path| {
+                Line::from(Span::styled(
+                    format!("   {}", path.display()),
+                    Style::default().fg(SOFT_WHITE),
+                ))
+            }));
+            if count > shown {
+                lines.push(Line::from(Span::styled(
+                    format!("   … and {} more", count - shown),
+                    Style::default().fg(MUTED_GRAY),
+                )));
+            }
+        }
+
+        let hint = if git_state.show_merge_conflicts { " hide files " } else { " list files " };
+        let banner = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(WARNING_ORANGE))
+                .style(Style::default().bg(PANEL_BG))
+                .title_bottom(Line::from(vec![
+                    Span::styled(" c", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                    Span::styled(hint, Style::default().fg(MUTED_GRAY)),
+                ])),
+        );
+        frame.render_widget(banner, area);
+    }
+
     /// Render tabs in classic raised tab style
     /// Active tab has a raised box that connects to content below
     fn render_raised_tabs(frame: &mut Frame, area: Rect, tabs: &[&str], selected: usize) {
@@ -1398,6 +2027,7 @@ impl GitViewComponent {
                     } else {
                         spans.insert(0, Span::raw("  "));
                     }
+                    spans.push(Self::selection_span(git_state, item));
                     spans.extend(status_prefix);
                     spans.extend(vec![
                         Span::styled(expand_symbol, Style::default().fg(expand_color)),
@@ -1442,6 +2072,7 @@ impl GitViewComponent {
                     }
                     spans.extend(vec![
                         Span::styled(indent.clone(), indent_style),
+                        Self::selection_span(git_state, item),
                         Span::styled(format!("[{}]", status.symbol()), status_style),
                         Span::raw(" "),
                         Span::raw(file_icon),
@@ -1548,6 +2179,12 @@ impl GitViewComponent {
         }
     }
 
+    fn selection_span<'a>(git_state: &GitViewState, item: &FileTreeItem) -> Span<'a> {
+        let marker = git_state.selection_marker(item);
+        let color = if marker == "[ ]" { SUBDUED_BORDER } else { GOLD };
+        Span::styled(format!("{} ", marker), Style::default().fg(color))
+    }
+
     fn render_diff_tab(frame: &mut Frame, area: Rect, git_state: &GitViewState) {
         if git_state.diff_content.is_empty() {
             let no_diff = Paragraph::new(vec![
@@ -1573,8 +2210,9 @@ impl GitViewComponent {
 
         // Calculate visible lines
         let content_height = area.height.saturating_sub(2) as usize; // Account for borders
-        let start_line = git_state.diff_scroll_offset;
-        let end_line = (start_line + content_height).min(git_state.diff_content.len());
+        let line_count = git_state.diff_line_count();
+        let start_line = git_state.diff_scroll_offset.min(line_count.saturating_sub(1));
+        let end_line = (start_line + content_height).min(line_count);
 
         // Diff colors (enhanced for visibility)
         let addition_color = Color::Rgb(100, 200, 100);    // Softer green
@@ -1582,36 +2220,92 @@ impl GitViewComponent {
         let hunk_color = PROGRESS_CYAN;
         let file_header_color = WARNING_ORANGE;
 
-        let visible_lines: Vec<Line> = git_state.diff_content[start_line..end_line]
-            .iter()
-            .map(|line| {
-                let style = if line.starts_with('+') && !line.starts_with("+++") {
-                    Style::default().fg(addition_color)
-                } else if line.starts_with('-') && !line.starts_with("---") {
-                    Style::default().fg(deletion_color)
-                } else if line.starts_with("@@") {
-                    Style::default().fg(hunk_color).add_modifier(Modifier::BOLD)
-                } else if line.starts_with("+++") || line.starts_with("---") {
-                    Style::default().fg(file_header_color).add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(SOFT_WHITE)
+        let plain_line = |line: &String| {
+            let style = if line.starts_with('+') && !line.starts_with("+++") {
+                Style::default().fg(addition_color)
+            } else if line.starts_with('-') && !line.starts_with("---") {
+                Style::default().fg(deletion_color)
+            } else if line.starts_with("@@") {
+                Style::default().fg(hunk_color).add_modifier(Modifier::BOLD)
+            } else if line.starts_with("+++") || line.starts_with("---") {
+                Style::default().fg(file_header_color).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(SOFT_WHITE)
+            };
+
+            Line::from(Span::styled(line.clone(), style))
+        };
+
+        let mut visible_lines: Vec<Line> = match git_state.word_diff_content {
+            Some(ref word_lines) => word_lines[start_line..end_line]
+                .iter()
+                .map(|word_line| match word_line {
+                    WordDiffLine::Plain(line) => plain_line(line),
+                    WordDiffLine::Words(spans) => {
+                        // Removed and added words share one line, marked with '~'
+                        let mut line_spans = vec![Span::styled("~", Style::default().fg(hunk_color))];
+                        line_spans.extend(spans.iter().map(|span| {
+                            let style = match span.change {
+                                WordChange::Unchanged => Style::default().fg(SOFT_WHITE),
+                                WordChange::Removed => Style::default()
+                                    .fg(deletion_color)
+                                    .add_modifier(Modifier::CROSSED_OUT),
+                                WordChange::Added => Style::default()
+                                    .fg(addition_color)
+                                    .add_modifier(Modifier::BOLD),
+                            };
+                            Span::styled(span.text.clone(), style)
+                        }));
+                        Line::from(line_spans)
+                    }
+                })
+                .collect(),
+            None => git_state.diff_content[start_line..end_line].iter().map(plain_line).collect(),
+        };
+
+        // The gutter follows working-tree line numbers, which word diff lines don't keep
+        let blame = git_state.diff_blame().filter(|_| git_state.word_diff_content.is_none());
+        if let Some(blame) = blame {
+            let numbers = diff_new_line_numbers(&git_state.diff_content);
+            for (line, number) in visible_lines.iter_mut().zip(&numbers[start_line..end_line]) {
+                let entry = number.and_then(|n| n.checked_sub(1)).and_then(|i| blame.get(i));
+                let gutter = match entry {
+                    Some(entry) if entry.commit.bytes().all(|b| b == b'0') => {
+                        format!("{:<18} ", "uncommitted")
+                    }
+                    Some(entry) => format!(
+                        "{:<10} {:<7} ",
+                        entry.author.chars().take(10).collect::<String>(),
+                        entry.commit
+                    ),
+                    None => " ".repeat(19),
                 };
+                line.spans.insert(0, Span::styled(gutter, Style::default().fg(MUTED_GRAY)));
+            }
+        }
 
-                Line::from(Span::styled(line.clone(), style))
-            })
-            .collect();
+        let mode_text = match (git_state.word_diff, &git_state.word_diff_content) {
+            (false, _) => "",
+            (true, Some(_)) => " word diff",
+            (true, None) => " word diff off: file too large",
+        };
+        let blame_text = match (git_state.show_blame, blame.is_some()) {
+            (false, _) => "",
+            (true, true) => " blame",
+            (true, false) if git_state.word_diff_content.is_some() => " blame off in word diff",
+            (true, false) => " no blame: new or binary file",
+        };
 
-        let selected_file_name = git_state
-            .changed_files
-            .get(git_state.selected_file_index)
-            .map(|f| f.path.as_str())
-            .unwrap_or("No file selected");
+        let selected_file_name = match git_state.touched_diff_path {
+            Some(ref path) => format!("{} (since session start)", path),
+            None => git_state
+                .changed_files
+                .get(git_state.selected_file_index)
+                .map(|f| f.path.clone())
+                .unwrap_or_else(|| "No file selected".to_string()),
+        };
 
-        let scroll_info = format!(
-            " [{}/{}]",
-            git_state.diff_scroll_offset + 1,
-            git_state.diff_content.len().max(1)
-        );
+        let scroll_info = format!(" [{}/{}]", start_line + 1, line_count.max(1));
 
         let diff_paragraph = Paragraph::new(visible_lines)
             .block(
@@ -1625,11 +2319,19 @@ impl GitViewComponent {
                         Span::styled("Diff: ", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
                         Span::styled(selected_file_name, Style::default().fg(SOFT_WHITE)),
                         Span::styled(scroll_info, Style::default().fg(MUTED_GRAY)),
+                        Span::styled(mode_text, Style::default().fg(PROGRESS_CYAN)),
+                        Span::styled(blame_text, Style::default().fg(PROGRESS_CYAN)),
                     ]))
                     .title_bottom(Line::from(vec![
                         Span::styled(" j/k", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
                         Span::styled(" scroll ", Style::default().fg(MUTED_GRAY)),
                         Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
+                        Span::styled(" w", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                        Span::styled(" word/line ", Style::default().fg(MUTED_GRAY)),
+                        Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
+                        Span::styled(" b", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                        Span::styled(" blame ", Style::default().fg(MUTED_GRAY)),
+                        Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
                         Span::styled(" Tab", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
                         Span::styled(" switch tab ", Style::default().fg(MUTED_GRAY)),
                     ]))
@@ -1639,6 +2341,140 @@ impl GitViewComponent {
         frame.render_widget(diff_paragraph, area);
     }
 
+    fn render_touched_tab(frame: &mut Frame, area: Rect, git_state: &GitViewState) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CORNFLOWER_BLUE))
+            .style(Style::default().bg(DARK_BG))
+            .title(Line::from(vec![
+                Span::styled(" 👣 ", Style::default().fg(GOLD)),
+                Span::styled("Files Touched ", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("({})", git_state.touched_files.len()),
+                    Style::default().fg(CORNFLOWER_BLUE).add_modifier(Modifier::BOLD)
+                ),
+            ]));
+
+        if git_state.touched_files.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(Span::styled("✨ Nothing touched yet", Style::default().fg(MUTED_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("Files changed in this session's commits or working tree appear here", Style::default().fg(MUTED_GRAY).add_modifier(Modifier::ITALIC))),
+            ])
+            .block(block)
+            .wrap(Wrap { trim: true });
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = git_state
+            .touched_files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let is_selected = index == git_state.selected_touched_index;
+                let mut spans = vec![if is_selected {
+                    Span::styled("▶ ", Style::default().fg(SELECTION_GREEN))
+                } else {
+                    Span::raw("  ")
+                }];
+                spans.push(Span::raw(Self::get_file_icon(&file.path)));
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    file.path.clone(),
+                    if is_selected {
+                        Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(SOFT_WHITE)
+                    },
+                ));
+                if file.commits > 0 {
+                    let label = if file.commits == 1 { "commit" } else { "commits" };
+                    spans.push(Span::styled(
+                        format!("  {} {}", file.commits, label),
+                        Style::default().fg(MUTED_GRAY),
+                    ));
+                }
+                if file.uncommitted {
+                    spans.push(Span::styled("  uncommitted", Style::default().fg(WARNING_ORANGE)));
+                }
+
+                let base_style = if is_selected {
+                    Style::default().bg(LIST_HIGHLIGHT_BG)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(spans)).style(base_style)
+            })
+            .collect();
+
+        let touched_list = List::new(items)
+            .block(block.title_bottom(Line::from(vec![
+                Span::styled(" Enter", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                Span::styled(" view diff ", Style::default().fg(MUTED_GRAY)),
+                Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
+                Span::styled(" Tab", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                Span::styled(" switch tab ", Style::default().fg(MUTED_GRAY)),
+            ])))
+            .highlight_style(Style::default().bg(LIST_HIGHLIGHT_BG));
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(git_state.selected_touched_index));
+
+        frame.render_stateful_widget(touched_list, area, &mut list_state);
+    }
+
+    fn render_commits_tab(frame: &mut Frame, area: Rect, git_state: &GitViewState) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CORNFLOWER_BLUE))
+            .style(Style::default().bg(DARK_BG))
+            .title(Line::from(vec![
+                Span::styled(" 🧾 ", Style::default().fg(GOLD)),
+                Span::styled("Commits ", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("({})", git_state.commits.len()),
+                    Style::default().fg(CORNFLOWER_BLUE).add_modifier(Modifier::BOLD)
+                ),
+            ]));
+
+        if git_state.commits.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(Span::styled("✨ No commits on this branch yet", Style::default().fg(MUTED_GRAY))),
+                Line::from(""),
+                Line::from(Span::styled("Commits made since the branch left its base appear here", Style::default().fg(MUTED_GRAY).add_modifier(Modifier::ITALIC))),
+            ])
+            .block(block)
+            .wrap(Wrap { trim: true });
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let lines: Vec<Line> = git_state
+            .commits
+            .iter()
+            .skip(git_state.commits_scroll_offset)
+            .map(|commit| {
+                Line::from(vec![
+                    Span::styled(format!(" {} ", commit.hash), Style::default().fg(WARNING_ORANGE)),
+                    Span::styled(commit.subject.clone(), Style::default().fg(SOFT_WHITE)),
+                    Span::styled(format!("  {}", commit.relative_date), Style::default().fg(MUTED_GRAY)),
+                ])
+            })
+            .collect();
+
+        let commits = Paragraph::new(lines).block(block.title_bottom(Line::from(vec![
+            Span::styled(" j/k", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+            Span::styled(" scroll ", Style::default().fg(MUTED_GRAY)),
+            Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
+            Span::styled(" Tab", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+            Span::styled(" switch tab ", Style::default().fg(MUTED_GRAY)),
+        ])));
+        frame.render_widget(commits, area);
+    }
+
     fn render_markdown_tab(frame: &mut Frame, area: Rect, git_state: &GitViewState) {
         if git_state.markdown_content.is_empty() {
             let no_content = Paragraph::new(vec![
@@ -1771,6 +2607,12 @@ impl GitViewComponent {
             Span::styled(after_cursor, Style::default().fg(SOFT_WHITE)),
         ]);
 
+        let scope = match git_state.selected_paths.len() {
+            0 => " (all changes) ".to_string(),
+            1 => " (1 selected file) ".to_string(),
+            n => format!(" ({} selected files) ", n),
+        };
+
         let input_paragraph = Paragraph::new(input_line)
             .block(
                 Block::default()
@@ -1781,6 +2623,7 @@ impl GitViewComponent {
                     .title(Line::from(vec![
                         Span::styled(" ✏️ ", Style::default().fg(GOLD)),
                         Span::styled("Commit Message", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                        Span::styled(scope, Style::default().fg(MUTED_GRAY)),
                     ]))
                     .title_bottom(Line::from(vec![
                         Span::styled(" Enter", Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD)),
@@ -1805,26 +2648,57 @@ impl GitViewComponent {
             ("✓", "Working directory clean".to_string(), SELECTION_GREEN)
         };
 
-        let (push_icon, push_text, push_color) = if git_state.can_push {
+        let (push_icon, push_text, push_color) = if git_state.review_only {
+            ("👁", "Review only (detached HEAD)", CORNFLOWER_BLUE)
+        } else if git_state.can_push {
             ("🚀", "Ready to push", SELECTION_GREEN)
         } else {
             ("✓", "Up to date", MUTED_GRAY)
         };
 
         // Build the status line with rich formatting
-        let status_line = Line::from(vec![
+        let mut spans = vec![
             Span::styled(format!(" {} ", status_icon), Style::default().fg(status_color)),
             Span::styled(&status_text, Style::default().fg(status_color)),
             Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)),
             Span::styled(format!("{} ", push_icon), Style::default().fg(push_color)),
             Span::styled(push_text, Style::default().fg(push_color)),
             Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)),
-            Span::styled("p", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
-            Span::styled(" push ", Style::default().fg(MUTED_GRAY)),
-            Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
-            Span::styled(" Esc", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
-            Span::styled(" back ", Style::default().fg(MUTED_GRAY)),
-        ]);
+        ];
+        if !git_state.stashes.is_empty() {
+            spans.push(Span::styled(
+                format!("📦 {} stashed  ", git_state.stashes.len()),
+                Style::default().fg(PROGRESS_CYAN),
+            ));
+            spans.push(Span::styled("│  ", Style::default().fg(SUBDUED_BORDER)));
+        }
+        if !git_state.selected_paths.is_empty() {
+            spans.push(Span::styled(
+                format!("☑ {} selected  ", git_state.selected_paths.len()),
+                Style::default().fg(GOLD),
+            ));
+            spans.push(Span::styled("│  ", Style::default().fg(SUBDUED_BORDER)));
+        }
+        spans.push(Span::styled("Space", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled(" select ", Style::default().fg(MUTED_GRAY)));
+        spans.push(Span::styled("s", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled(" stash ", Style::default().fg(MUTED_GRAY)));
+        spans.push(Span::styled("u", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled(" unstash ", Style::default().fg(MUTED_GRAY)));
+        spans.push(Span::styled("y", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled(" copy diff ", Style::default().fg(MUTED_GRAY)));
+        spans.push(Span::styled("│", Style::default().fg(SUBDUED_BORDER)));
+        // Review worktrees are read-only, so there are no discard or push controls to advertise
+        if !git_state.review_only {
+            spans.push(Span::styled("d/D", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)));
+            spans.push(Span::styled(" discard file/all ", Style::default().fg(MUTED_GRAY)));
+            spans.push(Span::styled("p", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)));
+            spans.push(Span::styled(" push ", Style::default().fg(MUTED_GRAY)));
+            spans.push(Span::styled("│", Style::default().fg(SUBDUED_BORDER)));
+        }
+        spans.push(Span::styled(" Esc", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled(" back ", Style::default().fg(MUTED_GRAY)));
+        let status_line = Line::from(spans);
 
         let status_paragraph = Paragraph::new(status_line)
             .block(
@@ -1839,3 +2713,299 @@ impl GitViewComponent {
         frame.render_widget(status_paragraph, area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn init_repo(path: &std::path::Path) -> Repository {
+        let repo = Repository::init(path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        fs::write(path.join("tracked.txt"), "original").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("tracked.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[]).unwrap();
+        drop(tree);
+        repo
+    }
+
+    #[test]
+    fn test_stash_and_pop_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let _repo = init_repo(temp_dir.path());
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+        git_state.refresh_git_status().unwrap();
+        assert!(git_state.stashes.is_empty());
+        assert!(git_state.stash_changes().is_err()); // Nothing to stash
+        assert!(git_state.pop_stash().is_err()); // Nothing to pop
+
+        fs::write(temp_dir.path().join("tracked.txt"), "edited").unwrap();
+        fs::write(temp_dir.path().join("new.txt"), "untracked").unwrap();
+        git_state.refresh_git_status().unwrap();
+        assert!(git_state.is_dirty);
+
+        git_state.stash_changes().unwrap();
+        assert!(!git_state.is_dirty);
+        assert_eq!(git_state.stashes.len(), 1);
+        assert!(!temp_dir.path().join("new.txt").exists());
+
+        git_state.pop_stash().unwrap();
+        assert!(git_state.is_dirty);
+        assert!(git_state.stashes.is_empty());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("tracked.txt")).unwrap(), "edited");
+        assert!(temp_dir.path().join("new.txt").exists());
+    }
+
+    #[test]
+    fn test_toggle_file_selection() {
+        let temp_dir = TempDir::new().unwrap();
+        let _repo = init_repo(temp_dir.path());
+        fs::write(temp_dir.path().join("tracked.txt"), "edited").unwrap();
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src").join("main.rs"), "fn main() {}").unwrap();
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+        git_state.expanded_folders.insert("src".to_string());
+        git_state.refresh_git_status().unwrap();
+
+        let tracked_index = git_state
+            .file_tree_items
+            .iter()
+            .position(|item| item.full_path == "tracked.txt")
+            .unwrap();
+        git_state.selected_tree_index = tracked_index;
+        git_state.toggle_file_selection();
+        assert_eq!(git_state.selected_paths, HashSet::from(["tracked.txt".to_string()]));
+        let item = git_state.file_tree_items[tracked_index].clone();
+        assert_eq!(git_state.selection_marker(&item), "[x]");
+
+        git_state.toggle_file_selection();
+        assert!(git_state.selected_paths.is_empty());
+        assert_eq!(git_state.selection_marker(&item), "[ ]");
+
+        // A selection whose change disappears is dropped on refresh
+        git_state.toggle_file_selection();
+        fs::write(temp_dir.path().join("tracked.txt"), "original").unwrap();
+        git_state.refresh_git_status().unwrap();
+        assert!(git_state.selected_paths.is_empty());
+    }
+
+    #[test]
+    fn test_commit_log_since_base() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = init_repo(temp_dir.path());
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+        git_state.commit_log_base = Some(base.clone());
+        git_state.refresh_git_status().unwrap();
+        assert!(git_state.commits.is_empty()); // No commits beyond the base yet
+
+        let initial = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &initial, false).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let mut parent = initial;
+        for subject in ["First change", "Second change"] {
+            let tree = parent.tree().unwrap();
+            let id = repo
+                .commit(Some("HEAD"), &signature, &signature, subject, &tree, &[&parent])
+                .unwrap();
+            parent = repo.find_commit(id).unwrap();
+        }
+
+        git_state.refresh_git_status().unwrap();
+        let subjects: Vec<&str> = git_state.commits.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Second change", "First change"]);
+        assert_eq!(git_state.commits[0].hash, parent.id().to_string()[..7]);
+        assert!(!git_state.commits[0].relative_date.is_empty());
+
+        git_state.scroll_commits_down();
+        git_state.scroll_commits_down();
+        assert_eq!(git_state.commits_scroll_offset, 1);
+
+        // A base that doesn't exist lists nothing rather than failing the refresh
+        git_state.commit_log_base = Some("no-such-base".to_string());
+        assert!(git_state.commit_log().is_err());
+        git_state.refresh_git_status().unwrap();
+        assert!(git_state.commits.is_empty());
+    }
+
+    #[test]
+    fn test_stashes_of_other_worktrees_are_left_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let _repo = init_repo(temp_dir.path());
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+
+        fs::write(temp_dir.path().join("tracked.txt"), "mine").unwrap();
+        git_state.stash_changes().unwrap();
+        // Another session's stash, made later and so on top of the shared list
+        fs::write(temp_dir.path().join("tracked.txt"), "theirs").unwrap();
+        git_state.run_git(&["stash", "push", "-m", "agents-box:other-branch"]).unwrap();
+
+        git_state.refresh_git_status().unwrap();
+        assert_eq!(git_state.stashes.len(), 1);
+        assert!(git_state.stashes[0].starts_with("stash@{1}: "));
+
+        git_state.pop_stash().unwrap();
+        assert_eq!(fs::read_to_string(temp_dir.path().join("tracked.txt")).unwrap(), "mine");
+        assert!(git_state.stashes.is_empty());
+        assert_eq!(git_state.run_git(&["stash", "list"]).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_pop_stash_conflict_keeps_stash() {
+        let temp_dir = TempDir::new().unwrap();
+        let _repo = init_repo(temp_dir.path());
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+
+        fs::write(temp_dir.path().join("tracked.txt"), "stashed edit").unwrap();
+        git_state.refresh_git_status().unwrap();
+        git_state.stash_changes().unwrap();
+
+        // A conflicting edit made after stashing blocks the pop and the stash survives
+        fs::write(temp_dir.path().join("tracked.txt"), "later edit").unwrap();
+        assert!(git_state.pop_stash().is_err());
+        assert_eq!(git_state.stashes.len(), 1);
+    }
+
+    #[test]
+    fn test_discard_file_and_all_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let _repo = init_repo(temp_dir.path());
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+        assert!(git_state.discard_all_changes().is_err()); // Nothing to discard
+
+        fs::write(temp_dir.path().join("tracked.txt"), "edited").unwrap();
+        fs::create_dir(temp_dir.path().join("scratch")).unwrap();
+        fs::write(temp_dir.path().join("scratch").join("notes.txt"), "untracked").unwrap();
+        git_state.refresh_git_status().unwrap();
+        assert_eq!(git_state.untracked_count(&["scratch/notes.txt".to_string()]), 1);
+
+        // Paths that could reach outside the worktree are refused
+        assert!(git_state.discard_file("../tracked.txt").is_err());
+        assert!(git_state.discard_file("/etc/hosts").is_err());
+
+        git_state.discard_file("tracked.txt").unwrap();
+        assert_eq!(fs::read_to_string(temp_dir.path().join("tracked.txt")).unwrap(), "original");
+        assert!(temp_dir.path().join("scratch").exists());
+        assert_eq!(git_state.changed_files.len(), 1);
+
+        fs::write(temp_dir.path().join("tracked.txt"), "edited again").unwrap();
+        git_state.refresh_git_status().unwrap();
+        git_state.discard_all_changes().unwrap();
+        assert_eq!(fs::read_to_string(temp_dir.path().join("tracked.txt")).unwrap(), "original");
+        assert!(!temp_dir.path().join("scratch").exists());
+        assert!(!git_state.is_dirty);
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let alice = "a".repeat(40);
+        let uncommitted = "0".repeat(40);
+        let output = format!(
+            "{alice} 1 1 2\n\
+             author Alice Example\n\
+             author-mail <alice@example.com>\n\
+             summary Initial\n\
+             filename src/lib.rs\n\
+             \tfn main() {{\n\
+             {alice} 2 2\n\
+             \t}}\n\
+             {uncommitted} 3 3 1\n\
+             author Not Committed Yet\n\
+             filename src/lib.rs\n\
+             \t// author 1 2 3\n"
+        );
+
+        let blame = parse_blame_porcelain(&output);
+        assert_eq!(blame.len(), 3);
+        let alice_line =
+            BlameLine { commit: "aaaaaaa".to_string(), author: "Alice Example".to_string() };
+        assert_eq!(blame[0], alice_line);
+        assert_eq!(blame[1], alice_line); // Repeat entries carry no author lines of their own
+        assert_eq!(blame[2].commit, "0000000");
+        assert_eq!(blame[2].author, "Not Committed Yet");
+        assert!(parse_blame_porcelain("").is_empty());
+    }
+
+    #[test]
+    fn test_diff_new_line_numbers() {
+        let lines: Vec<String> = [
+            "File: diff --git a/x b/x",
+            "Hunk: @@ -3,3 +3,4 @@ fn main",
+            " context",
+            "-removed",
+            "+added",
+            "+added too",
+            " context",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        assert_eq!(
+            diff_new_line_numbers(&lines),
+            vec![None, None, Some(3), None, Some(4), Some(5), Some(6)]
+        );
+    }
+
+    #[test]
+    fn test_blame_cached_and_skipped_for_new_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let _repo = init_repo(temp_dir.path());
+        let mut git_state = GitViewState::new(temp_dir.path().to_path_buf());
+        assert_eq!(git_state.blame("tracked.txt").unwrap()[0].author, "Test User");
+
+        fs::write(temp_dir.path().join("tracked.txt"), "edited\nmore").unwrap();
+        fs::write(temp_dir.path().join("new.txt"), "untracked").unwrap();
+        git_state.refresh_git_status().unwrap();
+        let blame = git_state.blame("tracked.txt").unwrap();
+        assert_eq!(blame.len(), 2);
+        assert_eq!(blame[1].commit, "0000000");
+        assert!(git_state.blame("new.txt").unwrap().is_empty());
+
+        // Turning the gutter on blames the shown file once; a refresh drops stale entries
+        git_state.toggle_blame();
+        assert!(git_state.blame_cache.contains_key(git_state.diff_path().unwrap()));
+        git_state.toggle_blame();
+        git_state.refresh_git_status().unwrap();
+        assert!(git_state.blame_cache.is_empty());
+    }
+
+    #[test]
+    fn test_clipboard_diff_matches_analyzer_patch() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = init_repo(temp_dir.path());
+        let git_state = GitViewState::new(temp_dir.path().to_path_buf());
+        assert!(git_state.clipboard_diff(true, CLIPBOARD_DIFF_MAX_BYTES).is_err());
+
+        fs::write(temp_dir.path().join("tracked.txt"), "edited").unwrap();
+        fs::write(temp_dir.path().join("staged.txt"), "staged").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+
+        let analyzer = DiffAnalyzer::new(temp_dir.path()).unwrap();
+        let (all, full_path) = git_state.clipboard_diff(true, CLIPBOARD_DIFF_MAX_BYTES).unwrap();
+        assert_eq!(all, analyzer.working_tree_patch(true).unwrap());
+        assert!(full_path.is_none());
+        assert!(all.contains("+++ b/staged.txt") && all.contains("+edited"));
+
+        let (unstaged, _) = git_state.clipboard_diff(false, CLIPBOARD_DIFF_MAX_BYTES).unwrap();
+        assert_eq!(unstaged, analyzer.working_tree_patch(false).unwrap());
+        assert!(!unstaged.contains("staged.txt") && unstaged.contains("+edited"));
+
+        // Over the limit the copy is cut on a line and the full diff goes to a file
+        let (truncated, full_path) = git_state.clipboard_diff(true, 40).unwrap();
+        let full_path = full_path.unwrap();
+        assert!(truncated.starts_with("diff --git"));
+        assert!(truncated.contains("[diff truncated at"));
+        assert_eq!(fs::read_to_string(&full_path).unwrap(), all);
+        fs::remove_file(full_path).unwrap();
+    }
+}
@@ -6,6 +6,8 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem},
 };
 
+use crate::config::{KeyAction, Keymap};
+
 pub struct HelpComponent;
 
 impl HelpComponent {
@@ -13,11 +15,16 @@ impl HelpComponent {
         Self
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    /// Remappable actions show their effective keys from the keymap
+    pub fn render(&self, frame: &mut Frame, area: Rect, keymap: &Keymap) {
         let popup_area = self.centered_rect(60, 80, area);
 
         frame.render_widget(Clear, popup_area);
 
+        let bound = |action: KeyAction| {
+            ListItem::new(format!("  {:<10} {}", keymap.label(action), action.description()))
+        };
+
         let help_items = vec![
             ListItem::new("Navigation:")
                 .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
@@ -27,37 +34,88 @@ impl HelpComponent {
             ListItem::new("  l/→        Next workspace"),
             ListItem::new("  g          Go to top"),
             ListItem::new("  G          Go to bottom"),
+            ListItem::new("  < / >      Resize session list (or Ctrl+←/→)"),
             ListItem::new(""),
             ListItem::new("Session Actions:")
                 .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            ListItem::new("  n          New session (current directory)"),
-            ListItem::new("  s          Search & select workspace"),
-            ListItem::new("  a          Attach to session"),
-            ListItem::new("  e          Restart stopped session"),
-            ListItem::new("  r          Re-authenticate credentials"),
-            ListItem::new("  d          Delete session"),
-            ListItem::new("  x          Cleanup orphaned containers"),
+            bound(KeyAction::NewSession),
+            bound(KeyAction::ReviewSession),
+            bound(KeyAction::SearchWorkspace),
+            bound(KeyAction::Attach),
+            bound(KeyAction::Restart),
+            bound(KeyAction::Reauthenticate),
+            bound(KeyAction::AuthProfiles),
+            bound(KeyAction::Logout),
+            bound(KeyAction::Delete),
+            bound(KeyAction::StopKeepWorktree),
+            bound(KeyAction::StopSession),
+            bound(KeyAction::CleanupOrphaned),
+            bound(KeyAction::CleanupDead),
+            bound(KeyAction::ToggleProtection),
+            bound(KeyAction::CycleSort),
+            bound(KeyAction::FilterSessions),
+            ListItem::new("  y          Copy & view boss prompt"),
+            bound(KeyAction::CopyContainerId),
+            bound(KeyAction::CopyWorktreePath),
             ListItem::new("  f          Refresh workspaces"),
+            bound(KeyAction::Dashboard),
+            bound(KeyAction::LogsViewer),
             ListItem::new(""),
             ListItem::new("Git Actions:")
                 .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            ListItem::new("  g          Show git view"),
-            ListItem::new("  p          Commit & push"),
+            bound(KeyAction::GitView),
+            bound(KeyAction::ExternalDiff),
+            bound(KeyAction::CompareLastRun),
+            bound(KeyAction::QuickCommit),
+            ListItem::new(""),
+            ListItem::new("Live Logs:")
+                .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            ListItem::new("  Space      Toggle follow newest lines (End jumps back)"),
+            ListItem::new("  t          Toggle timestamps"),
+            ListItem::new("  A          Toggle ANSI colors (off = plain text)"),
+            ListItem::new("  m          Bookmark current line"),
+            ListItem::new("  [ / ]      Previous / next bookmark"),
+            ListItem::new("  y          Copy tool call command"),
+            ListItem::new("  f          Filter lines (text or regex)"),
+            ListItem::new("  w          Export session logs to a file"),
+            ListItem::new("  b          Toggle line wrap (off = ←/→ scroll sideways)"),
+            ListItem::new("  m          Bookmark top line"),
+            ListItem::new("  [ / ]      Previous / next bookmark"),
+            ListItem::new(""),
+            ListItem::new("Logs Viewer (v):")
+                .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            ListItem::new("  /          Search (case-insensitive by default)"),
+            ListItem::new("  n / N      Next / previous match"),
+            ListItem::new("  c          Toggle case-sensitive search"),
+            ListItem::new("  b          Toggle line wrap (off = ←/→ scroll sideways)"),
+            ListItem::new("  m          Bookmark top line"),
+            ListItem::new("  [ / ]      Previous / next bookmark"),
             ListItem::new(""),
             ListItem::new("Views:")
                 .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
             ListItem::new("  Tab        Switch between views"),
+            bound(KeyAction::ClaudeChat),
+            bound(KeyAction::ExpandAll),
+            bound(KeyAction::NotificationHistory),
+            bound(KeyAction::DismissNotification),
             ListItem::new(""),
             ListItem::new("General:")
                 .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            ListItem::new("  ?          Toggle this help"),
-            ListItem::new("  q/Esc      Quit application"),
+            bound(KeyAction::ToggleHelp),
+            ListItem::new("  Esc        Close popup / back to session list"),
+            bound(KeyAction::Quit),
             ListItem::new("  Ctrl+C     Force quit"),
+            ListItem::new(""),
+            ListItem::new("Remap keys in ~/.agents-in-a-box/keys.toml (e.g. new_session = \"N\")")
+                .style(Style::default().fg(Color::DarkGray)),
         ];
 
         let help_list = List::new(help_items).block(
             Block::default()
-                .title("Help - Press ? or Esc to close")
+                .title(format!(
+                    "Help - Press {} or Esc to close",
+                    keymap.label(KeyAction::ToggleHelp)
+                ))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         );
@@ -1,10 +1,11 @@
 // ABOUTME: Main layout component handling split-pane arrangement and bottom menu bar
 
 use ratatui::{
+    layout::Position,
     prelude::*,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, BorderType, Paragraph},
+    widgets::{Block, Borders, BorderType, Clear, Paragraph, Wrap},
 };
 
 // Premium color palette (TUI Style Guide)
@@ -18,13 +19,26 @@ const SOFT_WHITE: Color = Color::Rgb(220, 220, 230);
 const MUTED_GRAY: Color = Color::Rgb(120, 120, 140);
 const SUBDUED_BORDER: Color = Color::Rgb(60, 60, 80);
 
+/// Narrowest the session list and logs pane get, whatever the configured ratio
+const MIN_SESSION_LIST_WIDTH: u16 = 24;
+const MIN_LOGS_PANE_WIDTH: u16 = 30;
+
 use super::{
     AttachedTerminalComponent, AuthSetupComponent, ClaudeChatComponent,
     ConfirmationDialogComponent, HelpComponent, LiveLogsStreamComponent, LogsViewerComponent,
     NewSessionComponent, NonGitNotificationComponent, SessionListComponent, TmuxPreviewPane,
 };
+use super::session_list::SessionListRow;
 use crate::app::{AppState, state::View};
 
+/// Where a mouse click landed on the main screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickTarget {
+    SessionList(Option<SessionListRow>), // None between or below the rows
+    Logs,
+    NewLogLines, // The live logs "new lines" indicator
+}
+
 pub struct LayoutComponent {
     session_list: SessionListComponent,
     logs_viewer: LogsViewerComponent,
@@ -37,6 +51,10 @@ pub struct LayoutComponent {
     attached_terminal: AttachedTerminalComponent,
     auth_setup: AuthSetupComponent,
     tmux_preview: TmuxPreviewPane,
+    // Panes as last drawn, for mouse hit-testing; empty outside the session list view
+    session_list_area: Rect,
+    logs_area: Rect,
+    new_log_lines_area: Rect,
 }
 
 impl LayoutComponent {
@@ -53,10 +71,37 @@ impl LayoutComponent {
             attached_terminal: AttachedTerminalComponent::new(),
             auth_setup: AuthSetupComponent::new(),
             tmux_preview: TmuxPreviewPane::new(),
+            session_list_area: Rect::default(),
+            logs_area: Rect::default(),
+            new_log_lines_area: Rect::default(),
         }
     }
 
     pub fn render(&mut self, frame: &mut Frame, state: &AppState) {
+        self.render_view(frame, state);
+
+        // Visual bell: invert the whole screen for the few frames the flash lasts
+        if state.alerter.is_flashing(std::time::Instant::now()) {
+            let area = frame.size();
+            frame
+                .buffer_mut()
+                .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+        }
+    }
+
+    fn render_view(&mut self, frame: &mut Frame, state: &AppState) {
+        self.session_list_area = Rect::default();
+        self.logs_area = Rect::default();
+        self.new_log_lines_area = Rect::default();
+
+        // Below the configured minimum the panes overlap and render garbage, so show
+        // a resize hint instead; normal rendering resumes once the terminal grows
+        if let Some(message) = terminal_too_small_message(frame.size(), state.min_terminal_size)
+        {
+            render_terminal_too_small(frame, &message);
+            return;
+        }
+
         // Special handling for auth setup view (full screen)
         if state.current_view == View::AuthSetup {
             let centered_area = centered_rect(60, 60, frame.size());
@@ -81,6 +126,24 @@ impl LayoutComponent {
             if let Some(ref git_state) = state.git_view_state {
                 crate::components::GitViewComponent::render(frame, frame.size(), git_state);
             }
+            // Committing without a file selection asks for confirmation over the git view
+            if state.confirmation_dialog.is_some() {
+                self.confirmation_dialog.render(frame, frame.size(), state);
+            }
+            return;
+        }
+
+        // Special handling for the logs viewer (full screen)
+        if state.current_view == View::Logs {
+            self.logs_viewer.render_full(frame, frame.size(), state);
+            return;
+        }
+
+        // Special handling for dashboard view (full screen)
+        if state.current_view == View::Dashboard {
+            if let Some(ref dashboard) = state.dashboard {
+                crate::components::DashboardComponent::render(frame, frame.size(), dashboard);
+            }
             return;
         }
 
@@ -91,6 +154,7 @@ impl LayoutComponent {
                 Constraint::Min(0),    // Main content area
                 Constraint::Length(3), // Session info (single line + borders)
                 Constraint::Length(3), // Bottom menu bar
+                Constraint::Length(1), // Session summary line
             ])
             .split(frame.size());
 
@@ -98,16 +162,19 @@ impl LayoutComponent {
         self.render_status_bar(frame, main_layout[0], state);
 
         // Simple 2-panel layout: session list | logs (Claude chat is now a popup)
+        let list_width = session_list_width(main_layout[1].width, state.session_list_percent);
         let content_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(40), // Session list
-                Constraint::Percentage(60), // Live logs stream
+                Constraint::Length(list_width), // Session list
+                Constraint::Min(0),             // Live logs stream
             ])
             .split(main_layout[1]);
 
         // Pass focus information to components
         self.session_list.render(frame, content_chunks[0], state);
+        self.session_list_area = content_chunks[0];
+        self.logs_area = content_chunks[1];
 
         // Render tmux preview if selected session has tmux, otherwise show live logs
         let selected_has_tmux = state
@@ -121,6 +188,7 @@ impl LayoutComponent {
         } else {
             // Render traditional live logs stream
             self.live_logs_stream.render(frame, content_chunks[1], state);
+            self.new_log_lines_area = self.live_logs_stream.new_lines_area();
         }
 
         // Render bottom logs area (traditional logs viewer)
@@ -128,10 +196,11 @@ impl LayoutComponent {
 
         // Render bottom menu bar
         self.render_menu_bar(frame, main_layout[3]);
+        render_session_summary(frame, main_layout[4], state);
 
         // Render help overlay if visible
         if state.help_visible {
-            self.help.render(frame, frame.size());
+            self.help.render(frame, frame.size(), &state.keymap);
         }
 
         // Render new session overlay if visible
@@ -155,15 +224,82 @@ impl LayoutComponent {
             self.render_quick_commit_dialog(frame, frame.size(), state);
         }
 
+        if let Some(ref prompt) = state.log_export {
+            self.render_log_export_dialog(frame, frame.size(), prompt);
+        }
+
+        // Render boss prompt viewer if visible
+        if let Some(ref viewer) = state.prompt_viewer {
+            let popup_area = centered_rect(80, 80, frame.size());
+            crate::components::PromptViewerComponent::render(frame, popup_area, viewer);
+        }
+
+        // Render notification history if open
+        if let Some(scroll) = state.notification_history_scroll {
+            let popup_area = centered_rect(80, 70, frame.size());
+            crate::components::NotificationHistoryComponent::render(
+                frame,
+                popup_area,
+                &state.notification_history,
+                scroll,
+                &state.theme,
+            );
+        }
+
+        // Render auth profile switcher if open
+        if let Some(ref picker) = state.auth_profile_picker {
+            let popup_area = centered_rect(50, 50, frame.size());
+            crate::components::AuthProfilesComponent::render(
+                frame,
+                popup_area,
+                picker,
+                &state.theme,
+            );
+        }
+
+        // Render run comparison popup if visible
+        if let Some(ref compare) = state.run_compare {
+            let popup_area = centered_rect(80, 80, frame.size());
+            crate::components::RunCompareComponent::render(frame, popup_area, compare);
+        }
+
         // Render notifications (top-right corner)
         self.render_notifications(frame, frame.size(), state);
     }
 
+    /// The pane, and session list row, under a click on the main screen. Clicks land
+    /// nowhere while another view or an overlay is showing.
+    pub fn click_target(&self, x: u16, y: u16, state: &AppState) -> Option<ClickTarget> {
+        if state.current_view != View::SessionList || state.is_overlay_open() {
+            return None;
+        }
+        let position = Position::new(x, y);
+        if self.session_list_area.contains(position) {
+            let row = self.session_list.row_at(self.session_list_area, x, y, state);
+            Some(ClickTarget::SessionList(row))
+        } else if self.new_log_lines_area.contains(position) {
+            Some(ClickTarget::NewLogLines)
+        } else if self.logs_area.contains(position) {
+            Some(ClickTarget::Logs)
+        } else {
+            None
+        }
+    }
+
+    pub fn logs_viewer_mut(&mut self) -> &mut LogsViewerComponent {
+        &mut self.logs_viewer
+    }
+
     /// Get mutable reference to live logs component for scroll handling
     pub fn live_logs_mut(&mut self) -> &mut LiveLogsStreamComponent {
         &mut self.live_logs_stream
     }
 
+    /// Width the boss prompt was last wrapped at (None when not soft-wrapping)
+    pub fn prompt_wrap_width(&self) -> Option<usize> {
+        self.new_session.prompt_wrap_width()
+    }
+
     /// Get mutable reference to tmux preview component for scroll handling
     pub fn tmux_preview_mut(&mut self) -> &mut TmuxPreviewPane {
         &mut self.tmux_preview
@@ -188,7 +324,9 @@ impl LayoutComponent {
             Span::styled("e", Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD)),
             Span::styled(" restart ", Style::default().fg(MUTED_GRAY)),
             Span::styled("d", Style::default().fg(Color::Rgb(230, 100, 100)).add_modifier(Modifier::BOLD)),
-            Span::styled("elete", Style::default().fg(MUTED_GRAY)),
+            Span::styled("elete ", Style::default().fg(MUTED_GRAY)),
+            Span::styled("S", Style::default().fg(WARNING_ORANGE).add_modifier(Modifier::BOLD)),
+            Span::styled("top", Style::default().fg(MUTED_GRAY)),
             Span::styled(" │ ", Style::default().fg(SUBDUED_BORDER)),
             // Git group
             Span::styled("g", Style::default().fg(CORNFLOWER_BLUE).add_modifier(Modifier::BOLD)),
@@ -270,19 +408,73 @@ impl LayoutComponent {
                                 status_spans.push(Span::styled(format!("{} ", session.name), Style::default().fg(SOFT_WHITE)));
                                 status_spans.push(Span::styled(format!("({})", short_id), Style::default().fg(MUTED_GRAY)));
                             }
+
+                            if let Some(model) = &session.model {
+                                status_spans.push(Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)));
+                                status_spans.push(Span::styled("🧠 ", Style::default().fg(GOLD)));
+                                status_spans.push(Span::styled(model.clone(), Style::default().fg(SOFT_WHITE)));
+                            }
+
+                            // Where to browse to a dev server running in the container
+                            if !session.ports.is_empty() {
+                                let ports: Vec<String> = session
+                                    .ports
+                                    .iter()
+                                    .map(|(host, container)| format!("localhost:{}→{}", host, container))
+                                    .collect();
+                                status_spans.push(Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)));
+                                status_spans.push(Span::styled("🔌 ", Style::default().fg(GOLD)));
+                                status_spans.push(Span::styled(ports.join(", "), Style::default().fg(SOFT_WHITE)));
+                            }
                         }
                     }
                 }
             }
         }
 
+        // OAuth token refresh status
+        match &state.auth_status {
+            crate::app::state::AuthStatus::Refreshing => {
+                if !status_spans.is_empty() {
+                    status_spans.push(Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)));
+                }
+                status_spans.push(Span::styled("🔄 ", Style::default().fg(GOLD)));
+                status_spans.push(Span::styled("auth refreshing…", Style::default().fg(GOLD)));
+            }
+            crate::app::state::AuthStatus::RefreshFailed(_) => {
+                if !status_spans.is_empty() {
+                    status_spans.push(Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)));
+                }
+                status_spans.push(Span::styled("⚠ ", Style::default().fg(WARNING_ORANGE)));
+                status_spans.push(Span::styled("auth refresh failed", Style::default().fg(WARNING_ORANGE)));
+            }
+            _ => {
+                if let Some(label) = state.auth_credential.label(chrono::Utc::now()) {
+                    if !status_spans.is_empty() {
+                        status_spans.push(Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)));
+                    }
+                    status_spans.push(Span::styled("🔑 ", Style::default().fg(GOLD)));
+                    if state.auth_profile != crate::config::auth_profile::DEFAULT_PROFILE {
+                        status_spans.push(Span::styled(
+                            format!("{} · ", state.auth_profile),
+                            Style::default().fg(GOLD),
+                        ));
+                    }
+                    status_spans.push(Span::styled(label, Style::default().fg(MUTED_GRAY)));
+                }
+            }
+        }
+
         // Claude chat status
         if !status_spans.is_empty() {
             status_spans.push(Span::styled("  │  ", Style::default().fg(SUBDUED_BORDER)));
         }
         if state.claude_chat_visible {
             status_spans.push(Span::styled("🗨️ ", Style::default().fg(SELECTION_GREEN)));
-            status_spans.push(Span::styled("ON", Style::default().fg(SELECTION_GREEN)));
+            status_spans.push(Span::styled(
+                format!("ON ({})", state.chat_model),
+                Style::default().fg(SELECTION_GREEN),
+            ));
         } else {
             status_spans.push(Span::styled("🗨️ ", Style::default().fg(MUTED_GRAY)));
             status_spans.push(Span::styled("OFF", Style::default().fg(MUTED_GRAY)));
@@ -342,34 +534,38 @@ impl LayoutComponent {
                 height: 3.min(notification_area.height - y_offset),
             };
 
-            let (icon, text_color, border_color) = match notification.notification_type {
-                crate::app::state::NotificationType::Success => {
-                    ("✓ ", SELECTION_GREEN, SELECTION_GREEN)
-                }
-                crate::app::state::NotificationType::Error => {
-                    ("✗ ", Color::Rgb(230, 100, 100), Color::Rgb(230, 100, 100))
-                }
-                crate::app::state::NotificationType::Warning => {
-                    ("⚠ ", WARNING_ORANGE, WARNING_ORANGE)
-                }
-                crate::app::state::NotificationType::Info => {
-                    ("ℹ ", CORNFLOWER_BLUE, CORNFLOWER_BLUE)
-                }
+            let theme = &state.theme;
+            let (icon, color) = match notification.notification_type {
+                crate::app::state::NotificationType::Success => ("✓ ", theme.notification_success),
+                crate::app::state::NotificationType::Error => ("✗ ", theme.notification_error),
+                crate::app::state::NotificationType::Warning => ("⚠ ", theme.notification_warning),
+                crate::app::state::NotificationType::Info => ("ℹ ", theme.notification_info),
             };
 
             let notification_line = Line::from(vec![
-                Span::styled(icon, Style::default().fg(text_color).add_modifier(Modifier::BOLD)),
-                Span::styled(notification.message.as_str(), Style::default().fg(text_color)),
+                Span::styled(icon, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                Span::styled(notification.display_message(), Style::default().fg(color)),
             ]);
 
+            let mut block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(color))
+                .style(Style::default().bg(PANEL_BG));
+            // Sticky notifications stay until dismissed, so say how
+            if notification.sticky {
+                let key = state.keymap.label(crate::config::KeyAction::DismissNotification);
+                block = block.title_bottom(
+                    Line::from(Span::styled(
+                        format!(" {} dismiss ", key),
+                        Style::default().fg(MUTED_GRAY),
+                    ))
+                    .alignment(Alignment::Right),
+                );
+            }
+
             let notification_widget = Paragraph::new(notification_line)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .border_type(BorderType::Rounded)
-                        .border_style(Style::default().fg(border_color))
-                        .style(Style::default().bg(PANEL_BG)),
-                )
+                .block(block)
                 .wrap(ratatui::widgets::Wrap { trim: true });
 
             frame.render_widget(notification_widget, single_notification_area);
@@ -453,6 +649,58 @@ impl LayoutComponent {
         .alignment(Alignment::Center);
         frame.render_widget(instructions, dialog_layout[2]);
     }
+
+    fn render_log_export_dialog(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        prompt: &crate::app::log_export::LogExportPrompt,
+    ) {
+        let dialog_area = centered_rect(70, 20, area);
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CORNFLOWER_BLUE))
+            .style(Style::default().bg(DARK_BG))
+            .title(Line::from(vec![
+                Span::styled(" 💾 ", Style::default().fg(GOLD)),
+                Span::styled("Export Logs ", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+            ]));
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let mode = if prompt.raw {
+            "Raw (ANSI escape sequences kept)"
+        } else {
+            "Plain text (ANSI escape sequences stripped)"
+        };
+        let lines = vec![
+            Line::from(Span::styled("File:", Style::default().fg(MUTED_GRAY))),
+            Line::from(vec![
+                Span::styled(prompt.path.as_str(), Style::default().fg(SOFT_WHITE)),
+                Span::styled("█", Style::default().fg(SELECTION_GREEN)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Mode: ", Style::default().fg(MUTED_GRAY)),
+                Span::styled(mode, Style::default().fg(SOFT_WHITE)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Enter", Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD)),
+                Span::styled(" Export ", Style::default().fg(MUTED_GRAY)),
+                Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
+                Span::styled(" Tab", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                Span::styled(" Raw/plain ", Style::default().fg(MUTED_GRAY)),
+                Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
+                Span::styled(" Esc", Style::default().fg(WARNING_ORANGE).add_modifier(Modifier::BOLD)),
+                Span::styled(" Cancel", Style::default().fg(MUTED_GRAY)),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
 }
 
 impl Default for LayoutComponent {
@@ -461,7 +709,134 @@ impl Default for LayoutComponent {
     }
 }
 
+/// Width of the session list for `percent` of `total` columns. Neither pane is squeezed
+/// below its minimum; the logs pane wins when there isn't room for both.
+fn session_list_width(total: u16, percent: u16) -> u16 {
+    let wanted =
+        u16::try_from(u32::from(total) * u32::from(percent.min(100)) / 100).unwrap_or(total);
+    wanted.max(MIN_SESSION_LIST_WIDTH).min(total.saturating_sub(MIN_LOGS_PANE_WIDTH))
+}
+
+/// Message shown instead of the UI when `area` is smaller than `min_size` (width, height)
+fn terminal_too_small_message(area: Rect, min_size: (u16, u16)) -> Option<String> {
+    let (min_width, min_height) = min_size;
+    if area.width >= min_width && area.height >= min_height {
+        return None;
+    }
+    Some(format!(
+        "Terminal too small (need {}x{}, have {}x{})",
+        min_width, min_height, area.width, area.height
+    ))
+}
+
+fn render_terminal_too_small(frame: &mut Frame, message: &str) {
+    let area = frame.size();
+    let lines = vec![
+        Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(WARNING_ORANGE).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "Resize the window to continue",
+            Style::default().fg(MUTED_GRAY),
+        )),
+    ];
+
+    // Vertically center the text; it wraps on very narrow terminals
+    let text_height = (lines.len() as u16).min(area.height);
+    let top_padding = area.height.saturating_sub(text_height) / 2;
+    let text_area = Rect {
+        y: area.y + top_padding,
+        height: area.height - top_padding,
+        ..area
+    };
+
+    frame.render_widget(Block::default().style(Style::default().bg(DARK_BG)), area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        text_area,
+    );
+}
+
 /// Helper function to create a centered rectangle
+/// One line of session counts, live log lines, the current view and, when sampled,
+/// container CPU/memory. Parts are dropped to fit: resources first, then log lines,
+/// then the view; the counts always stay.
+fn session_summary_line(state: &AppState, width: u16) -> Line<'static> {
+    let theme = &state.theme;
+    let muted = Style::default().fg(theme.muted);
+    let (mut running, mut idle, mut stopped, mut errored) = (0, 0, 0, 0);
+    for session in state.workspaces.iter().flat_map(|w| &w.sessions) {
+        match session.status {
+            crate::models::SessionStatus::Running => running += 1,
+            crate::models::SessionStatus::Idle => idle += 1,
+            crate::models::SessionStatus::Stopped => stopped += 1,
+            crate::models::SessionStatus::Error(_) => errored += 1,
+        }
+    }
+    let log_lines: usize = state.live_logs.values().map(Vec::len).sum();
+
+    // (drop order, spans): the highest drop order goes first when space runs out
+    let mut parts: Vec<(u8, Vec<Span<'static>>)> = vec![
+        (1, vec![Span::styled(state.current_view.label(), Style::default().fg(GOLD))]),
+        (
+            0,
+            vec![
+                Span::styled(format!("● {} running", running), Style::default().fg(theme.running)),
+                Span::styled(format!("  ◐ {} idle", idle), Style::default().fg(theme.idle)),
+                Span::styled(
+                    format!("  ○ {} stopped", stopped),
+                    Style::default().fg(theme.stopped),
+                ),
+                Span::styled(format!("  ✗ {} error", errored), Style::default().fg(theme.error)),
+            ],
+        ),
+        (2, vec![Span::styled(format!("{} log lines", log_lines), muted)]),
+    ];
+    if let Some(usage) = state.container_usage {
+        parts.push((
+            3,
+            vec![Span::styled(
+                format!(
+                    "CPU {:.1}%  MEM {}",
+                    usage.cpu_percent,
+                    super::dashboard::format_bytes(usage.memory_bytes)
+                ),
+                muted,
+            )],
+        ));
+    }
+
+    const SEPARATOR: &str = " │ ";
+    let line_width = |parts: &[(u8, Vec<Span<'static>>)]| {
+        1 + parts.iter().flat_map(|(_, spans)| spans).map(Span::width).sum::<usize>()
+            + SEPARATOR.chars().count() * parts.len().saturating_sub(1)
+    };
+    while line_width(&parts) > width as usize && parts.len() > 1 {
+        let drop = (0..parts.len()).max_by_key(|&i| parts[i].0).unwrap_or(0);
+        parts.remove(drop);
+    }
+
+    let mut spans = vec![Span::raw(" ")];
+    for (index, (_, part)) in parts.into_iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled(SEPARATOR, Style::default().fg(theme.border)));
+        }
+        spans.extend(part);
+    }
+    Line::from(spans)
+}
+
+fn render_session_summary(frame: &mut Frame, area: Rect, state: &AppState) {
+    frame.render_widget(
+        Paragraph::new(session_summary_line(state, area.width))
+            .style(Style::default().bg(state.theme.background)),
+        area,
+    );
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -481,3 +856,120 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_list_width_keeps_minimums() {
+        assert_eq!(session_list_width(200, 40), 80);
+        assert_eq!(session_list_width(200, 5), MIN_SESSION_LIST_WIDTH);
+        assert_eq!(session_list_width(100, 85), 100 - MIN_LOGS_PANE_WIDTH);
+        assert_eq!(session_list_width(40, 50), 40 - MIN_LOGS_PANE_WIDTH);
+    }
+
+    #[test]
+    fn test_terminal_too_small_message() {
+        let min_size = (60, 20);
+
+        assert_eq!(terminal_too_small_message(Rect::new(0, 0, 60, 20), min_size), None);
+        assert_eq!(terminal_too_small_message(Rect::new(0, 0, 200, 50), min_size), None);
+        assert_eq!(
+            terminal_too_small_message(Rect::new(0, 0, 40, 30), min_size).as_deref(),
+            Some("Terminal too small (need 60x20, have 40x30)")
+        );
+        assert!(terminal_too_small_message(Rect::new(0, 0, 80, 19), min_size).is_some());
+    }
+
+    #[test]
+    fn test_click_selects_session_and_focuses_pane() {
+        use crate::app::events::AppEvent;
+        use crate::app::state::FocusedPane;
+        use crate::app::EventHandler;
+        use crate::models::{Session, Workspace};
+
+        let mut state = AppState::new();
+        let mut workspace = Workspace::new("api".to_string(), "/tmp/api".into());
+        for name in ["first", "second", "third"] {
+            workspace.add_session(Session::new(name.to_string(), "/tmp".to_string()));
+        }
+        state.workspaces.push(workspace);
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(0);
+        state.focused_pane = FocusedPane::LiveLogs;
+
+        let mut layout = LayoutComponent::new();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| layout.render(frame, &state)).unwrap();
+
+        // Status bar takes rows 0-2, the list border row 3, the workspace row 4
+        let target = layout.click_target(5, 7, &state).unwrap();
+        assert_eq!(target, ClickTarget::SessionList(Some(SessionListRow::Session(0, 2))));
+        EventHandler::process_event(AppEvent::MouseClick(target), &mut state);
+        assert_eq!(state.selected_session_index, Some(2));
+        assert_eq!(state.focused_pane, FocusedPane::Sessions);
+
+        assert_eq!(layout.click_target(5, 20, &state), Some(ClickTarget::SessionList(None)));
+        let target = layout.click_target(80, 10, &state).unwrap();
+        assert_eq!(target, ClickTarget::Logs);
+        EventHandler::process_event(AppEvent::MouseClick(target), &mut state);
+        assert_eq!(state.focused_pane, FocusedPane::LiveLogs);
+
+        // Overlays take the clicks
+        state.help_visible = true;
+        assert_eq!(layout.click_target(5, 7, &state), None);
+    }
+
+    fn rendered_summary(state: &AppState, width: u16) -> String {
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(width, 1)).unwrap();
+        terminal.draw(|frame| render_session_summary(frame, frame.size(), state)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect::<String>().trim_end().to_string()
+    }
+
+    #[test]
+    fn test_session_summary_counts_and_degrades() {
+        use crate::components::live_logs_stream::{LogEntry, LogEntryLevel};
+        use crate::models::{Session, SessionStatus, Workspace};
+
+        let mut state = AppState::new();
+        for (name, sessions) in [("api", 3), ("web", 2)] {
+            let mut workspace = Workspace::new(name.to_string(), format!("/tmp/{}", name).into());
+            for i in 0..sessions {
+                workspace.add_session(Session::new(format!("{}-{}", name, i), "/tmp".to_string()));
+            }
+            state.workspaces.push(workspace);
+        }
+        state.workspaces[0].sessions[0].set_status(SessionStatus::Running);
+        state.workspaces[0].sessions[1].set_status(SessionStatus::Running);
+        state.workspaces[0].sessions[2].set_status(SessionStatus::Error("oom".to_string()));
+        state.workspaces[1].sessions[0].set_status(SessionStatus::Stopped);
+        state.workspaces[1].sessions[1].set_status(SessionStatus::Running);
+        let session_id = state.workspaces[0].sessions[0].id;
+        let entry = LogEntry::new(LogEntryLevel::Info, "claude".to_string(), "hi".to_string());
+        state.live_logs.insert(session_id, vec![entry; 42]);
+        state.container_usage = Some(crate::docker::ContainerUsage {
+            cpu_percent: 12.5,
+            memory_bytes: 512 * 1024 * 1024,
+        });
+
+        assert_eq!(
+            rendered_summary(&state, 120),
+            " Sessions │ ● 3 running  ◐ 0 idle  ○ 1 stopped  ✗ 1 error │ 42 log lines │ \
+             CPU 12.5%  MEM 512.0 MB"
+        );
+        assert_eq!(
+            rendered_summary(&state, 80),
+            " Sessions │ ● 3 running  ◐ 0 idle  ○ 1 stopped  ✗ 1 error │ 42 log lines"
+        );
+        assert_eq!(
+            rendered_summary(&state, 60),
+            " Sessions │ ● 3 running  ◐ 0 idle  ○ 1 stopped  ✗ 1 error"
+        );
+        assert_eq!(
+            rendered_summary(&state, 50),
+            " ● 3 running  ◐ 0 idle  ○ 1 stopped  ✗ 1 error"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// ABOUTME: Wraps styled lines to a pane width so scrolling can count visual rows exactly
+// Breaks at the character that would overflow, keeping each span's style on every piece
+
+use ratatui::text::{Line, Span};
+
+/// Columns moved per left/right key press when lines aren't wrapped
+pub const HORIZONTAL_SCROLL_STEP: usize = 8;
+
+/// Terminal columns taken by one character, as ratatui measures it
+fn char_width(ch: char) -> usize {
+    let mut buf = [0; 4];
+    Span::raw(&*ch.encode_utf8(&mut buf)).width()
+}
+
+/// Rows `line` takes when wrapped to `width` columns; an empty line still takes one
+pub fn row_count(line: &Line<'_>, width: usize) -> usize {
+    let width = width.max(1);
+    let mut rows = 1;
+    let mut row_width = 0;
+    for ch in line.spans.iter().flat_map(|span| span.content.chars()) {
+        let ch_width = char_width(ch);
+        if row_width > 0 && row_width + ch_width > width {
+            rows += 1;
+            row_width = 0;
+        }
+        row_width += ch_width;
+    }
+    rows
+}
+
+/// `line` split into rows of at most `width` columns, `row_count` of them
+pub fn wrap_line(line: &Line<'_>, width: usize) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut row: Vec<Span<'static>> = Vec::new();
+    let mut row_width = 0;
+    for span in &line.spans {
+        let mut text = String::new();
+        for ch in span.content.chars() {
+            let ch_width = char_width(ch);
+            if row_width > 0 && row_width + ch_width > width {
+                if !text.is_empty() {
+                    row.push(Span::styled(std::mem::take(&mut text), span.style));
+                }
+                rows.push(Line::from(std::mem::take(&mut row)).style(line.style));
+                row_width = 0;
+            }
+            text.push(ch);
+            row_width += ch_width;
+        }
+        if !text.is_empty() {
+            row.push(Span::styled(text, span.style));
+        }
+    }
+    rows.push(Line::from(row).style(line.style));
+    rows
+}
+
+/// Last line that can sit at the top of a `height`-row view: scrolling further would
+/// only add empty rows below the final line. `row_counts` holds each line's rows.
+pub fn max_top_line(row_counts: &[usize], height: usize) -> usize {
+    let mut rows = 0;
+    for (index, count) in row_counts.iter().enumerate().rev() {
+        rows += count;
+        if rows > height {
+            return (index + 1).min(row_counts.len() - 1);
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Style};
+
+    #[test]
+    fn test_row_count_matches_wrapped_rows() {
+        let red = Style::default().fg(Color::Red);
+        let line = Line::from(vec![Span::styled("abcdef", red), Span::raw("ghij")]);
+
+        assert_eq!(row_count(&line, 4), 3);
+        let rows = wrap_line(&line, 4);
+        assert_eq!(rows.len(), 3);
+        // The break inside the styled span keeps its style on both pieces
+        assert_eq!(rows[1].spans, vec![Span::styled("ef", red), Span::raw("gh")]);
+        assert_eq!(rows[2].spans, vec![Span::raw("ij")]);
+
+        // Exactly the width fits on one row; empty lines still take a row
+        assert_eq!(row_count(&Line::from("abcd"), 4), 1);
+        assert_eq!(row_count(&Line::from(""), 4), 1);
+        assert_eq!(wrap_line(&Line::from(""), 4).len(), 1);
+        assert_eq!(row_count(&line, 0), 10);
+    }
+
+    #[test]
+    fn test_wide_characters_never_split_across_rows() {
+        // Each CJK character is two columns, so a third would overflow a 5-column row
+        let line = Line::from("日本語です");
+        assert_eq!(row_count(&line, 5), 3);
+        let rows = wrap_line(&line, 5);
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.width() <= 5));
+        assert_eq!(rows[0].spans, vec![Span::raw("日本")]);
+    }
+
+    #[test]
+    fn test_max_top_line_counts_wrapped_rows() {
+        // Unwrapped lines: the last `height` lines fill the view
+        assert_eq!(max_top_line(&[1; 10], 4), 6);
+        assert_eq!(max_top_line(&[1; 3], 4), 0);
+        assert_eq!(max_top_line(&[], 4), 0);
+
+        // The last two lines take exactly five rows; a sixth fits the line before them
+        assert_eq!(max_top_line(&[1, 1, 3, 2], 5), 2);
+        assert_eq!(max_top_line(&[1, 1, 3, 2], 6), 1);
+        // A final line taller than the view is still scrolled to its first row
+        assert_eq!(max_top_line(&[1, 1, 9], 4), 2);
+    }
+}
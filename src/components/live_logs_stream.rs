@@ -2,22 +2,202 @@
 
 #![allow(dead_code)]
 
+use super::line_wrap::{self, HORIZONTAL_SCROLL_STEP};
 use super::log_formatter_simple::{FormatConfig, SimpleLogFormatter};
 use crate::app::AppState;
 use ratatui::{
     prelude::*,
     style::{Color, Style},
     text::Line,
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Paragraph,
+        block::{Position, Title},
+    },
 };
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
 pub struct LiveLogsStreamComponent {
     auto_scroll: bool,
-    scroll_offset: usize,
+    scroll_offset: usize, // In rows as drawn, so wrapped lines count once per row
     max_visible_lines: usize,
+    wrap: bool,               // Wrap long lines to the pane width; otherwise scroll sideways
+    horizontal_offset: usize, // First column shown when not wrapping
+    drawn_entry_rows: Vec<usize>, // Rows each visible entry took in the last render
+    follow_anchor: Option<FollowAnchor>,
+    new_lines_area: Rect, // The "new lines" indicator as last drawn; empty when hidden
     show_timestamps: bool,
+    ansi_colors: bool, // Render ANSI colors in messages rather than stripping them
+    theme: crate::config::Theme, // Synced from AppState each render
+    timestamp_format: TimestampFormat,
     filter_level: LogLevel,
     log_formatter: SimpleLogFormatter,
+    bookmarks: HashMap<Option<Uuid>, Vec<LogBookmark>>, // Keyed by selected session (None = all sessions)
+    text_filter: Option<LogTextFilter>, // Only lines matching this are shown
+    filter_input: Option<String>,       // Pattern being typed, applied as it changes
+}
+
+/// A line a `LogBookmark` can be anchored to: its timestamp, when it has one, and its text
+pub trait BookmarkLine {
+    fn timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>>;
+    fn text(&self) -> &str;
+}
+
+impl BookmarkLine for LogEntry {
+    fn timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        Some(self.timestamp)
+    }
+
+    fn text(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Plain text lines, as shown by the full-screen logs view
+impl BookmarkLine for String {
+    fn timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        None
+    }
+
+    fn text(&self) -> &str {
+        self
+    }
+}
+
+/// A bookmarked log line, anchored by timestamp and content so it survives buffer truncation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogBookmark {
+    pub index: usize,
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub message: String,
+}
+
+impl LogBookmark {
+    pub fn from_line(index: usize, line: &impl BookmarkLine) -> Self {
+        Self {
+            index,
+            timestamp: line.timestamp(),
+            message: line.text().to_string(),
+        }
+    }
+
+    fn matches(&self, line: &impl BookmarkLine) -> bool {
+        line.timestamp() == self.timestamp && line.text() == self.message
+    }
+
+    /// Find the bookmarked line's current index. Truncation only moves lines up, so a
+    /// shifted line is looked for at or above where it was.
+    pub fn resolve<L: BookmarkLine>(&self, lines: &[L]) -> Option<usize> {
+        if lines.get(self.index).is_some_and(|line| self.matches(line)) {
+            return Some(self.index);
+        }
+        lines[..self.index.min(lines.len())].iter().rposition(|line| self.matches(line))
+    }
+}
+
+/// Drop bookmarks whose lines have been truncated away and re-anchor the rest
+pub fn prune_bookmarks<L: BookmarkLine>(bookmarks: &mut Vec<LogBookmark>, lines: &[L]) {
+    bookmarks.retain_mut(|bookmark| match bookmark.resolve(lines) {
+        Some(index) => {
+            bookmark.index = index;
+            true
+        }
+        None => false,
+    });
+}
+
+/// Marker gutter cell for a line: an arrow on bookmarked lines, blank otherwise
+pub fn bookmark_gutter(bookmarked: bool) -> ratatui::text::Span<'static> {
+    if bookmarked {
+        ratatui::text::Span::styled("▶ ", Style::default().fg(Color::Yellow))
+    } else {
+        ratatui::text::Span::raw("  ")
+    }
+}
+
+/// The newest entry when the view last followed the tail; entries after it are new
+#[derive(Debug, Clone)]
+struct FollowAnchor {
+    key: Option<Uuid>, // Selected session, as for bookmarks
+    newest: Option<LogBookmark>,
+}
+
+/// How the live logs timestamp column is rendered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimestampFormat {
+    /// Age of the entry, e.g. "2s ago"
+    Relative,
+    /// Absolute time using a chrono strftime pattern, e.g. "%H:%M:%S"
+    Absolute(String),
+}
+
+impl Default for TimestampFormat {
+    fn default() -> Self {
+        TimestampFormat::Absolute("%H:%M:%S".to_string())
+    }
+}
+
+impl TimestampFormat {
+    /// Parse the `log_timestamp_format` config value: "relative", or a strftime pattern.
+    /// Invalid patterns fall back to the default rather than panicking at render time.
+    pub fn from_config(value: &str) -> Self {
+        use chrono::format::{Item, StrftimeItems};
+
+        if value.eq_ignore_ascii_case("relative") {
+            return TimestampFormat::Relative;
+        }
+        if value.is_empty() || StrftimeItems::new(value).any(|item| matches!(item, Item::Error)) {
+            tracing::warn!("Invalid log timestamp format '{}', using default", value);
+            return TimestampFormat::default();
+        }
+        TimestampFormat::Absolute(value.to_string())
+    }
+
+    /// Fixed column width so the log text doesn't shift as entries age or arrive
+    fn column_width(&self) -> usize {
+        match self {
+            // Longest relative forms: "59s ago", "59m ago", "23h ago"
+            TimestampFormat::Relative => 8,
+            TimestampFormat::Absolute(pattern) => {
+                let sample = chrono::DateTime::<chrono::Utc>::from_timestamp(0, 0)
+                    .unwrap_or_default()
+                    .format(pattern)
+                    .to_string();
+                sample.chars().count()
+            }
+        }
+    }
+
+    fn format(&self, timestamp: &chrono::DateTime<chrono::Utc>) -> String {
+        match self {
+            TimestampFormat::Relative => {
+                let age = chrono::Utc::now() - *timestamp;
+                if age < chrono::Duration::seconds(1) {
+                    "now".to_string()
+                } else if age < chrono::Duration::minutes(1) {
+                    format!("{}s ago", age.num_seconds())
+                } else if age < chrono::Duration::hours(1) {
+                    format!("{}m ago", age.num_minutes())
+                } else if age < chrono::Duration::days(1) {
+                    format!("{}h ago", age.num_hours())
+                } else {
+                    timestamp.format("%m-%d").to_string()
+                }
+            }
+            TimestampFormat::Absolute(pattern) => timestamp.format(pattern).to_string(),
+        }
+    }
+
+    /// Timestamp column text for an entry, padded to a stable width. Uses the parsed
+    /// log timestamp when available, otherwise the time the entry arrived.
+    pub fn column(&self, log: &LogEntry) -> String {
+        let timestamp = log
+            .parsed_data
+            .as_ref()
+            .and_then(|parsed| parsed.timestamp)
+            .unwrap_or(log.timestamp);
+        format!("{:>width$} ", self.format(&timestamp), width = self.column_width())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +228,78 @@ impl LogLevel {
     }
 }
 
+/// Pattern the displayed log lines are filtered by: a regex when it compiles,
+/// otherwise a plain substring
+#[derive(Debug, Clone)]
+pub struct LogTextFilter {
+    pattern: String,
+    regex: regex::Regex,
+    is_regex: bool,
+}
+
+impl LogTextFilter {
+    /// None for an empty pattern, which filters nothing
+    pub fn new(pattern: &str) -> Option<Self> {
+        if pattern.is_empty() {
+            return None;
+        }
+        let (regex, is_regex) = match regex::Regex::new(pattern) {
+            Ok(regex) => (regex, true),
+            Err(_) => (regex::Regex::new(&regex::escape(pattern)).ok()?, false),
+        };
+        Some(Self {
+            pattern: pattern.to_string(),
+            regex,
+            is_regex,
+        })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn is_regex(&self) -> bool {
+        self.is_regex
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+
+    /// Byte ranges of the non-empty matches in `text`
+    pub fn match_ranges(&self, text: &str) -> Vec<std::ops::Range<usize>> {
+        self.regex.find_iter(text).map(|m| m.range()).filter(|r| !r.is_empty()).collect()
+    }
+
+    /// Split each span around its matches, highlighting the matched text
+    fn highlight<'a>(&self, line: Line<'a>) -> Line<'a> {
+        let highlight = Style::default().fg(Color::Black).bg(Color::Yellow);
+        let mut spans = Vec::with_capacity(line.spans.len());
+        for span in line.spans {
+            let ranges = self.match_ranges(&span.content);
+            if ranges.is_empty() {
+                spans.push(span);
+                continue;
+            }
+            let mut last = 0;
+            for range in ranges {
+                if range.start > last {
+                    spans.push(Span::styled(span.content[last..range.start].to_string(), span.style));
+                }
+                spans.push(Span::styled(
+                    span.content[range.clone()].to_string(),
+                    span.style.patch(highlight),
+                ));
+                last = range.end;
+            }
+            if last < span.content.len() {
+                spans.push(Span::styled(span.content[last..].to_string(), span.style));
+            }
+        }
+        Line::from(spans)
+    }
+}
+
 impl LiveLogsStreamComponent {
     pub fn new() -> Self {
         let format_config = FormatConfig {
@@ -62,41 +314,81 @@ impl LiveLogsStreamComponent {
             auto_scroll: true,
             scroll_offset: 0,
             max_visible_lines: 20,
+            wrap: true,
+            horizontal_offset: 0,
+            drawn_entry_rows: Vec::new(),
+            follow_anchor: None,
+            new_lines_area: Rect::default(),
             show_timestamps: false,
+            ansi_colors: true,
+            theme: crate::config::Theme::default(),
+            timestamp_format: TimestampFormat::default(),
             filter_level: LogLevel::All,
             log_formatter: SimpleLogFormatter::new(format_config),
+            bookmarks: HashMap::new(),
+            text_filter: None,
+            filter_input: None,
         }
     }
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        // Timestamp display is toggled through app state and defaults from config
+        self.show_timestamps = state.show_log_timestamps;
+        self.ansi_colors = state.log_ansi_colors;
+        self.theme = state.theme;
+        if self.timestamp_format != state.log_timestamp_format {
+            self.timestamp_format = state.log_timestamp_format.clone();
+        }
+
         // Get logs from the selected session
         let session_logs = self.get_session_logs(state);
+        let new_lines = self.track_new_lines(Self::bookmark_key(state), &session_logs);
+        self.new_lines_area = Rect::default();
 
         // Filter logs based on level
         let filtered_logs = self.filter_logs(&session_logs);
 
-        let title = self.build_title(state, filtered_logs.len(), session_logs.len());
+        let bookmarked = self.resolve_bookmarks(state, &session_logs);
+        let bookmark_marks: Vec<bool> = self
+            .visible_entries(&session_logs)
+            .iter()
+            .map(|(index, _)| bookmarked.contains(index))
+            .collect();
+
+        let mut title = self.build_title(state, filtered_logs.len(), session_logs.len());
+        if !bookmarked.is_empty() {
+            title.push_str(&format!("🔖{} ", bookmarked.len()));
+        }
 
         // Show focus indicator
         use crate::app::state::FocusedPane;
         let (border_color, title_color) = match state.focused_pane {
-            FocusedPane::LiveLogs => (Color::Cyan, Color::Yellow), // Focused
-            FocusedPane::Sessions => (Color::Gray, Color::Blue),   // Not focused
+            FocusedPane::LiveLogs => (self.theme.logs_border_focused, self.theme.logs_title_focused),
+            FocusedPane::Sessions => (self.theme.logs_border, self.theme.logs_title),
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .title(title)
             .title_style(Style::default().fg(title_color))
             .border_style(Style::default().fg(border_color));
+        if let Some(mcp_status) = Self::mcp_status_line(state) {
+            block = block.title(
+                Title::from(mcp_status).position(Position::Top).alignment(Alignment::Right),
+            );
+        }
 
         if filtered_logs.is_empty() {
-            let empty_message = match self.filter_level {
-                LogLevel::All => {
+            let empty_message = match (&self.text_filter, self.filter_level) {
+                (Some(filter), _) => &format!(
+                    "No lines match '{}'\n\nPress 'f' to change the filter.",
+                    filter.pattern()
+                ),
+                (None, LogLevel::All) => {
                     "No logs available\n\nLogs will appear here when containers are active."
                 }
-                _ => &format!(
-                    "No {} level logs\n\nAdjust filter level with 'f' key.",
+                (None, _) => &format!(
+                    "No {} level logs",
                     self.filter_level.as_str().to_lowercase()
                 ),
             };
@@ -108,27 +400,104 @@ impl LiveLogsStreamComponent {
                     .alignment(Alignment::Center),
                 area,
             );
+            self.drawn_entry_rows.clear();
+            self.render_controls_hint(frame, area);
             return;
         }
 
-        // Get scroll position before borrowing self mutably
-        let scroll_pos = self.get_scroll_position(&filtered_logs);
+        // Update max visible lines based on actual area
+        self.max_visible_lines = (area.height.saturating_sub(4)) as usize;
+        let width = area.width.saturating_sub(2) as usize;
+        let wrap = self.wrap;
+        let max_visible_lines = self.max_visible_lines;
+        let scroll_offset = if self.auto_scroll { usize::MAX } else { self.scroll_offset };
+        let horizontal_offset = self.horizontal_offset;
 
         // Create formatted log lines using the beautiful formatter
-        let log_lines = self.create_formatted_log_lines(&filtered_logs);
+        let log_lines = self.create_formatted_log_lines(&filtered_logs, &bookmark_marks);
+
+        // Wrap here rather than in the Paragraph so the scroll math counts the rows drawn
+        let mut log_lines = log_lines.into_iter();
+        let mut rows = Vec::new();
+        let mut entry_rows = Vec::with_capacity(filtered_logs.len());
+        for log in &filtered_logs {
+            let first_row = rows.len();
+            for line in log_lines.by_ref().take(Self::entry_line_count(log)) {
+                if wrap {
+                    rows.extend(line_wrap::wrap_line(&line, width));
+                } else {
+                    rows.push(line);
+                }
+            }
+            entry_rows.push(rows.len() - first_row);
+        }
+        let max_line_width = if wrap { 0 } else { rows.iter().map(Line::width).max().unwrap_or(0) };
+        let scroll_offset = scroll_offset.min(rows.len().saturating_sub(max_visible_lines));
+        let horizontal_offset = horizontal_offset.min(max_line_width.saturating_sub(width));
 
-        let paragraph = Paragraph::new(log_lines)
+        let paragraph = Paragraph::new(rows)
             .block(block)
-            .wrap(Wrap { trim: false })
-            .scroll((scroll_pos as u16, 0));
+            .scroll((scroll_offset as u16, horizontal_offset as u16));
 
         frame.render_widget(paragraph, area);
+        self.drawn_entry_rows = entry_rows;
+        self.scroll_offset = scroll_offset;
+        self.horizontal_offset = horizontal_offset;
 
         // Render controls hint
         self.render_controls_hint(frame, area);
+        self.render_new_lines_indicator(frame, area, new_lines);
+    }
 
-        // Update max visible lines based on actual area
-        self.max_visible_lines = (area.height.saturating_sub(4)) as usize;
+    /// "▼ N new lines" on the bottom border while not following and lines have arrived
+    fn render_new_lines_indicator(&mut self, frame: &mut Frame, area: Rect, new_lines: usize) {
+        if new_lines == 0 || area.height < 3 {
+            return;
+        }
+        let text = format!(
+            " ▼ {} new line{} [End] ",
+            new_lines,
+            if new_lines == 1 { "" } else { "s" }
+        );
+        let width = (Line::from(text.as_str()).width() as u16).min(area.width.saturating_sub(2));
+        self.new_lines_area = Rect {
+            x: area.right().saturating_sub(width + 1),
+            y: area.bottom() - 1,
+            width,
+            height: 1,
+        };
+        frame.render_widget(
+            Paragraph::new(text).style(Style::default().fg(Color::Black).bg(Color::Yellow)),
+            self.new_lines_area,
+        );
+    }
+
+    /// Where the "new lines" indicator was drawn, for clicks; empty when it's hidden
+    pub fn new_lines_area(&self) -> Rect {
+        self.new_lines_area
+    }
+
+    /// Entries that arrived, and pass the filter, since the view stopped following the
+    /// tail. While following, or after switching sessions, the newest entry becomes the
+    /// anchor and nothing is new.
+    fn track_new_lines(&mut self, key: Option<Uuid>, logs: &[LogEntry]) -> usize {
+        let anchor = self.follow_anchor.as_ref().filter(|anchor| anchor.key == key);
+        let after = match anchor {
+            Some(anchor) if !self.auto_scroll => match &anchor.newest {
+                // Truncation drops the oldest lines first, so if the anchor is gone
+                // everything left is newer
+                Some(newest) => newest.resolve(logs).map_or(0, |index| index + 1),
+                None => 0,
+            },
+            _ => {
+                self.follow_anchor = Some(FollowAnchor {
+                    key,
+                    newest: logs.last().map(|entry| LogBookmark::from_line(logs.len() - 1, entry)),
+                });
+                return 0;
+            }
+        };
+        self.visible_entries(logs).iter().filter(|(index, _)| *index >= after).count()
     }
 
     fn get_session_logs(&self, state: &AppState) -> Vec<LogEntry> {
@@ -158,6 +527,9 @@ impl LiveLogsStreamComponent {
     }
 
     fn should_include_log(&self, log: &LogEntry) -> bool {
+        if self.text_filter.as_ref().is_some_and(|filter| !filter.is_match(&log.message)) {
+            return false;
+        }
         match self.filter_level {
             LogLevel::All => true,
             LogLevel::Info => matches!(
@@ -182,23 +554,60 @@ impl LiveLogsStreamComponent {
             String::new()
         };
 
+        let text_filter_info = match &self.text_filter {
+            Some(filter) if filter.is_regex() => format!(" /{}/ ", filter.pattern()),
+            Some(filter) => format!(" \"{}\" ", filter.pattern()),
+            None => String::new(),
+        };
+
         let count_info = if filtered_count != total_count {
             format!(" ({}/{}) ", filtered_count, total_count)
         } else {
             format!(" ({}) ", total_count)
         };
 
-        format!("🔴 Live Logs{}{}{}", session_info, filter_info, count_info)
+        format!(
+            "🔴 Live Logs{}{}{}{}",
+            session_info, filter_info, text_filter_info, count_info
+        )
+    }
+
+    /// MCP servers the selected session's agent reported, failed ones in red with their status
+    fn mcp_status_line(state: &AppState) -> Option<Line<'static>> {
+        let session = state.selected_session()?;
+        let servers = state.session_mcp_servers.get(&session.id).filter(|s| !s.is_empty())?;
+
+        let mut spans = vec![Span::styled(" MCP ", Style::default().fg(Color::Gray))];
+        for server in servers {
+            let span = if server.is_error() {
+                Span::styled(
+                    format!("✗ {} ({}) ", server.name, server.status),
+                    Style::default().fg(Color::Red),
+                )
+            } else if server.status == "pending" {
+                Span::styled(format!("… {} ", server.name), Style::default().fg(Color::Yellow))
+            } else {
+                Span::styled(format!("● {} ", server.name), Style::default().fg(Color::Green))
+            };
+            spans.push(span);
+        }
+        Some(Line::from(spans))
     }
 
-    fn create_formatted_log_lines(&mut self, logs: &[&LogEntry]) -> Vec<Line> {
+    fn create_formatted_log_lines(&mut self, logs: &[&LogEntry], bookmarked: &[bool]) -> Vec<Line> {
         let mut all_lines = Vec::new();
+        let show_gutter = bookmarked.iter().any(|marked| *marked);
 
         // Process each log entry
-        for log in logs {
+        for (idx, log) in logs.iter().enumerate() {
+            let first_line = all_lines.len();
             if let Some(ref parsed_data) = log.parsed_data {
                 // Use beautiful formatter for parsed logs
-                all_lines.push(self.log_formatter.format_log(parsed_data));
+                let mut line = self.log_formatter.format_log(parsed_data);
+                if self.show_timestamps {
+                    line.spans.insert(0, self.timestamp_span(log));
+                }
+                all_lines.push(line);
             } else {
                 // Check if this is a structured message with multiple lines
                 if log.message.contains('\n') && log.metadata.get("event_type") == Some(&"structured".to_string()) {
@@ -209,10 +618,17 @@ impl LiveLogsStreamComponent {
                             all_lines.push(self.format_basic_log_line_with_text(log, line_str));
                         } else {
                             // Subsequent lines without timestamp, just indented
-                            all_lines.push(Line::from(vec![
+                            let indent = if self.show_timestamps {
+                                " ".repeat(self.timestamp_format.column_width() + 1)
+                            } else {
+                                String::new()
+                            };
+                            let mut spans = vec![
+                                ratatui::text::Span::raw(indent),
                                 ratatui::text::Span::raw("         "), // Indent for alignment
-                                ratatui::text::Span::raw(line_str.to_string()),
-                            ]));
+                            ];
+                            spans.extend(self.message_spans(line_str));
+                            all_lines.push(Line::from(spans));
                         }
                     }
                 } else {
@@ -220,6 +636,20 @@ impl LiveLogsStreamComponent {
                     all_lines.push(self.format_basic_log_line(log));
                 }
             }
+
+            if let Some(filter) = &self.text_filter {
+                for line in all_lines.iter_mut().skip(first_line) {
+                    *line = filter.highlight(std::mem::take(line));
+                }
+            }
+
+            // Marker gutter for bookmarked lines
+            if show_gutter {
+                let is_bookmarked = bookmarked.get(idx).copied().unwrap_or(false);
+                for (line_idx, line) in all_lines.iter_mut().enumerate().skip(first_line) {
+                    line.spans.insert(0, bookmark_gutter(is_bookmarked && line_idx == first_line));
+                }
+            }
         }
 
         all_lines
@@ -229,26 +659,43 @@ impl LiveLogsStreamComponent {
         self.format_basic_log_line_with_text(log, &log.message)
     }
 
+    fn timestamp_span(&self, log: &LogEntry) -> ratatui::text::Span<'static> {
+        ratatui::text::Span::styled(
+            self.timestamp_format.column(log),
+            Style::default().fg(Color::DarkGray),
+        )
+    }
+
     fn format_basic_log_line_with_text(&self, log: &LogEntry, text: &str) -> Line {
         let timestamp_str = if self.show_timestamps {
-            format!("[{}] ", log.timestamp.format("%H:%M:%S"))
+            self.timestamp_format.column(log)
         } else {
             String::new()
         };
 
         let (level_icon, level_color) = match log.level {
-            LogEntryLevel::Debug => ("🔍", Color::DarkGray),
-            LogEntryLevel::Info => ("ℹ️", Color::Blue),
-            LogEntryLevel::Warn => ("⚠️", Color::Yellow),
-            LogEntryLevel::Error => ("❌", Color::Red),
+            LogEntryLevel::Debug => ("🔍", self.theme.log_debug),
+            LogEntryLevel::Info => ("ℹ️", self.theme.log_info),
+            LogEntryLevel::Warn => ("⚠️", self.theme.log_warn),
+            LogEntryLevel::Error => ("❌", self.theme.log_error),
         };
 
-        Line::from(vec![
+        let mut spans = vec![
             ratatui::text::Span::styled(timestamp_str, Style::default().fg(Color::DarkGray)),
             ratatui::text::Span::styled(level_icon, Style::default().fg(level_color)),
             ratatui::text::Span::raw(" "),
-            ratatui::text::Span::raw(text.to_string()),
-        ])
+        ];
+        spans.extend(self.message_spans(text));
+        Line::from(spans)
+    }
+
+    /// Message text with its ANSI colors, or stripped to plain text when colors are off
+    fn message_spans(&self, text: &str) -> Vec<ratatui::text::Span<'static>> {
+        if self.ansi_colors {
+            super::ansi_line::ansi_line_spans(text)
+        } else {
+            vec![ratatui::text::Span::raw(crate::app::log_export::strip_ansi(text))]
+        }
     }
 
     fn create_log_text(&self, logs: &[&LogEntry], available_width: u16) -> String {
@@ -259,28 +706,9 @@ impl LiveLogsStreamComponent {
             .join("\n")
     }
 
-    fn get_scroll_position(&self, logs: &[&LogEntry]) -> usize {
-        if self.auto_scroll {
-            // Calculate total lines needed for all logs (rough estimate assuming wrapping)
-            let total_lines: usize = logs
-                .iter()
-                .map(|log| {
-                    // Estimate lines needed based on message length
-                    let msg_len = self.format_log_entry_wrapped(log, 80).len();
-                    (msg_len / 80).max(1)
-                })
-                .sum();
-
-            // Scroll to show the bottom portion of logs
-            total_lines.saturating_sub(self.max_visible_lines)
-        } else {
-            self.scroll_offset
-        }
-    }
-
     fn format_log_entry_wrapped(&self, log: &LogEntry, _available_width: u16) -> String {
         let timestamp_str = if self.show_timestamps {
-            format!("[{}] ", log.timestamp.format("%H:%M:%S"))
+            self.timestamp_format.column(log)
         } else {
             String::new()
         };
@@ -310,11 +738,18 @@ impl LiveLogsStreamComponent {
             return; // Not enough space
         }
 
-        let controls = format!(
-            "[f]Filter:{} [t]Time [↑↓]Scroll [Space]AutoScroll:{}",
-            self.filter_level.as_str(),
-            if self.auto_scroll { "ON" } else { "OFF" }
-        );
+        let controls = match &self.filter_input {
+            Some(input) => format!("Filter: {}▏ [Enter]Apply [Esc]Clear", input),
+            None => format!(
+                "[f]Filter [t]Time [A]Colors [↑↓]Scroll [Space]Follow:{} [m]Mark [[ ]]Jump {}",
+                if self.auto_scroll { "ON" } else { "OFF" },
+                if self.wrap {
+                    "[b]Wrap:ON".to_string()
+                } else {
+                    format!("[b]Wrap:OFF [←→]Col:{}", self.horizontal_offset + 1)
+                }
+            ),
+        };
 
         let hint_area = Rect {
             x: area.x + 1,
@@ -329,18 +764,26 @@ impl LiveLogsStreamComponent {
         );
     }
 
-    /// Toggle auto-scroll mode
+    /// Toggle following the newest lines; turning it back on jumps to the bottom
     pub fn toggle_auto_scroll(&mut self) {
         self.auto_scroll = !self.auto_scroll;
     }
 
-    /// Toggle timestamp display
-    pub fn toggle_timestamps(&mut self) {
-        self.show_timestamps = !self.show_timestamps;
-        // Update formatter config
-        let mut config = FormatConfig::default();
-        config.show_timestamps = self.show_timestamps;
-        self.log_formatter = SimpleLogFormatter::new(config);
+    /// Wrap long lines to the pane width, or show them on one row with sideways scrolling
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.horizontal_offset = 0;
+    }
+
+    pub fn scroll_left(&mut self) {
+        self.horizontal_offset = self.horizontal_offset.saturating_sub(HORIZONTAL_SCROLL_STEP);
+    }
+
+    /// Scroll sideways when not wrapping; rendering stops at the end of the longest line
+    pub fn scroll_right(&mut self) {
+        if !self.wrap {
+            self.horizontal_offset += HORIZONTAL_SCROLL_STEP;
+        }
     }
 
     /// Cycle through filter levels
@@ -348,6 +791,59 @@ impl LiveLogsStreamComponent {
         self.filter_level = self.filter_level.next();
     }
 
+    /// Start typing a filter pattern, starting from the current one
+    pub fn start_filter_input(&mut self) {
+        let pattern = self.text_filter.as_ref().map(|f| f.pattern().to_string());
+        self.filter_input = Some(pattern.unwrap_or_default());
+    }
+
+    pub fn is_editing_filter(&self) -> bool {
+        self.filter_input.is_some()
+    }
+
+    pub fn filter_input_char(&mut self, ch: char) {
+        if let Some(input) = &mut self.filter_input {
+            input.push(ch);
+        }
+        self.apply_filter_input();
+    }
+
+    pub fn filter_input_backspace(&mut self) {
+        if let Some(input) = &mut self.filter_input {
+            input.pop();
+        }
+        self.apply_filter_input();
+    }
+
+    /// Stop typing and keep the filter as typed
+    pub fn confirm_filter_input(&mut self) {
+        self.filter_input = None;
+    }
+
+    /// Stop typing and show every line again; the log buffer itself is never filtered
+    pub fn clear_filter(&mut self) {
+        self.filter_input = None;
+        self.set_text_filter(None);
+    }
+
+    fn apply_filter_input(&mut self) {
+        let filter = self.filter_input.as_deref().and_then(LogTextFilter::new);
+        self.set_text_filter(filter);
+    }
+
+    /// Offsets into the old filtered view mean nothing in the new one, so follow the tail
+    fn set_text_filter(&mut self, filter: Option<LogTextFilter>) {
+        self.text_filter = filter;
+        self.auto_scroll = true;
+        self.scroll_offset = 0;
+    }
+
+    /// Rendered row count of the filtered view, for the scroll handlers
+    pub fn filtered_line_count(&self, state: &AppState) -> usize {
+        let logs = self.get_session_logs(state);
+        self.entry_rows(&self.visible_entries(&logs)).iter().sum()
+    }
+
     /// Scroll up manually
     pub fn scroll_up(&mut self) {
         self.auto_scroll = false; // Disable auto-scroll when manually scrolling
@@ -357,10 +853,10 @@ impl LiveLogsStreamComponent {
     }
 
     /// Scroll down manually
-    pub fn scroll_down(&mut self, _total_logs: usize) {
+    pub fn scroll_down(&mut self, total_logs: usize) {
         self.auto_scroll = false; // Disable auto-scroll when manually scrolling
-        // No upper limit check - the Paragraph widget will handle bounds
-        self.scroll_offset += 1;
+        let max_offset = total_logs.saturating_sub(self.max_visible_lines);
+        self.scroll_offset = (self.scroll_offset + 1).min(max_offset);
     }
 
     /// Scroll to bottom
@@ -379,6 +875,154 @@ impl LiveLogsStreamComponent {
         self.scroll_offset = 0;
     }
 
+    /// Toggle a bookmark on the current line (the top visible line, or the latest when
+    /// auto-scrolling). Returns `Some(true)` if a bookmark was added, `Some(false)` if removed.
+    pub fn toggle_bookmark(&mut self, state: &AppState) -> Option<bool> {
+        let logs = self.get_session_logs(state);
+        let visible = self.visible_entries(&logs);
+        let current = self.current_entry_index(&visible)?;
+        let key = Self::bookmark_key(state);
+
+        self.prune_bookmarks(key, &logs);
+        let bookmarks = self.bookmarks.entry(key).or_default();
+        if let Some(pos) = bookmarks.iter().position(|b| b.resolve(&logs) == Some(current)) {
+            bookmarks.remove(pos);
+            Some(false)
+        } else {
+            bookmarks.push(LogBookmark::from_line(current, &logs[current]));
+            Some(true)
+        }
+    }
+
+    /// Jump to the next bookmark after the current line, wrapping around
+    pub fn next_bookmark(&mut self, state: &AppState) -> bool {
+        self.jump_to_bookmark(state, true)
+    }
+
+    /// Jump to the previous bookmark before the current line, wrapping around
+    pub fn previous_bookmark(&mut self, state: &AppState) -> bool {
+        self.jump_to_bookmark(state, false)
+    }
+
+    fn jump_to_bookmark(&mut self, state: &AppState, forward: bool) -> bool {
+        let logs = self.get_session_logs(state);
+        let visible = self.visible_entries(&logs);
+        let bookmarked = self.resolve_bookmarks(state, &logs);
+
+        // Line offset of each bookmarked entry that passes the current filter
+        let mut targets = Vec::new();
+        let mut line = 0;
+        for ((index, _), rows) in visible.iter().zip(self.entry_rows(&visible)) {
+            if bookmarked.contains(index) {
+                targets.push((*index, line));
+            }
+            line += rows;
+        }
+        if targets.is_empty() {
+            return false;
+        }
+
+        let current = self.current_entry_index(&visible).unwrap_or(0);
+        let target = if forward {
+            targets.iter().find(|(index, _)| *index > current).or_else(|| targets.first())
+        } else {
+            targets.iter().rev().find(|(index, _)| *index < current).or_else(|| targets.last())
+        };
+
+        if let Some((_, line)) = target {
+            self.auto_scroll = false;
+            self.scroll_offset = *line;
+        }
+        true
+    }
+
+    /// Command of the focused tool call: the current line if it is a tool call,
+    /// otherwise the closest one above it
+    pub fn focused_tool_command(&self, state: &AppState) -> Option<String> {
+        let logs = self.get_session_logs(state);
+        let visible = self.visible_entries(&logs);
+        let current = self.current_entry_index(&visible)?;
+        Self::tool_command_at(&visible, current)
+    }
+
+    fn tool_command_at(visible: &[(usize, &LogEntry)], current: usize) -> Option<String> {
+        visible
+            .iter()
+            .rev()
+            .filter(|(index, _)| *index <= current)
+            .filter(|(_, log)| {
+                log.metadata.get("event_type").map(String::as_str) == Some("tool_call")
+            })
+            .find_map(|(_, log)| log.metadata.get("command").cloned())
+    }
+
+    fn bookmark_key(state: &AppState) -> Option<Uuid> {
+        state.selected_session().map(|session| session.id)
+    }
+
+    /// Current indices of bookmarked lines for the selected session
+    fn resolve_bookmarks(&self, state: &AppState, logs: &[LogEntry]) -> HashSet<usize> {
+        self.bookmarks
+            .get(&Self::bookmark_key(state))
+            .map(|bookmarks| bookmarks.iter().filter_map(|b| b.resolve(logs)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Drop bookmarks whose lines have been truncated out of the buffer and re-anchor the rest
+    fn prune_bookmarks(&mut self, key: Option<Uuid>, logs: &[LogEntry]) {
+        if let Some(bookmarks) = self.bookmarks.get_mut(&key) {
+            prune_bookmarks(bookmarks, logs);
+        }
+    }
+
+    /// Log entries that pass the current filter, paired with their buffer index
+    fn visible_entries<'a>(&self, logs: &'a [LogEntry]) -> Vec<(usize, &'a LogEntry)> {
+        logs.iter().enumerate().filter(|(_, log)| self.should_include_log(log)).collect()
+    }
+
+    /// Buffer index of the entry shown at the current scroll position
+    fn current_entry_index(&self, visible: &[(usize, &LogEntry)]) -> Option<usize> {
+        if self.auto_scroll {
+            return visible.last().map(|(index, _)| *index);
+        }
+
+        let mut line = 0;
+        for ((index, _), rows) in visible.iter().zip(self.entry_rows(visible)) {
+            line += rows;
+            if self.scroll_offset < line {
+                return Some(*index);
+            }
+        }
+        visible.last().map(|(index, _)| *index)
+    }
+
+    /// Rows each visible entry takes: as wrapped in the last render, falling back to its
+    /// line count for entries that haven't been drawn yet
+    fn entry_rows(&self, visible: &[(usize, &LogEntry)]) -> Vec<usize> {
+        visible
+            .iter()
+            .enumerate()
+            .map(|(position, (_, log))| {
+                self.drawn_entry_rows
+                    .get(position)
+                    .copied()
+                    .unwrap_or_else(|| Self::entry_line_count(log))
+            })
+            .collect()
+    }
+
+    /// Number of rendered lines for an entry (mirrors `create_formatted_log_lines`)
+    fn entry_line_count(log: &LogEntry) -> usize {
+        if log.parsed_data.is_none()
+            && log.message.contains('\n')
+            && log.metadata.get("event_type").map(String::as_str) == Some("structured")
+        {
+            log.message.lines().count().max(1)
+        } else {
+            1
+        }
+    }
+
     /// Update max visible lines based on area height
     pub fn update_max_visible(&mut self, area_height: u16) {
         self.max_visible_lines = ((area_height as usize).saturating_sub(4)).max(5);
@@ -597,3 +1241,234 @@ impl LogEntry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str, seconds: i64) -> LogEntry {
+        let mut log = LogEntry::new(LogEntryLevel::Info, "test".to_string(), message.to_string());
+        log.timestamp = chrono::DateTime::from_timestamp(seconds, 0).unwrap();
+        log
+    }
+
+    #[test]
+    fn test_bookmark_resolves_after_truncation() {
+        let mut logs: Vec<LogEntry> = (0..5).map(|i| entry(&format!("line {}", i), i)).collect();
+        let bookmark = LogBookmark::from_line(3, &logs[3]);
+        assert_eq!(bookmark.resolve(&logs), Some(3));
+
+        // Oldest lines dropped from the front of the buffer
+        logs.drain(..2);
+        assert_eq!(bookmark.resolve(&logs), Some(1));
+
+        // Bookmarked line itself truncated away
+        logs.drain(..2);
+        assert_eq!(bookmark.resolve(&logs), None);
+    }
+
+    #[test]
+    fn test_current_entry_index_accounts_for_multiline_entries() {
+        let mut component = LiveLogsStreamComponent::new();
+        let logs = vec![
+            entry("first", 0),
+            entry("a\nb\nc", 1).with_metadata("event_type", "structured"),
+            entry("last", 2),
+        ];
+        let visible = component.visible_entries(&logs);
+
+        assert_eq!(component.current_entry_index(&visible), Some(2));
+
+        component.auto_scroll = false;
+        component.scroll_offset = 2;
+        assert_eq!(component.current_entry_index(&visible), Some(1));
+        component.scroll_offset = 4;
+        assert_eq!(component.current_entry_index(&visible), Some(2));
+    }
+
+    #[test]
+    fn test_wrapped_entries_count_every_row_for_scrolling() {
+        use crate::models::{Session, Workspace};
+
+        let mut state = AppState::new();
+        state.show_log_timestamps = false;
+        let session = Session::new("wrap".to_string(), "/tmp".to_string());
+        let session_id = session.id;
+        let mut workspace = Workspace::new("api".to_string(), "/tmp/api".into());
+        workspace.add_session(session);
+        state.workspaces.push(workspace);
+        state.selected_workspace_index = Some(0);
+        state.selected_session_index = Some(0);
+        let long = "x".repeat(100);
+        state.live_logs.insert(
+            session_id,
+            vec![entry("first", 0), entry(&long, 1), entry("last", 2)],
+        );
+
+        // 30 columns inside the border: the long entry's icon and message take four rows
+        let mut component = LiveLogsStreamComponent::new();
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(32, 8)).unwrap();
+        terminal.draw(|frame| component.render(frame, frame.size(), &state)).unwrap();
+        assert_eq!(component.filtered_line_count(&state), 6);
+        // Following the tail shows the last four rows
+        assert_eq!(component.scroll_offset, 2);
+
+        let logs = component.get_session_logs(&state);
+        let visible = component.visible_entries(&logs);
+        component.scroll_to_top();
+        (0..10).for_each(|_| component.scroll_down(6));
+        assert_eq!(component.scroll_offset, 2);
+        assert_eq!(component.current_entry_index(&visible), Some(1));
+        component.scroll_offset = 5;
+        assert_eq!(component.current_entry_index(&visible), Some(2));
+
+        // Unwrapped, each entry is one row and the long one scrolls sideways
+        component.toggle_wrap();
+        terminal.draw(|frame| component.render(frame, frame.size(), &state)).unwrap();
+        assert_eq!(component.filtered_line_count(&state), 3);
+        assert_eq!(component.scroll_offset, 0);
+        (0..20).for_each(|_| component.scroll_right());
+        terminal.draw(|frame| component.render(frame, frame.size(), &state)).unwrap();
+        assert_eq!(component.horizontal_offset, long.len() + 3 - 30);
+    }
+
+    #[test]
+    fn test_new_lines_counted_while_scrolled_up() {
+        let mut component = LiveLogsStreamComponent::new();
+        let key = Some(Uuid::new_v4());
+        let mut logs: Vec<LogEntry> = (0..5).map(|i| entry(&format!("line {}", i), i)).collect();
+
+        // Following: nothing is pending, however many lines arrive
+        assert_eq!(component.track_new_lines(key, &logs), 0);
+        logs.push(entry("line 5", 5));
+        assert_eq!(component.track_new_lines(key, &logs), 0);
+
+        // Scrolled up: lines arriving after the last one seen are counted
+        component.scroll_up();
+        assert_eq!(component.track_new_lines(key, &logs), 0);
+        logs.push(entry("error 6", 6));
+        logs.push(entry("line 7", 7));
+        assert_eq!(component.track_new_lines(key, &logs), 2);
+
+        // The buffer cap dropping old lines doesn't change the count, even once the
+        // anchor line itself is gone
+        logs.drain(..3);
+        assert_eq!(component.track_new_lines(key, &logs), 2);
+        logs.drain(..3);
+        assert_eq!(component.track_new_lines(key, &logs), 2);
+
+        // Only lines passing the filter count
+        component.text_filter = LogTextFilter::new("error");
+        assert_eq!(component.track_new_lines(key, &logs), 1);
+        component.text_filter = None;
+
+        // Another session starts from its own newest line
+        assert_eq!(component.track_new_lines(Some(Uuid::new_v4()), &logs), 0);
+
+        // Jumping to the bottom follows again and clears the count
+        component.scroll_up();
+        assert_eq!(component.track_new_lines(key, &logs), 0);
+        logs.push(entry("line 8", 8));
+        assert_eq!(component.track_new_lines(key, &logs), 1);
+        component.scroll_to_bottom(logs.len());
+        assert_eq!(component.track_new_lines(key, &logs), 0);
+    }
+
+    #[test]
+    fn test_timestamp_column_is_stable_width() {
+        let mut log = entry("building", 3_600);
+
+        let absolute = TimestampFormat::from_config("%H:%M:%S");
+        assert_eq!(absolute.column(&log), "01:00:00 ");
+
+        // Parsed timestamps take precedence over arrival time
+        let mut parsed = crate::components::log_parser::LogParser::new().parse_log("building");
+        parsed.timestamp = chrono::DateTime::from_timestamp(7_200, 0);
+        log.parsed_data = Some(parsed);
+        assert_eq!(absolute.column(&log), "02:00:00 ");
+
+        let relative = TimestampFormat::from_config("relative");
+        let fresh = LogEntry::new(LogEntryLevel::Info, "test".to_string(), "now".to_string());
+        assert_eq!(relative.column(&fresh), "     now ");
+        assert_eq!(relative.column(&fresh).len(), relative.column(&log).len());
+
+        assert_eq!(TimestampFormat::from_config("%Q"), TimestampFormat::default());
+    }
+
+    #[test]
+    fn test_tool_command_at_focused_line() {
+        let logs = vec![
+            entry("🔧 Bash", 0)
+                .with_metadata("event_type", "tool_call")
+                .with_metadata("command", "cargo test"),
+            entry("✅ ok", 1).with_metadata("event_type", "tool_result"),
+            entry("🔧 Grep", 2)
+                .with_metadata("event_type", "tool_call")
+                .with_metadata("command", "fn main"),
+        ];
+        let component = LiveLogsStreamComponent::new();
+        let visible = component.visible_entries(&logs);
+
+        assert_eq!(
+            LiveLogsStreamComponent::tool_command_at(&visible, 2).as_deref(),
+            Some("fn main")
+        );
+        // A non-tool line falls back to the tool call above it
+        assert_eq!(
+            LiveLogsStreamComponent::tool_command_at(&visible, 1).as_deref(),
+            Some("cargo test")
+        );
+        assert_eq!(LiveLogsStreamComponent::tool_command_at(&visible[1..], 1), None);
+    }
+
+    #[test]
+    fn test_text_filter_matches_substring_and_regex() {
+        let logs = vec![
+            entry("Compiling agents-box", 0),
+            entry("error[E0425]: cannot find value", 1),
+            entry("warning: unused import", 2),
+            entry("error[E0308]: mismatched types", 3),
+        ];
+        let mut component = LiveLogsStreamComponent::new();
+
+        component.start_filter_input();
+        for ch in "error".chars() {
+            component.filter_input_char(ch);
+        }
+        let indices = |c: &LiveLogsStreamComponent| {
+            c.visible_entries(&logs).iter().map(|(i, _)| *i).collect::<Vec<_>>()
+        };
+        assert_eq!(indices(&component), vec![1, 3]);
+
+        component.clear_filter();
+        component.start_filter_input();
+        for ch in r"E0\d{3}.*types|unused".chars() {
+            component.filter_input_char(ch);
+        }
+        component.confirm_filter_input();
+        assert!(component.text_filter.as_ref().unwrap().is_regex());
+        assert_eq!(indices(&component), vec![2, 3]);
+
+        // Clearing the filter shows the whole, untouched buffer again
+        component.clear_filter();
+        assert_eq!(indices(&component), vec![0, 1, 2, 3]);
+
+        let filter = LogTextFilter::new("ab").unwrap();
+        assert_eq!(filter.match_ranges("xabyab"), vec![1..3, 4..6]);
+        let line = filter.highlight(Line::from("xaby"));
+        let contents: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(contents, vec!["x", "ab", "y"]);
+        assert_eq!(line.spans[1].style.bg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn test_invalid_regex_falls_back_to_substring() {
+        let filter = LogTextFilter::new("call foo(").unwrap();
+        assert!(!filter.is_regex());
+        assert!(filter.is_match("about to call foo(bar)"));
+        assert!(!filter.is_match("call foo bar"));
+        assert_eq!(filter.match_ranges("call foo(x)"), vec![0..9]);
+
+        assert!(LogTextFilter::new("").is_none());
+    }
+}
@@ -1,4 +1,5 @@
 // ABOUTME: Logs viewer component for displaying container logs and session information
+// The full-screen view searches a session's logs with '/' and jumps between matches and bookmarks
 
 #![allow(dead_code)]
 
@@ -8,14 +9,414 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, ListItem, Paragraph, Wrap},
 };
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
+use super::line_wrap::{self, HORIZONTAL_SCROLL_STEP};
+use super::live_logs_stream::{LogBookmark, bookmark_gutter, prune_bookmarks};
 use crate::app::AppState;
 
-pub struct LogsViewerComponent;
+pub struct LogsViewerComponent {
+    search_input: Option<String>, // Search term being typed
+    query: String,                // Last confirmed search term (empty = no search)
+    case_sensitive: bool,
+    matches: Vec<usize>, // Indices of lines containing the query
+    current_match: Option<usize>, // Index into `matches`
+    scroll_offset: usize, // Index of the top line
+    viewport_height: usize,
+    viewport_width: usize,
+    wrap: bool,               // Wrap long lines to the view width; otherwise scroll sideways
+    horizontal_offset: usize, // First column shown when not wrapping
+    bookmarks: HashMap<Uuid, Vec<LogBookmark>>, // Keyed by session; kept across reopening
+}
 
 impl LogsViewerComponent {
     pub fn new() -> Self {
-        Self
+        Self {
+            search_input: None,
+            query: String::new(),
+            case_sensitive: false,
+            matches: Vec::new(),
+            current_match: None,
+            scroll_offset: 0,
+            viewport_height: 20,
+            viewport_width: 80,
+            wrap: true,
+            horizontal_offset: 0,
+            bookmarks: HashMap::new(),
+        }
+    }
+
+    /// Start fresh each time the full-screen view is opened, keeping bookmarks
+    pub fn reset(&mut self) {
+        let bookmarks = std::mem::take(&mut self.bookmarks);
+        *self = Self {
+            bookmarks,
+            ..Self::new()
+        };
+    }
+
+    /// The selected session's creation logs followed by its live logs, as plain text
+    pub fn session_lines(state: &AppState) -> Vec<String> {
+        let Some(session) = state.selected_session() else {
+            return Vec::new();
+        };
+        let build_logs = state.logs.get(&session.id).map(Vec::as_slice).unwrap_or_default();
+        let live_logs = state.live_logs.get(&session.id).map(Vec::as_slice).unwrap_or_default();
+        crate::app::log_export::format_export(build_logs, live_logs, false)
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    fn search_regex(query: &str, case_sensitive: bool) -> Option<Regex> {
+        if query.is_empty() {
+            return None;
+        }
+        RegexBuilder::new(&regex::escape(query)).case_insensitive(!case_sensitive).build().ok()
+    }
+
+    /// Indices of the lines containing `query`
+    pub fn find_matches(lines: &[String], query: &str, case_sensitive: bool) -> Vec<usize> {
+        let Some(regex) = Self::search_regex(query, case_sensitive) else {
+            return Vec::new();
+        };
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| regex.is_match(line))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn start_search(&mut self) {
+        self.search_input = Some(self.query.clone());
+    }
+
+    pub fn is_editing_search(&self) -> bool {
+        self.search_input.is_some()
+    }
+
+    pub fn search_input_char(&mut self, ch: char) {
+        if let Some(input) = &mut self.search_input {
+            input.push(ch);
+        }
+    }
+
+    pub fn search_input_backspace(&mut self) {
+        if let Some(input) = &mut self.search_input {
+            input.pop();
+        }
+    }
+
+    pub fn cancel_search_input(&mut self) {
+        self.search_input = None;
+    }
+
+    /// Search for the typed term and jump to the first match at or below the top of the view
+    pub fn confirm_search(&mut self, lines: &[String]) {
+        let Some(query) = self.search_input.take() else {
+            return;
+        };
+        self.query = query;
+        self.matches = Self::find_matches(lines, &self.query, self.case_sensitive);
+        self.current_match = if self.matches.is_empty() {
+            None
+        } else {
+            let first_visible = self.matches.iter().position(|line| *line >= self.scroll_offset);
+            Some(first_visible.unwrap_or(0))
+        };
+        self.center_on_current_match();
+    }
+
+    pub fn toggle_case_sensitive(&mut self, lines: &[String]) {
+        self.case_sensitive = !self.case_sensitive;
+        let current_line = self.current_match_line();
+        self.matches = Self::find_matches(lines, &self.query, self.case_sensitive);
+        self.current_match = match current_line {
+            _ if self.matches.is_empty() => None,
+            Some(line) => Some(self.matches.iter().position(|m| *m >= line).unwrap_or(0)),
+            None => Some(0),
+        };
+        self.center_on_current_match();
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Jump to the next match, wrapping to the first. False when nothing matches.
+    pub fn next_match(&mut self) -> bool {
+        if self.matches.is_empty() {
+            return false;
+        }
+        self.current_match = Some(match self.current_match {
+            Some(index) => (index + 1) % self.matches.len(),
+            None => 0,
+        });
+        self.center_on_current_match();
+        true
+    }
+
+    /// Jump to the previous match, wrapping to the last. False when nothing matches.
+    pub fn previous_match(&mut self) -> bool {
+        if self.matches.is_empty() {
+            return false;
+        }
+        let last = self.matches.len() - 1;
+        self.current_match = Some(match self.current_match {
+            Some(0) | None => last,
+            Some(index) => index - 1,
+        });
+        self.center_on_current_match();
+        true
+    }
+
+    pub fn current_match_line(&self) -> Option<usize> {
+        self.current_match.and_then(|index| self.matches.get(index).copied())
+    }
+
+    fn center_on_current_match(&mut self) {
+        if let Some(line) = self.current_match_line() {
+            self.scroll_offset = line.saturating_sub(self.viewport_height / 2);
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self, lines: &[String]) {
+        self.scroll_offset = (self.scroll_offset + 1).min(self.max_scroll_offset(lines));
+    }
+
+    /// Last top line that still fills the view, counting each wrapped line's rows
+    fn max_scroll_offset(&self, lines: &[String]) -> usize {
+        if !self.wrap {
+            return lines.len().saturating_sub(self.viewport_height);
+        }
+        let row_counts: Vec<usize> = lines
+            .iter()
+            .map(|line| line_wrap::row_count(&Line::from(line.as_str()), self.viewport_width))
+            .collect();
+        line_wrap::max_top_line(&row_counts, self.viewport_height)
+    }
+
+    /// Toggle a bookmark on the top visible line of the selected session's logs.
+    /// Returns `Some(true)` if a bookmark was added, `Some(false)` if removed.
+    pub fn toggle_bookmark(&mut self, state: &AppState) -> Option<bool> {
+        let session_id = state.selected_session()?.id;
+        self.toggle_bookmark_in(session_id, &Self::session_lines(state))
+    }
+
+    fn toggle_bookmark_in(&mut self, session_id: Uuid, lines: &[String]) -> Option<bool> {
+        let current = self.scroll_offset;
+        let line = lines.get(current)?;
+
+        let bookmarks = self.bookmarks.entry(session_id).or_default();
+        prune_bookmarks(bookmarks, lines);
+        if let Some(pos) = bookmarks.iter().position(|bookmark| bookmark.index == current) {
+            bookmarks.remove(pos);
+            Some(false)
+        } else {
+            bookmarks.push(LogBookmark::from_line(current, line));
+            Some(true)
+        }
+    }
+
+    /// Scroll the next bookmark after the top line into view, wrapping around.
+    /// False when the session has no bookmarks.
+    pub fn next_bookmark(&mut self, state: &AppState) -> bool {
+        let Some(session) = state.selected_session() else {
+            return false;
+        };
+        self.jump_to_bookmark(session.id, &Self::session_lines(state), true)
+    }
+
+    /// Scroll the previous bookmark before the top line into view, wrapping around
+    pub fn previous_bookmark(&mut self, state: &AppState) -> bool {
+        let Some(session) = state.selected_session() else {
+            return false;
+        };
+        self.jump_to_bookmark(session.id, &Self::session_lines(state), false)
+    }
+
+    fn jump_to_bookmark(&mut self, session_id: Uuid, lines: &[String], forward: bool) -> bool {
+        let mut targets: Vec<usize> =
+            self.bookmarked_lines(session_id, lines).into_iter().collect();
+        targets.sort_unstable();
+
+        let current = self.scroll_offset;
+        let target = if forward {
+            targets.iter().find(|line| **line > current).or_else(|| targets.first())
+        } else {
+            targets.iter().rev().find(|line| **line < current).or_else(|| targets.last())
+        };
+        match target {
+            Some(line) => {
+                self.scroll_offset = *line;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Current indices of the session's bookmarked lines
+    fn bookmarked_lines(&self, session_id: Uuid, lines: &[String]) -> HashSet<usize> {
+        self.bookmarks
+            .get(&session_id)
+            .map(|bookmarks| bookmarks.iter().filter_map(|b| b.resolve(lines)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Wrap long lines to the view width, or show them on one row with sideways scrolling
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.horizontal_offset = 0;
+    }
+
+    pub fn scroll_left(&mut self) {
+        self.horizontal_offset = self.horizontal_offset.saturating_sub(HORIZONTAL_SCROLL_STEP);
+    }
+
+    /// Scroll sideways when not wrapping; rendering stops at the end of the longest line
+    pub fn scroll_right(&mut self) {
+        if !self.wrap {
+            self.horizontal_offset += HORIZONTAL_SCROLL_STEP;
+        }
+    }
+
+    /// Full-screen view of the selected session's logs with search
+    pub fn render_full(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        let Some(session) = state.selected_session() else {
+            self.render_empty_state(frame, area);
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Session info
+                Constraint::Min(0),    // Log lines
+                Constraint::Length(1), // Search input or hints
+            ])
+            .split(area);
+        self.render_session_info(frame, chunks[0], state, session);
+
+        // Logs keep growing while the view is open, so matches follow them
+        let lines = Self::session_lines(state);
+        self.matches = Self::find_matches(&lines, &self.query, self.case_sensitive);
+        if self.current_match.is_some_and(|index| index >= self.matches.len()) {
+            self.current_match = self.matches.len().checked_sub(1);
+        }
+
+        self.viewport_height = chunks[1].height.saturating_sub(2).max(1) as usize;
+        self.viewport_width = chunks[1].width.saturating_sub(2).max(1) as usize;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset(&lines));
+
+        let regex = Self::search_regex(&self.query, self.case_sensitive);
+        let current_line = self.current_match_line();
+        let bookmarked = self.bookmarked_lines(session.id, &lines);
+        let highlighted = lines.iter().enumerate().skip(self.scroll_offset).map(|(index, line)| {
+            let mut line = Self::highlight_line(line, regex.as_ref(), current_line == Some(index));
+            // Marker gutter for bookmarked lines
+            if !bookmarked.is_empty() {
+                line.spans.insert(0, bookmark_gutter(bookmarked.contains(&index)));
+            }
+            line
+        });
+        let visible: Vec<Line> = if self.wrap {
+            highlighted
+                .flat_map(|line| line_wrap::wrap_line(&line, self.viewport_width))
+                .take(self.viewport_height)
+                .collect()
+        } else {
+            let longest = lines.iter().map(|line| Line::from(line.as_str()).width()).max();
+            let max_offset = longest.unwrap_or(0).saturating_sub(self.viewport_width);
+            self.horizontal_offset = self.horizontal_offset.min(max_offset);
+            highlighted.take(self.viewport_height).collect()
+        };
+
+        let marks = match bookmarked.len() {
+            0 => String::new(),
+            count => format!("🔖{} ", count),
+        };
+        let title = format!(" Logs: {} ({} lines) {}", session.branch_name, lines.len(), marks);
+        let body = Paragraph::new(visible)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((0, self.horizontal_offset as u16));
+        frame.render_widget(body, chunks[1]);
+
+        frame.render_widget(self.footer_line(), chunks[2]);
+    }
+
+    fn highlight_line<'a>(line: &'a str, regex: Option<&Regex>, is_current: bool) -> Line<'a> {
+        let Some(regex) = regex else {
+            return Line::from(line);
+        };
+        let match_style = if is_current {
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        };
+
+        let mut spans = Vec::new();
+        let mut last_end = 0;
+        for found in regex.find_iter(line) {
+            if found.start() > last_end {
+                spans.push(Span::raw(&line[last_end..found.start()]));
+            }
+            spans.push(Span::styled(found.as_str(), match_style));
+            last_end = found.end();
+        }
+        if last_end < line.len() {
+            spans.push(Span::raw(&line[last_end..]));
+        }
+        Line::from(spans)
+    }
+
+    fn footer_line(&self) -> Paragraph<'_> {
+        let case = if self.case_sensitive { "Aa" } else { "aa" };
+        if let Some(input) = &self.search_input {
+            return Paragraph::new(Line::from(vec![
+                Span::styled(" /", Style::default().fg(Color::Yellow)),
+                Span::raw(input.as_str()),
+                Span::styled("▏", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("  [{}] [Enter]Search [Esc]Cancel", case),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        let match_info = match (self.query.is_empty(), self.current_match) {
+            (true, _) => String::new(),
+            (false, Some(index)) => {
+                format!(" \"{}\" {}/{} ", self.query, index + 1, self.matches.len())
+            }
+            (false, None) => format!(" \"{}\" no matches ", self.query),
+        };
+        let wrap = if self.wrap {
+            "[b]Wrap:ON".to_string()
+        } else {
+            format!("[b]Wrap:OFF [←→]Col:{}", self.horizontal_offset + 1)
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled(match_info, Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!(
+                    " [/]Search [n/N]Next/Prev [c]Case:{} [m]Bookmark [↑↓]Scroll {} [Esc]Back",
+                    case, wrap
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect, state: &AppState) {
@@ -49,7 +450,7 @@ impl LogsViewerComponent {
         };
 
         // Build spans with colored status
-        let info_spans = vec![
+        let mut info_spans = vec![
             Span::styled(" ", Style::default()),
             Span::styled(&session.name, Style::default().fg(Color::White)),
             Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
@@ -62,6 +463,19 @@ impl LogsViewerComponent {
             Span::styled(&session.branch_name, Style::default().fg(Color::Cyan)),
         ];
 
+        // Full container ID (the status bar only shows a short prefix); copied with 'i'
+        info_spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+        match &session.container_id {
+            Some(container_id) => info_spans.push(Span::styled(
+                format!("🐳 {}", container_id),
+                Style::default().fg(Color::Gray),
+            )),
+            None => info_spans.push(Span::styled(
+                "🐳 no container",
+                Style::default().fg(Color::DarkGray),
+            )),
+        }
+
         let info_line = Line::from(info_spans);
 
         let info_paragraph = Paragraph::new(info_line)
@@ -152,3 +566,129 @@ impl Default for LogsViewerComponent {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_matches_case_sensitivity() {
+        let log = lines(&["Error: disk full", "ok", "retrying after error", "ERROR again", "a.b"]);
+
+        assert_eq!(LogsViewerComponent::find_matches(&log, "error", false), vec![0, 2, 3]);
+        assert_eq!(LogsViewerComponent::find_matches(&log, "error", true), vec![2]);
+        assert_eq!(LogsViewerComponent::find_matches(&log, "", false), Vec::<usize>::new());
+        // The term is literal text, not a pattern
+        assert_eq!(LogsViewerComponent::find_matches(&log, ".", false), vec![4]);
+    }
+
+    #[test]
+    fn test_next_and_previous_match_wrap_around() {
+        let log: Vec<String> = (0..100)
+            .map(|i| if i % 30 == 5 { format!("match {}", i) } else { format!("line {}", i) })
+            .collect();
+        let mut viewer = LogsViewerComponent::new();
+        viewer.viewport_height = 10;
+
+        assert!(!viewer.next_match());
+
+        viewer.start_search();
+        "MATCH".chars().for_each(|ch| viewer.search_input_char(ch));
+        viewer.confirm_search(&log);
+        assert_eq!(viewer.matches, vec![5, 35, 65, 95]);
+        assert_eq!(viewer.current_match_line(), Some(5));
+        assert_eq!(viewer.scroll_offset, 0);
+
+        assert!(viewer.next_match());
+        assert_eq!(viewer.current_match_line(), Some(35));
+        // Centered in the 10-line viewport
+        assert_eq!(viewer.scroll_offset, 30);
+
+        viewer.next_match();
+        viewer.next_match();
+        assert_eq!(viewer.current_match_line(), Some(95));
+        viewer.next_match();
+        assert_eq!(viewer.current_match_line(), Some(5));
+
+        viewer.previous_match();
+        assert_eq!(viewer.current_match_line(), Some(95));
+        viewer.previous_match();
+        assert_eq!(viewer.current_match_line(), Some(65));
+
+        // Case-sensitive "MATCH" finds nothing; insensitive again finds the lines
+        viewer.toggle_case_sensitive(&log);
+        assert!(viewer.is_case_sensitive());
+        assert_eq!(viewer.current_match_line(), None);
+        assert!(!viewer.previous_match());
+        viewer.toggle_case_sensitive(&log);
+        assert_eq!(viewer.current_match_line(), Some(5));
+    }
+
+    #[test]
+    fn test_bookmarks_jump_and_survive_truncation() {
+        let session_id = Uuid::new_v4();
+        let mut log: Vec<String> = (0..50).map(|i| format!("line {}", i)).collect();
+        let mut viewer = LogsViewerComponent::new();
+        viewer.viewport_height = 10;
+
+        assert!(!viewer.jump_to_bookmark(session_id, &log, true));
+        viewer.scroll_offset = 10;
+        assert_eq!(viewer.toggle_bookmark_in(session_id, &log), Some(true));
+        viewer.scroll_offset = 30;
+        assert_eq!(viewer.toggle_bookmark_in(session_id, &log), Some(true));
+
+        viewer.scroll_offset = 0;
+        assert!(viewer.jump_to_bookmark(session_id, &log, true));
+        assert_eq!(viewer.scroll_offset, 10);
+        viewer.jump_to_bookmark(session_id, &log, true);
+        assert_eq!(viewer.scroll_offset, 30);
+        viewer.jump_to_bookmark(session_id, &log, true);
+        assert_eq!(viewer.scroll_offset, 10);
+        viewer.jump_to_bookmark(session_id, &log, false);
+        assert_eq!(viewer.scroll_offset, 30);
+
+        // Older lines dropped: the bookmarks follow their lines, and reopening keeps them
+        log.drain(..5);
+        viewer.reset();
+        viewer.jump_to_bookmark(session_id, &log, true);
+        assert_eq!(viewer.scroll_offset, 5);
+        assert_eq!(log[5], "line 10");
+
+        // Toggling a bookmarked line removes it; other sessions have their own
+        assert_eq!(viewer.toggle_bookmark_in(session_id, &log), Some(false));
+        assert_eq!(viewer.bookmarked_lines(session_id, &log), HashSet::from([25]));
+        assert!(viewer.bookmarked_lines(Uuid::new_v4(), &log).is_empty());
+    }
+
+    #[test]
+    fn test_scroll_down_stops_when_wrapped_lines_fill_the_view() {
+        let mut log = lines(&["short"; 10]);
+        log[9] = "x".repeat(25); // Three rows at width 10
+        let mut viewer = LogsViewerComponent::new();
+        viewer.viewport_height = 4;
+        viewer.viewport_width = 10;
+
+        // Lines 8 and 9 take all four rows, so 8 is the last top line
+        (0..20).for_each(|_| viewer.scroll_down(&log));
+        assert_eq!(viewer.scroll_offset, 8);
+
+        // Unwrapped, every line is one row and the last four fill the view
+        viewer.toggle_wrap();
+        (0..20).for_each(|_| viewer.scroll_down(&log));
+        assert_eq!(viewer.scroll_offset, 6);
+
+        viewer.scroll_right();
+        viewer.scroll_right();
+        assert_eq!(viewer.horizontal_offset, 2 * HORIZONTAL_SCROLL_STEP);
+        viewer.scroll_left();
+        assert_eq!(viewer.horizontal_offset, HORIZONTAL_SCROLL_STEP);
+        // Wrapping again drops the sideways scroll
+        viewer.toggle_wrap();
+        viewer.scroll_right();
+        assert_eq!(viewer.horizontal_offset, 0);
+    }
+}
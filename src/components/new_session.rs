@@ -3,9 +3,10 @@
 use ratatui::{
     prelude::*,
     style::{Color, Modifier, Style},
-    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph},
 };
 
+use super::fuzzy_file_finder::PREVIEW_LINES;
 use crate::app::{
     AppState,
     state::{NewSessionState, NewSessionStep},
@@ -13,15 +14,33 @@ use crate::app::{
 
 pub struct NewSessionComponent {
     search_list_state: ListState,
+    prompt_wrap_width: Option<usize>, // Row width of the last soft-wrapped prompt render
+}
+
+/// Contents of a step that collects a list of container settings one entry at a time
+struct EntryListStep<'a> {
+    title: &'a str,
+    subtitle: &'a str,
+    empty: &'a str, // Shown in place of the list until an entry is added
+    entries: Vec<Line<'a>>,
+    input: &'a str,
+    input_title: &'a str,
+    error: Option<&'a str>,
 }
 
 impl NewSessionComponent {
     pub fn new() -> Self {
         Self {
             search_list_state: ListState::default(),
+            prompt_wrap_width: None,
         }
     }
 
+    /// Width the boss prompt was last wrapped at, for moving the cursor by visual rows
+    pub fn prompt_wrap_width(&self) -> Option<usize> {
+        self.prompt_wrap_width
+    }
+
     pub fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
         if let Some(ref session_state) = state.new_session_state {
             // Create a centered popup
@@ -41,16 +60,34 @@ impl NewSessionComponent {
                 NewSessionStep::InputBranch => {
                     self.render_branch_input(frame, popup_area, session_state)
                 }
+                NewSessionStep::SelectBaseBranch => {
+                    self.render_base_branch_selection(frame, popup_area, session_state)
+                }
                 NewSessionStep::SelectMode => {
                     self.render_mode_selection(frame, popup_area, session_state)
                 }
-                NewSessionStep::InputPrompt => {
-                    self.render_prompt_input(frame, popup_area, session_state)
-                }
+                NewSessionStep::InputPrompt => self.render_prompt_input(
+                    frame,
+                    popup_area,
+                    session_state,
+                    state.prompt_soft_wrap,
+                    state.prompt_char_limits,
+                ),
                 NewSessionStep::ConfigurePermissions => {
                     self.render_permissions_config(frame, popup_area, session_state)
                 }
-                NewSessionStep::Creating => self.render_creating(frame, popup_area),
+                NewSessionStep::ConfigureEnv => {
+                    self.render_env_config(frame, popup_area, session_state)
+                }
+                NewSessionStep::ConfigureVolumes => {
+                    self.render_volumes_config(frame, popup_area, session_state)
+                }
+                NewSessionStep::ConfigurePorts => {
+                    self.render_ports_config(frame, popup_area, session_state)
+                }
+                NewSessionStep::Creating => {
+                    self.render_creating(frame, popup_area, state.build_progress.as_ref());
+                }
             }
         }
     }
@@ -375,13 +412,21 @@ impl NewSessionComponent {
     }
 
     fn render_branch_input(&self, frame: &mut Frame, area: Rect, session_state: &NewSessionState) {
+        // Review sessions reuse this step to ask for the revision to check out
+        let (title, input_label, input_hint, placeholder) = if session_state.review_mode {
+            (" 🔍 Review Session ", " Revision ", " branch, tag, commit or pull/N/head  ", "origin/main")
+        } else {
+            let prefix = session_state.branch_prefix.as_str();
+            (" 🌿 New Session ", " Branch Name ", " branch name  ", prefix)
+        };
+
         // Draw outer border with modern styling
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(Style::default().fg(Color::Rgb(100, 149, 237))) // Cornflower blue
             .title(Span::styled(
-                " 🌿 New Session ",
+                title,
                 Style::default()
                     .fg(Color::Rgb(255, 215, 0)) // Gold
                     .add_modifier(Modifier::BOLD),
@@ -467,7 +512,7 @@ impl NewSessionComponent {
             Line::from(vec![
                 Span::styled("  🔀 ", Style::default().fg(Color::Rgb(100, 200, 100))),
                 Span::styled(
-                    "agents-in-a-box/",
+                    placeholder,
                     Style::default().fg(Color::Rgb(128, 128, 128)).add_modifier(Modifier::ITALIC),
                 ),
                 Span::styled("█", Style::default().fg(Color::Rgb(100, 200, 100))),
@@ -483,32 +528,68 @@ impl NewSessionComponent {
             ])
         };
 
+        let error_red = Color::Rgb(255, 100, 100);
+        let border_color = if session_state.branch_error.is_some() {
+            error_red
+        } else {
+            Color::Rgb(100, 200, 100) // Green border
+        };
         let branch_input = Paragraph::new(branch_text)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_type(ratatui::widgets::BorderType::Rounded)
-                    .border_style(Style::default().fg(Color::Rgb(100, 200, 100))) // Green border
+                    .border_style(Style::default().fg(border_color))
                     .title(Span::styled(
-                        " Branch Name ",
+                        input_label,
                         Style::default().fg(Color::Rgb(100, 200, 100)),
                     ))
                     .style(Style::default().bg(Color::Rgb(35, 35, 45))),
             );
         frame.render_widget(branch_input, chunks[2]);
 
-        // Styled instructions footer
-        let instructions = Line::from(vec![
-            Span::styled("  ⌨️  ", Style::default()),
-            Span::styled("Type", Style::default().fg(Color::Rgb(100, 200, 100))),
-            Span::styled(" branch name  ", Style::default().fg(Color::Rgb(128, 128, 128))),
-            Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
-            Span::styled("  ⏎ ", Style::default().fg(Color::Rgb(100, 200, 100))),
-            Span::styled("Create Session  ", Style::default().fg(Color::Rgb(128, 128, 128))),
-            Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
-            Span::styled("  Esc ", Style::default().fg(Color::Rgb(255, 100, 100))),
-            Span::styled("Cancel  ", Style::default().fg(Color::Rgb(128, 128, 128))),
-        ]);
+        if let Some(ref error) = session_state.branch_error {
+            let error_line = Paragraph::new(Line::from(Span::styled(
+                format!("✗ {}", error),
+                Style::default().fg(error_red),
+            )))
+            .alignment(Alignment::Center);
+            frame.render_widget(error_line, chunks[3]);
+        }
+
+        // Styled instructions footer; a branch conflict swaps in the ways to resolve it
+        let instructions = if let Some(ref conflict) = session_state.branch_conflict {
+            let mut spans = vec![
+                Span::styled("  Tab ", Style::default().fg(Color::Rgb(100, 200, 100))),
+                Span::styled("Add suffix  ", Style::default().fg(Color::Rgb(128, 128, 128))),
+            ];
+            if conflict.session_id.is_some() {
+                spans.extend([
+                    Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
+                    Span::styled("  Ctrl+R ", Style::default().fg(Color::Rgb(100, 200, 100))),
+                    Span::styled("Reuse session  ", Style::default().fg(Color::Rgb(128, 128, 128))),
+                ]);
+            }
+            spans.extend([
+                Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
+                Span::styled("  ⌨️  ", Style::default()),
+                Span::styled("Type", Style::default().fg(Color::Rgb(100, 200, 100))),
+                Span::styled(" to edit  ", Style::default().fg(Color::Rgb(128, 128, 128))),
+            ]);
+            Line::from(spans)
+        } else {
+            Line::from(vec![
+                Span::styled("  ⌨️  ", Style::default()),
+                Span::styled("Type", Style::default().fg(Color::Rgb(100, 200, 100))),
+                Span::styled(input_hint, Style::default().fg(Color::Rgb(128, 128, 128))),
+                Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
+                Span::styled("  ⏎ ", Style::default().fg(Color::Rgb(100, 200, 100))),
+                Span::styled("Create Session  ", Style::default().fg(Color::Rgb(128, 128, 128))),
+                Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
+                Span::styled("  Esc ", Style::default().fg(Color::Rgb(255, 100, 100))),
+                Span::styled("Cancel  ", Style::default().fg(Color::Rgb(128, 128, 128))),
+            ])
+        };
 
         let instructions_widget = Paragraph::new(instructions)
             .alignment(Alignment::Center)
@@ -655,21 +736,260 @@ impl NewSessionComponent {
         frame.render_widget(options, chunks[2]);
 
         // Modern footer with keyboard hints
-        let footer = Paragraph::new(Line::from(vec![
+        let mut footer_spans = vec![
             Span::styled("Space", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
             Span::styled(" Toggle", Style::default().fg(muted_gray)),
             Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+        ];
+        // Environment variables and mounts go to the session container, so only Boss mode
+        // offers them
+        if session_state.mode == crate::models::SessionMode::Boss {
+            footer_spans.extend([
+                Span::styled("e", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" Env vars ({})", session_state.env_vars.len()),
+                    Style::default().fg(muted_gray),
+                ),
+                Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+                Span::styled("v", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" Mounts ({})", session_state.volumes.len()),
+                    Style::default().fg(muted_gray),
+                ),
+                Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+                Span::styled("p", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" Ports ({})", session_state.ports.len()),
+                    Style::default().fg(muted_gray),
+                ),
+                Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+            ]);
+        }
+        footer_spans.extend([
             Span::styled("Enter", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
             Span::styled(" Continue", Style::default().fg(muted_gray)),
             Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
             Span::styled("Esc", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
             Span::styled(" Cancel", Style::default().fg(muted_gray)),
+        ]);
+        let footer = Paragraph::new(Line::from(footer_spans)).alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[3]);
+    }
+
+    fn render_env_config(&self, frame: &mut Frame, area: Rect, session_state: &NewSessionState) {
+        let cornflower_blue = Color::Rgb(100, 149, 237);
+        let soft_white = Color::Rgb(220, 220, 230);
+        let muted_gray = Color::Rgb(120, 120, 140);
+
+        let entries = session_state
+            .env_vars
+            .iter()
+            .map(|(key, value)| {
+                Line::from(vec![
+                    Span::styled(format!("  {}", key), Style::default().fg(cornflower_blue)),
+                    Span::styled("=", Style::default().fg(muted_gray)),
+                    Span::styled(value.clone(), Style::default().fg(soft_white)),
+                ])
+            })
+            .collect();
+        self.render_entry_list_step(
+            frame,
+            area,
+            EntryListStep {
+                title: " 🌱 Environment Variables",
+                subtitle: "Set in the session container and reused when the session is restarted",
+                empty: "  No variables yet",
+                entries,
+                input: &session_state.list_entry.text,
+                input_title: " KEY=VALUE ",
+                error: session_state.list_entry.error.as_deref(),
+            },
+        );
+    }
+
+    fn render_volumes_config(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        session_state: &NewSessionState,
+    ) {
+        let cornflower_blue = Color::Rgb(100, 149, 237);
+        let soft_white = Color::Rgb(220, 220, 230);
+        let muted_gray = Color::Rgb(120, 120, 140);
+
+        let entries = session_state
+            .volumes
+            .iter()
+            .map(|(host_path, container_path, read_only)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("  {}", host_path.display()),
+                        Style::default().fg(soft_white),
+                    ),
+                    Span::styled(" → ", Style::default().fg(muted_gray)),
+                    Span::styled(container_path.clone(), Style::default().fg(cornflower_blue)),
+                    Span::styled(
+                        if *read_only { "  read-only" } else { "" },
+                        Style::default().fg(muted_gray),
+                    ),
+                ])
+            })
+            .collect();
+        self.render_entry_list_step(
+            frame,
+            area,
+            EntryListStep {
+                title: " 📂 Extra Mounts",
+                subtitle: "Host directories mounted in the session container, kept for restarts",
+                empty: "  No extra mounts yet",
+                entries,
+                input: &session_state.list_entry.text,
+                input_title: " HOST:CONTAINER[:ro] ",
+                error: session_state.list_entry.error.as_deref(),
+            },
+        );
+    }
+
+    fn render_ports_config(&self, frame: &mut Frame, area: Rect, session_state: &NewSessionState) {
+        let cornflower_blue = Color::Rgb(100, 149, 237);
+        let soft_white = Color::Rgb(220, 220, 230);
+        let muted_gray = Color::Rgb(120, 120, 140);
+
+        let entries = session_state
+            .ports
+            .iter()
+            .map(|(host_port, container_port)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("  localhost:{}", host_port),
+                        Style::default().fg(soft_white),
+                    ),
+                    Span::styled(" → ", Style::default().fg(muted_gray)),
+                    Span::styled(container_port.to_string(), Style::default().fg(cornflower_blue)),
+                ])
+            })
+            .collect();
+        self.render_entry_list_step(
+            frame,
+            area,
+            EntryListStep {
+                title: " 🔌 Published Ports",
+                subtitle: "Container ports reachable from the host, besides the config defaults",
+                empty: "  No ports yet",
+                entries,
+                input: &session_state.list_entry.text,
+                input_title: " HOST:CONTAINER or PORT ",
+                error: session_state.list_entry.error.as_deref(),
+            },
+        );
+    }
+
+    /// A list of added entries above an input line, as used by the container settings steps
+    fn render_entry_list_step(&self, frame: &mut Frame, area: Rect, step: EntryListStep) {
+        let cornflower_blue = Color::Rgb(100, 149, 237);
+        let dark_bg = Color::Rgb(25, 25, 35);
+        let gold = Color::Rgb(255, 215, 0);
+        let soft_white = Color::Rgb(220, 220, 230);
+        let muted_gray = Color::Rgb(120, 120, 140);
+        let error_red = Color::Rgb(230, 100, 100);
+
+        let background = Block::default().style(Style::default().bg(dark_bg));
+        frame.render_widget(background, area);
+
+        let title_line = Line::from(vec![
+            Span::styled(step.title, Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" ", Style::default()),
+        ]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(cornflower_blue))
+            .title(title_line)
+            .title_alignment(Alignment::Center)
+            .style(Style::default().bg(dark_bg));
+        frame.render_widget(block.clone(), area);
+        let inner = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(2), // Subtitle
+                Constraint::Min(3),    // Entries
+                Constraint::Length(3), // Input
+                Constraint::Length(1), // Error
+                Constraint::Length(2), // Footer
+            ])
+            .split(inner);
+
+        let subtitle = Paragraph::new(Line::from(vec![Span::styled(
+            step.subtitle,
+            Style::default().fg(muted_gray),
+        )]))
+        .alignment(Alignment::Center);
+        frame.render_widget(subtitle, chunks[0]);
+
+        let items: Vec<ListItem> = if step.entries.is_empty() {
+            vec![ListItem::new(Span::styled(
+                step.empty,
+                Style::default().fg(muted_gray).add_modifier(Modifier::ITALIC),
+            ))]
+        } else {
+            step.entries.into_iter().map(ListItem::new).collect()
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Rgb(60, 60, 80)))
+                .style(Style::default().bg(dark_bg)),
+        );
+        frame.render_widget(list, chunks[1]);
+
+        let input_color = if step.error.is_some() { error_red } else { gold };
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled(step.input.to_string(), Style::default().fg(soft_white)),
+            Span::styled("▏", Style::default().fg(input_color)),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(input_color))
+                .title(Span::styled(step.input_title, Style::default().fg(input_color)))
+                .style(Style::default().bg(dark_bg)),
+        );
+        frame.render_widget(input, chunks[2]);
+
+        if let Some(error) = step.error {
+            let error_line = Paragraph::new(Span::styled(
+                format!("  ✗ {}", error),
+                Style::default().fg(error_red),
+            ));
+            frame.render_widget(error_line, chunks[3]);
+        }
+
+        let footer = Paragraph::new(Line::from(vec![
+            Span::styled("Enter", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" Add (empty: done)", Style::default().fg(muted_gray)),
+            Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+            Span::styled("Backspace", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" Delete (empty: remove last)", Style::default().fg(muted_gray)),
+            Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+            Span::styled("Esc", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" Back", Style::default().fg(muted_gray)),
         ]))
         .alignment(Alignment::Center);
-        frame.render_widget(footer, chunks[3]);
+        frame.render_widget(footer, chunks[4]);
     }
 
-    fn render_creating(&self, frame: &mut Frame, area: Rect) {
+    fn render_creating(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        build_progress: Option<&crate::docker::BuildProgress>,
+    ) {
         // Modern color palette
         let cornflower_blue = Color::Rgb(100, 149, 237);
         let dark_bg = Color::Rgb(25, 25, 35);
@@ -707,6 +1027,7 @@ impl NewSessionComponent {
             .constraints([
                 Constraint::Length(2), // Subtitle
                 Constraint::Min(0),    // Progress content
+                Constraint::Length(if build_progress.is_some() { 3 } else { 0 }), // Image build bar
                 Constraint::Length(2), // Footer
             ])
             .split(inner);
@@ -754,16 +1075,168 @@ impl NewSessionComponent {
             );
         frame.render_widget(progress, chunks[1]);
 
+        // Image build/pull progress, once the build has reported a step or layer
+        if let Some(progress) = build_progress {
+            let gauge = Gauge::default()
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(Color::Rgb(60, 60, 80)))
+                        .style(Style::default().bg(dark_bg)),
+                )
+                .gauge_style(Style::default().fg(progress_cyan).bg(Color::Rgb(40, 40, 55)))
+                .ratio(progress.ratio().unwrap_or(0.0))
+                .label(Span::styled(
+                    progress.label().unwrap_or_default(),
+                    Style::default().fg(soft_white).add_modifier(Modifier::BOLD),
+                ));
+            frame.render_widget(gauge, chunks[2]);
+        }
+
         // Modern footer
         let footer = Paragraph::new(Line::from(vec![
             Span::styled("⏳ ", Style::default().fg(progress_cyan)),
             Span::styled("Please wait", Style::default().fg(muted_gray)),
             Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
             Span::styled("Esc", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" Continue in background", Style::default().fg(muted_gray)),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[3]);
+    }
+
+    fn render_base_branch_selection(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        session_state: &NewSessionState,
+    ) {
+        // Modern color palette
+        let cornflower_blue = Color::Rgb(100, 149, 237);
+        let dark_bg = Color::Rgb(25, 25, 35);
+        let gold = Color::Rgb(255, 215, 0);
+        let soft_white = Color::Rgb(220, 220, 230);
+        let muted_gray = Color::Rgb(120, 120, 140);
+        let selection_green = Color::Rgb(100, 200, 100);
+
+        // Clear background
+        let background = Block::default().style(Style::default().bg(dark_bg));
+        frame.render_widget(background, area);
+
+        let title_line = Line::from(vec![
+            Span::styled(" 🌿 ", Style::default().fg(gold)),
+            Span::styled("Select Base Branch", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" ", Style::default()),
+        ]);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(cornflower_blue))
+            .title(title_line)
+            .title_alignment(Alignment::Center)
+            .style(Style::default().bg(dark_bg));
+        frame.render_widget(block.clone(), area);
+
+        let inner = block.inner(area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3), // Subtitle
+                Constraint::Length(3), // Filter
+                Constraint::Min(0),    // Branch list
+                Constraint::Length(2), // Footer
+            ])
+            .split(inner);
+
+        let subtitle = Paragraph::new(Line::from(vec![
+            Span::styled("Branch ", Style::default().fg(muted_gray)),
+            Span::styled(session_state.branch_name.as_str(), Style::default().fg(soft_white)),
+            Span::styled(" will be created from the selected branch", Style::default().fg(muted_gray)),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(subtitle, chunks[0]);
+
+        let filter = Paragraph::new(Line::from(vec![
+            Span::styled("🔍 ", Style::default().fg(gold)),
+            Span::styled(session_state.base_branch_filter.as_str(), Style::default().fg(soft_white)),
+            Span::styled("█", Style::default().fg(gold)),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Rgb(60, 60, 80)))
+                .style(Style::default().bg(dark_bg)),
+        );
+        frame.render_widget(filter, chunks[1]);
+
+        let branches = session_state.filtered_base_branches();
+        let items: Vec<ListItem> = if branches.is_empty() {
+            vec![ListItem::new(Line::from(vec![
+                Span::styled("  ⚠️  ", Style::default().fg(gold)),
+                Span::styled("No branches match the filter", Style::default().fg(muted_gray)),
+            ]))]
+        } else {
+            branches
+                .iter()
+                .enumerate()
+                .map(|(index, branch)| {
+                    if Some(index) == session_state.selected_base_branch_index {
+                        ListItem::new(Line::from(vec![
+                            Span::styled("  ▶ ", Style::default().fg(selection_green)),
+                            Span::styled(
+                                branch.as_str(),
+                                Style::default().fg(selection_green).add_modifier(Modifier::BOLD),
+                            ),
+                        ]))
+                    } else {
+                        ListItem::new(Line::from(vec![
+                            Span::styled("    ", Style::default()),
+                            Span::styled(branch.as_str(), Style::default().fg(soft_white)),
+                        ]))
+                    }
+                })
+                .collect()
+        };
+
+        let list_title = Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(format!("Local branches ({})", branches.len()), Style::default().fg(cornflower_blue)),
+            Span::styled(" ", Style::default()),
+        ]);
+
+        let branch_list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Rgb(60, 60, 80)))
+                .title(list_title)
+                .style(Style::default().bg(dark_bg)),
+        );
+        // Keep the highlighted branch scrolled into view in long lists
+        let mut list_state = ListState::default();
+        list_state.select(session_state.selected_base_branch_index);
+        frame.render_stateful_widget(branch_list, chunks[2], &mut list_state);
+
+        let footer = Paragraph::new(Line::from(vec![
+            Span::styled("↑↓", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" Navigate", Style::default().fg(muted_gray)),
+            Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+            Span::styled("Type", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" Filter", Style::default().fg(muted_gray)),
+            Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+            Span::styled("Enter", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::styled(" Select", Style::default().fg(muted_gray)),
+            Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+            Span::styled("Esc", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
             Span::styled(" Cancel", Style::default().fg(muted_gray)),
         ]))
         .alignment(Alignment::Center);
-        frame.render_widget(footer, chunks[2]);
+        frame.render_widget(footer, chunks[3]);
     }
 
     fn render_mode_selection(
@@ -927,11 +1400,31 @@ impl NewSessionComponent {
             );
         frame.render_widget(boss_para, chunks[2]);
 
+        // Agent choice decides which CLI the container runs and how its output is parsed
+        let agent_line = Line::from(vec![
+            Span::styled("Agent: ", Style::default().fg(Color::Rgb(128, 128, 128))),
+            Span::styled(
+                session_state.agent_type.display_name(),
+                Style::default().fg(Color::Rgb(255, 215, 0)).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("   Model: ", Style::default().fg(Color::Rgb(128, 128, 128))),
+            Span::styled(
+                session_state.model.clone().unwrap_or_else(|| "CLI default".to_string()),
+                Style::default().fg(Color::Rgb(255, 215, 0)).add_modifier(Modifier::BOLD),
+            ),
+        ]);
+
         // Styled instructions footer
         let instructions = Line::from(vec![
             Span::styled("  ↑↓ ", Style::default().fg(Color::Rgb(100, 200, 100))),
             Span::styled("Switch Mode  ", Style::default().fg(Color::Rgb(128, 128, 128))),
             Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
+            Span::styled("  a ", Style::default().fg(Color::Rgb(100, 200, 100))),
+            Span::styled("Agent  ", Style::default().fg(Color::Rgb(128, 128, 128))),
+            Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
+            Span::styled("  m ", Style::default().fg(Color::Rgb(100, 200, 100))),
+            Span::styled("Model  ", Style::default().fg(Color::Rgb(128, 128, 128))),
+            Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
             Span::styled("  ⏎ ", Style::default().fg(Color::Rgb(100, 200, 100))),
             Span::styled("Continue  ", Style::default().fg(Color::Rgb(128, 128, 128))),
             Span::styled("│", Style::default().fg(Color::Rgb(70, 70, 90))),
@@ -939,13 +1432,20 @@ impl NewSessionComponent {
             Span::styled("Cancel  ", Style::default().fg(Color::Rgb(128, 128, 128))),
         ]);
 
-        let instructions_widget = Paragraph::new(instructions)
+        let instructions_widget = Paragraph::new(vec![agent_line, instructions])
             .alignment(Alignment::Center)
             .style(Style::default().bg(Color::Rgb(25, 25, 35)));
         frame.render_widget(instructions_widget, chunks[4]);
     }
 
-    fn render_prompt_input(&self, frame: &mut Frame, area: Rect, session_state: &NewSessionState) {
+    fn render_prompt_input(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        session_state: &NewSessionState,
+        soft_wrap: bool,
+        char_limits: (usize, usize),
+    ) {
         // Modern color palette
         let cornflower_blue = Color::Rgb(100, 149, 237);
         let dark_bg = Color::Rgb(25, 25, 35);
@@ -989,15 +1489,32 @@ impl NewSessionComponent {
             .split(inner);
 
         // Subtitle
-        let subtitle_text = if session_state.file_finder.is_active {
-            "File finder active - search for files to reference"
+        let subtitle_line = if let Some(ref paste) = session_state.pending_paste {
+            Line::from(vec![
+                Span::styled("📋 Large paste ", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("({} chars, {} lines)  ", paste.chars().count(), paste.lines().count()),
+                    Style::default().fg(muted_gray),
+                ),
+                Span::styled("i", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(" insert inline  •  ", Style::default().fg(soft_white)),
+                Span::styled("f", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(" save as @file  •  ", Style::default().fg(soft_white)),
+                Span::styled("Esc", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(" cancel", Style::default().fg(soft_white)),
+            ])
+        } else if session_state.file_finder.is_active {
+            Line::from(Span::styled(
+                "File finder active - search for files to reference",
+                Style::default().fg(muted_gray),
+            ))
         } else {
-            "Enter the task or prompt for Claude to execute"
+            Line::from(Span::styled(
+                "Enter the task or prompt for Claude to execute",
+                Style::default().fg(muted_gray),
+            ))
         };
-        let subtitle = Paragraph::new(Line::from(vec![
-            Span::styled(subtitle_text, Style::default().fg(muted_gray)),
-        ]))
-        .alignment(Alignment::Center);
+        let subtitle = Paragraph::new(subtitle_line).alignment(Alignment::Center);
         frame.render_widget(subtitle, chunks[0]);
 
         // Instructions - update to mention @ symbol for file finder
@@ -1061,6 +1578,8 @@ impl NewSessionComponent {
             );
         frame.render_widget(instructions, chunks[1]);
 
+        let prompt_size = Self::prompt_size_line(&session_state.boss_prompt, char_limits);
+
         // Split the prompt input area if file finder is active
         if session_state.file_finder.is_active {
             let input_chunks = Layout::default()
@@ -1072,13 +1591,27 @@ impl NewSessionComponent {
                 .split(chunks[2]);
 
             // Render prompt on the left
-            self.render_text_editor(frame, input_chunks[0], &session_state.boss_prompt, "Prompt");
+            self.render_text_editor(
+                frame,
+                input_chunks[0],
+                &session_state.boss_prompt,
+                "Prompt",
+                soft_wrap,
+                prompt_size,
+            );
 
             // Render file finder on the right
             self.render_file_finder(frame, input_chunks[1], session_state);
         } else {
             // Normal full-width prompt input
-            self.render_text_editor(frame, chunks[2], &session_state.boss_prompt, "Prompt");
+            self.render_text_editor(
+                frame,
+                chunks[2],
+                &session_state.boss_prompt,
+                "Prompt",
+                soft_wrap,
+                prompt_size,
+            );
         }
 
         // Modern footer with keyboard hints
@@ -1104,6 +1637,9 @@ impl NewSessionComponent {
                 Span::styled("Ctrl+J", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
                 Span::styled(" Newline", Style::default().fg(muted_gray)),
                 Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
+                Span::styled("Ctrl+Z/Y", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+                Span::styled(" Undo/Redo", Style::default().fg(muted_gray)),
+                Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
                 Span::styled("@", Style::default().fg(file_finder_yellow).add_modifier(Modifier::BOLD)),
                 Span::styled(" Files", Style::default().fg(muted_gray)),
                 Span::styled("  │  ", Style::default().fg(Color::Rgb(60, 60, 80))),
@@ -1126,11 +1662,14 @@ impl NewSessionComponent {
         let file_finder_yellow = Color::Rgb(255, 200, 100);
         let selection_bg = Color::Rgb(80, 70, 40);
 
+        let finder = &session_state.file_finder;
+        let preview_height = finder.preview.len().clamp(1, PREVIEW_LINES) as u16 + 2;
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Query input
-                Constraint::Min(0),    // File list
+                Constraint::Length(3),              // Query input
+                Constraint::Min(5),                 // File list
+                Constraint::Length(preview_height), // Highlighted file's first lines
             ])
             .split(area);
 
@@ -1196,6 +1735,25 @@ impl NewSessionComponent {
         );
 
         frame.render_widget(file_list, chunks[1]);
+
+        let preview_title = match finder.get_selected_file() {
+            Some(file) => format!(" {} ", file.relative_path),
+            None => " No file selected ".to_string(),
+        };
+        let preview_lines: Vec<Line> = finder
+            .preview
+            .iter()
+            .map(|line| Line::from(Span::styled(line.as_str(), Style::default().fg(muted_gray))))
+            .collect();
+        let preview = Paragraph::new(preview_lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Rgb(60, 60, 80)))
+                .title(Span::styled(preview_title, Style::default().fg(muted_gray)))
+                .style(Style::default().bg(dark_bg)),
+        );
+        frame.render_widget(preview, chunks[2]);
     }
 
     fn centered_rect(&self, percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -1218,99 +1776,121 @@ impl NewSessionComponent {
             .split(popup_layout[1])[1]
     }
 
+    /// "312 chars / 14 lines", yellow past the soft limit and red with a warning past the hard one
+    fn prompt_size_line(
+        editor: &crate::app::state::TextEditor,
+        (soft_limit, hard_limit): (usize, usize),
+    ) -> Line<'static> {
+        let chars = editor.char_count();
+        let size = format!(" {} chars / {} lines ", chars, editor.line_count());
+        if chars > hard_limit {
+            Line::from(vec![
+                Span::styled(size, Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    "⚠ very large prompts may be truncated by the agent ",
+                    Style::default().fg(Color::Red),
+                ),
+            ])
+        } else if chars > soft_limit {
+            Line::from(Span::styled(size, Style::default().fg(Color::Yellow)))
+        } else {
+            Line::from(Span::styled(size, Style::default().fg(Color::Rgb(120, 120, 140))))
+        }
+    }
+
     fn render_text_editor(
-        &self,
+        &mut self,
         frame: &mut Frame,
         area: Rect,
         editor: &crate::app::state::TextEditor,
         title: &str,
+        soft_wrap: bool,
+        footer: Line<'static>,
     ) {
         use ratatui::layout::Alignment;
         use ratatui::style::{Color, Style};
         use ratatui::text::{Line, Span};
+        use ratatui::widgets::block::{Position, Title};
         use ratatui::widgets::{Block, Borders, Paragraph};
 
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Green))
-            .title(title);
+            .title(title)
+            .title(Title::from(footer).position(Position::Bottom).alignment(Alignment::Right));
 
         let inner_area = block.inner(area);
         frame.render_widget(block, area);
 
+        // One column is kept free so a cursor at the end of a full row stays visible
+        let width = (inner_area.width as usize).saturating_sub(1).max(1);
+        let height = (inner_area.height as usize).max(1);
+        self.prompt_wrap_width = soft_wrap.then_some(width);
+
         if editor.is_empty() {
             // Show placeholder text
             let placeholder = Paragraph::new("Type your prompt here...")
                 .style(Style::default().fg(Color::DarkGray))
                 .alignment(Alignment::Left);
             frame.render_widget(placeholder, inner_area);
+            return;
+        }
+
+        // Visual rows: wrapped at the pane width, or one per line when wrapping is off
+        let lines = editor.get_lines();
+        let (rows, (cursor_row, cursor_column)) = if soft_wrap {
+            (editor.visual_rows(width), editor.visual_cursor(width))
         } else {
-            // Render text with cursor
             let (cursor_line, cursor_col) = editor.get_cursor_position();
-            let lines = editor.get_lines();
+            let column = lines[cursor_line].get(..cursor_col).map_or(0, |s| s.chars().count());
+            let rows =
+                lines.iter().enumerate().map(|(index, line)| (index, 0..line.len())).collect();
+            (rows, (cursor_line, column))
+        };
 
-            let rendered_lines: Vec<Line> = lines
-                .iter()
-                .enumerate()
-                .map(|(line_idx, line_text)| {
-                    if line_idx == cursor_line {
-                        // This line contains the cursor
-                        let mut spans = Vec::new();
-
-                        if cursor_col == 0 {
-                            // Cursor at beginning of line
-                            spans.push(Span::styled(
-                                "█",
-                                Style::default().fg(Color::White).bg(Color::Green),
-                            ));
-                            if !line_text.is_empty() {
-                                spans.push(Span::styled(
-                                    line_text,
-                                    Style::default().fg(Color::White),
-                                ));
-                            }
-                        } else if cursor_col >= line_text.len() {
-                            // Cursor at end of line
-                            spans.push(Span::styled(line_text, Style::default().fg(Color::White)));
-                            spans.push(Span::styled(
-                                "█",
-                                Style::default().fg(Color::White).bg(Color::Green),
-                            ));
-                        } else {
-                            // Cursor in middle of line
-                            let (before, rest) = line_text.split_at(cursor_col);
-                            let (cursor_char, after) = if rest.len() > 1 {
-                                rest.split_at(1)
-                            } else {
-                                (rest, "")
-                            };
-
-                            if !before.is_empty() {
-                                spans.push(Span::styled(before, Style::default().fg(Color::White)));
-                            }
-                            spans.push(Span::styled(
-                                cursor_char,
-                                Style::default().fg(Color::White).bg(Color::Green),
-                            ));
-                            if !after.is_empty() {
-                                spans.push(Span::styled(after, Style::default().fg(Color::White)));
-                            }
-                        }
+        let text_style = Style::default().fg(Color::White);
+        let cursor_style = Style::default().fg(Color::White).bg(Color::Green);
+        let rendered_lines: Vec<Line> = rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, (line_index, range))| {
+                let row_text = &lines[*line_index][range.clone()];
+                if row_index != cursor_row {
+                    return Line::from(Span::styled(row_text, text_style));
+                }
 
-                        Line::from(spans)
-                    } else {
-                        // Normal line without cursor
-                        Line::from(Span::styled(line_text, Style::default().fg(Color::White)))
-                    }
-                })
-                .collect();
+                // Split the row around the character under the cursor
+                let split =
+                    row_text.char_indices().nth(cursor_column).map_or(row_text.len(), |(i, _)| i);
+                let (before, rest) = row_text.split_at(split);
+                let cursor_len = rest.chars().next().map_or(0, char::len_utf8);
+                let (cursor_char, after) = rest.split_at(cursor_len);
+                let cursor_char = if cursor_char.is_empty() { "█" } else { cursor_char };
+
+                let mut spans = Vec::new();
+                if !before.is_empty() {
+                    spans.push(Span::styled(before, text_style));
+                }
+                spans.push(Span::styled(cursor_char, cursor_style));
+                if !after.is_empty() {
+                    spans.push(Span::styled(after, text_style));
+                }
+                Line::from(spans)
+            })
+            .collect();
 
-            let paragraph = Paragraph::new(rendered_lines)
-                .alignment(Alignment::Left)
-                .wrap(ratatui::widgets::Wrap { trim: false }); // Don't trim to preserve exact formatting
+        // Keep the cursor in view
+        let scroll_y = cursor_row.saturating_sub(height - 1);
+        let scroll_x = if soft_wrap {
+            0
+        } else {
+            cursor_column.saturating_sub(width)
+        };
+        let paragraph = Paragraph::new(rendered_lines)
+            .alignment(Alignment::Left)
+            .scroll((scroll_y as u16, scroll_x as u16));
 
-            frame.render_widget(paragraph, inner_area);
-        }
+        frame.render_widget(paragraph, inner_area);
     }
 }
 
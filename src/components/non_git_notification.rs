@@ -33,6 +33,13 @@ impl NonGitNotificationComponent {
                 ),
                 Span::raw(" - Search for workspaces"),
             ]),
+            Line::from(vec![
+                Span::styled(
+                    "  Esc",
+                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" - Go to session list"),
+            ]),
             Line::from(vec![
                 Span::styled(
                     "  q",
@@ -0,0 +1,104 @@
+// ABOUTME: Scrollable popup listing past notifications, newest first, after their toasts expire
+
+use ratatui::{
+    prelude::*,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use std::collections::VecDeque;
+
+use crate::app::state::{ArchivedNotification, NotificationType};
+use crate::config::Theme;
+
+const GOLD: Color = Color::Rgb(255, 215, 0);
+const CORNFLOWER_BLUE: Color = Color::Rgb(100, 149, 237);
+const SELECTION_GREEN: Color = Color::Rgb(100, 200, 100);
+
+pub struct NotificationHistoryComponent;
+
+impl NotificationHistoryComponent {
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        history: &VecDeque<ArchivedNotification>,
+        scroll_offset: usize,
+        theme: &Theme,
+    ) {
+        frame.render_widget(Clear, area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let lines: Vec<Line> = if history.is_empty() {
+            vec![Line::from(Span::styled(
+                "No notifications yet",
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+            ))]
+        } else {
+            history.iter().rev().map(|entry| Self::entry_line(entry, theme)).collect()
+        };
+
+        let list = Paragraph::new(lines)
+            .scroll((scroll_offset.min(u16::MAX as usize) as u16, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(CORNFLOWER_BLUE))
+                    .style(Style::default().bg(theme.background))
+                    .title(Line::from(vec![
+                        Span::styled(" 🔔 ", Style::default().fg(GOLD)),
+                        Span::styled(
+                            "Notification History ",
+                            Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!("({}) ", history.len()),
+                            Style::default().fg(theme.muted),
+                        ),
+                    ])),
+            );
+        frame.render_widget(list, layout[0]);
+
+        let key = |label| {
+            Span::styled(label, Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD))
+        };
+        let instructions = Paragraph::new(Line::from(vec![
+            key("j/k"),
+            Span::styled(" scroll  ", Style::default().fg(theme.muted)),
+            key("c"),
+            Span::styled(" clear history  ", Style::default().fg(theme.muted)),
+            key("Esc"),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ]))
+        .style(Style::default().bg(theme.background))
+        .alignment(Alignment::Center);
+        frame.render_widget(instructions, layout[1]);
+    }
+
+    fn entry_line(entry: &ArchivedNotification, theme: &Theme) -> Line<'static> {
+        let (icon, color) = match entry.notification.notification_type {
+            NotificationType::Success => ("✓", theme.notification_success),
+            NotificationType::Error => ("✗", theme.notification_error),
+            NotificationType::Warning => ("⚠", theme.notification_warning),
+            NotificationType::Info => ("ℹ", theme.notification_info),
+        };
+        Line::from(vec![
+            Span::styled(
+                entry.received_at.format("%H:%M:%S ").to_string(),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(
+                format!("{} ", icon),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                entry.notification.display_message().replace('\n', " "),
+                Style::default().fg(color),
+            ),
+        ])
+    }
+}
@@ -0,0 +1,239 @@
+// ABOUTME: Scrollable popup for reviewing a session's boss prompt, optionally with @-file contents
+
+use ratatui::{
+    prelude::*,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use std::path::{Path, PathBuf};
+
+const GOLD: Color = Color::Rgb(255, 215, 0);
+const CORNFLOWER_BLUE: Color = Color::Rgb(100, 149, 237);
+const SELECTION_GREEN: Color = Color::Rgb(100, 200, 100);
+const DARK_BG: Color = Color::Rgb(25, 25, 35);
+const SOFT_WHITE: Color = Color::Rgb(220, 220, 230);
+const MUTED_GRAY: Color = Color::Rgb(120, 120, 140);
+
+/// Maximum bytes of a referenced file to inline into the expanded prompt
+const MAX_INLINED_FILE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct PromptViewerState {
+    pub session_name: String,
+    pub prompt: String,
+    pub workspace_path: PathBuf,
+    pub include_file_contents: bool,
+    pub scroll_offset: usize,
+    expanded_prompt: Option<String>, // Read when file contents are turned on, not per frame
+}
+
+impl PromptViewerState {
+    pub fn new(session_name: String, prompt: String, workspace_path: PathBuf) -> Self {
+        Self {
+            session_name,
+            prompt,
+            workspace_path,
+            include_file_contents: false,
+            scroll_offset: 0,
+            expanded_prompt: None,
+        }
+    }
+
+    /// Text as currently shown (and copied): the raw prompt, or the prompt followed by
+    /// the contents of each `@`-referenced file when file contents are included
+    pub fn display_text(&self) -> &str {
+        match &self.expanded_prompt {
+            Some(expanded) if self.include_file_contents => expanded,
+            _ => &self.prompt,
+        }
+    }
+
+    /// Show or hide the referenced files, reading them afresh each time they're shown
+    pub fn toggle_file_contents(&mut self) {
+        self.include_file_contents = !self.include_file_contents;
+        self.expanded_prompt = self
+            .include_file_contents
+            .then(|| expand_file_references(&self.prompt, &self.workspace_path));
+        self.scroll_offset = 0;
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        let line_count = self.display_text().lines().count();
+        if self.scroll_offset + 1 < line_count {
+            self.scroll_offset += 1;
+        }
+    }
+}
+
+/// Append the contents of every `@path` reference in `prompt` that resolves to a file
+/// under `base_dir`. Missing files, and paths leading outside `base_dir` (absolute,
+/// `..` or through a symlink), are noted rather than read.
+pub fn expand_file_references(prompt: &str, base_dir: &Path) -> String {
+    let mut expanded = prompt.to_string();
+    let mut seen = Vec::new();
+    let base_dir = base_dir.canonicalize().unwrap_or_else(|_| base_dir.to_path_buf());
+
+    for reference in prompt
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix('@'))
+        .map(|path| path.trim_end_matches(|c: char| matches!(c, ',' | '.' | ';' | ':' | ')')))
+        .filter(|path| !path.is_empty())
+    {
+        if seen.contains(&reference) {
+            continue;
+        }
+        seen.push(reference);
+
+        let file_path = base_dir.join(reference);
+        expanded.push_str(&format!("\n\n--- @{} ---\n", reference));
+        let contents = file_path.canonicalize().and_then(|path| {
+            if path.starts_with(&base_dir) {
+                std::fs::read(path)
+            } else {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "outside the workspace",
+                ))
+            }
+        });
+        match contents {
+            Ok(bytes) => {
+                let truncated = bytes.len() > MAX_INLINED_FILE_BYTES;
+                let content =
+                    String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_INLINED_FILE_BYTES)]);
+                expanded.push_str(&content);
+                if truncated {
+                    expanded.push_str(&format!("\n[... truncated, {} bytes total]", bytes.len()));
+                }
+            }
+            Err(e) => {
+                expanded.push_str(&format!("[could not read {}: {}]", file_path.display(), e))
+            }
+        }
+    }
+
+    expanded
+}
+
+pub struct PromptViewerComponent;
+
+impl PromptViewerComponent {
+    pub fn render(frame: &mut Frame, area: Rect, viewer: &PromptViewerState) {
+        frame.render_widget(Clear, area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let files_label = if viewer.include_file_contents {
+            "with @-files"
+        } else {
+            "prompt only"
+        };
+        let prompt = Paragraph::new(viewer.display_text())
+            .style(Style::default().fg(SOFT_WHITE))
+            .wrap(Wrap { trim: false })
+            .scroll((viewer.scroll_offset as u16, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(CORNFLOWER_BLUE))
+                    .style(Style::default().bg(DARK_BG))
+                    .title(Line::from(vec![
+                        Span::styled(" 📝 ", Style::default().fg(GOLD)),
+                        Span::styled(
+                            format!("Boss Prompt: {} ", viewer.session_name),
+                            Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!("({}) ", files_label),
+                            Style::default().fg(MUTED_GRAY),
+                        ),
+                    ])),
+            );
+        frame.render_widget(prompt, layout[0]);
+
+        let instructions = Paragraph::new(Line::from(vec![
+            Span::styled(
+                "j/k",
+                Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" scroll  ", Style::default().fg(MUTED_GRAY)),
+            Span::styled(
+                "y",
+                Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" copy  ", Style::default().fg(MUTED_GRAY)),
+            Span::styled(
+                "f",
+                Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" toggle @-files  ", Style::default().fg(MUTED_GRAY)),
+            Span::styled(
+                "Esc",
+                Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" close", Style::default().fg(MUTED_GRAY)),
+        ]))
+        .style(Style::default().bg(DARK_BG))
+        .alignment(Alignment::Center);
+        frame.render_widget(instructions, layout[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_expand_file_references() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src/lib.rs"), "pub fn hello() {}").unwrap();
+
+        let expanded =
+            expand_file_references("Refactor @src/lib.rs, see @missing.md", temp_dir.path());
+
+        assert!(expanded.starts_with("Refactor @src/lib.rs, see @missing.md"));
+        assert!(expanded.contains("--- @src/lib.rs ---\npub fn hello() {}"));
+        assert!(expanded.contains("--- @missing.md ---\n[could not read"));
+    }
+
+    #[test]
+    fn test_expand_file_references_stays_in_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let secret = temp_dir.path().join("secret.txt");
+        std::fs::write(&secret, "hunter2").unwrap();
+
+        let prompt = format!("Read @../secret.txt and @{}", secret.display());
+        let expanded = expand_file_references(&prompt, &workspace);
+
+        assert!(!expanded.contains("hunter2"));
+        assert_eq!(expanded.matches("outside the workspace").count(), 2);
+    }
+
+    #[test]
+    fn test_display_text_toggles_file_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("notes.md"), "details").unwrap();
+        let mut viewer = PromptViewerState::new(
+            "session".to_string(),
+            "Read @notes.md".to_string(),
+            temp_dir.path().to_path_buf(),
+        );
+
+        assert_eq!(viewer.display_text(), "Read @notes.md");
+        viewer.toggle_file_contents();
+        assert!(viewer.display_text().contains("details"));
+    }
+}
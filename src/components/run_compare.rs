@@ -0,0 +1,127 @@
+// ABOUTME: Popup comparing a boss session's changes with the previous run of the same prompt
+// Lists the files whose contents differ between the two runs' branches
+
+use crate::git::diff_analyzer::{FileDiff, FileStatus};
+use ratatui::{
+    prelude::*,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+const GOLD: Color = Color::Rgb(255, 215, 0);
+const CORNFLOWER_BLUE: Color = Color::Rgb(100, 149, 237);
+const SELECTION_GREEN: Color = Color::Rgb(100, 200, 100);
+const WARNING_ORANGE: Color = Color::Rgb(255, 165, 0);
+const ERROR_RED: Color = Color::Rgb(230, 90, 90);
+const DARK_BG: Color = Color::Rgb(25, 25, 35);
+const SOFT_WHITE: Color = Color::Rgb(220, 220, 230);
+const MUTED_GRAY: Color = Color::Rgb(120, 120, 140);
+
+#[derive(Debug, Clone)]
+pub struct RunCompareState {
+    pub session_name: String,
+    pub previous_name: String,
+    pub files: Vec<FileDiff>, // Statuses are relative to the previous run
+    pub scroll_offset: usize,
+}
+
+impl RunCompareState {
+    pub fn new(session_name: String, previous_name: String, files: Vec<FileDiff>) -> Self {
+        Self {
+            session_name,
+            previous_name,
+            files,
+            scroll_offset: 0,
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.scroll_offset + 1 < self.files.len() {
+            self.scroll_offset += 1;
+        }
+    }
+}
+
+pub struct RunCompareComponent;
+
+impl RunCompareComponent {
+    pub fn render(frame: &mut Frame, area: Rect, compare: &RunCompareState) {
+        frame.render_widget(Clear, area);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let lines: Vec<Line> = if compare.files.is_empty() {
+            vec![Line::from(Span::styled(
+                "✨ Both runs made identical changes",
+                Style::default().fg(SELECTION_GREEN),
+            ))]
+        } else {
+            compare.files.iter().map(Self::file_line).collect()
+        };
+
+        let files = Paragraph::new(lines).scroll((compare.scroll_offset as u16, 0)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(CORNFLOWER_BLUE))
+                .style(Style::default().bg(DARK_BG))
+                .title(Line::from(vec![
+                    Span::styled(" 🔁 ", Style::default().fg(GOLD)),
+                    Span::styled(
+                        format!(
+                            "{} vs previous run {} ",
+                            compare.session_name, compare.previous_name
+                        ),
+                        Style::default().fg(GOLD).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("({} files differ) ", compare.files.len()),
+                        Style::default().fg(MUTED_GRAY),
+                    ),
+                ])),
+        );
+        frame.render_widget(files, layout[0]);
+
+        let instructions = Paragraph::new(Line::from(vec![
+            Span::styled(
+                "j/k",
+                Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" scroll  ", Style::default().fg(MUTED_GRAY)),
+            Span::styled(
+                "Esc",
+                Style::default().fg(SELECTION_GREEN).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" close", Style::default().fg(MUTED_GRAY)),
+        ]))
+        .style(Style::default().bg(DARK_BG))
+        .alignment(Alignment::Center);
+        frame.render_widget(instructions, layout[1]);
+    }
+
+    fn file_line(file: &FileDiff) -> Line<'static> {
+        let (label, color) = match file.status {
+            FileStatus::Added | FileStatus::Untracked => ("only this run", SELECTION_GREEN),
+            FileStatus::Deleted => ("only previous run", ERROR_RED),
+            FileStatus::Renamed | FileStatus::Copied => ("renamed", CORNFLOWER_BLUE),
+            FileStatus::Modified => ("differs", WARNING_ORANGE),
+        };
+
+        Line::from(vec![
+            Span::styled(format!(" {:<17} ", label), Style::default().fg(color)),
+            Span::styled(file.path.clone(), Style::default().fg(SOFT_WHITE)),
+            Span::styled(
+                format!("  +{} -{}", file.insertions, file.deletions),
+                Style::default().fg(MUTED_GRAY),
+            ),
+        ])
+    }
+}
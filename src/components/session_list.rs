@@ -6,27 +6,44 @@ use ratatui::{
     prelude::*,
     style::{Color, Modifier, Style},
     text::{Line, Span},
+    layout::{Margin, Position},
     widgets::{Block, Borders, BorderType, List, ListItem, ListState},
 };
 
 // Premium color palette (TUI Style Guide)
 const CORNFLOWER_BLUE: Color = Color::Rgb(100, 149, 237);
 const GOLD: Color = Color::Rgb(255, 215, 0);
-const SELECTION_GREEN: Color = Color::Rgb(100, 200, 100);
 const WARNING_ORANGE: Color = Color::Rgb(255, 165, 0);
-const DARK_BG: Color = Color::Rgb(25, 25, 35);
-const LIST_HIGHLIGHT_BG: Color = Color::Rgb(40, 40, 60);
-const SOFT_WHITE: Color = Color::Rgb(220, 220, 230);
-const MUTED_GRAY: Color = Color::Rgb(120, 120, 140);
-const SUBDUED_BORDER: Color = Color::Rgb(60, 60, 80);
 
 use crate::app::AppState;
-use crate::models::{SessionMode, SessionStatus, Workspace};
+use crate::config::Theme;
+use crate::models::{Session, SessionMode, SessionStatus, Workspace};
 
 pub struct SessionListComponent {
     list_state: ListState,
 }
 
+/// What one row of the session list stands for, so a click can be turned into a selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionListRow {
+    Workspace(usize),
+    Session(usize, usize), // Workspace index, session index
+    OtherTmuxHeader,
+    OtherTmux(usize),
+    Blank, // Separator or placeholder text
+}
+
+/// Index of the list row under (x, y) for a bordered list drawn in `area`, scrolled by
+/// `offset` rows. None on the border or below the last row.
+pub fn list_row_at(area: Rect, offset: usize, row_count: usize, x: u16, y: u16) -> Option<usize> {
+    let inner = area.inner(&Margin::new(1, 1));
+    if !inner.contains(Position::new(x, y)) {
+        return None;
+    }
+    let row = offset + usize::from(y - inner.y);
+    (row < row_count).then_some(row)
+}
+
 impl Default for SessionListComponent {
     fn default() -> Self {
         let mut list_state = ListState::default();
@@ -48,12 +65,18 @@ impl SessionListComponent {
 
         // Show focus indicator with premium colors
         use crate::app::state::FocusedPane;
+        let theme = &state.theme;
         let (border_color, is_focused) = match state.focused_pane {
-            FocusedPane::Sessions => (SELECTION_GREEN, true),
-            FocusedPane::LiveLogs => (SUBDUED_BORDER, false),
+            FocusedPane::Sessions => (theme.border_focused, true),
+            FocusedPane::LiveLogs => (theme.border, false),
         };
 
         let workspace_count = state.workspaces.len();
+        let filter_text = match state.session_filter.as_deref() {
+            Some(filter) if state.session_filter_editing => format!(" 🔍 /{}▏", filter),
+            Some(filter) => format!(" 🔍 /{} (Esc clears)", filter),
+            None => String::new(),
+        };
 
         let list = List::new(items)
             .block(
@@ -61,30 +84,89 @@ impl SessionListComponent {
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
                     .border_style(Style::default().fg(border_color))
-                    .style(Style::default().bg(DARK_BG))
+                    .style(Style::default().bg(theme.background))
                     .title(Line::from(vec![
                         Span::styled(" 📁 ", Style::default().fg(GOLD)),
                         Span::styled("Workspaces ", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
                         Span::styled(
                             format!("({})", workspace_count),
-                            Style::default().fg(if is_focused { CORNFLOWER_BLUE } else { MUTED_GRAY }).add_modifier(Modifier::BOLD)
+                            Style::default().fg(if is_focused { CORNFLOWER_BLUE } else { theme.muted }).add_modifier(Modifier::BOLD)
                         ),
+                        Span::styled(filter_text, Style::default().fg(WARNING_ORANGE)),
                     ]))
                     .title_bottom(Line::from(vec![
                         Span::styled(" j/k", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
-                        Span::styled(" nav ", Style::default().fg(MUTED_GRAY)),
-                        Span::styled("│", Style::default().fg(SUBDUED_BORDER)),
+                        Span::styled(" nav ", Style::default().fg(theme.muted)),
+                        Span::styled("│", Style::default().fg(theme.border)),
                         Span::styled(" Enter", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
-                        Span::styled(" select ", Style::default().fg(MUTED_GRAY)),
+                        Span::styled(" select ", Style::default().fg(theme.muted)),
+                        Span::styled("│", Style::default().fg(theme.border)),
+                        Span::styled(" o", Style::default().fg(GOLD).add_modifier(Modifier::BOLD)),
+                        Span::styled(
+                            format!(" sort: {} ", state.session_sort_mode.label()),
+                            Style::default().fg(theme.muted),
+                        ),
                     ])),
             )
-            .highlight_style(Style::default().bg(LIST_HIGHLIGHT_BG))
+            .highlight_style(Style::default().bg(theme.selection_bg))
             .highlight_symbol("▶ ");
 
         frame.render_stateful_widget(list, area, &mut self.list_state);
     }
 
+    /// The row under (x, y), given the area the list was last drawn in
+    pub fn row_at(&self, area: Rect, x: u16, y: u16, state: &AppState) -> Option<SessionListRow> {
+        let rows = Self::rows(state);
+        let row = list_row_at(area, self.list_state.offset(), rows.len(), x, y)?;
+        Some(rows[row])
+    }
+
+    /// One entry per list item, in the order `build_list_items_static` draws them
+    pub fn rows(state: &AppState) -> Vec<SessionListRow> {
+        let mut rows = Vec::new();
+        if state.session_filter.is_some() {
+            let matches = state.filtered_sessions();
+            for (position, &(workspace_idx, session_idx)) in matches.iter().enumerate() {
+                if position == 0 || matches[position - 1].0 != workspace_idx {
+                    rows.push(SessionListRow::Workspace(workspace_idx));
+                }
+                rows.push(SessionListRow::Session(workspace_idx, session_idx));
+            }
+        } else {
+            for (workspace_idx, workspace) in state.workspaces.iter().enumerate() {
+                rows.push(SessionListRow::Workspace(workspace_idx));
+                if state.selected_workspace_index == Some(workspace_idx)
+                    || state.expand_all_workspaces
+                {
+                    rows.extend(
+                        (0..workspace.sessions.len())
+                            .map(|session_idx| SessionListRow::Session(workspace_idx, session_idx)),
+                    );
+                }
+            }
+            if !state.other_tmux_sessions.is_empty() {
+                if !rows.is_empty() {
+                    rows.push(SessionListRow::Blank);
+                }
+                rows.push(SessionListRow::OtherTmuxHeader);
+                if state.other_tmux_expanded {
+                    let others = state.other_tmux_sessions.len();
+                    rows.extend((0..others).map(SessionListRow::OtherTmux));
+                }
+            }
+        }
+        if rows.is_empty() {
+            rows.push(SessionListRow::Blank);
+        }
+        rows
+    }
+
     fn build_list_items_static(state: &AppState) -> Vec<ListItem<'static>> {
+        if state.session_filter.is_some() {
+            return Self::build_filtered_items(state);
+        }
+
+        let theme = &state.theme;
         let mut items = Vec::new();
 
         for (workspace_idx, workspace) in state.workspaces.iter().enumerate() {
@@ -104,9 +186,9 @@ impl SessionListComponent {
 
             // Premium workspace styling
             let (symbol_color, name_color) = if is_selected_workspace {
-                (SELECTION_GREEN, SELECTION_GREEN)
+                (theme.selection, theme.selection)
             } else {
-                (MUTED_GRAY, SOFT_WHITE)
+                (theme.muted, theme.text)
             };
 
             let count_display = if session_count > 0 {
@@ -115,12 +197,22 @@ impl SessionListComponent {
                 String::new()
             };
 
-            let workspace_line = Line::from(vec![
+            let mut workspace_spans = vec![
                 Span::styled(workspace_symbol, Style::default().fg(symbol_color)),
                 Span::styled(" 📁 ", Style::default().fg(if is_selected_workspace { GOLD } else { CORNFLOWER_BLUE })),
                 Span::styled(workspace.name.clone(), Style::default().fg(name_color).add_modifier(if is_selected_workspace { Modifier::BOLD } else { Modifier::empty() })),
-                Span::styled(count_display, Style::default().fg(MUTED_GRAY)),
-            ]);
+                Span::styled(count_display, Style::default().fg(theme.muted)),
+            ];
+
+            // Nudge toward cleanup when a workspace accumulates too many sessions
+            if state.session_warning_threshold.is_some_and(|limit| workspace.exceeds_session_limit(limit)) {
+                workspace_spans.push(Span::styled(
+                    format!(" ⚠ {} sessions", session_count),
+                    Style::default().fg(WARNING_ORANGE),
+                ));
+            }
+
+            let workspace_line = Line::from(workspace_spans);
 
             items.push(ListItem::new(workspace_line));
 
@@ -131,55 +223,7 @@ impl SessionListComponent {
                     let is_selected_session = is_selected_workspace && state.selected_session_index == Some(session_idx);
                     let is_last_session = session_idx == session_len - 1;
 
-                    // Tree line characters with subdued color
-                    let tree_prefix = if is_last_session { "└─" } else { "├─" };
-
-                    let status_indicator = session.status.indicator();
-
-                    // Mode indicator
-                    let mode_indicator = match session.mode {
-                        SessionMode::Boss => "🐳",
-                        SessionMode::Interactive => "🖥️",
-                    };
-
-                    // Tmux status indicator
-                    let tmux_indicator = if session.is_attached {
-                        "🔗"
-                    } else if session.tmux_session_name.is_some() {
-                        "●"
-                    } else {
-                        "○"
-                    };
-
-                    let changes_text = if session.git_changes.total() > 0 {
-                        format!(" ({})", session.git_changes.format())
-                    } else {
-                        String::new()
-                    };
-
-                    // Premium session styling
-                    let (branch_color, tmux_color) = if is_selected_session {
-                        (SELECTION_GREEN, SELECTION_GREEN)
-                    } else {
-                        match session.status {
-                            SessionStatus::Running => (SELECTION_GREEN, SOFT_WHITE),
-                            SessionStatus::Stopped => (MUTED_GRAY, MUTED_GRAY),
-                            SessionStatus::Idle => (WARNING_ORANGE, SOFT_WHITE),
-                            SessionStatus::Error(_) => (Color::Rgb(230, 100, 100), SOFT_WHITE),
-                        }
-                    };
-
-                    let session_line = Line::from(vec![
-                        Span::styled("  ", Style::default()),
-                        Span::styled(tree_prefix, Style::default().fg(SUBDUED_BORDER)),
-                        Span::styled(format!(" {} ", status_indicator), Style::default()),
-                        Span::styled(format!("{} ", mode_indicator), Style::default()),
-                        Span::styled(format!("{} ", tmux_indicator), Style::default().fg(tmux_color)),
-                        Span::styled(session.branch_name.clone(), Style::default().fg(branch_color).add_modifier(if is_selected_session { Modifier::BOLD } else { Modifier::empty() })),
-                        Span::styled(changes_text, Style::default().fg(WARNING_ORANGE)),
-                    ]);
-
-                    items.push(ListItem::new(session_line));
+                    items.push(Self::session_item(session, is_selected_session, is_last_session, theme));
                 }
             }
         }
@@ -200,14 +244,14 @@ impl SessionListComponent {
             let header_color = if state.selected_workspace_index.is_none() {
                 CORNFLOWER_BLUE
             } else {
-                MUTED_GRAY
+                theme.muted
             };
 
             let other_header = Line::from(vec![
                 Span::styled(other_symbol, Style::default().fg(header_color)),
                 Span::styled(" 🖥️ ", Style::default().fg(header_color)),
                 Span::styled("Other tmux ", Style::default().fg(header_color).add_modifier(if is_selected_other { Modifier::BOLD } else { Modifier::empty() })),
-                Span::styled(format!("({})", session_count), Style::default().fg(MUTED_GRAY)),
+                Span::styled(format!("({})", session_count), Style::default().fg(theme.muted)),
             ]);
 
             items.push(ListItem::new(other_header));
@@ -229,19 +273,19 @@ impl SessionListComponent {
                     };
 
                     let name_color = if is_selected {
-                        SELECTION_GREEN
+                        theme.selection
                     } else if other_session.attached {
                         CORNFLOWER_BLUE
                     } else {
-                        MUTED_GRAY
+                        theme.muted
                     };
 
                     let session_line = Line::from(vec![
                         Span::styled("  ", Style::default()),
-                        Span::styled(tree_prefix, Style::default().fg(SUBDUED_BORDER)),
+                        Span::styled(tree_prefix, Style::default().fg(theme.border)),
                         Span::styled(format!(" {} ", status), Style::default()),
                         Span::styled(other_session.name.clone(), Style::default().fg(name_color).add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() })),
-                        Span::styled(windows_text, Style::default().fg(MUTED_GRAY)),
+                        Span::styled(windows_text, Style::default().fg(theme.muted)),
                     ]);
 
                     items.push(ListItem::new(session_line));
@@ -251,8 +295,8 @@ impl SessionListComponent {
 
         if items.is_empty() {
             let empty_line = Line::from(vec![
-                Span::styled("✨ ", Style::default().fg(MUTED_GRAY)),
-                Span::styled("No workspaces found", Style::default().fg(MUTED_GRAY).add_modifier(Modifier::ITALIC)),
+                Span::styled("✨ ", Style::default().fg(theme.muted)),
+                Span::styled("No workspaces found", Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)),
             ]);
             items.push(ListItem::new(empty_line));
         }
@@ -260,7 +304,141 @@ impl SessionListComponent {
         items
     }
 
+    /// Only the sessions matching the filter, each under its workspace; the "Other tmux"
+    /// section is hidden while filtering
+    fn build_filtered_items(state: &AppState) -> Vec<ListItem<'static>> {
+        let theme = &state.theme;
+        let matches = state.filtered_sessions();
+        let mut items = Vec::new();
+
+        for (position, &(workspace_idx, session_idx)) in matches.iter().enumerate() {
+            let workspace = &state.workspaces[workspace_idx];
+            if position == 0 || matches[position - 1].0 != workspace_idx {
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled("▼", Style::default().fg(theme.muted)),
+                    Span::styled(" 📁 ", Style::default().fg(CORNFLOWER_BLUE)),
+                    Span::styled(workspace.name.clone(), Style::default().fg(theme.text)),
+                ])));
+            }
+
+            let is_selected = state.selected_workspace_index == Some(workspace_idx)
+                && state.selected_session_index == Some(session_idx);
+            let is_last = matches.get(position + 1).is_none_or(|next| next.0 != workspace_idx);
+            items.push(Self::session_item(
+                &workspace.sessions[session_idx],
+                is_selected,
+                is_last,
+                theme,
+            ));
+        }
+
+        if items.is_empty() {
+            items.push(ListItem::new(Line::from(Span::styled(
+                "No sessions match the filter",
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+            ))));
+        }
+
+        items
+    }
+
+    /// Row of the selected session in the filtered list
+    fn filtered_selection_index(state: &AppState) -> Option<usize> {
+        let selected = state.selected_workspace_index.zip(state.selected_session_index)?;
+        let matches = state.filtered_sessions();
+        let mut row = 0;
+        for (position, &pair) in matches.iter().enumerate() {
+            if position == 0 || matches[position - 1].0 != pair.0 {
+                row += 1; // Workspace header
+            }
+            if pair == selected {
+                return Some(row);
+            }
+            row += 1;
+        }
+        None
+    }
+
+    /// Tree line for one session under its workspace
+    fn session_item(
+        session: &Session,
+        is_selected_session: bool,
+        is_last_session: bool,
+        theme: &Theme,
+    ) -> ListItem<'static> {
+        // Tree line characters with subdued color
+        let tree_prefix = if is_last_session { "└─" } else { "├─" };
+
+        let status_indicator = session.status.indicator();
+
+        // Mode indicator
+        let mode_indicator = match session.mode {
+            SessionMode::Boss => "🐳",
+            SessionMode::Interactive => "🖥️",
+        };
+
+        // Tmux status indicator
+        let tmux_indicator = if session.is_attached {
+            "🔗"
+        } else if session.tmux_session_name.is_some() {
+            "●"
+        } else {
+            "○"
+        };
+
+        // Review sessions sit on a detached HEAD and never commit
+        let review_text = if session.review_only { " 👁 review" } else { "" };
+
+        // Protected sessions are skipped by bulk operations
+        let protected_text = if session.protected { " 🔒" } else { "" };
+
+        let changes_text = if session.git_changes.total() > 0 {
+            format!(" ({})", session.git_changes.format())
+        } else {
+            String::new()
+        };
+
+        // Commits ahead of/behind the base branch; nothing without a base to compare to
+        let ahead_behind_text = session
+            .git_changes
+            .format_ahead_behind()
+            .map(|counts| format!(" {}", counts))
+            .unwrap_or_default();
+
+        // Premium session styling
+        let (branch_color, tmux_color) = if is_selected_session {
+            (theme.selection, theme.selection)
+        } else {
+            match session.status {
+                SessionStatus::Running => (theme.running, theme.text),
+                SessionStatus::Stopped => (theme.stopped, theme.stopped),
+                SessionStatus::Idle => (theme.idle, theme.text),
+                SessionStatus::Error(_) => (theme.error, theme.text),
+            }
+        };
+
+        let session_line = Line::from(vec![
+            Span::styled("  ", Style::default()),
+            Span::styled(tree_prefix, Style::default().fg(theme.border)),
+            Span::styled(format!(" {} ", status_indicator), Style::default()),
+            Span::styled(format!("{} ", mode_indicator), Style::default()),
+            Span::styled(format!("{} ", tmux_indicator), Style::default().fg(tmux_color)),
+            Span::styled(session.branch_name.clone(), Style::default().fg(branch_color).add_modifier(if is_selected_session { Modifier::BOLD } else { Modifier::empty() })),
+            Span::styled(review_text, Style::default().fg(theme.muted)),
+            Span::styled(protected_text, Style::default().fg(WARNING_ORANGE)),
+            Span::styled(changes_text, Style::default().fg(WARNING_ORANGE)),
+            Span::styled(ahead_behind_text, Style::default().fg(CORNFLOWER_BLUE)),
+        ]);
+
+        ListItem::new(session_line)
+    }
+
     fn update_selection(&mut self, state: &AppState) {
+        if state.session_filter.is_some() {
+            self.list_state.select(Self::filtered_selection_index(state));
+            return;
+        }
+
         if let Some(workspace_idx) = state.selected_workspace_index {
             let mut current_index = 0;
 
@@ -346,3 +524,57 @@ impl SessionListComponent {
 fn workspace_running_count(workspace: &Workspace) -> usize {
     workspace.running_sessions().len()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Session;
+
+    #[test]
+    fn test_list_row_at_maps_clicks_inside_the_border() {
+        let area = Rect::new(0, 3, 30, 10); // Rows 4..=11 inside the border
+
+        assert_eq!(list_row_at(area, 0, 5, 5, 4), Some(0));
+        assert_eq!(list_row_at(area, 0, 5, 5, 8), Some(4));
+        assert_eq!(list_row_at(area, 0, 5, 5, 9), None); // Below the last row
+        assert_eq!(list_row_at(area, 0, 5, 5, 3), None); // Top border
+        assert_eq!(list_row_at(area, 0, 5, 0, 4), None); // Left border
+        assert_eq!(list_row_at(area, 0, 5, 29, 4), None); // Right border
+        assert_eq!(list_row_at(area, 0, 20, 5, 12), None); // Bottom border
+        // A list scrolled by 6 shows row 6 first
+        assert_eq!(list_row_at(area, 6, 20, 5, 4), Some(6));
+        assert_eq!(list_row_at(area, 6, 20, 5, 11), Some(13));
+    }
+
+    #[test]
+    fn test_rows_follow_the_drawn_items() {
+        let mut state = AppState::new();
+        for name in ["api", "web"] {
+            let mut workspace = Workspace::new(name.to_string(), format!("/tmp/{}", name).into());
+            workspace.add_session(Session::new(format!("{}-a", name), "/tmp".to_string()));
+            workspace.add_session(Session::new(format!("{}-b", name), "/tmp".to_string()));
+            state.workspaces.push(workspace);
+        }
+        state.selected_workspace_index = Some(1);
+        state.selected_session_index = Some(0);
+        state.expand_all_workspaces = false;
+
+        // Only the selected workspace is expanded
+        let rows = SessionListComponent::rows(&state);
+        assert_eq!(
+            rows,
+            vec![
+                SessionListRow::Workspace(0),
+                SessionListRow::Workspace(1),
+                SessionListRow::Session(1, 0),
+                SessionListRow::Session(1, 1),
+            ]
+        );
+        assert_eq!(rows.len(), SessionListComponent::build_list_items_static(&state).len());
+
+        state.session_filter = Some("web-b".to_string());
+        let rows = SessionListComponent::rows(&state);
+        assert_eq!(rows, vec![SessionListRow::Workspace(1), SessionListRow::Session(1, 1)]);
+        assert_eq!(rows.len(), SessionListComponent::build_list_items_static(&state).len());
+    }
+}
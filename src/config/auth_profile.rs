@@ -0,0 +1,182 @@
+// ABOUTME: Named auth profiles under ~/.agents-in-a-box/auth/profiles/<name>, plus the active pointer
+// The default profile is the original ~/.agents-in-a-box/auth layout, so existing setups keep working
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// The profile stored directly in `auth/`, used until another one is selected
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Resolves credential, `.claude.json` and `.env` paths for auth profiles
+#[derive(Debug, Clone)]
+pub struct AuthProfiles {
+    /// `~/.agents-in-a-box`
+    base_dir: PathBuf,
+}
+
+impl AuthProfiles {
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self { base_dir }
+    }
+
+    /// Profiles under `~/.agents-in-a-box`, or None without a home directory
+    pub fn in_home_dir() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".agents-in-a-box")))
+    }
+
+    fn auth_root(&self) -> PathBuf {
+        self.base_dir.join("auth")
+    }
+
+    fn active_pointer(&self) -> PathBuf {
+        self.auth_root().join("active_profile")
+    }
+
+    /// Profile names become directory names, so keep them to a safe character set
+    pub fn validate_name(name: &str) -> Result<(), String> {
+        if name.is_empty() || name.len() > 64 {
+            return Err("profile names must be 1-64 characters".to_string());
+        }
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!(
+                "invalid profile name '{}' (use letters, digits, '-' and '_')",
+                name
+            ));
+        }
+        Ok(())
+    }
+
+    /// The selected profile; a missing or invalid pointer means the default profile
+    pub fn active(&self) -> String {
+        let name = match fs::read_to_string(self.active_pointer()) {
+            Ok(contents) => contents.trim().to_string(),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed to read {}: {}", self.active_pointer().display(), e);
+                }
+                return DEFAULT_PROFILE.to_string();
+            }
+        };
+        match Self::validate_name(&name) {
+            Ok(()) => name,
+            Err(e) => {
+                warn!("Ignoring active auth profile: {}", e);
+                DEFAULT_PROFILE.to_string()
+            }
+        }
+    }
+
+    /// Select a profile for new sessions, creating its directory if needed
+    pub fn set_active(&self, name: &str) -> Result<(), String> {
+        Self::validate_name(name)?;
+        let dir = self.profile_dir(name);
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        if name == DEFAULT_PROFILE {
+            return match fs::remove_file(self.active_pointer()) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    Err(format!("Failed to reset active profile: {}", e))
+                }
+                _ => Ok(()),
+            };
+        }
+        fs::write(self.active_pointer(), format!("{}\n", name))
+            .map_err(|e| format!("Failed to save active profile: {}", e))
+    }
+
+    /// The default profile followed by every named profile, sorted
+    pub fn list(&self) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(self.auth_root().join("profiles"))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter(|name| name != DEFAULT_PROFILE && Self::validate_name(name).is_ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names.insert(0, DEFAULT_PROFILE.to_string());
+        names
+    }
+
+    /// Directory holding a profile's `.credentials.json` and `.claude.json`
+    pub fn profile_dir(&self, name: &str) -> PathBuf {
+        if name == DEFAULT_PROFILE {
+            self.auth_root()
+        } else {
+            self.auth_root().join("profiles").join(name)
+        }
+    }
+
+    /// A profile's `.env`; the default profile keeps using `~/.agents-in-a-box/.env`
+    pub fn env_file(&self, name: &str) -> PathBuf {
+        if name == DEFAULT_PROFILE {
+            self.base_dir.join(".env")
+        } else {
+            self.profile_dir(name).join(".env")
+        }
+    }
+
+    /// Auth directory of the active profile
+    pub fn active_dir(&self) -> PathBuf {
+        self.profile_dir(&self.active())
+    }
+
+    /// `.env` of the active profile
+    pub fn active_env_file(&self) -> PathBuf {
+        self.env_file(&self.active())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_default_profile_uses_the_original_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let profiles = AuthProfiles::new(temp_dir.path().to_path_buf());
+
+        assert_eq!(profiles.active(), DEFAULT_PROFILE);
+        assert_eq!(profiles.active_dir(), temp_dir.path().join("auth"));
+        assert_eq!(profiles.active_env_file(), temp_dir.path().join(".env"));
+        assert_eq!(profiles.list(), vec![DEFAULT_PROFILE.to_string()]);
+    }
+
+    #[test]
+    fn test_paths_resolve_through_the_active_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let profiles = AuthProfiles::new(temp_dir.path().to_path_buf());
+
+        profiles.set_active("work").unwrap();
+        profiles.set_active("personal").unwrap();
+        let personal = temp_dir.path().join("auth").join("profiles").join("personal");
+        assert_eq!(profiles.active(), "personal");
+        assert_eq!(profiles.active_dir(), personal);
+        assert_eq!(profiles.active_env_file(), personal.join(".env"));
+        assert!(personal.is_dir());
+        assert_eq!(profiles.list(), vec!["default", "personal", "work"]);
+
+        profiles.set_active(DEFAULT_PROFILE).unwrap();
+        assert_eq!(profiles.active_dir(), temp_dir.path().join("auth"));
+        assert!(!temp_dir.path().join("auth").join("active_profile").exists());
+    }
+
+    #[test]
+    fn test_invalid_profile_names_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let profiles = AuthProfiles::new(temp_dir.path().to_path_buf());
+
+        assert!(profiles.set_active("../escape").is_err());
+        assert!(profiles.set_active("").is_err());
+
+        fs::create_dir_all(temp_dir.path().join("auth")).unwrap();
+        fs::write(temp_dir.path().join("auth").join("active_profile"), "../../etc\n").unwrap();
+        assert_eq!(profiles.active(), DEFAULT_PROFILE);
+    }
+}
@@ -0,0 +1,540 @@
+// ABOUTME: Remappable session list keybindings, loaded from ~/.agents-in-a-box/keys.toml
+// The event handler and the help overlay both read the same keymap, so help matches reality
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A key plus modifiers, e.g. `ctrl-n`, `D`, `f5`, `space`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    const fn key(ch: char) -> Self {
+        Self::new(KeyCode::Char(ch), KeyModifiers::NONE)
+    }
+
+    /// Parse `ctrl-n`, `alt+x`, `G`, `enter`, `f5`, ...
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = input;
+        // A lone "-" or "+" is the key itself, not a separator
+        while key.len() > 1 {
+            let Some((prefix, rest)) = key.split_once(['-', '+']) else {
+                break;
+            };
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            key = rest;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", input)),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers).normalized())
+    }
+
+    /// Terminals report uppercase letters with or without SHIFT, so the case of a
+    /// character key carries the shift and the modifier is dropped
+    fn normalized(self) -> Self {
+        match self.code {
+            KeyCode::Char(_) => Self::new(self.code, self.modifiers - KeyModifiers::SHIFT),
+            _ => self,
+        }
+    }
+
+    pub fn from_event(event: &KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers).normalized()
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(ch) => write!(f, "{}", ch),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// Keys the event handler matches directly (navigation, pane-specific actions),
+/// which therefore can't be bound to an action
+const RESERVED_CHORDS: [KeyChord; 26] = [
+    KeyChord::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+    KeyChord::new(KeyCode::Tab, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::Enter, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::Esc, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::Up, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::Down, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::Left, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::Right, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::Home, KeyModifiers::NONE),
+    KeyChord::new(KeyCode::End, KeyModifiers::NONE),
+    KeyChord::key('j'),
+    KeyChord::key('k'),
+    KeyChord::key('h'),
+    KeyChord::key('l'),
+    KeyChord::key('<'),
+    KeyChord::key('>'),
+    KeyChord::key(' '),
+    KeyChord::key('f'),
+    KeyChord::key('y'),
+    KeyChord::key('t'),
+    KeyChord::key('A'),
+    KeyChord::key('m'),
+    KeyChord::key('w'),
+    KeyChord::key('['),
+    KeyChord::key(']'),
+    KeyChord::key('b'),
+];
+
+/// Session list actions that can be remapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum KeyAction {
+    Quit,
+    ToggleHelp,
+    NewSession,
+    ReviewSession,
+    SearchWorkspace,
+    Attach,
+    Reauthenticate,
+    Restart,
+    Delete,
+    StopKeepWorktree,
+    StopSession,
+    ToggleProtection,
+    CycleSort,
+    FilterSessions,
+    CleanupOrphaned,
+    CleanupDead,
+    ClaudeChat,
+    GitView,
+    Dashboard,
+    LogsViewer,
+    ExternalDiff,
+    CompareLastRun,
+    CopyContainerId,
+    CopyWorktreePath,
+    QuickCommit,
+    ExpandAll,
+    NotificationHistory,
+    DismissNotification,
+    AuthProfiles,
+    Logout,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 30] = [
+        KeyAction::Quit,
+        KeyAction::ToggleHelp,
+        KeyAction::NewSession,
+        KeyAction::ReviewSession,
+        KeyAction::SearchWorkspace,
+        KeyAction::Attach,
+        KeyAction::Reauthenticate,
+        KeyAction::Restart,
+        KeyAction::Delete,
+        KeyAction::StopKeepWorktree,
+        KeyAction::StopSession,
+        KeyAction::ToggleProtection,
+        KeyAction::CycleSort,
+        KeyAction::FilterSessions,
+        KeyAction::CleanupOrphaned,
+        KeyAction::CleanupDead,
+        KeyAction::ClaudeChat,
+        KeyAction::GitView,
+        KeyAction::Dashboard,
+        KeyAction::LogsViewer,
+        KeyAction::ExternalDiff,
+        KeyAction::CompareLastRun,
+        KeyAction::CopyContainerId,
+        KeyAction::CopyWorktreePath,
+        KeyAction::QuickCommit,
+        KeyAction::ExpandAll,
+        KeyAction::NotificationHistory,
+        KeyAction::DismissNotification,
+        KeyAction::AuthProfiles,
+        KeyAction::Logout,
+    ];
+
+    /// Name used in keys.toml
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::Quit => "quit",
+            KeyAction::ToggleHelp => "toggle_help",
+            KeyAction::NewSession => "new_session",
+            KeyAction::ReviewSession => "review_session",
+            KeyAction::SearchWorkspace => "search_workspace",
+            KeyAction::Attach => "attach",
+            KeyAction::Reauthenticate => "reauthenticate",
+            KeyAction::Restart => "restart",
+            KeyAction::Delete => "delete",
+            KeyAction::StopKeepWorktree => "stop_keep_worktree",
+            KeyAction::StopSession => "stop_session",
+            KeyAction::ToggleProtection => "toggle_protection",
+            KeyAction::CycleSort => "cycle_sort",
+            KeyAction::FilterSessions => "filter_sessions",
+            KeyAction::CleanupOrphaned => "cleanup_orphaned",
+            KeyAction::CleanupDead => "cleanup_dead",
+            KeyAction::ClaudeChat => "claude_chat",
+            KeyAction::GitView => "git_view",
+            KeyAction::Dashboard => "dashboard",
+            KeyAction::LogsViewer => "logs_viewer",
+            KeyAction::ExternalDiff => "external_diff",
+            KeyAction::CompareLastRun => "compare_last_run",
+            KeyAction::CopyContainerId => "copy_container_id",
+            KeyAction::CopyWorktreePath => "copy_worktree_path",
+            KeyAction::QuickCommit => "quick_commit",
+            KeyAction::ExpandAll => "expand_all",
+            KeyAction::NotificationHistory => "notification_history",
+            KeyAction::DismissNotification => "dismiss_notification",
+            KeyAction::AuthProfiles => "auth_profiles",
+            KeyAction::Logout => "logout",
+        }
+    }
+
+    /// What the help overlay says the action does
+    pub fn description(&self) -> &'static str {
+        match self {
+            KeyAction::Quit => "Quit application",
+            KeyAction::ToggleHelp => "Toggle this help",
+            KeyAction::NewSession => "New session (current directory)",
+            KeyAction::ReviewSession => "New review session (detached revision)",
+            KeyAction::SearchWorkspace => "Search & select workspace",
+            KeyAction::Attach => "Attach to session",
+            KeyAction::Reauthenticate => "Re-authenticate credentials",
+            KeyAction::Restart => "Restart stopped session",
+            KeyAction::Delete => "Delete session (container + worktree)",
+            KeyAction::StopKeepWorktree => "Remove container, keep worktree",
+            KeyAction::StopSession => "Stop container (restart later)",
+            KeyAction::ToggleProtection => "Protect session from bulk actions",
+            KeyAction::CycleSort => "Cycle session sort (status/name/activity/changes)",
+            KeyAction::FilterSessions => "Filter sessions by name, branch, or workspace",
+            KeyAction::CleanupOrphaned => "Cleanup orphaned containers",
+            KeyAction::CleanupDead => "Delete all stopped/errored sessions",
+            KeyAction::ClaudeChat => "Claude chat",
+            KeyAction::GitView => "Show git view",
+            KeyAction::Dashboard => "Dashboard (all sessions at a glance)",
+            KeyAction::LogsViewer => "View session logs full screen (searchable)",
+            KeyAction::ExternalDiff => "Open diff in external tool",
+            KeyAction::CompareLastRun => "Compare boss run with previous run",
+            KeyAction::CopyContainerId => "Copy container ID",
+            KeyAction::CopyWorktreePath => "Copy worktree path",
+            KeyAction::QuickCommit => "Commit & push",
+            KeyAction::ExpandAll => "Expand / collapse all workspaces",
+            KeyAction::NotificationHistory => "Notification history",
+            KeyAction::DismissNotification => "Dismiss a sticky error notification",
+            KeyAction::AuthProfiles => "Switch auth profile",
+            KeyAction::Logout => "Log out and remove stored credentials",
+        }
+    }
+
+    fn default_chord(&self) -> KeyChord {
+        KeyChord::key(match self {
+            KeyAction::Quit => 'q',
+            KeyAction::ToggleHelp => '?',
+            KeyAction::NewSession => 'n',
+            KeyAction::ReviewSession => 'R',
+            KeyAction::SearchWorkspace => 's',
+            KeyAction::Attach => 'a',
+            KeyAction::Reauthenticate => 'r',
+            KeyAction::Restart => 'e',
+            KeyAction::Delete => 'd',
+            KeyAction::StopKeepWorktree => 'S',
+            KeyAction::StopSession => 'P',
+            KeyAction::ToggleProtection => 'L',
+            KeyAction::CycleSort => 'o',
+            KeyAction::FilterSessions => '/',
+            KeyAction::CleanupOrphaned => 'x',
+            KeyAction::CleanupDead => 'X',
+            KeyAction::ClaudeChat => 'c',
+            KeyAction::GitView => 'g',
+            KeyAction::Dashboard => 'H',
+            KeyAction::LogsViewer => 'v',
+            KeyAction::ExternalDiff => 'D',
+            KeyAction::CompareLastRun => 'C',
+            KeyAction::CopyContainerId => 'i',
+            KeyAction::CopyWorktreePath => 'W',
+            KeyAction::QuickCommit => 'p',
+            KeyAction::ExpandAll => 'E',
+            KeyAction::NotificationHistory => 'M',
+            KeyAction::DismissNotification => 'z',
+            KeyAction::AuthProfiles => 'u',
+            KeyAction::Logout => 'O',
+        })
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// One chord or several for an action in keys.toml
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ChordList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Effective bindings: each action's chords, with unmapped actions on their defaults
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: BTreeMap<KeyAction, Vec<KeyChord>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: KeyAction::ALL
+                .into_iter()
+                .map(|action| (action, vec![action.default_chord()]))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// `~/.agents-in-a-box/keys.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".agents-in-a-box").join("keys.toml"))
+    }
+
+    /// Load the keymap file. A missing file means the defaults; a file with problems is
+    /// ignored entirely and its problems returned, so one typo can't leave keys unbound.
+    pub fn load(path: &Path) -> (Self, Vec<String>) {
+        match std::fs::read_to_string(path) {
+            Ok(content) => match Self::from_toml(&content) {
+                Ok(keymap) => (keymap, Vec::new()),
+                Err(errors) => (Self::default(), errors),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (Self::default(), Vec::new()),
+            Err(e) => (Self::default(), vec![format!("failed to read {}: {}", path.display(), e)]),
+        }
+    }
+
+    /// Apply `action = "chord"` / `action = ["chord", ...]` overrides on top of the
+    /// defaults. An empty list unbinds the action.
+    pub fn from_toml(content: &str) -> Result<Self, Vec<String>> {
+        let overrides: BTreeMap<String, ChordList> =
+            toml::from_str(content).map_err(|e| vec![format!("invalid keys.toml: {}", e)])?;
+
+        let mut keymap = Self::default();
+        let mut errors = Vec::new();
+        for (name, chords) in overrides {
+            let Some(action) = KeyAction::from_name(&name) else {
+                errors.push(format!("unknown action '{}'", name));
+                continue;
+            };
+            let chords = match chords {
+                ChordList::One(chord) => vec![chord],
+                ChordList::Many(chords) => chords,
+            };
+            let mut parsed = Vec::new();
+            for chord in chords {
+                match KeyChord::parse(&chord) {
+                    Ok(chord) if RESERVED_CHORDS.contains(&chord) => {
+                        errors.push(format!("{}: '{}' is a built-in key", name, chord));
+                    }
+                    Ok(chord) => parsed.push(chord),
+                    Err(e) => errors.push(format!("{}: {}", name, e)),
+                }
+            }
+            keymap.bindings.insert(action, parsed);
+        }
+
+        errors.extend(keymap.conflicts());
+        if errors.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Chords bound to more than one action
+    fn conflicts(&self) -> Vec<String> {
+        let mut owners: BTreeMap<String, Vec<&'static str>> = BTreeMap::new();
+        for (action, chords) in &self.bindings {
+            for chord in chords {
+                owners.entry(chord.to_string()).or_default().push(action.name());
+            }
+        }
+        owners
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(chord, actions)| format!("'{}' is bound to {}", chord, actions.join(" and ")))
+            .collect()
+    }
+
+    pub fn action_for(&self, event: &KeyEvent) -> Option<KeyAction> {
+        let chord = KeyChord::from_event(event);
+        self.bindings
+            .iter()
+            .find(|(_, chords)| chords.contains(&chord))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn is(&self, event: &KeyEvent, action: KeyAction) -> bool {
+        let chord = KeyChord::from_event(event);
+        self.bindings.get(&action).is_some_and(|chords| chords.contains(&chord))
+    }
+
+    /// The action's chords for display, e.g. `n` or `n/Ctrl+n`
+    pub fn label(&self, action: KeyAction) -> String {
+        match self.bindings.get(&action) {
+            Some(chords) if !chords.is_empty() => {
+                chords.iter().map(ToString::to_string).collect::<Vec<_>>().join("/")
+            }
+            _ => "(unbound)".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_parse_chords() {
+        let parse = |s: &str| KeyChord::parse(s).unwrap();
+        assert_eq!(parse("n"), KeyChord::key('n'));
+        assert_eq!(parse("N"), KeyChord::key('N'));
+        assert_eq!(parse("shift-n"), KeyChord::key('n'));
+        assert_eq!(parse("ctrl-n"), KeyChord::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(
+            parse("Ctrl+Alt+x"),
+            KeyChord::new(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(parse("-"), KeyChord::key('-'));
+        assert_eq!(parse("ctrl--"), KeyChord::new(KeyCode::Char('-'), KeyModifiers::CONTROL));
+        assert_eq!(parse("space"), KeyChord::key(' '));
+        assert_eq!(parse("f5"), KeyChord::new(KeyCode::F(5), KeyModifiers::NONE));
+        assert_eq!(parse("shift-tab").to_string(), "Shift+Tab");
+        assert!(KeyChord::parse("f13").is_err());
+        assert!(KeyChord::parse("hyper-x").is_err());
+    }
+
+    #[test]
+    fn test_overrides_replace_defaults_and_others_keep_theirs() {
+        let keymap = Keymap::from_toml(
+            r#"
+            new_session = ["N", "ctrl-n"]
+            delete = []
+            "#,
+        )
+        .unwrap();
+
+        // Uppercase letters match whether or not the terminal reports SHIFT
+        let shift_n = press(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert_eq!(keymap.action_for(&shift_n), Some(KeyAction::NewSession));
+        let ctrl_n = press(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action_for(&ctrl_n), Some(KeyAction::NewSession));
+        assert_eq!(keymap.action_for(&press(KeyCode::Char('n'), KeyModifiers::NONE)), None);
+        assert_eq!(keymap.action_for(&press(KeyCode::Char('d'), KeyModifiers::NONE)), None);
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('q'), KeyModifiers::NONE)),
+            Some(KeyAction::Quit)
+        );
+
+        assert_eq!(keymap.label(KeyAction::NewSession), "N/Ctrl+n");
+        assert_eq!(keymap.label(KeyAction::Delete), "(unbound)");
+        assert_eq!(keymap.label(KeyAction::Quit), "q");
+    }
+
+    #[test]
+    fn test_conflicts_and_mistakes_are_reported() {
+        // Binding delete to the default new_session key, with no new_session override
+        assert_eq!(
+            Keymap::from_toml(r#"delete = "n""#),
+            Err(vec!["'n' is bound to new_session and delete".to_string()])
+        );
+        // Moving new_session away first makes the same binding valid
+        assert!(Keymap::from_toml("delete = \"n\"\nnew_session = \"N\"").is_ok());
+
+        assert_eq!(
+            Keymap::from_toml("launch = \"z\"\nquit = \"hyper-q\"\nattach = \"j\""),
+            Err(vec![
+                "attach: 'j' is a built-in key".to_string(),
+                "unknown action 'launch'".to_string(),
+                "quit: unknown key 'hyper-q'".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_logs_pane_wrap_key_is_reserved() {
+        assert_eq!(
+            Keymap::from_toml(r#"git_view = "b""#),
+            Err(vec!["git_view: 'b' is a built-in key".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_default_keymap_has_no_conflicts() {
+        let keymap = Keymap::default();
+        assert!(keymap.conflicts().is_empty());
+        for action in KeyAction::ALL {
+            assert!(!RESERVED_CHORDS.contains(&action.default_chord()), "{:?}", action);
+        }
+    }
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (keymap, errors) = Keymap::load(&temp_dir.path().join("keys.toml"));
+        assert_eq!(keymap, Keymap::default());
+        assert!(errors.is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 
 #![allow(dead_code)]
 
+use crate::models::SessionMode;
 use anyhow::{Context, Result};
 use dirs;
 use serde::{Deserialize, Serialize};
@@ -10,13 +11,19 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod auth_profile;
 pub mod container;
+pub mod keymap;
 pub mod mcp;
 pub mod mcp_init;
+pub mod theme;
 
+pub use auth_profile::AuthProfiles;
 pub use container::{ContainerTemplate, ContainerTemplateConfig};
+pub use keymap::{KeyAction, Keymap};
 pub use mcp::{McpInitStrategy, McpServerConfig};
 pub use mcp_init::{McpInitResult, McpInitializer, apply_mcp_init_result};
+pub use theme::Theme;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -48,6 +55,11 @@ pub struct AppConfig {
     #[serde(default)]
     pub ui_preferences: UiPreferences,
 
+    /// Color overrides applied on top of the `ui_preferences.theme` preset,
+    /// e.g. `error = "light_red"` or `selection_bg = "#28283c"`
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    pub theme: toml::Table,
+
     /// Docker configuration
     #[serde(default)]
     pub docker: DockerConfig,
@@ -55,6 +67,10 @@ pub struct AppConfig {
     /// Tmux configuration
     #[serde(default)]
     pub tmux: TmuxConfig,
+
+    /// URL that session lifecycle and agent events are POSTed to as JSON (best-effort)
+    #[serde(default)]
+    pub webhook_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,9 +92,79 @@ pub struct WorkspaceDefaults {
     #[serde(default)]
     pub workspace_scan_paths: Vec<PathBuf>,
 
+    /// Directories to scan instead of the defaults, in priority order. Repositories
+    /// are listed root by root, so the first root's repositories appear first.
+    /// `workspace_scan_paths` are still scanned after these. Missing roots are skipped.
+    #[serde(default)]
+    pub scan_roots: Vec<PathBuf>,
+
     /// Maximum number of repositories to show in search results (default: 500)
     #[serde(default = "default_max_repositories")]
     pub max_repositories: usize,
+
+    /// How many directory levels below each search path are scanned for repositories
+    #[serde(default = "default_max_scan_depth")]
+    pub max_scan_depth: usize,
+
+    /// Whether to remove orphaned containers and prune stale worktrees on startup
+    #[serde(default)]
+    pub cleanup_orphaned_on_startup: bool,
+
+    /// Only list repositories that opt in, via a `.agents-box-enabled` marker file or
+    /// `opt_in = true` in `.agents-box/project.toml`
+    #[serde(default)]
+    pub require_opt_in: bool,
+
+    /// Name Boss sessions after the first line of their prompt (e.g. `agents/fix-login-bug`)
+    /// instead of a random id, when the generated branch name is left unchanged
+    #[serde(default)]
+    pub name_from_prompt: bool,
+
+    /// Claude settings file (allowed tools, MCP servers, ...) given to every session's
+    /// container. A project's `claude_settings` takes precedence.
+    #[serde(default)]
+    pub claude_settings_template: Option<PathBuf>,
+
+    /// Shell command run on the host, detached, after a session is created. Placeholders:
+    /// `{worktree}`, `{branch}`, `{session_id}`. This runs arbitrary commands with your
+    /// user's privileges; only set commands you trust. Unset by default.
+    #[serde(default)]
+    pub on_create_host_command: Option<String>,
+
+    /// Let a repository's `.agents-box/project.toml` supply its own `on_create_host_command`.
+    /// Off by default because a cloned repository could then run commands on your host.
+    #[serde(default)]
+    pub allow_project_host_commands: bool,
+
+    /// Mode preselected for new sessions (`"Interactive"` or `"Boss"`)
+    #[serde(default)]
+    pub default_mode: SessionMode,
+
+    /// Whether new sessions start with permission prompts skipped
+    #[serde(default)]
+    pub default_skip_permissions: bool,
+
+    /// Ref the git view's Commits tab lists commits since (e.g. `origin/develop`). Unset
+    /// uses the repository's default base branch.
+    #[serde(default)]
+    pub commit_log_base: Option<String>,
+
+    /// Claude model preselected for new sessions and used by the TUI chat (a full model
+    /// id such as `claude-sonnet-4-5`). Unset keeps the Claude CLI's own default in
+    /// containers and `claude-3-5-sonnet-20241022` in the chat.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Models offered when creating a session, cycled with `m` in the mode step. Session
+    /// containers also accept Claude CLI aliases such as `opus`.
+    #[serde(default = "default_models")]
+    pub models: Vec<String>,
+
+    /// Tokens (prompt plus reply) a TUI chat conversation may use before new messages
+    /// are blocked. Ctrl+B in the chat raises the budget by this amount again. Unset
+    /// means no limit.
+    #[serde(default)]
+    pub chat_token_budget: Option<u32>,
 }
 
 impl Default for WorkspaceDefaults {
@@ -88,12 +174,26 @@ impl Default for WorkspaceDefaults {
             auto_detect: default_true(),
             exclude_paths: Vec::new(),
             workspace_scan_paths: Vec::new(),
+            scan_roots: Vec::new(),
             max_repositories: default_max_repositories(),
+            max_scan_depth: default_max_scan_depth(),
+            cleanup_orphaned_on_startup: false,
+            require_opt_in: false,
+            name_from_prompt: false,
+            claude_settings_template: None,
+            on_create_host_command: None,
+            allow_project_host_commands: false,
+            default_mode: SessionMode::default(),
+            default_skip_permissions: false,
+            commit_log_base: None,
+            model: None,
+            models: default_models(),
+            chat_token_budget: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UiPreferences {
     /// Color theme
     #[serde(default = "default_theme")]
@@ -106,9 +206,247 @@ pub struct UiPreferences {
     /// Whether to show git status in UI
     #[serde(default = "default_true")]
     pub show_git_status: bool,
+
+    /// Whether to show a warning badge on workspaces with too many sessions
+    #[serde(default = "default_true")]
+    pub warn_on_session_count: bool,
+
+    /// Soft limit on sessions per workspace before the warning badge appears
+    #[serde(default = "default_max_sessions_per_workspace")]
+    pub max_sessions_per_workspace: usize,
+
+    /// External diff tool command (e.g. "delta", "less -R", "code --wait {file}").
+    /// `{file}` is replaced with a file containing the diff; without it the diff
+    /// is piped to the command on stdin. Unset uses the built-in git view.
+    #[serde(default)]
+    pub diff_tool_command: Option<String>,
+
+    /// What Escape does once there is no popup or view left to back out of
+    #[serde(default)]
+    pub escape_policy: EscapePolicy,
+
+    /// Whether running sessions' containers are stopped when the app exits; off leaves
+    /// them running in the background
+    #[serde(default)]
+    pub stop_sessions_on_exit: bool,
+
+    /// Minimum terminal width (columns) before a "terminal too small" message replaces the UI
+    #[serde(default = "default_min_terminal_width")]
+    pub min_terminal_width: u16,
+
+    /// Minimum terminal height (rows) before a "terminal too small" message replaces the UI
+    #[serde(default = "default_min_terminal_height")]
+    pub min_terminal_height: u16,
+
+    /// Whether the live logs pane shows a timestamp column on startup (toggle with 't')
+    #[serde(default)]
+    pub show_log_timestamps: bool,
+
+    /// Whether live logs render ANSI colors from agent output; off shows stripped plain
+    /// text (toggle with 'A')
+    #[serde(default = "default_true")]
+    pub log_ansi_colors: bool,
+
+    /// Live log timestamp format: "relative" (e.g. "2s ago") or a strftime pattern
+    #[serde(default = "default_log_timestamp_format")]
+    pub log_timestamp_format: String,
+
+    /// Periodically save the in-progress boss prompt so it survives a stray Escape or crash
+    #[serde(default = "default_true")]
+    pub auto_save_prompt_drafts: bool,
+
+    /// Seconds between boss prompt draft auto-saves
+    #[serde(default = "default_prompt_draft_interval_secs")]
+    pub prompt_draft_interval_secs: u64,
+
+    /// Ring the terminal bell when a session completes, errors, or waits for input
+    #[serde(default)]
+    pub bell_on_session_events: bool,
+
+    /// Briefly invert the screen when a session completes, errors, or waits for input
+    #[serde(default)]
+    pub flash_on_session_events: bool,
+
+    /// Turn off all bells and flashes, whatever the settings above say (for shared environments)
+    #[serde(default)]
+    pub silent: bool,
+
+    /// Pastes into the boss prompt larger than this many bytes ask whether to insert
+    /// inline or save to a file and reference it with `@`. 0 always inserts inline.
+    #[serde(default = "default_large_paste_threshold")]
+    pub large_paste_threshold: usize,
+
+    /// Soft-wrap long lines of the boss prompt editor at the pane width (no newlines
+    /// are inserted). When off, long lines scroll horizontally.
+    #[serde(default = "default_true")]
+    pub prompt_soft_wrap: bool,
+
+    /// Boss prompt size (characters) at which the editor's counter turns yellow
+    #[serde(default = "default_prompt_soft_char_limit")]
+    pub prompt_soft_char_limit: usize,
+
+    /// Boss prompt size (characters) at which the counter turns red and warns that the
+    /// agent may truncate the prompt
+    #[serde(default = "default_prompt_hard_char_limit")]
+    pub prompt_hard_char_limit: usize,
+
+    /// Percentage of the width given to the session list; the logs pane gets the rest.
+    /// Adjustable at runtime with Ctrl+Left/Right or `<`/`>`.
+    #[serde(default = "default_session_list_percent")]
+    pub session_list_percent: u16,
+
+    /// Milliseconds between UI ticks once nothing has happened for `idle_after_secs`,
+    /// to cut idle CPU use. Values at or below the normal 250ms tick never slow down.
+    #[serde(default = "default_idle_tick_ms")]
+    pub idle_tick_ms: u64,
+
+    /// Seconds without input, streamed logs, or pending work before ticks slow down
+    #[serde(default = "default_idle_after_secs")]
+    pub idle_after_secs: u64,
+
+    /// Order of sessions within each workspace; cycled with 'o' and saved back here
+    #[serde(default)]
+    pub session_sort: SessionSortMode,
+
+    /// Live log entries kept in memory per session. Older entries are moved to a file
+    /// under `~/.agents-in-a-box/log_spill`, a tenth of the cap at a time, so exports
+    /// still include them.
+    #[serde(default = "default_max_log_entries")]
+    pub max_log_entries: usize,
+
+    /// How long each kind of notification stays on screen, and whether errors stay
+    /// until dismissed (`[ui_preferences.notifications]`)
+    #[serde(default)]
+    pub notifications: NotificationConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl Default for UiPreferences {
+    fn default() -> Self {
+        Self {
+            theme: default_theme(),
+            show_container_status: default_true(),
+            show_git_status: default_true(),
+            warn_on_session_count: default_true(),
+            max_sessions_per_workspace: default_max_sessions_per_workspace(),
+            diff_tool_command: None,
+            escape_policy: EscapePolicy::default(),
+            stop_sessions_on_exit: false,
+            min_terminal_width: default_min_terminal_width(),
+            min_terminal_height: default_min_terminal_height(),
+            show_log_timestamps: false,
+            log_ansi_colors: default_true(),
+            log_timestamp_format: default_log_timestamp_format(),
+            auto_save_prompt_drafts: default_true(),
+            prompt_draft_interval_secs: default_prompt_draft_interval_secs(),
+            bell_on_session_events: false,
+            flash_on_session_events: false,
+            silent: false,
+            large_paste_threshold: default_large_paste_threshold(),
+            prompt_soft_wrap: default_true(),
+            prompt_soft_char_limit: default_prompt_soft_char_limit(),
+            prompt_hard_char_limit: default_prompt_hard_char_limit(),
+            session_list_percent: default_session_list_percent(),
+            idle_tick_ms: default_idle_tick_ms(),
+            idle_after_secs: default_idle_after_secs(),
+            session_sort: SessionSortMode::default(),
+            max_log_entries: default_max_log_entries(),
+            notifications: NotificationConfig::default(),
+        }
+    }
+}
+
+/// What Escape does on the session list.
+///
+/// Everywhere else Escape closes the topmost overlay (confirmation, help, popups,
+/// file finder) or, with nothing open, returns to the session list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EscapePolicy {
+    /// Escape on the session list does nothing; the app is only quit with `q` or Ctrl+C
+    #[default]
+    Back,
+    /// Escape on the session list quits the app (the pre-policy behavior)
+    BackOrQuit,
+}
+
+/// How sessions are ordered within each workspace of the session list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionSortMode {
+    /// Running first, then idle, stopped, and errored sessions
+    #[default]
+    Status,
+    /// Alphabetical by session name
+    Name,
+    /// Most recently active first
+    LastActivity,
+    /// Most changed files first
+    Changes,
+}
+
+impl SessionSortMode {
+    /// The mode after this one when cycling
+    pub fn next(self) -> Self {
+        match self {
+            SessionSortMode::Status => SessionSortMode::Name,
+            SessionSortMode::Name => SessionSortMode::LastActivity,
+            SessionSortMode::LastActivity => SessionSortMode::Changes,
+            SessionSortMode::Changes => SessionSortMode::Status,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionSortMode::Status => "status",
+            SessionSortMode::Name => "name",
+            SessionSortMode::LastActivity => "last activity",
+            SessionSortMode::Changes => "changes",
+        }
+    }
+}
+
+/// Seconds each notification type stays on screen. Sticky errors ignore their duration
+/// and stay until dismissed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    #[serde(default = "default_success_notification_secs")]
+    pub success_secs: u64,
+    #[serde(default = "default_info_notification_secs")]
+    pub info_secs: u64,
+    #[serde(default = "default_warning_notification_secs")]
+    pub warning_secs: u64,
+    #[serde(default = "default_error_notification_secs")]
+    pub error_secs: u64,
+    #[serde(default = "default_true")]
+    pub sticky_errors: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            success_secs: default_success_notification_secs(),
+            info_secs: default_info_notification_secs(),
+            warning_secs: default_warning_notification_secs(),
+            error_secs: default_error_notification_secs(),
+            sticky_errors: default_true(),
+        }
+    }
+}
+
+/// What to do when the agents-dev image needed for sessions and authentication is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingImagePolicy {
+    /// Build the image automatically (the original behavior)
+    #[default]
+    Build,
+    /// Ask before starting the build, which can take several minutes
+    Prompt,
+    /// Never build implicitly; the image is built with `agents-box build-image`
+    Manual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerConfig {
     /// Docker host connection string
     /// Examples:
@@ -124,6 +462,88 @@ pub struct DockerConfig {
     /// TLS configuration for TCP connections
     #[serde(default)]
     pub tls: Option<DockerTlsConfig>,
+
+    /// Maximum number of image builds allowed to run at once; further builds queue
+    #[serde(default = "default_max_concurrent_builds")]
+    pub max_concurrent_builds: usize,
+
+    /// Whether a missing agents-dev image is built automatically, after a prompt, or only on request
+    #[serde(default)]
+    pub missing_image_policy: MissingImagePolicy,
+
+    /// Seconds after creation during which a container in an unexpected state (still
+    /// `created`, `restarting`, ...) is shown as stopped rather than errored
+    #[serde(default = "default_startup_grace_secs")]
+    pub startup_grace_secs: u64,
+
+    /// Memory limit in MB for session containers, overriding the container template
+    #[serde(default)]
+    pub memory_limit_mb: Option<u64>,
+
+    /// Relative CPU weight for session containers (Docker's default is 1024)
+    #[serde(default)]
+    pub cpu_shares: Option<u64>,
+
+    /// Image for session containers, replacing the container template's image
+    #[serde(default)]
+    pub container_image: Option<String>,
+
+    /// Dockerfile that builds `container_image` when it isn't present locally, with its
+    /// directory as the build context (the image is pulled when unset)
+    #[serde(default)]
+    pub container_dockerfile: Option<PathBuf>,
+
+    /// Ports published from every session container, as [host, container] pairs
+    #[serde(default)]
+    pub ports: Vec<(u16, u16)>,
+
+    /// Seconds between checks that running sessions' containers are still up (0 disables)
+    #[serde(default = "default_health_check_interval_secs")]
+    pub health_check_interval_secs: u64,
+
+    /// Start a session's container again when the health check finds it stopped
+    #[serde(default)]
+    pub auto_restart: bool,
+
+    /// Consecutive automatic restarts before a session is given up on and marked errored
+    #[serde(default = "default_auto_restart_max_attempts")]
+    pub auto_restart_max_attempts: u32,
+
+    /// Runs of the OAuth token refresh container, backing off between Docker or network
+    /// failures, before the refresh is reported as failed
+    #[serde(default = "default_oauth_refresh_attempts")]
+    pub oauth_refresh_attempts: u32,
+}
+
+impl Default for DockerConfig {
+    fn default() -> Self {
+        Self {
+            host: None,
+            timeout: default_docker_timeout(),
+            tls: None,
+            max_concurrent_builds: default_max_concurrent_builds(),
+            missing_image_policy: MissingImagePolicy::default(),
+            startup_grace_secs: default_startup_grace_secs(),
+            memory_limit_mb: None,
+            cpu_shares: None,
+            container_image: None,
+            container_dockerfile: None,
+            ports: Vec::new(),
+            health_check_interval_secs: default_health_check_interval_secs(),
+            auto_restart: false,
+            auto_restart_max_attempts: default_auto_restart_max_attempts(),
+            oauth_refresh_attempts: default_oauth_refresh_attempts(),
+        }
+    }
+}
+
+impl DockerConfig {
+    pub fn startup_grace(&self) -> chrono::Duration {
+        i64::try_from(self.startup_grace_secs)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .unwrap_or(chrono::Duration::MAX)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,8 +585,13 @@ fn default_container_template() -> String {
     "claude-dev".to_string()
 }
 
-fn default_branch_prefix() -> String {
-    "agents/".to_string()
+pub fn default_branch_prefix() -> String {
+    "agents-in-a-box/".to_string()
+}
+
+/// Claude CLI aliases for the latest model of each family
+fn default_models() -> Vec<String> {
+    ["sonnet", "opus", "haiku"].map(String::from).to_vec()
 }
 
 fn default_theme() -> String {
@@ -197,10 +622,98 @@ fn default_docker_timeout() -> u64 {
     60
 }
 
+fn default_max_sessions_per_workspace() -> usize {
+    5
+}
+
+fn default_min_terminal_width() -> u16 {
+    60
+}
+
+fn default_min_terminal_height() -> u16 {
+    20
+}
+
+fn default_log_timestamp_format() -> String {
+    "%H:%M:%S".to_string()
+}
+
+fn default_prompt_draft_interval_secs() -> u64 {
+    5
+}
+
+fn default_session_list_percent() -> u16 {
+    40
+}
+
+fn default_idle_tick_ms() -> u64 {
+    1000
+}
+
+fn default_idle_after_secs() -> u64 {
+    10
+}
+
+fn default_prompt_soft_char_limit() -> usize {
+    8000
+}
+
+fn default_prompt_hard_char_limit() -> usize {
+    16_000
+}
+
+fn default_large_paste_threshold() -> usize {
+    10_000
+}
+
+fn default_max_log_entries() -> usize {
+    1000
+}
+
+fn default_success_notification_secs() -> u64 {
+    3
+}
+
+fn default_info_notification_secs() -> u64 {
+    3
+}
+
+fn default_warning_notification_secs() -> u64 {
+    4
+}
+
+fn default_error_notification_secs() -> u64 {
+    5
+}
+
+fn default_max_concurrent_builds() -> usize {
+    1
+}
+
+fn default_startup_grace_secs() -> u64 {
+    30
+}
+
+fn default_health_check_interval_secs() -> u64 {
+    30
+}
+
+fn default_auto_restart_max_attempts() -> u32 {
+    3
+}
+
+fn default_oauth_refresh_attempts() -> u32 {
+    3
+}
+
 fn default_max_repositories() -> usize {
     500
 }
 
+fn default_max_scan_depth() -> usize {
+    3
+}
+
 impl AppConfig {
     /// Load configuration from default locations
     pub fn load() -> Result<Self> {
@@ -242,6 +755,12 @@ impl AppConfig {
         Ok(())
     }
 
+    /// Set one `[ui_preferences]` key in the user config file, leaving the rest of the file as is
+    pub fn save_ui_preference(key: &str, value: toml::Value) -> Result<()> {
+        let config_dir = Self::get_user_config_dir()?;
+        set_ui_preference_in(&config_dir.join("config.toml"), key, value)
+    }
+
     /// Get configuration file paths in order of precedence
     fn get_config_paths() -> Vec<PathBuf> {
         let mut paths = vec![];
@@ -280,6 +799,9 @@ impl AppConfig {
         self.container_templates.extend(other.container_templates);
         self.mcp_servers.extend(other.mcp_servers);
         self.environment.extend(other.environment);
+        if other.webhook_url.is_some() {
+            self.webhook_url = other.webhook_url;
+        }
 
         // Override workspace defaults if provided
         if other.workspace_defaults.branch_prefix != default_branch_prefix() {
@@ -293,15 +815,102 @@ impl AppConfig {
             self.workspace_defaults.workspace_scan_paths =
                 other.workspace_defaults.workspace_scan_paths;
         }
+        if !other.workspace_defaults.scan_roots.is_empty() {
+            self.workspace_defaults.scan_roots = other.workspace_defaults.scan_roots;
+        }
         // Always take max_repositories from config if loaded from file
         self.workspace_defaults.max_repositories = other.workspace_defaults.max_repositories;
+        self.workspace_defaults.max_scan_depth = other.workspace_defaults.max_scan_depth;
+        self.workspace_defaults.cleanup_orphaned_on_startup =
+            other.workspace_defaults.cleanup_orphaned_on_startup;
+        self.workspace_defaults.require_opt_in = other.workspace_defaults.require_opt_in;
+        self.workspace_defaults.name_from_prompt = other.workspace_defaults.name_from_prompt;
+        if other.workspace_defaults.claude_settings_template.is_some() {
+            self.workspace_defaults.claude_settings_template =
+                other.workspace_defaults.claude_settings_template;
+        }
+        if other.workspace_defaults.on_create_host_command.is_some() {
+            self.workspace_defaults.on_create_host_command =
+                other.workspace_defaults.on_create_host_command;
+        }
+        self.workspace_defaults.allow_project_host_commands =
+            other.workspace_defaults.allow_project_host_commands;
+        self.workspace_defaults.default_mode = other.workspace_defaults.default_mode;
+        self.workspace_defaults.default_skip_permissions =
+            other.workspace_defaults.default_skip_permissions;
+        if other.workspace_defaults.commit_log_base.is_some() {
+            self.workspace_defaults.commit_log_base = other.workspace_defaults.commit_log_base;
+        }
+        if other.workspace_defaults.model.is_some() {
+            self.workspace_defaults.model = other.workspace_defaults.model;
+        }
+        self.workspace_defaults.models = other.workspace_defaults.models;
+        if other.workspace_defaults.chat_token_budget.is_some() {
+            self.workspace_defaults.chat_token_budget = other.workspace_defaults.chat_token_budget;
+        }
 
         // Override UI preferences
         if other.ui_preferences.theme != default_theme() {
             self.ui_preferences.theme = other.ui_preferences.theme;
         }
+        self.theme.extend(other.theme);
         self.ui_preferences.show_container_status = other.ui_preferences.show_container_status;
         self.ui_preferences.show_git_status = other.ui_preferences.show_git_status;
+        self.ui_preferences.warn_on_session_count = other.ui_preferences.warn_on_session_count;
+        self.ui_preferences.max_sessions_per_workspace =
+            other.ui_preferences.max_sessions_per_workspace;
+        if other.ui_preferences.diff_tool_command.is_some() {
+            self.ui_preferences.diff_tool_command = other.ui_preferences.diff_tool_command;
+        }
+        self.ui_preferences.escape_policy = other.ui_preferences.escape_policy;
+        self.ui_preferences.stop_sessions_on_exit = other.ui_preferences.stop_sessions_on_exit;
+        self.ui_preferences.min_terminal_width = other.ui_preferences.min_terminal_width;
+        self.ui_preferences.min_terminal_height = other.ui_preferences.min_terminal_height;
+        self.ui_preferences.show_log_timestamps = other.ui_preferences.show_log_timestamps;
+        if other.ui_preferences.log_timestamp_format != default_log_timestamp_format() {
+            self.ui_preferences.log_timestamp_format = other.ui_preferences.log_timestamp_format;
+        }
+        self.ui_preferences.auto_save_prompt_drafts = other.ui_preferences.auto_save_prompt_drafts;
+        self.ui_preferences.prompt_draft_interval_secs =
+            other.ui_preferences.prompt_draft_interval_secs;
+        self.ui_preferences.bell_on_session_events = other.ui_preferences.bell_on_session_events;
+        self.ui_preferences.flash_on_session_events = other.ui_preferences.flash_on_session_events;
+        self.ui_preferences.silent = other.ui_preferences.silent;
+        self.ui_preferences.large_paste_threshold = other.ui_preferences.large_paste_threshold;
+        self.ui_preferences.prompt_soft_wrap = other.ui_preferences.prompt_soft_wrap;
+        self.ui_preferences.prompt_soft_char_limit = other.ui_preferences.prompt_soft_char_limit;
+        self.ui_preferences.prompt_hard_char_limit = other.ui_preferences.prompt_hard_char_limit;
+        self.ui_preferences.session_list_percent = other.ui_preferences.session_list_percent;
+        self.ui_preferences.idle_tick_ms = other.ui_preferences.idle_tick_ms;
+        self.ui_preferences.idle_after_secs = other.ui_preferences.idle_after_secs;
+        self.ui_preferences.session_sort = other.ui_preferences.session_sort;
+        self.ui_preferences.max_log_entries = other.ui_preferences.max_log_entries;
+        self.ui_preferences.log_ansi_colors = other.ui_preferences.log_ansi_colors;
+        self.ui_preferences.notifications = other.ui_preferences.notifications;
+
+        // Override docker settings
+        self.docker.max_concurrent_builds = other.docker.max_concurrent_builds;
+        self.docker.missing_image_policy = other.docker.missing_image_policy;
+        self.docker.startup_grace_secs = other.docker.startup_grace_secs;
+        self.docker.health_check_interval_secs = other.docker.health_check_interval_secs;
+        self.docker.auto_restart = other.docker.auto_restart;
+        self.docker.auto_restart_max_attempts = other.docker.auto_restart_max_attempts;
+        self.docker.oauth_refresh_attempts = other.docker.oauth_refresh_attempts;
+        if other.docker.memory_limit_mb.is_some() {
+            self.docker.memory_limit_mb = other.docker.memory_limit_mb;
+        }
+        if other.docker.cpu_shares.is_some() {
+            self.docker.cpu_shares = other.docker.cpu_shares;
+        }
+        if other.docker.container_image.is_some() {
+            self.docker.container_image = other.docker.container_image;
+        }
+        if other.docker.container_dockerfile.is_some() {
+            self.docker.container_dockerfile = other.docker.container_dockerfile;
+        }
+        if !other.docker.ports.is_empty() {
+            self.docker.ports = other.docker.ports;
+        }
     }
 
     /// Load built-in container templates
@@ -342,8 +951,10 @@ impl Default for AppConfig {
             environment: HashMap::new(),
             workspace_defaults: WorkspaceDefaults::default(),
             ui_preferences: UiPreferences::default(),
+            theme: toml::Table::new(),
             docker: DockerConfig::default(),
             tmux: TmuxConfig::default(),
+            webhook_url: None,
         };
 
         // Load built-in templates
@@ -386,6 +997,19 @@ pub struct ProjectConfig {
     /// Additional paths to mount from host
     #[serde(default)]
     pub additional_mounts: Vec<MountConfig>,
+
+    /// List this repository in the picker when `require_opt_in` is enabled
+    #[serde(default)]
+    pub opt_in: bool,
+
+    /// Claude settings file for this project's sessions, relative to the repository root
+    #[serde(default)]
+    pub claude_settings: Option<PathBuf>,
+
+    /// Host command run after this project's sessions are created, replacing the global
+    /// one. Ignored unless `allow_project_host_commands` is set in your own config.
+    #[serde(default)]
+    pub on_create_host_command: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -422,6 +1046,31 @@ impl ProjectConfig {
     }
 }
 
+fn set_ui_preference_in(config_path: &Path, key: &str, value: toml::Value) -> Result<()> {
+    let mut table: toml::Table = if config_path.exists() {
+        let content = fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read config from {}", config_path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config from {}", config_path.display()))?
+    } else {
+        toml::Table::new()
+    };
+
+    let ui_preferences = table
+        .entry("ui_preferences")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    let Some(ui_preferences) = ui_preferences.as_table_mut() else {
+        anyhow::bail!("ui_preferences in {} is not a table", config_path.display());
+    };
+    ui_preferences.insert(key.to_string(), value);
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(config_path, toml::to_string_pretty(&table)?)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,6 +1082,128 @@ mod tests {
         assert_eq!(config.version, env!("CARGO_PKG_VERSION"));
         assert_eq!(config.default_container_template, "claude-dev");
         assert!(!config.container_templates.is_empty());
+        assert!(!config.workspace_defaults.cleanup_orphaned_on_startup);
+    }
+
+    #[test]
+    fn test_cleanup_orphaned_on_startup_from_toml() {
+        let file_config: AppConfig = toml::from_str(
+            r#"
+            [workspace_defaults]
+            cleanup_orphaned_on_startup = true
+            "#,
+        )
+        .unwrap();
+
+        let mut config = AppConfig::default();
+        config.merge(file_config);
+
+        assert!(config.workspace_defaults.cleanup_orphaned_on_startup);
+    }
+
+    #[test]
+    fn test_max_log_entries_survives_merge() {
+        let file_config: AppConfig =
+            toml::from_str("[ui_preferences]\nmax_log_entries = 1234").unwrap();
+
+        let mut config = AppConfig::default();
+        config.merge(file_config);
+
+        assert_eq!(config.ui_preferences.max_log_entries, 1234);
+    }
+
+    #[test]
+    fn test_log_ansi_colors_survives_merge() {
+        let file_config: AppConfig =
+            toml::from_str("[ui_preferences]\nlog_ansi_colors = false").unwrap();
+
+        let mut config = AppConfig::default();
+        config.merge(file_config);
+
+        assert!(!config.ui_preferences.log_ansi_colors);
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let mut config = AppConfig::default();
+        config.workspace_defaults.default_mode = SessionMode::Boss;
+        config.workspace_defaults.default_skip_permissions = true;
+        config.workspace_defaults.branch_prefix = "me/".to_string();
+        config.workspace_defaults.model = Some("claude-opus-4-1".to_string());
+        config.workspace_defaults.workspace_scan_paths = vec![PathBuf::from("/src")];
+        config.docker.container_image = Some("ghcr.io/me/agent:latest".to_string());
+
+        let parsed: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed.workspace_defaults.default_mode, SessionMode::Boss);
+        assert!(parsed.workspace_defaults.default_skip_permissions);
+        assert_eq!(parsed.workspace_defaults.branch_prefix, "me/");
+        assert_eq!(parsed.workspace_defaults.model.as_deref(), Some("claude-opus-4-1"));
+        assert_eq!(
+            parsed.workspace_defaults.workspace_scan_paths,
+            vec![PathBuf::from("/src")]
+        );
+        assert_eq!(
+            parsed.docker.container_image.as_deref(),
+            Some("ghcr.io/me/agent:latest")
+        );
+
+        // An empty file keeps every default
+        let parsed: AppConfig = toml::from_str("").unwrap();
+        assert_eq!(parsed.workspace_defaults.default_mode, SessionMode::Interactive);
+        assert!(!parsed.workspace_defaults.default_skip_permissions);
+        assert_eq!(parsed.workspace_defaults.branch_prefix, "agents-in-a-box/");
+        assert_eq!(parsed.workspace_defaults.model, None);
+        assert_eq!(parsed.workspace_defaults.models, default_models());
+        assert_eq!(parsed.docker.container_image, None);
+    }
+
+    #[test]
+    fn test_session_count_warning_defaults() {
+        let config: AppConfig = toml::from_str("[ui_preferences]\ntheme = \"dark\"").unwrap();
+        assert!(config.ui_preferences.warn_on_session_count);
+        assert_eq!(config.ui_preferences.max_sessions_per_workspace, 5);
+    }
+
+    #[test]
+    fn test_escape_policy_from_toml() {
+        let config: AppConfig = toml::from_str("[ui_preferences]\ntheme = \"dark\"").unwrap();
+        assert_eq!(config.ui_preferences.escape_policy, EscapePolicy::Back);
+
+        let config: AppConfig =
+            toml::from_str("[ui_preferences]\nescape_policy = \"back_or_quit\"").unwrap();
+        assert_eq!(config.ui_preferences.escape_policy, EscapePolicy::BackOrQuit);
+    }
+
+    #[test]
+    fn test_notification_durations_from_toml() {
+        let config: AppConfig = toml::from_str("[ui_preferences]\ntheme = \"dark\"").unwrap();
+        assert_eq!(config.ui_preferences.notifications, NotificationConfig::default());
+
+        let config: AppConfig = toml::from_str(
+            "[ui_preferences.notifications]\nerror_secs = 30\nsticky_errors = false",
+        )
+        .unwrap();
+        let notifications = config.ui_preferences.notifications;
+        assert_eq!(notifications.error_secs, 30);
+        assert!(!notifications.sticky_errors);
+        assert_eq!(notifications.success_secs, 3);
+    }
+
+    #[test]
+    fn test_missing_image_policy_from_toml() {
+        let config: AppConfig = toml::from_str("[docker]\ntimeout = 30").unwrap();
+        assert_eq!(config.docker.missing_image_policy, MissingImagePolicy::Build);
+
+        let config: AppConfig =
+            toml::from_str("[docker]\nmissing_image_policy = \"prompt\"").unwrap();
+        assert_eq!(config.docker.missing_image_policy, MissingImagePolicy::Prompt);
+    }
+
+    #[test]
+    fn test_min_terminal_size_defaults() {
+        let config: AppConfig = toml::from_str("[ui_preferences]\ntheme = \"dark\"").unwrap();
+        assert_eq!(config.ui_preferences.min_terminal_width, 60);
+        assert_eq!(config.ui_preferences.min_terminal_height, 20);
     }
 
     #[test]
@@ -445,6 +1216,9 @@ mod tests {
             environment: HashMap::new(),
             mount_claude_config: true,
             additional_mounts: vec![],
+            opt_in: false,
+            claude_settings: None,
+            on_create_host_command: None,
         };
 
         project_config.save_to_dir(temp_dir.path()).unwrap();
@@ -453,4 +1227,21 @@ mod tests {
         assert_eq!(loaded.container_template, Some("node".to_string()));
         assert_eq!(loaded.mcp_servers, vec!["context7".to_string()]);
     }
+
+    #[test]
+    fn test_set_ui_preference_keeps_other_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        let existing = "webhook_url = \"https://example.com\"\n[ui_preferences]\ntheme = \"light\"\n";
+        fs::write(&config_path, existing).unwrap();
+
+        let value = toml::Value::from("last_activity");
+        set_ui_preference_in(&config_path, "session_sort", value).unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        let config: AppConfig = toml::from_str(&content).unwrap();
+        assert_eq!(config.ui_preferences.session_sort, SessionSortMode::LastActivity);
+        assert_eq!(config.ui_preferences.theme, "light");
+        assert_eq!(config.webhook_url.as_deref(), Some("https://example.com"));
+    }
 }
@@ -0,0 +1,224 @@
+// ABOUTME: TUI color theme: built-in dark/light presets plus [theme] overrides from config.toml
+// Components read colors from the theme on AppState instead of hardcoding them
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// Colors for session status, notifications, borders and selection.
+/// Serialized as color names (`light_red`), `#rrggbb` or a 256-color index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    #[serde(with = "color_name")]
+    pub running: Color,
+    #[serde(with = "color_name")]
+    pub idle: Color,
+    #[serde(with = "color_name")]
+    pub stopped: Color,
+    #[serde(with = "color_name")]
+    pub error: Color,
+
+    #[serde(with = "color_name")]
+    pub notification_success: Color,
+    #[serde(with = "color_name")]
+    pub notification_error: Color,
+    #[serde(with = "color_name")]
+    pub notification_warning: Color,
+    #[serde(with = "color_name")]
+    pub notification_info: Color,
+
+    /// Session list background, body text and secondary text
+    #[serde(with = "color_name")]
+    pub background: Color,
+    #[serde(with = "color_name")]
+    pub text: Color,
+    #[serde(with = "color_name")]
+    pub muted: Color,
+
+    /// Session list borders
+    #[serde(with = "color_name")]
+    pub border: Color,
+    #[serde(with = "color_name")]
+    pub border_focused: Color,
+    /// Selected workspace/session text and the highlighted row behind it
+    #[serde(with = "color_name")]
+    pub selection: Color,
+    #[serde(with = "color_name")]
+    pub selection_bg: Color,
+
+    /// Live logs pane borders and titles
+    #[serde(with = "color_name")]
+    pub logs_border: Color,
+    #[serde(with = "color_name")]
+    pub logs_border_focused: Color,
+    #[serde(with = "color_name")]
+    pub logs_title: Color,
+    #[serde(with = "color_name")]
+    pub logs_title_focused: Color,
+
+    /// Live log level icons
+    #[serde(with = "color_name")]
+    pub log_debug: Color,
+    #[serde(with = "color_name")]
+    pub log_info: Color,
+    #[serde(with = "color_name")]
+    pub log_warn: Color,
+    #[serde(with = "color_name")]
+    pub log_error: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub const PRESETS: [&'static str; 2] = ["dark", "light"];
+
+    /// The original colors, tuned for dark terminals
+    pub fn dark() -> Self {
+        Self {
+            running: Color::Rgb(100, 200, 100),
+            idle: Color::Rgb(255, 165, 0),
+            stopped: Color::Rgb(120, 120, 140),
+            error: Color::Rgb(230, 100, 100),
+            notification_success: Color::Rgb(100, 200, 100),
+            notification_error: Color::Rgb(230, 100, 100),
+            notification_warning: Color::Rgb(255, 165, 0),
+            notification_info: Color::Rgb(100, 149, 237),
+            background: Color::Rgb(25, 25, 35),
+            text: Color::Rgb(220, 220, 230),
+            muted: Color::Rgb(120, 120, 140),
+            border: Color::Rgb(60, 60, 80),
+            border_focused: Color::Rgb(100, 200, 100),
+            selection: Color::Rgb(100, 200, 100),
+            selection_bg: Color::Rgb(40, 40, 60),
+            logs_border: Color::Gray,
+            logs_border_focused: Color::Cyan,
+            logs_title: Color::Blue,
+            logs_title_focused: Color::Yellow,
+            log_debug: Color::DarkGray,
+            log_info: Color::Blue,
+            log_warn: Color::Yellow,
+            log_error: Color::Red,
+        }
+    }
+
+    /// Darker, more saturated colors that stay readable on light terminals
+    pub fn light() -> Self {
+        Self {
+            running: Color::Rgb(30, 130, 50),
+            idle: Color::Rgb(190, 100, 0),
+            stopped: Color::Rgb(110, 110, 120),
+            error: Color::Rgb(190, 30, 40),
+            notification_success: Color::Rgb(30, 130, 50),
+            notification_error: Color::Rgb(190, 30, 40),
+            notification_warning: Color::Rgb(190, 100, 0),
+            notification_info: Color::Rgb(30, 90, 200),
+            background: Color::Rgb(250, 250, 245),
+            text: Color::Rgb(30, 30, 40),
+            muted: Color::Rgb(110, 110, 120),
+            border: Color::Rgb(180, 180, 190),
+            border_focused: Color::Rgb(30, 130, 50),
+            selection: Color::Rgb(30, 130, 50),
+            selection_bg: Color::Rgb(220, 230, 245),
+            logs_border: Color::Rgb(180, 180, 190),
+            logs_border_focused: Color::Rgb(30, 90, 200),
+            logs_title: Color::Rgb(110, 110, 120),
+            logs_title_focused: Color::Rgb(30, 90, 200),
+            log_debug: Color::Rgb(130, 130, 140),
+            log_info: Color::Rgb(30, 90, 200),
+            log_warn: Color::Rgb(190, 100, 0),
+            log_error: Color::Rgb(190, 30, 40),
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            _ => None,
+        }
+    }
+
+    /// The named preset with the `[theme]` table's colors applied on top
+    pub fn resolve(preset: &str, overrides: &toml::Table) -> Result<Self, String> {
+        let base = Self::preset(preset).ok_or_else(|| {
+            format!("unknown theme '{}' (expected one of: {})", preset, Self::PRESETS.join(", "))
+        })?;
+        if overrides.is_empty() {
+            return Ok(base);
+        }
+
+        let mut table = toml::Table::try_from(base).map_err(|e| e.to_string())?;
+        table.extend(overrides.clone());
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| format!("invalid [theme]: {}", e.message()))
+    }
+}
+
+/// Colors as strings, so `[theme]` reads like `error = "light_red"`
+mod color_name {
+    use ratatui::style::Color;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(color)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Color::from_str(&name).map_err(|_| {
+            serde::de::Error::custom(format!(
+                "unknown color '{}' (use a name like \"light_red\", \"#rrggbb\" or 0-255)",
+                name
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_round_trip_through_toml() {
+        for name in Theme::PRESETS {
+            let theme = Theme::preset(name).unwrap();
+            let serialized = toml::to_string(&theme).unwrap();
+            assert_eq!(toml::from_str::<Theme>(&serialized).unwrap(), theme);
+        }
+        assert!(toml::to_string(&Theme::dark()).unwrap().contains("error = \"#E66464\""));
+    }
+
+    #[test]
+    fn test_overrides_apply_on_top_of_the_preset() {
+        let overrides: toml::Table =
+            toml::from_str("error = \"light_red\"\nselection_bg = \"#102030\"\nborder = \"240\"")
+                .unwrap();
+        let theme = Theme::resolve("light", &overrides).unwrap();
+        assert_eq!(theme.error, Color::LightRed);
+        assert_eq!(theme.selection_bg, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.border, Color::Indexed(240));
+        assert_eq!(theme.running, Theme::light().running);
+
+        assert_eq!(Theme::resolve("dark", &toml::Table::new()).unwrap(), Theme::default());
+    }
+
+    #[test]
+    fn test_bad_theme_entries_error_clearly() {
+        let overrides: toml::Table = toml::from_str("error = \"blurple\"").unwrap();
+        let err = Theme::resolve("dark", &overrides).unwrap_err();
+        assert!(err.contains("unknown color 'blurple'"), "{}", err);
+
+        let overrides: toml::Table = toml::from_str("eror = \"red\"").unwrap();
+        let err = Theme::resolve("dark", &overrides).unwrap_err();
+        assert!(err.contains("eror"), "{}", err);
+
+        let err = Theme::resolve("solarized", &toml::Table::new()).unwrap_err();
+        assert!(err.contains("unknown theme 'solarized'"), "{}", err);
+    }
+}
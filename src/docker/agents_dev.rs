@@ -14,6 +14,11 @@ use uuid::Uuid;
 
 use super::builder::ImageBuilder;
 use super::container_manager::ContainerManager;
+use crate::config::MissingImagePolicy;
+use crate::models::AgentType;
+
+/// Image used for agents-dev sessions and for the authentication containers
+pub const AGENTS_DEV_IMAGE: &str = "agents-box:agents-dev";
 
 /// Configuration for agents-dev container setup
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,12 +39,18 @@ pub struct AgentsDevConfig {
     pub skip_permissions: bool,
     /// Environment variables to pass to container
     pub env_vars: HashMap<String, String>,
+    /// Whether a missing image may be built implicitly
+    #[serde(default)]
+    pub missing_image_policy: MissingImagePolicy,
+    /// Agent the container runs, recorded as a label so its logs can be parsed after restart
+    #[serde(default)]
+    pub agent_type: AgentType,
 }
 
 impl Default for AgentsDevConfig {
     fn default() -> Self {
         Self {
-            image_name: "agents-box:agents-dev".to_string(),
+            image_name: AGENTS_DEV_IMAGE.to_string(),
             memory_limit: None,
             gpu_access: None,
             force_rebuild: false,
@@ -47,6 +58,8 @@ impl Default for AgentsDevConfig {
             continue_session: false,
             skip_permissions: false,
             env_vars: HashMap::new(),
+            missing_image_policy: MissingImagePolicy::default(),
+            agent_type: AgentType::default(),
         }
     }
 }
@@ -73,6 +86,17 @@ pub enum AgentsDevProgress {
     Error(String),
 }
 
+/// Why the agents-dev image could not be made available
+#[derive(Debug, thiserror::Error)]
+pub enum MissingImageError {
+    #[error("Image {0} is missing and needs to be built, which can take several minutes")]
+    NeedsConfirmation(String),
+    #[error("Image {0} is missing. Build it with `agents-box build-image`")]
+    BuildRequired(String),
+    #[error(transparent)]
+    Failed(#[from] anyhow::Error),
+}
+
 /// Main agents-dev container manager
 pub struct AgentsDevManager {
     config: AgentsDevConfig,
@@ -220,6 +244,34 @@ impl AgentsDevManager {
         Ok(())
     }
 
+    /// Make sure the image exists, building it only when the missing-image policy allows.
+    /// `build_confirmed` is the user's go-ahead under `MissingImagePolicy::Prompt`.
+    pub async fn ensure_image(
+        &self,
+        build_confirmed: bool,
+        progress_tx: Option<mpsc::Sender<AgentsDevProgress>>,
+    ) -> Result<(), MissingImageError> {
+        if !self.config.force_rebuild && !self.image_exists(&self.config.image_name).await? {
+            match self.config.missing_image_policy {
+                MissingImagePolicy::Build => {}
+                MissingImagePolicy::Prompt if build_confirmed => {}
+                MissingImagePolicy::Prompt => {
+                    return Err(MissingImageError::NeedsConfirmation(
+                        self.config.image_name.clone(),
+                    ));
+                }
+                MissingImagePolicy::Manual => {
+                    return Err(MissingImageError::BuildRequired(
+                        self.config.image_name.clone(),
+                    ));
+                }
+            }
+        }
+
+        self.build_image_if_needed(progress_tx).await?;
+        Ok(())
+    }
+
     /// Build agents-dev Docker image if needed
     pub async fn build_image_if_needed(
         &self,
@@ -368,6 +420,10 @@ impl AgentsDevManager {
         // Container run options
         let mut labels = std::collections::HashMap::new();
         labels.insert("agents-session-id".to_string(), session_id.to_string());
+        labels.insert(
+            "agents-agent-type".to_string(),
+            self.config.agent_type.as_str().to_string(),
+        );
 
         let run_options = super::container_manager::RunOptions {
             image: self.config.image_name.clone(),
@@ -478,6 +534,23 @@ Host gitlab.com
     }
 }
 
+/// Make sure the default agents-dev image exists, honoring `policy`. This is the single
+/// entry point for the authentication and token refresh paths, which need the image too.
+pub async fn ensure_agents_dev_image(
+    policy: MissingImagePolicy,
+    build_confirmed: bool,
+    progress_tx: Option<mpsc::Sender<AgentsDevProgress>>,
+) -> Result<(), MissingImageError> {
+    let config = AgentsDevConfig {
+        missing_image_policy: policy,
+        ..Default::default()
+    };
+    AgentsDevManager::new(config)
+        .await?
+        .ensure_image(build_confirmed, progress_tx)
+        .await
+}
+
 /// Helper function to create an agents-dev session
 pub async fn create_agents_dev_session(
     workspace_path: &Path,
@@ -494,8 +567,8 @@ pub async fn create_agents_dev_session(
     // Setup environment
     manager.setup_environment(progress_tx.clone()).await?;
 
-    // Build image if needed
-    manager.build_image_if_needed(progress_tx.clone()).await?;
+    // Build image if needed and allowed by the missing-image policy
+    manager.ensure_image(false, progress_tx.clone()).await?;
 
     // Run container
     let container_id = manager
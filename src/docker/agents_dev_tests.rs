@@ -54,6 +54,8 @@ mod tests {
                 env_vars.insert("TEST_MODE".to_string(), "true".to_string());
                 env_vars
             },
+            missing_image_policy: crate::config::MissingImagePolicy::Build,
+            agent_type: crate::models::AgentType::Claude,
         }
     }
 
@@ -391,6 +393,10 @@ mod tests {
         assert!(!default_config.force_rebuild);
         assert!(!default_config.no_cache);
         assert!(!default_config.continue_session);
+        assert_eq!(
+            default_config.missing_image_policy,
+            crate::config::MissingImagePolicy::Build
+        );
 
         // Test custom config
         let custom_config = AgentsDevConfig {
@@ -402,6 +408,8 @@ mod tests {
             continue_session: true,
             skip_permissions: true,
             env_vars: HashMap::new(),
+            missing_image_policy: crate::config::MissingImagePolicy::Manual,
+            agent_type: crate::models::AgentType::Gemini,
         };
 
         assert_eq!(custom_config.image_name, "custom-image");
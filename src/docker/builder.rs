@@ -9,15 +9,33 @@ use bollard::image::{BuildImageOptions, CreateImageOptions};
 use futures_util::stream::StreamExt;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 use tar::{Builder, Header};
 use tokio::io::AsyncReadExt;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore, TryAcquireError};
 use tracing::{debug, error, info};
 
 use crate::config::{ContainerTemplate, container::ImageSource};
 
+/// Process-wide limit on concurrent image builds, sized from `docker.max_concurrent_builds`
+static BUILD_PERMITS: OnceLock<Arc<Semaphore>> = OnceLock::new();
+
+fn build_permits() -> Arc<Semaphore> {
+    BUILD_PERMITS
+        .get_or_init(|| {
+            let max_builds = crate::config::AppConfig::load()
+                .map(|config| config.docker.max_concurrent_builds)
+                .unwrap_or(1)
+                .max(1);
+            info!("Limiting concurrent image builds to {}", max_builds);
+            Arc::new(Semaphore::new(max_builds))
+        })
+        .clone()
+}
+
 pub struct ImageBuilder {
     docker: Docker,
+    build_permits: Arc<Semaphore>,
 }
 
 #[derive(Debug)]
@@ -48,7 +66,40 @@ impl ImageBuilder {
         // Test connection
         docker.ping().await.context("Failed to ping Docker daemon")?;
 
-        Ok(Self { docker })
+        Ok(Self {
+            docker,
+            build_permits: build_permits(),
+        })
+    }
+
+    /// Wait for a build slot, reporting through `report` whether the build had to queue.
+    /// The permit is released when dropped, so a failed build lets queued builds proceed.
+    async fn acquire_build_permit(
+        &self,
+        tag: &str,
+        report: impl Fn(String),
+    ) -> Result<OwnedSemaphorePermit> {
+        let permit = match self.build_permits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(TryAcquireError::NoPermits) => {
+                info!("Image build for {} queued behind running builds", tag);
+                report(format!(
+                    "⏳ Queued: waiting for another image build to finish before building {}",
+                    tag
+                ));
+                self.build_permits
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .context("Image build queue closed")?
+            }
+            Err(TryAcquireError::Closed) => {
+                return Err(anyhow::anyhow!("Image build queue closed"));
+            }
+        };
+
+        report(format!("🔨 Building image {}", tag));
+        Ok(permit)
     }
 
     /// Build a Docker image with the given options
@@ -60,6 +111,14 @@ impl ImageBuilder {
     ) -> Result<()> {
         info!("Building Docker image: {}", tag);
 
+        let _permit = self
+            .acquire_build_permit(tag, |message| {
+                if let Some(ref sender) = log_sender {
+                    let _ = sender.try_send(message);
+                }
+            })
+            .await?;
+
         // Create build context tar
         let build_context = self.create_build_context(options).await?;
 
@@ -217,19 +276,20 @@ impl ImageBuilder {
                 Ok(())
             }
             ImageSource::Dockerfile { path, build_args } => {
-                info!("Building image from Dockerfile: {}", path.display());
-                let context = BuildContext {
-                    dockerfile_path: path.clone(),
-                    context_dir: path.parent().unwrap_or(Path::new(".")).to_path_buf(),
-                    build_args: build_args.clone(),
-                    tag: tag.to_string(),
-                };
-                self.build_from_dockerfile_with_logs(&context, log_sender.as_ref()).await
+                self.build_dockerfile_with_logs(path, build_args, tag, log_sender).await
             }
             ImageSource::ClaudeDocker {
                 base_image,
                 build_args,
             } => {
+                let _permit = self
+                    .acquire_build_permit(tag, |message| {
+                        if let Some(ref sender) = log_sender {
+                            let _ = sender.send(message);
+                        }
+                    })
+                    .await?;
+
                 info!("Building claude-docker based image");
                 self.build_claude_dev_image_with_logs(
                     tag,
@@ -242,6 +302,32 @@ impl ImageBuilder {
         }
     }
 
+    /// Build `tag` from a Dockerfile, using the Dockerfile's directory as the build context
+    pub async fn build_dockerfile_with_logs(
+        &self,
+        dockerfile: &Path,
+        build_args: &HashMap<String, String>,
+        tag: &str,
+        log_sender: Option<mpsc::UnboundedSender<String>>,
+    ) -> Result<()> {
+        let _permit = self
+            .acquire_build_permit(tag, |message| {
+                if let Some(ref sender) = log_sender {
+                    let _ = sender.send(message);
+                }
+            })
+            .await?;
+
+        info!("Building image from Dockerfile: {}", dockerfile.display());
+        let context = BuildContext {
+            dockerfile_path: dockerfile.to_path_buf(),
+            context_dir: dockerfile.parent().unwrap_or(Path::new(".")).to_path_buf(),
+            build_args: build_args.clone(),
+            tag: tag.to_string(),
+        };
+        self.build_from_dockerfile_with_logs(&context, log_sender.as_ref()).await
+    }
+
     /// Pull a pre-built image
     async fn pull_image(&self, image: &str) -> Result<()> {
         // Check if image exists locally first
@@ -586,6 +672,7 @@ mod tests {
 
         let builder = ImageBuilder {
             docker: Docker::connect_with_local_defaults().unwrap(),
+            build_permits: Arc::new(Semaphore::new(1)),
         };
         let build_options = BuildOptions {
             dockerfile_path: Some(dockerfile.clone()),
@@ -600,4 +687,32 @@ mod tests {
         assert!(tar_data.is_ok());
         assert!(!tar_data.unwrap().is_empty());
     }
+
+    #[tokio::test]
+    async fn test_builds_queue_and_failed_build_releases_permit() {
+        let builder = ImageBuilder {
+            docker: Docker::connect_with_local_defaults().unwrap(),
+            build_permits: Arc::new(Semaphore::new(1)),
+        };
+        let messages = std::sync::Mutex::new(Vec::new());
+        let report = |message: String| messages.lock().unwrap().push(message);
+
+        let first = builder.acquire_build_permit("first", report).await.unwrap();
+        assert!(messages.lock().unwrap()[0].contains("Building image first"));
+
+        // Second build has to wait while the first holds the only permit
+        let queued = builder.acquire_build_permit("second", report);
+        tokio::pin!(queued);
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(50), &mut queued)
+                .await
+                .is_err()
+        );
+        assert!(messages.lock().unwrap()[1].starts_with("⏳ Queued"));
+
+        // Simulate the first build failing: dropping its permit lets the queued build run
+        drop(first);
+        let _second = queued.await.unwrap();
+        assert!(messages.lock().unwrap()[2].contains("Building image second"));
+    }
 }
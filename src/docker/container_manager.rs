@@ -7,12 +7,13 @@ use anyhow::Result;
 use bollard::Docker;
 use bollard::container::{
     Config, CreateContainerOptions, ListContainersOptions, LogOutput, LogsOptions,
-    RemoveContainerOptions, StartContainerOptions, StopContainerOptions,
+    RemoveContainerOptions, StartContainerOptions, Stats, StatsOptions, StopContainerOptions,
 };
 use bollard::exec::{CreateExecOptions, StartExecResults};
 use bollard::image::{CreateImageOptions, ListImagesOptions};
 use bollard::models::{
-    ContainerSummary, HostConfig, HostConfigLogConfig, Mount, MountTypeEnum, PortBinding,
+    ContainerState, ContainerSummary, HostConfig, HostConfigLogConfig, Mount, MountTypeEnum,
+    PortBinding,
 };
 use futures_util::stream::StreamExt;
 use std::collections::HashMap;
@@ -26,6 +27,8 @@ use uuid::Uuid;
 pub enum ContainerError {
     #[error("Docker connection error: {0}")]
     Connection(#[from] bollard::errors::Error),
+    #[error("Permission denied connecting to the Docker daemon: {0}\n\n{DOCKER_PERMISSION_HINT}")]
+    PermissionDenied(String),
     #[error("Container not found: {0}")]
     NotFound(String),
     #[error("Container already exists: {0}")]
@@ -34,6 +37,171 @@ pub enum ContainerError {
     InvalidConfig(String),
     #[error("Container operation failed: {0}")]
     OperationFailed(String),
+    #[error("Image not available: {0}")]
+    ImageUnavailable(String),
+}
+
+/// CPU and memory a container is using right now
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ContainerUsage {
+    pub cpu_percent: f64, // Share of one CPU, so busy multi-core containers exceed 100
+    pub memory_bytes: u64,
+}
+
+impl ContainerUsage {
+    /// Usage from a non-streaming stats sample, computed the way `docker stats` does
+    pub fn from_stats(stats: &Stats) -> Self {
+        let cpu_delta = stats
+            .cpu_stats
+            .cpu_usage
+            .total_usage
+            .saturating_sub(stats.precpu_stats.cpu_usage.total_usage);
+        let system_delta = stats
+            .cpu_stats
+            .system_cpu_usage
+            .unwrap_or(0)
+            .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or(0));
+        let online_cpus = stats.cpu_stats.online_cpus.unwrap_or(1).max(1);
+        let cpu_percent = if system_delta > 0 {
+            cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            cpu_percent,
+            memory_bytes: stats.memory_stats.usage.unwrap_or(0),
+        }
+    }
+
+    pub fn add(&mut self, other: Self) {
+        self.cpu_percent += other.cpu_percent;
+        self.memory_bytes += other.memory_bytes;
+    }
+}
+
+/// How to fix a Docker socket the current user is not allowed to use
+pub const DOCKER_PERMISSION_HINT: &str = concat!(
+    "Your user cannot access the Docker daemon socket. Either:\n",
+    "  • add yourself to the docker group: sudo usermod -aG docker $USER\n",
+    "    then log out and back in (or run `newgrp docker`)\n",
+    "  • use rootless Docker: https://docs.docker.com/engine/security/rootless/\n",
+    "  • run agents-box with sudo"
+);
+
+/// Whether a Docker client or CLI error means the daemon socket refused us
+pub fn is_docker_permission_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("permission denied")
+        && (message.contains("docker")
+            || message.contains("socket")
+            || message.contains("os error 13"))
+}
+
+impl ContainerError {
+    /// Wrap a Docker client error, calling out socket permission problems
+    fn from_docker(e: bollard::errors::Error) -> Self {
+        let message = e.to_string();
+        if is_docker_permission_error(&message) || Self::is_io_permission_denied(&e) {
+            Self::PermissionDenied(message)
+        } else {
+            Self::Connection(e)
+        }
+    }
+
+    fn is_io_permission_denied(e: &bollard::errors::Error) -> bool {
+        matches!(
+            e,
+            bollard::errors::Error::IOError { err } if err.kind() == std::io::ErrorKind::PermissionDenied
+        )
+    }
+}
+
+/// Shown as the session error when Docker stopped a container for exceeding its memory limit
+pub const OOM_KILLED_MESSAGE: &str = "container killed (out of memory)";
+
+/// Inspect requests `get_statuses` keeps in flight at once
+const STATUS_QUERY_CONCURRENCY: usize = 8;
+
+/// Host settings for a session container: published ports, mounts and resource limits
+fn session_host_config(config: &ContainerConfig) -> HostConfig {
+    // Create port bindings
+    let mut port_bindings = HashMap::new();
+    for port_mapping in &config.ports {
+        let host_port =
+            port_mapping.host_port.map(|p| p.to_string()).unwrap_or_else(|| "".to_string()); // Empty string for auto-assignment
+
+        let container_port_key =
+            format!("{}/{}", port_mapping.container_port, port_mapping.protocol);
+        port_bindings.insert(
+            container_port_key,
+            Some(vec![PortBinding {
+                host_ip: Some("127.0.0.1".to_string()),
+                host_port: Some(host_port),
+            }]),
+        );
+    }
+
+    // Create volume mounts
+    let mut mounts = Vec::new();
+    for volume in &config.volumes {
+        mounts.push(Mount {
+            target: Some(volume.container_path.clone()),
+            source: Some(volume.host_path.to_string_lossy().to_string()),
+            typ: Some(MountTypeEnum::BIND),
+            read_only: Some(volume.read_only),
+            consistency: Some("delegated".to_string()), // Better performance on macOS
+            ..Default::default()
+        });
+    }
+
+    HostConfig {
+        port_bindings: Some(port_bindings),
+        mounts: Some(mounts),
+        memory: config.memory_limit.map(|m| m as i64),
+        cpu_shares: config.cpu_shares.map(|s| s as i64),
+        nano_cpus: config.cpu_limit.map(|c| (c * 1_000_000_000.0) as i64),
+        auto_remove: Some(false), // We want to manage lifecycle manually
+        log_config: Some(HostConfigLogConfig {
+            typ: Some("json-file".to_string()),
+            config: Some({
+                let mut log_config = HashMap::new();
+                log_config.insert("max-size".to_string(), "10m".to_string());
+                log_config.insert("max-file".to_string(), "3".to_string());
+                log_config
+            }),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Check the Docker daemon through the CLI, returning its version or why it is unusable
+pub fn check_docker_daemon() -> Result<String, String> {
+    match std::process::Command::new("docker")
+        .args(["version", "--format", "{{.Server.Version}}"])
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        }
+        Ok(output) => {
+            let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            if is_docker_permission_error(&error) {
+                Err(format!(
+                    "Permission denied connecting to the Docker daemon.\n\n{}",
+                    DOCKER_PERMISSION_HINT
+                ))
+            } else if error.is_empty() {
+                Err("Docker is not running. Please start Docker and try again.".to_string())
+            } else {
+                Err(error)
+            }
+        }
+        Err(e) => Err(format!(
+            "Docker not found: {}. Please install Docker and try again.",
+            e
+        )),
+    }
 }
 
 /// Options for running a container
@@ -63,7 +231,7 @@ pub struct ContainerManager {
 
 impl ContainerManager {
     pub async fn new() -> Result<Self, ContainerError> {
-        let docker = Self::connect_to_docker().map_err(ContainerError::Connection)?;
+        let docker = Self::connect_to_docker().map_err(ContainerError::from_docker)?;
 
         // Test the connection with timeout
         let ping_timeout = std::time::Duration::from_secs(10);
@@ -75,14 +243,14 @@ impl ContainerManager {
                     message: "Docker ping timeout - daemon may be unresponsive".to_string(),
                 })
             })?
-            .map_err(ContainerError::Connection)?;
+            .map_err(ContainerError::from_docker)?;
 
         info!("Successfully connected to Docker daemon");
         Ok(Self { docker })
     }
 
     pub fn new_sync() -> Result<Self, ContainerError> {
-        let docker = Self::connect_to_docker().map_err(ContainerError::Connection)?;
+        let docker = Self::connect_to_docker().map_err(ContainerError::from_docker)?;
 
         info!("Successfully connected to Docker daemon (sync)");
         Ok(Self { docker })
@@ -267,57 +435,24 @@ impl ContainerManager {
             return Err(ContainerError::AlreadyExists(container_name));
         }
 
-        // Ensure image exists
-        self.ensure_image_available(&config.image, log_sender).await?;
-
-        // Create port bindings
-        let mut port_bindings = HashMap::new();
-        for port_mapping in &config.ports {
-            let host_port =
-                port_mapping.host_port.map(|p| p.to_string()).unwrap_or_else(|| "".to_string()); // Empty string for auto-assignment
-
-            let container_port_key =
-                format!("{}/{}", port_mapping.container_port, port_mapping.protocol);
-            port_bindings.insert(
-                container_port_key,
-                Some(vec![PortBinding {
-                    host_ip: Some("127.0.0.1".to_string()),
-                    host_port: Some(host_port),
-                }]),
-            );
+        // Fail before pulling or building when a published host port is taken
+        let port_mappings = config.host_port_mappings();
+        let conflicts = super::session_container::find_port_conflicts(
+            &port_mappings,
+            &super::session_container::host_ports_in_use(port_mappings.iter().map(|(h, _)| *h)),
+        );
+        if !conflicts.is_empty() {
+            let ports: Vec<String> = conflicts.iter().map(u16::to_string).collect();
+            return Err(ContainerError::InvalidConfig(format!(
+                "Host port {} already in use; choose another host port",
+                ports.join(", ")
+            )));
         }
 
-        // Create volume mounts
-        let mut mounts = Vec::new();
-        for volume in &config.volumes {
-            mounts.push(Mount {
-                target: Some(volume.container_path.clone()),
-                source: Some(volume.host_path.to_string_lossy().to_string()),
-                typ: Some(MountTypeEnum::BIND),
-                read_only: Some(volume.read_only),
-                consistency: Some("delegated".to_string()), // Better performance on macOS
-                ..Default::default()
-            });
-        }
+        // Ensure image exists
+        self.ensure_image_available(&config.image, log_sender).await?;
 
-        // Create host config
-        let host_config = HostConfig {
-            port_bindings: Some(port_bindings),
-            mounts: Some(mounts),
-            memory: config.memory_limit.map(|m| m as i64),
-            nano_cpus: config.cpu_limit.map(|c| (c * 1_000_000_000.0) as i64),
-            auto_remove: Some(false), // We want to manage lifecycle manually
-            log_config: Some(HostConfigLogConfig {
-                typ: Some("json-file".to_string()),
-                config: Some({
-                    let mut log_config = HashMap::new();
-                    log_config.insert("max-size".to_string(), "10m".to_string());
-                    log_config.insert("max-file".to_string(), "3".to_string());
-                    log_config
-                }),
-            }),
-            ..Default::default()
-        };
+        let host_config = session_host_config(&config);
 
         // Prepare environment variables
         let env: Vec<String> =
@@ -331,9 +466,16 @@ impl ContainerManager {
             cmd: config.command.clone(),
             entrypoint: config.entrypoint.clone(),
             user: config.user.clone(),
+            exposed_ports: Some(
+                config
+                    .ports
+                    .iter()
+                    .map(|p| (format!("{}/{}", p.container_port, p.protocol), HashMap::new()))
+                    .collect(),
+            ),
             host_config: Some(host_config),
             labels: Some({
-                let mut labels = HashMap::new();
+                let mut labels = config.labels.clone();
                 labels.insert("agents-session-id".to_string(), session_id.to_string());
                 labels.insert("agents-managed".to_string(), "true".to_string());
                 labels
@@ -466,6 +608,36 @@ impl ContainerManager {
         }
     }
 
+    /// Start a stopped container by its ID
+    pub async fn start_container_by_id(&self, container_id: &str) -> Result<(), ContainerError> {
+        info!("Starting container by ID: {}", container_id);
+        self.docker
+            .start_container(container_id, None::<StartContainerOptions<String>>)
+            .await?;
+        Ok(())
+    }
+
+    /// Stop a container by its ID without removing it, so it can be started again
+    pub async fn stop_container_by_id(&self, container_id: &str) -> Result<(), ContainerError> {
+        info!("Stopping container by ID: {}", container_id);
+
+        let stop_options = StopContainerOptions { t: 10 }; // 10 second grace period
+
+        match self.docker.stop_container(container_id, Some(stop_options)).await {
+            Ok(_) => {
+                info!("Successfully stopped container {}", container_id);
+                Ok(())
+            }
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 304, ..
+            }) => {
+                debug!("Container {} was already stopped", container_id);
+                Ok(())
+            }
+            Err(e) => Err(ContainerError::Connection(e)),
+        }
+    }
+
     /// Remove a container by its ID directly
     ///
     /// This is useful for cleanup operations where we only have the container ID
@@ -526,6 +698,9 @@ impl ContainerManager {
             match status {
                 "running" => Ok(ContainerStatus::Running),
                 "paused" => Ok(ContainerStatus::Paused),
+                "exited" | "dead" if self.was_oom_killed(container_id).await? => {
+                    Ok(ContainerStatus::Error(OOM_KILLED_MESSAGE.to_string()))
+                }
                 "exited" | "dead" => Ok(ContainerStatus::Stopped),
                 "created" => Ok(ContainerStatus::Creating),
                 _ => Ok(ContainerStatus::Error(format!(
@@ -538,6 +713,43 @@ impl ContainerManager {
         }
     }
 
+    /// Status of each container, inspected concurrently with at most
+    /// `STATUS_QUERY_CONCURRENCY` requests in flight. A failed inspect becomes that
+    /// container's `Error` status rather than failing the batch.
+    pub async fn get_statuses(&self, container_ids: &[String]) -> HashMap<String, ContainerStatus> {
+        query_statuses(container_ids, STATUS_QUERY_CONCURRENCY, |id| async move {
+            match self.docker.inspect_container(&id, None).await {
+                Ok(container) => status_from_state(container.state.as_ref()),
+                Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => ContainerStatus::NotFound,
+                Err(e) => ContainerStatus::Error(e.to_string()),
+            }
+        })
+        .await
+    }
+
+    /// Whether Docker killed the container for going over its memory limit
+    pub async fn was_oom_killed(&self, container_id: &str) -> Result<bool, ContainerError> {
+        let container = self.docker.inspect_container(container_id, None).await?;
+        Ok(container.state.and_then(|state| state.oom_killed).unwrap_or(false))
+    }
+
+    /// One CPU/memory sample. Docker measures CPU over about a second, so this takes that long.
+    pub async fn container_usage(
+        &self,
+        container_id: &str,
+    ) -> Result<ContainerUsage, ContainerError> {
+        let options = StatsOptions {
+            stream: false,
+            one_shot: false,
+        };
+        match self.docker.stats(container_id, Some(options)).next().await {
+            Some(stats) => Ok(ContainerUsage::from_stats(&stats?)),
+            None => Err(ContainerError::NotFound(container_id.to_string())),
+        }
+    }
+
     pub async fn get_container_logs(
         &self,
         container_id: &str,
@@ -661,6 +873,32 @@ impl ContainerManager {
             return Ok(());
         }
 
+        // A custom image with a configured Dockerfile is built rather than pulled
+        let dockerfile = crate::config::AppConfig::load().ok().and_then(|config| {
+            (config.docker.container_image.as_deref() == Some(image))
+                .then_some(config.docker.container_dockerfile)
+                .flatten()
+        });
+        if let Some(dockerfile) = dockerfile {
+            info!("Building image {} from {}", image, dockerfile.display());
+            let builder = super::ImageBuilder::new().await.map_err(|e| {
+                ContainerError::OperationFailed(format!("Failed to create image builder: {}", e))
+            })?;
+            builder
+                .build_dockerfile_with_logs(&dockerfile, &HashMap::new(), image, log_sender)
+                .await
+                .map_err(|e| {
+                    ContainerError::ImageUnavailable(format!(
+                        "{} is not present locally and failed to build from {}: {}",
+                        image,
+                        dockerfile.display(),
+                        e
+                    ))
+                })?;
+            info!("Successfully built image {}", image);
+            return Ok(());
+        }
+
         info!("Pulling image {}", image);
 
         let create_image_options = CreateImageOptions {
@@ -673,12 +911,24 @@ impl ContainerManager {
 
         while let Some(result) = stream.next().await {
             match result {
-                Ok(_) => {} // Progress update
+                // Forwarded in `docker pull`'s format so the progress bar can parse it
+                Ok(info) => {
+                    if let (Some(sender), Some(status)) = (&log_sender, info.status) {
+                        let line = match (info.id, info.progress) {
+                            (Some(id), Some(progress)) => {
+                                format!("{}: {} {}", id, status, progress)
+                            }
+                            (Some(id), None) => format!("{}: {}", id, status),
+                            _ => status,
+                        };
+                        let _ = sender.send(line);
+                    }
+                }
                 Err(e) => {
                     error!("Failed to pull image {}: {}", image, e);
-                    return Err(ContainerError::OperationFailed(format!(
-                        "Failed to pull image: {}",
-                        e
+                    return Err(ContainerError::ImageUnavailable(format!(
+                        "{} is not present locally and could not be pulled: {}",
+                        image, e
                     )));
                 }
             }
@@ -1015,6 +1265,40 @@ impl ContainerManager {
     }
 }
 
+/// Run `query` for every id with at most `limit` running at once
+async fn query_statuses<F, Fut>(
+    ids: &[String],
+    limit: usize,
+    query: F,
+) -> HashMap<String, ContainerStatus>
+where
+    F: Fn(String) -> Fut + Sync,
+    Fut: std::future::Future<Output = ContainerStatus> + Send,
+{
+    futures_util::stream::iter(ids.iter().cloned())
+        .map(|id| {
+            let status = query(id.clone());
+            async move { (id, status.await) }
+        })
+        .buffer_unordered(limit.max(1))
+        .collect()
+        .await
+}
+
+/// Same mapping as `get_container_status`, from an inspect response
+fn status_from_state(state: Option<&ContainerState>) -> ContainerStatus {
+    let status = state.and_then(|s| s.status).map(|s| s.to_string());
+    let oom_killed = state.and_then(|s| s.oom_killed).unwrap_or(false);
+    match status.as_deref().unwrap_or("unknown") {
+        "running" => ContainerStatus::Running,
+        "paused" => ContainerStatus::Paused,
+        "exited" | "dead" if oom_killed => ContainerStatus::Error(OOM_KILLED_MESSAGE.to_string()),
+        "exited" | "dead" => ContainerStatus::Stopped,
+        "created" => ContainerStatus::Creating,
+        status => ContainerStatus::Error(format!("Unknown status: {}", status)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1023,6 +1307,107 @@ mod tests {
     // Note: These tests require Docker to be running
     // They are integration tests and should be run with `cargo test --ignored`
 
+    #[tokio::test]
+    async fn test_query_statuses_bounds_concurrency_and_isolates_failures() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let ids: Vec<String> =
+            ["c1", "c2", "broken", "c4", "gone", "c6"].map(String::from).to_vec();
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let statuses = query_statuses(&ids, 3, |id| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let running = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                match id.as_str() {
+                    "broken" => ContainerStatus::Error("inspect failed".to_string()),
+                    "gone" => ContainerStatus::NotFound,
+                    _ => ContainerStatus::Running,
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(statuses.len(), 6);
+        assert_eq!(statuses["c1"], ContainerStatus::Running);
+        assert_eq!(statuses["c6"], ContainerStatus::Running);
+        assert_eq!(statuses["broken"], ContainerStatus::Error("inspect failed".to_string()));
+        assert_eq!(statuses["gone"], ContainerStatus::NotFound);
+    }
+
+    #[test]
+    fn test_docker_permission_error_detection() {
+        assert!(is_docker_permission_error(
+            "permission denied while trying to connect to the Docker daemon socket at unix:///var/run/docker.sock"
+        ));
+        assert!(is_docker_permission_error(
+            "Error in the hyper legacy client: client error (Connect): Permission denied (os error 13)"
+        ));
+        assert!(!is_docker_permission_error(
+            "Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?"
+        ));
+
+        let error = ContainerError::from_docker(bollard::errors::Error::IOError {
+            err: std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        });
+        assert!(matches!(error, ContainerError::PermissionDenied(_)));
+        assert!(error.to_string().contains("usermod -aG docker"));
+    }
+
+    #[test]
+    fn test_session_host_config_resource_limits() {
+        let config = ContainerConfig::new("alpine:latest".to_string())
+            .with_memory_limit_mb(512)
+            .with_cpu_shares(256);
+        let host_config = session_host_config(&config);
+        assert_eq!(host_config.memory, Some(512 * 1024 * 1024));
+        assert_eq!(host_config.cpu_shares, Some(256));
+
+        let host_config = session_host_config(&ContainerConfig {
+            memory_limit: None,
+            cpu_shares: None,
+            ..ContainerConfig::default()
+        });
+        assert_eq!(host_config.memory, None);
+        assert_eq!(host_config.cpu_shares, None);
+    }
+
+    #[test]
+    fn test_session_host_config_binds_volumes() {
+        let config = ContainerConfig::new("alpine:latest".to_string())
+            .with_volume(PathBuf::from("/home/me/.cache/pip"), "/cache".to_string(), false)
+            .with_volume(PathBuf::from("/home/me/creds"), "/creds".to_string(), true);
+        let mounts = session_host_config(&config).mounts.unwrap();
+
+        let binds: Vec<(Option<&str>, Option<&str>, Option<bool>)> = mounts
+            .iter()
+            .map(|m| (m.source.as_deref(), m.target.as_deref(), m.read_only))
+            .collect();
+        assert_eq!(
+            binds,
+            vec![
+                (Some("/home/me/.cache/pip"), Some("/cache"), Some(false)),
+                (Some("/home/me/creds"), Some("/creds"), Some(true)),
+            ]
+        );
+        assert!(mounts.iter().all(|m| m.typ == Some(MountTypeEnum::BIND)));
+    }
+
+    #[test]
+    fn test_session_host_config_port_bindings() {
+        let config = ContainerConfig::new("node:20".to_string()).with_port(3000, Some(8080));
+        let bindings = session_host_config(&config).port_bindings.unwrap();
+
+        assert_eq!(bindings.len(), 1);
+        let binding = &bindings["3000/tcp"].as_ref().unwrap()[0];
+        assert_eq!(binding.host_ip.as_deref(), Some("127.0.0.1"));
+        assert_eq!(binding.host_port.as_deref(), Some("8080"));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_container_manager_creation() {
@@ -6,7 +6,7 @@
 use crate::agent_parsers::AgentOutputParser;
 use crate::components::live_logs_stream::{LogEntry, LogEntryLevel};
 use crate::components::log_parser::LogParser;
-use crate::docker::ContainerManager;
+use crate::docker::{ContainerManager, WebhookEvent, WebhookEventKind, WebhookNotifier};
 use anyhow::{Result, anyhow};
 use bollard::container::{LogOutput, LogsOptions};
 use futures_util::StreamExt;
@@ -26,6 +26,7 @@ pub struct DockerLogStreamingManager {
     streaming_tasks: HashMap<Uuid, StreamingTask>,
     log_sender: mpsc::UnboundedSender<(Uuid, LogEntry)>,
     session_modes: HashMap<Uuid, crate::models::SessionMode>, // Track session modes for proper parsing
+    webhook: Option<WebhookNotifier>, // Receives parsed agent events when a webhook is configured
 }
 
 #[derive(Debug)]
@@ -43,23 +44,34 @@ impl DockerLogStreamingManager {
             streaming_tasks: HashMap::new(),
             log_sender,
             session_modes: HashMap::new(),
+            webhook: None,
         })
     }
 
+    /// Forward parsed agent events of streams started from now on to a webhook
+    pub fn set_webhook(&mut self, webhook: Option<WebhookNotifier>) {
+        self.webhook = webhook;
+    }
+
     /// Start streaming logs for a session's container
     pub async fn start_streaming(
         &mut self,
         session_id: Uuid,
         container_id: String,
         container_name: String,
+        workspace: String,
         session_mode: crate::models::SessionMode,
+        agent_type: crate::models::AgentType,
     ) -> Result<()> {
         // Stop any existing streaming for this session
         self.stop_streaming(session_id).await?;
 
         info!(
-            "Starting log streaming for session {} (container: {}) in {:?} mode",
-            session_id, container_id, session_mode
+            "Starting log streaming for session {} (container: {}) in {:?} mode with {} output",
+            session_id,
+            container_id,
+            session_mode,
+            agent_type.as_str()
         );
 
         // Store session mode for parsing
@@ -69,6 +81,7 @@ impl DockerLogStreamingManager {
         let container_id_clone = container_id.clone();
         let container_name_clone = container_name.clone();
         let docker = self.container_manager.get_docker_client();
+        let webhook = self.webhook.clone().map(|notifier| (notifier, workspace));
 
         // Spawn a task to stream logs
         let task_handle = tokio::spawn(async move {
@@ -78,7 +91,8 @@ impl DockerLogStreamingManager {
                 container_id_clone.clone(),
                 container_name_clone.clone(),
                 log_sender,
-                session_mode,
+                agent_type,
+                webhook,
             )
             .await
             {
@@ -143,13 +157,14 @@ impl DockerLogStreamingManager {
         container_id: String,
         container_name: String,
         log_sender: mpsc::UnboundedSender<(Uuid, LogEntry)>,
-        session_mode: crate::models::SessionMode,
+        agent_type: crate::models::AgentType,
+        mut webhook: Option<(WebhookNotifier, String)>, // (notifier, workspace name)
     ) -> Result<()> {
         let options = LogsOptions::<String> {
             stdout: true,
             stderr: true,
             follow: true,
-            timestamps: false,  // Disable timestamps for cleaner JSON output
+            timestamps: true, // Split off each line; replayed usage reports are told apart by it
             tail: "100".to_string(), // Start with last 100 lines
             ..Default::default()
         };
@@ -162,9 +177,10 @@ impl DockerLogStreamingManager {
         let mut log_stream = docker.logs(&container_id, Some(options));
         let mut log_parser = LogParser::new();
 
-        // JSON streaming parser (used for Boss Mode, but safe to try for any session)
+        // JSON streaming parser for the session's agent, created on the first JSON object
         let mut agent_parser: Option<Box<dyn AgentOutputParser>> = None;
-        let _is_boss_mode = matches!(session_mode, crate::models::SessionMode::Boss);
+        // Plain text agents print braces in ordinary output, so never look for JSON there
+        let parse_json = agent_type != crate::models::AgentType::PlainText;
         // Buffer for partial JSON objects across frames
         let mut boss_json_buffer = String::new();
         let _parser_debug = std::env::var("AGENTS_BOX_PARSER_DEBUG").is_ok();
@@ -177,6 +193,9 @@ impl DockerLogStreamingManager {
         use crate::widgets::MessageRouter;
         let mut message_router = MessageRouter::new();
 
+        // A stream error is reported as such; only a clean end means the session completed
+        let mut stream_failed = false;
+
         // Send initial connection message
         let _ = log_sender.send((
             session_id,
@@ -192,7 +211,7 @@ impl DockerLogStreamingManager {
             match log_result {
                 Ok(log_output) => {
                     // Extract raw message
-                    let raw_message = match &log_output {
+                    let raw_line = match &log_output {
                         LogOutput::StdOut { message }
                         | LogOutput::StdErr { message }
                         | LogOutput::Console { message }
@@ -200,6 +219,7 @@ impl DockerLogStreamingManager {
                             String::from_utf8_lossy(message).to_string()
                         }
                     };
+                    let (logged_at, raw_message) = Self::split_log_timestamp(&raw_line);
 
                     // For boss mode, try to extract a JSON slice from the line
                     // Docker logs format: "2025-09-08T19:20:30.123456789Z {"type":"..."}"
@@ -207,7 +227,7 @@ impl DockerLogStreamingManager {
                     let parser_debug = std::env::var("AGENTS_BOX_PARSER_DEBUG").is_ok();
 
                     // Prefer robust streaming JSON handling for any line that looks like JSON
-                    if let Some(start) = raw_message.find('{') {
+                    if let Some(start) = raw_message.find('{').filter(|_| parse_json) {
                         let mut candidate = String::new();
                         if !boss_json_buffer.is_empty() {
                             candidate.push_str(&boss_json_buffer);
@@ -221,7 +241,7 @@ impl DockerLogStreamingManager {
                                 got_objects = objects.len(),
                                 incomplete,
                                 preview = %candidate.chars().take(120).collect::<String>(),
-                                mode = ?session_mode,
+                                agent = agent_type.as_str(),
                                 "JSON candidate evaluated"
                             );
                         }
@@ -229,20 +249,30 @@ impl DockerLogStreamingManager {
                         if !objects.is_empty() {
                             if agent_parser.is_none() {
                                 agent_parser =
-                                    Some(Box::new(crate::agent_parsers::ClaudeJsonParser::new()));
+                                    Some(crate::agent_parsers::ParserFactory::for_agent(agent_type));
                             }
                             if let Some(ref mut parser) = agent_parser {
                                 for obj in objects {
                                     match parser.parse_line(&obj) {
                                         Ok(events) => {
                                             for event in events {
+                                                if let Some((notifier, workspace)) = &webhook {
+                                                    for webhook_event in WebhookEvent::from_agent_event(
+                                                        session_id, workspace, &event,
+                                                    ) {
+                                                        notifier.notify(webhook_event);
+                                                    }
+                                                }
                                                 let log_entries = Self::agent_event_to_log_entries(
                                                     event,
                                                     &container_name,
                                                     session_id,
                                                     &mut message_router,
                                                 );
-                                                for log_entry in log_entries {
+                                                for mut log_entry in log_entries {
+                                                    if let Some(logged_at) = logged_at {
+                                                        log_entry.timestamp = logged_at;
+                                                    }
                                                     if let Err(e) =
                                                         log_sender.send((session_id, log_entry))
                                                     {
@@ -308,19 +338,21 @@ impl DockerLogStreamingManager {
                         }
                     } else if !boss_json_buffer.is_empty() {
                         // Continue buffering if we were mid-object
-                        boss_json_buffer.push_str(&raw_message);
+                        boss_json_buffer.push_str(raw_message);
                         handled_as_json = true;
                     }
 
                     // Regular parsing for non-JSON lines or when JSON parsing fails
                     if !handled_as_json {
-                        let log_entry = Self::parse_log_output_with_parser(
-                            log_output,
+                        let mut log_entry = Self::parse_log_line_with_parser(
+                            raw_message,
                             &container_name,
                             session_id,
-                            &session_mode,
                             &mut log_parser,
                         );
+                        if let Some(logged_at) = logged_at {
+                            log_entry.timestamp = logged_at;
+                        }
 
                         if let Err(e) = log_sender.send((session_id, log_entry)) {
                             warn!("Failed to send log entry: {}", e);
@@ -330,6 +362,14 @@ impl DockerLogStreamingManager {
                 }
                 Err(e) => {
                     error!("Error reading log stream: {}", e);
+                    if let Some((notifier, workspace)) = webhook.take() {
+                        notifier.notify(WebhookEvent::new(
+                            WebhookEventKind::Errored,
+                            session_id,
+                            &workspace,
+                            serde_json::json!({ "message": format!("Log stream error: {}", e) }),
+                        ));
+                    }
                     let _ = log_sender.send((
                         session_id,
                         LogEntry::new(
@@ -337,8 +377,10 @@ impl DockerLogStreamingManager {
                             "system".to_string(),
                             format!("❌ Log stream error: {}", e),
                         )
-                        .with_session(session_id),
+                        .with_session(session_id)
+                        .with_metadata("event_type", "stream_error"),
                     ));
+                    stream_failed = true;
                     break;
                 }
             }
@@ -349,6 +391,16 @@ impl DockerLogStreamingManager {
             container_id, session_id
         );
 
+        // The stream ends when the container exits; a stream error already reported errored
+        if let Some((notifier, workspace)) = &webhook {
+            notifier.notify(WebhookEvent::new(
+                WebhookEventKind::Completed,
+                session_id,
+                workspace,
+                serde_json::json!({ "container": container_name }),
+            ));
+        }
+
         // Send disconnection message
         let _ = log_sender.send((
             session_id,
@@ -357,12 +409,23 @@ impl DockerLogStreamingManager {
                 "system".to_string(),
                 format!("📡 Disconnected from container logs: {}", container_name),
             )
-            .with_session(session_id),
+            .with_session(session_id)
+            .with_metadata("event_type", if stream_failed { "stream_closed" } else { "stream_end" }),
         ));
 
         Ok(())
     }
 
+    /// Split the RFC 3339 timestamp Docker puts in front of each line when asked for one
+    fn split_log_timestamp(line: &str) -> (Option<chrono::DateTime<chrono::Utc>>, &str) {
+        line.split_once(' ')
+            .and_then(|(stamp, rest)| {
+                let logged_at = chrono::DateTime::parse_from_rfc3339(stamp).ok()?;
+                Some((Some(logged_at.with_timezone(&chrono::Utc)), rest))
+            })
+            .unwrap_or((None, line))
+    }
+
     /// Extract a JSON object slice from a Docker log line.
     /// Examples:
     ///  - "2025-09-08T19:20:30.123Z {\"type\":\"assistant\"}"
@@ -447,24 +510,14 @@ impl DockerLogStreamingManager {
     }
 
     /// Parse Docker log output with the new parser
-    fn parse_log_output_with_parser(
-        log_output: LogOutput,
+    fn parse_log_line_with_parser(
+        raw_message: &str,
         container_name: &str,
         session_id: Uuid,
-        _session_mode: &crate::models::SessionMode,
         parser: &mut LogParser,
     ) -> LogEntry {
-        let (raw_message, _is_stderr) = match log_output {
-            LogOutput::StdOut { message } => (String::from_utf8_lossy(&message).to_string(), false),
-            LogOutput::StdErr { message } => (String::from_utf8_lossy(&message).to_string(), true),
-            LogOutput::Console { message } => {
-                (String::from_utf8_lossy(&message).to_string(), false)
-            }
-            LogOutput::StdIn { message } => (String::from_utf8_lossy(&message).to_string(), false),
-        };
-
         // Parse the log with our advanced parser
-        let parsed_log = parser.parse_log(&raw_message);
+        let parsed_log = parser.parse_log(raw_message);
 
         // Convert parsed log to LogEntry
         let level = match parsed_log.level {
@@ -487,22 +540,54 @@ impl DockerLogStreamingManager {
     }
 
     /// Convert AgentEvent to multiple LogEntries for display using the widget system
-    fn agent_event_to_log_entries(
+    pub(crate) fn agent_event_to_log_entries(
         event: crate::agent_parsers::AgentEvent,
         container_name: &str,
         session_id: Uuid,
         message_router: &mut crate::widgets::MessageRouter,
     ) -> Vec<LogEntry> {
-        // Use the message router to render the event
+        // Usage isn't displayed; forward it as metadata so the app can total it per session
+        if let crate::agent_parsers::AgentEvent::Usage {
+            input_tokens,
+            output_tokens,
+            cache_tokens,
+            total_cost,
+        } = &event
+        {
+            let mut entry =
+                LogEntry::new(LogEntryLevel::Debug, container_name.to_string(), String::new())
+                    .with_session(session_id)
+                    .with_metadata("event_type", "usage")
+                    .with_metadata("input_tokens", &input_tokens.to_string())
+                    .with_metadata("output_tokens", &output_tokens.to_string())
+                    .with_metadata("cache_tokens", &cache_tokens.unwrap_or(0).to_string());
+            if let Some(cost) = total_cost {
+                entry = entry.with_metadata("total_cost", &cost.to_string());
+            }
+            return vec![entry];
+        }
+
+        // MCP server status goes along as metadata too, so the app can show it per session;
+        // the init event itself is still rendered below
+        let mcp_entry = match &event {
+            crate::agent_parsers::AgentEvent::SessionInfo {
+                mcp_servers: Some(servers),
+                ..
+            } => serde_json::to_string(servers).ok().map(|servers| {
+                LogEntry::new(LogEntryLevel::Debug, container_name.to_string(), String::new())
+                    .with_session(session_id)
+                    .with_metadata("event_type", "mcp_servers")
+                    .with_metadata("servers", &servers)
+            }),
+            _ => None,
+        };
 
         // Render the event using the appropriate widget
         let output = message_router.route_event(event, container_name, session_id);
 
         // Convert widget output to LogEntry vector using the proper to_log_entries method
-        let entries = output.to_log_entries();
-
         // Return entries as-is, even if empty (for filtered events)
-        entries
+        mcp_entry.into_iter().chain(output.to_log_entries()).collect()
     }
 
     /// Convert AgentEvent to LogEntry for display (backwards compatibility)
@@ -642,11 +727,19 @@ impl DockerLogStreamingManager {
                     }
                 }
 
+                // What to copy for this call: the shell command or query, else the raw input
+                let command = input
+                    .get("command")
+                    .or_else(|| input.get("query"))
+                    .and_then(|v| v.as_str())
+                    .map_or_else(|| input.to_string(), str::to_string);
+
                 LogEntry::new(LogEntryLevel::Info, container_name.to_string(), msg)
                     .with_session(session_id)
                     .with_metadata("event_type", "tool_call")
                     .with_metadata("tool_id", &id)
                     .with_metadata("tool_name", &name)
+                    .with_metadata("command", &command)
             }
 
             AgentEvent::ToolResult {
@@ -823,15 +916,26 @@ impl DockerLogStreamingManager {
     /// Start streaming logs for all active sessions
     pub async fn start_streaming_for_sessions(
         &mut self,
-        sessions: &[(Uuid, String, String, crate::models::SessionMode)], // (session_id, container_id, container_name, session_mode)
+        sessions: &[(
+            Uuid,
+            String,
+            String,
+            String,
+            crate::models::SessionMode,
+            crate::models::AgentType,
+        )], // (session_id, container_id, container_name, workspace, session_mode, agent_type)
     ) -> Result<()> {
-        for (session_id, container_id, container_name, session_mode) in sessions {
+        for (session_id, container_id, container_name, workspace, session_mode, agent_type) in
+            sessions
+        {
             if let Err(e) = self
                 .start_streaming(
                     *session_id,
                     container_id.clone(),
                     container_name.clone(),
+                    workspace.clone(),
                     session_mode.clone(),
+                    *agent_type,
                 )
                 .await
             {
@@ -884,6 +988,13 @@ impl LogStreamingCoordinator {
         Ok(())
     }
 
+    /// Forward parsed agent events to a webhook (no-op until the manager is initialized)
+    pub fn set_webhook(&mut self, webhook: Option<WebhookNotifier>) {
+        if let Some(manager) = &mut self.manager {
+            manager.set_webhook(webhook);
+        }
+    }
+
     /// Get the next log entry from any container (non-blocking)
     pub fn try_next_log(&mut self) -> Option<(Uuid, LogEntry)> {
         self.log_receiver.try_recv().ok()
@@ -900,11 +1011,20 @@ impl LogStreamingCoordinator {
         session_id: Uuid,
         container_id: String,
         container_name: String,
+        workspace: String,
         session_mode: crate::models::SessionMode,
+        agent_type: crate::models::AgentType,
     ) -> Result<()> {
         if let Some(manager) = &mut self.manager {
             manager
-                .start_streaming(session_id, container_id, container_name, session_mode)
+                .start_streaming(
+                    session_id,
+                    container_id,
+                    container_name,
+                    workspace,
+                    session_mode,
+                    agent_type,
+                )
                 .await
         } else {
             Err(anyhow!("Log streaming manager not initialized"))
@@ -934,6 +1054,23 @@ impl LogStreamingCoordinator {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_split_log_timestamp() {
+        let (logged_at, rest) = DockerLogStreamingManager::split_log_timestamp(
+            r#"2025-09-08T19:20:30.123456789Z {"type":"result"}"#,
+        );
+        assert_eq!(
+            logged_at.map(|t| t.to_rfc3339()),
+            Some("2025-09-08T19:20:30.123456789+00:00".to_string())
+        );
+        assert_eq!(rest, r#"{"type":"result"}"#);
+
+        // Lines without a timestamp are passed through whole
+        let (logged_at, rest) = DockerLogStreamingManager::split_log_timestamp("plain output");
+        assert!(logged_at.is_none());
+        assert_eq!(rest, "plain output");
+    }
+
     #[tokio::test]
     async fn test_log_entry_parsing() {
         let container_name = "test-container";
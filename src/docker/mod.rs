@@ -8,11 +8,19 @@ pub mod log_streaming;
 pub mod session_container;
 pub mod session_lifecycle;
 pub mod session_progress;
+pub mod webhook;
 
 pub use builder::ImageBuilder;
-pub use agents_dev::{AgentsDevConfig, AgentsDevProgress, create_agents_dev_session};
-pub use container_manager::{ContainerError, ContainerManager};
+pub use agents_dev::{
+    AgentsDevConfig, AgentsDevProgress, MissingImageError, create_agents_dev_session,
+    ensure_agents_dev_image,
+};
+pub use container_manager::{
+    ContainerError, ContainerManager, ContainerUsage, OOM_KILLED_MESSAGE, check_docker_daemon,
+    is_docker_permission_error,
+};
 pub use log_streaming::LogStreamingCoordinator;
 pub use session_container::{ContainerConfig, ContainerStatus, SessionContainer};
 pub use session_lifecycle::SessionLifecycleManager;
-pub use session_progress::SessionProgress;
+pub use session_progress::{BuildProgress, SessionProgress, parse_build_line};
+pub use webhook::{WebhookEvent, WebhookEventKind, WebhookNotifier};
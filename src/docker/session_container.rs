@@ -3,7 +3,8 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::net::TcpListener;
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -50,6 +51,8 @@ pub struct ContainerConfig {
     pub user: Option<String>,
     pub memory_limit: Option<u64>, // bytes
     pub cpu_limit: Option<f64>,    // CPU shares (1.0 = 1 CPU)
+    pub cpu_shares: Option<u64>,   // Relative CPU weight (Docker default 1024)
+    pub labels: HashMap<String, String>, // Added alongside the session and managed labels
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +94,8 @@ impl Default for ContainerConfig {
             user: None,
             memory_limit: Some(2 * 1024 * 1024 * 1024), // 2GB default
             cpu_limit: Some(2.0),                       // 2 CPUs default
+            cpu_shares: None,
+            labels: HashMap::new(),
         }
     }
 }
@@ -136,6 +141,14 @@ impl ContainerConfig {
         self
     }
 
+    /// Published ports as (host, container) pairs, skipping auto-assigned host ports
+    pub fn host_port_mappings(&self) -> Vec<(u16, u16)> {
+        self.ports
+            .iter()
+            .filter_map(|port| port.host_port.map(|host| (host, port.container_port)))
+            .collect()
+    }
+
     pub fn with_command(mut self, command: Vec<String>) -> Self {
         self.command = Some(command);
         self
@@ -146,11 +159,20 @@ impl ContainerConfig {
         self
     }
 
+    pub fn with_memory_limit_mb(self, megabytes: u64) -> Self {
+        self.with_memory_limit(megabytes * 1024 * 1024)
+    }
+
     pub fn with_cpu_limit(mut self, cpus: f64) -> Self {
         self.cpu_limit = Some(cpus);
         self
     }
 
+    pub fn with_cpu_shares(mut self, shares: u64) -> Self {
+        self.cpu_shares = Some(shares);
+        self
+    }
+
     /// Create a Claude Code development environment configuration
     pub fn claude_dev_config(worktree_path: PathBuf) -> Self {
         let mut env_vars = HashMap::new();
@@ -186,6 +208,8 @@ impl ContainerConfig {
             user: Some("developer".to_string()),
             memory_limit: Some(4 * 1024 * 1024 * 1024), // 4GB for development
             cpu_limit: Some(4.0),                       // 4 CPUs for development
+            cpu_shares: None,
+            labels: HashMap::new(),
         }
     }
 }
@@ -221,3 +245,64 @@ impl SessionContainer {
             .map(|host_port| format!("http://localhost:{}", host_port))
     }
 }
+
+/// Host ports in `ports` that can't be used: mapped more than once, or found in `in_use`.
+/// Sorted, each listed once.
+pub fn find_port_conflicts(ports: &[(u16, u16)], in_use: &HashSet<u16>) -> Vec<u16> {
+    let mut seen = HashSet::new();
+    let mut conflicts: Vec<u16> = ports
+        .iter()
+        .map(|(host, _)| *host)
+        .filter(|host| !seen.insert(*host) || in_use.contains(host))
+        .collect();
+    conflicts.sort_unstable();
+    conflicts.dedup();
+    conflicts
+}
+
+/// Which of `ports` something on the host is already listening on, found by trying to
+/// bind each on the loopback address session ports are published to. Other bind errors
+/// don't count: a non-root user can't bind ports below 1024, but the daemon can publish them.
+pub fn host_ports_in_use(ports: impl IntoIterator<Item = u16>) -> HashSet<u16> {
+    ports
+        .into_iter()
+        .filter(|port| {
+            matches!(
+                TcpListener::bind(("127.0.0.1", *port)),
+                Err(e) if e.kind() == std::io::ErrorKind::AddrInUse
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_port_conflicts() {
+        let in_use: HashSet<u16> = [5432, 8080].into_iter().collect();
+        assert!(find_port_conflicts(&[(3000, 3000), (5173, 5173)], &in_use).is_empty());
+        assert_eq!(find_port_conflicts(&[(8080, 3000), (3001, 3001)], &in_use), vec![8080]);
+        // The same host port twice conflicts even when the host has it free
+        assert_eq!(
+            find_port_conflicts(&[(3000, 3000), (3000, 4000), (8080, 80), (8080, 81)], &in_use),
+            vec![3000, 8080]
+        );
+    }
+
+    #[test]
+    fn test_host_ports_in_use_detects_listener() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let taken = listener.local_addr().unwrap().port();
+        assert_eq!(host_ports_in_use([taken]), [taken].into_iter().collect());
+    }
+
+    #[test]
+    fn test_host_port_mappings_skip_auto_assigned() {
+        let config = ContainerConfig::new("alpine:latest".to_string())
+            .with_port(3000, Some(8080))
+            .with_port(9229, None);
+        assert_eq!(config.host_port_mappings(), vec![(8080, 3000)]);
+    }
+}
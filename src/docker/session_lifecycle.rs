@@ -13,7 +13,7 @@ use crate::git::{WorktreeInfo, WorktreeManager};
 use crate::models::{Session, SessionStatus};
 use anyhow::Result;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
@@ -33,8 +33,15 @@ pub enum SessionLifecycleError {
     InvalidState(String),
     #[error("Configuration error: {0}")]
     ConfigError(String),
+    #[error("Invalid Claude settings file {}: {reason}", path.display())]
+    InvalidClaudeSettings { path: PathBuf, reason: String },
 }
 
+/// Where session Claude settings are mounted. Claude reads managed settings from here
+/// with the highest precedence, and the path is outside both the mounted ~/.claude
+/// directory and the worktree, so nothing is created on the host.
+const CLAUDE_SETTINGS_CONTAINER_PATH: &str = "/etc/claude-code/managed-settings.json";
+
 pub struct SessionLifecycleManager {
     worktree_manager: WorktreeManager,
     container_manager: ContainerManager,
@@ -60,6 +67,14 @@ pub struct SessionRequest {
     pub skip_permissions: bool,
     pub mode: crate::models::SessionMode,
     pub boss_prompt: Option<String>,
+    pub env_vars: Vec<(String, String)>, // Set in the container last, over template and project values
+    pub volumes: Vec<(PathBuf, String, bool)>, // Extra mounts: host path, container path, read-only
+    pub ports: Vec<(u16, u16)>, // Published (host, container) ports, added to the config defaults
+    pub memory_limit_mb: Option<u64>,    // Overrides the config file and template limits
+    pub cpu_shares: Option<u64>,         // Overrides the config file and template weight
+    pub agent_type: crate::models::AgentType, // CLI the container runs, and its output parser
+    pub model: Option<String>, // Claude model for the container (None = the CLI's default)
+    pub image: Option<String>, // Overrides the config file and template images
 }
 
 impl SessionLifecycleManager {
@@ -172,17 +187,25 @@ impl SessionLifecycleManager {
         );
         session.id = request.session_id;
         session.branch_name = request.branch_name.clone();
+        session.agent_type = request.agent_type;
+        session.model = request.model.clone();
 
         // Use agents_dev module to create container
         let agents_dev_config = AgentsDevConfig {
-            image_name: "agents-box:agents-dev".to_string(),
+            image_name: super::agents_dev::AGENTS_DEV_IMAGE.to_string(),
             memory_limit: None,
             gpu_access: None,
             force_rebuild: false,
             no_cache: false,
             continue_session: false,
             skip_permissions: request.skip_permissions,
-            env_vars: std::collections::HashMap::new(),
+            env_vars: request
+                .model
+                .iter()
+                .map(|model| ("ANTHROPIC_MODEL".to_string(), model.clone()))
+                .collect(),
+            missing_image_policy: self.app_config.docker.missing_image_policy,
+            agent_type: request.agent_type,
         };
 
         // Create the agents-dev container using the native module
@@ -391,10 +414,16 @@ impl SessionLifecycleManager {
                 let status = self.container_manager.get_container_status(container_id).await?;
                 container.status = status.clone();
 
-                // Update session status based on container status
+                // Update session status based on container status. A container that
+                // hasn't settled yet keeps its status during the startup grace period.
+                let starting = chrono::Utc::now() - session_state.session.created_at
+                    < self.app_config.docker.startup_grace();
                 session_state.session.set_status(match status {
                     ContainerStatus::Running => SessionStatus::Running,
                     ContainerStatus::Stopped | ContainerStatus::NotFound => SessionStatus::Stopped,
+                    ContainerStatus::Error(_) if starting => {
+                        session_state.session.status.clone()
+                    }
                     ContainerStatus::Error(msg) => SessionStatus::Error(msg),
                     _ => SessionStatus::Stopped,
                 });
@@ -644,6 +673,11 @@ impl SessionLifecycleManager {
                 SessionLifecycleError::ConfigError(format!("Failed to load project config: {}", e))
             })?;
 
+        // Reject a broken settings file before any worktree or container is created
+        if let Some(settings_path) = self.claude_settings_path(request, &project_config) {
+            validate_claude_settings(&settings_path)?;
+        }
+
         // Determine which template to use
         let template_name = project_config
             .as_ref()
@@ -723,6 +757,16 @@ impl SessionLifecycleManager {
             false, // read-write
         );
 
+        // Large pastes saved from the prompt editor are referenced by their host path,
+        // so mount this repository's at the same path for the agent to read
+        if let Some(pastes_dir) = crate::app::prompt_drafts::PromptDraftStore::in_home_dir()
+            .map(|store| store.pastes_dir_for_repo(&worktree_info.source_repository))
+            .filter(|dir| dir.exists())
+        {
+            let container_path = pastes_dir.to_string_lossy().to_string();
+            config = config.with_volume(pastes_dir, container_path, true);
+        }
+
         Ok(config)
     }
 
@@ -738,6 +782,19 @@ impl SessionLifecycleManager {
             self.apply_project_config(config, project_config);
         }
 
+        if let Some(settings_path) = self.claude_settings_path(request, project_config) {
+            info!(
+                "Mounting Claude settings from {} for session {}",
+                settings_path.display(),
+                request.session_id
+            );
+            *config = config.clone().with_volume(
+                settings_path,
+                CLAUDE_SETTINGS_CONTAINER_PATH.to_string(),
+                true,
+            );
+        }
+
         // Set session mode environment variable
         let mode_str = match request.mode {
             crate::models::SessionMode::Interactive => "interactive",
@@ -751,12 +808,88 @@ impl SessionLifecycleManager {
             mode_str, request.session_id
         );
 
+        // The startup script launches this agent's CLI; the label lets the session loader
+        // pick the right log parser after a restart
+        config
+            .environment_vars
+            .insert("AGENTS_BOX_AGENT".to_string(), request.agent_type.as_str().to_string());
+        config.labels.insert(
+            "agents-agent-type".to_string(),
+            request.agent_type.as_str().to_string(),
+        );
+
+        // The Claude CLI reads its model from ANTHROPIC_MODEL; the label lets the session
+        // loader show it after a restart
+        if let Some(ref model) = request.model {
+            config.environment_vars.insert("ANTHROPIC_MODEL".to_string(), model.clone());
+            config.labels.insert("agents-model".to_string(), model.clone());
+            info!("Set model '{}' for session {}", model, request.session_id);
+        }
+
+        // Lets the session loader compare the session with the branch it started from
+        if let Some(ref base_branch) = request.base_branch {
+            config.labels.insert("agents-base-branch".to_string(), base_branch.clone());
+        }
+
         // Set boss prompt if in boss mode
         if let Some(ref prompt) = request.boss_prompt {
             config.environment_vars.insert("AGENTS_BOX_PROMPT".to_string(), prompt.clone());
             info!("Set boss prompt for session {}", request.session_id);
         }
 
+        // Resource limits: the session request wins over the config file, which wins over
+        // the template and project values applied above
+        if let Some(memory) = request.memory_limit_mb.or(self.app_config.docker.memory_limit_mb) {
+            *config = config.clone().with_memory_limit_mb(memory);
+        }
+        if let Some(shares) = request.cpu_shares.or(self.app_config.docker.cpu_shares) {
+            *config = config.clone().with_cpu_shares(shares);
+        }
+        config.image = select_image(
+            request.image.as_deref(),
+            self.app_config.docker.container_image.as_deref(),
+            &config.image,
+        );
+        info!("Using image {} for session {}", config.image, request.session_id);
+
+        // Extra mounts chosen when creating the session, alongside the automatic ones
+        for (host_path, container_path, read_only) in &request.volumes {
+            *config =
+                config.clone().with_volume(host_path.clone(), container_path.clone(), *read_only);
+        }
+        if !request.volumes.is_empty() {
+            info!(
+                "Mounted {} extra host directories for session {}",
+                request.volumes.len(),
+                request.session_id
+            );
+        }
+
+        // Published ports: the config defaults, then the session's own, which win for a
+        // container port both map
+        let ports = merge_port_mappings(&self.app_config.docker.ports, &request.ports);
+        for (host_port, container_port) in &ports {
+            *config = config.clone().with_port(*container_port, Some(*host_port));
+        }
+        if !ports.is_empty() {
+            // The label lets the session loader show the ports, even while stopped
+            let label: Vec<String> = ports.iter().map(|(h, c)| format!("{}:{}", h, c)).collect();
+            config.labels.insert("agents-ports".to_string(), label.join(","));
+            info!("Publishing ports {:?} for session {}", ports, request.session_id);
+        }
+
+        // Session variables chosen when creating the session override everything above
+        for (key, value) in &request.env_vars {
+            config.environment_vars.insert(key.clone(), value.clone());
+        }
+        if !request.env_vars.is_empty() {
+            info!(
+                "Set {} session environment variables for session {}",
+                request.env_vars.len(),
+                request.session_id
+            );
+        }
+
         // Apply skip_permissions flag if requested
         if request.skip_permissions {
             let current_flag =
@@ -784,12 +917,27 @@ impl SessionLifecycleManager {
         Ok(())
     }
 
+    /// The Claude settings file for a session: the project's `claude_settings`
+    /// (relative to the repository) or else the configured template
+    fn claude_settings_path(
+        &self,
+        request: &SessionRequest,
+        project_config: &Option<ProjectConfig>,
+    ) -> Option<PathBuf> {
+        project_config
+            .as_ref()
+            .and_then(|pc| pc.claude_settings.as_ref())
+            .map(|path| request.workspace_path.join(path))
+            .or_else(|| self.app_config.workspace_defaults.claude_settings_template.clone())
+    }
+
     /// Update the auth .claude.json file to set hasTrustDialogAccepted=true when skip permissions is enabled
     fn update_auth_claude_json_for_skip_permissions() -> Result<(), Box<dyn std::error::Error>> {
         use std::fs;
 
-        let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-        let auth_claude_json = home_dir.join(".agents-in-a-box/auth/.claude.json");
+        let profiles =
+            crate::config::AuthProfiles::in_home_dir().ok_or("Could not find home directory")?;
+        let auth_claude_json = profiles.active_dir().join(".claude.json");
 
         if !auth_claude_json.exists() {
             return Err("Auth .claude.json file not found".into());
@@ -874,9 +1022,12 @@ impl SessionLifecycleManager {
                     info!("Mounting user's entire .claude directory from ~/.claude");
                 }
 
-                // Then mount agents-in-a-box auth credentials on top
+                // Then mount the active auth profile's credentials on top
                 // This will override any .credentials.json from the host .claude directory
-                let credentials_path = home_dir.join(".agents-in-a-box/auth/.credentials.json");
+                let profiles = crate::config::AuthProfiles::new(home_dir.join(".agents-in-a-box"));
+                let profile = profiles.active();
+                let auth_dir = profiles.profile_dir(&profile);
+                let credentials_path = auth_dir.join(".credentials.json");
                 if credentials_path.exists() {
                     *config = config.clone().with_volume(
                         credentials_path.clone(),
@@ -884,7 +1035,9 @@ impl SessionLifecycleManager {
                         true, // read-only for security
                     );
                     info!(
-                        "Mounting agents-in-a-box auth credentials from ~/.agents-in-a-box/auth/.credentials.json"
+                        "Mounting auth credentials for profile '{}' from {}",
+                        profile,
+                        credentials_path.display()
                     );
 
                     // ALSO set OAuth token as environment variable for redundancy
@@ -909,12 +1062,13 @@ impl SessionLifecycleManager {
                     }
                 } else {
                     warn!(
-                        "mount_claude_config is true but ~/.agents-in-a-box/auth/.credentials.json not found - run 'agents-box auth' first"
+                        "mount_claude_config is true but {} not found - run 'agents-box auth' first",
+                        credentials_path.display()
                     );
                 }
 
                 // Check for .claude.json in the auth directory (created during OAuth)
-                let claude_json_auth_path = home_dir.join(".agents-in-a-box/auth/.claude.json");
+                let claude_json_auth_path = auth_dir.join(".claude.json");
                 if claude_json_auth_path.exists() {
                     *config = config.clone().with_volume(
                         claude_json_auth_path,
@@ -931,7 +1085,7 @@ impl SessionLifecycleManager {
                 }
 
                 // Mount .env file if it exists for API key authentication
-                let env_path = home_dir.join(".agents-in-a-box/.env");
+                let env_path = profiles.env_file(&profile);
                 if env_path.exists() {
                     *config = config.clone().with_volume(
                         env_path,
@@ -997,7 +1151,10 @@ impl SessionLifecycleManager {
         );
         session.id = request.session_id;
         session.branch_name = request.branch_name.clone();
+        session.agent_type = request.agent_type;
+        session.model = request.model.clone();
         session.container_id = container.container_id.clone();
+        session.ports = container.config.host_port_mappings();
 
         // Set session status to Running since the container was successfully created and started
         session.set_status(SessionStatus::Running);
@@ -1055,6 +1212,8 @@ impl SessionLifecycleManager {
         );
         session.id = request.session_id;
         session.branch_name = request.branch_name.clone();
+        session.agent_type = request.agent_type;
+        session.model = request.model.clone();
 
         // Create base container config using existing helper
         let mut container_config =
@@ -1113,6 +1272,7 @@ impl SessionLifecycleManager {
 
         let container_id = container.container_id.clone().unwrap_or_default();
         session.container_id = Some(container_id.clone());
+        session.ports = container.config.host_port_mappings();
 
         // Start the container
         self.container_manager.start_container(&mut container).await?;
@@ -1158,6 +1318,14 @@ impl SessionRequest {
             skip_permissions: false,
             mode: crate::models::SessionMode::Interactive, // Default to interactive mode
             boss_prompt: None,
+            env_vars: Vec::new(),
+            volumes: Vec::new(),
+            ports: Vec::new(),
+            memory_limit_mb: None,
+            cpu_shares: None,
+            agent_type: crate::models::AgentType::default(),
+            model: None,
+            image: None,
         }
     }
 
@@ -1166,6 +1334,11 @@ impl SessionRequest {
         self
     }
 
+    pub fn with_env_vars(mut self, env_vars: Vec<(String, String)>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+
     pub fn with_container_config(mut self, config: ContainerConfig) -> Self {
         self.container_config = Some(config);
         self
@@ -1189,6 +1362,14 @@ impl SessionRequest {
             skip_permissions: false,
             mode: crate::models::SessionMode::Interactive, // Default to interactive mode
             boss_prompt: None,
+            env_vars: Vec::new(),
+            volumes: Vec::new(),
+            ports: Vec::new(),
+            memory_limit_mb: None,
+            cpu_shares: None,
+            agent_type: crate::models::AgentType::default(),
+            model: None,
+            image: None,
         }
     }
 
@@ -1206,6 +1387,45 @@ impl SessionRequest {
     }
 }
 
+/// `defaults` followed by `session` ports, dropping any default whose container port the
+/// session maps itself
+fn merge_port_mappings(defaults: &[(u16, u16)], session: &[(u16, u16)]) -> Vec<(u16, u16)> {
+    defaults
+        .iter()
+        .filter(|(_, container)| !session.iter().any(|(_, c)| c == container))
+        .chain(session)
+        .copied()
+        .collect()
+}
+
+/// Image for a session's container: the one picked for the session, else the config
+/// file's `docker.container_image`, else the template's. Blank choices are skipped.
+fn select_image(requested: Option<&str>, configured: Option<&str>, template_image: &str) -> String {
+    [requested, configured]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|image| !image.is_empty())
+        .unwrap_or(template_image)
+        .to_string()
+}
+
+/// Check that a Claude settings file exists and holds a JSON object
+fn validate_claude_settings(path: &Path) -> Result<(), SessionLifecycleError> {
+    let invalid = |reason: String| SessionLifecycleError::InvalidClaudeSettings {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    let content = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+    let settings: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    if !settings.is_object() {
+        return Err(invalid("expected a JSON object".to_string()));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1413,4 +1633,50 @@ mod tests {
             .any(|p| matches!(p, AgentsDevProgress::CheckingEnvironment));
         assert!(has_env_check);
     }
+
+    #[test]
+    fn test_merge_port_mappings() {
+        let defaults = [(3000, 3000), (5432, 5432)];
+        assert_eq!(merge_port_mappings(&defaults, &[]), defaults.to_vec());
+        assert_eq!(
+            merge_port_mappings(&defaults, &[(8080, 3000), (9229, 9229)]),
+            vec![(5432, 5432), (8080, 3000), (9229, 9229)]
+        );
+    }
+
+    #[test]
+    fn test_select_image_precedence() {
+        let template = "agents-box:agents-dev";
+        assert_eq!(select_image(None, None, template), template);
+        assert_eq!(select_image(None, Some("ghcr.io/me/agent:1"), template), "ghcr.io/me/agent:1");
+        assert_eq!(
+            select_image(Some("python:3.12"), Some("ghcr.io/me/agent:1"), template),
+            "python:3.12"
+        );
+        assert_eq!(select_image(Some(" node:20 "), None, template), "node:20");
+        assert_eq!(
+            select_image(Some(""), Some("ghcr.io/me/agent:1"), template),
+            "ghcr.io/me/agent:1"
+        );
+        assert_eq!(select_image(Some(" "), None, template), template);
+    }
+
+    #[test]
+    fn test_validate_claude_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let valid = temp_dir.path().join("settings.json");
+        std::fs::write(&valid, r#"{"permissions": {"allow": ["Bash(npm test)"]}}"#).unwrap();
+        assert!(validate_claude_settings(&valid).is_ok());
+
+        let broken = temp_dir.path().join("broken.json");
+        std::fs::write(&broken, "{\n  \"permissions\": \n}").unwrap();
+        let message = validate_claude_settings(&broken).unwrap_err().to_string();
+        assert!(message.contains("broken.json"));
+        assert!(message.contains("line 3"));
+
+        let array = temp_dir.path().join("array.json");
+        std::fs::write(&array, "[]").unwrap();
+        assert!(validate_claude_settings(&array).is_err());
+        assert!(validate_claude_settings(&temp_dir.path().join("missing.json")).is_err());
+    }
 }
@@ -172,6 +172,107 @@ impl SessionPhase {
     }
 }
 
+/// One line of `docker build` or `docker pull` output, parsed for the progress bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildProgress {
+    /// A Dockerfile step: classic `Step 3/15 : RUN ...` or BuildKit `#7 [3/15] RUN ...`
+    Step { current: u32, total: u32, instruction: String },
+    /// A layer being downloaded or extracted: `a1b2c3d4: Downloading [==>  ] 12MB/45MB`
+    Layer { id: String, action: String, percent: u8 },
+    /// Anything else, streamed as a plain log line
+    Line(String),
+}
+
+impl BuildProgress {
+    /// How far along the build or layer is, from 0.0 to 1.0
+    pub fn ratio(&self) -> Option<f64> {
+        match self {
+            BuildProgress::Step { current, total, .. } if *total > 0 => {
+                Some((f64::from(*current) / f64::from(*total)).clamp(0.0, 1.0))
+            }
+            BuildProgress::Layer { percent, .. } => Some(f64::from(*percent) / 100.0),
+            _ => None,
+        }
+    }
+
+    /// Progress bar label, e.g. `Building image 7/15`
+    pub fn label(&self) -> Option<String> {
+        match self {
+            BuildProgress::Step { current, total, .. } => {
+                Some(format!("Building image {}/{}", current, total))
+            }
+            BuildProgress::Layer { id, action, percent } => {
+                Some(format!("{} layer {} {}%", action, id, percent))
+            }
+            BuildProgress::Line(_) => None,
+        }
+    }
+}
+
+/// Parse one line of build or pull output; unrecognised lines come back as `Line`
+pub fn parse_build_line(line: &str) -> BuildProgress {
+    let trimmed = line.trim();
+    parse_classic_step(trimmed)
+        .or_else(|| parse_buildkit_step(trimmed))
+        .or_else(|| parse_layer_progress(trimmed))
+        .unwrap_or_else(|| BuildProgress::Line(line.trim_end().to_string()))
+}
+
+/// `3/15` as (3, 15)
+fn parse_fraction(text: &str) -> Option<(u32, u32)> {
+    let (current, total) = text.split_once('/')?;
+    Some((current.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+/// `Step 3/15 : RUN apt-get update`
+fn parse_classic_step(line: &str) -> Option<BuildProgress> {
+    let rest = line.strip_prefix("Step ")?;
+    let (fraction, instruction) = rest.split_once(':').unwrap_or((rest, ""));
+    let (current, total) = parse_fraction(fraction)?;
+    Some(BuildProgress::Step { current, total, instruction: instruction.trim().to_string() })
+}
+
+/// `#7 [3/15] RUN apt-get update`, or `#7 [builder 3/15] ...` in multi-stage builds
+fn parse_buildkit_step(line: &str) -> Option<BuildProgress> {
+    let rest = line.strip_prefix('#')?;
+    let (number, rest) = rest.split_once(' ')?;
+    number.parse::<u32>().ok()?;
+    let rest = rest.trim_start().strip_prefix('[')?;
+    let (label, instruction) = rest.split_once(']')?;
+    let (current, total) = parse_fraction(label.split_whitespace().last()?)?;
+    Some(BuildProgress::Step { current, total, instruction: instruction.trim().to_string() })
+}
+
+/// `a1b2c3d4e5f6: Downloading [=====>     ]  12.3MB/45.6MB`
+fn parse_layer_progress(line: &str) -> Option<BuildProgress> {
+    let (id, rest) = line.split_once(": ")?;
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let action = rest.split(['[', ' ']).next()?.to_string();
+    let (current, total) = rest.split_whitespace().last()?.split_once('/')?;
+    let (current, total) = (parse_size(current)?, parse_size(total)?);
+    if action.is_empty() || total <= 0.0 {
+        return None;
+    }
+    let percent = (current / total * 100.0).clamp(0.0, 100.0) as u8;
+    Some(BuildProgress::Layer { id: id.to_string(), action, percent })
+}
+
+/// Docker's human-readable sizes (`512B`, `1.5kB`, `12.3MB`, `2GB`) in bytes
+fn parse_size(text: &str) -> Option<f64> {
+    let split = text.find(|c: char| c.is_ascii_alphabetic())?;
+    let (number, unit) = text.split_at(split);
+    let multiplier = match unit {
+        "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        _ => return None,
+    };
+    Some(number.parse::<f64>().ok()? * multiplier)
+}
+
 // Conversion from AgentsDevProgress for backward compatibility
 impl From<crate::docker::agents_dev::AgentsDevProgress> for SessionProgress {
     fn from(agents_progress: crate::docker::agents_dev::AgentsDevProgress) -> Self {
@@ -235,4 +336,73 @@ mod tests {
         assert_eq!(SessionPhase::Configuration.progress_percentage(), 10);
         assert_eq!(SessionPhase::Complete.progress_percentage(), 100);
     }
+
+    #[test]
+    fn test_parse_classic_build_steps() {
+        let step = parse_build_line("Step 7/15 : RUN npm install -g @anthropic-ai/claude-code\n");
+        assert_eq!(
+            step,
+            BuildProgress::Step {
+                current: 7,
+                total: 15,
+                instruction: "RUN npm install -g @anthropic-ai/claude-code".to_string(),
+            }
+        );
+        assert_eq!(step.label().as_deref(), Some("Building image 7/15"));
+        assert_eq!(step.ratio(), Some(7.0 / 15.0));
+
+        assert_eq!(
+            parse_build_line(" ---> Running in 4f2c9a1b3d5e"),
+            BuildProgress::Line(" ---> Running in 4f2c9a1b3d5e".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_buildkit_steps() {
+        assert_eq!(
+            parse_build_line("#8 [3/9] RUN apt-get update && apt-get install -y git"),
+            BuildProgress::Step {
+                current: 3,
+                total: 9,
+                instruction: "RUN apt-get update && apt-get install -y git".to_string(),
+            }
+        );
+        assert!(matches!(
+            parse_build_line("#12 [builder 5/6] COPY . ."),
+            BuildProgress::Step { current: 5, total: 6, .. }
+        ));
+        assert!(matches!(
+            parse_build_line("#1 [internal] load build definition from Dockerfile"),
+            BuildProgress::Line(_)
+        ));
+        assert!(matches!(
+            parse_build_line("#8 0.412 Reading package lists..."),
+            BuildProgress::Line(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_pull_layer_progress() {
+        let layer = parse_build_line("a1b2c3d4e5f6: Downloading [=====>     ]  12.5MB/50MB");
+        assert_eq!(
+            layer,
+            BuildProgress::Layer {
+                id: "a1b2c3d4e5f6".to_string(),
+                action: "Downloading".to_string(),
+                percent: 25,
+            }
+        );
+        assert_eq!(layer.label().as_deref(), Some("Downloading layer a1b2c3d4e5f6 25%"));
+        assert!(matches!(
+            parse_build_line("a1b2c3d4e5f6: Extracting [==========>] 1.5kB/1.5kB"),
+            BuildProgress::Layer { percent: 100, .. }
+        ));
+
+        // Status lines without sizes stream as plain text
+        assert!(matches!(parse_build_line("a1b2c3d4e5f6: Pull complete"), BuildProgress::Line(_)));
+        assert!(matches!(
+            parse_build_line("Status: Downloaded newer image for node:20"),
+            BuildProgress::Line(_)
+        ));
+    }
 }
@@ -0,0 +1,167 @@
+// ABOUTME: Best-effort delivery of session lifecycle and agent events to a configured webhook
+// Events are queued and POSTed from a background task so a slow endpoint never blocks the UI
+
+use crate::agent_parsers::AgentEvent;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Per-request timeout; delivery is best-effort and never retried
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Events waiting to be posted. Further events are dropped while the queue is full.
+const WEBHOOK_QUEUE_SIZE: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    Created,
+    Running,
+    Completed,
+    Errored,
+    AgentEvent,
+}
+
+/// JSON body POSTed to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub event: WebhookEventKind,
+    pub session_id: Uuid,
+    pub workspace: String,
+    pub timestamp: DateTime<Utc>,
+    pub payload: Value,
+}
+
+impl WebhookEvent {
+    pub fn new(event: WebhookEventKind, session_id: Uuid, workspace: &str, payload: Value) -> Self {
+        Self {
+            event,
+            session_id,
+            workspace: workspace.to_string(),
+            timestamp: Utc::now(),
+            payload,
+        }
+    }
+
+    /// Events to post for a parsed agent event: the event itself, preceded by the
+    /// lifecycle event it implies (session init means running, an error means errored).
+    /// Streaming deltas are skipped; the complete message follows them.
+    pub fn from_agent_event(session_id: Uuid, workspace: &str, event: &AgentEvent) -> Vec<Self> {
+        let lifecycle = match event {
+            AgentEvent::StreamingText { .. } => return Vec::new(),
+            AgentEvent::SessionInfo { .. } => Some(WebhookEventKind::Running),
+            AgentEvent::Error { .. } => Some(WebhookEventKind::Errored),
+            _ => None,
+        };
+
+        let payload = serde_json::to_value(event).unwrap_or(Value::Null);
+        lifecycle
+            .map(|kind| Self::new(kind, session_id, workspace, payload.clone()))
+            .into_iter()
+            .chain(std::iter::once(Self::new(
+                WebhookEventKind::AgentEvent,
+                session_id,
+                workspace,
+                payload,
+            )))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    sender: mpsc::Sender<WebhookEvent>,
+}
+
+impl WebhookNotifier {
+    /// Start the delivery task for `url`. Must be called from within the tokio runtime.
+    pub fn spawn(url: String) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent("agents-in-a-box/0.1.0")
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .context("Failed to create webhook HTTP client")?;
+        let (sender, receiver) = mpsc::channel(WEBHOOK_QUEUE_SIZE);
+
+        tokio::spawn(Self::deliver(client, url, receiver));
+        Ok(Self { sender })
+    }
+
+    /// Queue an event without waiting. Dropped if the queue is full.
+    pub fn notify(&self, event: WebhookEvent) {
+        if let Err(e) = self.sender.try_send(event) {
+            debug!("Dropping webhook event: {}", e);
+        }
+    }
+
+    async fn deliver(
+        client: reqwest::Client,
+        url: String,
+        mut receiver: mpsc::Receiver<WebhookEvent>,
+    ) {
+        let mut failing = false;
+        while let Some(event) = receiver.recv().await {
+            let result = client
+                .post(&url)
+                .json(&event)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+
+            match result {
+                Ok(_) if failing => {
+                    info!("Webhook {} is reachable again", url);
+                    failing = false;
+                }
+                Ok(_) => {}
+                // Only the first failure in a run is a warning, so an unreachable
+                // endpoint doesn't flood the log
+                Err(e) if failing => debug!("Webhook delivery failed: {}", e),
+                Err(e) => {
+                    warn!("Webhook delivery to {} failed: {}", url, e);
+                    failing = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_events_map_to_lifecycle() {
+        let session_id = Uuid::new_v4();
+        let init = AgentEvent::SessionInfo {
+            model: "claude".to_string(),
+            tools: vec![],
+            session_id: "abc".to_string(),
+            mcp_servers: None,
+        };
+
+        let events = WebhookEvent::from_agent_event(session_id, "app", &init);
+        let kinds: Vec<_> = events.iter().map(|e| e.event).collect();
+        assert_eq!(
+            kinds,
+            vec![WebhookEventKind::Running, WebhookEventKind::AgentEvent]
+        );
+
+        let body = serde_json::to_value(&events[1]).unwrap();
+        assert_eq!(body["event"], "agent_event");
+        assert_eq!(body["workspace"], "app");
+        assert_eq!(body["session_id"], session_id.to_string());
+        assert_eq!(body["payload"]["SessionInfo"]["model"], "claude");
+
+        let delta = AgentEvent::StreamingText {
+            delta: "partial".to_string(),
+            message_id: None,
+        };
+        assert!(WebhookEvent::from_agent_event(session_id, "app", &delta).is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+// ABOUTME: Validation and cleanup of user-typed branch names before a worktree is created
+// Catches names `git worktree add` would reject so the user can fix them while typing
+
+use git2::Branch;
+use thiserror::Error;
+
+/// Sequences git never allows in a ref name
+const FORBIDDEN_SEQUENCES: [&str; 10] = ["..", "~", "^", ":", "?", "*", "[", "\\", "@{", "//"];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BranchNameError {
+    #[error("Branch name is empty")]
+    Empty,
+    #[error("Branch name cannot contain control characters")]
+    ControlCharacter,
+    #[error("Branch name cannot contain '{0}'")]
+    ForbiddenSequence(&'static str),
+    #[error("'{0}' is not a valid git branch name")]
+    Invalid(String),
+}
+
+/// Clean up a typed branch name: runs of whitespace become hyphens and leading or
+/// trailing slashes are dropped. Anything else git would reject is an error.
+pub fn sanitize_branch_name(input: &str) -> Result<String, BranchNameError> {
+    if input.chars().any(char::is_control) {
+        return Err(BranchNameError::ControlCharacter);
+    }
+
+    let name = input.split_whitespace().collect::<Vec<_>>().join("-");
+    let name = name.trim_matches('/');
+    if name.is_empty() {
+        return Err(BranchNameError::Empty);
+    }
+
+    if let Some(sequence) = FORBIDDEN_SEQUENCES.iter().find(|seq| name.contains(*seq)) {
+        return Err(BranchNameError::ForbiddenSequence(sequence));
+    }
+
+    // Covers the remaining rules: `.lock` suffixes, components starting with `.`, ...
+    if !Branch::name_is_valid(name).unwrap_or(false) {
+        return Err(BranchNameError::Invalid(name.to_string()));
+    }
+
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_branch_name() {
+        assert_eq!(
+            sanitize_branch_name("feat/foo bar"),
+            Ok("feat/foo-bar".to_string())
+        );
+        assert_eq!(
+            sanitize_branch_name("  /agents/fix  login bug/ "),
+            Ok("agents/fix-login-bug".to_string())
+        );
+        assert_eq!(
+            sanitize_branch_name("agents/a1b2c3d4"),
+            Ok("agents/a1b2c3d4".to_string())
+        );
+
+        assert_eq!(sanitize_branch_name(""), Err(BranchNameError::Empty));
+        assert_eq!(sanitize_branch_name(" / "), Err(BranchNameError::Empty));
+        assert_eq!(
+            sanitize_branch_name("../escape"),
+            Err(BranchNameError::ForbiddenSequence(".."))
+        );
+        assert_eq!(
+            sanitize_branch_name("feat:thing"),
+            Err(BranchNameError::ForbiddenSequence(":"))
+        );
+        assert_eq!(
+            sanitize_branch_name("bad\u{7}name"),
+            Err(BranchNameError::ControlCharacter)
+        );
+        assert!(matches!(
+            sanitize_branch_name("feature.lock"),
+            Err(BranchNameError::Invalid(_))
+        ));
+        assert!(matches!(
+            sanitize_branch_name("feat/.hidden"),
+            Err(BranchNameError::Invalid(_))
+        ));
+    }
+}
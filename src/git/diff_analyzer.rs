@@ -3,10 +3,13 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use git2::{Diff, DiffOptions, Repository};
+use git2::{Diff, DiffFormat, DiffOptions, Oid, Repository};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
+use std::process::Command;
 use tracing::debug;
 
+use crate::git::worktree_manager::default_base_branch;
 use crate::models::GitChanges;
 
 #[derive(Debug, Clone)]
@@ -35,6 +38,39 @@ pub enum FileStatus {
     Untracked,
 }
 
+/// A file the session changed at some point, whether or not the change is still there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchedFile {
+    pub path: String,
+    pub commits: usize,    // Commits since the session base that changed this file
+    pub uncommitted: bool, // Changed in the index or working tree right now
+}
+
+/// Patches longer than this keep plain line highlighting in word mode
+pub const WORD_DIFF_MAX_PATCH_LINES: usize = 5000;
+/// Line pairs with more tokens than this are shown as a whole-line replacement
+const WORD_DIFF_MAX_TOKENS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordChange {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordSpan {
+    pub text: String,
+    pub change: WordChange,
+}
+
+/// A patch line in word mode: kept as-is, or a removed/added line pair merged into one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordDiffLine {
+    Plain(String),
+    Words(Vec<WordSpan>),
+}
+
 pub struct DiffAnalyzer {
     repo: Repository,
 }
@@ -77,7 +113,9 @@ impl DiffAnalyzer {
         self.analyze_diff(&diff)
     }
 
-    pub fn get_simple_changes(&self) -> Result<GitChanges> {
+    /// Added, modified and deleted file counts, plus commits ahead of and behind
+    /// `base_branch` (see `tracking_base`)
+    pub fn get_simple_changes(&self, base_branch: Option<&str>) -> Result<GitChanges> {
         let working_diff = self.analyze_working_directory()?;
         let staged_diff = self.analyze_staged_changes()?;
 
@@ -120,13 +158,55 @@ impl DiffAnalyzer {
             }
         }
 
+        // Divergence is best-effort: no base (or a failed count) just shows nothing
+        if let Some(base) = self.tracking_base(base_branch) {
+            match self.ahead_behind(&base) {
+                Ok((ahead, behind)) => {
+                    changes.ahead = ahead;
+                    changes.behind = behind;
+                }
+                Err(e) => debug!("Could not count commits against {}: {}", base, e),
+            }
+        }
+
         debug!(
-            "Simple changes: +{} ~{} -{}",
-            changes.added, changes.modified, changes.deleted
+            "Simple changes: +{} ~{} -{} ↑{} ↓{}",
+            changes.added, changes.modified, changes.deleted, changes.ahead, changes.behind
         );
         Ok(changes)
     }
 
+    /// Branch HEAD is compared with for ahead/behind counts: `base_branch`, the one the
+    /// session was created from, or else the repository's default base branch. Pushing
+    /// doesn't change it. None for a detached HEAD or on the base branch itself.
+    pub fn tracking_base(&self, base_branch: Option<&str>) -> Option<String> {
+        let head = self.repo.head().ok().filter(git2::Reference::is_branch)?;
+        let branch_name = head.shorthand()?;
+        let base = base_branch.map_or_else(|| default_base_branch(&self.repo), str::to_string);
+        (base != branch_name).then_some(base)
+    }
+
+    /// Commits HEAD is ahead of and behind `base`, from
+    /// `git rev-list --left-right --count base...HEAD`
+    pub fn ahead_behind(&self, base: &str) -> Result<(usize, usize)> {
+        let workdir = self.repo.workdir().unwrap_or_else(|| self.repo.path());
+        let output = Command::new("git")
+            .args(["rev-list", "--left-right", "--count", &format!("{}...HEAD", base)])
+            .current_dir(workdir)
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        // Left side is commits only on the base, right side commits only on HEAD
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut counts = stdout.split_whitespace().map(str::parse::<usize>);
+        match (counts.next(), counts.next()) {
+            (Some(Ok(behind)), Some(Ok(ahead))) => Ok((ahead, behind)),
+            _ => anyhow::bail!("Unexpected rev-list output: {}", stdout.trim()),
+        }
+    }
+
     fn analyze_diff(&self, diff: &Diff) -> Result<CustomDiffStats> {
         let git_stats = diff.stats()?;
         let mut files = Vec::new();
@@ -224,11 +304,209 @@ impl DiffAnalyzer {
         Ok(summary)
     }
 
+    /// Commit where HEAD diverged from `base_branch`, i.e. where the session's work starts
+    pub fn session_base(&self, base_branch: &str) -> Result<Oid> {
+        let head = self.repo.head()?.peel_to_commit()?.id();
+        let base = self.repo.revparse_single(base_branch)?.peel_to_commit()?.id();
+        Ok(self.repo.merge_base(head, base)?)
+    }
+
+    /// Every file changed since `base`: the union of each commit's changes plus anything
+    /// uncommitted, sorted by path. Files whose edits were later reverted are still listed.
+    pub fn files_touched(&self, base: Oid) -> Result<Vec<TouchedFile>> {
+        let mut touched: BTreeMap<String, TouchedFile> = BTreeMap::new();
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.hide(base)?;
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            let parent_tree = commit.parents().next().map(|parent| parent.tree()).transpose()?;
+            let diff =
+                self.repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+            for path in Self::delta_paths(&diff) {
+                Self::touched_entry(&mut touched, path).commits += 1;
+            }
+        }
+
+        let head_tree = self.repo.head()?.peel_to_tree()?;
+        let mut opts = DiffOptions::new();
+        opts.include_untracked(true).recurse_untracked_dirs(true);
+        let diff = self.repo.diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut opts))?;
+        for path in Self::delta_paths(&diff) {
+            Self::touched_entry(&mut touched, path).uncommitted = true;
+        }
+
+        Ok(touched.into_values().collect())
+    }
+
+    /// Diff of one file from `base` to the working tree, staged changes included
+    pub fn file_diff_since(&self, base: Oid, path: &str) -> Result<Diff<'_>> {
+        let base_tree = self.repo.find_commit(base)?.tree()?;
+        let mut opts = DiffOptions::new();
+        opts.pathspec(path)
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        Ok(self.repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))?)
+    }
+
+    /// Unified patch of the working tree as `git diff` prints it: against HEAD when
+    /// `include_staged`, otherwise against the index so only unstaged changes show
+    pub fn working_tree_patch(&self, include_staged: bool) -> Result<String> {
+        let diff = if include_staged {
+            let head_tree = self.repo.head()?.peel_to_tree()?;
+            self.repo.diff_tree_to_workdir_with_index(Some(&head_tree), None)?
+        } else {
+            self.repo.diff_index_to_workdir(None, None)?
+        };
+
+        let mut patch = String::new();
+        diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                patch.push(line.origin());
+            }
+            patch.push_str(&String::from_utf8_lossy(line.content()));
+            true
+        })?;
+        Ok(patch)
+    }
+
+    fn touched_entry(touched: &mut BTreeMap<String, TouchedFile>, path: String) -> &mut TouchedFile {
+        touched.entry(path.clone()).or_insert(TouchedFile {
+            path,
+            commits: 0,
+            uncommitted: false,
+        })
+    }
+
+    /// Old and new paths of every delta, so both sides of a rename count as touched
+    fn delta_paths(diff: &Diff) -> BTreeSet<String> {
+        diff.deltas()
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect()
+    }
+
     pub fn get_repository_path(&self) -> &Path {
         self.repo.path()
     }
 }
 
+/// Split a line into alternating runs of whitespace and non-whitespace, so the tokens
+/// join back into the original line
+fn word_tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous_is_space = None;
+    for (i, ch) in line.char_indices() {
+        let is_space = ch.is_whitespace();
+        if previous_is_space.is_some_and(|was_space| was_space != is_space) {
+            tokens.push(&line[start..i]);
+            start = i;
+        }
+        previous_is_space = Some(is_space);
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+fn push_word_span(spans: &mut Vec<WordSpan>, text: &str, change: WordChange) {
+    match spans.last_mut() {
+        Some(last) if last.change == change => last.text.push_str(text),
+        _ => spans.push(WordSpan {
+            text: text.to_string(),
+            change,
+        }),
+    }
+}
+
+/// Word-level diff of a changed line pair: the longest common subsequence of tokens is
+/// unchanged, the rest of `old` removed and the rest of `new` added
+pub fn word_diff(old: &str, new: &str) -> Vec<WordSpan> {
+    let (old_tokens, new_tokens) = (word_tokens(old), word_tokens(new));
+    let mut spans = Vec::new();
+    if old_tokens.len() > WORD_DIFF_MAX_TOKENS || new_tokens.len() > WORD_DIFF_MAX_TOKENS {
+        push_word_span(&mut spans, old, WordChange::Removed);
+        push_word_span(&mut spans, new, WordChange::Added);
+        return spans;
+    }
+
+    // lcs[i][j] = length of the common subsequence of old_tokens[i..] and new_tokens[j..]
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_tokens[i] == new_tokens[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_tokens[i] == new_tokens[j] {
+            push_word_span(&mut spans, old_tokens[i], WordChange::Unchanged);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push_word_span(&mut spans, old_tokens[i], WordChange::Removed);
+            i += 1;
+        } else {
+            push_word_span(&mut spans, new_tokens[j], WordChange::Added);
+            j += 1;
+        }
+    }
+    for token in &old_tokens[i..] {
+        push_word_span(&mut spans, token, WordChange::Removed);
+    }
+    for token in &new_tokens[j..] {
+        push_word_span(&mut spans, token, WordChange::Added);
+    }
+    spans
+}
+
+/// Word-mode version of patch lines: each run of removed lines followed by added lines
+/// is paired up line by line. None when the patch is over `WORD_DIFF_MAX_PATCH_LINES`.
+pub fn word_diff_patch(lines: &[String]) -> Option<Vec<WordDiffLine>> {
+    if lines.len() > WORD_DIFF_MAX_PATCH_LINES {
+        return None;
+    }
+
+    let is_removed = |line: &str| line.starts_with('-') && !line.starts_with("---");
+    let is_added = |line: &str| line.starts_with('+') && !line.starts_with("+++");
+
+    let mut result = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        let removed_end = i + lines[i..].iter().take_while(|line| is_removed(line)).count();
+        if removed_end == i {
+            result.push(WordDiffLine::Plain(lines[i].clone()));
+            i += 1;
+            continue;
+        }
+        let added_end =
+            removed_end + lines[removed_end..].iter().take_while(|line| is_added(line)).count();
+
+        let removed = &lines[i..removed_end];
+        let added = &lines[removed_end..added_end];
+        let pairs = removed.len().min(added.len());
+        for (old, new) in removed.iter().zip(added) {
+            result.push(WordDiffLine::Words(word_diff(&old[1..], &new[1..])));
+        }
+        for line in removed[pairs..].iter().chain(&added[pairs..]) {
+            result.push(WordDiffLine::Plain(line.clone()));
+        }
+        i = added_end;
+    }
+    Some(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,6 +549,93 @@ mod tests {
         Ok(repo)
     }
 
+    fn commit_index(repo: &Repository, index: &mut git2::Index, message: &str) {
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&parent])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_files_touched_includes_commits_and_working_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = create_test_repo_with_changes(temp_dir.path()).unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+        // A file added and then removed again is still part of the session's footprint
+        fs::write(temp_dir.path().join("scratch.txt"), "temporary").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("scratch.txt")).unwrap();
+        commit_index(&repo, &mut index, "Add scratch");
+        fs::remove_file(temp_dir.path().join("scratch.txt")).unwrap();
+        index.remove_path(Path::new("scratch.txt")).unwrap();
+        commit_index(&repo, &mut index, "Remove scratch");
+
+        let analyzer = DiffAnalyzer::new(temp_dir.path()).unwrap();
+        assert_eq!(analyzer.session_base("HEAD~2").unwrap(), base);
+
+        let touched = analyzer.files_touched(base).unwrap();
+        let summary: Vec<(&str, usize, bool)> = touched
+            .iter()
+            .map(|f| (f.path.as_str(), f.commits, f.uncommitted))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("initial.txt", 0, true),
+                ("new.txt", 0, true),
+                ("scratch.txt", 2, false),
+            ]
+        );
+
+        // No net change remains for the reverted file
+        let diff = analyzer.file_diff_since(base, "scratch.txt").unwrap();
+        assert_eq!(diff.deltas().len(), 0);
+        let diff = analyzer.file_diff_since(base, "new.txt").unwrap();
+        assert_eq!(diff.deltas().len(), 1);
+    }
+
+    #[test]
+    fn test_ahead_behind_counts_divergence_from_base() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = create_test_repo_with_changes(temp_dir.path()).unwrap();
+        let base_branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        let initial = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &initial, false).unwrap();
+
+        let analyzer = DiffAnalyzer::new(temp_dir.path()).unwrap();
+        assert_eq!(analyzer.tracking_base(None), None); // On the base branch itself
+        assert_eq!(analyzer.get_simple_changes(None).unwrap().format_ahead_behind(), None);
+
+        // One commit on the base, then two on the feature branch
+        let commit_file = |name: &str| {
+            fs::write(temp_dir.path().join(name), name).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            commit_index(&repo, &mut index, name);
+        };
+        commit_file("base.txt");
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+        commit_file("one.txt");
+        commit_file("two.txt");
+
+        let analyzer = DiffAnalyzer::new(temp_dir.path()).unwrap();
+        assert_eq!(analyzer.tracking_base(None), Some(base_branch.clone()));
+        assert_eq!(analyzer.ahead_behind(&base_branch).unwrap(), (2, 1));
+        let changes = analyzer.get_simple_changes(None).unwrap();
+        assert_eq!((changes.ahead, changes.behind), (2, 1));
+        assert_eq!(changes.format_ahead_behind(), Some("↑2 ↓1".to_string()));
+
+        // A session records the branch it was created from, which wins over the default
+        let changes = analyzer.get_simple_changes(Some("feature~1")).unwrap();
+        assert_eq!((changes.ahead, changes.behind), (1, 0));
+
+        assert!(analyzer.ahead_behind("no-such-branch").is_err());
+    }
+
     #[test]
     fn test_diff_analyzer_creation() {
         let temp_dir = TempDir::new().unwrap();
@@ -307,7 +672,7 @@ mod tests {
         create_test_repo_with_changes(temp_dir.path()).unwrap();
 
         let analyzer = DiffAnalyzer::new(temp_dir.path()).unwrap();
-        let changes = analyzer.get_simple_changes().unwrap();
+        let changes = analyzer.get_simple_changes(None).unwrap();
 
         assert!(changes.total() > 0);
     }
@@ -357,8 +722,40 @@ mod tests {
         .unwrap();
 
         let analyzer = DiffAnalyzer::new(temp_dir.path()).unwrap();
-        let changes = analyzer.get_simple_changes().unwrap();
+        let changes = analyzer.get_simple_changes(None).unwrap();
 
         assert_eq!(changes.total(), 0);
     }
+
+    #[test]
+    fn test_word_diff_marks_changed_words() {
+        let spans = word_diff("timeout = 30 # seconds", "timeout = 60 # seconds");
+        let rendered: Vec<(&str, WordChange)> =
+            spans.iter().map(|span| (span.text.as_str(), span.change)).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                ("timeout = ", WordChange::Unchanged),
+                ("30", WordChange::Removed),
+                ("60", WordChange::Added),
+                (" # seconds", WordChange::Unchanged),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_diff_patch_pairs_changed_lines() {
+        let lines: Vec<String> = ["@@ -1,3 +1,2 @@", " same", "-old one", "-gone", "+new one"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let word_lines = word_diff_patch(&lines).unwrap();
+        assert_eq!(word_lines.len(), 4);
+        assert_eq!(word_lines[1], WordDiffLine::Plain(" same".to_string()));
+        assert!(matches!(&word_lines[2], WordDiffLine::Words(spans) if spans.len() == 3));
+        assert_eq!(word_lines[3], WordDiffLine::Plain("-gone".to_string()));
+
+        let huge = vec!["+line".to_string(); WORD_DIFF_MAX_PATCH_LINES + 1];
+        assert!(word_diff_patch(&huge).is_none());
+    }
 }
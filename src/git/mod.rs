@@ -1,10 +1,12 @@
 // ABOUTME: Git integration module for workspace detection, worktree management, and git operations
 
+pub mod branch_name;
 pub mod diff_analyzer;
 pub mod operations;
 pub mod repository;
 pub mod workspace_scanner;
 pub mod worktree_manager;
 
+pub use branch_name::sanitize_branch_name;
 pub use workspace_scanner::WorkspaceScanner;
 pub use worktree_manager::{WorktreeError, WorktreeInfo, WorktreeManager};
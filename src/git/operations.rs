@@ -1,7 +1,7 @@
 // ABOUTME: Shared git operations for commit and push functionality - ensures DRY compliance
 
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::{debug, error};
 
@@ -33,6 +33,121 @@ pub fn commit_and_push_changes(worktree_path: &Path, commit_message: &str) -> Re
     commit_and_push_git2(worktree_path, commit_message)
 }
 
+/// Commit only `paths` (relative to the worktree) and push. Other changes, staged or
+/// not, stay out of the commit and remain in the working tree.
+pub fn commit_selected_and_push(
+    worktree_path: &Path,
+    commit_message: &str,
+    paths: &[PathBuf],
+) -> Result<String> {
+    debug!(
+        "Committing {} selected paths and pushing for worktree: {:?}",
+        paths.len(),
+        worktree_path
+    );
+
+    if commit_message.trim().is_empty() {
+        return Err(anyhow::anyhow!("Commit message cannot be empty"));
+    }
+    if paths.is_empty() {
+        return Err(anyhow::anyhow!("No files selected to commit"));
+    }
+
+    // Stage the selected paths, including deletions and untracked files
+    let add_output = Command::new("git")
+        .args(["add", "--all", "--"])
+        .args(paths)
+        .current_dir(worktree_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()?;
+
+    if !add_output.status.success() {
+        let stderr = String::from_utf8_lossy(&add_output.stderr);
+        return Err(anyhow::anyhow!("git add failed: {}", stderr));
+    }
+
+    // With pathspecs, git commit takes only those paths even if other changes are staged
+    let commit_output = Command::new("git")
+        .args(["commit", "--no-gpg-sign", "-m", commit_message, "--"])
+        .args(paths)
+        .current_dir(worktree_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_ASKPASS", "echo")
+        .output()?;
+
+    if !commit_output.status.success() {
+        let stderr = String::from_utf8_lossy(&commit_output.stderr);
+        return Err(anyhow::anyhow!("git commit failed: {}", stderr));
+    }
+
+    let push_output = Command::new("git")
+        .args(["push"])
+        .current_dir(worktree_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_ASKPASS", "echo")
+        .output()?;
+
+    if !push_output.status.success() {
+        let stderr = String::from_utf8_lossy(&push_output.stderr);
+        error!("git push failed - stderr: {}", stderr);
+        return Err(anyhow::anyhow!("git push failed: {}", stderr));
+    }
+
+    Ok(format!(
+        "Committed {} files and pushed: {}",
+        paths.len(),
+        commit_message
+    ))
+}
+
+/// Unified diff of a worktree, including uncommitted changes, against the point where
+/// it diverged from `base_branch`, or the repository's default base branch
+pub fn diff_against_base(worktree_path: &Path, base_branch: Option<&str>) -> Result<String> {
+    let base_branch = match base_branch {
+        Some(base) => base.to_string(),
+        None => super::worktree_manager::default_base_branch(&git2::Repository::open(
+            worktree_path,
+        )?),
+    };
+
+    let merge_base_output = Command::new("git")
+        .args(["merge-base", &base_branch, "HEAD"])
+        .current_dir(worktree_path)
+        .output()?;
+    let base_ref = if merge_base_output.status.success() {
+        String::from_utf8_lossy(&merge_base_output.stdout).trim().to_string()
+    } else {
+        debug!("No merge base with {}, diffing against it directly", base_branch);
+        base_branch
+    };
+
+    let diff_output = Command::new("git")
+        .args(["diff", &base_ref])
+        .current_dir(worktree_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()?;
+
+    if !diff_output.status.success() {
+        let stderr = String::from_utf8_lossy(&diff_output.stderr);
+        return Err(anyhow::anyhow!("git diff failed: {}", stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&diff_output.stdout).to_string())
+}
+
+/// Build the shell command line that opens `diff_file` in an external diff tool.
+/// `{file}` in the tool command is replaced with the quoted path; otherwise the
+/// diff is redirected to the tool's stdin.
+pub fn external_diff_command_line(tool_command: &str, diff_file: &Path) -> String {
+    let quoted_path = format!("'{}'", diff_file.display().to_string().replace('\'', "'\\''"));
+
+    if tool_command.contains("{file}") {
+        tool_command.replace("{file}", &quoted_path)
+    } else {
+        format!("{} < {}", tool_command, quoted_path)
+    }
+}
+
 fn commit_and_push_cli(worktree_path: &Path, commit_message: &str) -> Result<String> {
     debug!("Using CLI git for commit and push");
 
@@ -168,3 +283,92 @@ fn commit_and_push_git2(worktree_path: &Path, commit_message: &str) -> Result<St
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git").args(args).current_dir(dir).output().unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
+    #[test]
+    fn test_commit_selected_only_commits_selected_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let remote = temp_dir.path().join("remote.git");
+        let work = temp_dir.path().join("work");
+        fs::create_dir(&work).unwrap();
+        git(temp_dir.path(), &["init", "--bare", remote.to_str().unwrap()]);
+        git(&work, &["init"]);
+        git(&work, &["config", "user.name", "Test User"]);
+        git(&work, &["config", "user.email", "test@example.com"]);
+        fs::write(work.join("a.txt"), "a").unwrap();
+        fs::write(work.join("b.txt"), "b").unwrap();
+        fs::write(work.join("gone.txt"), "gone").unwrap();
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "--no-gpg-sign", "-m", "initial"]);
+        git(&work, &["remote", "add", "origin", remote.to_str().unwrap()]);
+        git(&work, &["push", "-u", "origin", "HEAD"]);
+
+        fs::write(work.join("a.txt"), "a changed").unwrap();
+        fs::write(work.join("b.txt"), "b changed").unwrap();
+        fs::write(work.join("new.txt"), "new").unwrap();
+        fs::remove_file(work.join("gone.txt")).unwrap();
+        // Already staged changes outside the selection must stay out of the commit too
+        git(&work, &["add", "b.txt"]);
+
+        let paths = [
+            PathBuf::from("a.txt"),
+            PathBuf::from("gone.txt"),
+            PathBuf::from("new.txt"),
+        ];
+        commit_selected_and_push(&work, "Selected files", &paths).unwrap();
+
+        let committed = git(&work, &["show", "--name-status", "--format=", "HEAD"]);
+        assert_eq!(committed.trim(), "M\ta.txt\nD\tgone.txt\nA\tnew.txt");
+        assert_eq!(git(&work, &["status", "--porcelain"]).trim(), "M  b.txt");
+        assert_eq!(
+            git(&work, &["rev-parse", "HEAD"]),
+            git(&work, &["rev-parse", "@{upstream}"])
+        );
+    }
+
+    #[test]
+    fn test_commit_selected_requires_paths() {
+        let result = commit_selected_and_push(Path::new("."), "Message", &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_external_diff_command_line() {
+        let diff_file = PathBuf::from("/tmp/session.diff");
+
+        assert_eq!(
+            external_diff_command_line("delta", &diff_file),
+            "delta < '/tmp/session.diff'"
+        );
+        assert_eq!(
+            external_diff_command_line("code --wait {file}", &diff_file),
+            "code --wait '/tmp/session.diff'"
+        );
+    }
+
+    #[test]
+    fn test_external_diff_command_line_quotes_path() {
+        let diff_file = PathBuf::from("/tmp/it's.diff");
+
+        assert_eq!(
+            external_diff_command_line("less", &diff_file),
+            "less < '/tmp/it'\\''s.diff'"
+        );
+    }
+}
@@ -8,8 +8,35 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
+use crate::config::ProjectConfig;
 use crate::models::Workspace;
 
+/// Marker file that opts a repository into the picker when `require_opt_in` is set
+pub const OPT_IN_MARKER: &str = ".agents-box-enabled";
+
+/// Colon-separated directories to scan instead of the configured and default paths
+pub const SEARCH_PATHS_ENV: &str = "AGENTS_BOX_SEARCH_PATHS";
+
+/// Directories in a `SEARCH_PATHS_ENV` value; empty entries are ignored and `~/` is
+/// expanded to the home directory
+pub fn parse_search_paths(value: &str) -> Vec<PathBuf> {
+    value
+        .split(':')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match (entry.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(entry),
+        })
+        .collect()
+}
+
+/// Search paths from `SEARCH_PATHS_ENV`, or None when it is unset or lists no paths
+pub fn search_paths_from_env() -> Option<Vec<PathBuf>> {
+    let paths = parse_search_paths(&std::env::var(SEARCH_PATHS_ENV).ok()?);
+    (!paths.is_empty()).then_some(paths)
+}
+
 #[derive(Debug, Clone)]
 pub struct ScanResult {
     pub workspaces: Vec<Workspace>,
@@ -20,6 +47,8 @@ pub struct WorkspaceScanner {
     search_paths: Vec<PathBuf>,
     max_depth: usize,
     ignore_patterns: Vec<String>,
+    require_opt_in: bool,
+    preserve_root_order: bool, // List results root by root instead of sorting them all by name
 }
 
 impl WorkspaceScanner {
@@ -41,9 +70,20 @@ impl WorkspaceScanner {
                 "dist".to_string(),
                 "build".to_string(),
             ],
+            require_opt_in: false,
+            preserve_root_order: false,
         }
     }
 
+    /// Scan exactly `roots`, in order, instead of the default paths. Repositories are
+    /// listed root by root (sorted by name within each root), so the first root's
+    /// repositories come first.
+    pub fn with_scan_roots(roots: Vec<PathBuf>) -> Self {
+        let mut scanner = Self::with_additional_paths(vec![]).with_search_paths(roots);
+        scanner.preserve_root_order = true;
+        scanner
+    }
+
     pub fn with_search_paths(mut self, paths: Vec<PathBuf>) -> Self {
         self.search_paths = paths;
         self
@@ -54,25 +94,60 @@ impl WorkspaceScanner {
         self
     }
 
-    pub fn scan(&self) -> Result<ScanResult> {
-        info!(
-            "Starting workspace scan with {} search paths",
-            self.search_paths.len()
-        );
+    /// Only list repositories that carry the opt-in marker or project setting
+    pub fn with_require_opt_in(mut self, require_opt_in: bool) -> Self {
+        self.require_opt_in = require_opt_in;
+        self
+    }
 
-        // Log each search path for debugging
-        for (i, path) in self.search_paths.iter().enumerate() {
-            info!("Search path {}: {}", i + 1, path.display());
+    /// Whether a repository has opted in to being listed
+    pub fn is_opted_in(path: &Path) -> bool {
+        if path.join(OPT_IN_MARKER).exists() {
+            return true;
         }
 
-        let mut workspaces = Vec::new();
+        match ProjectConfig::load_from_dir(path) {
+            Ok(config) => config.is_some_and(|config| config.opt_in),
+            Err(e) => {
+                warn!("Failed to read project config in {}: {}", path.display(), e);
+                false
+            }
+        }
+    }
+
+    /// Scan each search path once, skipping missing ones with a warning. A repository
+    /// found under several roots is listed once.
+    pub fn scan(&self) -> Result<ScanResult> {
+        let mut workspaces: Vec<Workspace> = Vec::new();
         let mut errors = Vec::new();
+        let mut scanned: Vec<PathBuf> = Vec::new();
 
         for search_path in &self.search_paths {
+            if !search_path.is_dir() {
+                let error_msg = format!("Scan root {} does not exist, skipping", search_path.display());
+                warn!("{}", error_msg);
+                errors.push(error_msg);
+                continue;
+            }
+
+            // The same directory can be listed twice, e.g. as a default and in the config
+            let root = search_path.canonicalize().unwrap_or_else(|_| search_path.clone());
+            if scanned.contains(&root) {
+                debug!("Skipping duplicate scan root {}", search_path.display());
+                continue;
+            }
+            scanned.push(root);
+
             info!("Scanning path: {}", search_path.display());
             match self.scan_directory(search_path, 0) {
                 Ok(mut found_workspaces) => {
                     info!("Found {} workspaces in {}", found_workspaces.len(), search_path.display());
+                    if self.preserve_root_order {
+                        // Order within the root; roots keep their configured order
+                        found_workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+                    }
+                    // Nested roots find the same repositories again
+                    found_workspaces.retain(|w| workspaces.iter().all(|seen| seen.path != w.path));
                     workspaces.append(&mut found_workspaces);
                 }
                 Err(e) => {
@@ -84,12 +159,20 @@ impl WorkspaceScanner {
         }
 
         // Sort workspaces by name for consistent ordering
-        workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+        if !self.preserve_root_order {
+            workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+        }
 
         info!(
-            "Workspace scan complete: found {} workspaces, {} errors",
+            "Workspace scan complete: found {} repositories in {} roots (depth {}): {}",
             workspaces.len(),
-            errors.len()
+            scanned.len(),
+            self.max_depth,
+            scanned
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
         );
 
         Ok(ScanResult { workspaces, errors })
@@ -137,6 +220,10 @@ impl WorkspaceScanner {
         // Check if current directory is a git repository
         if Self::validate_workspace(path)? {
             debug!("Found git repository at: {}", path.display());
+            if self.require_opt_in && !Self::is_opted_in(path) {
+                debug!("Skipping {} - not opted in", path.display());
+                return Ok(workspaces);
+            }
             match Self::create_workspace_from_path(path) {
                 Ok(workspace) => workspaces.push(workspace),
                 Err(e) => {
@@ -360,4 +447,104 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_scan_roots_keep_configured_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        let personal = temp_dir.path().join("personal");
+        for repo_dir in [work.join("zeta"), work.join("beta"), personal.join("alpha")] {
+            fs::create_dir_all(&repo_dir).unwrap();
+            create_test_git_repo(&repo_dir).unwrap();
+        }
+
+        let result = WorkspaceScanner::with_scan_roots(vec![
+            work.clone(),
+            temp_dir.path().join("missing"),
+            personal,
+            work,
+        ])
+        .scan()
+        .unwrap();
+
+        let names: Vec<&str> = result.workspaces.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["beta", "zeta", "alpha"]);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].contains("missing"));
+    }
+
+    #[test]
+    fn test_scan_dedupes_roots_and_limits_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let dev = temp_dir.path().join("dev");
+        for repo_dir in [
+            dev.join("app"),
+            dev.join("team").join("api"),
+            dev.join("a/b/c/deep"),
+        ] {
+            fs::create_dir_all(&repo_dir).unwrap();
+            create_test_git_repo(&repo_dir).unwrap();
+        }
+
+        let result = WorkspaceScanner::new()
+            .with_search_paths(vec![
+                dev.clone(),
+                temp_dir.path().join("missing"),
+                dev.join("team"),
+                temp_dir.path().join("dev/../dev"),
+            ])
+            .with_max_depth(2)
+            .scan()
+            .unwrap();
+
+        let names: Vec<&str> = result.workspaces.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "app"]);
+        assert_eq!(result.errors.len(), 1);
+
+        let result = WorkspaceScanner::new()
+            .with_search_paths(vec![dev])
+            .with_max_depth(4)
+            .scan()
+            .unwrap();
+        assert_eq!(result.workspaces.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_search_paths() {
+        assert_eq!(
+            parse_search_paths("/opt/projects: :/srv/code:"),
+            vec![PathBuf::from("/opt/projects"), PathBuf::from("/srv/code")]
+        );
+        assert!(parse_search_paths("").is_empty());
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(parse_search_paths("~/work"), vec![home.join("work")]);
+        }
+    }
+
+    #[test]
+    fn test_require_opt_in_filters_repositories() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["plain", "with-marker", "with-project-config"] {
+            let repo_dir = temp_dir.path().join(name);
+            fs::create_dir(&repo_dir).unwrap();
+            create_test_git_repo(&repo_dir).unwrap();
+        }
+        fs::write(temp_dir.path().join("with-marker").join(OPT_IN_MARKER), "").unwrap();
+        let config_dir = temp_dir.path().join("with-project-config").join(".agents-box");
+        fs::create_dir(&config_dir).unwrap();
+        fs::write(config_dir.join("project.toml"), "opt_in = true\n").unwrap();
+
+        let all = WorkspaceScanner::new().scan_directory(temp_dir.path(), 0).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let scanner = WorkspaceScanner::new().with_require_opt_in(true);
+        let mut names: Vec<String> = scanner
+            .scan_directory(temp_dir.path(), 0)
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["with-marker", "with-project-config"]);
+    }
 }
@@ -3,7 +3,7 @@
 #![allow(dead_code)]
 
 use anyhow::{Context, Result};
-use git2::{BranchType, Repository};
+use git2::{Branch, BranchType, Repository};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use thiserror::Error;
@@ -22,6 +22,8 @@ pub enum WorktreeError {
     NotFound(String),
     #[error("Invalid branch name: {0}")]
     InvalidBranchName(String),
+    #[error("Revision not found: {0}")]
+    RevisionNotFound(String),
     #[error("Command execution failed: {0}")]
     CommandFailed(String),
 }
@@ -34,6 +36,7 @@ pub struct WorktreeInfo {
     pub branch_name: String,
     pub source_repository: PathBuf,
     pub commit_hash: Option<String>,
+    pub detached: bool, // Review worktree checked out at a revision without a branch
 }
 
 pub struct WorktreeManager {
@@ -123,6 +126,7 @@ impl WorktreeManager {
             branch_name: branch_name.to_string(),
             source_repository: repository_path.to_path_buf(),
             commit_hash,
+            detached: false,
         };
 
         info!(
@@ -132,6 +136,99 @@ impl WorktreeManager {
         Ok(worktree_info)
     }
 
+    /// Create a review worktree checked out at `revision` (branch, tag, commit or a
+    /// fetchable remote ref such as `pull/123/head`) with a detached HEAD and no new branch.
+    /// The returned `branch_name` is the session's stable review label.
+    pub fn create_detached_worktree(
+        &self,
+        session_id: Uuid,
+        repository_path: &Path,
+        revision: &str,
+    ) -> Result<WorktreeInfo, WorktreeError> {
+        info!(
+            "Creating detached review worktree for session {} at {}",
+            session_id, revision
+        );
+
+        let repo = Repository::open(repository_path)?;
+        let commit_id = Self::resolve_revision(&repo, repository_path, revision)?;
+
+        let label = review_label(session_id);
+        let worktree_path = self.generate_worktree_path(session_id, repository_path, &label)?;
+        if worktree_path.exists() {
+            return Err(WorktreeError::AlreadyExists(
+                worktree_path.display().to_string(),
+            ));
+        }
+
+        let output = Command::new("git")
+            .current_dir(repository_path)
+            .args([
+                "worktree",
+                "add",
+                "--detach",
+                worktree_path.to_str().unwrap(),
+                &commit_id,
+            ])
+            .output()?;
+        if !output.status.success() {
+            return Err(WorktreeError::CommandFailed(format!(
+                "Failed to create review worktree: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let session_path = self.base_worktree_dir.join("by-session").join(session_id.to_string());
+        self.create_session_symlink(&worktree_path, &session_path)?;
+
+        info!(
+            "Created review worktree at {} (detached at {})",
+            worktree_path.display(),
+            &commit_id[..7.min(commit_id.len())]
+        );
+        Ok(WorktreeInfo {
+            id: session_id,
+            path: worktree_path,
+            session_path,
+            branch_name: label,
+            source_repository: repository_path.to_path_buf(),
+            commit_hash: Some(commit_id),
+            detached: true,
+        })
+    }
+
+    /// Resolve `revision` to a commit id, fetching it from origin if it isn't available locally
+    fn resolve_revision(
+        repo: &Repository,
+        repository_path: &Path,
+        revision: &str,
+    ) -> Result<String, WorktreeError> {
+        let revision = revision.trim();
+        if revision.is_empty() || revision.starts_with('-') {
+            return Err(WorktreeError::RevisionNotFound(revision.to_string()));
+        }
+
+        if let Ok(commit) = repo.revparse_single(revision).and_then(|obj| obj.peel_to_commit()) {
+            return Ok(commit.id().to_string());
+        }
+
+        debug!("Revision {} not found locally, fetching from origin", revision);
+        let fetched = Command::new("git")
+            .current_dir(repository_path)
+            .args(["fetch", "origin", revision])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if fetched {
+            if let Ok(commit) =
+                repo.revparse_single("FETCH_HEAD").and_then(|obj| obj.peel_to_commit())
+            {
+                return Ok(commit.id().to_string());
+            }
+        }
+
+        Err(WorktreeError::RevisionNotFound(revision.to_string()))
+    }
+
     pub fn remove_worktree(&self, session_id: Uuid) -> Result<(), WorktreeError> {
         info!("Removing worktree for session {}", session_id);
 
@@ -262,7 +359,12 @@ impl WorktreeManager {
 
         let repo = Repository::open(&worktree_path)?;
         let head = repo.head()?;
-        let branch_name = head.shorthand().unwrap_or("unknown").to_string();
+        let detached = repo.head_detached().unwrap_or(false);
+        let branch_name = if detached {
+            review_label(session_id)
+        } else {
+            head.shorthand().unwrap_or("unknown").to_string()
+        };
         let commit_hash = self.get_current_commit_hash(&worktree_path)?;
 
         // Find the source repository
@@ -275,6 +377,51 @@ impl WorktreeManager {
             branch_name,
             source_repository,
             commit_hash,
+            detached,
+        })
+    }
+
+    /// Whether `branch` is checked out in any worktree of `repo`, including the main
+    /// checkout. `git worktree add` refuses such a branch.
+    pub fn branch_exists(&self, repo: &Path, branch: &str) -> bool {
+        self.branch_worktree(repo, branch).is_some()
+    }
+
+    /// Path of the worktree of `repo` that has `branch` checked out
+    pub fn branch_worktree(&self, repo: &Path, branch: &str) -> Option<PathBuf> {
+        let output = Command::new("git")
+            .current_dir(repo)
+            .args(["worktree", "list", "--porcelain"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        worktree_for_branch(&String::from_utf8_lossy(&output.stdout), branch)
+    }
+
+    /// `branch` with the first free `-N` suffix: neither a local branch nor checked out
+    pub fn unique_branch_name(&self, repo: &Path, branch: &str) -> String {
+        let local = Repository::open(repo).ok();
+        (2..100)
+            .map(|n| format!("{}-{}", branch, n))
+            .find(|candidate| {
+                local
+                    .as_ref()
+                    .is_none_or(|r| r.find_branch(candidate, BranchType::Local).is_err())
+                    && !self.branch_exists(repo, candidate)
+            })
+            .unwrap_or_else(|| format!("{}-{}", branch, &Uuid::new_v4().to_string()[..8]))
+    }
+
+    /// Files that would conflict if the session's branch were merged with `base` now.
+    /// Advisory only: an empty list when nothing conflicts or the check can't be made.
+    pub fn check_merge_conflicts(&self, session_id: Uuid, base: &str) -> Vec<PathBuf> {
+        let result = self
+            .get_worktree_info(session_id)
+            .map_err(anyhow::Error::from)
+            .and_then(|info| merge_conflicts(&info.path, base));
+        result.unwrap_or_else(|e| {
+            debug!("Could not check session {} against {}: {}", session_id, base, e);
+            Vec::new()
         })
     }
 
@@ -305,20 +452,7 @@ impl WorktreeManager {
     }
 
     fn get_default_branch(&self, repo: &Repository) -> String {
-        // Try to find the default branch (main or master)
-        if repo.find_branch("main", BranchType::Local).is_ok() {
-            "main".to_string()
-        } else if repo.find_branch("master", BranchType::Local).is_ok() {
-            "master".to_string()
-        } else {
-            // If neither exists, try to get the current HEAD
-            if let Ok(head) = repo.head() {
-                if let Some(name) = head.shorthand() {
-                    return name.to_string();
-                }
-            }
-            "main".to_string() // Default fallback
-        }
+        default_base_branch(repo)
     }
 
     fn ensure_branch_exists(
@@ -333,9 +467,12 @@ impl WorktreeManager {
             return Ok(());
         }
 
-        // Get the base branch commit
-        let base_branch_ref = repo.find_branch(base_branch, BranchType::Local)?;
-        let base_commit = base_branch_ref.get().peel_to_commit()?;
+        // Get the base branch commit, falling back to remote-tracking branches
+        // (e.g. "origin/main") when the base has no local counterpart
+        let base_commit = match repo.find_branch(base_branch, BranchType::Local) {
+            Ok(branch) => branch.get().peel_to_commit()?,
+            Err(_) => repo.find_branch(base_branch, BranchType::Remote)?.get().peel_to_commit()?,
+        };
 
         // Create the new branch
         repo.branch(branch_name, &base_commit, false)?;
@@ -596,6 +733,163 @@ impl WorktreeManager {
     }
 }
 
+/// Local branch names, the checked-out branch first and the rest alphabetically
+pub fn local_branches(repo: &Repository) -> Vec<String> {
+    let current = repo
+        .head()
+        .ok()
+        .filter(git2::Reference::is_branch)
+        .and_then(|head| head.shorthand().map(str::to_string));
+
+    let mut branches: Vec<String> = repo
+        .branches(Some(BranchType::Local))
+        .map(|branches| {
+            branches
+                .filter_map(Result::ok)
+                .filter_map(|(branch, _)| branch.name().ok().flatten().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    branches.sort_by_key(|name| (Some(name) != current.as_ref(), name.clone()));
+    branches
+}
+
+/// Resolve the branch new sessions should be based on when no explicit base is chosen.
+///
+/// Prefers the remote's default branch (`origin/HEAD`), then a local `main` or
+/// `master`, and only then the currently checked-out branch. The returned name is
+/// the local branch when one exists, otherwise the remote-tracking branch
+/// (e.g. `origin/main`).
+pub fn default_base_branch(repo: &Repository) -> String {
+    if let Some(branch) = origin_head_branch(repo) {
+        if repo.find_branch(&branch, BranchType::Local).is_ok() {
+            return branch;
+        }
+        let remote_branch = format!("origin/{}", branch);
+        if repo.find_branch(&remote_branch, BranchType::Remote).is_ok() {
+            return remote_branch;
+        }
+    }
+
+    for candidate in ["main", "master"] {
+        if repo.find_branch(candidate, BranchType::Local).is_ok() {
+            return candidate.to_string();
+        }
+    }
+
+    for candidate in ["origin/main", "origin/master"] {
+        if repo.find_branch(candidate, BranchType::Remote).is_ok() {
+            return candidate.to_string();
+        }
+    }
+
+    // If nothing matches, fall back to the current HEAD
+    if let Ok(head) = repo.head() {
+        if head.is_branch() {
+            if let Some(name) = head.shorthand() {
+                return name.to_string();
+            }
+        }
+    }
+
+    "main".to_string() // Default fallback
+}
+
+/// Paths that conflict when merging the HEAD of the repository at `worktree` with `base`.
+/// The merge is a dry run in memory, so the worktree and index are untouched.
+pub fn merge_conflicts(worktree: &Path, base: &str) -> Result<Vec<PathBuf>> {
+    let repo = Repository::open(worktree)?;
+    let head = repo.head()?.peel_to_commit()?;
+    let base_commit = repo.revparse_single(base)?.peel_to_commit()?;
+    let index = repo.merge_commits(&head, &base_commit, None)?;
+    if !index.has_conflicts() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+            paths.push(PathBuf::from(String::from_utf8_lossy(&entry.path).to_string()));
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Label standing in for the branch name of a detached review worktree. Derived from the
+/// session id so it stays stable (and tmux names stay unique) whatever is checked out.
+pub fn review_label(session_id: Uuid) -> String {
+    format!("review-{}", &session_id.to_string()[..8])
+}
+
+/// Longest slug taken from a prompt, so derived branch names stay readable
+const MAX_PROMPT_SLUG_LEN: usize = 50;
+
+/// Slugify the first non-blank line of a prompt into a branch-name component.
+///
+/// "Fix the login redirect bug!" becomes "fix-the-login-redirect-bug". Long lines are
+/// cut at a word boundary. Returns None when the line has nothing usable.
+pub fn slugify_prompt(prompt: &str) -> Option<String> {
+    let line = prompt.lines().map(str::trim).find(|line| !line.is_empty())?;
+
+    let mut slug = String::new();
+    for c in line.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.len() > MAX_PROMPT_SLUG_LEN {
+        // The slug is ASCII, so byte indexing is safe
+        let cut = slug[..=MAX_PROMPT_SLUG_LEN].rfind('-').unwrap_or(MAX_PROMPT_SLUG_LEN);
+        slug.truncate(cut);
+    }
+
+    let slug = slug.trim_end_matches('-');
+    (!slug.is_empty()).then(|| slug.to_string())
+}
+
+/// Branch name derived from a session prompt: `prefix` plus the prompt slug.
+///
+/// A numeric suffix is added when a local branch of that name already exists. None when
+/// the prompt yields no slug or the result isn't a valid git branch name.
+pub fn branch_name_from_prompt(repo: &Repository, prefix: &str, prompt: &str) -> Option<String> {
+    let base = format!("{}{}", prefix, slugify_prompt(prompt)?);
+    let name = std::iter::once(base.clone())
+        .chain((2..100).map(|n| format!("{}-{}", base, n)))
+        .find(|candidate| repo.find_branch(candidate, BranchType::Local).is_err())?;
+
+    Branch::name_is_valid(&name).unwrap_or(false).then_some(name)
+}
+
+/// Worktree path for `branch` in `git worktree list --porcelain` output
+fn worktree_for_branch(porcelain: &str, branch: &str) -> Option<PathBuf> {
+    let target = format!("refs/heads/{}", branch);
+    porcelain.split("\n\n").find_map(|entry| {
+        let mut path = None;
+        let mut checked_out = false;
+        for line in entry.lines() {
+            if let Some(worktree) = line.strip_prefix("worktree ") {
+                path = Some(PathBuf::from(worktree));
+            } else if line.strip_prefix("branch ") == Some(target.as_str()) {
+                checked_out = true;
+            }
+        }
+        path.filter(|_| checked_out)
+    })
+}
+
+/// Read the branch name `refs/remotes/origin/HEAD` points at, if set
+fn origin_head_branch(repo: &Repository) -> Option<String> {
+    let reference = repo.find_reference("refs/remotes/origin/HEAD").ok()?;
+    let target = reference.symbolic_target()?;
+    target.strip_prefix("refs/remotes/origin/").map(|s| s.to_string())
+}
+
 impl Default for WorktreeManager {
     fn default() -> Self {
         Self::new().expect("Failed to create default WorktreeManager")
@@ -654,6 +948,204 @@ mod tests {
         assert!(!default_branch.is_empty());
     }
 
+    #[test]
+    fn test_local_branches_lists_current_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = create_test_repo(temp_dir.path()).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.branch("develop", &head_commit, false).unwrap();
+        repo.branch("aaa-release", &head_commit, false).unwrap();
+
+        assert_eq!(
+            local_branches(&repo),
+            vec![current, "aaa-release".to_string(), "develop".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_default_base_branch_prefers_origin_head() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = create_test_repo(temp_dir.path()).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+
+        // Simulate a clone whose remote default branch is "develop" with no local copy
+        repo.reference("refs/remotes/origin/develop", head_commit.id(), true, "test")
+            .unwrap();
+        repo.reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/develop",
+            true,
+            "test",
+        )
+        .unwrap();
+
+        assert_eq!(default_base_branch(&repo), "origin/develop");
+
+        // A local branch of the same name takes precedence over the remote one
+        repo.branch("develop", &head_commit, false).unwrap();
+        assert_eq!(default_base_branch(&repo), "develop");
+    }
+
+    #[test]
+    fn test_default_base_branch_ignores_feature_checkout() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = create_test_repo(temp_dir.path()).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+
+        repo.branch("main", &head_commit, true).unwrap();
+        repo.branch("feature/wip", &head_commit, false).unwrap();
+        repo.set_head("refs/heads/feature/wip").unwrap();
+
+        assert_eq!(default_base_branch(&repo), "main");
+    }
+
+    #[test]
+    fn test_ensure_branch_exists_from_remote_base() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = create_test_repo(temp_dir.path()).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.reference("refs/remotes/origin/trunk", head_commit.id(), true, "test").unwrap();
+        let manager = WorktreeManager::with_base_dir(temp_dir.path().join("wt")).unwrap();
+
+        manager.ensure_branch_exists(&repo, "session-branch", "origin/trunk").unwrap();
+
+        assert!(repo.find_branch("session-branch", BranchType::Local).is_ok());
+    }
+
+    #[test]
+    fn test_create_detached_worktree_at_revision() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        let repo = create_test_repo(&repo_dir).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v1.0", head_commit.as_object(), false).unwrap();
+        let branches_before = repo.branches(None).unwrap().count();
+        let manager = WorktreeManager::with_base_dir(temp_dir.path().join("wt")).unwrap();
+        let session_id = Uuid::new_v4();
+
+        let info = manager.create_detached_worktree(session_id, &repo_dir, "v1.0").unwrap();
+
+        assert!(info.detached);
+        assert_eq!(info.branch_name, review_label(session_id));
+        assert_eq!(info.commit_hash, Some(head_commit.id().to_string()));
+        assert!(Repository::open(&info.path).unwrap().head_detached().unwrap());
+        assert_eq!(repo.branches(None).unwrap().count(), branches_before);
+
+        // Rediscovered worktrees keep the review label and detached state
+        let rediscovered = manager.get_worktree_info(session_id).unwrap();
+        assert!(rediscovered.detached);
+        assert_eq!(rediscovered.branch_name, info.branch_name);
+
+        assert!(matches!(
+            manager.create_detached_worktree(Uuid::new_v4(), &repo_dir, "no-such-rev"),
+            Err(WorktreeError::RevisionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_slugify_prompt() {
+        assert_eq!(
+            slugify_prompt("\n  Fix login redirect bug!\nDetails follow").as_deref(),
+            Some("fix-login-redirect-bug")
+        );
+        assert_eq!(
+            slugify_prompt("Use @src/app.rs -- don't break `main`").as_deref(),
+            Some("use-src-app-rs-don-t-break-main")
+        );
+        assert_eq!(slugify_prompt("   \n!!!"), None);
+        assert_eq!(slugify_prompt(""), None);
+
+        let long = slugify_prompt(&"refactor ".repeat(20)).unwrap();
+        assert!(long.len() <= MAX_PROMPT_SLUG_LEN);
+        assert!(long.ends_with("refactor"));
+    }
+
+    #[test]
+    fn test_branch_name_from_prompt_is_unique() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = create_test_repo(temp_dir.path()).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+
+        let name = branch_name_from_prompt(&repo, "agents/", "Fix login redirect bug");
+        assert_eq!(name.as_deref(), Some("agents/fix-login-redirect-bug"));
+
+        repo.branch("agents/fix-login-redirect-bug", &head_commit, false).unwrap();
+        let name = branch_name_from_prompt(&repo, "agents/", "Fix login redirect bug");
+        assert_eq!(name.as_deref(), Some("agents/fix-login-redirect-bug-2"));
+
+        assert_eq!(branch_name_from_prompt(&repo, "agents/", "  "), None);
+    }
+
+    #[test]
+    fn test_branch_exists_in_worktrees() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        let repo = create_test_repo(&repo_dir).unwrap();
+        let head_commit = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("agents/taken", &head_commit, false).unwrap();
+        repo.branch("agents/taken-2", &head_commit, false).unwrap();
+
+        let manager = WorktreeManager::with_base_dir(temp_dir.path().join("worktrees")).unwrap();
+        let worktree_path = temp_dir.path().join("taken");
+        manager.create_worktree_command(&repo_dir, &worktree_path, "agents/taken").unwrap();
+
+        assert!(manager.branch_exists(&repo_dir, "agents/taken"));
+        assert_eq!(
+            manager.branch_worktree(&repo_dir, "agents/taken").map(|p| p.canonicalize().unwrap()),
+            Some(worktree_path.canonicalize().unwrap())
+        );
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        assert!(manager.branch_exists(&repo_dir, &current));
+
+        assert!(!manager.branch_exists(&repo_dir, "agents/fresh"));
+        assert!(!manager.branch_exists(&repo_dir, "agents/taken-2"));
+        assert_eq!(manager.unique_branch_name(&repo_dir, "agents/taken"), "agents/taken-3");
+    }
+
+    #[test]
+    fn test_check_merge_conflicts_reports_conflicting_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        let repo = create_test_repo(&repo_dir).unwrap();
+        let base = repo.head().unwrap().shorthand().unwrap().to_string();
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let commit_file = |repo: &Repository, file: &str, content: &str, message: &str| {
+            let workdir = repo.workdir().unwrap();
+            std::fs::write(workdir.join(file), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(file)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&parent])
+                .unwrap();
+        };
+        commit_file(&repo, "shared.txt", "original\n", "Add shared file");
+        commit_file(&repo, "other.txt", "untouched\n", "Add other file");
+
+        let manager = WorktreeManager::with_base_dir(temp_dir.path().join("worktrees")).unwrap();
+        let session_id = Uuid::new_v4();
+        let info = manager
+            .create_worktree(session_id, &repo_dir, "agents/conflict", Some(&base))
+            .unwrap();
+        assert!(manager.check_merge_conflicts(session_id, &base).is_empty());
+
+        // The same line edited differently on the branch and on the base
+        let worktree_repo = Repository::open(&info.path).unwrap();
+        commit_file(&worktree_repo, "shared.txt", "branch edit\n", "Edit on branch");
+        commit_file(&worktree_repo, "branch-only.txt", "new\n", "Branch-only file");
+        commit_file(&repo, "shared.txt", "base edit\n", "Edit on base");
+
+        assert_eq!(
+            manager.check_merge_conflicts(session_id, &base),
+            vec![PathBuf::from("shared.txt")]
+        );
+        assert!(manager.check_merge_conflicts(session_id, "no-such-branch").is_empty());
+    }
+
     #[test]
     fn test_worktree_manager_creation() {
         let temp_dir = TempDir::new().unwrap();
@@ -56,6 +56,7 @@ pub struct InteractiveSession {
     pub branch_name: String,
     pub workspace_name: String,
     pub created_at: DateTime<Utc>,
+    pub review_only: bool, // Detached review worktree (no branch, no commits expected)
 }
 
 /// Manager for Interactive mode sessions (host-based, no Docker)
@@ -86,6 +87,7 @@ impl InteractiveSessionManager {
     /// * `workspace_path` - Path to the git repository
     /// * `branch_name` - Branch name to create worktree for
     /// * `base_branch` - Optional base branch to branch from
+    /// * `model` - Claude model to start with, or None for the CLI's default
     ///
     /// # Returns
     /// * `Result<InteractiveSession>` - The created session or an error
@@ -97,6 +99,7 @@ impl InteractiveSessionManager {
         branch_name: String,
         base_branch: Option<String>,
         skip_permissions: bool,
+        model: Option<&str>,
     ) -> Result<InteractiveSession, InteractiveSessionError> {
         info!(
             "Creating Interactive session {} for branch '{}' in workspace '{}' (skip_permissions={})",
@@ -128,7 +131,7 @@ impl InteractiveSessionManager {
 
         // Step 4: Start claude CLI in tmux session
         info!("Starting claude CLI in tmux session (skip_permissions={})", skip_permissions);
-        self.start_claude_in_tmux(&tmux_session_name, skip_permissions).await?;
+        self.start_claude_in_tmux(&tmux_session_name, skip_permissions, model).await?;
 
         // Step 5: Create session record
         let session = InteractiveSession {
@@ -139,6 +142,7 @@ impl InteractiveSessionManager {
             branch_name: branch_name.clone(),
             workspace_name: workspace_name.clone(),
             created_at: Utc::now(),
+            review_only: false,
         };
 
         self.active_sessions.insert(session_id, session.clone());
@@ -147,6 +151,52 @@ impl InteractiveSessionManager {
         Ok(session)
     }
 
+    /// Create a review-only Interactive session on a detached worktree at `revision`
+    ///
+    /// No branch is created; the session is labelled `review-<id>` and commit/push
+    /// actions are disabled for it.
+    pub async fn create_review_session(
+        &mut self,
+        session_id: Uuid,
+        workspace_name: String,
+        workspace_path: PathBuf,
+        revision: String,
+        model: Option<&str>,
+    ) -> Result<InteractiveSession, InteractiveSessionError> {
+        info!(
+            "Creating review session {} at '{}' in workspace '{}'",
+            session_id, revision, workspace_name
+        );
+
+        if self.active_sessions.contains_key(&session_id) {
+            return Err(InteractiveSessionError::SessionAlreadyExists(session_id));
+        }
+
+        let worktree_info =
+            self.worktree_manager.create_detached_worktree(session_id, &workspace_path, &revision)?;
+        info!("Created review worktree at: {}", worktree_info.path.display());
+
+        let tmux_session_name = Self::generate_tmux_name(&worktree_info.branch_name);
+        self.start_tmux_session(&tmux_session_name, &worktree_info.path).await?;
+        self.start_claude_in_tmux(&tmux_session_name, false, model).await?;
+
+        let session = InteractiveSession {
+            session_id,
+            worktree_path: worktree_info.path.clone(),
+            source_repository: worktree_info.source_repository.clone(),
+            tmux_session_name,
+            branch_name: worktree_info.branch_name.clone(),
+            workspace_name,
+            created_at: Utc::now(),
+            review_only: true,
+        };
+
+        self.active_sessions.insert(session_id, session.clone());
+
+        info!("Successfully created review session {}", session_id);
+        Ok(session)
+    }
+
     /// Discover and list all active Interactive sessions by scanning tmux
     ///
     /// This enables stateless recovery - we can discover sessions created in
@@ -234,6 +284,7 @@ impl InteractiveSessionManager {
                     branch_name: worktree.branch_name,
                     workspace_name,
                     created_at: Utc::now(), // We don't persist creation time
+                    review_only: worktree.detached,
                 });
             }
         }
@@ -423,14 +474,27 @@ impl InteractiveSessionManager {
         Ok(())
     }
 
+    /// The claude command line typed into the tmux session
+    fn claude_command(skip_permissions: bool, model: Option<&str>) -> String {
+        let mut claude_cmd = "claude".to_string();
+        if skip_permissions {
+            claude_cmd.push_str(" --dangerously-skip-permissions");
+        }
+        if let Some(model) = model {
+            claude_cmd.push_str(" --model ");
+            claude_cmd.push_str(&crate::app::host_hooks::shell_quote(model));
+        }
+        claude_cmd
+    }
+
     /// Start claude CLI in the tmux session
-    async fn start_claude_in_tmux(&self, session_name: &str, skip_permissions: bool) -> Result<(), InteractiveSessionError> {
-        // Build the claude command with appropriate flags
-        let claude_cmd = if skip_permissions {
-            "claude --dangerously-skip-permissions"
-        } else {
-            "claude"
-        };
+    async fn start_claude_in_tmux(
+        &self,
+        session_name: &str,
+        skip_permissions: bool,
+        model: Option<&str>,
+    ) -> Result<(), InteractiveSessionError> {
+        let claude_cmd = Self::claude_command(skip_permissions, model);
 
         info!("Starting claude with command: {}", claude_cmd);
 
@@ -438,7 +502,7 @@ impl InteractiveSessionManager {
         let output = Command::new("tmux")
             .args([
                 "send-keys", "-t", session_name,
-                claude_cmd, "C-m"  // C-m = Enter key
+                &claude_cmd, "C-m"  // C-m = Enter key
             ])
             .output()
             .await?;
@@ -472,6 +536,7 @@ impl InteractiveSession {
         session.container_id = None; // No Docker container
         session.status = SessionStatus::Running; // If tmux session exists, it's running
         session.created_at = self.created_at;
+        session.review_only = self.review_only;
 
         session
     }
@@ -499,6 +564,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_claude_command_passes_model() {
+        assert_eq!(InteractiveSessionManager::claude_command(false, None), "claude");
+        assert_eq!(
+            InteractiveSessionManager::claude_command(true, Some("claude-opus-4")),
+            "claude --dangerously-skip-permissions --model 'claude-opus-4'"
+        );
+    }
+
     #[test]
     fn test_session_manager_creation() {
         let manager = InteractiveSessionManager::new();
@@ -3,7 +3,7 @@
 #![allow(missing_docs)]
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
@@ -11,7 +11,8 @@ use crossterm::{
 };
 use ratatui::{Terminal, backend::Backend, prelude::*};
 use std::{
-    io::{self, IsTerminal},
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
     time::{Duration, Instant},
 };
 
@@ -28,7 +29,7 @@ mod tmux;
 mod widgets;
 
 use app::{App, EventHandler};
-use components::LayoutComponent;
+use components::{LayoutComponent, LogsViewerComponent};
 
 /// Terminal cleanup utility to ensure proper restoration
 fn cleanup_terminal() {
@@ -62,7 +63,75 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Set up Claude authentication for containers
-    Auth,
+    Auth {
+        /// Authenticate a named profile and make it the active one
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Revoke the OAuth refresh token and remove stored credentials (a backup is kept)
+    Logout {
+        /// Log out of this profile instead of the active one
+        #[arg(long)]
+        profile: Option<String>,
+        /// Only remove the local credential files, without revoking the token
+        #[arg(long)]
+        local_only: bool,
+    },
+    /// Build the agents-dev container image used for sessions and authentication
+    BuildImage {
+        /// Rebuild even if the image already exists
+        #[arg(long)]
+        rebuild: bool,
+    },
+    /// Create a session without the TUI and print its id
+    Create {
+        /// Repository to create the session's worktree from
+        #[arg(long)]
+        repo: PathBuf,
+        /// Branch for the worktree (generated when omitted)
+        #[arg(long)]
+        branch: Option<String>,
+        /// Session mode
+        #[arg(long, value_enum, default_value_t = CreateMode::Interactive)]
+        mode: CreateMode,
+        /// Prompt for a boss mode session
+        #[arg(long, conflicts_with = "prompt_file")]
+        prompt: Option<String>,
+        /// Read the boss mode prompt from a file
+        #[arg(long)]
+        prompt_file: Option<PathBuf>,
+        /// Run the agent with --dangerously-skip-permissions
+        #[arg(long)]
+        skip_permissions: bool,
+        #[command(flatten)]
+        container: ContainerArgs,
+    },
+}
+
+/// Container settings, overriding the config file defaults
+#[derive(Args)]
+pub struct ContainerArgs {
+    /// Image for the session container, pulled if it isn't present locally
+    #[arg(long)]
+    image: Option<String>,
+    /// Extra host directory to mount (repeatable)
+    #[arg(long = "volume", value_name = "HOST:CONTAINER[:ro]")]
+    volumes: Vec<String>,
+    /// Container port to publish on the host (repeatable)
+    #[arg(long = "port", value_name = "HOST:CONTAINER")]
+    ports: Vec<String>,
+    /// Memory limit for the session container in MB
+    #[arg(long)]
+    memory_limit_mb: Option<u64>,
+    /// Relative CPU weight for the session container (Docker's default is 1024)
+    #[arg(long)]
+    cpu_shares: Option<u64>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CreateMode {
+    Interactive,
+    Boss,
 }
 
 #[tokio::main]
@@ -73,7 +142,23 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     let result = match cli.command {
-        Some(Commands::Auth) => run_auth_setup().await,
+        Some(Commands::Auth { profile }) => run_auth_setup(profile).await,
+        Some(Commands::Logout {
+            profile,
+            local_only,
+        }) => run_logout(profile, local_only).await,
+        Some(Commands::BuildImage { rebuild }) => run_build_image(rebuild).await,
+        Some(Commands::Create {
+            repo,
+            branch,
+            mode,
+            prompt,
+            prompt_file,
+            skip_permissions,
+            container,
+        }) => {
+            run_create(repo, branch, mode, prompt, prompt_file, skip_permissions, container).await
+        }
         None => {
             // No command specified, run TUI
             let mut app = App::new();
@@ -92,15 +177,173 @@ async fn main() -> Result<()> {
     result
 }
 
-async fn run_auth_setup() -> Result<()> {
+/// Progress sender that prints image build output to the terminal
+fn print_build_progress() -> tokio::sync::mpsc::Sender<docker::AgentsDevProgress> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+    tokio::spawn(async move {
+        while let Some(progress) = rx.recv().await {
+            if let docker::AgentsDevProgress::BuildingImage(line) = progress {
+                println!("   {}", line.trim_end());
+            }
+        }
+    });
+    tx
+}
+
+async fn run_build_image(rebuild: bool) -> Result<()> {
+    let manager = docker::agents_dev::AgentsDevManager::new(docker::AgentsDevConfig {
+        force_rebuild: rebuild,
+        ..Default::default()
+    })
+    .await?;
+
+    println!("🏗️  Preparing agents-dev image (a build can take several minutes)...");
+    manager.ensure_image(true, Some(print_build_progress())).await?;
+    println!("✅ Image ready");
+    Ok(())
+}
+
+async fn run_create(
+    repo: PathBuf,
+    branch: Option<String>,
+    mode: CreateMode,
+    prompt: Option<String>,
+    prompt_file: Option<PathBuf>,
+    skip_permissions: bool,
+    container: ContainerArgs,
+) -> Result<()> {
+    use app::session_images::{SESSION_IMAGES_FILE, SessionImagesStore};
+    use app::session_ports::{SESSION_PORTS_FILE, SessionPortsStore};
+    use app::session_volumes::{SESSION_VOLUMES_FILE, SessionVolumesStore};
+    use docker::session_lifecycle::{SessionLifecycleManager, SessionRequest};
+
+    // Same gate as the TUI, which sends first-time users to the auth screen
+    if app::AppState::is_first_time_setup() {
+        return Err(anyhow::anyhow!(
+            "Authentication is not set up. Run `agents-box auth` (or set ANTHROPIC_API_KEY) first."
+        ));
+    }
+
+    let prompt = match prompt_file {
+        Some(path) => Some(std::fs::read_to_string(&path).map_err(|e| {
+            anyhow::anyhow!("Failed to read prompt file {}: {}", path.display(), e)
+        })?),
+        None => prompt,
+    };
+    let (mode, boss_prompt) = match (mode, prompt) {
+        (CreateMode::Boss, Some(prompt)) if !prompt.trim().is_empty() => {
+            (models::SessionMode::Boss, Some(prompt))
+        }
+        (CreateMode::Boss, _) => {
+            return Err(anyhow::anyhow!("Boss mode needs --prompt or --prompt-file"));
+        }
+        (CreateMode::Interactive, Some(_)) => {
+            return Err(anyhow::anyhow!("A prompt is only used with --mode boss"));
+        }
+        (CreateMode::Interactive, None) => (models::SessionMode::Interactive, None),
+    };
+
+    let repo = repo
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("Repository {} not found: {}", repo.display(), e))?;
+    git2::Repository::open(&repo)
+        .map_err(|e| anyhow::anyhow!("{} is not a git repository: {}", repo.display(), e))?;
+    docker::check_docker_daemon().map_err(|reason| anyhow::anyhow!(reason))?;
+
+    let workspace_defaults = config::AppConfig::load()?.workspace_defaults;
+    let branch_name = match branch {
+        Some(branch) => branch,
+        None => {
+            let uuid_str = uuid::Uuid::new_v4().to_string();
+            format!("{}{}", workspace_defaults.branch_prefix, &uuid_str[..8])
+        }
+    };
+    let volumes = container
+        .volumes
+        .iter()
+        .map(|spec| app::session_volumes::parse_volume_spec(spec))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Invalid --volume: {}", e))?;
+    let ports = container
+        .ports
+        .iter()
+        .map(|spec| app::session_ports::parse_port_spec(spec))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Invalid --port: {}", e))?;
+    let session_id = uuid::Uuid::new_v4();
+    let request = SessionRequest {
+        session_id,
+        workspace_name: repo.file_name().and_then(|n| n.to_str()).unwrap_or("unknown").to_string(),
+        workspace_path: repo.clone(),
+        branch_name: branch_name.clone(),
+        base_branch: None,
+        container_config: None,
+        skip_permissions,
+        mode,
+        boss_prompt,
+        env_vars: Vec::new(),
+        volumes: volumes.clone(),
+        ports: ports.clone(),
+        memory_limit_mb: container.memory_limit_mb,
+        cpu_shares: container.cpu_shares,
+        agent_type: models::AgentType::default(),
+        model: workspace_defaults.model,
+        image: container.image.clone(),
+    };
+
+    println!("🚀 Creating session on branch {} in {}", branch_name, repo.display());
+    let (log_sender, mut log_receiver) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let printer = tokio::spawn(async move {
+        while let Some(line) = log_receiver.recv().await {
+            println!("   {}", line.trim_end());
+        }
+    });
+
+    let mut manager = SessionLifecycleManager::new().await?;
+    let result = manager.create_session_with_logs(request, Some(log_sender)).await;
+    // Let the last build lines print before the outcome
+    let _ = tokio::time::timeout(Duration::from_secs(1), printer).await;
+
+    result.map_err(|e| anyhow::anyhow!("Session creation failed: {}", e))?;
+
+    // Saved where the TUI looks for them, so restarting the session keeps them
+    if let Some(image) = container.image {
+        if let Some(store) = SessionImagesStore::in_home_dir(SESSION_IMAGES_FILE) {
+            if let Err(e) = store.insert(session_id, image) {
+                eprintln!("⚠️  Could not save the image for restarts: {}", e);
+            }
+        }
+    }
+    let volumes_store = SessionVolumesStore::in_home_dir(SESSION_VOLUMES_FILE);
+    if let Some(store) = volumes_store.filter(|_| !volumes.is_empty()) {
+        if let Err(e) = store.insert(session_id, volumes) {
+            eprintln!("⚠️  Could not save the mounts for restarts: {}", e);
+        }
+    }
+    let ports_store = SessionPortsStore::in_home_dir(SESSION_PORTS_FILE);
+    if let Some(store) = ports_store.filter(|_| !ports.is_empty()) {
+        if let Err(e) = store.insert(session_id, ports) {
+            eprintln!("⚠️  Could not save the ports for restarts: {}", e);
+        }
+    }
+
+    println!("{}", session_id);
+    Ok(())
+}
+
+async fn run_auth_setup(profile: Option<String>) -> Result<()> {
     println!("🔐 Setting up Claude authentication for agents-in-a-box...");
     println!();
 
-    // Create the auth directory structure
-    let home_dir =
-        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-    let claude_box_dir = home_dir.join(".agents-in-a-box");
-    let auth_dir = claude_box_dir.join("auth");
+    // Create the auth directory structure for the requested (or active) profile
+    let profiles = config::AuthProfiles::in_home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    if let Some(name) = &profile {
+        profiles.set_active(name).map_err(|e| anyhow::anyhow!(e))?;
+    }
+    let profile = profiles.active();
+    let auth_dir = profiles.profile_dir(&profile);
+    println!("👤 Profile: {}", profile);
 
     std::fs::create_dir_all(&auth_dir)
         .map_err(|e| anyhow::anyhow!("Failed to create auth directory: {}", e))?;
@@ -120,30 +363,27 @@ async fn run_auth_setup() -> Result<()> {
     println!("   Auth directory: {}", auth_dir.display());
 
     // Check if Docker is available
-    let docker_version =
-        std::process::Command::new("docker").args(["--version"]).output().map_err(|e| {
-            anyhow::anyhow!(
-                "Docker not found: {}. Please install Docker and try again.",
-                e
-            )
-        })?;
-
-    if !docker_version.status.success() {
-        return Err(anyhow::anyhow!(
-            "Docker is not running. Please start Docker and try again."
-        ));
-    }
-
-    println!("🏗️  Building authentication container (agents-dev)...");
-    let build_status = std::process::Command::new("docker")
-        .args(["build", "-t", "agents-box:agents-dev", "docker/agents-dev"])
-        .status()
-        .map_err(|e| anyhow::anyhow!("Failed to build container: {}", e))?;
-
-    if !build_status.success() {
-        return Err(anyhow::anyhow!(
-            "Container build failed. Please check Docker and try again."
-        ));
+    docker::check_docker_daemon().map_err(|reason| anyhow::anyhow!(reason))?;
+
+    let policy = config::AppConfig::load()
+        .map(|config| config.docker.missing_image_policy)
+        .unwrap_or_default();
+    match docker::ensure_agents_dev_image(policy, false, Some(print_build_progress())).await {
+        Ok(()) => {}
+        Err(docker::MissingImageError::NeedsConfirmation(image)) => {
+            print!("🏗️  Image {} is missing. Build it now? This can take several minutes [y/N] ", image);
+            io::Write::flush(&mut io::stdout())?;
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !answer.trim().eq_ignore_ascii_case("y") {
+                return Err(anyhow::anyhow!(
+                    "Image {} is required for authentication. Build it with `agents-box build-image`.",
+                    image
+                ));
+            }
+            docker::ensure_agents_dev_image(policy, true, Some(print_build_progress())).await?;
+        }
+        Err(e) => return Err(e.into()),
     }
 
     // Execute the auth container
@@ -169,7 +409,7 @@ async fn run_auth_setup() -> Result<()> {
             "claude-user",
             "--entrypoint",
             "bash",
-            "agents-box:agents-dev",
+            docker::agents_dev::AGENTS_DEV_IMAGE,
             "-c",
             "/app/scripts/auth-setup.sh",
         ])
@@ -193,6 +433,46 @@ async fn run_auth_setup() -> Result<()> {
     Ok(())
 }
 
+async fn run_logout(profile: Option<String>, local_only: bool) -> Result<()> {
+    let profiles = config::AuthProfiles::in_home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    let profile = match profile {
+        Some(name) => {
+            config::AuthProfiles::validate_name(&name).map_err(|e| anyhow::anyhow!(e))?;
+            name
+        }
+        None => profiles.active(),
+    };
+
+    // Same guard as re-authentication: running sessions use the current credentials
+    if docker::check_docker_daemon().is_ok() {
+        let workspaces = app::SessionLoader::new().await?.load_active_sessions().await?;
+        let running: usize = workspaces.iter().map(|w| w.running_sessions().len()).sum();
+        if running > 0 {
+            return Err(anyhow::anyhow!(
+                "Cannot log out with {} running sessions. Stop them before logging out.",
+                running
+            ));
+        }
+    }
+
+    println!("🔒 Logging out of auth profile '{}'...", profile);
+    let outcome = app::logout::logout(&profiles.profile_dir(&profile), !local_only).await?;
+    for backup in &outcome.backups {
+        println!("   Backed up to {}", backup.display());
+    }
+    match &outcome.revocation {
+        Some(Err(e)) => {
+            println!("❌ {}", outcome.summary());
+            Err(anyhow::anyhow!("Refresh token was not revoked: {}", e))
+        }
+        _ => {
+            println!("✅ {}", outcome.summary());
+            Ok(())
+        }
+    }
+}
+
 async fn run_tui(app: &mut App, layout: &mut LayoutComponent) -> Result<()> {
     // Check if we have a proper TTY
     if !IsTerminal::is_terminal(&io::stdout()) {
@@ -225,6 +505,13 @@ async fn run_tui(app: &mut App, layout: &mut LayoutComponent) -> Result<()> {
     // Ensure terminal cleanup happens even if there's an error
     let result = run_tui_loop(app, layout, &mut terminal).await;
 
+    // Stop sessions while the UI is still up, so a slow docker daemon shows why exit waits
+    if app.state.stop_sessions_on_exit && !app.state.running_sessions().is_empty() {
+        app.state.add_info_notification("Stopping running sessions...".to_string());
+        let _ = terminal.draw(|frame| layout.render(frame, &app.state));
+    }
+    let (left_running, protected) = app.stop_sessions_on_exit().await;
+
     // Always clean up terminal using unified cleanup
     if let Err(e) = cleanup_terminal_with_instance(&mut terminal) {
         tracing::error!("Failed to cleanup terminal: {}", e);
@@ -232,6 +519,16 @@ async fn run_tui(app: &mut App, layout: &mut LayoutComponent) -> Result<()> {
         cleanup_terminal();
     }
 
+    if !left_running.is_empty() {
+        eprintln!(
+            "⚠️  Could not stop these sessions; their containers may still be running: {}",
+            left_running.join(", ")
+        );
+    }
+    if !protected.is_empty() {
+        eprintln!("🔒 Skipped protected sessions; still running: {}", protected.join(", "));
+    }
+
     result
 }
 
@@ -240,7 +537,6 @@ async fn run_tui_loop(
     layout: &mut LayoutComponent,
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
 ) -> Result<()> {
-    let tick_rate = Duration::from_millis(250);
     let mut last_tick = Instant::now();
 
     loop {
@@ -248,21 +544,32 @@ async fn run_tui_loop(
             layout.render(frame, &app.state);
         })?;
 
+        // The bell is written straight to the terminal, outside the drawn frame
+        if app.state.alerter.take_bell() {
+            terminal.backend_mut().write_all(b"\x07")?;
+            Write::flush(terminal.backend_mut())?;
+        }
+
+        // Slows down when idle; poll still returns as soon as input arrives
+        let tick_rate = app.state.tick_rate(Instant::now());
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if crossterm::event::poll(timeout)? {
+            app.state.mark_activity();
             match event::read()? {
                 Event::Key(key_event) => {
-                    // Intercept keys when tmux preview is in scroll mode
-                    use crossterm::event::KeyCode;
+                    // Intercept keys when tmux preview is in scroll mode, unless a
+                    // dialog or popup is open on top of it
+                    use crossterm::event::{KeyCode, KeyModifiers};
+                    let overlay_open = app.state.is_overlay_open();
                     let preview = layout.tmux_preview_mut();
-                    if preview.is_scroll_mode() {
+                    if preview.is_scroll_mode() && !overlay_open {
                         match key_event.code {
                             KeyCode::Esc => {
                                 preview.exit_scroll_mode();
-                                continue; // Don't process ESC as Quit
+                                continue; // Leaving scroll mode is this Escape's only effect
                             }
                             KeyCode::Up | KeyCode::Char('k') => {
                                 preview.scroll_up();
@@ -284,6 +591,61 @@ async fn run_tui_loop(
                         }
                     }
 
+                    // Typing a live logs filter: the pattern is applied as it changes
+                    let live_logs = layout.live_logs_mut();
+                    let on_session_list = app.state.current_view == app::state::View::SessionList;
+                    if live_logs.is_editing_filter() && on_session_list && !overlay_open {
+                        match key_event.code {
+                            KeyCode::Esc => {
+                                live_logs.clear_filter();
+                                continue;
+                            }
+                            KeyCode::Enter => {
+                                live_logs.confirm_filter_input();
+                                continue;
+                            }
+                            KeyCode::Backspace => {
+                                live_logs.filter_input_backspace();
+                                continue;
+                            }
+                            KeyCode::Char(ch)
+                                if !key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                            {
+                                live_logs.filter_input_char(ch);
+                                continue;
+                            }
+                            _ => {} // Let other keys pass through to event handler
+                        }
+                    }
+
+                    // Typing a logs viewer search term
+                    let logs_viewer = layout.logs_viewer_mut();
+                    let on_logs_view = app.state.current_view == app::state::View::Logs;
+                    if logs_viewer.is_editing_search() && on_logs_view && !overlay_open {
+                        match key_event.code {
+                            KeyCode::Esc => {
+                                logs_viewer.cancel_search_input();
+                                continue;
+                            }
+                            KeyCode::Enter => {
+                                let lines = LogsViewerComponent::session_lines(&app.state);
+                                logs_viewer.confirm_search(&lines);
+                                continue;
+                            }
+                            KeyCode::Backspace => {
+                                logs_viewer.search_input_backspace();
+                                continue;
+                            }
+                            KeyCode::Char(ch)
+                                if !key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                            {
+                                logs_viewer.search_input_char(ch);
+                                continue;
+                            }
+                            _ => {} // Let other keys pass through to event handler
+                        }
+                    }
+
                     if let Some(app_event) =
                         EventHandler::handle_key_event(key_event, &mut app.state)
                     {
@@ -294,21 +656,117 @@ async fn run_tui_loop(
                                 layout.live_logs_mut().scroll_up();
                             }
                             AppEvent::ScrollLogsDown => {
-                                let total_logs =
-                                    app.state.live_logs.values().map(|v| v.len()).sum::<usize>();
-                                layout.live_logs_mut().scroll_down(total_logs);
+                                let live_logs = layout.live_logs_mut();
+                                let total_lines = live_logs.filtered_line_count(&app.state);
+                                live_logs.scroll_down(total_lines);
                             }
                             AppEvent::ScrollLogsToTop => {
                                 layout.live_logs_mut().scroll_to_top();
                             }
                             AppEvent::ScrollLogsToBottom => {
-                                let total_logs =
-                                    app.state.live_logs.values().map(|v| v.len()).sum::<usize>();
-                                layout.live_logs_mut().scroll_to_bottom(total_logs);
+                                let live_logs = layout.live_logs_mut();
+                                let total_lines = live_logs.filtered_line_count(&app.state);
+                                live_logs.scroll_to_bottom(total_lines);
+                            }
+                            AppEvent::StartLogFilter => {
+                                layout.live_logs_mut().start_filter_input();
+                            }
+                            AppEvent::ShowLogsViewer => {
+                                layout.logs_viewer_mut().reset();
+                                EventHandler::process_event(app_event, &mut app.state);
+                            }
+                            AppEvent::LogsViewerStartSearch => {
+                                layout.logs_viewer_mut().start_search();
+                            }
+                            AppEvent::LogsViewerNextMatch | AppEvent::LogsViewerPreviousMatch => {
+                                let logs_viewer = layout.logs_viewer_mut();
+                                let found = if matches!(app_event, AppEvent::LogsViewerNextMatch) {
+                                    logs_viewer.next_match()
+                                } else {
+                                    logs_viewer.previous_match()
+                                };
+                                if !found {
+                                    app.state.add_info_notification("No matches".to_string());
+                                }
+                            }
+                            AppEvent::LogsViewerToggleCase => {
+                                let lines = LogsViewerComponent::session_lines(&app.state);
+                                layout.logs_viewer_mut().toggle_case_sensitive(&lines);
+                            }
+                            AppEvent::LogsViewerScrollUp => {
+                                layout.logs_viewer_mut().scroll_up();
+                            }
+                            AppEvent::LogsViewerScrollDown => {
+                                let lines = LogsViewerComponent::session_lines(&app.state);
+                                layout.logs_viewer_mut().scroll_down(&lines);
+                            }
+                            AppEvent::LogsViewerToggleWrap => {
+                                layout.logs_viewer_mut().toggle_wrap();
+                            }
+                            AppEvent::LogsViewerScrollLeft => {
+                                layout.logs_viewer_mut().scroll_left();
+                            }
+                            AppEvent::LogsViewerScrollRight => {
+                                layout.logs_viewer_mut().scroll_right();
+                            }
+                            AppEvent::LogsViewerToggleBookmark => {
+                                let logs_viewer = layout.logs_viewer_mut();
+                                if let Some(added) = logs_viewer.toggle_bookmark(&app.state) {
+                                    let message = if added {
+                                        "🔖 Bookmarked log line"
+                                    } else {
+                                        "Bookmark removed"
+                                    };
+                                    app.state.add_info_notification(message.to_string());
+                                }
+                            }
+                            AppEvent::LogsViewerNextBookmark
+                            | AppEvent::LogsViewerPreviousBookmark => {
+                                let logs_viewer = layout.logs_viewer_mut();
+                                let found = match app_event {
+                                    AppEvent::LogsViewerNextBookmark => {
+                                        logs_viewer.next_bookmark(&app.state)
+                                    }
+                                    _ => logs_viewer.previous_bookmark(&app.state),
+                                };
+                                if !found {
+                                    let message = "No bookmarks in this log".to_string();
+                                    app.state.add_info_notification(message);
+                                }
                             }
                             AppEvent::ToggleAutoScroll => {
                                 layout.live_logs_mut().toggle_auto_scroll();
                             }
+                            AppEvent::ToggleLogWrap => {
+                                layout.live_logs_mut().toggle_wrap();
+                            }
+                            AppEvent::ScrollLogsLeft => {
+                                layout.live_logs_mut().scroll_left();
+                            }
+                            AppEvent::ScrollLogsRight => {
+                                layout.live_logs_mut().scroll_right();
+                            }
+                            AppEvent::ToggleLogBookmark => {
+                                match layout.live_logs_mut().toggle_bookmark(&app.state) {
+                                    Some(true) => app.state.add_info_notification("🔖 Bookmarked log line".to_string()),
+                                    Some(false) => app.state.add_info_notification("Bookmark removed".to_string()),
+                                    None => {}
+                                }
+                            }
+                            AppEvent::NextLogBookmark => {
+                                if !layout.live_logs_mut().next_bookmark(&app.state) {
+                                    app.state.add_info_notification("No bookmarks in this log".to_string());
+                                }
+                            }
+                            AppEvent::PreviousLogBookmark => {
+                                if !layout.live_logs_mut().previous_bookmark(&app.state) {
+                                    app.state.add_info_notification("No bookmarks in this log".to_string());
+                                }
+                            }
+                            AppEvent::CopyToolCommand => {
+                                let command = layout.live_logs_mut().focused_tool_command(&app.state);
+                                app.state.copy_tool_command(command);
+                            }
                             // Tmux preview scroll events
                             AppEvent::ScrollPreviewUp => {
                                 let preview = layout.tmux_preview_mut();
@@ -327,6 +785,11 @@ async fn run_tui_loop(
                             AppEvent::EnterScrollMode => {
                                 layout.tmux_preview_mut().enter_scroll_mode();
                             }
+                            AppEvent::NewSessionCursorUp | AppEvent::NewSessionCursorDown => {
+                                // Up/down follow the prompt's wrapped rows at its rendered width
+                                app.state.prompt_wrap_width = layout.prompt_wrap_width();
+                                EventHandler::process_event(app_event, &mut app.state);
+                            }
                             AppEvent::ExitScrollMode => {
                                 layout.tmux_preview_mut().exit_scroll_mode();
                             }
@@ -362,16 +825,22 @@ async fn run_tui_loop(
                 Event::Mouse(mouse_event) => {
                     use crossterm::event::{MouseEventKind, MouseButton};
                     use crate::app::events::AppEvent;
+                    use crate::components::layout::ClickTarget;
 
                     match mouse_event.kind {
                         MouseEventKind::Down(MouseButton::Left) => {
-                            // Convert coordinates to pane focus
+                            // Focus the clicked pane and select the session row under it
                             let (col, row) = (mouse_event.column, mouse_event.row);
-                            if let Some(app_event) = EventHandler::handle_mouse_event(
-                                AppEvent::MouseClick { x: col, y: row },
-                                &mut app.state
-                            ) {
-                                EventHandler::process_event(app_event, &mut app.state);
+                            if let Some(target) = layout.click_target(col, row, &app.state) {
+                                if target == ClickTarget::NewLogLines {
+                                    let live_logs = layout.live_logs_mut();
+                                    let total_lines = live_logs.filtered_line_count(&app.state);
+                                    live_logs.scroll_to_bottom(total_lines);
+                                }
+                                EventHandler::process_event(
+                                    AppEvent::MouseClick(target),
+                                    &mut app.state,
+                                );
                             }
                         }
                         MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
@@ -398,15 +867,41 @@ async fn run_tui_loop(
                                                 git_state.scroll_markdown_up_by(SCROLL_LINES);
                                             }
                                         }
+                                        crate::components::git_view::GitTab::Commits => {
+                                            for _ in 0..SCROLL_LINES {
+                                                if is_down {
+                                                    git_state.scroll_commits_down();
+                                                } else {
+                                                    git_state.scroll_commits_up();
+                                                }
+                                            }
+                                        }
                                         _ => {}
                                     }
                                 }
+                            } else if let Some(ClickTarget::SessionList(_)) =
+                                layout.click_target(mouse_event.column, mouse_event.row, &app.state)
+                            {
+                                // Over the session list the wheel moves the selection
+                                if is_down {
+                                    app.state.next_session();
+                                } else {
+                                    app.state.previous_session();
+                                }
+                            } else if layout.tmux_preview_mut().is_scroll_mode() {
+                                for _ in 0..SCROLL_LINES {
+                                    if is_down {
+                                        layout.tmux_preview_mut().scroll_down();
+                                    } else {
+                                        layout.tmux_preview_mut().scroll_up();
+                                    }
+                                }
                             } else {
                                 // Default: scroll live logs
                                 if is_down {
-                                    let total_logs =
-                                        app.state.live_logs.values().map(|v| v.len()).sum::<usize>();
-                                    layout.live_logs_mut().scroll_down(total_logs);
+                                    let live_logs = layout.live_logs_mut();
+                                    let total_lines = live_logs.filtered_line_count(&app.state);
+                                    live_logs.scroll_down(total_lines);
                                 } else {
                                     layout.live_logs_mut().scroll_up();
                                 }
@@ -433,7 +928,7 @@ async fn run_tui_loop(
                         _ => {}
                     }
                 }
-                Event::Resize(_, _) => {}
+                Event::Resize(_, _) => {} // Next draw re-lays out (and re-checks minimum size)
                 Event::FocusGained => {}
                 Event::FocusLost => {}
                 Event::Paste(_) => {}
@@ -445,7 +940,7 @@ async fn run_tui_loop(
             EventHandler::process_event(pending_event, &mut app.state);
         }
 
-        if last_tick.elapsed() >= tick_rate {
+        if last_tick.elapsed() >= app.state.tick_rate(Instant::now()) {
             // Handle tmux-related async actions BEFORE app.tick() to get terminal access
             // IMPORTANT: Use match instead of multiple if-let with .take() to avoid dropping unmatched actions
             if let Some(action) = app.state.pending_async_action.take() {
@@ -519,27 +1014,27 @@ async fn run_tui_loop(
                         debug!("[ACTION] Looking for session in {} workspaces", app.state.workspaces.len());
 
                         // Get session to find tmux session name
-                        let tmux_session_name = if let Some(session) = app.state.workspaces
-                            .iter()
-                            .flat_map(|w| &w.sessions)
-                            .find(|s| s.id == session_id)
-                        {
-                            debug!("[ACTION] Found session: name='{}', status={:?}, tmux_name={:?}",
-                                session.name, session.status, session.tmux_session_name);
-                            if let Some(ref name) = session.tmux_session_name {
+                        let tmux_session_name = match app.state.tmux_session_name_for(session_id) {
+                            Ok(name) => {
                                 info!("[ACTION] Using tmux session name: {}", name);
-                                Some(name.clone())
-                            } else {
-                                error!("[ACTION] No tmux session name found for session {} (name={})", session_id, session.name);
-                                app.state.add_error_notification(format!("Session '{}' has no tmux session", session.name));
+                                Some(name)
+                            }
+                            Err(e) => {
+                                error!("[ACTION] Cannot attach to session {}: {}", session_id, e);
+                                app.state.add_error_notification(e);
                                 app.state.ui_needs_refresh = true;
                                 None
                             }
-                        } else {
-                            error!("[ACTION] Session {} not found in workspaces", session_id);
-                            app.state.add_error_notification("Session not found".to_string());
-                            app.state.ui_needs_refresh = true;
-                            None
+                        };
+
+                        // A tmux session that has exited can't be attached; offer a restart
+                        // instead of suspending the TUI only to fail
+                        let tmux_session_name = match tmux_session_name {
+                            Some(name) if !app::attach_handler::tmux_session_exists(&name).await => {
+                                app.state.offer_restart_for_dead_tmux(session_id, &name);
+                                None
+                            }
+                            other => other,
                         };
 
                         if let Some(tmux_session_name) = tmux_session_name {
@@ -581,6 +1076,56 @@ async fn run_tui_loop(
                         }
                     }
 
+                    AsyncAction::OpenExternalDiff(session_id) => {
+                        use crate::app::AttachHandler;
+                        use crate::git::operations::{diff_against_base, external_diff_command_line};
+
+                        let session = app.state.workspaces
+                            .iter()
+                            .flat_map(|w| &w.sessions)
+                            .find(|s| s.id == session_id)
+                            .map(|s| {
+                                (std::path::PathBuf::from(&s.workspace_path), s.base_branch.clone())
+                            });
+
+                        if let (Some((worktree_path, base_branch)), Some(tool_command)) =
+                            (session, app.state.diff_tool_command.clone())
+                        {
+                            info!("[ACTION] Opening external diff for session {} with '{}'", session_id, tool_command);
+                            match diff_against_base(&worktree_path, base_branch.as_deref()) {
+                                Ok(diff) if diff.trim().is_empty() => {
+                                    app.state.add_info_notification("No changes against the base branch".to_string());
+                                }
+                                Ok(diff) => {
+                                    let diff_file = std::env::temp_dir()
+                                        .join(format!("agents-box-{}.diff", session_id));
+                                    if let Err(e) = std::fs::write(&diff_file, diff) {
+                                        app.state.add_error_notification(format!("Failed to write diff: {}", e));
+                                    } else {
+                                        let mut command = tokio::process::Command::new("sh");
+                                        command
+                                            .arg("-c")
+                                            .arg(external_diff_command_line(&tool_command, &diff_file))
+                                            .current_dir(&worktree_path);
+
+                                        let mut attach_handler = AttachHandler::new_from_terminal(terminal)?;
+                                        if let Err(e) = attach_handler.run_external_command(&mut command).await {
+                                            error!("[ACTION] External diff tool failed: {}", e);
+                                            app.state.add_error_notification(format!("Diff tool failed: {}", e));
+                                        }
+                                        let _ = std::fs::remove_file(&diff_file);
+                                    }
+                                }
+                                Err(e) => {
+                                    error!("[ACTION] Failed to compute diff for session {}: {}", session_id, e);
+                                    app.state.add_error_notification(format!("Failed to compute diff: {}", e));
+                                }
+                            }
+                        }
+
+                        app.state.ui_needs_refresh = true;
+                    }
+
                     // Put back any other actions we don't handle here
                     other => {
                         debug!("[ACTION] Passing through unhandled action in main loop: {:?}", std::any::type_name_of_val(&other));
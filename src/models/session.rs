@@ -8,8 +8,10 @@ use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SessionMode {
+    #[serde(alias = "interactive")]
     Interactive, // Traditional interactive mode with shell access
-    Boss,        // Non-interactive mode with direct prompt execution
+    #[serde(alias = "boss")]
+    Boss, // Non-interactive mode with direct prompt execution
 }
 
 impl Default for SessionMode {
@@ -18,6 +20,54 @@ impl Default for SessionMode {
     }
 }
 
+/// CLI agent running in the session, which decides how its output is parsed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AgentType {
+    #[default]
+    #[serde(alias = "claude")]
+    Claude, // Claude Code's stream-json output
+    #[serde(alias = "gemini")]
+    Gemini, // Gemini CLI's stream-json output
+    #[serde(alias = "plain-text")]
+    PlainText, // Any other CLI; output is shown as plain text
+}
+
+impl AgentType {
+    pub const ALL: [AgentType; 3] = [AgentType::Claude, AgentType::Gemini, AgentType::PlainText];
+
+    /// Agents whose CLI the session container can launch; plain text has no CLI of its own
+    pub const LAUNCHABLE: [Self; 2] = [Self::Claude, Self::Gemini];
+
+    /// Stable identifier, used for the container label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentType::Claude => "claude",
+            AgentType::Gemini => "gemini",
+            AgentType::PlainText => "plain-text",
+        }
+    }
+
+    /// Parse an identifier from `as_str`; unknown or missing values mean Claude, which
+    /// every session was before the agent type was recorded
+    pub fn from_label(label: Option<&str>) -> Self {
+        Self::ALL.into_iter().find(|agent| Some(agent.as_str()) == label).unwrap_or_default()
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AgentType::Claude => "Claude",
+            AgentType::Gemini => "Gemini",
+            AgentType::PlainText => "Plain text",
+        }
+    }
+
+    /// The next launchable agent type, wrapping around, for cycling through the options
+    pub fn next(self) -> Self {
+        let index = Self::LAUNCHABLE.iter().position(|agent| *agent == self).unwrap_or(0);
+        Self::LAUNCHABLE[(index + 1) % Self::LAUNCHABLE.len()]
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SessionStatus {
     Running,
@@ -40,6 +90,16 @@ impl SessionStatus {
         matches!(self, SessionStatus::Running)
     }
 
+    /// Position in the status sort order: running, idle, stopped, then errored
+    pub fn sort_rank(&self) -> u8 {
+        match self {
+            SessionStatus::Running => 0,
+            SessionStatus::Idle => 1,
+            SessionStatus::Stopped => 2,
+            SessionStatus::Error(_) => 3,
+        }
+    }
+
     /// Helper to check if session can be restarted
     pub fn can_restart(&self) -> bool {
         matches!(self, SessionStatus::Idle | SessionStatus::Error(_))
@@ -66,6 +126,24 @@ pub struct Session {
     pub tmux_session_name: Option<String>, // Name of the tmux session if using tmux backend
     pub preview_content: Option<String>,   // Cached preview content for display
     pub is_attached: bool,                 // Whether user is currently attached to the session
+
+    #[serde(default)]
+    pub review_only: bool, // Detached review worktree; commit/push actions are disabled
+
+    #[serde(default)]
+    pub protected: bool, // Skipped by bulk operations such as orphan cleanup
+
+    #[serde(default)]
+    pub agent_type: AgentType, // Selects the parser for the container's output
+
+    #[serde(default)]
+    pub model: Option<String>, // Claude model the container runs (None = the CLI's default)
+
+    #[serde(default)]
+    pub ports: Vec<(u16, u16)>, // Published (host, container) ports, for the status line
+
+    #[serde(default)]
+    pub base_branch: Option<String>, // Branch the worktree was created from; None = repo default
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -73,6 +151,10 @@ pub struct GitChanges {
     pub added: u32,
     pub modified: u32,
     pub deleted: u32,
+    #[serde(default)]
+    pub ahead: usize, // Commits on the branch that its base doesn't have
+    #[serde(default)]
+    pub behind: usize, // Commits on the base the branch doesn't have yet
 }
 
 impl GitChanges {
@@ -87,6 +169,62 @@ impl GitChanges {
             format!("+{} ~{} -{}", self.added, self.modified, self.deleted)
         }
     }
+
+    /// "↑2 ↓1" style divergence from the base, leaving out zero counts; None when in sync
+    /// or when there was no base to compare with
+    pub fn format_ahead_behind(&self) -> Option<String> {
+        let parts: Vec<String> = [("↑", self.ahead), ("↓", self.behind)]
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(arrow, count)| format!("{}{}", arrow, count))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// Token usage reported by an agent's stream-json output, accumulated per session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_tokens: u64,
+    pub reported_cost_usd: Option<f64>, // Cost reported by the agent, when it reports one
+    // Log time of the newest usage report counted, so replayed log lines aren't counted twice
+    pub counted_through: Option<DateTime<Utc>>,
+}
+
+impl TokenUsage {
+    /// USD per million input/output tokens used when a Claude session doesn't report a
+    /// cost (Claude Sonnet list pricing)
+    const CLAUDE_INPUT_USD_PER_MTOK: f64 = 3.0;
+    const CLAUDE_OUTPUT_USD_PER_MTOK: f64 = 15.0;
+
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_tokens += other.cache_tokens;
+        if let Some(cost) = other.reported_cost_usd {
+            *self.reported_cost_usd.get_or_insert(0.0) += cost;
+        }
+        self.counted_through = self.counted_through.max(other.counted_through);
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Reported cost if the agent gave one, otherwise an estimate from list pricing; None
+    /// for agents whose pricing isn't known
+    pub fn cost_usd(&self, agent_type: AgentType) -> Option<f64> {
+        self.reported_cost_usd.or_else(|| {
+            (agent_type == AgentType::Claude).then(|| {
+                (self.input_tokens as f64 * Self::CLAUDE_INPUT_USD_PER_MTOK
+                    + self.output_tokens as f64 * Self::CLAUDE_OUTPUT_USD_PER_MTOK)
+                    / 1_000_000.0
+            })
+        })
+    }
 }
 
 impl Session {
@@ -121,6 +259,12 @@ impl Session {
             tmux_session_name: None,
             preview_content: None,
             is_attached: false,
+            review_only: false,
+            protected: false,
+            agent_type: AgentType::default(),
+            model: None,
+            ports: Vec::new(),
+            base_branch: None,
         }
     }
 
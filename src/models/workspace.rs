@@ -48,4 +48,9 @@ impl Workspace {
     pub fn session_count(&self) -> usize {
         self.sessions.len()
     }
+
+    /// Whether this workspace has more sessions than the given soft limit
+    pub fn exceeds_session_limit(&self, limit: usize) -> bool {
+        self.session_count() > limit
+    }
 }
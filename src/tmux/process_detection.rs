@@ -122,6 +122,24 @@ impl ClaudeProcessDetector {
         is_claude_running
     }
 
+    /// Check if Claude is waiting on a permission or choice prompt
+    ///
+    /// Claude asks "Do you want to ...?" above a numbered menu whose highlighted
+    /// option starts with "❯ 1.". Only the last lines are checked, since the
+    /// captured scrollback still holds prompts that were already answered.
+    pub fn is_awaiting_input(&self, content: &str) -> bool {
+        let tail: Vec<&str> = content
+            .lines()
+            .rev()
+            .filter(|line| !line.trim().is_empty())
+            .take(15)
+            .collect();
+
+        let has_question = tail.iter().any(|line| line.contains("Do you want to"));
+        let has_menu = tail.iter().any(|line| line.contains("❯ 1."));
+        has_question && has_menu
+    }
+
     /// Check if a tmux session exists
     ///
     /// # Arguments
@@ -231,4 +249,23 @@ mod tests {
         let long_session = "Model: Sonnet 4.5  Session: 2h15m  Ctx: 150k";
         assert!(detector.has_claude_status_bar(long_session));
     }
+
+    #[test]
+    fn test_awaiting_input_detection() {
+        let detector = ClaudeProcessDetector::new();
+
+        let prompt = r#"
+            ● Bash(cargo test)
+            Do you want to proceed?
+            ❯ 1. Yes
+              2. No, and tell Claude what to do differently (esc)
+        "#;
+        assert!(detector.is_awaiting_input(prompt));
+
+        // A prompt that scrolled away was already answered
+        let answered = format!("{}{}", prompt, "    output line\n".repeat(20));
+        assert!(!detector.is_awaiting_input(&answered));
+
+        assert!(!detector.is_awaiting_input("> Do you want to refactor this?"));
+    }
 }
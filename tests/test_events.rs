@@ -19,8 +19,9 @@ fn test_quit_key_events() {
         EventHandler::handle_key_event(create_key_event(KeyCode::Char('q')), &mut state);
     assert!(quit_event1.is_some());
 
-    let quit_event2 = EventHandler::handle_key_event(create_key_event(KeyCode::Esc), &mut state);
-    assert!(quit_event2.is_some());
+    // Escape on the session list never quits under the default escape policy
+    let escape_event = EventHandler::handle_key_event(create_key_event(KeyCode::Esc), &mut state);
+    assert!(escape_event.is_none());
 
     let quit_event3 = EventHandler::handle_key_event(
         create_key_event_with_modifiers(KeyCode::Char('c'), KeyModifiers::CONTROL),